@@ -4,12 +4,49 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use package_publisher::core::config::PublishConfig;
+use package_publisher::core::config_schema;
+use package_publisher::core::error::PublishErrorKind;
+use package_publisher::core::i18n::{self, Locale};
+use package_publisher::core::logging::{self, LogFormat};
+use package_publisher::core::publish_lock::PublishLock;
+use package_publisher::core::rate_limit::rate_limit_status;
+use package_publisher::core::reporter::{HumanReporter, QuietReporter, Reporter};
+use package_publisher::core::state_machine::{BatchProgress, PublishState, saved_publish_states};
+use package_publisher::orchestration::analytics::{ExportFormat, TrendBucket};
+use package_publisher::orchestration::check_runner::{CheckMode, CheckRunner, CheckStatus};
+use package_publisher::orchestration::duration_budget;
+use package_publisher::orchestration::events::FindingSeverity;
+use package_publisher::orchestration::git_hooks::{GitHookChange, GitHookInstaller};
+use package_publisher::orchestration::github_actions::GitHubActions;
+use package_publisher::orchestration::prometheus;
+use package_publisher::orchestration::release_window::{self, ReleaseWindows};
+use package_publisher::orchestration::report_file::{PublishResults, ReportDocument, ReportFormat};
+use package_publisher::orchestration::scheduler::{
+    self, Readiness, ReleaseScheduler, ScheduledRelease,
+};
+use package_publisher::orchestration::setup_wizard::{self, SetupWizard};
+use package_publisher::orchestration::tui::PublishTui;
+use package_publisher::orchestration::watch::{self, ProjectWatcher};
+use package_publisher::security::redaction::{RedactingReporter, redact};
+use package_publisher::security::secrets_baseline::BASELINE_FILE;
+use package_publisher::security::{
+    DependencyConfusionChecker, InternalNames, SecretsBaseline, SecretsScanner,
+};
+use package_publisher::tr;
+use package_publisher::validation::dependency_checker::IssueSeverity;
+use package_publisher::validation::manifest_validator::{ManifestType, ManifestValidator};
 use package_publisher::{
-    AnalyticsOptions, BatchPublishOptions, BatchPublisher, PackagePublisher, PluginLoader,
-    PublishAnalytics, PublishOptions,
+    AnalyticsOptions, AuditExporter, BatchPublishOptions, BatchPublisher, BumpLevel,
+    CONFIG_FILENAME, ChangelogGenerator, ConfigLoadOptions, ConfigLoader, ConfigSource,
+    CredentialPreflight, CredentialStatus, CredentialValidator, DependencyAuditor,
+    PackagePublisher, PluginLoader, PublishAnalytics, PublishOptions, RegistryType, ReporterKind,
+    RollbackOptions, VersionBumper, WarningBudget, WorkspaceScanner,
 };
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::Arc;
 
 /// Multi-registry package publishing assistant
 #[derive(Parser)]
@@ -19,6 +56,18 @@ use std::process;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Do not offer the guided setup in projects without a config file
+    #[arg(long, global = true)]
+    no_wizard: bool,
+
+    /// Write structured logs with a span per publish stage to stderr (text, json); publish output is logged instead of printed
+    #[arg(long, global = true, value_name = "FORMAT")]
+    log_format: Option<LogFormat>,
+
+    /// Config profile to overlay on the base config (profiles.<NAME>; default: $PUBLISH_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -29,7 +78,7 @@ enum Commands {
         #[arg(value_name = "PROJECT_PATH")]
         project_path: Option<PathBuf>,
 
-        /// Specify registry (npm, crates.io, pypi, homebrew)
+        /// Specify registry (npm, crates.io, pypi, homebrew, github-packages, upm, cran, julia, bazel, cpan, hackage, expo, bucket, artifactory, nexus, cloudsmith, gemfury, scoop, winget, aur, debian, a registries.custom key or a plugin name)
         #[arg(short, long)]
         registry: Option<String>,
 
@@ -49,6 +98,18 @@ enum Commands {
         #[arg(long)]
         continue_on_error: bool,
 
+        /// Publish every package of the monorepo workspace (batch mode)
+        #[arg(long)]
+        workspace: bool,
+
+        /// Comma-separated package names or directory globs (implies --workspace)
+        #[arg(long)]
+        packages: Option<String>,
+
+        /// Only packages changed since a git ref, plus their dependents (implies --workspace)
+        #[arg(long, value_name = "GIT_REF")]
+        since: Option<String>,
+
         /// Only perform dry-run
         #[arg(long)]
         dry_run: bool,
@@ -61,10 +122,18 @@ enum Commands {
         #[arg(long)]
         resume: bool,
 
+        /// Wait for another publish of the project to finish instead of failing (default: 30m)
+        #[arg(long, value_name = "DURATION", num_args = 0..=1, default_missing_value = "30m")]
+        wait_for_lock: Option<String>,
+
         /// 2FA one-time password (npm)
         #[arg(long)]
         otp: Option<String>,
 
+        /// Command printing a 2FA one-time password when npm asks for one (e.g. "op item get npm --otp")
+        #[arg(long, value_name = "COMMAND")]
+        otp_command: Option<String>,
+
         /// Publish with tag
         #[arg(long)]
         tag: Option<String>,
@@ -80,6 +149,64 @@ enum Commands {
         /// Execute hooks only
         #[arg(long)]
         hooks_only: bool,
+
+        /// Treat validation warnings as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Output format (human, json, quiet, tracing)
+        #[arg(long, default_value = "human")]
+        output: ReporterKind,
+
+        /// Interactive terminal view with per-registry progress, output and confirmations
+        #[arg(long, conflicts_with_all = ["non_interactive", "output"])]
+        tui: bool,
+
+        /// Write the results to a file (JSON, or JUnit XML for .xml paths)
+        #[arg(long, value_name = "PATH")]
+        report_file: Option<PathBuf>,
+
+        /// Report file format (json, junit); defaults to the file extension
+        #[arg(long, value_name = "FORMAT", requires = "report_file")]
+        report_format: Option<ReportFormat>,
+
+        /// Show the rate limit state of every registry host after publishing
+        #[arg(long)]
+        show_rate_limits: bool,
+
+        /// Validate and dry-run now, publish with `run-scheduled` from this time on (e.g. 2024-06-01T10:00Z)
+        #[arg(long, value_name = "TIME", conflicts_with_all = ["dry_run", "resume", "hooks_only", "tui", "workspace", "packages", "since"])]
+        at: Option<String>,
+
+        /// Validate and dry-run now, publish with `run-scheduled` once the commit's GitHub checks passed
+        #[arg(long, conflicts_with_all = ["dry_run", "resume", "hooks_only", "tui", "workspace", "packages", "since"])]
+        after_ci_green: bool,
+    },
+
+    /// Publish the releases scheduled with `publish --at` / `--after-ci-green` that are due
+    RunScheduled {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Only list scheduled releases and whether they are due
+        #[arg(long)]
+        list: bool,
+
+        /// Output format (human, json, quiet, tracing)
+        #[arg(long, default_value = "human")]
+        output: ReporterKind,
+    },
+
+    /// Show the state of the last publish: stage, registry, transitions and whether it can be resumed
+    Status {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Print the saved states as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Check if project is ready to publish
@@ -91,10 +218,121 @@ enum Commands {
         /// Specify registry to check
         #[arg(short, long)]
         registry: Option<String>,
+
+        /// Treat validation warnings as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Also verify registry credentials (npm login, tokens, tap push access)
+        #[arg(long)]
+        credentials: bool,
+
+        /// Also audit dependencies (npm audit, cargo audit, pip-audit)
+        #[arg(long)]
+        audit: bool,
+
+        /// Also check internal package names for dependency confusion
+        #[arg(long)]
+        dependency_confusion: bool,
+
+        /// Stop the remaining registry validations after the first failure
+        #[arg(long, conflicts_with = "all")]
+        fail_fast: bool,
+
+        /// Validate every registry even after a failure (default)
+        #[arg(long)]
+        all: bool,
+
+        /// Print the combined validation result as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Re-run validation and secret scanning whenever project files change
+        #[arg(long)]
+        watch: bool,
+
+        /// Write the results to a file (JSON, or JUnit XML for .xml paths)
+        #[arg(long, value_name = "PATH")]
+        report_file: Option<PathBuf>,
+
+        /// Report file format (json, junit); defaults to the file extension
+        #[arg(long, value_name = "FORMAT", requires = "report_file")]
+        report_format: Option<ReportFormat>,
+    },
+
+    /// Verify registry credentials before publishing
+    Doctor {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Specify registry to check
+        #[arg(short, long)]
+        registry: Option<String>,
+    },
+
+    /// Audit dependencies (npm audit, cargo audit, pip-audit) for detected registries
+    Audit {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Specify registry to audit
+        #[arg(short, long)]
+        registry: Option<String>,
+
+        /// Lowest severity that fails the audit (critical, high, medium, low);
+        /// defaults to security.audit.failOn, else high
+        #[arg(long)]
+        fail_on: Option<String>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage secrets scanning
+    Secrets {
+        #[command(subcommand)]
+        action: SecretsAction,
+    },
+
+    /// Scan the project (or only the staged files) for secrets
+    Scan {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Scan the contents staged for commit instead of the working tree
+        #[arg(long)]
+        staged: bool,
+    },
+
+    /// Validate package manifests without running builds
+    Lint {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+    },
+
+    /// Manage pre-commit / pre-push git hooks
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Validate the configuration file or print its JSON Schema
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
 
     /// Display publishing statistics
+    #[command(args_conflicts_with_subcommands = true)]
     Stats {
+        #[command(subcommand)]
+        action: Option<StatsAction>,
+
         /// Project path (defaults to current directory)
         #[arg(value_name = "PROJECT_PATH")]
         project_path: Option<PathBuf>,
@@ -118,9 +356,100 @@ enum Commands {
         /// Show statistics for last N days
         #[arg(long, default_value = "30")]
         days: usize,
+
+        /// Period of the trend table (day, week)
+        #[arg(long, default_value = "week")]
+        bucket: TrendBucket,
+    },
+
+    /// Export releases and release freezes as an iCalendar (.ics) feed
+    Calendar {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Filter by registry
+        #[arg(short, long)]
+        registry: Option<String>,
+
+        /// Filter by package name
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// Output .ics path (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Export a release audit bundle (zip)
+    ExportAudit {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Release version to export
+        #[arg(long)]
+        version: String,
+
+        /// Output zip path (defaults to audit-<version>.zip)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Roll back a published version (unpublish/deprecate, yank, revert)
+    Rollback {
+        /// Version to roll back
+        #[arg(value_name = "VERSION")]
+        version: String,
+
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Specify registry (npm, crates.io, pypi, homebrew, github-packages, upm, cran, julia, bazel)
+        #[arg(short, long)]
+        registry: Option<String>,
+
+        /// Non-interactive mode (skip confirmation)
+        #[arg(long)]
+        non_interactive: bool,
+    },
+
+    /// Bump the version in all manifests (package.json, Cargo.toml, pyproject.toml, Formula)
+    Bump {
+        /// Version component to bump (major, minor, patch, prerelease)
+        #[arg(value_name = "LEVEL")]
+        level: BumpLevel,
+
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Prerelease identifier (e.g. alpha, beta, rc)
+        #[arg(long)]
+        preid: Option<String>,
+
+        /// Show the new version without modifying files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Generate CHANGELOG.md from conventional commits since the last tag
+    Changelog {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Release version for the new section (defaults to the manifest version)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Print the section without modifying CHANGELOG.md
+        #[arg(long)]
+        dry_run: bool,
     },
 
-    /// Initialize package-publisher configuration
+    /// Guided setup: detect registries, check credentials, write a config
     Init {
         /// Project path (defaults to current directory)
         #[arg(value_name = "PROJECT_PATH")]
@@ -132,6 +461,118 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum SecretsAction {
+    /// Accept all current findings in .secrets-baseline.json (generate or update)
+    Baseline {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsAction {
+    /// Export the publish history (to merge it into another project with `stats import`)
+    Export {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Export format (csv, json); defaults to the output file extension
+        #[arg(long)]
+        format: Option<ExportFormat>,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Filter by registry
+        #[arg(short, long)]
+        registry: Option<String>,
+
+        /// Filter by package name
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// Only records of the last N days
+        #[arg(long)]
+        days: Option<usize>,
+    },
+    /// Print the publish history as Prometheus metrics, write them to a textfile or serve them
+    Metrics {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Write the metrics to this file (node_exporter textfile collector)
+        #[arg(long, value_name = "PATH", conflicts_with = "serve")]
+        textfile: Option<PathBuf>,
+
+        /// Serve the metrics at http://ADDRESS/metrics (e.g. 127.0.0.1:9464)
+        #[arg(long, value_name = "ADDRESS")]
+        serve: Option<String>,
+
+        /// Exit after the first scrape
+        #[arg(long, requires = "serve")]
+        once: bool,
+    },
+    /// Merge an exported publish history, skipping records already present
+    Import {
+        /// Export file (csv, json) or another project's analytics.json
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Import format (csv, json); defaults to the file extension
+        #[arg(long)]
+        format: Option<ExportFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check the project configuration against the schema: errors, unknown keys and typos with their line
+    Validate {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Config file (.yaml, .yml, .toml or .json) to validate instead of the project configuration
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the JSON Schema of the configuration file
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum HooksAction {
+    /// Write pre-commit / pre-push hooks running the checks from gitHooks
+    Install {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+
+        /// Replace existing hooks installed by other tools
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Remove the hooks installed by package-publisher
+    Uninstall {
+        /// Project path (defaults to current directory)
+        #[arg(value_name = "PROJECT_PATH")]
+        project_path: Option<PathBuf>,
+    },
+}
+
 #[tokio::main]
 async fn main() {
     // Custom exit override behavior
@@ -141,14 +582,33 @@ async fn main() {
         Ok(exit_code) => process::exit(exit_code),
         Err(e) => {
             eprintln!("\n❌ Error");
-            eprintln!("{}", e);
+            eprintln!("{}", redact(&e.to_string()));
             process::exit(1);
         }
     }
 }
 
 async fn run() -> Result<i32> {
+    i18n::set_locale(Locale::from_env(|name| std::env::var(name).ok()));
     let cli = Cli::parse();
+    if let Some(format) = cli.log_format {
+        logging::init(format)?;
+    }
+
+    let profile = cli.profile.as_deref();
+
+    // The configured language replaces the one from LANG
+    if let Some(locale) = configured_locale(&command_project_path(&cli.command), profile).await {
+        i18n::set_locale(locale);
+    }
+
+    if !cli.no_wizard
+        && let Some(path) = first_run_path(&cli.command)
+        && std::io::stdin().is_terminal()
+        && SetupWizard::new(&path).is_first_run().await
+    {
+        setup_command(path, true, false, profile).await?;
+    }
 
     match cli.command {
         Commands::Publish {
@@ -158,315 +618,2204 @@ async fn run() -> Result<i32> {
             sequential,
             max_concurrency,
             continue_on_error,
+            workspace,
+            packages,
+            since,
             dry_run,
             non_interactive,
             resume,
+            wait_for_lock,
             otp,
+            otp_command,
             tag,
             access,
             skip_hooks,
             hooks_only,
+            strict,
+            output,
+            tui,
+            report_file,
+            report_format,
+            show_rate_limits,
+            at,
+            after_ci_green,
         } => {
             let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            let at = at
+                .as_deref()
+                .map(scheduler::parse_schedule_time)
+                .transpose()?;
+            if tui && (cli.log_format.is_some() || !std::io::stdout().is_terminal()) {
+                anyhow::bail!("--tui requires a terminal and cannot be combined with --log-format");
+            }
+            let output = if cli.log_format.is_some() {
+                ReporterKind::Tracing
+            } else {
+                output
+            };
+            let wait_for_lock = wait_for_lock
+                .as_deref()
+                .map(duration_budget::parse_duration)
+                .transpose()?
+                .map(std::time::Duration::from_millis);
+            let tui = tui.then(|| Arc::new(PublishTui::new()));
+            let frontend = PublishFrontend::new(
+                &path,
+                output,
+                tui.clone(),
+                report_target(report_file, report_format),
+            );
 
-            // Check if batch mode (multiple registries)
-            if let Some(registries_str) = registries {
-                let registries_vec: Vec<String> = registries_str
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect();
+            let options = PublishOptions {
+                registry,
+                dry_run,
+                non_interactive,
+                resume,
+                skip_hooks,
+                hooks_only,
+                otp,
+                otp_command,
+                tag,
+                access,
+                strict,
+                skip_notifications: false,
+                prepare_only: false,
+                wait_for_lock,
+                profile: cli.profile.clone(),
+            };
 
-                publish_batch_command(
-                    path,
-                    registries_vec,
-                    sequential,
-                    max_concurrency,
-                    continue_on_error,
-                    dry_run,
-                    non_interactive,
-                    resume,
-                    otp,
-                    tag,
-                    access,
-                    skip_hooks,
-                    hooks_only,
-                )
-                .await
+            let publish = async {
+                if at.is_some() || after_ci_green {
+                    let registries =
+                        registries.map(|r| r.split(',').map(|s| s.trim().to_string()).collect());
+                    schedule_command(path, options, registries, at, after_ci_green, &frontend).await
+                } else if workspace || packages.is_some() || since.is_some() {
+                    let patterns: Vec<String> = packages
+                        .map(|p| p.split(',').map(|s| s.trim().to_string()).collect())
+                        .unwrap_or_default();
+                    let batch_options = BatchPublishOptions {
+                        sequential,
+                        continue_on_error,
+                        max_concurrency,
+                        publish_options: options,
+                    };
+                    publish_workspace_command(path, patterns, since, batch_options, &frontend).await
+                } else if let Some(registries_str) = registries {
+                    // Batch mode (multiple registries)
+                    let registries_vec: Vec<String> = registries_str
+                        .split(',')
+                        .map(|s| s.trim().to_string())
+                        .collect();
+
+                    let batch_options = BatchPublishOptions {
+                        sequential,
+                        continue_on_error,
+                        max_concurrency,
+                        publish_options: PublishOptions {
+                            registry: None, // Will be set per-registry
+                            ..options
+                        },
+                    };
+
+                    publish_batch_command(path, registries_vec, batch_options, &frontend).await
+                } else {
+                    publish_command(path, options, &frontend).await
+                }
+            };
+            let code = match &tui {
+                Some(tui) => tui.run(publish).await??,
+                None => publish.await?,
+            };
+            report_rate_limits(output, show_rate_limits);
+            Ok(code)
+        }
+        Commands::RunScheduled {
+            project_path,
+            list,
+            output,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            let output = if cli.log_format.is_some() {
+                ReporterKind::Tracing
             } else {
-                publish_command(
-                    path,
-                    registry,
-                    dry_run,
-                    non_interactive,
-                    resume,
-                    otp,
-                    tag,
-                    access,
-                    skip_hooks,
-                    hooks_only,
-                )
-                .await
-            }
+                output
+            };
+            run_scheduled_command(path, list, output).await
+        }
+        Commands::Status { project_path, json } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            status_command(path, json).await
         }
         Commands::Check {
             project_path,
             registry,
+            strict,
+            credentials,
+            audit,
+            dependency_confusion,
+            fail_fast,
+            all: _,
+            json,
+            watch,
+            report_file,
+            report_format,
         } => {
             let path = project_path.unwrap_or_else(|| PathBuf::from("."));
-            check_command(path, registry).await
+            let report_file = report_target(report_file, report_format);
+            let mode = if fail_fast {
+                CheckMode::FailFast
+            } else {
+                CheckMode::All
+            };
+            let run_checks = async || -> Result<i32> {
+                let mut exit_code = check_command(
+                    path.clone(),
+                    registry.clone(),
+                    strict,
+                    mode,
+                    json,
+                    report_file.as_ref(),
+                    profile,
+                )
+                .await?;
+                if credentials {
+                    let credentials_exit_code =
+                        doctor_command(path.clone(), registry.clone(), profile).await?;
+                    exit_code = exit_code.max(credentials_exit_code);
+                }
+                if audit {
+                    let audit_exit_code =
+                        audit_command(path.clone(), registry.clone(), None, false, profile).await?;
+                    exit_code = exit_code.max(audit_exit_code);
+                }
+                if dependency_confusion {
+                    let confusion_exit_code =
+                        dependency_confusion_command(path.clone(), registry.clone(), profile)
+                            .await?;
+                    exit_code = exit_code.max(confusion_exit_code);
+                }
+                if watch {
+                    let secrets_exit_code =
+                        secrets_check_command(path.clone(), false, profile).await?;
+                    exit_code = exit_code.max(secrets_exit_code);
+                }
+                Ok(exit_code)
+            };
+
+            if watch {
+                watch_command(&path, run_checks).await
+            } else {
+                run_checks().await
+            }
         }
-        Commands::Stats {
+        Commands::Doctor {
             project_path,
             registry,
-            package,
-            success_only,
-            failures_only,
-            days,
         } => {
             let path = project_path.unwrap_or_else(|| PathBuf::from("."));
-            stats_command(path, registry, package, success_only, failures_only, days).await
+            doctor_command(path, registry, profile).await
+        }
+        Commands::Audit {
+            project_path,
+            registry,
+            fail_on,
+            json,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            audit_command(path, registry, fail_on, json, profile).await
+        }
+        Commands::Secrets { action } => match action {
+            SecretsAction::Baseline { project_path } => {
+                let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+                secrets_baseline_command(path).await
+            }
+        },
+        Commands::Scan {
+            project_path,
+            staged,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            secrets_check_command(path, staged, profile).await
+        }
+        Commands::Lint { project_path } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            lint_command(path).await
+        }
+        Commands::Hooks { action } => match action {
+            HooksAction::Install {
+                project_path,
+                force,
+            } => {
+                let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+                hooks_command(path, Some(force), profile).await
+            }
+            HooksAction::Uninstall { project_path } => {
+                let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+                hooks_command(path, None, profile).await
+            }
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Validate {
+                project_path,
+                file,
+                json,
+            } => {
+                let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+                let source = match file {
+                    Some(file) => ConfigSource::File(file),
+                    None => ConfigSource::find(&path).await?.ok_or_else(|| {
+                        anyhow::anyhow!("No configuration found in {}", path.display())
+                    })?,
+                };
+                config_validate_command(source, json).await
+            }
+            ConfigAction::Schema => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&config_schema::config_schema())?
+                );
+                Ok(0)
+            }
+        },
+        Commands::Stats {
+            action:
+                Some(StatsAction::Export {
+                    project_path,
+                    format,
+                    output,
+                    registry,
+                    package,
+                    days,
+                }),
+            ..
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            let options = AnalyticsOptions {
+                registry,
+                package_name: package,
+                start_date: days
+                    .map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64)),
+                ..Default::default()
+            };
+            stats_export_command(path, options, format, output).await
+        }
+        Commands::Stats {
+            action:
+                Some(StatsAction::Metrics {
+                    project_path,
+                    textfile,
+                    serve,
+                    once,
+                }),
+            ..
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            stats_metrics_command(path, textfile, serve, once).await
+        }
+        Commands::Stats {
+            action:
+                Some(StatsAction::Import {
+                    file,
+                    project_path,
+                    format,
+                }),
+            ..
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            stats_import_command(path, file, format).await
+        }
+        Commands::Stats {
+            action: None,
+            project_path,
+            registry,
+            package,
+            success_only,
+            failures_only,
+            days,
+            bucket,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            let options = AnalyticsOptions {
+                registry,
+                package_name: package,
+                start_date: Some(chrono::Utc::now() - chrono::Duration::days(days as i64)),
+                end_date: None,
+                success_only,
+                failures_only,
+                limit: None,
+                bucket,
+            };
+            stats_command(path, options).await
+        }
+        Commands::Calendar {
+            project_path,
+            registry,
+            package,
+            output,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            calendar_command(path, registry, package, output, profile).await
+        }
+        Commands::ExportAudit {
+            project_path,
+            version,
+            output,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            export_audit_command(path, version, output, profile).await
+        }
+        Commands::Rollback {
+            version,
+            project_path,
+            registry,
+            non_interactive,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            let options = RollbackOptions {
+                registry,
+                non_interactive,
+            };
+            rollback_command(path, version, options).await
+        }
+        Commands::Bump {
+            level,
+            project_path,
+            preid,
+            dry_run,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            bump_command(path, level, preid, dry_run).await
+        }
+        Commands::Changelog {
+            project_path,
+            version,
+            dry_run,
+        } => {
+            let path = project_path.unwrap_or_else(|| PathBuf::from("."));
+            changelog_command(path, version, dry_run, profile).await
         }
         Commands::Init {
             project_path,
             force,
         } => {
             let path = project_path.unwrap_or_else(|| PathBuf::from("."));
-            init_command(path, force).await
+            setup_command(path, false, force, profile).await
+        }
+    }
+}
+
+/// Report file path and its format (`--report-file`, `--report-format`)
+type ReportTarget = (PathBuf, ReportFormat);
+
+fn report_target(path: Option<PathBuf>, format: Option<ReportFormat>) -> Option<ReportTarget> {
+    path.map(|path| {
+        let format = format.unwrap_or_else(|| ReportFormat::from_path(&path));
+        (path, format)
+    })
+}
+
+/// Write `document` to the report file, if one was requested
+async fn write_report_file(
+    target: Option<&ReportTarget>,
+    document: ReportDocument,
+    reporter: &dyn Reporter,
+) {
+    if let Some((path, format)) = target
+        && let Err(e) = document.write(path, *format).await
+    {
+        reporter.warning(&format!("⚠️  {}", e));
+    }
+}
+
+/// Where publish output, confirmations and results go: the `--output`
+/// reporter or the TUI with `--tui`, the `--report-file`, and GitHub Actions
+/// annotations and job summary
+struct PublishFrontend {
+    reporter: Arc<dyn Reporter>,
+    tui: Option<Arc<PublishTui>>,
+    report_file: Option<ReportTarget>,
+    github: Option<Arc<GitHubActions>>,
+}
+
+impl PublishFrontend {
+    fn new(
+        project_path: &Path,
+        output: ReporterKind,
+        tui: Option<Arc<PublishTui>>,
+        report_file: Option<ReportTarget>,
+    ) -> Self {
+        // Wrapped once here; the publishers keep an already masking reporter
+        let reporter = RedactingReporter::wrap(match &tui {
+            Some(tui) => tui.clone() as Arc<dyn Reporter>,
+            None => output.build(),
+        });
+        // Annotations printed to stdout would garble the TUI
+        let github = GitHubActions::detect(project_path)
+            .filter(|_| tui.is_none())
+            .map(Arc::new);
+        Self {
+            reporter,
+            tui,
+            report_file,
+            github,
+        }
+    }
+
+    /// Write the report file and the job summary
+    async fn write_report(&self, results: PublishResults) {
+        if let Some(github) = &self.github {
+            let reports: Vec<_> = results.reports.values().cloned().collect();
+            if let Err(e) = github.write_summary(&reports).await {
+                self.reporter
+                    .warning(&format!("⚠️  Failed to write the job summary: {}", e));
+            }
+        }
+        write_report_file(
+            self.report_file.as_ref(),
+            ReportDocument::Publish(results),
+            self.reporter.as_ref(),
+        )
+        .await;
+    }
+
+    fn publisher(&self, project_path: &Path) -> PackagePublisher {
+        let mut publisher =
+            PackagePublisher::new(project_path).with_reporter(self.reporter.clone());
+        if let Some(github) = &self.github {
+            publisher = publisher.with_event_sink(github.clone());
+        }
+        match &self.tui {
+            Some(tui) => publisher
+                .with_event_sink(tui.clone())
+                .with_prompt(tui.clone()),
+            None => publisher,
+        }
+    }
+
+    fn batch_publisher(&self, project_path: &Path) -> BatchPublisher {
+        let mut publisher = BatchPublisher::new(project_path).with_reporter(self.reporter.clone());
+        if let Some(github) = &self.github {
+            publisher = publisher.with_event_sink(github.clone());
+        }
+        match &self.tui {
+            Some(tui) => publisher
+                .with_event_sink(tui.clone())
+                .with_prompt(tui.clone()),
+            None => publisher,
         }
     }
 }
 
 async fn publish_command(
     project_path: PathBuf,
-    registry: Option<String>,
-    dry_run: bool,
-    non_interactive: bool,
-    resume: bool,
-    otp: Option<String>,
-    tag: Option<String>,
-    access: Option<String>,
-    skip_hooks: bool,
-    hooks_only: bool,
+    options: PublishOptions,
+    frontend: &PublishFrontend,
 ) -> Result<i32> {
-    println!("\n📦 package-publisher\n");
+    let reporter = frontend.reporter.clone();
+    reporter.info(&tr!("cli.publish_title"));
 
-    let mut publisher = PackagePublisher::new(&project_path);
-
-    let options = PublishOptions {
-        registry,
-        dry_run,
-        non_interactive,
-        resume,
-        skip_hooks,
-        hooks_only,
-        otp,
-        tag,
-        access,
-    };
+    let mut publisher = frontend.publisher(&project_path);
+    let registry = options.registry.clone();
+    let profile = options.profile.clone();
 
     match publisher.publish(options).await {
         Ok(report) => {
+            frontend.write_report(PublishResults::from(&report)).await;
+
             // Record analytics
             let mut analytics = PublishAnalytics::new(&project_path);
             if let Err(e) = analytics.initialize().await {
-                eprintln!("⚠️  Failed to initialize analytics: {}", e);
+                reporter.warning(&tr!("cli.analytics_init_failed", error = e));
             }
             if let Err(e) = analytics.record_publish(&report).await {
-                eprintln!("⚠️  Failed to record analytics: {}", e);
+                reporter.warning(&tr!("cli.analytics_failed", error = e));
             }
+            update_metrics_textfile(&project_path, &analytics, &*reporter, profile.as_deref())
+                .await;
 
-            if report.success {
-                println!("\n✅ Publishing completed successfully!");
+            if let Some(ref url) = report.submission_url {
+                reporter.info(&tr!("cli.submitted_for_review", url = url));
+                reporter.info(&tr!("cli.available_once_accepted"));
+                Ok(0)
+            } else if report.success {
+                reporter.success(&tr!("cli.publish_succeeded"));
                 Ok(0)
             } else {
-                println!("\n❌ Publishing failed");
+                reporter.error(&tr!("cli.publish_failed"));
                 for error in &report.errors {
-                    eprintln!("  - {}", error);
+                    reporter.error(&format!("  - {}", error));
                 }
-                Ok(1)
+                Ok(report
+                    .error_kind
+                    .as_ref()
+                    .map_or(1, PublishErrorKind::exit_code))
             }
         }
         Err(e) => {
-            eprintln!("\n❌ Publishing failed: {}", e);
-            Ok(1)
+            frontend
+                .write_report(PublishResults::from_error(
+                    registry.as_deref().unwrap_or("publish"),
+                    &e,
+                ))
+                .await;
+            reporter.error(&tr!("cli.publish_failed_with", error = e));
+            Ok(PublishErrorKind::of(&e).exit_code())
         }
     }
 }
 
-async fn publish_batch_command(
+/// Validate and dry-run now, then record the release for `run-scheduled`
+async fn schedule_command(
     project_path: PathBuf,
-    registries: Vec<String>,
-    sequential: bool,
-    max_concurrency: usize,
-    continue_on_error: bool,
-    dry_run: bool,
-    non_interactive: bool,
-    resume: bool,
-    otp: Option<String>,
-    tag: Option<String>,
-    access: Option<String>,
-    skip_hooks: bool,
-    hooks_only: bool,
+    options: PublishOptions,
+    registries: Option<Vec<String>>,
+    at: Option<chrono::DateTime<chrono::Utc>>,
+    after_ci_green: bool,
+    frontend: &PublishFrontend,
 ) -> Result<i32> {
-    println!("\n📦 package-publisher (Batch Mode)\n");
+    let reporter = frontend.reporter.clone();
+    reporter.info("\n📦 package-publisher (Scheduled)\n");
 
-    let batch_publisher = BatchPublisher::new(&project_path);
+    let prepare = PublishOptions {
+        prepare_only: true,
+        ..options.clone()
+    };
+    let prepared = match registries {
+        Some(registries) => frontend
+            .batch_publisher(&project_path)
+            .publish_to_multiple(
+                registries,
+                BatchPublishOptions {
+                    sequential: true,
+                    continue_on_error: false,
+                    max_concurrency: 1,
+                    publish_options: PublishOptions {
+                        registry: None,
+                        ..prepare
+                    },
+                },
+            )
+            .await
+            .and_then(|result| {
+                if result.success {
+                    Ok(result.results.into_values().collect::<Vec<_>>())
+                } else {
+                    Err(anyhow::anyhow!(
+                        "preparation failed for {}",
+                        result.failed.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ))
+                }
+            }),
+        None => frontend
+            .publisher(&project_path)
+            .publish(prepare)
+            .await
+            .and_then(|report| {
+                if report.success {
+                    Ok(vec![report])
+                } else {
+                    Err(anyhow::anyhow!("{}", report.errors.join("; ")))
+                }
+            }),
+    };
+    let reports = match prepared {
+        Ok(reports) if !reports.is_empty() => reports,
+        Ok(_) => {
+            reporter.error("\n❌ Nothing to schedule");
+            return Ok(1);
+        }
+        Err(e) => {
+            reporter.error(&format!("\n❌ Release not scheduled: {}", e));
+            return Ok(1);
+        }
+    };
 
-    let batch_options = BatchPublishOptions {
-        sequential,
-        continue_on_error,
-        max_concurrency,
-        publish_options: PublishOptions {
-            registry: None, // Will be set per-registry
-            dry_run,
-            non_interactive,
-            resume,
-            skip_hooks,
-            hooks_only,
-            otp,
-            tag,
-            access,
-        },
+    let scheduler = ReleaseScheduler::new(&project_path);
+    let mut release = ScheduledRelease::new(
+        &reports[0].package_name,
+        &reports[0].version,
+        reports.iter().map(|r| r.registry.clone()).collect(),
+    );
+    release.commit = scheduler.head_commit().await;
+    release.at = at;
+    release.after_ci_green = after_ci_green;
+    release.tag = options.tag;
+    release.access = options.access;
+    release.skip_hooks = options.skip_hooks;
+    release.strict = options.strict;
+    release.profile = options.profile;
+    if after_ci_green && release.commit.is_none() {
+        reporter.error("\n❌ --after-ci-green requires a git repository");
+        return Ok(1);
+    }
+    let record = scheduler.save(&release).await?;
+
+    reporter.success(&format!(
+        "\n🗓️  Scheduled {}@{} to {} {}",
+        release.package_name,
+        release.version,
+        release.registries.join(", "),
+        release.condition()
+    ));
+    reporter.info(&format!(
+        "   Saved to {}; `package-publisher run-scheduled` (from cron or CI) publishes it when due",
+        record.display()
+    ));
+    Ok(0)
+}
+
+/// Publish the scheduled releases that are due
+///
+/// Published releases are removed; failed ones are kept and not retried.
+async fn run_scheduled_command(
+    project_path: PathBuf,
+    list: bool,
+    output: ReporterKind,
+) -> Result<i32> {
+    let frontend = PublishFrontend::new(&project_path, output, None, None);
+    let reporter = frontend.reporter.clone();
+    let scheduler = ReleaseScheduler::new(&project_path);
+    let releases = scheduler.list().await?;
+    if releases.is_empty() {
+        reporter.info("No scheduled releases");
+        return Ok(0);
+    }
+
+    let mut exit_code = 0;
+    for mut release in releases {
+        let label = format!(
+            "{}@{} → {} ({})",
+            release.package_name,
+            release.version,
+            release.registries.join(", "),
+            release.id
+        );
+        match scheduler.readiness(&release, chrono::Utc::now()).await {
+            Readiness::Due if !list => {}
+            readiness @ Readiness::Blocked(_) => {
+                reporter.warning(&format!("⛔ {}: {}", label, readiness));
+                continue;
+            }
+            readiness => {
+                reporter.info(&format!("🗓️  {}: {}", label, readiness));
+                continue;
+            }
+        }
+
+        reporter.info(&format!("\n🚀 Running scheduled release {}", label));
+        let code = match release.registries.as_slice() {
+            [registry] => {
+                let options = release.publish_options(Some(registry.clone()));
+                publish_command(project_path.clone(), options, &frontend).await?
+            }
+            registries => {
+                let batch_options = BatchPublishOptions {
+                    sequential: false,
+                    continue_on_error: false,
+                    max_concurrency: 3,
+                    publish_options: release.publish_options(None),
+                };
+                publish_batch_command(
+                    project_path.clone(),
+                    registries.to_vec(),
+                    batch_options,
+                    &frontend,
+                )
+                .await?
+            }
+        };
+        if code == 0 {
+            scheduler.remove(&release.id).await?;
+        } else {
+            release.failed_at = Some(chrono::Utc::now());
+            scheduler.save(&release).await?;
+            exit_code = 1;
+        }
+    }
+    Ok(exit_code)
+}
+
+async fn status_command(project_path: PathBuf, json: bool) -> Result<i32> {
+    let states = saved_publish_states(&project_path).await?;
+    let batch = match BatchProgress::restore(&project_path).await? {
+        Some(progress) => Some(progress.data().await),
+        None => None,
     };
+    let lock = PublishLock::current_owner(&project_path).await;
+
+    if json {
+        let states: Vec<_> = states
+            .iter()
+            .map(|state| {
+                serde_json::json!({
+                    "key": state.key,
+                    "stateFile": state.path,
+                    "startedAt": state.started_at(),
+                    "updatedAt": state.updated_at(),
+                    "state": state.data,
+                })
+            })
+            .collect();
+        let status = serde_json::json!({ "states": states, "batch": batch, "lock": lock });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(0);
+    }
+
+    println!("{}", tr!("cli.status_title"));
+    if let Some(owner) = &lock {
+        let stale = if owner.is_stale(chrono::Utc::now()) {
+            tr!("cli.lock_stale")
+        } else {
+            String::new()
+        };
+        println!("{}", tr!("cli.locked_by", owner = owner, stale = stale));
+    }
+    if states.is_empty() && batch.is_none() {
+        println!(
+            "{}",
+            tr!("cli.no_publish_state", path = project_path.display())
+        );
+        return Ok(0);
+    }
+
+    for state in &states {
+        let data = &state.data;
+        let file = state
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        println!("{} ({})", state_icon(data.current_state), file);
+        println!(
+            "{}",
+            tr!(
+                "cli.status_state",
+                state = format!("{:?}", data.current_state)
+            )
+        );
+        if let Some(registry) = &data.registry {
+            println!("{}", tr!("cli.status_registry", registry = registry));
+        }
+        if let Some(version) = &data.version {
+            println!("{}", tr!("cli.status_version", version = version));
+        }
+        if let (Some(started), Some(updated)) = (state.started_at(), state.updated_at()) {
+            let elapsed = (updated - started).num_milliseconds().max(0) as u64;
+            println!("{}", tr!("cli.status_started", time = started.to_rfc3339()));
+            println!(
+                "{}",
+                tr!(
+                    "cli.status_updated",
+                    time = updated.to_rfc3339(),
+                    elapsed = duration_budget::format_duration(elapsed)
+                )
+            );
+        }
+        if !data.artifacts.completed.is_empty() {
+            let completed: Vec<String> = data
+                .artifacts
+                .completed
+                .iter()
+                .map(|stage| format!("{:?}", stage))
+                .collect();
+            println!(
+                "{}",
+                tr!("cli.status_completed", stages = completed.join(", "))
+            );
+        }
+        if let Some(error) = &data.error {
+            println!("{}", tr!("cli.status_error", error = error));
+        }
+
+        println!("{}", tr!("cli.status_transitions"));
+        let mut previous: Option<chrono::DateTime<chrono::Utc>> = None;
+        for transition in &data.transitions {
+            let spent = previous
+                .map(|at| {
+                    let ms = (transition.timestamp - at).num_milliseconds().max(0) as u64;
+                    format!(" (+{})", duration_budget::format_duration(ms))
+                })
+                .unwrap_or_default();
+            println!(
+                "    {}  {:?} → {:?}{}",
+                transition.timestamp.to_rfc3339(),
+                transition.from,
+                transition.to,
+                spent
+            );
+            previous = Some(transition.timestamp);
+        }
+
+        if data.can_resume {
+            let in_flight = data
+                .registry
+                .clone()
+                .unwrap_or_else(|| tr!("cli.the_registry"));
+            println!("{}", tr!("cli.status_resumable", registry = in_flight));
+        } else {
+            println!("{}", tr!("cli.status_nothing_to_resume"));
+        }
+        println!();
+    }
+
+    if let Some(batch) = batch {
+        let remaining: Vec<&String> = batch
+            .targets
+            .iter()
+            .filter(|target| !batch.completed.contains(target))
+            .collect();
+        println!(
+            "{}",
+            tr!(
+                "cli.batch_interrupted",
+                done = batch.completed.len(),
+                total = batch.targets.len()
+            )
+        );
+        if !batch.completed.is_empty() {
+            println!(
+                "{}",
+                tr!("cli.batch_published", targets = batch.completed.join(", "))
+            );
+        }
+        if !remaining.is_empty() {
+            let remaining: Vec<&str> = remaining.iter().map(|t| t.as_str()).collect();
+            println!(
+                "{}",
+                tr!("cli.batch_remaining", targets = remaining.join(", "))
+            );
+        }
+        println!("{}", tr!("cli.batch_resume_hint"));
+    }
+    Ok(0)
+}
+
+fn state_icon(state: PublishState) -> &'static str {
+    match state {
+        PublishState::Success => "✅",
+        PublishState::Failed | PublishState::RolledBack => "❌",
+        PublishState::Initial => "⚪",
+        _ => "⏸️ ",
+    }
+}
+
+/// Report registry rate limits: hosts that throttled publishing, or every
+/// host seen with `--show-rate-limits`
+fn report_rate_limits(output: ReporterKind, show_all: bool) {
+    let status: Vec<_> = rate_limit_status()
+        .into_iter()
+        .filter(|s| show_all || s.throttled > 0)
+        .collect();
+    if status.is_empty() {
+        return;
+    }
+    let reporter = output.build();
+    reporter.info(&tr!("cli.rate_limits"));
+    for host in status {
+        reporter.info(&format!("  {}", host));
+    }
+}
+
+async fn publish_batch_command(
+    project_path: PathBuf,
+    registries: Vec<String>,
+    batch_options: BatchPublishOptions,
+    frontend: &PublishFrontend,
+) -> Result<i32> {
+    let reporter = frontend.reporter.clone();
+    reporter.info(&tr!("cli.batch_mode"));
+
+    let batch_publisher = frontend.batch_publisher(&project_path);
+    let profile = batch_options.publish_options.profile.clone();
 
     match batch_publisher
         .publish_to_multiple(registries, batch_options)
         .await
     {
         Ok(result) => {
+            frontend.write_report(PublishResults::from(&result)).await;
+
             // Record analytics for each publish
             let mut analytics = PublishAnalytics::new(&project_path);
             if let Err(e) = analytics.initialize().await {
-                eprintln!("⚠️  Failed to initialize analytics: {}", e);
+                reporter.warning(&tr!("cli.analytics_init_failed", error = e));
             }
 
-            for (_, report) in &result.results {
+            for report in result.results.values() {
                 if let Err(e) = analytics.record_publish(report).await {
-                    eprintln!(
-                        "⚠️  Failed to record analytics for {}: {}",
-                        report.registry, e
-                    );
+                    reporter.warning(&tr!(
+                        "cli.analytics_record_failed",
+                        target = report.registry,
+                        error = e
+                    ));
                 }
             }
+            update_metrics_textfile(&project_path, &analytics, &*reporter, profile.as_deref())
+                .await;
 
             if result.success {
-                println!("\n✅ Batch publishing completed successfully!");
+                reporter.success(&tr!("cli.batch_succeeded"));
                 Ok(0)
             } else {
-                println!("\n❌ Batch publishing completed with errors");
-                Ok(1)
+                reporter.error(&tr!("cli.batch_completed_with_errors"));
+                Ok(result.exit_code())
             }
         }
         Err(e) => {
-            eprintln!("\n❌ Batch publishing failed: {}", e);
-            Ok(1)
+            frontend
+                .write_report(PublishResults::from_error("batch", &e))
+                .await;
+            reporter.error(&tr!("cli.batch_failed", error = e));
+            Ok(PublishErrorKind::of(&e).exit_code())
         }
     }
 }
 
-async fn check_command(project_path: PathBuf, registry_filter: Option<String>) -> Result<i32> {
-    println!("\n🔍 Package Check\n");
-
-    let loader = PluginLoader::new();
-
-    // Detect registries
-    let detected = loader.detect_plugins(project_path.as_path()).await?;
+async fn publish_workspace_command(
+    project_path: PathBuf,
+    patterns: Vec<String>,
+    since: Option<String>,
+    batch_options: BatchPublishOptions,
+    frontend: &PublishFrontend,
+) -> Result<i32> {
+    let reporter = frontend.reporter.clone();
+    reporter.info(&tr!("cli.workspace_mode"));
 
-    if detected.is_empty() {
-        println!("⚠️  No supported registries detected");
+    let scanner = WorkspaceScanner::new(&project_path);
+    let all_packages = scanner.scan().await?;
+    if all_packages.is_empty() {
+        let message = tr!("cli.no_workspace_packages");
+        reporter.error(&format!("❌ {}", message));
+        frontend
+            .write_report(PublishResults::from_error(
+                "workspace",
+                &anyhow::anyhow!(message),
+            ))
+            .await;
         return Ok(1);
     }
+    let mut packages = all_packages.clone();
+    if !patterns.is_empty() {
+        packages = WorkspaceScanner::select(&packages, &patterns);
+    }
+    if let Some(git_ref) = since.as_deref() {
+        let affected = scanner.affected_since(&all_packages, git_ref).await?;
+        packages.retain(|package| affected.iter().any(|a| a.name == package.name));
+        reporter.info(&tr!(
+            "cli.changed_since",
+            git_ref = git_ref,
+            count = packages.len()
+        ));
+    }
 
-    println!(
-        "Detected registries: {}\n",
-        detected
-            .iter()
-            .map(|d| d.registry_type.as_str())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-
-    // Validate each detected registry
-    for plugin_info in detected {
-        let registry_name = plugin_info.registry_type.as_str();
-
-        // Apply filter if specified
-        if let Some(ref filter) = registry_filter {
-            if registry_name != filter {
-                continue;
-            }
+    // Versions already on the registry are not published again
+    let mut unpublished = Vec::new();
+    for package in packages {
+        if WorkspaceScanner::is_published(&package).await {
+            reporter.info(&tr!(
+                "cli.already_published",
+                name = package.name,
+                version = package.version.as_deref().unwrap_or("?")
+            ));
+        } else {
+            unpublished.push(package);
         }
+    }
+    let packages = unpublished;
+    if packages.is_empty() {
+        reporter.success(&tr!("cli.nothing_to_publish"));
+        frontend
+            .write_report(PublishResults {
+                success: true,
+                ..Default::default()
+            })
+            .await;
+        return Ok(0);
+    }
 
-        println!("\n📦 {}:", registry_name);
-
-        // Load and validate
-        let plugin =
-            loader.load_plugin(plugin_info.registry_type, project_path.to_str().unwrap())?;
+    reporter.info(&tr!("cli.workspace_packages"));
+    for package in &packages {
+        reporter.info(&format!(
+            "  - {}@{} ({}, {})",
+            package.name,
+            package.version.as_deref().unwrap_or("?"),
+            package.ecosystem,
+            package.relative_path
+        ));
+    }
 
-        match plugin.validate().await {
-            Ok(result) => {
-                if result.valid {
-                    println!("  ✅ Validation successful");
-                } else {
-                    println!("  ❌ Validation failed");
-                    for error in &result.errors {
-                        println!("    - [{}] {}", error.field, error.message);
-                    }
-                }
+    let batch_publisher = frontend.batch_publisher(&project_path);
+    let profile = batch_options.publish_options.profile.clone();
+    match batch_publisher
+        .publish_workspace(packages, batch_options)
+        .await
+    {
+        Ok(result) => {
+            frontend.write_report(PublishResults::from(&result)).await;
 
-                if !result.warnings.is_empty() {
-                    println!("  ⚠️  Warnings:");
-                    for warning in &result.warnings {
-                        println!("    - [{}] {}", warning.field, warning.message);
-                    }
+            let mut analytics = PublishAnalytics::new(&project_path);
+            if let Err(e) = analytics.initialize().await {
+                reporter.warning(&tr!("cli.analytics_init_failed", error = e));
+            }
+            for report in result.results.values() {
+                if let Err(e) = analytics.record_publish(report).await {
+                    reporter.warning(&tr!(
+                        "cli.analytics_record_failed",
+                        target = report.package_name,
+                        error = e
+                    ));
                 }
             }
-            Err(e) => {
-                println!("  ❌ Error: {}", e);
+            update_metrics_textfile(&project_path, &analytics, &*reporter, profile.as_deref())
+                .await;
+
+            if result.success {
+                reporter.success(&tr!("cli.workspace_succeeded"));
+                Ok(0)
+            } else {
+                reporter.error(&tr!("cli.workspace_completed_with_errors"));
+                Ok(result.exit_code())
             }
         }
+        Err(e) => {
+            frontend
+                .write_report(PublishResults::from_error("workspace", &e))
+                .await;
+            reporter.error(&tr!("cli.workspace_failed", error = e));
+            Ok(PublishErrorKind::of(&e).exit_code())
+        }
     }
-
-    println!();
-    Ok(0)
 }
 
-async fn stats_command(
+async fn check_command(
     project_path: PathBuf,
-    registry: Option<String>,
-    package: Option<String>,
-    success_only: bool,
-    failures_only: bool,
-    days: usize,
+    registry_filter: Option<String>,
+    strict: bool,
+    mode: CheckMode,
+    json: bool,
+    report_file: Option<&ReportTarget>,
+    profile: Option<&str>,
 ) -> Result<i32> {
-    println!("\n📊 Publishing Statistics\n");
-
-    let mut analytics = PublishAnalytics::new(&project_path);
-    analytics.initialize().await?;
+    let config = load_config(&project_path, profile).await?;
+    let warning_budget = WarningBudget::from_config(config.validation.as_ref(), strict);
 
-    // Calculate start date from days
-    let start_date = chrono::Utc::now() - chrono::Duration::days(days as i64);
+    // Detect registries
+    let detected = PluginLoader::new()
+        .detect_plugins(project_path.as_path())
+        .await?;
+    let registries: Vec<_> = detected
+        .iter()
+        .map(|d| d.registry_type)
+        .filter(|r| {
+            registry_filter
+                .as_deref()
+                .is_none_or(|filter| r.as_str() == filter)
+        })
+        .collect();
 
-    let options = AnalyticsOptions {
-        registry,
-        package_name: package,
-        start_date: Some(start_date),
-        end_date: None,
-        success_only,
-        failures_only,
-        limit: None,
+    let summary = CheckRunner::new(mode, warning_budget)
+        .run(&project_path, &registries)
+        .await;
+    write_report_file(
+        report_file,
+        ReportDocument::Check(summary.clone()),
+        &HumanReporter,
+    )
+    .await;
+    if let Some(github) = GitHubActions::detect(&project_path) {
+        for check in &summary.registries {
+            let Some(result) = &check.validation else {
+                continue;
+            };
+            let findings = result
+                .errors
+                .iter()
+                .map(|e| (FindingSeverity::Error, &e.field, &e.message))
+                .chain(
+                    result
+                        .warnings
+                        .iter()
+                        .map(|w| (FindingSeverity::Warning, &w.field, &w.message)),
+                );
+            for (severity, field, message) in findings {
+                println!(
+                    "{}",
+                    github.validation_annotation(&check.registry, severity, field, message)
+                );
+            }
+        }
+    }
+    let exit_code = if summary.success && !detected.is_empty() {
+        0
+    } else {
+        1
     };
 
-    let report = analytics.generate_report(&options).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(exit_code);
+    }
 
-    println!("{}", report.markdown_summary);
+    println!("{}", tr!("cli.check_title"));
 
-    Ok(0)
-}
+    if detected.is_empty() {
+        println!("{}", tr!("cli.no_registries"));
+        return Ok(1);
+    }
 
-async fn init_command(_project_path: PathBuf, _force: bool) -> Result<i32> {
-    println!("\n🎯 Initialize package-publisher\n");
-    eprintln!("⚠️  Init command not yet fully implemented");
-    eprintln!("This will create a default .package-publisher.yml configuration.\n");
-    Ok(1)
+    let names = detected
+        .iter()
+        .map(|d| d.registry_type.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("{}", tr!("cli.detected_registries", registries = names));
+
+    for check in &summary.registries {
+        println!("\n📦 {}:", check.registry);
+
+        if check.status == CheckStatus::Skipped {
+            println!("{}", tr!("cli.check_skipped"));
+            continue;
+        }
+
+        let Some(result) = &check.validation else {
+            println!(
+                "{}",
+                tr!(
+                    "cli.check_error",
+                    error = check.error.as_deref().unwrap_or_default()
+                )
+            );
+            continue;
+        };
+
+        if result.valid {
+            println!("{}", tr!("cli.check_passed", ms = check.duration));
+        } else {
+            println!("{}", tr!("cli.check_failed"));
+            for error in &result.errors {
+                println!("    - [{}] {}", error.field, error.message);
+            }
+        }
+
+        if !result.warnings.is_empty() {
+            println!("{}", tr!("cli.check_warnings"));
+            for warning in &result.warnings {
+                println!("    - [{}] {}", warning.field, warning.message);
+            }
+        }
+
+        if let Some(exceeded) = &check.error {
+            println!("  ❌ {}", exceeded);
+        }
+    }
+
+    println!();
+    Ok(exit_code)
+}
+
+/// Run `checks` now and again after every batch of file changes (until Ctrl+C)
+async fn watch_command(project_path: &Path, checks: impl AsyncFn() -> Result<i32>) -> Result<i32> {
+    let mut watcher = ProjectWatcher::new(project_path, watch::DEFAULT_DEBOUNCE)?;
+    let mut exit_code = checks().await?;
+
+    loop {
+        println!(
+            "👀 Watching {} for changes (Ctrl+C to stop)...",
+            project_path.display()
+        );
+        let changed = tokio::select! {
+            changed = watcher.next_change() => changed,
+            _ = tokio::signal::ctrl_c() => None,
+        };
+        let Some(changed) = changed else {
+            return Ok(exit_code);
+        };
+
+        println!(
+            "\n🔄 Changed: {}",
+            changed
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        exit_code = match checks().await {
+            Ok(code) => code,
+            Err(e) => {
+                println!("❌ Error: {}", e);
+                1
+            }
+        };
+    }
+}
+
+/// Secrets scan of the working tree or the staged files (baseline and
+/// `security.secretsScanning` applied)
+async fn secrets_check_command(
+    project_path: PathBuf,
+    staged: bool,
+    profile: Option<&str>,
+) -> Result<i32> {
+    println!(
+        "🔒 Secrets Scan{}\n",
+        if staged { " (staged files)" } else { "" }
+    );
+
+    let config = load_config(&project_path, profile).await.ok();
+
+    let mut scanner = SecretsScanner::new();
+    scanner.set_baseline(SecretsBaseline::load(&project_path).await?);
+    if let Some(scanning) = config
+        .as_ref()
+        .and_then(|c| c.security.as_ref())
+        .and_then(|s| s.secrets_scanning.as_ref())
+    {
+        if let Some(max_file_size) = scanning.max_file_size {
+            scanner.set_max_file_size(max_file_size);
+        }
+        if let Some(respect) = scanning.respect_gitignore {
+            scanner.set_respect_gitignore(respect);
+        }
+    }
+
+    let report = if staged {
+        scanner.scan_staged(&project_path).await?
+    } else {
+        scanner.scan_project(&project_path).await?
+    };
+    if report.findings.is_empty() {
+        println!(
+            "  ✅ No secrets detected ({} files)\n",
+            report.scanned_files
+        );
+        return Ok(0);
+    }
+
+    if let Some(github) = GitHubActions::detect(&project_path) {
+        for finding in &report.findings {
+            println!(
+                "{}",
+                github.secret_annotation(&finding.file, finding.line, &finding.secret_type)
+            );
+        }
+    }
+
+    println!("  ⚠️  Potential secrets detected:");
+    for finding in &report.findings {
+        println!(
+            "    - {} in {}:{}",
+            finding.secret_type,
+            finding.file.display(),
+            finding.line
+        );
+    }
+    println!();
+    Ok(1)
+}
+
+/// Manifest validation without builds (package.json, Cargo.toml, Formula/*.rb)
+async fn lint_command(project_path: PathBuf) -> Result<i32> {
+    println!("📋 Manifest Lint\n");
+
+    let mut manifests = vec![
+        (project_path.join("package.json"), ManifestType::Npm),
+        (project_path.join("Cargo.toml"), ManifestType::Cargo),
+    ];
+    if let Ok(entries) = std::fs::read_dir(project_path.join("Formula")) {
+        manifests.extend(
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "rb"))
+                .map(|p| (p, ManifestType::Homebrew)),
+        );
+    }
+
+    let validator = ManifestValidator::new();
+    let mut exit_code = 0;
+    let mut linted = 0;
+    for (path, manifest_type) in manifests {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        // Virtual workspace manifests have no package to validate
+        if manifest_type == ManifestType::Cargo && !content.contains("[package]") {
+            continue;
+        }
+
+        linted += 1;
+        let name = path.strip_prefix(&project_path).unwrap_or(&path).display();
+        let result = validator.validate(&path, manifest_type).await?;
+        if result.is_valid {
+            println!("  ✅ {}", name);
+        } else {
+            println!("  ❌ {}", name);
+            exit_code = 1;
+        }
+        for error in &result.errors {
+            println!("    - {}", error);
+        }
+        for warning in &result.warnings {
+            println!("    ⚠️  {}", warning);
+        }
+    }
+
+    if linted == 0 {
+        println!("  ⚠️  No manifests found (package.json, Cargo.toml, Formula/*.rb)");
+    }
+    println!();
+    Ok(exit_code)
+}
+
+/// Install (`force` is Some) or uninstall (None) the git hooks
+async fn config_validate_command(source: ConfigSource, json: bool) -> Result<i32> {
+    let (content, format) = source
+        .read()
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No configuration found in {}", source))?;
+    let mut result = config_schema::validate(&content, format);
+    if !source.has_locations() {
+        // Lines of the extracted section do not match the manifest
+        for error in &mut result.errors {
+            error.location = None;
+        }
+        for warning in &mut result.warnings {
+            warning.location = None;
+        }
+    }
+    let exit_code = if result.valid { 0 } else { 1 };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(exit_code);
+    }
+
+    println!("\n🔎 Validating {}\n", source);
+    println!("{}", ConfigLoader::format_validation_result(&result));
+    Ok(exit_code)
+}
+
+async fn hooks_command(
+    project_path: PathBuf,
+    force: Option<bool>,
+    profile: Option<&str>,
+) -> Result<i32> {
+    let config = load_config(&project_path, profile).await.ok();
+    let installer = GitHookInstaller::new(
+        &project_path,
+        config.as_ref().and_then(|c| c.git_hooks.as_ref()),
+    );
+
+    let changes = match force {
+        Some(force) => {
+            println!("\n🪝 Installing git hooks\n");
+            installer.install(force).await?
+        }
+        None => {
+            println!("\n🪝 Removing git hooks\n");
+            installer.uninstall().await?
+        }
+    };
+
+    let mut exit_code = 0;
+    for change in &changes {
+        match change {
+            GitHookChange::Written(path) => println!("  ✅ Wrote {}", path.display()),
+            GitHookChange::Removed(path) => println!("  ✅ Removed {}", path.display()),
+            GitHookChange::Skipped(stage) => {
+                println!("  ➖ {}: no checks configured in gitHooks", stage)
+            }
+            GitHookChange::Kept(path) => {
+                println!(
+                    "  ⚠️  {} was not installed by package-publisher; left unchanged{}",
+                    path.display(),
+                    if force.is_some() {
+                        " (use --force to replace it)"
+                    } else {
+                        ""
+                    }
+                );
+                exit_code = 1;
+            }
+        }
+    }
+    if changes.is_empty() {
+        println!("  No package-publisher hooks installed");
+    }
+    println!();
+    Ok(exit_code)
+}
+
+/// Project of an interactive publish/check that may offer the first-run setup
+fn first_run_path(command: &Commands) -> Option<PathBuf> {
+    match command {
+        Commands::Publish {
+            project_path,
+            non_interactive: false,
+            ..
+        }
+        | Commands::Check {
+            project_path,
+            json: false,
+            ..
+        } => Some(project_path.clone().unwrap_or_else(|| PathBuf::from("."))),
+        _ => None,
+    }
+}
+
+/// Project directory of a command
+fn command_project_path(command: &Commands) -> PathBuf {
+    let project_path = match command {
+        Commands::Publish { project_path, .. }
+        | Commands::RunScheduled { project_path, .. }
+        | Commands::Status { project_path, .. }
+        | Commands::Check { project_path, .. }
+        | Commands::Doctor { project_path, .. }
+        | Commands::Audit { project_path, .. }
+        | Commands::Scan { project_path, .. }
+        | Commands::Lint { project_path }
+        | Commands::Stats { project_path, .. }
+        | Commands::Calendar { project_path, .. }
+        | Commands::ExportAudit { project_path, .. }
+        | Commands::Rollback { project_path, .. }
+        | Commands::Bump { project_path, .. }
+        | Commands::Changelog { project_path, .. }
+        | Commands::Init { project_path, .. }
+        | Commands::Secrets {
+            action: SecretsAction::Baseline { project_path },
+        }
+        | Commands::Config {
+            action: ConfigAction::Validate { project_path, .. },
+        }
+        | Commands::Hooks {
+            action:
+                HooksAction::Install { project_path, .. } | HooksAction::Uninstall { project_path },
+        } => project_path.clone(),
+        Commands::Config {
+            action: ConfigAction::Schema,
+        } => None,
+    };
+    project_path.unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// `locale` of the project's config (loader warnings and errors are left to
+/// the command)
+async fn configured_locale(project_path: &Path, profile: Option<&str>) -> Option<Locale> {
+    ConfigLoader::load(ConfigLoadOptions {
+        project_path: project_path.to_path_buf(),
+        cli_args: None,
+        env: std::env::vars().collect(),
+        reporter: Some(Arc::new(QuietReporter)),
+        profile: profile.map(str::to_string),
+    })
+    .await
+    .ok()?
+    .locale
+}
+
+/// Ask a yes/no question on the terminal (an empty answer picks `default`)
+fn prompt_yes_no(question: &str, default: bool) -> Result<bool> {
+    use std::io::Write;
+
+    print!("{} {} ", question, if default { "[Y/n]" } else { "[y/N]" });
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+fn credential_icon(status: CredentialStatus) -> &'static str {
+    match status {
+        CredentialStatus::Ok => "✅",
+        CredentialStatus::Unverified => "🔸",
+        CredentialStatus::NotRequired => "➖",
+        CredentialStatus::Missing | CredentialStatus::Invalid | CredentialStatus::Expired => "❌",
+    }
+}
+
+/// Guided setup (`init`, or offered on the first interactive run)
+async fn setup_command(
+    project_path: PathBuf,
+    first_run: bool,
+    force: bool,
+    profile: Option<&str>,
+) -> Result<i32> {
+    let wizard = SetupWizard::new(&project_path);
+    if first_run {
+        println!(
+            "\n👋 No {} found in {}",
+            CONFIG_FILENAME,
+            project_path.display()
+        );
+        if !prompt_yes_no("Run the guided setup now?", true)? {
+            wizard.skip().await?;
+            println!(
+                "   Skipped. Run `package-publisher init` at any time (or pass --no-wizard).\n"
+            );
+            return Ok(0);
+        }
+    }
+
+    println!("\n🧭 Guided setup\n");
+    let proposal = wizard.detect().await?;
+    if proposal.registries.is_empty() {
+        println!("⚠️  No supported registries detected");
+        return Ok(1);
+    }
+
+    println!("Detected registries:");
+    for registry in &proposal.registries {
+        println!("  • {}", registry.as_str());
+    }
+    println!("\nCredentials:");
+    for check in &proposal.credentials {
+        println!(
+            "  {} {} ({}): {}",
+            credential_icon(check.status),
+            check.registry,
+            check.source,
+            check.detail
+        );
+    }
+
+    println!("\nProposed {}:\n", CONFIG_FILENAME);
+    for line in proposal.config_yaml().lines() {
+        if line.is_empty() {
+            println!();
+        } else {
+            println!("    {}", line);
+        }
+    }
+    println!();
+    let existing = ConfigSource::find(&project_path).await;
+    let yaml = ConfigSource::File(project_path.join(CONFIG_FILENAME));
+    if let Ok(Some(source)) = &existing
+        && (!force || *source != yaml)
+    {
+        println!(
+            "ℹ️  {} already configures this project; leaving it unchanged{}",
+            source,
+            if *source == yaml {
+                " (use --force to replace it)"
+            } else {
+                ""
+            }
+        );
+    } else if let Err(e) = &existing {
+        println!("⚠️  {}", e);
+    } else if prompt_yes_no(&format!("Write {}?", CONFIG_FILENAME), true)? {
+        let path = wizard.write_config(&proposal, force).await?;
+        println!("  ✅ Wrote {}", path.display());
+    }
+
+    if prompt_yes_no(
+        "Install pre-commit / pre-push git hooks (secrets scan, manifest lint)?",
+        false,
+    )? {
+        hooks_command(project_path.clone(), Some(false), profile).await?;
+    }
+
+    if prompt_yes_no(
+        &format!(
+            "Add a GitHub Actions publish workflow ({})?",
+            setup_wizard::CI_WORKFLOW_FILE
+        ),
+        false,
+    )? {
+        match wizard.write_ci_workflow(&proposal).await {
+            Ok(path) => {
+                println!("  ✅ Wrote {}", path.display());
+                let secrets = proposal.secret_names();
+                if !secrets.is_empty() {
+                    println!("     Add these repository secrets: {}", secrets.join(", "));
+                }
+            }
+            Err(e) => println!("  ⚠️  {}", e),
+        }
+    }
+
+    println!("\n✅ Setup complete. Next: package-publisher check\n");
+    Ok(0)
+}
+
+async fn doctor_command(
+    project_path: PathBuf,
+    registry_filter: Option<String>,
+    profile: Option<&str>,
+) -> Result<i32> {
+    println!("\n🩺 Credential Check\n");
+
+    let config = load_config(&project_path, profile).await?;
+
+    let detected: Vec<_> = PluginLoader::new()
+        .detect_plugins(project_path.as_path())
+        .await?
+        .into_iter()
+        .filter(|d| {
+            registry_filter
+                .as_deref()
+                .is_none_or(|filter| d.registry_type.as_str() == filter)
+        })
+        .collect();
+
+    if detected.is_empty() {
+        println!("⚠️  No supported registries detected");
+        return Ok(1);
+    }
+
+    let validator = CredentialValidator::new();
+    let checks = CredentialPreflight::new(&project_path, &validator)
+        .with_config(Some(&config))
+        .check_all(&detected)
+        .await;
+
+    let mut exit_code = 0;
+    for check in &checks {
+        if check.status.is_problem() {
+            exit_code = 1;
+        }
+        println!(
+            "{} {} ({}): {}{}",
+            credential_icon(check.status),
+            check.registry,
+            check.source,
+            check.detail,
+            check
+                .account
+                .as_ref()
+                .map(|a| format!(" as {}", a))
+                .unwrap_or_default()
+        );
+    }
+
+    let problems = checks.iter().filter(|c| c.status.is_problem()).count();
+    if problems > 0 {
+        println!(
+            "\n❌ {} credential(s) missing, invalid or expired\n",
+            problems
+        );
+    } else {
+        println!("\n✅ All credentials available\n");
+    }
+
+    Ok(exit_code)
+}
+
+async fn audit_command(
+    project_path: PathBuf,
+    registry_filter: Option<String>,
+    fail_on: Option<String>,
+    json: bool,
+    profile: Option<&str>,
+) -> Result<i32> {
+    let config = load_config(&project_path, profile).await?;
+    let audit_config = config.security.as_ref().and_then(|s| s.audit.clone());
+    let threshold: IssueSeverity = fail_on
+        .or_else(|| audit_config.as_ref().and_then(|a| a.fail_on.clone()))
+        .as_deref()
+        .unwrap_or("high")
+        .parse()
+        .map_err(anyhow::Error::msg)?;
+    let ignore = audit_config.and_then(|a| a.ignore).unwrap_or_default();
+
+    let registries: Vec<_> = PluginLoader::new()
+        .detect_plugins(project_path.as_path())
+        .await?
+        .into_iter()
+        .map(|d| d.registry_type)
+        .filter(|r| {
+            registry_filter
+                .as_deref()
+                .is_none_or(|filter| r.as_str() == filter)
+        })
+        .collect();
+
+    let report = DependencyAuditor::new(&project_path)
+        .audit_registries(&registries)
+        .await;
+    let blocking = report.blocking(threshold, &ignore).count();
+    let exit_code = if blocking > 0 { 1 } else { 0 };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(exit_code);
+    }
+
+    println!("\n🛡️  Dependency Audit\n");
+    if report.backends.is_empty() && report.errors.is_empty() {
+        println!("⚠️  No auditable registries detected (npm, crates.io, pypi)");
+        return Ok(1);
+    }
+
+    for finding in &report.findings {
+        let icon = if ignore.contains(&finding.advisory_id) {
+            "➖"
+        } else if finding.severity.meets(threshold) {
+            "❌"
+        } else {
+            "⚠️ "
+        };
+        println!(
+            "{} [{:?}] {}{} - {} ({})",
+            icon,
+            finding.severity,
+            finding.package,
+            finding
+                .version
+                .as_ref()
+                .map(|v| format!("@{}", v))
+                .unwrap_or_default(),
+            finding.title,
+            finding.advisory_id
+        );
+        println!("    → {}", finding.remediation);
+    }
+    for error in &report.errors {
+        println!("🔸 {}", error);
+    }
+
+    println!(
+        "\n{} finding(s): {}",
+        report.findings.len(),
+        report.summary()
+    );
+    if blocking > 0 {
+        println!("❌ {} finding(s) at or above {:?}\n", blocking, threshold);
+    } else {
+        println!("✅ No findings at or above {:?}\n", threshold);
+    }
+
+    Ok(exit_code)
+}
+
+async fn dependency_confusion_command(
+    project_path: PathBuf,
+    registry_filter: Option<String>,
+    profile: Option<&str>,
+) -> Result<i32> {
+    println!("\n🕵️  Dependency Confusion Check\n");
+
+    let config = load_config(&project_path, profile).await?;
+    let confusion_config = config
+        .security
+        .as_ref()
+        .and_then(|s| s.dependency_confusion.clone())
+        .unwrap_or_default();
+    let threshold: IssueSeverity = confusion_config
+        .fail_on
+        .as_deref()
+        .unwrap_or("high")
+        .parse()
+        .map_err(anyhow::Error::msg)?;
+    let checker = DependencyConfusionChecker::new(
+        &project_path,
+        InternalNames::new(
+            confusion_config
+                .internal_packages
+                .as_deref()
+                .unwrap_or_default(),
+            confusion_config
+                .internal_scopes
+                .as_deref()
+                .unwrap_or_default(),
+        ),
+    );
+
+    let registries: Vec<_> = PluginLoader::new()
+        .detect_plugins(project_path.as_path())
+        .await?
+        .into_iter()
+        .map(|d| d.registry_type)
+        .filter(|r| {
+            registry_filter
+                .as_deref()
+                .is_none_or(|filter| r.as_str() == filter)
+        })
+        .collect();
+
+    let mut exit_code = 0;
+    for registry in registries {
+        let report = checker
+            .check(registry, registry == RegistryType::GitHubPackages)
+            .await;
+        if report.checked.is_empty() && report.findings.is_empty() && report.errors.is_empty() {
+            continue;
+        }
+
+        println!(
+            "📦 {}: {} internal name(s)",
+            registry.as_str(),
+            report.checked.len()
+        );
+        for finding in &report.findings {
+            let icon = if finding.severity.meets(threshold) {
+                exit_code = 1;
+                "❌"
+            } else {
+                "⚠️ "
+            };
+            println!("  {} [{:?}] {}", icon, finding.severity, finding.message);
+        }
+        for error in &report.errors {
+            println!("  🔸 {}", error);
+        }
+        if report.findings.is_empty() && report.errors.is_empty() {
+            println!("  ✅ No public shadowing or registry fallbacks");
+        }
+    }
+
+    println!();
+    Ok(exit_code)
+}
+
+async fn secrets_baseline_command(project_path: PathBuf) -> Result<i32> {
+    println!("\n🔒 Secrets Baseline\n");
+
+    let previous = SecretsBaseline::load(&project_path).await?;
+    let report = SecretsScanner::new().scan_project(&project_path).await?;
+    let baseline = SecretsBaseline::from_findings(&report.findings, &project_path);
+
+    let added = baseline
+        .findings
+        .iter()
+        .filter(|f| !previous.contains(&f.fingerprint))
+        .count();
+    let removed = previous
+        .findings
+        .iter()
+        .filter(|f| !baseline.contains(&f.fingerprint))
+        .count();
+
+    for entry in &baseline.findings {
+        println!(
+            "  ➖ {} in {}:{}",
+            entry.secret_type, entry.file, entry.line
+        );
+    }
+    baseline.save(&project_path).await?;
+
+    println!(
+        "\n✅ Wrote {} with {} finding(s) ({} added, {} removed)",
+        BASELINE_FILE,
+        baseline.findings.len(),
+        added,
+        removed
+    );
+    println!("   Review it before committing: accepted findings are no longer reported\n");
+
+    Ok(0)
+}
+
+async fn stats_command(project_path: PathBuf, options: AnalyticsOptions) -> Result<i32> {
+    println!("\n📊 Publishing Statistics\n");
+
+    let mut analytics = PublishAnalytics::new(&project_path);
+    analytics.initialize().await?;
+
+    let report = analytics.generate_report(&options).await?;
+
+    println!("{}", report.markdown_summary);
+
+    Ok(0)
+}
+
+async fn stats_export_command(
+    project_path: PathBuf,
+    options: AnalyticsOptions,
+    format: Option<ExportFormat>,
+    output: Option<PathBuf>,
+) -> Result<i32> {
+    let mut analytics = PublishAnalytics::new(&project_path);
+    analytics.initialize().await?;
+    let format = format
+        .or_else(|| output.as_deref().map(ExportFormat::from_path))
+        .unwrap_or_default();
+    let export = analytics.export(&options, format)?;
+    match output {
+        Some(path) => {
+            tokio::fs::write(&path, export).await?;
+            println!(
+                "📤 {} records exported to {}",
+                analytics.get_records(&options).len(),
+                path.display()
+            );
+        }
+        None => print!("{}", export),
+    }
+    Ok(0)
+}
+
+async fn stats_metrics_command(
+    project_path: PathBuf,
+    textfile: Option<PathBuf>,
+    serve: Option<String>,
+    once: bool,
+) -> Result<i32> {
+    let metrics = async || {
+        let mut analytics = PublishAnalytics::new(&project_path);
+        analytics.initialize().await?;
+        Ok(analytics.prometheus_metrics())
+    };
+    if let Some(address) = serve {
+        let listener = tokio::net::TcpListener::bind(&address).await?;
+        println!(
+            "📈 Serving metrics at http://{}/metrics",
+            listener.local_addr()?
+        );
+        prometheus::serve(listener, once, metrics).await?;
+    } else if let Some(path) = textfile {
+        prometheus::write_textfile(&path, &metrics().await?).await?;
+        println!("📈 Metrics written to {}", path.display());
+    } else {
+        print!("{}", metrics().await?);
+    }
+    Ok(0)
+}
+
+/// Config of a command, with the environment and `--profile` (default:
+/// `$PUBLISH_PROFILE`) applied
+async fn load_config(project_path: &Path, profile: Option<&str>) -> Result<PublishConfig> {
+    Ok(ConfigLoader::load(ConfigLoadOptions {
+        project_path: project_path.to_path_buf(),
+        cli_args: None,
+        env: std::env::vars().collect(),
+        reporter: None,
+        profile: profile.map(str::to_string),
+    })
+    .await?)
+}
+
+/// Rewrite the `metrics.textfile` after recording publishes
+async fn update_metrics_textfile(
+    project_path: &Path,
+    analytics: &PublishAnalytics,
+    reporter: &dyn Reporter,
+    profile: Option<&str>,
+) {
+    let config = load_config(project_path, profile).await.ok();
+    let Some(path) = prometheus::textfile_path(
+        project_path,
+        config.as_ref().and_then(|c| c.metrics.as_ref()),
+    ) else {
+        return;
+    };
+    if let Err(e) = prometheus::write_textfile(&path, &analytics.prometheus_metrics()).await {
+        reporter.warning(&format!("⚠️  Failed to write metrics: {}", e));
+    }
+}
+
+async fn stats_import_command(
+    project_path: PathBuf,
+    file: PathBuf,
+    format: Option<ExportFormat>,
+) -> Result<i32> {
+    let content = tokio::fs::read_to_string(&file).await?;
+    let format = format.unwrap_or_else(|| ExportFormat::from_path(&file));
+    let records = PublishAnalytics::parse_export(&content, format)
+        .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+
+    let mut analytics = PublishAnalytics::new(&project_path);
+    analytics.initialize().await?;
+    let summary = analytics.import(records).await?;
+    println!(
+        "📥 {} records imported from {} ({} already present)",
+        summary.imported,
+        file.display(),
+        summary.duplicates
+    );
+    Ok(0)
+}
+
+async fn calendar_command(
+    project_path: PathBuf,
+    registry: Option<String>,
+    package: Option<String>,
+    output: Option<PathBuf>,
+    profile: Option<&str>,
+) -> Result<i32> {
+    let mut analytics = PublishAnalytics::new(&project_path);
+    analytics.initialize().await?;
+    let records = analytics.get_records(&AnalyticsOptions {
+        registry,
+        package_name: package,
+        ..Default::default()
+    });
+
+    let config = load_config(&project_path, profile).await.ok();
+    let freeze = match config.as_ref().and_then(|c| c.release_windows.as_ref()) {
+        Some(windows) => ReleaseWindows::from_config(windows)?
+            .freeze_periods()
+            .to_vec(),
+        None => Vec::new(),
+    };
+
+    let ics = release_window::releases_to_ics(&records, &freeze);
+    match output {
+        Some(path) => {
+            tokio::fs::write(&path, ics).await?;
+            println!(
+                "🗓️  {} releases, {} freeze periods written to {}",
+                records.len(),
+                freeze.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", ics),
+    }
+    Ok(0)
+}
+
+async fn export_audit_command(
+    project_path: PathBuf,
+    version: String,
+    output: Option<PathBuf>,
+    profile: Option<&str>,
+) -> Result<i32> {
+    println!("\n🗂️  Release Audit Export\n");
+
+    let config = load_config(&project_path, profile).await.ok();
+
+    let exporter = AuditExporter::new(&project_path);
+    match exporter.export(&version, output, config.as_ref()).await {
+        Ok(result) => {
+            println!("✅ Audit bundle written: {}", result.output_path.display());
+            for file in &result.manifest.files {
+                println!("  - {}", file);
+            }
+            if !result.manifest.missing.is_empty() {
+                println!(
+                    "\n⚠️  Not recorded for this release: {}",
+                    result.manifest.missing.join(", ")
+                );
+            }
+            Ok(0)
+        }
+        Err(e) => {
+            eprintln!("❌ Audit export failed: {}", redact(&e.to_string()));
+            Ok(1)
+        }
+    }
+}
+
+async fn rollback_command(
+    project_path: PathBuf,
+    version: String,
+    options: RollbackOptions,
+) -> Result<i32> {
+    println!("\n📦 package-publisher rollback\n");
+
+    let mut publisher = PackagePublisher::new(&project_path);
+
+    match publisher.rollback(&version, options).await {
+        Ok(report) if report.success => {
+            println!("✅ {}", report.message);
+            Ok(0)
+        }
+        Ok(report) => {
+            eprintln!("❌ {}", report.message);
+            if let Some(ref error) = report.error {
+                eprintln!("  - {}", redact(error));
+            }
+            Ok(1)
+        }
+        Err(e) => {
+            eprintln!("\n❌ Rollback failed: {}", redact(&e.to_string()));
+            Ok(1)
+        }
+    }
+}
+
+async fn bump_command(
+    project_path: PathBuf,
+    level: BumpLevel,
+    preid: Option<String>,
+    dry_run: bool,
+) -> Result<i32> {
+    println!("\n🔢 Version Bump ({})\n", level);
+
+    let mut bumper = VersionBumper::new(&project_path);
+    if let Some(preid) = preid {
+        bumper = bumper.with_preid(preid);
+    }
+
+    match bumper.bump(level, dry_run).await {
+        Ok(result) => {
+            println!(
+                "{} {} → {}",
+                if dry_run { "🔍" } else { "✅" },
+                result.previous_version,
+                result.new_version
+            );
+            for file in &result.files {
+                println!("  - {}", file.display());
+            }
+            for warning in &result.warnings {
+                println!("  ⚠️  {}", warning);
+            }
+            if dry_run {
+                println!("\n(dry-run: no files were modified)");
+            }
+            Ok(0)
+        }
+        Err(e) => {
+            eprintln!("❌ Version bump failed: {}", redact(&e.to_string()));
+            Ok(1)
+        }
+    }
+}
+
+async fn changelog_command(
+    project_path: PathBuf,
+    version: Option<String>,
+    dry_run: bool,
+    profile: Option<&str>,
+) -> Result<i32> {
+    println!("\n📝 Changelog\n");
+
+    let version = match version {
+        Some(version) => version,
+        None => match VersionBumper::new(&project_path)
+            .find_versions()
+            .await
+            .first()
+        {
+            Some(file) => file.version.clone(),
+            None => {
+                eprintln!("❌ No version found; pass --version");
+                return Ok(1);
+            }
+        },
+    };
+
+    let config = load_config(&project_path, profile).await.ok();
+    let mut generator = ChangelogGenerator::new(&project_path);
+    if let Some(changelog) = config.as_ref().and_then(|c| c.changelog.as_ref()) {
+        generator = generator.with_config(changelog);
+    }
+
+    match generator.generate(&version, dry_run).await {
+        Ok(result) => {
+            println!("{}", result.section);
+            println!(
+                "{} {} commits since {}",
+                if result.written { "✅" } else { "🔍" },
+                result.commit_count,
+                result.since_tag.as_deref().unwrap_or("the first commit")
+            );
+            if result.written {
+                println!("Updated {}", result.path.display());
+            } else {
+                println!("(dry-run: {} was not modified)", result.path.display());
+            }
+            Ok(0)
+        }
+        Err(e) => {
+            eprintln!("❌ Changelog generation failed: {}", redact(&e.to_string()));
+            Ok(1)
+        }
+    }
 }
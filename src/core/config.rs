@@ -2,11 +2,13 @@
 //!
 //! This module provides type-safe configuration management with serde support.
 
+use super::i18n::Locale;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Root configuration object
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct PublishConfig {
     /// Schema version (required)
     pub version: String,
@@ -15,6 +17,14 @@ pub struct PublishConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extends: Option<String>,
 
+    /// Built-in preset to start from: rust-cli, ts-library, python-package (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+
+    /// Language of messages: en, ja (optional, default: from LANG)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+
     /// Variable definitions (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<HashMap<String, String>>,
@@ -34,6 +44,10 @@ pub struct PublishConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hooks: Option<HooksConfig>,
 
+    /// Checks run by the git hooks from `hooks install` (optional)
+    #[serde(rename = "gitHooks", skip_serializing_if = "Option::is_none")]
+    pub git_hooks: Option<GitHooksConfig>,
+
     /// Publish options (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub publish: Option<PublishOptionsConfig>,
@@ -46,13 +60,402 @@ pub struct PublishConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notifications: Option<NotificationsConfig>,
 
+    /// Changelog generation before publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<ChangelogConfig>,
+
+    /// Git tag / GitHub Release after publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<ReleaseConfig>,
+
+    /// Post-release maintenance tasks (optional)
+    #[serde(rename = "postRelease", skip_serializing_if = "Option::is_none")]
+    pub post_release: Option<PostReleaseConfig>,
+
+    /// Supply chain artifacts (SBOM) (optional)
+    #[serde(rename = "supplyChain", skip_serializing_if = "Option::is_none")]
+    pub supply_chain: Option<SupplyChainConfig>,
+
+    /// Artifact signing with Sigstore / cosign (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
+
+    /// Allowed/blocked publish windows and freeze periods (optional)
+    #[serde(rename = "releaseWindows", skip_serializing_if = "Option::is_none")]
+    pub release_windows: Option<ReleaseWindowsConfig>,
+
+    /// Publish gating policies evaluated before publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policies: Option<Vec<PolicyConfig>>,
+
+    /// Manual approval by a second person before publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalConfig>,
+
+    /// HTTP client settings: proxy, custom CA bundle, timeouts (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpConfig>,
+
+    /// OpenTelemetry (OTLP) export of publish traces and metrics (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
+
+    /// Prometheus metrics of the publish history (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsConfig>,
+
+    /// Signed webhooks for publish lifecycle events (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<Vec<WebhookConfig>>,
+
     /// Plugin configurations (optional, Phase 4-5)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugins: Option<Vec<PluginConfig>>,
+
+    /// Named overlays selected with `--profile` / `PUBLISH_PROFILE` (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<BTreeMap<String, ProfileConfig>>,
+}
+
+/// Named overlay on the configuration (e.g. `profiles.staging`)
+///
+/// Set fields replace the base configuration field by field, the same way a
+/// project config overrides the global one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ProfileConfig {
+    /// Variable definitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, String>>,
+
+    /// Project basic information (optional, auto-detection available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectConfig>,
+
+    /// Registry-specific configurations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<RegistryConfigs>,
+
+    /// Security settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<SecurityConfig>,
+
+    /// Pre/Post-publish hooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<HooksConfig>,
+
+    /// Checks run by the git hooks from `hooks install`
+    #[serde(rename = "gitHooks", skip_serializing_if = "Option::is_none")]
+    pub git_hooks: Option<GitHooksConfig>,
+
+    /// Publish options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish: Option<PublishOptionsConfig>,
+
+    /// Validation rules
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<ValidationConfig>,
+
+    /// Notification settings (optional, Phase 4-4)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationsConfig>,
+
+    /// Changelog generation before publishing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<ChangelogConfig>,
+
+    /// Git tag / GitHub Release after publishing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<ReleaseConfig>,
+
+    /// Post-release maintenance tasks
+    #[serde(rename = "postRelease", skip_serializing_if = "Option::is_none")]
+    pub post_release: Option<PostReleaseConfig>,
+
+    /// Supply chain artifacts (SBOM)
+    #[serde(rename = "supplyChain", skip_serializing_if = "Option::is_none")]
+    pub supply_chain: Option<SupplyChainConfig>,
+
+    /// Artifact signing with Sigstore / cosign
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
+
+    /// Allowed/blocked publish windows and freeze periods
+    #[serde(rename = "releaseWindows", skip_serializing_if = "Option::is_none")]
+    pub release_windows: Option<ReleaseWindowsConfig>,
+
+    /// Publish gating policies evaluated before publishing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policies: Option<Vec<PolicyConfig>>,
+
+    /// Manual approval by a second person before publishing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalConfig>,
+
+    /// HTTP client settings: proxy, custom CA bundle, timeouts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpConfig>,
+
+    /// OpenTelemetry (OTLP) export of publish traces and metrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
+
+    /// Prometheus metrics of the publish history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsConfig>,
+
+    /// Signed webhooks for publish lifecycle events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<Vec<WebhookConfig>>,
+
+    /// Plugin configurations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<Vec<PluginConfig>>,
+}
+
+/// HTTP client configuration shared by registry plugins and verification
+///
+/// `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` are honored without configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct HttpConfig {
+    /// Proxy URL for all requests, overriding `HTTPS_PROXY`/`HTTP_PROXY`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// Comma-separated hosts that bypass `proxy` (default: `NO_PROXY`)
+    #[serde(rename = "noProxy", skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+
+    /// PEM bundle of additional trusted CA certificates, relative to the
+    /// project (default: `SSL_CERT_FILE`)
+    #[serde(rename = "caBundle", skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+
+    /// Total time a request may take (default: "5m")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Time allowed to establish a connection (default: "30s")
+    #[serde(rename = "connectTimeout", skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<String>,
+}
+
+/// OpenTelemetry export configuration
+///
+/// Traces and metrics are sent as OTLP/HTTP JSON to `{endpoint}/v1/traces`
+/// and `{endpoint}/v1/metrics` after every publish.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct TelemetryConfig {
+    /// Export the telemetry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Base URL of the OTLP/HTTP receiver (e.g. "http://localhost:4318")
+    pub endpoint: String,
+
+    /// Headers sent with every export (e.g. authentication)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+
+    /// `service.name` resource attribute (default: "package-publisher")
+    #[serde(rename = "serviceName", skip_serializing_if = "Option::is_none")]
+    pub service_name: Option<String>,
+}
+
+/// Prometheus metrics configuration
+///
+/// The publish history is written in the Prometheus text format after every
+/// publish, for the node_exporter textfile collector.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct MetricsConfig {
+    /// Write the metrics (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Output file, relative to the project (e.g.
+    /// "/var/lib/node_exporter/textfile/package_publisher.prom")
+    pub textfile: String,
+}
+
+/// Webhook receiving publish lifecycle events
+///
+/// Events are POSTed as JSON; with a secret the body is signed with
+/// HMAC-SHA256 in the `X-Package-Publisher-Signature` header.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct WebhookConfig {
+    /// Endpoint URL (environment variable expansion supported)
+    pub url: String,
+
+    /// HMAC signing secret (environment variable expansion supported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Events to send, e.g. ["publish.succeeded", "publish.failed"]
+    /// (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<String>>,
+
+    /// Extra request headers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+
+    /// Delivery attempts before giving up (default: 3)
+    #[serde(rename = "maxAttempts", skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+}
+
+/// Release window configuration
+///
+/// Windows are cron expressions (`minute hour day-of-month month day-of-week`)
+/// matching every minute they describe; dry-runs are never restricted.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ReleaseWindowsConfig {
+    /// Enforce the windows (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Timezone of windows and freeze dates: "UTC", "local" or an offset
+    /// like "+09:00" (default: "UTC")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// Publishing must fall inside one of these windows (default: any time)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+
+    /// Publishing must not fall inside any of these windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<Vec<String>>,
+
+    /// Freeze periods during which nothing is published
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze: Option<Vec<FreezePeriodConfig>>,
+}
+
+/// Publish gating policy
+///
+/// Rules:
+/// - `schedule`: publishing must fall inside `allowed` and outside `blocked`
+///   cron windows (same syntax as `releaseWindows`)
+/// - `branch`: publishing must happen on one of `branches` and not on
+///   `excludeBranches` (globs like `release/*`)
+/// - `vulnerabilities`: no dependency audit findings at or above `failOn`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct PolicyConfig {
+    /// Name shown in the violation report
+    pub name: String,
+
+    /// Rule: schedule, branch or vulnerabilities
+    pub rule: String,
+
+    /// `block` aborts the publish, `warn` only reports (default: block)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<String>,
+
+    /// Message shown instead of the rule's own explanation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Only evaluate for these registries (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<Vec<String>>,
+
+    /// Only evaluate for `prerelease` or `stable` versions (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<String>,
+
+    /// schedule: timezone of the windows, like `releaseWindows.timezone`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// schedule: publishing must fall inside one of these windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+
+    /// schedule: publishing must not fall inside any of these windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<Vec<String>>,
+
+    /// branch: allowed branches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branches: Option<Vec<String>>,
+
+    /// branch: forbidden branches
+    #[serde(rename = "excludeBranches", skip_serializing_if = "Option::is_none")]
+    pub exclude_branches: Option<Vec<String>>,
+
+    /// vulnerabilities: lowest blocking severity (default: critical)
+    #[serde(rename = "failOn", skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<String>,
+
+    /// vulnerabilities: advisory IDs that never block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
+}
+
+/// Approval gate configuration
+///
+/// The request is posted to Slack (approvers react with ✅ or ❌) or opened as
+/// a GitHub issue (approvers comment `/approve` or `/reject`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ApprovalConfig {
+    /// Require approval before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Where the request is posted: slack or github
+    pub provider: String,
+
+    /// Only require approval for these registries (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<Vec<String>>,
+
+    /// Also require approval for prerelease versions (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prereleases: Option<bool>,
+
+    /// Slack user IDs or GitHub logins allowed to decide (default: anyone)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approvers: Option<Vec<String>>,
+
+    /// How long to wait for a decision ("30m", "2h"; default: "30m")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Delay between checks for a decision (default: "15s")
+    #[serde(rename = "pollInterval", skip_serializing_if = "Option::is_none")]
+    pub poll_interval: Option<String>,
+
+    /// slack: channel ID or name to post the request to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// github: repository as "owner/repo" (default: from the git remote URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Environment variable holding the token
+    /// (default: SLACK_BOT_TOKEN for slack, GITHUB_TOKEN for github)
+    #[serde(rename = "tokenEnv", skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+/// Release freeze period
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct FreezePeriodConfig {
+    /// First frozen day (YYYY-MM-DD)
+    pub start: String,
+
+    /// Last frozen day, inclusive (default: `start`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+
+    /// Shown when publishing is refused and in the calendar export
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Project basic information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct ProjectConfig {
     /// Package name (optional, auto-detection from package.json/Cargo.toml etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -64,7 +467,7 @@ pub struct ProjectConfig {
 }
 
 /// Registry configurations
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct RegistryConfigs {
     /// npm registry configuration
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -82,13 +485,73 @@ pub struct RegistryConfigs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub homebrew: Option<HomebrewRegistryConfig>,
 
+    /// GitHub Packages registry configuration (npm / Maven / ghcr.io)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "githubPackages")]
+    pub github_packages: Option<GitHubPackagesRegistryConfig>,
+
+    /// Unity Package Manager registry configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upm: Option<UpmRegistryConfig>,
+
+    /// Julia General registry configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub julia: Option<JuliaRegistryConfig>,
+
+    /// Bazel Central Registry configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bazel: Option<BazelRegistryConfig>,
+
+    /// Hackage configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hackage: Option<HackageRegistryConfig>,
+
+    /// Expo (EAS Update) configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expo: Option<ExpoRegistryConfig>,
+
+    /// Object storage bucket (S3 / GCS / Azure Blob) configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<BucketRegistryConfig>,
+
+    /// JFrog Artifactory generic repository configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifactory: Option<RepositoryManagerConfig>,
+
+    /// Sonatype Nexus raw repository configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nexus: Option<RepositoryManagerConfig>,
+
+    /// Cloudsmith configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloudsmith: Option<CloudsmithRegistryConfig>,
+
+    /// Gemfury configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gemfury: Option<GemfuryRegistryConfig>,
+
+    /// Scoop bucket manifest configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoop: Option<WindowsManifestConfig>,
+
+    /// winget manifest configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winget: Option<WindowsManifestConfig>,
+
+    /// AUR (Arch User Repository) configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aur: Option<AurRegistryConfig>,
+
+    /// Debian package (Launchpad PPA / aptly / Artifactory apt) configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debian: Option<DebianRegistryConfig>,
+
     /// Custom registries (generic schema)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom: Option<HashMap<String, CustomRegistryConfig>>,
 }
 
 /// npm registry configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct NPMRegistryConfig {
     /// Enable this registry (default: true if defined)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -105,10 +568,60 @@ pub struct NPMRegistryConfig {
     /// One-time password (2FA) configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub otp: Option<OTPConfig>,
+
+    /// Public API diff against the published release (TypeScript packages)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "apiReport")]
+    pub api_report: Option<ApiReportConfig>,
+
+    /// Package size regression gate
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bundleSize")]
+    pub bundle_size: Option<BundleSizeConfig>,
+}
+
+/// npm package size gate configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct BundleSizeConfig {
+    /// Measure the package and check the limits (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Maximum tarball size in bytes
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxPackedSize")]
+    pub max_packed_size: Option<u64>,
+
+    /// Maximum unpacked size in bytes
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxUnpackedSize")]
+    pub max_unpacked_size: Option<u64>,
+
+    /// Maximum growth over the previous release in percent
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxGrowthPercent")]
+    pub max_growth_percent: Option<f64>,
+
+    /// "error" blocks publishing when a limit is exceeded, "warning" only reports it
+    /// (default: "warning")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+}
+
+/// TypeScript API report configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ApiReportConfig {
+    /// Diff the API during dry-run (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// "error" fails the dry-run when the version bump is too small,
+    /// "warning" only reports it (default: "error")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+
+    /// Release to compare against (default: the "latest" dist-tag)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "baselineVersion")]
+    pub baseline_version: Option<String>,
 }
 
 /// npm package access level
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum NPMAccess {
     Public,
@@ -116,63 +629,629 @@ pub enum NPMAccess {
 }
 
 /// OTP (2FA) configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct OTPConfig {
-    /// Is OTP required? (default: false)
+    /// Get a one-time password before publishing instead of when npm asks
+    /// for one (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
 
-    /// When to prompt for OTP (required if otp.required=true)
+    /// Ask for a one-time password when npm requires one: "runtime", or
+    /// "never" to fail instead (default: "runtime")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+
+    /// Command printing a one-time password (e.g. `op item get npm --otp`,
+    /// `pass otp npm`), used instead of asking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
 }
 
 /// crates.io registry configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct CratesRegistryConfig {
     /// Enable this registry (default: true if defined)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
 
-    /// Cargo features to enable
+    /// Cargo features to enable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+
+    /// Check feature combinations during validation
+    #[serde(skip_serializing_if = "Option::is_none", rename = "featureMatrix")]
+    pub feature_matrix: Option<FeatureMatrixConfig>,
+
+    /// Detect breaking API changes with cargo-semver-checks
+    #[serde(skip_serializing_if = "Option::is_none", rename = "semverChecks")]
+    pub semver_checks: Option<SemverChecksConfig>,
+
+    /// Alternative registry name from `.cargo/config.toml` (default: crates.io,
+    /// or a single `package.publish` entry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// crates.io authentication (default: "auto", trusted publishing when
+    /// the CI provides an OIDC token, else `CARGO_REGISTRY_TOKEN`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<CratesAuthMode>,
+}
+
+/// crates.io authentication mode
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CratesAuthMode {
+    /// Trusted publishing in CI jobs with an OIDC token, else the token
+    #[default]
+    Auto,
+    /// Always trusted publishing (fails outside CI)
+    Trusted,
+    /// Always `CARGO_REGISTRY_TOKEN` / `cargo login`
+    Token,
+}
+
+/// cargo-semver-checks configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct SemverChecksConfig {
+    /// Run the check during validation (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// "error" blocks publishing on breaking changes, "warning" only reports them
+    /// (default: "error")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+
+    /// Release to compare against (default: latest on crates.io)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "baselineVersion")]
+    pub baseline_version: Option<String>,
+}
+
+/// Cargo feature matrix configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct FeatureMatrixConfig {
+    /// Run the matrix during validation (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Combinations: "no-default-features", "each-feature", "all-features",
+    /// "powerset" (cargo hack only); default: the first three
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategies: Option<Vec<String>>,
+
+    /// Features left out of the matrix
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip: Option<Vec<String>>,
+
+    /// Use `cargo hack` when installed (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "cargoHack")]
+    pub cargo_hack: Option<bool>,
+}
+
+/// PyPI registry configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PyPIRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Repository name (default: "pypi")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<PyPIRepository>,
+}
+
+/// PyPI repository name
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PyPIRepository {
+    Pypi,
+    Testpypi,
+}
+
+/// Homebrew registry configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct HomebrewRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Custom tap name (default: auto-detect)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tap: Option<String>,
+
+    /// Update the formula from the release tarball before publishing
+    #[serde(skip_serializing_if = "Option::is_none", rename = "autoUpdate")]
+    pub auto_update: Option<HomebrewAutoUpdateConfig>,
+
+    /// Open a pull request to the tap instead of pushing to its branch
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pullRequest")]
+    pub pull_request: Option<HomebrewPullRequestConfig>,
+}
+
+/// Homebrew tap pull request configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct HomebrewPullRequestConfig {
+    /// Push to a new branch and open a PR (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Tap repository as "owner/repo" (default: from the origin remote URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Branch the PR targets (default: the current branch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+
+    /// Environment variable holding the token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Homebrew formula auto-update configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct HomebrewAutoUpdateConfig {
+    /// Rewrite `url`/`sha256`/`version` on publish (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Release tarball URL; `{version}` is replaced with the released version
+    /// (e.g. "https://github.com/me/tool/archive/refs/tags/v{version}.tar.gz")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Formula name used when no formula exists yet (default: project directory name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formula: Option<String>,
+}
+
+/// GitHub Packages registry configuration
+///
+/// The package type is detected from package.json (`publishConfig.registry`),
+/// pom.xml (`distributionManagement`) or a Dockerfile
+/// (`org.opencontainers.image.source` label).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct GitHubPackagesRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Repository owner (default: npm scope / Maven URL / image source label)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// Repository name (required for Maven if not in distributionManagement)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Container image name (default: repository name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+
+    /// Environment variable holding the token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Unity Package Manager (UPM) registry configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct UpmRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// npm-compatible UPM registry URL (default: package.json publishConfig.registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// Environment variable holding the auth token (default: "UPM_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Julia General registry configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct JuliaRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// GitHub repository (`owner/repo`) to comment on (default: from the origin remote)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Environment variable holding the GitHub token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Bazel Central Registry (BCR) configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct BazelRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Fork of the registry the PR branch is pushed to (`owner/bazel-central-registry`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork: Option<String>,
+
+    /// Registry repository (default: "bazelbuild/bazel-central-registry")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// Source repository (`owner/repo`) (default: from the origin remote)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Release tag format (default: "v{version}")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tagFormat")]
+    pub tag_format: Option<String>,
+
+    /// Environment variable holding the GitHub token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Hackage configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct HackageRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Build and upload Haddock documentation after the package (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs: Option<bool>,
+
+    /// Environment variable holding the Hackage API token (default: "HACKAGE_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Expo (EAS Update) configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ExpoRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Update channel (default: the publish tag, else "production")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Platform: "all", "ios" or "android" (default: "all")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+
+    /// Update message (default: "Release <version>")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Also submit the latest store build with `eas submit` (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submit: Option<bool>,
+
+    /// eas.json submit profile (default: "production")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "submitProfile")]
+    pub submit_profile: Option<String>,
+
+    /// Environment variable holding the Expo access token (default: "EXPO_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Object storage bucket configuration
+///
+/// Artifacts are uploaded with the provider CLI (`aws`, `gcloud`, `az`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct BucketRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Storage provider: "s3", "gcs" or "azure" (default: "s3")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// Bucket name (container name for Azure)
+    pub bucket: String,
+
+    /// Azure storage account (required for Azure)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+
+    /// S3 region (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// S3-compatible endpoint URL, e.g. MinIO (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// Files to upload (paths, directories or `dist/*.tar.gz` patterns) (default: ["dist"])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Object key template with {name}, {version} and {file} (default: "{name}/{version}/{file}")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "keyTemplate")]
+    pub key_template: Option<String>,
+
+    /// Index format: "json" or "apt" (default: no index)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+
+    /// Index object key (default: "{name}/index.json" or "{name}/Packages")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "indexKey")]
+    pub index_key: Option<String>,
+
+    /// Package name (default: from package.json, Cargo.toml or pyproject.toml)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Artifactory / Nexus repository configuration
+///
+/// The API token is read through the token manager (`ARTIFACTORY_TOKEN` /
+/// `NEXUS_TOKEN`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct RepositoryManagerConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Server base URL (e.g. "https://acme.jfrog.io/artifactory", "https://nexus.acme.dev")
+    pub url: String,
+
+    /// Repository key (Artifactory generic repository / Nexus raw hosted repository)
+    pub repository: String,
+
+    /// User for basic authentication (required for Nexus; Artifactory uses a bearer token without it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Files to upload (paths, directories or `dist/*.tar.gz` patterns) (default: ["dist"])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Path template with {name}, {version} and {file} (default: "{name}/{version}/{file}")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pathTemplate")]
+    pub path_template: Option<String>,
+
+    /// Extra Artifactory properties set on each artifact (name/version are always set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<HashMap<String, String>>,
+
+    /// Nexus tag associated with the uploaded assets (Nexus Pro)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+
+    /// Package name (default: from package.json, Cargo.toml or pyproject.toml)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Cloudsmith configuration
+///
+/// The API key is read through the token manager (`CLOUDSMITH_API_KEY`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct CloudsmithRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Organization or user that owns the repository
+    pub owner: String,
+
+    /// Repository slug
+    pub repository: String,
+
+    /// Package format: "npm", "cargo", "python" or "deb" (default: detected from the project)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Debian distribution/release, e.g. "ubuntu/jammy" (required for deb)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<String>,
+
+    /// Files to upload (default: the package built for the format, or dist/*.deb)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Environment variable with an entitlement token for private repositories,
+    /// used in the reported install URL (default: "CLOUDSMITH_ENTITLEMENT")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "entitlementEnv")]
+    pub entitlement_env: Option<String>,
+
+    /// Package name (default: from package.json, Cargo.toml or pyproject.toml)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Gemfury configuration
+///
+/// The push token is read through the token manager (`GEMFURY_PUSH_TOKEN`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct GemfuryRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Gemfury account name
+    pub account: String,
+
+    /// Package format: "npm", "cargo", "python" or "deb" (default: detected from the project)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Files to upload (default: the package built for the format, or dist/*.deb)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Environment variable with a deploy (read) token used for verification
+    /// (default: "GEMFURY_DEPLOY_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "deployTokenEnv")]
+    pub deploy_token_env: Option<String>,
+
+    /// Package name (default: from package.json, Cargo.toml or pyproject.toml)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// AUR (Arch User Repository) configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct AurRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Directory containing the PKGBUILD (default: the project root, then "aur")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// AUR git remote (default: "ssh://aur@aur.archlinux.org/{pkgbase}.git")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    /// SSH private key registered with the AUR account (default: the SSH agent / ~/.ssh/config)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sshKey")]
+    pub ssh_key: Option<String>,
+}
+
+/// Debian package configuration
+///
+/// `target` selects where the package goes: "ppa" (signed source upload with
+/// dput; Launchpad builds the binaries), "aptly" (aptly REST API) or
+/// "artifactory" (Debian repository; token from `ARTIFACTORY_TOKEN`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct DebianRegistryConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// "ppa", "aptly" or "artifactory" (default: "ppa" when `ppa` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Package builder: "cargo-deb" or "dpkg-buildpackage"
+    /// (default: dpkg-buildpackage with a debian/ directory, else cargo-deb)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder: Option<String>,
+
+    /// Prebuilt .deb files to upload instead of building (aptly / Artifactory)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// GPG key used to sign the upload (debsign for PPAs, dpkg-sig otherwise)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "signKey")]
+    pub sign_key: Option<String>,
+
+    /// Launchpad PPA as "owner/name"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ppa: Option<String>,
+
+    /// aptly API URL or Artifactory base URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// aptly local repository or Artifactory repository key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// aptly publish prefix (default: ".")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// Distribution (e.g. "jammy", "stable")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<String>,
+
+    /// Component (default: "main")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+
+    /// Architecture checked on verification (default: "amd64")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+
+    /// Environment variable holding the API token (aptly behind an
+    /// authenticating proxy; Artifactory default: ARTIFACTORY_TOKEN)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Scoop / winget manifest configuration
+///
+/// Manifests are written to the manifest repository through the GitHub API;
+/// the token is read from `tokenEnv`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct WindowsManifestConfig {
+    /// Enable this registry (default: true if defined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Manifest repository as "owner/repo" (Scoop: the bucket, required;
+    /// winget default: "microsoft/winget-pkgs")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Fork the PR branch is pushed to (default: the manifest repository)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork: Option<String>,
+
+    /// Open a pull request instead of committing to the default branch
+    /// (default: false for Scoop, always true for winget)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pullRequest")]
+    pub pull_request: Option<bool>,
+
+    /// Download URL template of the Windows build (`{version}` is replaced)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Download URL templates per architecture ("x64", "x86", "arm64")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urls: Option<BTreeMap<String, String>>,
+
+    /// Manifest path in the repository (default: "bucket/{name}.json" for
+    /// Scoop, "manifests/<letter>/<Publisher>/<Package>/{version}" for winget)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "manifestPath")]
+    pub manifest_path: Option<String>,
+
+    /// winget package identifier ("Publisher.Package")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "packageIdentifier")]
+    pub package_identifier: Option<String>,
+
+    /// Publisher name (winget default: the identifier's first segment)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub features: Option<Vec<String>>,
-}
+    pub publisher: Option<String>,
 
-/// PyPI registry configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct PyPIRegistryConfig {
-    /// Enable this registry (default: true if defined)
+    /// Short description
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enabled: Option<bool>,
+    pub description: Option<String>,
 
-    /// Repository name (default: "pypi")
+    /// Project homepage
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub repository: Option<PyPIRepository>,
-}
+    pub homepage: Option<String>,
 
-/// PyPI repository name
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum PyPIRepository {
-    Pypi,
-    Testpypi,
-}
+    /// License identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
 
-/// Homebrew registry configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct HomebrewRegistryConfig {
-    /// Enable this registry (default: true if defined)
+    /// Executable inside the archive (default: "{name}.exe")
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enabled: Option<bool>,
+    pub bin: Option<String>,
 
-    /// Custom tap name (default: auto-detect)
+    /// Package name (default: from package.json, Cargo.toml or pyproject.toml)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tap: Option<String>,
+    pub name: Option<String>,
+
+    /// Environment variable holding the GitHub token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
 }
 
 /// Custom registry configuration (generic schema)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct CustomRegistryConfig {
     /// Enable this registry (default: true if defined)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -192,10 +1271,18 @@ pub struct CustomRegistryConfig {
     /// Verify command template (optional)
     #[serde(skip_serializing_if = "Option::is_none", rename = "verifyCommand")]
     pub verify_command: Option<String>,
+
+    /// Programs the commands may run besides the built-in whitelist
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowedCommands")]
+    pub allowed_commands: Option<Vec<String>>,
+
+    /// Command timeout in seconds (default: 600)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
 }
 
 /// Security configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct SecurityConfig {
     /// Environment variable expansion settings
     #[serde(skip_serializing_if = "Option::is_none", rename = "envVarExpansion")]
@@ -208,10 +1295,158 @@ pub struct SecurityConfig {
     /// Allowed commands settings
     #[serde(skip_serializing_if = "Option::is_none", rename = "allowedCommands")]
     pub allowed_commands: Option<HashMap<String, AllowedCommandConfig>>,
+
+    /// Dependency audit gate (npm audit / cargo audit / pip-audit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit: Option<AuditConfig>,
+
+    /// Virus/malware scanning of release artifacts
+    #[serde(skip_serializing_if = "Option::is_none", rename = "malwareScanning")]
+    pub malware_scanning: Option<MalwareScanningConfig>,
+
+    /// Dependency confusion checks for internal package names
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "dependencyConfusion"
+    )]
+    pub dependency_confusion: Option<DependencyConfusionConfig>,
+
+    /// Secrets and file policy checks on the packed artifact
+    #[serde(skip_serializing_if = "Option::is_none", rename = "artifactInspection")]
+    pub artifact_inspection: Option<ArtifactInspectionConfig>,
+
+    /// Environment and working directory restrictions of hooks and publish
+    /// commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+}
+
+/// Command sandbox configuration
+///
+/// Hooks and the registry tooling plugins run (npm, cargo, twine, brew,
+/// cabal, makepkg, the EAS and storage CLIs, custom registry commands, ...)
+/// get a minimal environment: the basics (PATH, HOME, locale, proxies,
+/// ...), the tool's own variables (e.g. `NPM_TOKEN`, `CARGO_*`, `TWINE_*`,
+/// `HOMEBREW_*`, `AWS_*`), the CI identity npm, cargo and GitHub Packages
+/// need for provenance and trusted publishing (`ACTIONS_ID_TOKEN_REQUEST_*`,
+/// `GITHUB_*`, ...) and `allowEnv`. Registry commands are also stopped
+/// after `publish.commandTimeout`.
+///
+/// Dynamically loaded plugins (shared libraries and plugin executables) are
+/// not sandboxed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct SandboxConfig {
+    /// Drop environment variables that are not allowed (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Additional variables passed to every command (`NAME`, or `PREFIX_*`)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEnv")]
+    pub allow_env: Option<Vec<String>>,
+
+    /// Reject hook and command working directories outside the project
+    /// (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jail: Option<bool>,
+}
+
+/// Packed artifact inspection configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ArtifactInspectionConfig {
+    /// Inspect artifacts before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Files that must not be published (.gitignore syntax, `!` re-allows);
+    /// replaces the defaults (.env, *.pem, *.key, .npmrc, ...)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "forbiddenFiles")]
+    pub forbidden_files: Option<Vec<String>>,
+
+    /// Artifacts to inspect (files or `*` patterns);
+    /// default: the package built for the registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// "error" blocks publishing on findings, "warning" only reports them
+    /// (default: "error")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+}
+
+/// Dependency confusion check configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct DependencyConfusionConfig {
+    /// Check before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Internal package names (`*` wildcards allowed, e.g. "acme-*")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "internalPackages")]
+    pub internal_packages: Option<Vec<String>>,
+
+    /// Internal npm scopes (e.g. "@acme"); each must be routed in .npmrc
+    #[serde(skip_serializing_if = "Option::is_none", rename = "internalScopes")]
+    pub internal_scopes: Option<Vec<String>>,
+
+    /// Lowest severity that blocks publishing (default: "high"); a public package
+    /// with an internal name is high, a registry fallback is medium
+    #[serde(skip_serializing_if = "Option::is_none", rename = "failOn")]
+    pub fail_on: Option<String>,
+}
+
+/// Malware scanning configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct MalwareScanningConfig {
+    /// Scan artifacts before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Scanner: "clamav", "command" or "rest" (default: "clamav")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scanner: Option<String>,
+
+    /// Scanner command for "command" (`{file}` is replaced with the artifact path;
+    /// exit code 0 = clean, 1 = infected)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+
+    /// Scanning API endpoint for "rest"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Environment variable holding the scanning API token (default: "MALWARE_SCAN_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+
+    /// Artifacts to scan (files, directories, or `*` patterns);
+    /// default: the package built for the registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Block publishing when the scanner cannot produce a verdict (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "failOnError")]
+    pub fail_on_error: Option<bool>,
+}
+
+/// Dependency audit gate configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct AuditConfig {
+    /// Run the audit before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Lowest severity that blocks publishing: "critical", "high", "medium" or "low"
+    /// (default: "high")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "failOn")]
+    pub fail_on: Option<String>,
+
+    /// Advisory IDs to ignore (GHSA-..., RUSTSEC-..., PYSEC-...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
 }
 
 /// Environment variable expansion configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct EnvVarExpansionConfig {
     /// Enable environment variable expansion (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -227,7 +1462,7 @@ pub struct EnvVarExpansionConfig {
 }
 
 /// Secrets scanning configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct SecretsScanningConfig {
     /// Enable secrets scanning (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -240,10 +1475,26 @@ pub struct SecretsScanningConfig {
     /// Reject path traversal attempts (default: true)
     #[serde(skip_serializing_if = "Option::is_none", rename = "rejectTraversal")]
     pub reject_traversal: Option<bool>,
+
+    /// Also scan the diffs of this many recent commits (default: history not scanned)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "historyDepth")]
+    pub history_depth: Option<usize>,
+
+    /// Files scanned concurrently (default: twice the CPU cores)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallelism: Option<usize>,
+
+    /// Skip files larger than this many bytes (default: 1 MiB)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "maxFileSize")]
+    pub max_file_size: Option<u64>,
+
+    /// Skip paths matched by .gitignore (default: true; .publishignore always applies)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "respectGitignore")]
+    pub respect_gitignore: Option<bool>,
 }
 
 /// Ignore pattern for secrets scanning
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct IgnorePattern {
     /// Pattern to match (glob pattern)
     pub pattern: String,
@@ -254,7 +1505,7 @@ pub struct IgnorePattern {
 }
 
 /// Allowed commands configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct AllowedCommandConfig {
     /// Full path to executable (required)
     pub executable: String,
@@ -269,7 +1520,7 @@ pub struct AllowedCommandConfig {
 }
 
 /// Hooks configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct HooksConfig {
     /// Pre-build hooks
     #[serde(skip_serializing_if = "Option::is_none", rename = "preBuild")]
@@ -288,8 +1539,24 @@ pub struct HooksConfig {
     pub on_error: Option<Vec<HookCommand>>,
 }
 
+/// Git hook configuration (`hooks install`)
+///
+/// Checks: `secrets` (staged files on pre-commit, the working tree on
+/// pre-push), `manifest` (manifest validation without builds) and `check`
+/// (full `check`, including builds). An empty list skips the hook.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct GitHooksConfig {
+    /// Checks run before each commit (default: secrets, manifest)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "preCommit")]
+    pub pre_commit: Option<Vec<String>>,
+
+    /// Checks run before each push (default: secrets, manifest)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "prePush")]
+    pub pre_push: Option<Vec<String>>,
+}
+
 /// Hook command configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct HookCommand {
     /// Command to execute
     pub command: String,
@@ -305,10 +1572,15 @@ pub struct HookCommand {
     /// Working directory (default: "./")
     #[serde(skip_serializing_if = "Option::is_none", rename = "workingDirectory")]
     pub working_directory: Option<String>,
+
+    /// Environment variables passed to this hook on top of
+    /// `security.sandbox` (`NAME`, or `PREFIX_*`)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowEnv")]
+    pub allow_env: Option<Vec<String>>,
 }
 
 /// Publish options configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct PublishOptionsConfig {
     /// Dry-run behavior (default: "first")
     #[serde(skip_serializing_if = "Option::is_none", rename = "dryRun")]
@@ -325,10 +1597,68 @@ pub struct PublishOptionsConfig {
     /// Interactive mode (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub interactive: Option<bool>,
+
+    /// Warn when a publish takes longer than this
+    #[serde(rename = "durationBudget", skip_serializing_if = "Option::is_none")]
+    pub duration_budget: Option<DurationBudgetConfig>,
+
+    /// Keep retrying verification for up to this long ("90s", "5m")
+    #[serde(rename = "verifyTimeout", skip_serializing_if = "Option::is_none")]
+    pub verify_timeout: Option<String>,
+
+    /// First delay between verification attempts, doubled each retry (default: "5s")
+    #[serde(rename = "verifyInterval", skip_serializing_if = "Option::is_none")]
+    pub verify_interval: Option<String>,
+
+    /// Retries of registry commands and HTTP calls on transient failures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+
+    /// Kill npm, cargo and twine commands (publish, builds, audits) running
+    /// longer than this, with the processes they spawned (default: "30m")
+    #[serde(rename = "commandTimeout", skip_serializing_if = "Option::is_none")]
+    pub command_timeout: Option<String>,
+}
+
+/// Retry policy for transient registry failures (network errors, HTTP 5xx,
+/// rate limiting)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct RetryConfig {
+    /// Attempts including the first one (default: 3, 1 disables retries)
+    #[serde(rename = "maxAttempts", skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+
+    /// Delay before the first retry (default: "1s")
+    #[serde(rename = "initialDelay", skip_serializing_if = "Option::is_none")]
+    pub initial_delay: Option<String>,
+
+    /// Upper bound of the delay between retries (default: "30s")
+    #[serde(rename = "maxDelay", skip_serializing_if = "Option::is_none")]
+    pub max_delay: Option<String>,
+
+    /// Randomize delays (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jitter: Option<bool>,
+
+    /// Per-registry overrides of the settings above
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<HashMap<String, RetryConfig>>,
+}
+
+/// Publish duration budget ("90s", "5m", "1h30m", or seconds)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct DurationBudgetConfig {
+    /// Budget for every registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Per-registry budgets, overriding `default`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registries: Option<HashMap<String, String>>,
 }
 
 /// Dry-run mode
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum DryRunMode {
     First,
@@ -337,15 +1667,59 @@ pub enum DryRunMode {
 }
 
 /// Validation configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct ValidationConfig {
     /// Custom validation rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rules: Option<Vec<ValidationRule>>,
+
+    /// Treat validation warnings as errors (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+
+    /// Maximum number of validation warnings allowed (default: unlimited)
+    #[serde(rename = "maxWarnings", skip_serializing_if = "Option::is_none")]
+    pub max_warnings: Option<usize>,
+
+    /// Maximum packed package size ("10MB", "512KiB", bytes)
+    #[serde(rename = "maxPackageSize", skip_serializing_if = "Option::is_none")]
+    pub max_package_size: Option<String>,
+
+    /// "error" fails when the package exceeds maxPackageSize, "warning" only
+    /// reports it (default: "error")
+    #[serde(rename = "packageSizeLevel", skip_serializing_if = "Option::is_none")]
+    pub package_size_level: Option<String>,
+
+    /// Required/forbidden files in the published package
+    #[serde(rename = "packageContents", skip_serializing_if = "Option::is_none")]
+    pub package_contents: Option<PackageContentsConfig>,
+}
+
+/// Published file list rules (npm, crates.io)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct PackageContentsConfig {
+    /// Check the file list before publishing (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Patterns that must each match a published file (.gitignore syntax);
+    /// replaces the defaults (README*, LICENSE*)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+
+    /// Patterns no published file may match (.gitignore syntax, `!` re-allows);
+    /// replaces the defaults (tests/, .github/, .vscode/, .env, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forbidden: Option<Vec<String>>,
+
+    /// "error" blocks publishing on violations, "warning" only reports them
+    /// (default: "error")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
 }
 
 /// Validation rule
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct ValidationRule {
     /// Rule name
     pub name: String,
@@ -371,54 +1745,407 @@ pub struct ValidationRule {
 }
 
 /// Validation severity
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ValidationSeverity {
     Error,
     Warning,
 }
 
+/// Post-release configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PostReleaseConfig {
+    /// Deprecate/yank prereleases superseded by a stable release
+    #[serde(rename = "cleanupPrereleases", skip_serializing_if = "Option::is_none")]
+    pub cleanup_prereleases: Option<CleanupPrereleasesConfig>,
+}
+
+/// Changelog generation configuration
+///
+/// Conventional commits since the last tag are grouped by type and written
+/// to the changelog before publishing.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ChangelogConfig {
+    /// Generate the changelog as a pre-publish step (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Changelog file, relative to the project (default: "CHANGELOG.md")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Commit types to include, in section order (default: feat, fix, perf, revert)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
+}
+
+/// Release configuration (post-publish git tag and GitHub Release)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ReleaseConfig {
+    /// Create an annotated tag after a successful publish (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Tag name format (default: "v{version}")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tagFormat")]
+    pub tag_format: Option<String>,
+
+    /// Push the tag to the remote (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push: Option<bool>,
+
+    /// Git remote (default: "origin")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    /// GitHub Release creation (optional)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "githubRelease")]
+    pub github_release: Option<GitHubReleaseConfig>,
+
+    /// GitHub Deployment for the release environment (optional)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "githubDeployment")]
+    pub github_deployment: Option<GitHubDeploymentConfig>,
+}
+
+/// GitHub Deployment configuration
+///
+/// The deployment is created when publishing starts and marked
+/// success/failure at the end.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct GitHubDeploymentConfig {
+    /// Create a deployment for each publish (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Environment name; `{registry}` is replaced (default: "{registry}-production")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+
+    /// Repository as "owner/repo" (default: from the git remote URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Mark the environment as a production environment (default: true)
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "productionEnvironment"
+    )]
+    pub production_environment: Option<bool>,
+
+    /// Environment variable holding the token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// GitHub Release configuration
+///
+/// The release body is the version's changelog section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct GitHubReleaseConfig {
+    /// Create a GitHub Release for the tag (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Repository as "owner/repo" (default: from the git remote URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Create the release as a draft (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+
+    /// Files to attach (paths, directories or `dist/*.tar.gz` patterns)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<String>>,
+
+    /// Environment variable holding the token (default: "GITHUB_TOKEN")
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tokenEnv")]
+    pub token_env: Option<String>,
+}
+
+/// Supply chain configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct SupplyChainConfig {
+    /// SBOM generation before publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sbom: Option<SbomConfig>,
+
+    /// SLSA provenance attestation after publishing (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ProvenanceConfig>,
+}
+
+/// SBOM generation configuration
+///
+/// The SBOM is stored with the release artifacts and its SHA-256 is recorded
+/// in the publish report.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct SbomConfig {
+    /// Generate an SBOM before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Document format: "cyclonedx" or "spdx" (default: "cyclonedx")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Attach the SBOM to the GitHub Release (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "attachToRelease")]
+    pub attach_to_release: Option<bool>,
+}
+
+/// SLSA provenance configuration
+///
+/// The statement is written to `.package-publisher/provenance/` and its
+/// SHA-256 is recorded in the publish report.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ProvenanceConfig {
+    /// Generate provenance (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Attach the statement to the GitHub Release (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "attachToRelease")]
+    pub attach_to_release: Option<bool>,
+
+    /// Builder ID (default: the CI workflow, or package-publisher for local runs)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "builderId")]
+    pub builder_id: Option<String>,
+}
+
+/// Artifact signing configuration
+///
+/// Artifacts are signed with `cosign sign-blob` right before publishing.
+/// Without `key`, Sigstore keyless signing is used (OIDC identity from CI
+/// or the browser flow).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct SigningConfig {
+    /// Sign artifacts before publishing (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// cosign key reference (file path, KMS URI); keyless when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    /// Artifacts to sign (files, directories, or `*` patterns);
+    /// default: the package built for the registry (npm tarball, .crate, wheel/sdist)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// Also attest the generated SBOM for each artifact (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "attestSbom")]
+    pub attest_sbom: Option<bool>,
+
+    /// Upload signature bundles to the GitHub Release (default: true)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "attachToRelease")]
+    pub attach_to_release: Option<bool>,
+
+    /// Abort publishing when signing fails (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+/// Superseded prerelease cleanup configuration
+///
+/// After `1.2.0` is published, prereleases such as `1.2.0-beta.1` are
+/// deprecated (npm) or yanked (crates.io).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct CleanupPrereleasesConfig {
+    /// Enable cleanup (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Only list the versions that would be cleaned up (default: false)
+    #[serde(rename = "dryRun", skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Deprecation message; `{version}` is replaced with the stable version
+    /// (default: "Superseded by {version}")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 /// Notifications configuration (Phase 4-4)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct NotificationsConfig {
     /// Enable notifications (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
 
+    /// Notify on successful publish (default: true)
+    #[serde(rename = "onSuccess", skip_serializing_if = "Option::is_none")]
+    pub on_success: Option<bool>,
+
+    /// Notify on failed publish (default: true)
+    #[serde(rename = "onFailure", skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<bool>,
+
+    /// Message templates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub templates: Option<NotificationTemplates>,
+
     /// Slack notification settings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slack: Option<SlackNotificationConfig>,
 
+    /// Discord notification settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discord: Option<ChatNotificationConfig>,
+
+    /// Microsoft Teams notification settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teams: Option<ChatNotificationConfig>,
+
+    /// Mattermost notification settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mattermost: Option<ChatNotificationConfig>,
+
     /// Email notification settings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<EmailNotificationConfig>,
+
+    /// Jira release tracking (issues referenced by commits since the last tag)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira: Option<JiraNotificationConfig>,
+
+    /// Linear release tracking (issues referenced by commits since the last tag)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linear: Option<LinearNotificationConfig>,
+}
+
+/// Jira release tracking configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct JiraNotificationConfig {
+    /// Jira site URL, e.g. "https://acme.atlassian.net"
+    /// (environment variable expansion supported)
+    #[serde(rename = "baseUrl")]
+    pub base_url: String,
+
+    /// Account email for Jira Cloud basic auth (environment variable expansion
+    /// supported); without it the token is sent as a bearer personal access
+    /// token (Jira Server / Data Center)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// Environment variable holding the API token (default: JIRA_API_TOKEN)
+    #[serde(rename = "tokenEnv", skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+
+    /// Project keys to pick up (default: any `ABC-123` reference)
+    #[serde(rename = "projectKeys", skip_serializing_if = "Option::is_none")]
+    pub project_keys: Option<Vec<String>>,
+
+    /// Workflow transition applied to the issues (default: "Released")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transition: Option<String>,
+
+    /// Comment template; placeholders as in `templates` plus `{urls}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Linear release tracking configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct LinearNotificationConfig {
+    /// Environment variable holding the API key (default: LINEAR_API_KEY)
+    #[serde(rename = "tokenEnv", skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+
+    /// Team keys to pick up (default: any `ABC-123` reference)
+    #[serde(rename = "teamKeys", skip_serializing_if = "Option::is_none")]
+    pub team_keys: Option<Vec<String>>,
+
+    /// Workflow state the issues are moved to (default: "Released")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    /// Comment template; placeholders as in `templates` plus `{urls}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 /// Slack notification configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct SlackNotificationConfig {
     /// Slack webhook URL (environment variable expansion supported)
     #[serde(rename = "webhookUrl")]
     pub webhook_url: String,
 }
 
+/// Discord, Microsoft Teams or Mattermost notification configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ChatNotificationConfig {
+    /// Incoming webhook URL (environment variable expansion supported)
+    #[serde(rename = "webhookUrl")]
+    pub webhook_url: String,
+}
+
+/// Notification message templates
+///
+/// Placeholders: `{package}`, `{version}`, `{registry}`, `{duration}`,
+/// `{status}` and `{error}`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct NotificationTemplates {
+    /// Template for successful publishes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+
+    /// Template for failed publishes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
 /// Email notification configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct EmailNotificationConfig {
     /// Email recipients
     pub recipients: Vec<String>,
+
+    /// Sender address (default: first recipient)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// SMTP server host (environment variable expansion supported)
+    #[serde(rename = "smtpHost", skip_serializing_if = "Option::is_none")]
+    pub smtp_host: Option<String>,
+
+    /// SMTP server port (default: 587, STARTTLS; 465 uses implicit TLS)
+    #[serde(rename = "smtpPort", skip_serializing_if = "Option::is_none")]
+    pub smtp_port: Option<u16>,
+
+    /// SMTP username (environment variable expansion supported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// SMTP password (environment variable expansion supported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Subject template (default: "[package-publisher] {package}@{version} {status}")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
 }
 
-/// Plugin configuration (Phase 4-5)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Plugin configuration (shared library or subprocess registry plugins)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct PluginConfig {
-    /// Plugin name (npm package or local path)
+    /// Plugin name (library file name without platform prefix/extension)
     pub name: String,
 
-    /// Plugin version (npm package version)
+    /// Plugin version (informational)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 
+    /// Library path relative to the project
+    /// (default: `.package-publisher/plugins/<platform file name>`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Command running the plugin as a JSON-RPC subprocess (instead of a library)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
     /// Plugin-specific configuration
     pub config: HashMap<String, serde_json::Value>,
 }
@@ -429,6 +2156,8 @@ impl Default for PublishConfig {
         Self {
             version: "1.0".to_string(),
             extends: None,
+            preset: None,
+            locale: None,
             variables: None,
             project: None,
             registries: RegistryConfigs {
@@ -436,14 +2165,43 @@ impl Default for PublishConfig {
                 crates: None,
                 pypi: None,
                 homebrew: None,
+                github_packages: None,
+                upm: None,
+                julia: None,
+                bazel: None,
+                hackage: None,
+                expo: None,
+                bucket: None,
+                artifactory: None,
+                nexus: None,
+                cloudsmith: None,
+                gemfury: None,
+                scoop: None,
+                winget: None,
+                aur: None,
+                debian: None,
                 custom: None,
             },
             security: Some(SecurityConfig::default()),
             hooks: None,
+            git_hooks: None,
             publish: Some(PublishOptionsConfig::default()),
             validation: None,
             notifications: None,
+            changelog: None,
+            release: None,
+            post_release: None,
+            release_windows: None,
+            policies: None,
+            approval: None,
+            supply_chain: None,
+            signing: None,
+            http: None,
+            telemetry: None,
+            metrics: None,
+            webhooks: None,
             plugins: None,
+            profiles: None,
         }
     }
 }
@@ -460,8 +2218,17 @@ impl Default for SecurityConfig {
                 enabled: Some(true),
                 ignore_patterns: None,
                 reject_traversal: Some(true),
+                history_depth: None,
+                parallelism: None,
+                max_file_size: None,
+                respect_gitignore: None,
             }),
             allowed_commands: None,
+            audit: None,
+            malware_scanning: None,
+            dependency_confusion: None,
+            artifact_inspection: None,
+            sandbox: None,
         }
     }
 }
@@ -473,6 +2240,11 @@ impl Default for PublishOptionsConfig {
             confirm: Some(true),
             verify: Some(true),
             interactive: Some(true),
+            duration_budget: None,
+            verify_timeout: None,
+            verify_interval: None,
+            retry: None,
+            command_timeout: None,
         }
     }
 }
@@ -516,6 +2288,8 @@ registries:
             tag: Some("latest".to_string()),
             access: Some(NPMAccess::Public),
             otp: None,
+            api_report: None,
+            bundle_size: None,
         };
         let yaml = serde_yaml::to_string(&config).unwrap();
         assert!(yaml.contains("access: public"));
@@ -528,6 +2302,11 @@ registries:
             confirm: Some(true),
             verify: Some(true),
             interactive: Some(true),
+            duration_budget: None,
+            verify_timeout: None,
+            verify_interval: None,
+            retry: None,
+            command_timeout: None,
         };
         let yaml = serde_yaml::to_string(&config).unwrap();
         assert!(yaml.contains("dryRun: first"));
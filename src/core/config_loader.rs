@@ -3,19 +3,176 @@
 //! This module provides configuration loading, validation, and merging capabilities.
 
 use super::config::*;
+use super::config_schema;
+use super::presets;
+use super::variables;
 use crate::core::error::PublishError;
+use crate::core::reporter::{Reporter, default_reporter};
+use crate::orchestration::hook_runner::HOOK_PLACEHOLDERS;
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
 
 /// Configuration file name
-const CONFIG_FILENAME: &str = ".publish-config.yaml";
+pub const CONFIG_FILENAME: &str = ".publish-config.yaml";
+
+/// Configuration file names looked up in a directory
+pub const CONFIG_FILENAMES: [&str; 4] = [
+    CONFIG_FILENAME,
+    ".publish-config.yml",
+    ".publish-config.toml",
+    ".publish-config.json",
+];
+
+/// Format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Format of a file by its extension (YAML unless `.toml` or `.json`)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Yaml,
+        }
+    }
+
+    /// Parse a configuration in this format
+    pub fn parse(self, content: &str) -> Result<PublishConfig, String> {
+        match self {
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Where a configuration is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// `.publish-config.{yaml,yml,toml,json}` or an `extends` base
+    File(PathBuf),
+    /// `[package.metadata.publisher]` table of a Cargo.toml
+    CargoToml(PathBuf),
+    /// `publisher` key of a package.json
+    PackageJson(PathBuf),
+}
+
+impl ConfigSource {
+    /// Find the configuration of a project
+    ///
+    /// Several configurations (e.g. `.publish-config.yaml` next to a
+    /// `[package.metadata.publisher]` table) are an error rather than
+    /// silently picking one.
+    pub async fn find(project_path: &Path) -> Result<Option<Self>, PublishError> {
+        let mut found: Vec<Self> = CONFIG_FILENAMES
+            .iter()
+            .map(|name| project_path.join(name))
+            .filter(|path| path.is_file())
+            .map(ConfigSource::File)
+            .collect();
+        for source in [
+            ConfigSource::CargoToml(project_path.join("Cargo.toml")),
+            ConfigSource::PackageJson(project_path.join("package.json")),
+        ] {
+            if source.path().is_file() && source.read().await?.is_some() {
+                found.push(source);
+            }
+        }
+
+        if found.len() > 1 {
+            return Err(PublishError::ConfigError(format!(
+                "Multiple configurations found: {}. Keep only one of them",
+                found
+                    .iter()
+                    .map(|source| source.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        Ok(found.pop())
+    }
+
+    /// File holding the configuration
+    pub fn path(&self) -> &Path {
+        match self {
+            ConfigSource::File(path)
+            | ConfigSource::CargoToml(path)
+            | ConfigSource::PackageJson(path) => path,
+        }
+    }
+
+    /// Content and format of the configuration (`None` when a manifest has
+    /// no publisher section)
+    pub async fn read(&self) -> Result<Option<(String, ConfigFormat)>, PublishError> {
+        let content = fs::read_to_string(self.path()).await.map_err(|e| {
+            PublishError::ConfigError(format!("Failed to read {}: {}", self.path().display(), e))
+        })?;
+        let invalid = |e: String| {
+            PublishError::ConfigError(format!("Failed to parse {}: {}", self.path().display(), e))
+        };
+
+        match self {
+            ConfigSource::File(path) => Ok(Some((content, ConfigFormat::from_path(path)))),
+            ConfigSource::CargoToml(_) => {
+                let manifest: toml::Table =
+                    toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+                let Some(table) = manifest
+                    .get("package")
+                    .and_then(|p| p.get("metadata"))
+                    .and_then(|m| m.get("publisher"))
+                else {
+                    return Ok(None);
+                };
+                let table = toml::to_string(table).map_err(|e| invalid(e.to_string()))?;
+                Ok(Some((table, ConfigFormat::Toml)))
+            }
+            ConfigSource::PackageJson(_) => {
+                let manifest: serde_json::Value =
+                    serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+                let Some(publisher) = manifest.get("publisher") else {
+                    return Ok(None);
+                };
+                let publisher =
+                    serde_json::to_string_pretty(publisher).map_err(|e| invalid(e.to_string()))?;
+                Ok(Some((publisher, ConfigFormat::Json)))
+            }
+        }
+    }
+
+    /// Whether line and column of the read content match the file
+    pub fn has_locations(&self) -> bool {
+        matches!(self, ConfigSource::File(_))
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::File(path) => write!(f, "{}", path.display()),
+            ConfigSource::CargoToml(path) => {
+                write!(f, "{} [package.metadata.publisher]", path.display())
+            }
+            ConfigSource::PackageJson(path) => write!(f, "{} (\"publisher\")", path.display()),
+        }
+    }
+}
 
 /// Environment variable pattern (${VAR_NAME})
 const ENV_VAR_PATTERN: &str = r"\$\{([A-Z_][A-Z0-9_]*)\}";
 
+/// Environment variable selecting a config profile
+pub const PROFILE_ENV_VAR: &str = "PUBLISH_PROFILE";
+
 /// Configuration load options
 #[derive(Debug, Clone)]
 pub struct ConfigLoadOptions {
@@ -27,10 +184,16 @@ pub struct ConfigLoadOptions {
 
     /// Environment variables
     pub env: HashMap<String, String>,
+
+    /// Output for loader warnings (default: terminal)
+    pub reporter: Option<Arc<dyn Reporter>>,
+
+    /// Profile to overlay (default: `PUBLISH_PROFILE` from `env`)
+    pub profile: Option<String>,
 }
 
 /// Configuration validation result
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ConfigValidationResult {
     /// Is configuration valid?
     pub valid: bool,
@@ -43,7 +206,7 @@ pub struct ConfigValidationResult {
 }
 
 /// Configuration validation error
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ConfigValidationError {
     /// Field path (e.g., "registries.npm.tag")
     pub field: String,
@@ -56,10 +219,13 @@ pub struct ConfigValidationError {
 
     /// Actual type/value
     pub actual: Option<String>,
+
+    /// Position in the config file, when validated from YAML
+    pub location: Option<ConfigLocation>,
 }
 
 /// Configuration validation warning
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ConfigValidationWarning {
     /// Field path
     pub field: String,
@@ -69,6 +235,69 @@ pub struct ConfigValidationWarning {
 
     /// Suggestion
     pub suggestion: Option<String>,
+
+    /// Position in the config file, when validated from YAML
+    pub location: Option<ConfigLocation>,
+}
+
+/// Line and column (1-based) of a key in a config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConfigLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for ConfigLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Merge `source` into `target`: objects key by key, other values replaced
+/// (absent and null values in `source` keep `target`)
+fn merge_values(target: &mut serde_json::Value, source: serde_json::Value) {
+    match (target, source) {
+        (_, serde_json::Value::Null) => {}
+        (serde_json::Value::Object(target), serde_json::Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        if !value.is_null() {
+                            target.insert(key, value);
+                        }
+                    }
+                }
+            }
+        }
+        (target, source) => *target = source,
+    }
+}
+
+/// Config layer that sets nothing, for layers built in code (environment,
+/// CLI arguments)
+pub(crate) fn empty_layer() -> PublishConfig {
+    PublishConfig {
+        version: String::new(),
+        security: None,
+        publish: None,
+        ..Default::default()
+    }
+}
+
+/// Publish options that set nothing
+pub(crate) fn empty_publish_options() -> PublishOptionsConfig {
+    PublishOptionsConfig {
+        dry_run: None,
+        confirm: None,
+        verify: None,
+        interactive: None,
+        duration_budget: None,
+        verify_timeout: None,
+        verify_interval: None,
+        retry: None,
+        command_timeout: None,
+    }
 }
 
 /// Configuration file loader
@@ -80,25 +309,41 @@ impl ConfigLoader {
     /// Priority (high to low):
     /// 1. CLI arguments
     /// 2. Environment variables
-    /// 3. Project config (./.publish-config.yaml)
-    /// 4. Global config (~/.publish-config.yaml)
-    /// 5. Default values
+    /// 3. Selected profile (`profiles.<name>` of the configs below)
+    /// 4. Project config (./.publish-config.{yaml,yml,toml,json},
+    ///    `[package.metadata.publisher]` in Cargo.toml or `publisher` in
+    ///    package.json)
+    /// 5. Global config (~/.publish-config.{yaml,yml,toml,json})
+    /// 6. Default values
     pub async fn load(options: ConfigLoadOptions) -> Result<PublishConfig, PublishError> {
+        let reporter = options.reporter.unwrap_or_else(default_reporter);
         let mut configs: Vec<PublishConfig> = Vec::new();
 
-        // 5. Default values (lowest priority)
+        // 6. Default values (lowest priority)
         configs.push(PublishConfig::default());
 
-        // 4. Global config
-        if let Some(global_config) = Self::load_global_config().await? {
+        // 5. Global config
+        if let Some(global_config) = Self::load_global_config(reporter.as_ref()).await? {
             configs.push(global_config);
         }
 
-        // 3. Project config
-        if let Some(project_config) = Self::load_project_config(&options.project_path).await? {
+        // 4. Project config
+        if let Some(project_config) =
+            Self::load_project_config(&options.project_path, reporter.as_ref()).await?
+        {
             configs.push(project_config);
         }
 
+        // 3. Selected profile, overlaid on the config files
+        let profile = options
+            .profile
+            .or_else(|| options.env.get(PROFILE_ENV_VAR).cloned())
+            .filter(|name| !name.is_empty());
+        if let Some(name) = profile {
+            let base = Self::merge_configs(std::mem::take(&mut configs));
+            configs.push(Self::apply_profile(base, &name)?);
+        }
+
         // 2. Environment variables
         if let Some(env_config) = Self::load_env_config(&options.env) {
             configs.push(env_config);
@@ -113,75 +358,123 @@ impl ConfigLoader {
         let merged_config = Self::merge_configs(configs);
 
         // Expand environment variables
-        let expanded_config = Self::expand_env_vars(merged_config, &options.env)?;
+        let expanded_config =
+            Self::expand_env_vars(merged_config, &options.env, reporter.as_ref())?;
 
-        Ok(expanded_config)
+        // Substitute user-defined variables
+        Self::substitute_variables(expanded_config, reporter.as_ref())
     }
 
-    /// Load global configuration from ~/.publish-config.yaml
-    async fn load_global_config() -> Result<Option<PublishConfig>, PublishError> {
+    /// Load global configuration from ~/.publish-config.{yaml,yml,toml,json}
+    async fn load_global_config(
+        reporter: &dyn Reporter,
+    ) -> Result<Option<PublishConfig>, PublishError> {
         let home_dir = env::var("HOME").map_err(|_| {
             PublishError::ConfigError("HOME environment variable not set".to_string())
         })?;
-        let global_config_path = PathBuf::from(home_dir).join(CONFIG_FILENAME);
+        let home_dir = PathBuf::from(home_dir);
 
-        Self::load_config_file(&global_config_path).await
+        let mut found = CONFIG_FILENAMES
+            .iter()
+            .map(|name| home_dir.join(name))
+            .filter(|path| path.is_file());
+        let Some(path) = found.next() else {
+            return Ok(None);
+        };
+        if let Some(other) = found.next() {
+            return Err(PublishError::ConfigError(format!(
+                "Multiple global configurations found: {}, {}. Keep only one of them",
+                path.display(),
+                other.display()
+            )));
+        }
+        Self::load_config_file(&ConfigSource::File(path), reporter).await
     }
 
-    /// Load project configuration from ./.publish-config.yaml
+    /// Load the project configuration
     async fn load_project_config(
         project_path: &Path,
+        reporter: &dyn Reporter,
     ) -> Result<Option<PublishConfig>, PublishError> {
-        let project_config_path = project_path.join(CONFIG_FILENAME);
-
-        Self::load_config_file(&project_config_path).await
+        match ConfigSource::find(project_path).await? {
+            Some(source) => Self::load_config_file(&source, reporter).await,
+            None => Ok(None),
+        }
     }
 
-    /// Load configuration from YAML file
-    fn load_config_file(
-        file_path: &Path,
+    /// Load configuration from a YAML, TOML or JSON source
+    ///
+    /// Keys unknown to the schema (typos) are reported as warnings.
+    fn load_config_file<'a>(
+        source: &'a ConfigSource,
+        reporter: &'a dyn Reporter,
     ) -> std::pin::Pin<
         Box<
             dyn std::future::Future<Output = Result<Option<PublishConfig>, PublishError>>
                 + Send
-                + '_,
+                + 'a,
         >,
     > {
         Box::pin(async move {
             // Check if file exists
-            if !file_path.exists() {
+            if !source.path().exists() {
                 return Ok(None);
             }
 
-            let content = fs::read_to_string(file_path).await.map_err(|e| {
-                PublishError::ConfigError(format!("Failed to read config file: {}", e))
-            })?;
+            let Some((content, format)) = source.read().await? else {
+                return Ok(None);
+            };
 
-            let config: PublishConfig = serde_yaml::from_str(&content).map_err(|e| {
-                PublishError::ConfigError(format!("Failed to parse YAML config: {}", e))
+            let mut config = format.parse(&content).map_err(|e| {
+                PublishError::ConfigError(format!("Failed to parse config {}: {}", source, e))
             })?;
+            for warning in config_schema::unknown_keys(&content, format) {
+                let location = warning
+                    .location
+                    .filter(|_| source.has_locations())
+                    .map(|l| format!(":{}", l))
+                    .unwrap_or_default();
+                reporter.warning(&format!(
+                    "⚠️  {}{}: unknown key `{}`{}",
+                    source,
+                    location,
+                    warning.field,
+                    warning
+                        .suggestion
+                        .map(|s| format!(" ({})", s))
+                        .unwrap_or_default()
+                ));
+            }
 
             // Handle extends if present
             if let Some(extends_path) = &config.extends {
-                let base_path = file_path
+                let base_path = source
+                    .path()
                     .parent()
                     .ok_or_else(|| {
                         PublishError::ConfigError("Invalid config file path".to_string())
                     })?
                     .join(extends_path);
 
-                if let Some(base_config) = Self::load_config_file(&base_path).await? {
-                    return Ok(Some(Self::merge_configs(vec![base_config, config])));
+                if let Some(base_config) =
+                    Self::load_config_file(&ConfigSource::File(base_path), reporter).await?
+                {
+                    config = Self::merge_configs(vec![base_config, config]);
                 }
             }
 
+            // Built-in preset has the lowest priority within the file
+            if let Some(preset) = &config.preset {
+                config = Self::merge_configs(vec![presets::load(preset)?, config]);
+            }
+
             Ok(Some(config))
         })
     }
 
     /// Load configuration from environment variables
     fn load_env_config(env: &HashMap<String, String>) -> Option<PublishConfig> {
-        let mut config = PublishConfig::default();
+        let mut config = empty_layer();
         let mut has_changes = false;
 
         // PUBLISH_REGISTRY -> defaultRegistry
@@ -205,7 +498,7 @@ impl ConfigLoader {
             if let Some(mode) = mode {
                 config.publish = Some(PublishOptionsConfig {
                     dry_run: Some(mode),
-                    ..Default::default()
+                    ..empty_publish_options()
                 });
                 has_changes = true;
             }
@@ -213,7 +506,7 @@ impl ConfigLoader {
 
         // PUBLISH_NON_INTERACTIVE -> publish.interactive
         if env.get("PUBLISH_NON_INTERACTIVE").map(|s| s.as_str()) == Some("true") {
-            let mut publish_config = config.publish.unwrap_or_default();
+            let mut publish_config = config.publish.unwrap_or_else(empty_publish_options);
             publish_config.interactive = Some(false);
             config.publish = Some(publish_config);
             has_changes = true;
@@ -223,7 +516,7 @@ impl ConfigLoader {
     }
 
     /// Merge multiple configurations with priority
-    fn merge_configs(configs: Vec<PublishConfig>) -> PublishConfig {
+    pub(crate) fn merge_configs(configs: Vec<PublishConfig>) -> PublishConfig {
         let mut result = PublishConfig::default();
 
         for config in configs {
@@ -234,158 +527,164 @@ impl ConfigLoader {
     }
 
     /// Merge source config into target
+    ///
+    /// Sections are merged field by field at every depth, so a project config
+    /// setting `publish.dryRun` keeps `publish.confirm` from the global config.
+    /// Maps (`registries.custom`, `variables`, headers) are merged key by key;
+    /// scalars and lists (hooks, policies, plugins) set in `source` replace
+    /// those of `target`.
     fn merge_into(target: &mut PublishConfig, source: PublishConfig) {
-        // Version
-        if !source.version.is_empty() {
-            target.version = source.version;
-        }
-
-        // Extends
-        if source.extends.is_some() {
-            target.extends = source.extends;
-        }
-
-        // Variables
-        if let Some(source_vars) = source.variables {
-            let target_vars = target.variables.get_or_insert_with(HashMap::new);
-            target_vars.extend(source_vars);
-        }
-
-        // Project
-        if let Some(source_project) = source.project {
-            let target_project = target.project.get_or_insert(ProjectConfig {
-                name: None,
-                default_registry: None,
-            });
-
-            if source_project.name.is_some() {
-                target_project.name = source_project.name;
-            }
-            if source_project.default_registry.is_some() {
-                target_project.default_registry = source_project.default_registry;
-            }
-        }
-
-        // Registries (simplified merge)
-        if source.registries.npm.is_some() {
-            target.registries.npm = source.registries.npm;
-        }
-        if source.registries.crates.is_some() {
-            target.registries.crates = source.registries.crates;
-        }
-        if source.registries.pypi.is_some() {
-            target.registries.pypi = source.registries.pypi;
-        }
-        if source.registries.homebrew.is_some() {
-            target.registries.homebrew = source.registries.homebrew;
-        }
-        if source.registries.custom.is_some() {
-            target.registries.custom = source.registries.custom;
-        }
-
-        // Security
-        if source.security.is_some() {
-            target.security = source.security;
-        }
-
-        // Hooks
-        if source.hooks.is_some() {
-            target.hooks = source.hooks;
-        }
-
-        // Publish options
-        if source.publish.is_some() {
-            target.publish = source.publish;
-        }
+        let version = if source.version.is_empty() {
+            std::mem::take(&mut target.version)
+        } else {
+            source.version.clone()
+        };
 
-        // Validation
-        if source.validation.is_some() {
-            target.validation = source.validation;
-        }
+        // Config enums are plain strings, so merging two valid configs always
+        // yields a valid one
+        let mut merged = serde_json::to_value(&*target).expect("config serializes to JSON");
+        merge_values(
+            &mut merged,
+            serde_json::to_value(source).expect("config serializes to JSON"),
+        );
+        *target = serde_json::from_value(merged).expect("merged config deserializes");
+        target.version = version;
+    }
 
-        // Notifications
-        if source.notifications.is_some() {
-            target.notifications = source.notifications;
-        }
+    /// Overlay the profile `name` on a configuration
+    pub fn apply_profile(config: PublishConfig, name: &str) -> Result<PublishConfig, PublishError> {
+        let Some(profile) = config.profiles.as_ref().and_then(|p| p.get(name)) else {
+            let available = config
+                .profiles
+                .iter()
+                .flat_map(|p| p.keys())
+                .map(|k| k.as_str())
+                .collect::<Vec<_>>();
+            return Err(PublishError::ConfigError(if available.is_empty() {
+                format!("Unknown profile `{}`: no profiles are configured", name)
+            } else {
+                format!(
+                    "Unknown profile `{}` (available: {})",
+                    name,
+                    available.join(", ")
+                )
+            }));
+        };
 
-        // Plugins
-        if source.plugins.is_some() {
-            target.plugins = source.plugins;
-        }
+        let mut merged = serde_json::to_value(&config).expect("config serializes to JSON");
+        merge_values(
+            &mut merged,
+            serde_json::to_value(profile).expect("profile serializes to JSON"),
+        );
+        serde_json::from_value(merged)
+            .map_err(|e| PublishError::ConfigError(format!("Invalid profile `{}`: {}", name, e)))
     }
 
     /// Expand environment variables in configuration
     ///
+    /// Every string value of the tree is expanded (map keys are not), except
+    /// the expansion settings themselves and hook placeholders like
+    /// `${VERSION}`.
+    ///
     /// Security features:
     /// - Only expands variables matching ${VAR_NAME} pattern
     /// - Respects allowedPrefixes if configured
     /// - Checks forbiddenPatterns if configured
     fn expand_env_vars(
-        mut config: PublishConfig,
+        config: PublishConfig,
         env: &HashMap<String, String>,
+        reporter: &dyn Reporter,
     ) -> Result<PublishConfig, PublishError> {
-        let enabled = config
+        let settings = config
             .security
             .as_ref()
-            .and_then(|s| s.env_var_expansion.as_ref())
-            .and_then(|e| e.enabled)
-            .unwrap_or(true);
-
-        if !enabled {
+            .and_then(|s| s.env_var_expansion.clone())
+            .unwrap_or_default();
+        if !settings.enabled.unwrap_or(true) {
             return Ok(config);
         }
 
-        let allowed_prefixes = config
-            .security
-            .as_ref()
-            .and_then(|s| s.env_var_expansion.as_ref())
-            .and_then(|e| e.allowed_prefixes.clone());
-
-        let forbidden_patterns: Vec<Regex> = config
-            .security
-            .as_ref()
-            .and_then(|s| s.env_var_expansion.as_ref())
-            .and_then(|e| e.forbidden_patterns.clone())
+        let allowed_prefixes = settings.allowed_prefixes;
+        let forbidden_patterns: Vec<Regex> = settings
+            .forbidden_patterns
             .unwrap_or_default()
             .iter()
             .filter_map(|p| Regex::new(p).ok())
             .collect();
 
-        // Expand variables in registries.custom (most likely to contain env vars)
-        if let Some(custom_registries) = &mut config.registries.custom {
-            for (_, custom_config) in custom_registries.iter_mut() {
-                if let Some(publish_cmd) = &custom_config.publish_command {
-                    custom_config.publish_command = Some(Self::expand_string(
-                        publish_cmd,
-                        env,
-                        &allowed_prefixes,
-                        &forbidden_patterns,
-                    )?);
+        let mut tree = serde_json::to_value(&config).expect("config serializes to JSON");
+        Self::expand_value(&mut tree, "", &mut |input| {
+            Self::expand_string(input, env, &allowed_prefixes, &forbidden_patterns, reporter)
+        })?;
+        serde_json::from_value(tree).map_err(|e| {
+            PublishError::ConfigError(format!(
+                "Invalid configuration after expanding environment variables: {}",
+                e
+            ))
+        })
+    }
+
+    /// Substitute `{{name}}` references to `variables` in every string
+    ///
+    /// Unknown names are kept as written and reported; reference cycles
+    /// between variables fail.
+    fn substitute_variables(
+        mut config: PublishConfig,
+        reporter: &dyn Reporter,
+    ) -> Result<PublishConfig, PublishError> {
+        let Some(defined) = config.variables.take() else {
+            return Ok(config);
+        };
+        let resolved = variables::resolve(&defined)?;
+
+        let mut tree = serde_json::to_value(&config).expect("config serializes to JSON");
+        Self::expand_value(&mut tree, "", &mut |input| {
+            let (result, unknown) = variables::substitute(input, &resolved);
+            for name in unknown {
+                reporter.warning(&format!("⚠️  Variable {} is not defined", name));
+            }
+            Ok(result)
+        })?;
+        let mut config: PublishConfig = serde_json::from_value(tree).map_err(|e| {
+            PublishError::ConfigError(format!(
+                "Invalid configuration after substituting variables: {}",
+                e
+            ))
+        })?;
+        config.variables = Some(resolved);
+        Ok(config)
+    }
+
+    /// Expand every string below `value` (`path` is its dotted field path)
+    fn expand_value(
+        value: &mut serde_json::Value,
+        path: &str,
+        expand: &mut dyn FnMut(&str) -> Result<String, PublishError>,
+    ) -> Result<(), PublishError> {
+        match value {
+            serde_json::Value::String(text) if text.contains("${") || text.contains("{{") => {
+                *text = expand(text)?
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    Self::expand_value(item, path, expand)?;
                 }
-                if let Some(verify_cmd) = &custom_config.verify_command {
-                    custom_config.verify_command = Some(Self::expand_string(
-                        verify_cmd,
-                        env,
-                        &allowed_prefixes,
-                        &forbidden_patterns,
-                    )?);
+            }
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    let field_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    if field_path != "security.envVarExpansion" {
+                        Self::expand_value(field, &field_path, expand)?;
+                    }
                 }
             }
+            _ => {}
         }
-
-        // Expand variables in notifications
-        if let Some(notifications) = &mut config.notifications
-            && let Some(slack) = &mut notifications.slack
-        {
-            slack.webhook_url = Self::expand_string(
-                &slack.webhook_url,
-                env,
-                &allowed_prefixes,
-                &forbidden_patterns,
-            )?;
-        }
-
-        Ok(config)
+        Ok(())
     }
 
     /// Expand environment variables in a single string
@@ -394,6 +693,7 @@ impl ConfigLoader {
         env: &HashMap<String, String>,
         allowed_prefixes: &Option<Vec<String>>,
         forbidden_patterns: &[Regex],
+        reporter: &dyn Reporter,
     ) -> Result<String, PublishError> {
         let env_var_regex = Regex::new(ENV_VAR_PATTERN).unwrap();
 
@@ -401,25 +701,28 @@ impl ConfigLoader {
         for cap in env_var_regex.captures_iter(input) {
             let var_name = &cap[1];
 
+            // Substituted by the hook runner at publish time
+            if HOOK_PLACEHOLDERS.contains(&var_name) {
+                continue;
+            }
+
             // Check forbidden patterns
-            for pattern in forbidden_patterns {
-                if pattern.is_match(var_name) {
-                    eprintln!(
-                        "⚠️  Environment variable {} matches forbidden pattern, skipping",
-                        var_name
-                    );
-                    continue;
-                }
+            if forbidden_patterns.iter().any(|p| p.is_match(var_name)) {
+                reporter.warning(&format!(
+                    "⚠️  Environment variable {} matches forbidden pattern, skipping",
+                    var_name
+                ));
+                continue;
             }
 
             // Check allowed prefixes
             if let Some(prefixes) = allowed_prefixes {
                 let allowed = prefixes.iter().any(|prefix| var_name.starts_with(prefix));
                 if !allowed {
-                    eprintln!(
+                    reporter.warning(&format!(
                         "⚠️  Environment variable {} not allowed by prefix whitelist, skipping",
                         var_name
-                    );
+                    ));
                     continue;
                 }
             }
@@ -428,7 +731,7 @@ impl ConfigLoader {
             if let Some(value) = env.get(var_name) {
                 result = result.replace(&format!("${{{}}}", var_name), value);
             } else {
-                eprintln!("⚠️  Environment variable {} not found", var_name);
+                reporter.warning(&format!("⚠️  Environment variable {} not found", var_name));
             }
         }
 
@@ -447,12 +750,14 @@ impl ConfigLoader {
                 message: "Version is required".to_string(),
                 expected: Some("string (e.g., \"1.0\")".to_string()),
                 actual: Some("empty".to_string()),
+                location: None,
             });
         } else if config.version != "1.0" {
             warnings.push(ConfigValidationWarning {
                 field: "version".to_string(),
                 message: format!("Unknown version: {}", config.version),
                 suggestion: Some("Currently supported version is \"1.0\" only".to_string()),
+                location: None,
             });
         }
 
@@ -474,6 +779,19 @@ impl ConfigLoader {
             Self::validate_publish_options(publish, &mut errors, &mut warnings);
         }
 
+        // 6. Variables must not reference each other in a cycle
+        if let Some(defined) = &config.variables
+            && let Err(e) = variables::resolve(defined)
+        {
+            errors.push(ConfigValidationError {
+                field: "variables".to_string(),
+                message: e.to_string(),
+                expected: None,
+                actual: None,
+                location: None,
+            });
+        }
+
         ConfigValidationResult {
             valid: errors.is_empty(),
             errors,
@@ -506,6 +824,7 @@ impl ConfigLoader {
                         message: "executable is required".to_string(),
                         expected: Some("string (full path)".to_string()),
                         actual: Some("empty".to_string()),
+                        location: None,
                     });
                 }
 
@@ -515,6 +834,7 @@ impl ConfigLoader {
                         message: "allowedArgs is required".to_string(),
                         expected: Some("non-empty array".to_string()),
                         actual: Some("empty array".to_string()),
+                        location: None,
                     });
                 }
             }
@@ -531,6 +851,7 @@ impl ConfigLoader {
                         message: "pathPrefix is required (path traversal protection)".to_string(),
                         expected: Some("non-empty string".to_string()),
                         actual: Some("empty".to_string()),
+                        location: None,
                     });
                 }
             }
@@ -559,6 +880,7 @@ impl ConfigLoader {
                             message: "command is required".to_string(),
                             expected: Some("non-empty string".to_string()),
                             actual: Some("empty".to_string()),
+                            location: None,
                         });
                     }
 
@@ -568,6 +890,7 @@ impl ConfigLoader {
                             message: "allowedCommands is required".to_string(),
                             expected: Some("non-empty array".to_string()),
                             actual: Some("empty array".to_string()),
+                            location: None,
                         });
                     }
                 }
@@ -597,7 +920,12 @@ impl ConfigLoader {
         if !result.errors.is_empty() {
             lines.push("\n🔴 Errors:".to_string());
             for error in &result.errors {
-                lines.push(format!("  - [{}] {}", error.field, error.message));
+                lines.push(format!(
+                    "  - [{}] {}{}",
+                    error.field,
+                    error.message,
+                    format_location(error.location)
+                ));
                 if let (Some(expected), Some(actual)) = (&error.expected, &error.actual) {
                     lines.push(format!("    Expected: {}", expected));
                     lines.push(format!("    Actual: {}", actual));
@@ -608,7 +936,12 @@ impl ConfigLoader {
         if !result.warnings.is_empty() {
             lines.push("\n🟡 Warnings:".to_string());
             for warning in &result.warnings {
-                lines.push(format!("  - [{}] {}", warning.field, warning.message));
+                lines.push(format!(
+                    "  - [{}] {}{}",
+                    warning.field,
+                    warning.message,
+                    format_location(warning.location)
+                ));
                 if let Some(suggestion) = &warning.suggestion {
                     lines.push(format!("    Suggestion: {}", suggestion));
                 }
@@ -619,9 +952,18 @@ impl ConfigLoader {
     }
 }
 
+/// " (line L, column C)" suffix of a located finding
+fn format_location(location: Option<ConfigLocation>) -> String {
+    location
+        .map(|l| format!(" (line {}, column {})", l.line, l.column))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
+    use crate::core::reporter::QuietReporter;
 
     #[tokio::test]
     async fn test_load_env_config() {
@@ -655,7 +997,7 @@ mod tests {
         env.insert("NPM_TOKEN".to_string(), "secret123".to_string());
 
         let input = "https://registry.npmjs.org/:_authToken=${NPM_TOKEN}";
-        let result = ConfigLoader::expand_string(input, &env, &None, &[]).unwrap();
+        let result = ConfigLoader::expand_string(input, &env, &None, &[], &QuietReporter).unwrap();
 
         assert_eq!(result, "https://registry.npmjs.org/:_authToken=secret123");
     }
@@ -669,12 +1011,72 @@ mod tests {
         let allowed_prefixes = Some(vec!["NPM_".to_string()]);
 
         let input = "${NPM_TOKEN}-${SECRET_KEY}";
-        let result = ConfigLoader::expand_string(input, &env, &allowed_prefixes, &[]).unwrap();
+        let result =
+            ConfigLoader::expand_string(input, &env, &allowed_prefixes, &[], &QuietReporter)
+                .unwrap();
 
         // NPM_TOKEN should be expanded, SECRET_KEY should not
         assert_eq!(result, "secret123-${SECRET_KEY}");
     }
 
+    #[test]
+    fn test_expand_env_vars_across_the_config_tree() {
+        let yaml = r#"
+version: "1.0"
+registries:
+  homebrew:
+    tap: "${CI_TAP_OWNER}/homebrew-tap"
+  npm:
+    tag: "${CI_NPM_TAG}"
+hooks:
+  preBuild:
+    - command: "make ${CI_TARGET} TOKEN=${SECRET_TOKEN}"
+      allowedCommands: ["make"]
+  postPublish:
+    - command: "git tag v${VERSION}"
+      allowedCommands: ["git"]
+notifications:
+  email:
+    recipients: ["${CI_RELEASE_MAIL}"]
+security:
+  envVarExpansion:
+    allowedPrefixes: ["CI_", "SECRET_"]
+    forbiddenPatterns: ["^SECRET_"]
+"#;
+        let config: PublishConfig = serde_yaml::from_str(yaml).unwrap();
+        let env: HashMap<String, String> = [
+            ("CI_TAP_OWNER", "acme"),
+            ("CI_NPM_TAG", "next"),
+            ("CI_TARGET", "release"),
+            ("CI_RELEASE_MAIL", "release@example.com"),
+            ("SECRET_TOKEN", "hunter2"),
+            ("VERSION", "9.9.9"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let config = ConfigLoader::expand_env_vars(config, &env, &QuietReporter).unwrap();
+        assert_eq!(
+            config.registries.homebrew.unwrap().tap.as_deref(),
+            Some("acme/homebrew-tap")
+        );
+        assert_eq!(config.registries.npm.unwrap().tag.as_deref(), Some("next"));
+        let hooks = config.hooks.unwrap();
+        assert_eq!(
+            hooks.pre_build.unwrap()[0].command,
+            "make release TOKEN=${SECRET_TOKEN}"
+        );
+        assert_eq!(
+            hooks.post_publish.unwrap()[0].command,
+            "git tag v${VERSION}"
+        );
+        assert_eq!(
+            config.notifications.unwrap().email.unwrap().recipients,
+            vec!["release@example.com"]
+        );
+    }
+
     #[test]
     fn test_validate_version_required() {
         let mut config = PublishConfig::default();
@@ -699,6 +1101,185 @@ mod tests {
         assert_eq!(result.warnings[0].field, "version");
     }
 
+    #[test]
+    fn test_substitute_variables_across_the_config_tree() {
+        let config: PublishConfig = serde_yaml::from_str(
+            r#"
+version: "1.0"
+variables:
+  org: acme
+  tap: "{{org}}/homebrew-tap"
+  channel: next
+registries:
+  homebrew:
+    tap: "{{ tap }}"
+  npm:
+    tag: "{{channel}}"
+hooks:
+  postPublish:
+    - command: "gh release edit v${VERSION} --repo {{org}}/cli"
+      allowedCommands: ["gh"]
+notifications:
+  templates:
+    success: "{{org}}: {package}@{version} published ({{unknown}})"
+"#,
+        )
+        .unwrap();
+
+        let config = ConfigLoader::substitute_variables(config, &QuietReporter).unwrap();
+        assert_eq!(
+            config.registries.homebrew.unwrap().tap.as_deref(),
+            Some("acme/homebrew-tap")
+        );
+        assert_eq!(config.registries.npm.unwrap().tag.as_deref(), Some("next"));
+        assert_eq!(
+            config.hooks.unwrap().post_publish.unwrap()[0].command,
+            "gh release edit v${VERSION} --repo acme/cli"
+        );
+        assert_eq!(
+            config
+                .notifications
+                .unwrap()
+                .templates
+                .unwrap()
+                .success
+                .as_deref(),
+            Some("acme: {package}@{version} published ({{unknown}})")
+        );
+        assert_eq!(config.variables.unwrap()["tap"], "acme/homebrew-tap");
+
+        let cyclic: PublishConfig = serde_yaml::from_str(
+            "version: \"1.0\"\nregistries: {}\nvariables:\n  a: \"{{b}}\"\n  b: \"{{a}}\"\n",
+        )
+        .unwrap();
+        assert!(!ConfigLoader::validate(&cyclic).valid);
+        assert!(ConfigLoader::substitute_variables(cyclic, &QuietReporter).is_err());
+    }
+
+    #[test]
+    fn test_apply_profile_overlays_the_base_config() {
+        let config: PublishConfig = serde_yaml::from_str(
+            r#"
+version: "1.0"
+registries:
+  npm:
+    tag: latest
+    access: public
+  pypi:
+    repository: pypi
+profiles:
+  staging:
+    registries:
+      npm:
+        tag: next
+      pypi:
+        repository: testpypi
+    publish:
+      confirm: false
+  production: {}
+"#,
+        )
+        .unwrap();
+
+        let staging = ConfigLoader::apply_profile(config.clone(), "staging").unwrap();
+        let npm = staging.registries.npm.unwrap();
+        assert_eq!(npm.tag.as_deref(), Some("next"));
+        assert_eq!(npm.access, Some(NPMAccess::Public));
+        assert_eq!(
+            staging.registries.pypi.unwrap().repository,
+            Some(PyPIRepository::Testpypi)
+        );
+        assert_eq!(staging.publish.unwrap().confirm, Some(false));
+
+        let production = ConfigLoader::apply_profile(config.clone(), "production").unwrap();
+        assert_eq!(production.registries, config.registries);
+
+        let error = ConfigLoader::apply_profile(config, "dev")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Unknown profile `dev` (available: production, staging)"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_global_project_env_layers_merge_field_by_field() {
+        let global: PublishConfig = serde_yaml::from_str(
+            r#"
+version: "1.0"
+registries:
+  npm:
+    tag: next
+    access: public
+  custom:
+    internal:
+      type: command
+      config: {}
+      publishCommand: "make publish"
+publish:
+  confirm: false
+security:
+  secretsScanning:
+    historyDepth: 5
+hooks:
+  preBuild:
+    - command: "npm test"
+      allowedCommands: ["npm"]
+"#,
+        )
+        .unwrap();
+        let project: PublishConfig = serde_yaml::from_str(
+            r#"
+version: "1.0"
+registries:
+  npm:
+    tag: latest
+  custom:
+    mirror:
+      type: command
+      config: {}
+      publishCommand: "make mirror"
+publish:
+  dryRun: always
+security:
+  secretsScanning:
+    enabled: false
+"#,
+        )
+        .unwrap();
+        let env = HashMap::from([("PUBLISH_NON_INTERACTIVE".to_string(), "true".to_string())]);
+        let env_config = ConfigLoader::load_env_config(&env).unwrap();
+
+        let config = ConfigLoader::merge_configs(vec![
+            PublishConfig::default(),
+            global,
+            project,
+            env_config,
+        ]);
+
+        let publish = config.publish.unwrap();
+        assert_eq!(publish.confirm, Some(false));
+        assert_eq!(publish.dry_run, Some(DryRunMode::Always));
+        assert_eq!(publish.interactive, Some(false));
+        assert_eq!(publish.verify, Some(true));
+
+        let npm = config.registries.npm.unwrap();
+        assert_eq!(npm.tag.as_deref(), Some("latest"));
+        assert_eq!(npm.access, Some(NPMAccess::Public));
+        let custom = config.registries.custom.unwrap();
+        assert!(custom.contains_key("internal") && custom.contains_key("mirror"));
+
+        let scanning = config.security.unwrap().secrets_scanning.unwrap();
+        assert_eq!(scanning.enabled, Some(false));
+        assert_eq!(scanning.history_depth, Some(5));
+        assert_eq!(scanning.reject_traversal, Some(true));
+
+        assert_eq!(config.hooks.unwrap().pre_build.unwrap().len(), 1);
+        assert_eq!(config.version, "1.0");
+    }
+
     #[test]
     fn test_merge_configs() {
         let config1 = PublishConfig {
@@ -709,6 +1290,8 @@ mod tests {
                     tag: Some("latest".to_string()),
                     access: None,
                     otp: None,
+                    api_report: None,
+                    bundle_size: None,
                 }),
                 ..Default::default()
             },
@@ -723,6 +1306,8 @@ mod tests {
                     tag: Some("beta".to_string()), // Override
                     access: Some(NPMAccess::Public),
                     otp: None,
+                    api_report: None,
+                    bundle_size: None,
                 }),
                 ..Default::default()
             },
@@ -748,6 +1333,105 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_config_sources_by_format_and_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert_eq!(ConfigSource::find(project).await.unwrap(), None);
+
+        // A manifest without a publisher section is not a configuration
+        std::fs::write(
+            project.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(ConfigSource::find(project).await.unwrap(), None);
+
+        std::fs::write(
+            project.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[package.metadata.publisher]\nversion = \"1.0\"\n\n[package.metadata.publisher.registries.crates]\nenabled = true\n",
+        )
+        .unwrap();
+        let source = ConfigSource::find(project).await.unwrap().unwrap();
+        assert_eq!(source, ConfigSource::CargoToml(project.join("Cargo.toml")));
+        let config = ConfigLoader::load_project_config(project, &QuietReporter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.registries.crates.unwrap().enabled, Some(true));
+
+        std::fs::write(
+            project.join("package.json"),
+            r#"{"name": "demo", "publisher": {"version": "1.0", "registries": {"npm": {"tag": "next"}}}}"#,
+        )
+        .unwrap();
+        let error = ConfigSource::find(project).await.unwrap_err().to_string();
+        assert!(error.contains("Multiple configurations found"), "{}", error);
+        assert!(error.contains("[package.metadata.publisher]"));
+        assert!(error.contains("package.json"));
+
+        std::fs::remove_file(project.join("Cargo.toml")).unwrap();
+        let config = ConfigLoader::load_project_config(project, &QuietReporter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.registries.npm.unwrap().tag.as_deref(), Some("next"));
+
+        std::fs::remove_file(project.join("package.json")).unwrap();
+        std::fs::write(
+            project.join(".publish-config.toml"),
+            "version = \"1.0\"\n\n[publish]\ndryRun = \"always\"\n\n[registries]\n",
+        )
+        .unwrap();
+        let config = ConfigLoader::load_project_config(project, &QuietReporter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.publish.unwrap().dry_run, Some(DryRunMode::Always));
+    }
+
+    #[tokio::test]
+    async fn test_load_config_file_with_preset() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILENAME);
+        std::fs::write(
+            &path,
+            "version: \"1.0\"\npreset: rust-cli\nregistries:\n  homebrew:\n    tap: \"me/tap\"\npublish:\n  dryRun: always\n",
+        )
+        .unwrap();
+
+        let config =
+            ConfigLoader::load_config_file(&ConfigSource::File(path.clone()), &QuietReporter)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(config.preset.as_deref(), Some("rust-cli"));
+        // From the preset
+        assert!(config.registries.crates.is_some());
+        assert!(config.security.is_some());
+        // Overridden by the project
+        assert_eq!(config.publish.unwrap().dry_run, Some(DryRunMode::Always));
+        assert_eq!(
+            config.registries.homebrew.unwrap().tap.as_deref(),
+            Some("me/tap")
+        );
+
+        std::fs::write(
+            &path,
+            "version: \"1.0\"\npreset: go-module\nregistries: {}\n",
+        )
+        .unwrap();
+        let error =
+            ConfigLoader::load_config_file(&ConfigSource::File(path.clone()), &QuietReporter)
+                .await
+                .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("rust-cli, ts-library, python-package")
+        );
+    }
+
     #[test]
     fn test_format_validation_result() {
         let result = ConfigValidationResult {
@@ -757,11 +1441,13 @@ mod tests {
                 message: "Version is required".to_string(),
                 expected: Some("string".to_string()),
                 actual: Some("empty".to_string()),
+                location: None,
             }],
             warnings: vec![ConfigValidationWarning {
                 field: "registries.npm".to_string(),
                 message: "npm configuration is recommended".to_string(),
                 suggestion: Some("Add npm registry config".to_string()),
+                location: None,
             }],
         };
 
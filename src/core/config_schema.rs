@@ -0,0 +1,614 @@
+//! JSON Schema of `.publish-config.yaml`
+//!
+//! The schema is generated from [`PublishConfig`] and shipped as
+//! `schema/publish-config.schema.json` for editor completion. [`validate`]
+//! checks a raw config file against it before deserializing, so that typos
+//! like `registires:` are reported with their line and column instead of
+//! silently falling back to defaults.
+
+use super::config::PublishConfig;
+use super::config_loader::{
+    ConfigFormat, ConfigLoader, ConfigLocation, ConfigValidationError, ConfigValidationResult,
+    ConfigValidationWarning,
+};
+use serde_json::Value as Schema;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+
+/// JSON Schema of [`PublishConfig`]
+pub fn config_schema() -> Schema {
+    serde_json::to_value(schemars::schema_for!(PublishConfig))
+        .expect("the config schema serializes to JSON")
+}
+
+/// Validate a raw YAML config against the schema
+pub fn validate_yaml(content: &str) -> ConfigValidationResult {
+    validate(content, ConfigFormat::Yaml)
+}
+
+/// Validate a raw config against the schema
+///
+/// Type and value errors fail the result, unknown keys are warnings with a
+/// suggestion for likely typos. A config matching the schema is also checked
+/// with [`ConfigLoader::validate`]. Findings in YAML and JSON carry the
+/// location of their key; TOML only locates syntax errors.
+pub fn validate(content: &str, format: ConfigFormat) -> ConfigValidationResult {
+    match format {
+        // JSON is valid YAML, so both are located the same way
+        ConfigFormat::Yaml | ConfigFormat::Json => match serde_yaml::from_str(content) {
+            Ok(value) => validate_value(value, &YamlLocator::locate(content)),
+            Err(e) => parse_failure(&e),
+        },
+        ConfigFormat::Toml => match toml::from_str::<toml::Value>(content) {
+            Ok(value) => match serde_yaml::to_value(value) {
+                Ok(value) => validate_value(value, &YamlLocator::default()),
+                Err(e) => parse_failure(&e),
+            },
+            Err(e) => toml_failure(content, &e),
+        },
+    }
+}
+
+/// Validate a parsed config against the schema
+fn validate_value(value: Value, locations: &YamlLocator) -> ConfigValidationResult {
+    let schema = config_schema();
+    let mut validator = SchemaValidator {
+        root: &schema,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+    validator.check(&schema, &value, "");
+    let SchemaValidator {
+        mut errors,
+        mut warnings,
+        ..
+    } = validator;
+
+    if errors.is_empty() {
+        match serde_yaml::from_value::<PublishConfig>(value) {
+            Ok(config) => {
+                let semantic = ConfigLoader::validate(&config);
+                errors.extend(semantic.errors);
+                warnings.extend(semantic.warnings);
+            }
+            Err(e) => errors.extend(parse_failure(&e).errors),
+        }
+    }
+
+    for error in &mut errors {
+        if error.location.is_none() {
+            error.location = locations.find(&error.field);
+        }
+    }
+    for warning in &mut warnings {
+        if warning.location.is_none() {
+            warning.location = locations.find(&warning.field);
+        }
+    }
+
+    ConfigValidationResult {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+/// Unknown keys of a raw config, as located warnings
+///
+/// Used when loading a config: type errors already fail deserialization, but
+/// unknown keys would be ignored without a word.
+pub fn unknown_keys(content: &str, format: ConfigFormat) -> Vec<ConfigValidationWarning> {
+    validate(content, format)
+        .warnings
+        .into_iter()
+        .filter(|w| w.message == UNKNOWN_KEY)
+        .collect()
+}
+
+/// Message of unknown-key warnings
+const UNKNOWN_KEY: &str = "Unknown key";
+
+/// Result of a YAML syntax or deserialization error
+fn parse_failure(error: &serde_yaml::Error) -> ConfigValidationResult {
+    ConfigValidationResult {
+        valid: false,
+        errors: vec![ConfigValidationError {
+            field: String::new(),
+            message: error.to_string(),
+            expected: None,
+            actual: None,
+            location: error.location().map(|l| ConfigLocation {
+                line: l.line(),
+                column: l.column(),
+            }),
+        }],
+        warnings: Vec::new(),
+    }
+}
+
+/// Result of a TOML syntax error, located by its byte span
+fn toml_failure(content: &str, error: &toml::de::Error) -> ConfigValidationResult {
+    let location = error.span().map(|span| {
+        let before = &content[..span.start.min(content.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ConfigLocation {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    });
+    ConfigValidationResult {
+        valid: false,
+        errors: vec![ConfigValidationError {
+            field: String::new(),
+            message: error.message().to_string(),
+            expected: None,
+            actual: None,
+            location,
+        }],
+        warnings: Vec::new(),
+    }
+}
+
+/// Walks a YAML value along the generated schema
+struct SchemaValidator<'a> {
+    root: &'a Schema,
+    errors: Vec<ConfigValidationError>,
+    warnings: Vec<ConfigValidationWarning>,
+}
+
+impl<'a> SchemaValidator<'a> {
+    fn check(&mut self, schema: &Schema, value: &Value, path: &str) {
+        let schema = self.resolve(schema);
+        if let Value::Tagged(tagged) = value {
+            return self.check(schema, &tagged.value, path);
+        }
+
+        if let Some(branches) = schema.get("anyOf").and_then(Schema::as_array) {
+            return self.check_any_of(branches, value, path);
+        }
+
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Schema::String(t) => vec![t.as_str()],
+                Schema::Array(ts) => ts.iter().filter_map(Schema::as_str).collect(),
+                _ => Vec::new(),
+            };
+            let actual = type_name(value);
+            let matches = types
+                .iter()
+                .any(|t| *t == actual || (*t == "number" && actual == "integer"));
+            if !types.is_empty() && !matches {
+                self.errors.push(ConfigValidationError {
+                    field: path.to_string(),
+                    message: "Invalid type".to_string(),
+                    expected: Some(types.join(" or ")),
+                    actual: Some(describe(value)),
+                    location: None,
+                });
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Schema::as_array) {
+            let json = serde_json::to_value(value).unwrap_or(Schema::Null);
+            if !allowed.contains(&json) {
+                self.errors.push(ConfigValidationError {
+                    field: path.to_string(),
+                    message: "Invalid value".to_string(),
+                    expected: Some(
+                        allowed
+                            .iter()
+                            .map(Schema::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                    actual: Some(describe(value)),
+                    location: None,
+                });
+                return;
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            let below = schema
+                .get("minimum")
+                .and_then(Schema::as_f64)
+                .is_some_and(|min| number < min);
+            let above = schema
+                .get("maximum")
+                .and_then(Schema::as_f64)
+                .is_some_and(|max| number > max);
+            if below || above {
+                let bound = |key: &str| schema.get(key).map(Schema::to_string);
+                self.errors.push(ConfigValidationError {
+                    field: path.to_string(),
+                    message: "Out of range".to_string(),
+                    expected: Some(format!(
+                        "{}..{}",
+                        bound("minimum").unwrap_or_default(),
+                        bound("maximum").unwrap_or_default()
+                    )),
+                    actual: Some(describe(value)),
+                    location: None,
+                });
+                return;
+            }
+        }
+
+        match value {
+            Value::Mapping(mapping) => self.check_mapping(schema, mapping, path),
+            Value::Sequence(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.check(item_schema, item, &format!("{}[{}]", path, index));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Options (`T` or null) and other unions: the first matching branch wins,
+    /// otherwise the findings of the closest one are reported
+    fn check_any_of(&mut self, branches: &[Schema], value: &Value, path: &str) {
+        let mut best: Option<(bool, SchemaValidator)> = None;
+        for branch in branches {
+            let mut validator = SchemaValidator {
+                root: self.root,
+                errors: Vec::new(),
+                warnings: Vec::new(),
+            };
+            validator.check(branch, value, path);
+            if validator.errors.is_empty() {
+                self.warnings.extend(validator.warnings);
+                return;
+            }
+            // A branch of the wrong type explains less than one that matched
+            // the type but failed deeper
+            let type_mismatch = validator.errors.len() == 1
+                && validator.errors[0].field == path
+                && validator.errors[0].message == "Invalid type";
+            let closer = best.as_ref().is_none_or(|(best_mismatch, best)| {
+                (type_mismatch, validator.errors.len()) < (*best_mismatch, best.errors.len())
+            });
+            if closer {
+                best = Some((type_mismatch, validator));
+            }
+        }
+        if let Some((_, validator)) = best {
+            self.errors.extend(validator.errors);
+            self.warnings.extend(validator.warnings);
+        }
+    }
+
+    fn check_mapping(&mut self, schema: &Schema, mapping: &serde_yaml::Mapping, path: &str) {
+        let properties = schema.get("properties").and_then(Schema::as_object);
+        let additional = schema.get("additionalProperties");
+
+        for (key, value) in mapping {
+            let key = key_name(key);
+            let child = join(path, &key);
+            if let Some(property) = properties.and_then(|p| p.get(&key)) {
+                self.check(property, value, &child);
+            } else if let Some(additional) = additional.filter(|a| a.is_object()) {
+                self.check(additional, value, &child);
+            } else if additional == Some(&Schema::Bool(false)) {
+                self.errors.push(ConfigValidationError {
+                    field: child,
+                    message: "Key is not allowed here".to_string(),
+                    expected: None,
+                    actual: None,
+                    location: None,
+                });
+            } else if let Some(properties) = properties {
+                self.warnings.push(ConfigValidationWarning {
+                    field: child,
+                    message: UNKNOWN_KEY.to_string(),
+                    suggestion: closest(&key, properties.keys())
+                        .map(|known| format!("Did you mean `{}`?", known)),
+                    location: None,
+                });
+            }
+        }
+
+        for required in schema
+            .get("required")
+            .and_then(Schema::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Schema::as_str)
+        {
+            if !mapping.contains_key(required) {
+                self.errors.push(ConfigValidationError {
+                    field: join(path, required),
+                    message: "Missing required key".to_string(),
+                    expected: None,
+                    actual: None,
+                    location: None,
+                });
+            }
+        }
+    }
+
+    /// Follow `$ref`s into `$defs`
+    fn resolve<'s>(&self, schema: &'s Schema) -> &'s Schema
+    where
+        'a: 's,
+    {
+        let mut schema = schema;
+        while let Some(name) = schema
+            .get("$ref")
+            .and_then(Schema::as_str)
+            .and_then(|r| r.strip_prefix("#/$defs/"))
+        {
+            match self.root.get("$defs").and_then(|defs| defs.get(name)) {
+                Some(definition) => schema = definition,
+                None => break,
+            }
+        }
+        schema
+    }
+}
+
+/// Field path of a key below `path`
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Mapping keys as strings (numbers and booleans are stringified)
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// JSON Schema type of a YAML value
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Sequence(_) => "array",
+        Value::Mapping(_) => "object",
+        Value::Tagged(tagged) => type_name(&tagged.value),
+    }
+}
+
+/// Short description of a value for error messages
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Bool(b) => format!("boolean {}", b),
+        Value::Number(n) => format!("number {}", n),
+        Value::String(s) => format!("string \"{}\"", s),
+        other => type_name(other).to_string(),
+    }
+}
+
+/// Known key closest to a misspelled one, if it is likely a typo
+fn closest<'k>(key: &str, known: impl Iterator<Item = &'k String>) -> Option<&'k str> {
+    let lowered = key.to_lowercase();
+    known
+        .map(|k| (k, edit_distance(&lowered, &k.to_lowercase())))
+        .filter(|(k, distance)| *distance <= (k.len() / 3).max(1))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(k, _)| k.as_str())
+}
+
+/// Levenshtein distance, counting an adjacent transposition as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Positions of the keys and items of a YAML document, by field path
+#[derive(Debug, Default)]
+struct YamlLocator {
+    locations: HashMap<String, ConfigLocation>,
+    stack: Vec<Frame>,
+}
+
+/// Open mapping or sequence while locating
+#[derive(Debug)]
+enum Frame {
+    Mapping { path: String, key: Option<String> },
+    Sequence { path: String, index: usize },
+}
+
+impl YamlLocator {
+    /// Locate a document; a document with syntax errors locates what was
+    /// parsed before the error
+    fn locate(content: &str) -> Self {
+        let mut locator = Self::default();
+        let _ = Parser::new_from_str(content).load(&mut locator, false);
+        locator
+    }
+
+    /// Location of a field, or of its closest located parent
+    fn find(&self, field: &str) -> Option<ConfigLocation> {
+        let mut field = field;
+        loop {
+            if let Some(location) = self.locations.get(field) {
+                return Some(*location);
+            }
+            field = &field[..field.rfind(['.', '['])?];
+        }
+    }
+
+    /// Path of the value starting at `mark`, advancing the parent frame
+    fn enter_value(&mut self, mark: Marker) -> String {
+        let location = ConfigLocation {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+        match self.stack.last_mut() {
+            Some(Frame::Mapping { path, key }) => match key.take() {
+                Some(key) => join(path, &key),
+                None => String::new(),
+            },
+            Some(Frame::Sequence { path, index }) => {
+                let item = format!("{}[{}]", path, index);
+                *index += 1;
+                self.locations.insert(item.clone(), location);
+                item
+            }
+            None => String::new(),
+        }
+    }
+}
+
+impl MarkedEventReceiver for YamlLocator {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => {
+                if let Some(Frame::Mapping {
+                    path,
+                    key: key @ None,
+                }) = self.stack.last_mut()
+                {
+                    let field = join(path, &value);
+                    self.locations.insert(
+                        field,
+                        ConfigLocation {
+                            line: mark.line(),
+                            column: mark.col() + 1,
+                        },
+                    );
+                    *key = Some(value);
+                } else {
+                    self.enter_value(mark);
+                }
+            }
+            Event::Alias(_) => {
+                self.enter_value(mark);
+            }
+            Event::MappingStart(..) => {
+                let path = self.enter_value(mark);
+                self.stack.push(Frame::Mapping { path, key: None });
+            }
+            Event::SequenceStart(..) => {
+                let path = self.enter_value(mark);
+                self.stack.push(Frame::Sequence { path, index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typos_and_type_errors_are_located() {
+        let yaml = "version: \"1.0\"\nregistries:\n  npm:\n    tag: latest\n    acess: public\n    enabled: \"yes\"\nregistires:\n  crates:\n    enabled: true\n";
+        let result = validate_yaml(yaml);
+        assert!(!result.valid);
+
+        let typo = result
+            .warnings
+            .iter()
+            .find(|w| w.field == "registires")
+            .unwrap();
+        assert_eq!(
+            typo.suggestion.as_deref(),
+            Some("Did you mean `registries`?")
+        );
+        assert_eq!(typo.location, Some(ConfigLocation { line: 7, column: 1 }));
+        let nested = result
+            .warnings
+            .iter()
+            .find(|w| w.field == "registries.npm.acess")
+            .unwrap();
+        assert_eq!(nested.suggestion.as_deref(), Some("Did you mean `access`?"));
+        assert_eq!(nested.location, Some(ConfigLocation { line: 5, column: 5 }));
+
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.field, "registries.npm.enabled");
+        assert_eq!(error.expected.as_deref(), Some("boolean or null"));
+        assert_eq!(error.location, Some(ConfigLocation { line: 6, column: 5 }));
+    }
+
+    #[test]
+    fn test_valid_config_and_syntax_errors() {
+        let result = validate_yaml(
+            "version: \"1.0\"\nregistries:\n  npm:\n    access: public\nhooks:\n  preBuild:\n    - command: \"\"\n      allowedCommands: [npm]\n",
+        );
+        assert!(!result.valid);
+        assert_eq!(result.errors[0].field, "hooks.preBuild[0].command");
+        assert_eq!(
+            result.errors[0].location,
+            Some(ConfigLocation { line: 7, column: 7 })
+        );
+
+        let result = validate_yaml("version: \"1.0\"\nregistries: {}\n");
+        assert!(result.valid, "{:?}", result.errors);
+        assert!(result.warnings.is_empty());
+
+        let result = validate_yaml("version: \"1.0\"\nregistries:\n  npm: [\n");
+        assert!(!result.valid);
+        assert!(result.errors[0].location.is_some());
+    }
+
+    #[test]
+    fn test_toml_and_json_configs() {
+        let result = validate(
+            "version = \"1.0\"\n\n[registries.npm]\nacess = \"public\"\n",
+            ConfigFormat::Toml,
+        );
+        assert!(result.valid, "{:?}", result.errors);
+        assert_eq!(result.warnings[0].field, "registries.npm.acess");
+        assert_eq!(result.warnings[0].location, None);
+
+        let result = validate("version = \"1.0\"\nregistries = [\n", ConfigFormat::Toml);
+        assert!(!result.valid);
+        assert_eq!(result.errors[0].location.unwrap().line, 3);
+
+        let result = validate(
+            "{\n  \"version\": \"1.0\",\n  \"registries\": {\"npm\": {\"enabled\": 1}}\n}\n",
+            ConfigFormat::Json,
+        );
+        assert_eq!(result.errors[0].field, "registries.npm.enabled");
+        assert_eq!(result.errors[0].location.unwrap().line, 3);
+    }
+
+    #[test]
+    fn test_shipped_schema_is_up_to_date() {
+        let shipped: Schema =
+            serde_json::from_str(include_str!("../../schema/publish-config.schema.json")).unwrap();
+        assert!(
+            shipped == config_schema(),
+            "schema/publish-config.schema.json is outdated; regenerate it with \
+             `package-publisher config schema > schema/publish-config.schema.json`"
+        );
+    }
+}
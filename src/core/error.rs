@@ -1,78 +1,125 @@
 //! Error handling for package publishing
 //!
 //! This module provides comprehensive error types with recovery guidance
-//! using the thiserror crate for ergonomic error handling.
+//! using the thiserror crate for ergonomic error handling. Messages and
+//! suggested actions are localized (see [`crate::core::i18n`]).
 
+use super::retry::is_transient_error;
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
 /// Main error type for package publishing operations
 #[derive(Error, Debug)]
 pub enum PublishError {
     // Detection errors
-    #[error("[{registry}] レジストリが検出されませんでした")]
     RegistryNotDetected { registry: String },
 
     // Validation errors
-    #[error("[{registry}] パッケージの検証に失敗しました")]
     ValidationFailed { registry: String },
 
-    #[error("[{registry}] 無効なバージョン番号です")]
     InvalidVersion { registry: String },
 
-    #[error("[{registry}] 必須のメタデータが不足しています")]
     MissingMetadata { registry: String },
 
     // Security errors
-    #[error("[{registry}] ハードコードされた機密情報が検出されました")]
     SecretsDetected { registry: String },
 
-    #[error("[{registry}] 認証トークンが設定されていません")]
     TokenMissing { registry: String },
 
-    #[error("[{registry}] 認証に失敗しました")]
     AuthenticationFailed { registry: String },
 
     // Publishing errors
-    #[error("[{registry}] 公開処理に失敗しました: {message}")]
     PublishFailed { registry: String, message: String },
 
-    #[error("[{registry}] 同じバージョンが既に公開されています")]
     VersionConflict { registry: String },
 
-    #[error("[{registry}] 2要素認証が必要です")]
     OtpRequired { registry: String },
 
     // Network errors
-    #[error("[{registry}] ネットワークエラーが発生しました: {message}")]
     NetworkError { registry: String, message: String },
 
-    #[error("[{registry}] タイムアウトしました")]
     TimeoutError { registry: String },
 
     // Verification errors
-    #[error("[{registry}] 公開の検証に失敗しました")]
     VerificationFailed { registry: String },
 
     // State errors
-    #[error("[{registry}] 状態ファイルが破損しています")]
     StateCorrupted { registry: String },
 
     // Rollback errors
-    #[error("[{registry}] ロールバックに失敗しました")]
     RollbackFailed { registry: String },
 
-    #[error("[{registry}] このレジストリはロールバックをサポートしていません")]
     RollbackNotSupported { registry: String },
 
     // Command execution errors
-    #[error("[{registry}] コマンド実行エラー: {message}")]
     CommandError { registry: String, message: String },
 
     // Configuration errors
-    #[error("設定エラー: {0}")]
     ConfigError(String),
 }
 
+impl fmt::Display for PublishError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::RegistryNotDetected { registry } => {
+                tr!("error.registry_not_detected", registry = registry)
+            }
+            Self::ValidationFailed { registry } => {
+                tr!("error.validation_failed", registry = registry)
+            }
+            Self::InvalidVersion { registry } => tr!("error.invalid_version", registry = registry),
+            Self::MissingMetadata { registry } => {
+                tr!("error.missing_metadata", registry = registry)
+            }
+            Self::SecretsDetected { registry } => {
+                tr!("error.secrets_detected", registry = registry)
+            }
+            Self::TokenMissing { registry } => tr!("error.token_missing", registry = registry),
+            Self::AuthenticationFailed { registry } => {
+                tr!("error.authentication_failed", registry = registry)
+            }
+            Self::PublishFailed { registry, message } => {
+                tr!(
+                    "error.publish_failed",
+                    registry = registry,
+                    message = message
+                )
+            }
+            Self::VersionConflict { registry } => {
+                tr!("error.version_conflict", registry = registry)
+            }
+            Self::OtpRequired { registry } => tr!("error.otp_required", registry = registry),
+            Self::NetworkError { registry, message } => {
+                tr!(
+                    "error.network_error",
+                    registry = registry,
+                    message = message
+                )
+            }
+            Self::TimeoutError { registry } => tr!("error.timeout", registry = registry),
+            Self::VerificationFailed { registry } => {
+                tr!("error.verification_failed", registry = registry)
+            }
+            Self::StateCorrupted { registry } => tr!("error.state_corrupted", registry = registry),
+            Self::RollbackFailed { registry } => tr!("error.rollback_failed", registry = registry),
+            Self::RollbackNotSupported { registry } => {
+                tr!("error.rollback_not_supported", registry = registry)
+            }
+            Self::CommandError { registry, message } => {
+                tr!(
+                    "error.command_error",
+                    registry = registry,
+                    message = message
+                )
+            }
+            Self::ConfigError(message) => tr!("error.config", message = message),
+        };
+        f.write_str(&message)
+    }
+}
+
 impl PublishError {
     /// Get the registry name associated with this error
     pub fn registry(&self) -> &str {
@@ -109,80 +156,65 @@ impl PublishError {
     }
 
     /// Get suggested actions for this error
-    pub fn suggested_actions(&self) -> Vec<&'static str> {
-        match self {
-            Self::RegistryNotDetected { .. } => vec![
-                "プロジェクトディレクトリを確認してください",
-                "対応するパッケージマネージャーがインストールされているか確認してください",
+    pub fn suggested_actions(&self) -> Vec<String> {
+        let keys: &[&str] = match self {
+            Self::RegistryNotDetected { .. } => &[
+                "error.action.check_project_dir",
+                "error.action.check_package_manager",
             ],
-            Self::ValidationFailed { .. } => {
-                vec![
-                    "検証エラーを確認してください",
-                    "メタデータを修正してください",
-                ]
-            }
-            Self::InvalidVersion { .. } => {
-                vec!["SemVer形式（例: 1.0.0）で指定してください"]
-            }
-            Self::MissingMetadata { .. } => {
-                vec!["package.json/Cargo.toml等を確認してください"]
-            }
-            Self::SecretsDetected { .. } => vec![
-                "検出されたファイルを確認してください",
-                "環境変数の使用を推奨します",
-                ".gitignoreに追加してください",
+            Self::ValidationFailed { .. } => &[
+                "error.action.check_validation_errors",
+                "error.action.fix_metadata",
             ],
-            Self::TokenMissing { .. } => {
-                vec!["環境変数を設定してください（例: NPM_TOKEN, CARGO_REGISTRY_TOKEN）"]
-            }
-            Self::AuthenticationFailed { .. } => vec![
-                "認証情報を確認してください",
-                "環境変数が正しく設定されているか確認してください",
-                "トークンの有効期限を確認してください",
+            Self::InvalidVersion { .. } => &["error.action.use_semver"],
+            Self::MissingMetadata { .. } => &["error.action.check_manifest"],
+            Self::SecretsDetected { .. } => &[
+                "error.action.check_detected_files",
+                "error.action.prefer_env_vars",
+                "error.action.add_to_gitignore",
             ],
-            Self::PublishFailed { .. } => vec![
-                "エラーメッセージを確認してください",
-                "ネットワーク接続を確認してください",
-                "レジストリのステータスを確認してください",
+            Self::TokenMissing { .. } => &["error.action.set_token_env"],
+            Self::AuthenticationFailed { .. } => &[
+                "error.action.check_credentials",
+                "error.action.check_env_vars",
+                "error.action.check_token_expiry",
             ],
-            Self::VersionConflict { .. } => vec![
-                "バージョン番号を更新してください",
-                "npm version patch/minor/majorを実行してください",
+            Self::PublishFailed { .. } => &[
+                "error.action.check_error_message",
+                "error.action.check_network",
+                "error.action.check_registry_status",
             ],
-            Self::OtpRequired { .. } => {
-                vec!["--otpオプションでワンタイムパスワードを指定してください"]
+            Self::VersionConflict { .. } => {
+                &["error.action.bump_version", "error.action.run_npm_version"]
+            }
+            Self::OtpRequired { .. } => &["error.action.pass_otp"],
+            Self::NetworkError { .. } => {
+                &["error.action.check_internet", "error.action.retry_later"]
             }
-            Self::NetworkError { .. } => vec![
-                "インターネット接続を確認してください",
-                "しばらく待ってから再試行してください",
+            Self::TimeoutError { .. } => &[
+                "error.action.check_network_env",
+                "error.action.extend_timeout",
             ],
-            Self::TimeoutError { .. } => vec![
-                "ネットワーク環境を確認してください",
-                "--timeoutオプションで時間を延長できます",
+            Self::VerificationFailed { .. } => &[
+                "error.action.verify_manually",
+                "error.action.retry_propagation",
             ],
-            Self::VerificationFailed { .. } => vec![
-                "レジストリのWebサイトで手動確認してください",
-                "しばらく待ってから再試行してください（反映に時間がかかる場合があります）",
+            Self::StateCorrupted { .. } => &["error.action.delete_state"],
+            Self::RollbackFailed { .. } => &[
+                "error.action.check_registry_docs",
+                "error.action.manual_rollback",
             ],
-            Self::StateCorrupted { .. } => {
-                vec![".publish-state.jsonを削除して再試行してください"]
-            }
-            Self::RollbackFailed { .. } => vec![
-                "レジストリのドキュメントを確認してください",
-                "手動でロールバックが必要な場合があります",
+            Self::RollbackNotSupported { .. } => &["error.action.publish_fix"],
+            Self::CommandError { .. } => &[
+                "error.action.check_command_output",
+                "error.action.check_dependencies",
             ],
-            Self::RollbackNotSupported { .. } => {
-                vec!["新しいバージョンで修正版を公開してください"]
-            }
-            Self::CommandError { .. } => vec![
-                "コマンドの出力を確認してください",
-                "必要な依存関係がインストールされているか確認してください",
+            Self::ConfigError(_) => &[
+                "error.action.check_config_syntax",
+                "error.action.check_config_format",
             ],
-            Self::ConfigError(_) => vec![
-                "設定ファイルの構文を確認してください",
-                ".publish-config.yamlのフォーマットが正しいか確認してください",
-            ],
-        }
+        };
+        keys.iter().map(|key| tr!(key)).collect()
     }
 
     /// Get error code for this error
@@ -210,7 +242,160 @@ impl PublishError {
     }
 }
 
+/// Category of a failed publish, carried by [`PublishResult`] and
+/// [`PublishReport`] so that callers, analytics and exit codes can branch on
+/// it without parsing messages
+///
+/// [`PublishResult`]: crate::core::traits::PublishResult
+/// [`PublishReport`]: crate::orchestration::package_publisher::PublishReport
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PublishErrorKind {
+    /// Credentials are missing, invalid or lack permissions (including OTP)
+    AuthFailed,
+    /// The version is already published
+    VersionExists,
+    /// The registry could not be reached or did not answer in time
+    NetworkTimeout,
+    /// Pre-publish validation failed; `fields` lists the failing fields
+    ValidationFailed {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        fields: Vec<String>,
+    },
+    /// The registry rejected the request with HTTP 429
+    RegistryRateLimited,
+    /// A lifecycle hook exited unsuccessfully
+    HookFailed { stage: String, command: String },
+    /// Any other failure
+    Other,
+}
+
+impl PublishErrorKind {
+    /// Kind of `error`: the kind of a [`ClassifiedError`] or [`PublishError`]
+    /// in its chain, otherwise [`classify`](Self::classify) of its message
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| {
+                if let Some(classified) = cause.downcast_ref::<ClassifiedError>() {
+                    Some(classified.kind.clone())
+                } else {
+                    cause.downcast_ref::<PublishError>().map(Self::from)
+                }
+            })
+            .unwrap_or_else(|| Self::classify(&format!("{:#}", error)))
+    }
+
+    /// Kind of an error message from a registry or tool
+    pub fn classify(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let contains_any = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+        if contains_any(&["http 429", "too many requests", "rate limit"]) {
+            Self::RegistryRateLimited
+        } else if contains_any(&[
+            "already exists",
+            "already uploaded",
+            "already been published",
+            "cannot publish over",
+            "previously published",
+            "epublishconflict",
+        ]) {
+            Self::VersionExists
+        } else if contains_any(&[
+            "http 401",
+            "http 403",
+            "e401",
+            "e403",
+            "unauthorized",
+            "forbidden",
+            "authentication",
+            "invalid token",
+            "eotp",
+            "two-factor",
+        ]) {
+            Self::AuthFailed
+        } else if is_transient_error(message) {
+            Self::NetworkTimeout
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Error with this kind and `message`
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        ClassifiedError {
+            kind: self,
+            message: message.into(),
+        }
+        .into()
+    }
+
+    /// Process exit code of a publish that failed with this kind
+    ///
+    /// `1` stays the generic failure; clap uses `2` for usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::ValidationFailed { .. } => 3,
+            Self::AuthFailed => 4,
+            Self::VersionExists => 5,
+            Self::NetworkTimeout => 6,
+            Self::RegistryRateLimited => 7,
+            Self::HookFailed { .. } => 8,
+        }
+    }
+
+    /// Whether publishing again later may succeed without changes
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::NetworkTimeout | Self::RegistryRateLimited)
+    }
+
+    /// Stable identifier of the kind (used in analytics and metrics)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AuthFailed => "AUTH_FAILED",
+            Self::VersionExists => "VERSION_EXISTS",
+            Self::NetworkTimeout => "NETWORK_TIMEOUT",
+            Self::ValidationFailed { .. } => "VALIDATION_FAILED",
+            Self::RegistryRateLimited => "REGISTRY_RATE_LIMITED",
+            Self::HookFailed { .. } => "HOOK_FAILED",
+            Self::Other => "OTHER",
+        }
+    }
+}
+
+impl From<&PublishError> for PublishErrorKind {
+    fn from(error: &PublishError) -> Self {
+        match error {
+            PublishError::TokenMissing { .. }
+            | PublishError::AuthenticationFailed { .. }
+            | PublishError::OtpRequired { .. } => Self::AuthFailed,
+            PublishError::VersionConflict { .. } => Self::VersionExists,
+            PublishError::NetworkError { .. } | PublishError::TimeoutError { .. } => {
+                Self::NetworkTimeout
+            }
+            PublishError::ValidationFailed { .. }
+            | PublishError::InvalidVersion { .. }
+            | PublishError::MissingMetadata { .. }
+            | PublishError::SecretsDetected { .. } => Self::ValidationFailed { fields: Vec::new() },
+            PublishError::PublishFailed { message, .. } => Self::classify(message),
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Error whose [`PublishErrorKind`] is known where it is raised; displays as
+/// its message
+#[derive(Error, Debug)]
+#[error("{message}")]
+pub struct ClassifiedError {
+    pub kind: PublishErrorKind,
+    message: String,
+}
+
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests {
     use super::*;
 
@@ -261,7 +446,7 @@ mod tests {
         assert!(error.is_recoverable());
         let actions = error.suggested_actions();
         assert!(actions.len() >= 3);
-        assert!(actions.contains(&"環境変数の使用を推奨します"));
+        assert!(actions.iter().any(|a| a == "Prefer environment variables"));
     }
 
     #[test]
@@ -272,7 +457,7 @@ mod tests {
 
         assert!(error.is_recoverable());
         let actions = error.suggested_actions();
-        assert!(actions.iter().any(|&a| a.contains("環境変数")));
+        assert!(actions.iter().any(|a| a.contains("environment variable")));
     }
 
     #[test]
@@ -293,7 +478,7 @@ mod tests {
 
         assert!(error.is_recoverable());
         let actions = error.suggested_actions();
-        assert!(actions.iter().any(|&a| a.contains("バージョン番号")));
+        assert!(actions.iter().any(|a| a.contains("version number")));
     }
 
     #[test]
@@ -366,6 +551,70 @@ mod tests {
 
         let display = format!("{}", error);
         assert!(display.contains("test-registry"));
-        assert!(display.contains("検証に失敗"));
+        assert!(display.contains("validation failed"));
+    }
+
+    #[test]
+    fn test_error_kind_classification() {
+        assert_eq!(
+            PublishErrorKind::classify(
+                "npm ERR! code E403 You cannot publish over the previously published versions"
+            ),
+            PublishErrorKind::VersionExists
+        );
+        assert_eq!(
+            PublishErrorKind::classify("HTTP 429: slow down"),
+            PublishErrorKind::RegistryRateLimited
+        );
+        assert_eq!(
+            PublishErrorKind::classify("npm ERR! code E401 Unable to authenticate"),
+            PublishErrorKind::AuthFailed
+        );
+        assert_eq!(
+            PublishErrorKind::classify("connection reset by peer"),
+            PublishErrorKind::NetworkTimeout
+        );
+        assert_eq!(
+            PublishErrorKind::classify("cargo package failed"),
+            PublishErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_error_kind_of_typed_errors() {
+        let hook = PublishErrorKind::HookFailed {
+            stage: "prePublish".to_string(),
+            command: "npm test".to_string(),
+        };
+        let error = hook.clone().error("prePublish hook failed: npm test");
+        assert_eq!(error.to_string(), "prePublish hook failed: npm test");
+        assert_eq!(PublishErrorKind::of(&error), hook);
+        assert_eq!(
+            PublishErrorKind::of(&error.context("Publishing failed")),
+            hook
+        );
+
+        let error = anyhow::Error::from(PublishError::OtpRequired {
+            registry: "npm".to_string(),
+        });
+        assert_eq!(PublishErrorKind::of(&error), PublishErrorKind::AuthFailed);
+        assert_eq!(PublishErrorKind::AuthFailed.exit_code(), 4);
+        assert!(PublishErrorKind::RegistryRateLimited.is_retryable());
+    }
+
+    #[test]
+    fn test_error_kind_serialization() {
+        let kind = PublishErrorKind::ValidationFailed {
+            fields: vec!["name".to_string()],
+        };
+        let json = serde_json::to_value(&kind).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"kind": "validation_failed", "fields": ["name"]})
+        );
+        assert_eq!(
+            serde_json::from_value::<PublishErrorKind>(json).unwrap(),
+            kind
+        );
     }
 }
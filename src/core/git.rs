@@ -0,0 +1,92 @@
+//! Git commands run in a project checkout
+//!
+//! Releases, changelogs, provenance, deployments, policies, hooks, the
+//! secrets scanner and the Homebrew and AUR repositories all read from or
+//! write to git through [`run_git`] (or [`git_stdout`] for commands the
+//! caller builds itself, e.g. with a sandboxed environment).
+
+use std::path::Path;
+use std::process::{Output, Stdio};
+use tokio::process::Command;
+
+/// Run `git <args>` in `project_path`, returning its trimmed stdout
+///
+/// A failing command returns git's message: stderr, or stdout when git
+/// reported on stdout only (like `nothing to commit`).
+pub async fn run_git(project_path: &Path, args: &[&str]) -> anyhow::Result<String> {
+    git_stdout(git(project_path, args).output().await?)
+}
+
+/// [`run_git`] returning stdout as is (file contents, `-z` lists)
+pub async fn run_git_raw(project_path: &Path, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    checked(git(project_path, args).output().await?)
+}
+
+/// Trimmed stdout of a finished git command, or git's message when it
+/// failed (see [`run_git`])
+pub fn git_stdout(output: Output) -> anyhow::Result<String> {
+    let stdout = checked(output)?;
+    Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+fn git(project_path: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command
+        .args(args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    command
+}
+
+fn checked(output: Output) -> anyhow::Result<Vec<u8>> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let message = if stderr.is_empty() {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        } else {
+            stderr
+        };
+        anyhow::bail!("{}", message);
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_git() {
+        let dir = tempfile::tempdir().unwrap();
+        run_git(dir.path(), &["init", "-q"]).await.unwrap();
+
+        let inside = run_git(dir.path(), &["rev-parse", "--is-inside-work-tree"])
+            .await
+            .unwrap();
+        assert_eq!(inside, "true");
+
+        let error = run_git(dir.path(), &["rev-parse", "HEAD"])
+            .await
+            .unwrap_err();
+        assert!(!error.to_string().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_git_raw_keeps_output() {
+        let dir = tempfile::tempdir().unwrap();
+        run_git(dir.path(), &["init", "-q"]).await.unwrap();
+        std::fs::write(dir.path().join("a b.txt"), "content\n").unwrap();
+        run_git(dir.path(), &["add", "."]).await.unwrap();
+
+        let staged = run_git_raw(dir.path(), &["diff", "--cached", "--name-only", "-z"])
+            .await
+            .unwrap();
+        assert_eq!(staged, b"a b.txt\0");
+        let content = run_git_raw(dir.path(), &["show", ":a b.txt"])
+            .await
+            .unwrap();
+        assert_eq!(content, b"content\n");
+    }
+}
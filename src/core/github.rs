@@ -0,0 +1,326 @@
+//! GitHub REST API client
+//!
+//! Releases, deployments, approvals, scheduled releases, credential checks
+//! and the plugins updating GitHub-hosted registries (Homebrew taps, the
+//! Bazel Central Registry, Scoop buckets, winget, ...) talk to GitHub through
+//! [`GitHubClient`]. Each of them takes a `with_github_api_url` override for
+//! GitHub Enterprise (and tests), applied here.
+
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::tr;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+
+/// Public GitHub REST API endpoint
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// GitHub contents API entry (subset)
+#[derive(Debug, Deserialize)]
+struct ContentEntry {
+    sha: String,
+    #[serde(default)]
+    content: String,
+}
+
+/// GitHub pull request (subset)
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    html_url: String,
+}
+
+/// GitHub REST API endpoint and token
+#[derive(Clone)]
+pub struct GitHubClient {
+    api_url: String,
+    token: Option<String>,
+}
+
+impl std::fmt::Debug for GitHubClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubClient")
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for GitHubClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubClient {
+    /// Anonymous client for api.github.com
+    pub fn new() -> Self {
+        Self {
+            api_url: GITHUB_API_URL.to_string(),
+            token: None,
+        }
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Authenticate requests with `token`
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Copy of the client authenticated with `token`
+    pub fn authenticated(&self, token: &str) -> Self {
+        self.clone().with_token(token)
+    }
+
+    /// API endpoint without a trailing slash
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// Request to `path` (`/repos/...`) with the GitHub media type and token
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = http_client()
+            .request(method, format!("{}{}", self.api_url, path))
+            .header("Accept", "application/vnd.github+json");
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Send a request with an optional JSON body, waiting out rate limits
+    pub async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<Response> {
+        let mut request = self.request(method, path);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        Ok(request.send_rate_limited().await?)
+    }
+
+    /// Default branch of `repository`
+    pub async fn default_branch(&self, repository: &str) -> anyhow::Result<String> {
+        let response = self
+            .send(Method::GET, &format!("/repos/{}", repository), None)
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.fetch_status",
+                    resource = repository,
+                    status = response.status()
+                )
+            );
+        }
+        let info = response.json::<serde_json::Value>().await?;
+        Ok(info["default_branch"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string())
+    }
+
+    /// Commit `branch` of `repository` points to (`None` when it is missing)
+    pub async fn branch_sha(
+        &self,
+        repository: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let head = self
+            .send(
+                Method::GET,
+                &format!("/repos/{}/git/ref/heads/{}", repository, branch),
+                None,
+            )
+            .await?
+            .json::<serde_json::Value>()
+            .await?;
+        Ok(head["object"]["sha"].as_str().map(String::from))
+    }
+
+    /// Create `branch` at `sha`; an existing branch is reused
+    pub async fn create_branch(
+        &self,
+        repository: &str,
+        branch: &str,
+        sha: &str,
+    ) -> anyhow::Result<()> {
+        let created = self
+            .send(
+                Method::POST,
+                &format!("/repos/{}/git/refs", repository),
+                Some(serde_json::json!({ "ref": format!("refs/heads/{}", branch), "sha": sha })),
+            )
+            .await?;
+        if !created.status().is_success() && created.status() != StatusCode::UNPROCESSABLE_ENTITY {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.branch_create_failed",
+                    branch = branch,
+                    status = created.status()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// Bring `branch` of a fork up to date with its upstream repository
+    pub async fn merge_upstream(&self, fork: &str, branch: &str) -> anyhow::Result<()> {
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/repos/{}/merge-upstream", fork),
+                Some(serde_json::json!({ "branch": branch })),
+            )
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.fetch_status",
+                    resource = fork,
+                    status = response.status()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// File at `branch` as (blob sha, decoded content), `None` when missing
+    pub async fn fetch_file(
+        &self,
+        repository: &str,
+        path: &str,
+        branch: &str,
+    ) -> anyhow::Result<Option<(String, String)>> {
+        let response = self
+            .send(
+                Method::GET,
+                &format!("/repos/{}/contents/{}?ref={}", repository, path, branch),
+                None,
+            )
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.fetch_status",
+                    resource = path,
+                    status = response.status()
+                )
+            );
+        }
+        let entry = response.json::<ContentEntry>().await?;
+        let content = BASE64.decode(entry.content.replace('\n', ""))?;
+        Ok(Some((entry.sha, String::from_utf8(content)?)))
+    }
+
+    /// Create or replace `path` on `branch` with one commit
+    pub async fn put_file(
+        &self,
+        repository: &str,
+        branch: &str,
+        path: &str,
+        content: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        let mut body = serde_json::json!({
+            "message": message,
+            "content": BASE64.encode(content),
+            "branch": branch,
+        });
+        if let Some((sha, _)) = self.fetch_file(repository, path, branch).await? {
+            body["sha"] = serde_json::Value::String(sha);
+        }
+        let response = self
+            .send(
+                Method::PUT,
+                &format!("/repos/{}/contents/{}", repository, path),
+                Some(body),
+            )
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.file_write_failed",
+                    path = path,
+                    status = response.status()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// Open a pull request from `head` (`branch` or `owner:branch`) into
+    /// `base`; returns its URL
+    pub async fn open_pull_request(
+        &self,
+        repository: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/repos/{}/pulls", repository),
+                Some(serde_json::json!({
+                    "title": title,
+                    "head": head,
+                    "base": base,
+                    "body": body,
+                })),
+            )
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!("registry.pr_create_failed", status = status, body = body)
+            );
+        }
+        Ok(response.json::<PullRequest>().await?.html_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_url_override() {
+        let client = GitHubClient::new();
+        assert_eq!(client.api_url(), GITHUB_API_URL);
+
+        let client = client.with_api_url("https://github.example.com/api/v3/");
+        assert_eq!(client.api_url(), "https://github.example.com/api/v3");
+        let request = client
+            .authenticated("ghp_test")
+            .request(Method::GET, "/repos/me/tool")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://github.example.com/api/v3/repos/me/tool"
+        );
+        assert_eq!(request.headers()["authorization"], "Bearer ghp_test");
+        assert!(!format!("{:?}", client.with_token("ghp_test")).contains("ghp_test"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Shared HTTP client
+//!
+//! Registry plugins, verification and notifications send their requests
+//! through one `reqwest::Client` built by [`HttpClientFactory`], so corporate
+//! networks only need to be configured once:
+//!
+//! ```yaml
+//! http:
+//!   proxy: "http://proxy.corp.example:3128"   # default: HTTPS_PROXY / HTTP_PROXY
+//!   noProxy: "localhost,.corp.example"        # default: NO_PROXY
+//!   caBundle: "certs/corp-root.pem"           # default: SSL_CERT_FILE
+//!   timeout: "5m"
+//!   connectTimeout: "30s"
+//! ```
+//!
+//! [`PackagePublisher`](crate::orchestration::PackagePublisher) installs the
+//! configured client when it loads the configuration; [`http_client`] returns
+//! it (or a client with the defaults before that).
+
+use crate::core::config::HttpConfig;
+use crate::orchestration::duration_budget::parse_duration;
+use crate::tr;
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+/// User-Agent sent with every request
+pub const USER_AGENT: &str = concat!("package-publisher/", env!("CARGO_PKG_VERSION"));
+
+/// Default total request timeout (uploads of large artifacts included)
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Default connect timeout
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client returned by [`http_client`]
+static CLIENT: LazyLock<RwLock<Option<Client>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Builds `reqwest` clients with the proxy, CA and timeout settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpClientFactory {
+    proxy: Option<String>,
+    no_proxy: Option<String>,
+    ca_bundle: Option<PathBuf>,
+    timeout: Duration,
+    connect_timeout: Duration,
+}
+
+impl Default for HttpClientFactory {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            no_proxy: None,
+            ca_bundle: std::env::var_os("SSL_CERT_FILE").map(PathBuf::from),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+        }
+    }
+}
+
+impl HttpClientFactory {
+    /// Factory with the defaults (environment proxy and CA settings)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the `http` section; paths are relative to the project
+    pub fn from_config(config: Option<&HttpConfig>, project_path: &Path) -> anyhow::Result<Self> {
+        let mut factory = Self::default();
+        let Some(config) = config else {
+            return Ok(factory);
+        };
+        factory.proxy = config.proxy.clone();
+        factory.no_proxy = config.no_proxy.clone();
+        if let Some(ref bundle) = config.ca_bundle {
+            factory.ca_bundle = Some(project_path.join(bundle));
+        }
+        if let Some(ref timeout) = config.timeout {
+            factory.timeout = Duration::from_millis(parse_duration(timeout)?);
+        }
+        if let Some(ref timeout) = config.connect_timeout {
+            factory.connect_timeout = Duration::from_millis(parse_duration(timeout)?);
+        }
+        Ok(factory)
+    }
+
+    /// Build a client
+    pub fn build(&self) -> anyhow::Result<Client> {
+        let mut builder = Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout);
+        // Pooled connections belong to the runtime that opened them, and
+        // every test has its own runtime
+        if cfg!(test) {
+            builder = builder.pool_max_idle_per_host(0);
+        }
+
+        if let Some(ref url) = self.proxy {
+            let no_proxy = match self.no_proxy {
+                Some(ref hosts) => NoProxy::from_string(hosts),
+                None => NoProxy::from_env(),
+            };
+            let proxy = Proxy::all(url)
+                .map_err(|e| {
+                    anyhow::anyhow!("{}", tr!("http.invalid_proxy", url = url, error = e))
+                })?
+                .no_proxy(no_proxy);
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(ref path) = self.ca_bundle {
+            let pem = std::fs::read(path).map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!(
+                        "http.ca_bundle_unreadable",
+                        path = path.display(),
+                        error = e
+                    )
+                )
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("http.invalid_ca_bundle", path = path.display(), error = e)
+                )
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+
+    /// Build the client and make it the one returned by [`http_client`]
+    pub fn install(&self) -> anyhow::Result<()> {
+        let client = self.build()?;
+        if let Ok(mut installed) = CLIENT.write() {
+            *installed = Some(client);
+        }
+        Ok(())
+    }
+}
+
+/// The shared HTTP client
+///
+/// Clients share their connection pool, so cloning is cheap.
+pub fn http_client() -> Client {
+    if let Some(client) = CLIENT.read().ok().and_then(|c| c.clone()) {
+        return client;
+    }
+    // An unreadable SSL_CERT_FILE must not break every request
+    let client = HttpClientFactory::new().build().unwrap_or_else(|_| {
+        Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_default()
+    });
+    if let Ok(mut installed) = CLIENT.write() {
+        installed.get_or_insert(client).clone()
+    } else {
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_from_config() {
+        let config = HttpConfig {
+            proxy: Some("http://proxy.corp.example:3128".to_string()),
+            no_proxy: Some("localhost".to_string()),
+            ca_bundle: Some("certs/root.pem".to_string()),
+            timeout: Some("90s".to_string()),
+            connect_timeout: None,
+        };
+        let factory = HttpClientFactory::from_config(Some(&config), Path::new("/project")).unwrap();
+        assert_eq!(factory.timeout, Duration::from_secs(90));
+        assert_eq!(factory.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
+        assert_eq!(
+            factory.ca_bundle.as_deref(),
+            Some(Path::new("/project/certs/root.pem"))
+        );
+
+        let error = factory.build().unwrap_err();
+        assert!(error.to_string().contains("CA bundle"));
+    }
+
+    #[tokio::test]
+    async fn test_sends_user_agent() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let client = HttpClientFactory {
+            ca_bundle: None,
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        client
+            .get(format!("http://{}/", address))
+            .send()
+            .await
+            .unwrap();
+        let request = server.await.unwrap();
+        assert!(request.contains(&format!("user-agent: {}", USER_AGENT)));
+    }
+}
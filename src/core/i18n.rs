@@ -0,0 +1,225 @@
+//! Localized user-facing messages
+//!
+//! Messages live in per-locale catalogs (`locales/en.toml`, `locales/ja.toml`)
+//! keyed by `section.name`, with `{name}` placeholders filled by [`tr!`].
+//! English is the default; the locale comes from `LC_ALL` / `LC_MESSAGES` /
+//! `LANG`, and the CLI replaces it with `locale:` from the config it loads.
+//! Library callers pick the locale themselves with [`set_locale`]. Keys
+//! missing from a catalog fall back to English.
+//!
+//! [`tr!`]: crate::tr
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Language of user-facing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+impl Locale {
+    /// Locale of a POSIX locale name like `ja_JP.UTF-8` (`C`, `POSIX` and
+    /// unknown languages are English)
+    pub fn from_posix(name: &str) -> Self {
+        match name.split(['_', '.', '@', '-']).next() {
+            Some(language) if language.eq_ignore_ascii_case("ja") => Locale::Ja,
+            _ => Locale::En,
+        }
+    }
+
+    /// Locale selected by `LC_ALL`, `LC_MESSAGES` or `LANG` (first set wins)
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .find_map(|name| env(name).filter(|value| !value.is_empty()))
+            .map(|value| Self::from_posix(&value))
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("locales/en.toml"),
+            Locale::Ja => include_str!("locales/ja.toml"),
+        }
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Select the locale of all following messages
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// Locale of messages
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Ja,
+        _ => Locale::En,
+    }
+}
+
+/// Messages by locale, flattened to `section.name` keys
+static CATALOGS: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    [Locale::En, Locale::Ja]
+        .into_iter()
+        .map(|locale| {
+            let table: toml::Table = toml::from_str(locale.catalog())
+                .unwrap_or_else(|e| panic!("invalid {:?} message catalog: {}", locale, e));
+            let mut messages = HashMap::new();
+            flatten("", &table, &mut messages);
+            (locale, messages)
+        })
+        .collect()
+});
+
+fn flatten(prefix: &str, table: &toml::Table, messages: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, messages),
+            toml::Value::String(message) => {
+                messages.insert(key, message.clone());
+            }
+            other => panic!("message {} is not a string: {}", key, other),
+        }
+    }
+}
+
+/// Message `key` in the current locale with its placeholders filled
+pub fn translate(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate_in(locale(), key, args)
+}
+
+/// Message `key` in `locale` (English, or the key itself, when missing)
+pub fn translate_in(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let message = [locale, Locale::En]
+        .iter()
+        .find_map(|locale| CATALOGS[locale].get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    // One pass, so that values containing `{name}` are kept as they are
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let value = rest[start + 1..].split_once('}').and_then(|(name, _)| {
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (name.len(), value))
+        });
+        match value {
+            Some((len, value)) => {
+                result.push_str(&value.to_string());
+                rest = &rest[start + len + 2..];
+            }
+            None => {
+                result.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Localized message: `tr!("npm.name_too_long")`,
+/// `tr!("npm.invalid_semver", version = version)`
+#[macro_export]
+macro_rules! tr {
+    ($key:expr $(,)?) => {
+        $crate::core::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::core::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_keys_and_placeholders() {
+        let en = &CATALOGS[&Locale::En];
+        let ja = &CATALOGS[&Locale::Ja];
+        let en_keys: BTreeSet<_> = en.keys().collect();
+        let ja_keys: BTreeSet<_> = ja.keys().collect();
+        assert_eq!(en_keys, ja_keys);
+        for (key, message) in en {
+            assert_eq!(
+                placeholders(message),
+                placeholders(&ja[key]),
+                "placeholders of {} differ",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_translate_and_locale_selection() {
+        let registry = "npm";
+        assert_eq!(
+            translate_in(
+                Locale::En,
+                "error.token_missing",
+                &[("registry", &registry)]
+            ),
+            "[npm] Authentication token is not set"
+        );
+        assert_eq!(
+            translate_in(
+                Locale::Ja,
+                "error.token_missing",
+                &[("registry", &registry)]
+            ),
+            "[npm] 認証トークンが設定されていません"
+        );
+        assert_eq!(translate_in(Locale::Ja, "missing.key", &[]), "missing.key");
+        let message = "{version} not set";
+        assert_eq!(
+            translate_in(
+                Locale::En,
+                "error.config",
+                &[("message", &message), ("version", &"1.0")]
+            ),
+            "Configuration error: {version} not set"
+        );
+
+        assert_eq!(Locale::from_posix("ja_JP.UTF-8"), Locale::Ja);
+        assert_eq!(Locale::from_posix("C"), Locale::En);
+        let env = |name: &str| match name {
+            "LC_MESSAGES" => Some("ja_JP.UTF-8".to_string()),
+            "LANG" => Some("en_US.UTF-8".to_string()),
+            _ => None,
+        };
+        assert_eq!(Locale::from_env(env), Locale::Ja);
+        assert_eq!(Locale::from_env(|_| None), Locale::En);
+    }
+}
@@ -0,0 +1,484 @@
+//! Structured logging
+//!
+//! [`PackagePublisher`](crate::orchestration::PackagePublisher) runs each
+//! publish in a `publish` span (fields `registry`, `package`, `version` and
+//! the current `stage`) and opens a `stage` span per state (Detecting,
+//! Validating, DryRun, Publishing, Verifying), so the time spent in a stage
+//! is reported when its span closes. [`LogSubscriber`] writes these spans and
+//! the events inside them as text or JSON lines, which observability
+//! backends can ingest:
+//!
+//! ```text
+//! {"timestamp":"…","level":"INFO","target":"package_publisher","message":"🔍 Validating package...","span":{"name":"publish","registry":"npm","stage":"Validating"},"spans":[…]}
+//! ```
+//!
+//! The level defaults to `info` and can be changed with the
+//! `PACKAGE_PUBLISHER_LOG` environment variable (`trace` … `error`).
+
+use crate::tr;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+/// Environment variable with the maximum level
+pub const LOG_LEVEL_ENV: &str = "PACKAGE_PUBLISHER_LOG";
+
+/// Log line format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `timestamp LEVEL span{fields}:span{fields}: message fields`
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "Unknown log format: {} (expected text or json)",
+                other
+            )),
+        }
+    }
+}
+
+/// Install a [`LogSubscriber`] writing to stderr as the global subscriber
+pub fn init(format: LogFormat) -> anyhow::Result<()> {
+    let level = std::env::var(LOG_LEVEL_ENV)
+        .ok()
+        .and_then(|level| level.parse::<Level>().ok())
+        .unwrap_or(Level::INFO);
+    let subscriber = LogSubscriber::new(format, std::io::stderr()).with_max_level(level);
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| anyhow::anyhow!("{}", tr!("logging.init_failed", error = e)))
+}
+
+/// Open span
+struct SpanData {
+    metadata: &'static Metadata<'static>,
+    fields: Map<String, Value>,
+    parent: Option<Id>,
+    opened_at: Instant,
+    refs: usize,
+}
+
+thread_local! {
+    /// Spans entered on this thread, innermost last
+    static ENTERED: RefCell<Vec<Id>> = const { RefCell::new(Vec::new()) };
+}
+
+/// `tracing` subscriber writing text or JSON lines
+pub struct LogSubscriber {
+    format: LogFormat,
+    max_level: Level,
+    writer: Mutex<Box<dyn Write + Send>>,
+    spans: Mutex<HashMap<u64, SpanData>>,
+    next_id: AtomicU64,
+}
+
+impl fmt::Debug for LogSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSubscriber")
+            .field("format", &self.format)
+            .field("max_level", &self.max_level)
+            .finish()
+    }
+}
+
+impl LogSubscriber {
+    /// Subscriber writing lines of `format` to `writer` (level `info`)
+    pub fn new(format: LogFormat, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            format,
+            max_level: Level::INFO,
+            writer: Mutex::new(Box::new(writer)),
+            spans: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Only record spans and events up to `level`
+    pub fn with_max_level(mut self, level: Level) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// `(name, fields)` of `id` and its ancestors, outermost first
+    fn scope(
+        spans: &HashMap<u64, SpanData>,
+        id: Option<&Id>,
+    ) -> Vec<(&'static str, Map<String, Value>)> {
+        let mut scope = Vec::new();
+        let mut current = id.cloned();
+        while let Some(id) = current {
+            let Some(span) = spans.get(&id.into_u64()) else {
+                break;
+            };
+            scope.push((span.metadata.name(), span.fields.clone()));
+            current = span.parent.clone();
+        }
+        scope.reverse();
+        scope
+    }
+
+    fn format_line(
+        &self,
+        level: &Level,
+        target: &str,
+        mut fields: Map<String, Value>,
+        scope: Vec<(&'static str, Map<String, Value>)>,
+    ) -> String {
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let message = match fields.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+
+        match self.format {
+            LogFormat::Json => {
+                let spans: Vec<Value> = scope
+                    .into_iter()
+                    .map(|(name, mut span_fields)| {
+                        span_fields.insert("name".to_string(), Value::from(name));
+                        Value::Object(span_fields)
+                    })
+                    .collect();
+                let mut line = Map::new();
+                line.insert("timestamp".to_string(), Value::from(timestamp));
+                line.insert("level".to_string(), Value::from(level.as_str()));
+                line.insert("target".to_string(), Value::from(target));
+                line.insert("message".to_string(), Value::from(message));
+                line.extend(fields);
+                if let Some(span) = spans.last() {
+                    line.insert("span".to_string(), span.clone());
+                    line.insert("spans".to_string(), Value::Array(spans));
+                }
+                Value::Object(line).to_string()
+            }
+            LogFormat::Text => {
+                let mut line = format!("{} {:>5} ", timestamp, level.as_str());
+                for (name, span_fields) in scope {
+                    line.push_str(name);
+                    if !span_fields.is_empty() {
+                        line.push('{');
+                        line.push_str(&text_fields(&span_fields));
+                        line.push('}');
+                    }
+                    line.push(':');
+                }
+                if line.ends_with(':') {
+                    line.push(' ');
+                }
+                line.push_str(&message);
+                if !fields.is_empty() {
+                    line.push(' ');
+                    line.push_str(&text_fields(&fields));
+                }
+                line
+            }
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{}", line);
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// `key=value` pairs
+fn text_fields(fields: &Map<String, Value>) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(s) => format!("{}={}", key, s),
+            other => format!("{}={}", key, other),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Collects field values as JSON
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::from(format!("{:?}", value)),
+        );
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+}
+
+fn current_id() -> Option<Id> {
+    ENTERED.with(|entered| entered.borrow().last().cloned())
+}
+
+impl Subscriber for LogSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.max_level
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        Some(tracing::level_filters::LevelFilter::from_level(
+            self.max_level,
+        ))
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let id = Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let parent = if attrs.is_root() {
+            None
+        } else if attrs.is_contextual() {
+            current_id()
+        } else {
+            attrs.parent().cloned()
+        };
+
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor(&mut fields));
+
+        if let Ok(mut spans) = self.spans.lock() {
+            // A span keeps its parent open, so the scope of its events and
+            // of its close line is complete
+            let parent = parent.filter(|parent| match spans.get_mut(&parent.into_u64()) {
+                Some(span) => {
+                    span.refs += 1;
+                    true
+                }
+                None => false,
+            });
+            spans.insert(
+                id.into_u64(),
+                SpanData {
+                    metadata: attrs.metadata(),
+                    fields,
+                    parent,
+                    opened_at: Instant::now(),
+                    refs: 1,
+                },
+            );
+        }
+        id
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Ok(mut spans) = self.spans.lock()
+            && let Some(span) = spans.get_mut(&span.into_u64())
+        {
+            values.record(&mut JsonVisitor(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Map::new();
+        event.record(&mut JsonVisitor(&mut fields));
+        let parent = if event.is_root() {
+            None
+        } else if event.is_contextual() {
+            current_id()
+        } else {
+            event.parent().cloned()
+        };
+
+        let metadata = event.metadata();
+        let scope = self
+            .spans
+            .lock()
+            .map(|spans| Self::scope(&spans, parent.as_ref()))
+            .unwrap_or_default();
+        let line = self.format_line(metadata.level(), metadata.target(), fields, scope);
+        self.write_line(&line);
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.clone()));
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(position) = entered.iter().rposition(|id| id == span) {
+                entered.remove(position);
+            }
+        });
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        if let Ok(mut spans) = self.spans.lock()
+            && let Some(span) = spans.get_mut(&id.into_u64())
+        {
+            span.refs += 1;
+        }
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let mut lines = Vec::new();
+        let mut closed = false;
+        if let Ok(mut spans) = self.spans.lock() {
+            let mut next = Some(id);
+            let mut first = true;
+            while let Some(id) = next.take() {
+                let Some(span) = spans.get_mut(&id.into_u64()) else {
+                    break;
+                };
+                span.refs -= 1;
+                if span.refs > 0 {
+                    break;
+                }
+                if first {
+                    closed = true;
+                }
+                first = false;
+
+                let mut fields = Map::new();
+                fields.insert("message".to_string(), Value::from("close"));
+                fields.insert(
+                    "elapsed_ms".to_string(),
+                    Value::from(span.opened_at.elapsed().as_millis() as u64),
+                );
+                let level = *span.metadata.level();
+                let target = span.metadata.target();
+                let scope = Self::scope(&spans, Some(&id));
+                lines.push(self.format_line(&level, target, fields, scope));
+
+                next = spans.remove(&id.into_u64()).and_then(|span| span.parent);
+            }
+        }
+        for line in lines {
+            self.write_line(&line);
+        }
+        closed
+    }
+
+    fn current_span(&self) -> Current {
+        let Some(id) = current_id() else {
+            return Current::none();
+        };
+        let metadata = self
+            .spans
+            .lock()
+            .ok()
+            .and_then(|spans| spans.get(&id.into_u64()).map(|span| span.metadata));
+        match metadata {
+            Some(metadata) => Current::new(id, metadata),
+            None => Current::none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Writer shared with the test
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<String> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_json_lines_carry_span_fields() {
+        let buffer = Buffer::default();
+        let subscriber = LogSubscriber::new(LogFormat::Json, buffer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let publish =
+                tracing::info_span!("publish", registry = "npm", version = tracing::field::Empty);
+            let _entered = publish.enter();
+            publish.record("version", "1.2.3");
+            let stage = tracing::info_span!("stage", state = "Validating");
+            tracing::info!(target: "package_publisher", "validating");
+            tracing::debug!("filtered out");
+            drop(stage);
+        });
+
+        let lines: Vec<Value> = buffer
+            .lines()
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["message"], "validating");
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["span"]["name"], "publish");
+        assert_eq!(lines[0]["span"]["version"], "1.2.3");
+        assert_eq!(lines[1]["message"], "close");
+        assert_eq!(lines[1]["span"]["state"], "Validating");
+        assert_eq!(lines[1]["spans"][0]["registry"], "npm");
+        assert!(lines[1]["elapsed_ms"].is_u64());
+        assert_eq!(lines[2]["message"], "close");
+        assert_eq!(lines[2]["span"]["name"], "publish");
+    }
+
+    #[test]
+    fn test_text_format() {
+        let buffer = Buffer::default();
+        let subscriber = LogSubscriber::new(LogFormat::Text, buffer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("publish", registry = "crates.io");
+            span.in_scope(|| tracing::warn!(attempt = 2, "slow"));
+        });
+
+        let lines = buffer.lines();
+        assert!(lines[0].ends_with("WARN publish{registry=crates.io}: slow attempt=2"));
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
@@ -1,13 +1,25 @@
 pub mod config;
 pub mod config_loader;
+pub mod config_schema;
 pub mod error;
+pub mod git;
+pub mod github;
+pub mod http;
+pub mod i18n;
+pub mod logging;
+pub mod presets;
+pub mod publish_lock;
+pub mod rate_limit;
+pub mod reporter;
 pub mod retry;
 pub mod state_machine;
 pub mod traits;
+pub mod variables;
 
 pub use config::*;
 pub use config_loader::*;
 pub use error::*;
+pub use reporter::{Reporter, ReporterKind};
 pub use retry::*;
 pub use state_machine::*;
 pub use traits::*;
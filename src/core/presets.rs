@@ -0,0 +1,73 @@
+//! Built-in configuration presets
+//!
+//! `preset: rust-cli` in `.publish-config.yaml` expands to the recommended
+//! registries, quality gates and security settings for that kind of project.
+//! Presets are plain configuration files shipped with the binary; the project
+//! configuration (and an `extends` base) is merged on top, so any section can
+//! be overridden.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::core::presets;
+//!
+//! let preset = presets::load("ts-library").unwrap();
+//! assert!(preset.registries.npm.is_some());
+//! assert!(presets::load("go-module").is_err());
+//! ```
+
+use super::config::PublishConfig;
+use crate::core::error::PublishError;
+
+/// Built-in presets (name, YAML)
+const PRESETS: &[(&str, &str)] = &[
+    ("rust-cli", include_str!("presets/rust-cli.yaml")),
+    ("ts-library", include_str!("presets/ts-library.yaml")),
+    (
+        "python-package",
+        include_str!("presets/python-package.yaml"),
+    ),
+];
+
+/// Names of the built-in presets
+pub fn names() -> Vec<&'static str> {
+    PRESETS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Configuration of a built-in preset
+pub fn load(name: &str) -> Result<PublishConfig, PublishError> {
+    let (_, content) = PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .ok_or_else(|| {
+            PublishError::ConfigError(format!(
+                "Unknown preset: {} (available: {})",
+                name,
+                names().join(", ")
+            ))
+        })?;
+
+    serde_yaml::from_str(content)
+        .map_err(|e| PublishError::ConfigError(format!("Failed to parse preset {}: {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_presets_parse() {
+        for name in names() {
+            let config = load(name).unwrap();
+            assert_eq!(config.version, "1.0");
+            assert!(config.project.and_then(|p| p.default_registry).is_some());
+            assert!(
+                config
+                    .security
+                    .and_then(|s| s.secrets_scanning)
+                    .and_then(|s| s.enabled)
+                    .unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,475 @@
+//! Advisory lock against concurrent publishes of one project
+//!
+//! Two CI jobs publishing the same project at once would overwrite each
+//! other's state file and publish twice. [`PublishLock`] creates
+//! `.package-publisher/lock` (owner PID, host and expiry) before a publish
+//! starts and removes it when the publish ends. While the publish runs the
+//! expiry is pushed forward every third of the TTL, so long approvals and
+//! retries keep the lock. A lock whose TTL passed, or whose process no
+//! longer runs on this host, is stale and taken over.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Lock file, relative to the project
+pub const LOCK_FILE: &str = ".package-publisher/lock";
+
+/// Locks not refreshed for this long are treated as stale
+pub const DEFAULT_LOCK_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Shortest interval between refreshes of a held lock
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often a held lock is checked while waiting for it
+const LOCK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Unreadable lock files older than this are treated as stale
+const UNREADABLE_LOCK_GRACE: Duration = Duration::from_secs(5);
+
+/// Attempts to create the lock before giving up (each unreadable lock
+/// attempt waits 50ms, so this outlasts [`UNREADABLE_LOCK_GRACE`])
+const MAX_LOCK_ATTEMPTS: usize = 200;
+
+/// Owner of a publish lock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockOwner {
+    pub pid: u32,
+    pub host: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl LockOwner {
+    fn current(ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            pid: std::process::id(),
+            host: current_host(),
+            acquired_at: now,
+            expires_at: now + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    /// Whether the lock expired or its process is gone
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now || (self.host == current_host() && !process_alive(self.pid))
+    }
+
+    /// Whether `other` is this lock, possibly refreshed
+    fn same_lock(&self, other: &LockOwner) -> bool {
+        self.pid == other.pid && self.host == other.host && self.acquired_at == other.acquired_at
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pid {} on {} since {}",
+            self.pid,
+            self.host,
+            self.acquired_at.to_rfc3339()
+        )
+    }
+}
+
+/// Host name of this machine
+fn current_host() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Whether process `pid` is running (unknown outside Linux: assumed running,
+/// the TTL still applies)
+fn process_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        true
+    }
+}
+
+/// Content and modification time of the lock file
+async fn read_lock(path: &Path) -> std::io::Result<(Vec<u8>, SystemTime)> {
+    let content = tokio::fs::read(path).await?;
+    let modified = tokio::fs::metadata(path).await?.modified()?;
+    Ok((content, modified))
+}
+
+/// Owner recorded in the lock file at `path`
+fn read_owner(path: &Path) -> Option<LockOwner> {
+    let content = std::fs::read(path).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+/// Path next to `path` that no other process uses
+fn unique_sibling(path: &Path, suffix: &str) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}.{}.{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        suffix
+    ));
+    path.with_file_name(name)
+}
+
+/// Held publish lock, released when dropped
+#[derive(Debug)]
+pub struct PublishLock {
+    path: PathBuf,
+    owner: LockOwner,
+    /// Set once released; refreshes check it while holding the mutex, so
+    /// none can recreate the file after the release
+    released: Arc<Mutex<bool>>,
+    heartbeat: tokio::task::JoinHandle<()>,
+}
+
+impl PublishLock {
+    /// Take the lock of `project_path`
+    ///
+    /// A lock held by another publish fails immediately, or with `wait` is
+    /// polled until it is released (`on_wait` is called once with its owner)
+    /// or the wait times out.
+    pub async fn acquire<P: AsRef<Path>>(
+        project_path: P,
+        ttl: Duration,
+        wait: Option<Duration>,
+        on_wait: impl FnOnce(&LockOwner),
+    ) -> anyhow::Result<Self> {
+        let path = project_path.as_ref().join(LOCK_FILE);
+        let deadline = wait.map(|wait| Instant::now() + wait);
+        let mut on_wait = Some(on_wait);
+        loop {
+            let owner = match Self::try_create(&path, ttl).await? {
+                Ok(lock) => return Ok(lock),
+                Err(owner) => owner,
+            };
+            match deadline {
+                None => anyhow::bail!(
+                    "Another publish of this project is running ({}); use --wait-for-lock to wait for it",
+                    owner
+                ),
+                Some(deadline) if Instant::now() >= deadline => {
+                    anyhow::bail!("Timed out waiting for the publish lock held by {}", owner)
+                }
+                Some(_) => {
+                    if let Some(on_wait) = on_wait.take() {
+                        on_wait(&owner);
+                    }
+                    tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// Create the lock file, or return the owner of the live lock
+    ///
+    /// The owner is written to a temporary file first and hard-linked into
+    /// place, so the lock file is never seen half-written. Stale locks are
+    /// renamed aside and checked again before they are deleted, so two
+    /// publishes taking over the same stale lock cannot both succeed.
+    async fn try_create(path: &Path, ttl: Duration) -> anyhow::Result<Result<Self, LockOwner>> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        for _ in 0..MAX_LOCK_ATTEMPTS {
+            let owner = LockOwner::current(ttl);
+            let temp = unique_sibling(path, "tmp");
+            tokio::fs::write(&temp, serde_json::to_vec_pretty(&owner)?).await?;
+            let linked = tokio::fs::hard_link(&temp, path).await;
+            let _ = tokio::fs::remove_file(&temp).await;
+            match linked {
+                Ok(()) => return Ok(Ok(Self::hold(path, owner, ttl))),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            let (content, modified) = match read_lock(path).await {
+                Ok(lock) => lock,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            match serde_json::from_slice::<LockOwner>(&content) {
+                Ok(held) if !held.is_stale(Utc::now()) => return Ok(Err(held)),
+                Ok(_) => Self::take_over(path, &content).await?,
+                // Empty or truncated (e.g. a crash while writing the lock of
+                // an older version): stale once it stopped changing
+                Err(_) if modified.elapsed().unwrap_or_default() >= UNREADABLE_LOCK_GRACE => {
+                    Self::take_over(path, &content).await?
+                }
+                Err(_) => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        }
+        anyhow::bail!(
+            "Cannot acquire the publish lock {} (it keeps changing or is unreadable)",
+            path.display()
+        )
+    }
+
+    /// Move the stale lock `path` aside, putting it back when it was
+    /// replaced by a live lock after `stale` was read
+    async fn take_over(path: &Path, stale: &[u8]) -> anyhow::Result<()> {
+        let aside = unique_sibling(path, "stale");
+        match tokio::fs::rename(path, &aside).await {
+            Ok(()) => {}
+            // Taken over by another publish
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+        let moved = tokio::fs::read(&aside).await.unwrap_or_default();
+        if moved != stale {
+            // Fails when yet another lock was created meanwhile, which then
+            // decides who publishes
+            let _ = tokio::fs::hard_link(&aside, path).await;
+        }
+        let _ = tokio::fs::remove_file(&aside).await;
+        Ok(())
+    }
+
+    /// Hold the lock just created at `path`, refreshing it every third of
+    /// `ttl` until it is dropped
+    fn hold(path: &Path, owner: LockOwner, ttl: Duration) -> Self {
+        let released = Arc::new(Mutex::new(false));
+        let heartbeat = tokio::spawn({
+            let path = path.to_path_buf();
+            let owner = owner.clone();
+            let released = released.clone();
+            async move {
+                let interval = (ttl / 3).max(MIN_REFRESH_INTERVAL);
+                loop {
+                    tokio::time::sleep(interval).await;
+                    let path = path.clone();
+                    let owner = owner.clone();
+                    let released = released.clone();
+                    let refreshed = tokio::task::spawn_blocking(move || {
+                        Self::refresh(&path, &owner, ttl, &released)
+                    })
+                    .await;
+                    if !matches!(refreshed, Ok(true)) {
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            path: path.to_path_buf(),
+            owner,
+            released,
+            heartbeat,
+        }
+    }
+
+    /// Push the expiry of the lock at `path` to `ttl` from now; false once
+    /// the lock was released or is no longer `owner`'s
+    fn refresh(path: &Path, owner: &LockOwner, ttl: Duration, released: &Mutex<bool>) -> bool {
+        let released = released.lock().unwrap_or_else(|e| e.into_inner());
+        if *released || !read_owner(path).is_some_and(|held| owner.same_lock(&held)) {
+            return false;
+        }
+        let refreshed = LockOwner {
+            expires_at: LockOwner::current(ttl).expires_at,
+            ..owner.clone()
+        };
+        let temp = unique_sibling(path, "tmp");
+        let written = serde_json::to_vec_pretty(&refreshed)
+            .map_err(std::io::Error::other)
+            .and_then(|content| std::fs::write(&temp, content))
+            .and_then(|()| std::fs::rename(&temp, path));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        // A failed refresh is retried; the lock stays valid until it expires
+        true
+    }
+
+    /// Owner of the lock file at `project_path`, if locked
+    pub async fn current_owner<P: AsRef<Path>>(project_path: P) -> Option<LockOwner> {
+        let content = tokio::fs::read_to_string(project_path.as_ref().join(LOCK_FILE))
+            .await
+            .ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Owner recorded in the lock file
+    pub fn owner(&self) -> &LockOwner {
+        &self.owner
+    }
+}
+
+impl Drop for PublishLock {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        let mut released = self.released.lock().unwrap_or_else(|e| e.into_inner());
+        *released = true;
+        // Only remove the file if it is still ours (not taken over as stale)
+        if read_owner(&self.path).is_some_and(|held| self.owner.same_lock(&held)) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_lock_is_exclusive_until_dropped() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(lock.owner().pid, std::process::id());
+        assert_eq!(
+            PublishLock::current_owner(temp_dir.path()).await.as_ref(),
+            Some(lock.owner())
+        );
+
+        let error = PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("--wait-for-lock"));
+
+        drop(lock);
+        assert!(!temp_dir.path().join(LOCK_FILE).exists());
+        PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_locks_are_taken_over() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".package-publisher")).unwrap();
+        let expired = LockOwner {
+            expires_at: Utc::now() - chrono::Duration::minutes(1),
+            ..LockOwner::current(DEFAULT_LOCK_TTL)
+        };
+        std::fs::write(
+            temp_dir.path().join(LOCK_FILE),
+            serde_json::to_string(&expired).unwrap(),
+        )
+        .unwrap();
+        assert!(expired.is_stale(Utc::now()));
+        let lock = PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap();
+        drop(lock);
+
+        let live = LockOwner::current(DEFAULT_LOCK_TTL);
+        assert!(!live.is_stale(Utc::now()));
+        if cfg!(target_os = "linux") {
+            let dead = LockOwner {
+                pid: u32::MAX,
+                ..live
+            };
+            assert!(dead.is_stale(Utc::now()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_locks_are_taken_over_after_grace() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // Left behind by a crash between creating and writing the lock
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_modified(SystemTime::now() - UNREADABLE_LOCK_GRACE * 2)
+            .unwrap();
+        drop(file);
+
+        let lock = PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(
+            PublishLock::current_owner(temp_dir.path()).await.as_ref(),
+            Some(lock.owner())
+        );
+        drop(lock);
+
+        // Only the lock file is left in the directory
+        let leftovers = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[tokio::test]
+    async fn test_take_over_keeps_replaced_locks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("lock");
+        std::fs::write(&path, b"live").unwrap();
+        // Another publish replaced the stale lock after it was read
+        PublishLock::take_over(&path, b"stale").await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"live");
+
+        PublishLock::take_over(&path, b"live").await.unwrap();
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_held_lock_is_refreshed() {
+        let temp_dir = TempDir::new().unwrap();
+        let ttl = Duration::from_millis(600);
+        let lock = PublishLock::acquire(temp_dir.path(), ttl, None, |_| {})
+            .await
+            .unwrap();
+
+        tokio::time::sleep(ttl).await;
+        let held = PublishLock::current_owner(temp_dir.path()).await.unwrap();
+        assert!(lock.owner().same_lock(&held));
+        assert!(held.expires_at > lock.owner().expires_at);
+        assert!(!held.is_stale(Utc::now()));
+
+        drop(lock);
+        assert!(!temp_dir.path().join(LOCK_FILE).exists());
+        tokio::time::sleep(ttl).await;
+        assert!(!temp_dir.path().join(LOCK_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = PublishLock::acquire(temp_dir.path(), DEFAULT_LOCK_TTL, None, |_| {})
+            .await
+            .unwrap();
+
+        let error = PublishLock::acquire(
+            temp_dir.path(),
+            DEFAULT_LOCK_TTL,
+            Some(Duration::ZERO),
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("Timed out"));
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            drop(lock);
+        });
+        let mut waited_for = None;
+        PublishLock::acquire(
+            temp_dir.path(),
+            DEFAULT_LOCK_TTL,
+            Some(Duration::from_secs(10)),
+            |owner| waited_for = Some(owner.pid),
+        )
+        .await
+        .unwrap();
+        assert_eq!(waited_for, Some(std::process::id()));
+    }
+}
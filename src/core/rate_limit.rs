@@ -0,0 +1,283 @@
+//! Rate limit handling for registry APIs
+//!
+//! crates.io, npm and GitHub answer `429 Too Many Requests` (GitHub: `403`
+//! with `x-ratelimit-remaining: 0`) under load. Requests sent with
+//! [`RateLimitedSend::send_rate_limited`] wait as long as the registry asks
+//! (`Retry-After`, `RateLimit-Reset`, `X-RateLimit-Reset`) and are sent
+//! again, instead of failing the publish. The limits seen per host are kept
+//! for the report ([`rate_limit_status`]).
+
+use crate::orchestration::telemetry::record_latency;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+/// Throttled requests are sent again at most this many times
+const MAX_THROTTLE_RETRIES: u32 = 5;
+
+/// Longer waits are not worth blocking a publish for; the response is
+/// returned to the caller instead
+const MAX_THROTTLE_WAIT: Duration = Duration::from_secs(120);
+
+/// Rate limit state of a registry host
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Host name of the registry API
+    pub host: String,
+    /// Requests allowed per window
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// When the window resets
+    pub reset_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Number of throttled responses
+    pub throttled: u32,
+    /// Total time spent waiting for the limit to reset
+    pub waited: Duration,
+}
+
+impl fmt::Display for RateLimitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.host)?;
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) => write!(f, " {}/{} remaining", remaining, limit)?,
+            (Some(remaining), None) => write!(f, " {} remaining", remaining)?,
+            _ => write!(f, " no limit reported")?,
+        }
+        if let Some(reset_at) = self.reset_at {
+            write!(f, ", resets at {}", reset_at.format("%H:%M:%S UTC"))?;
+        }
+        if self.throttled > 0 {
+            write!(
+                f,
+                ", throttled {} time(s), waited {}s",
+                self.throttled,
+                self.waited.as_secs()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Rate limit state per host, shared by all plugins of the process
+static STATUS: LazyLock<Mutex<BTreeMap<String, RateLimitStatus>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Rate limit state of every host contacted so far (sorted by host)
+pub fn rate_limit_status() -> Vec<RateLimitStatus> {
+    STATUS
+        .lock()
+        .map(|status| status.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Rate limit headers of a response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RateLimitHeaders {
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    /// Time until the window resets
+    reset_after: Option<Duration>,
+    /// `Retry-After`
+    retry_after: Option<Duration>,
+}
+
+impl RateLimitHeaders {
+    fn parse(headers: &HeaderMap, now: chrono::DateTime<chrono::Utc>) -> Self {
+        let value = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| headers.get(*name)?.to_str().ok())
+                .map(str::trim)
+        };
+        let number = |names: &[&str]| value(names)?.parse::<u64>().ok();
+
+        // X-RateLimit-Reset is an epoch timestamp (GitHub), RateLimit-Reset
+        // (IETF draft) a number of seconds
+        let reset_after = number(&["ratelimit-reset", "x-ratelimit-reset"]).map(|reset| {
+            if reset > 1_000_000_000 {
+                Duration::from_secs(reset.saturating_sub(now.timestamp().max(0) as u64))
+            } else {
+                Duration::from_secs(reset)
+            }
+        });
+
+        // Retry-After is a number of seconds or an HTTP date
+        let retry_after = value(&["retry-after"]).and_then(|value| match value.parse::<u64>() {
+            Ok(seconds) => Some(Duration::from_secs(seconds)),
+            Err(_) => chrono::DateTime::parse_from_rfc2822(value).ok().map(|at| {
+                (at.with_timezone(&chrono::Utc) - now)
+                    .to_std()
+                    .unwrap_or_default()
+            }),
+        });
+
+        Self {
+            limit: number(&["ratelimit-limit", "x-ratelimit-limit"]),
+            remaining: number(&["ratelimit-remaining", "x-ratelimit-remaining"]),
+            reset_after,
+            retry_after,
+        }
+    }
+
+    /// How long to wait before sending again, or `None` when not throttled
+    fn throttle_delay(&self, status: StatusCode, attempt: u32) -> Option<Duration> {
+        let throttled = status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::SERVICE_UNAVAILABLE && self.retry_after.is_some())
+            || (status == StatusCode::FORBIDDEN && self.remaining == Some(0));
+        if !throttled {
+            return None;
+        }
+        Some(
+            self.retry_after
+                .or(self.reset_after)
+                .unwrap_or_else(|| Duration::from_secs(1 << attempt.min(6))),
+        )
+    }
+}
+
+/// Record the headers of a response (and the wait it caused)
+fn record(host: &str, headers: &RateLimitHeaders, throttled: Option<Duration>) {
+    let Ok(mut status) = STATUS.lock() else {
+        return;
+    };
+    if headers == &RateLimitHeaders::default() && throttled.is_none() {
+        return;
+    }
+    let entry = status
+        .entry(host.to_string())
+        .or_insert_with(|| RateLimitStatus {
+            host: host.to_string(),
+            ..Default::default()
+        });
+    entry.limit = headers.limit.or(entry.limit);
+    entry.remaining = headers.remaining.or(entry.remaining);
+    if let Some(reset_after) = headers.reset_after {
+        entry.reset_at = chrono::Duration::from_std(reset_after)
+            .ok()
+            .map(|after| chrono::Utc::now() + after);
+    }
+    if let Some(wait) = throttled {
+        entry.throttled += 1;
+        entry.waited += wait;
+    }
+}
+
+/// Send a request, waiting out rate limits
+pub trait RateLimitedSend {
+    /// Like [`RequestBuilder::send`], but throttled responses are sent again
+    /// after the delay the registry asks for
+    ///
+    /// Requests with streaming bodies cannot be repeated and are sent once.
+    fn send_rate_limited(self) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl RateLimitedSend for RequestBuilder {
+    async fn send_rate_limited(self) -> reqwest::Result<Response> {
+        let mut request = self;
+        let mut attempt = 0;
+        loop {
+            let retry = request.try_clone();
+            let sent_at = std::time::Instant::now();
+            let response = request.send().await?;
+            let host = response.url().host_str().unwrap_or_default().to_string();
+            record_latency(&host, sent_at.elapsed());
+            let headers = RateLimitHeaders::parse(response.headers(), chrono::Utc::now());
+            let wait = headers
+                .throttle_delay(response.status(), attempt)
+                .filter(|wait| *wait <= MAX_THROTTLE_WAIT && attempt < MAX_THROTTLE_RETRIES);
+
+            match (wait, retry) {
+                (Some(wait), Some(next)) => {
+                    record(&host, &headers, Some(wait));
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    request = next;
+                }
+                _ => {
+                    record(&host, &headers, None);
+                    return Ok(response);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_headers() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-10-17T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("5000"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_str(&(now.timestamp() + 30).to_string()).unwrap(),
+        );
+        let parsed = RateLimitHeaders::parse(&headers, now);
+        assert_eq!(parsed.limit, Some(5000));
+        assert_eq!(parsed.reset_after, Some(Duration::from_secs(30)));
+        assert_eq!(
+            parsed.throttle_delay(StatusCode::FORBIDDEN, 0),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parsed.throttle_delay(StatusCode::OK, 0), None);
+
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Sat, 17 Oct 2026 12:00:10 GMT"),
+        );
+        let parsed = RateLimitHeaders::parse(&headers, now);
+        assert_eq!(
+            parsed.throttle_delay(StatusCode::TOO_MANY_REQUESTS, 0),
+            Some(Duration::from_secs(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_retry_after() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\nx-ratelimit-remaining: 0\r\ncontent-length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nx-ratelimit-limit: 10\r\nx-ratelimit-remaining: 9\r\ncontent-length: 2\r\n\r\nok",
+            ];
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await.unwrap();
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/api/v1/crates/demo", address))
+            .header("connection", "close")
+            .send_rate_limited()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "ok");
+
+        let status = rate_limit_status()
+            .into_iter()
+            .find(|s| s.host == "127.0.0.1")
+            .unwrap();
+        assert_eq!(status.throttled, 1);
+        assert_eq!(status.remaining, Some(9));
+        assert_eq!(status.limit, Some(10));
+    }
+}
@@ -0,0 +1,242 @@
+//! Reporter - pluggable output for the orchestrators
+//!
+//! `PackagePublisher`, `BatchPublisher` and `ConfigLoader` write progress and
+//! diagnostics through a [`Reporter`] instead of printing directly, so library
+//! consumers can capture, reformat or suppress the output.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::core::reporter::BufferedReporter;
+//! use package_publisher::orchestration::PackagePublisher;
+//! use std::sync::Arc;
+//!
+//! let reporter = Arc::new(BufferedReporter::new());
+//! let publisher = PackagePublisher::new(".").with_reporter(reporter.clone());
+//! // ... publish, then inspect reporter.entries()
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Message level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ReportLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportLevel::Info => "info",
+            ReportLevel::Success => "success",
+            ReportLevel::Warning => "warning",
+            ReportLevel::Error => "error",
+        }
+    }
+}
+
+/// Output sink for orchestrator messages
+///
+/// Messages are preformatted for terminals: they may carry leading
+/// indentation, emoji and trailing newlines, and an empty message is a blank
+/// line. Structured reporters should trim them.
+pub trait Reporter: Send + Sync + fmt::Debug {
+    /// Report a message
+    fn report(&self, level: ReportLevel, message: &str);
+
+    fn info(&self, message: &str) {
+        self.report(ReportLevel::Info, message);
+    }
+
+    fn success(&self, message: &str) {
+        self.report(ReportLevel::Success, message);
+    }
+
+    fn warning(&self, message: &str) {
+        self.report(ReportLevel::Warning, message);
+    }
+
+    fn error(&self, message: &str) {
+        self.report(ReportLevel::Error, message);
+    }
+
+    /// Whether credentials are masked before messages reach the output, so
+    /// [`RedactingReporter::wrap`](crate::security::RedactingReporter::wrap)
+    /// does not stack another layer
+    fn masks_secrets(&self) -> bool {
+        false
+    }
+}
+
+/// Terminal output: warnings and errors to stderr, everything else to stdout
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HumanReporter;
+
+impl Reporter for HumanReporter {
+    fn report(&self, level: ReportLevel, message: &str) {
+        match level {
+            ReportLevel::Info | ReportLevel::Success => println!("{}", message),
+            ReportLevel::Warning | ReportLevel::Error => eprintln!("{}", message),
+        }
+    }
+}
+
+/// One JSON object per line on stdout (`{"level", "message", "timestamp"}`)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, level: ReportLevel, message: &str) {
+        let message = message.trim();
+        if message.is_empty() {
+            return;
+        }
+
+        println!(
+            "{}",
+            serde_json::json!({
+                "level": level,
+                "message": message,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            })
+        );
+    }
+}
+
+/// Discards all output
+#[derive(Debug, Default, Clone, Copy)]
+pub struct QuietReporter;
+
+impl Reporter for QuietReporter {
+    fn report(&self, _level: ReportLevel, _message: &str) {}
+}
+
+/// Forwards messages as `tracing` events (target `package_publisher`)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingReporter;
+
+impl Reporter for TracingReporter {
+    fn report(&self, level: ReportLevel, message: &str) {
+        let message = message.trim();
+        if message.is_empty() {
+            return;
+        }
+
+        match level {
+            ReportLevel::Info => tracing::info!(target: "package_publisher", "{}", message),
+            ReportLevel::Success => {
+                tracing::info!(target: "package_publisher", outcome = "success", "{}", message)
+            }
+            ReportLevel::Warning => tracing::warn!(target: "package_publisher", "{}", message),
+            ReportLevel::Error => tracing::error!(target: "package_publisher", "{}", message),
+        }
+    }
+}
+
+/// Captured message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportEntry {
+    pub level: ReportLevel,
+    pub message: String,
+}
+
+/// Collects messages in memory
+#[derive(Debug, Default)]
+pub struct BufferedReporter {
+    entries: Mutex<Vec<ReportEntry>>,
+}
+
+impl BufferedReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages reported so far
+    pub fn entries(&self) -> Vec<ReportEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+impl Reporter for BufferedReporter {
+    fn report(&self, level: ReportLevel, message: &str) {
+        self.entries.lock().unwrap().push(ReportEntry {
+            level,
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Built-in reporter kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReporterKind {
+    #[default]
+    Human,
+    Json,
+    Quiet,
+    Tracing,
+}
+
+impl ReporterKind {
+    /// Create the reporter
+    pub fn build(self) -> Arc<dyn Reporter> {
+        match self {
+            ReporterKind::Human => Arc::new(HumanReporter),
+            ReporterKind::Json => Arc::new(JsonReporter),
+            ReporterKind::Quiet => Arc::new(QuietReporter),
+            ReporterKind::Tracing => Arc::new(TracingReporter),
+        }
+    }
+}
+
+impl FromStr for ReporterKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(ReporterKind::Human),
+            "json" => Ok(ReporterKind::Json),
+            "quiet" => Ok(ReporterKind::Quiet),
+            "tracing" => Ok(ReporterKind::Tracing),
+            other => Err(format!(
+                "Unknown output format: {} (expected human, json, quiet or tracing)",
+                other
+            )),
+        }
+    }
+}
+
+/// Default reporter (terminal output)
+pub fn default_reporter() -> Arc<dyn Reporter> {
+    Arc::new(HumanReporter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffered_reporter() {
+        let reporter = BufferedReporter::new();
+        reporter.info("📤 Publishing...");
+        reporter.warning("  ⚠️  careful");
+
+        let entries = reporter.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].level, ReportLevel::Warning);
+        assert_eq!(entries[1].message, "  ⚠️  careful");
+    }
+
+    #[test]
+    fn test_reporter_kind_from_str() {
+        assert_eq!("JSON".parse::<ReporterKind>(), Ok(ReporterKind::Json));
+        assert_eq!("quiet".parse::<ReporterKind>(), Ok(ReporterKind::Quiet));
+        assert!("xml".parse::<ReporterKind>().is_err());
+    }
+}
@@ -1,12 +1,26 @@
 //! Retry logic with exponential backoff
 //!
 //! This module provides configurable retry mechanisms for network operations
-//! and other potentially transient failures.
-
+//! and other potentially transient failures (connection errors, HTTP 5xx and
+//! rate limiting). Registry plugins retry their CLI invocations and registry
+//! HTTP calls with the options from `publish.retry`; the upload itself goes
+//! through [`RetryManager::retry_publish`], which never repeats an attempt
+//! that may have reached the registry.
+
+use super::error::PublishErrorKind;
+use crate::security::command_executor::CommandError;
+use regex::Regex;
 use std::future::Future;
+use std::sync::LazyLock;
 use std::time::Duration;
 use tokio::time::sleep;
 
+/// HTTP status codes worth retrying, as reported by npm (`E503`), cargo
+/// (`got 503`) and reqwest (`(503 Service Unavailable)`)
+static TRANSIENT_STATUS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(\bE|\bgot |\bstatus:? |\bHTTP(/[\d.]+)? |\()(429|50[0234])\b").unwrap()
+});
+
 /// Options for retry behavior
 #[derive(Debug, Clone)]
 pub struct RetryOptions {
@@ -18,6 +32,9 @@ pub struct RetryOptions {
     pub max_delay: Duration,
     /// Backoff multiplier for exponential backoff
     pub backoff_multiplier: f64,
+    /// Randomize each delay between half and the full value, so parallel
+    /// publishes do not retry in lockstep
+    pub jitter: bool,
 }
 
 impl Default for RetryOptions {
@@ -27,6 +44,17 @@ impl Default for RetryOptions {
             initial_delay: Duration::from_secs(1),
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryOptions {
+    /// Options that run the operation exactly once
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
         }
     }
 }
@@ -118,7 +146,7 @@ impl RetryManager {
                     last_error = Some(error);
 
                     // Wait before retry with exponential backoff
-                    sleep(delay).await;
+                    sleep(self.jittered(delay)).await;
 
                     // Calculate next delay with backoff multiplier
                     delay = Duration::from_secs_f64(
@@ -133,29 +161,105 @@ impl RetryManager {
         Err(last_error.unwrap())
     }
 
-    /// Check if an error should be retried
+    /// Execute a publish with retry logic
     ///
-    /// Network errors and timeout errors are always retryable.
+    /// Unlike [`retry`](Self::retry), an attempt killed at its timeout is not
+    /// run again: the upload may already have reached the registry, and
+    /// publishing is not idempotent. When a retried attempt finds the version
+    /// already published, an earlier attempt that failed on the way back
+    /// uploaded it; that counts as success with the value of
+    /// `already_published`.
+    pub async fn retry_publish<F, Fut, T>(
+        &self,
+        mut operation: F,
+        already_published: impl FnOnce(anyhow::Error) -> T,
+    ) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut delay = self.options.initial_delay;
+        let mut attempt = 1;
+        loop {
+            let error = match operation().await {
+                Ok(result) => return Ok(result),
+                Err(error) => error,
+            };
+            if attempt > 1 && PublishErrorKind::of(&error) == PublishErrorKind::VersionExists {
+                return Ok(already_published(error));
+            }
+            if attempt >= self.options.max_attempts
+                || is_ambiguous_publish_error(&error)
+                || !self.is_retryable_error(&error)
+            {
+                return Err(error);
+            }
+
+            sleep(self.jittered(delay)).await;
+            delay = Duration::from_secs_f64(delay.as_secs_f64() * self.options.backoff_multiplier)
+                .min(self.options.max_delay);
+            attempt += 1;
+        }
+    }
+
+    /// Delay with jitter applied (between half and the full delay)
+    fn jittered(&self, delay: Duration) -> Duration {
+        if !self.options.jitter {
+            return delay;
+        }
+        // The leading 32 bits of a v4 UUID are random
+        let random = (uuid::Uuid::new_v4().as_u128() >> 96) as u32 as f64 / u32::MAX as f64;
+        delay.mul_f64(0.5 + random / 2.0)
+    }
+
+    /// Check if an error should be retried
     fn is_retryable_error<E: std::fmt::Display>(&self, error: &E) -> bool {
-        let error_msg = error.to_string();
+        is_transient_error(&error.to_string())
+    }
+}
 
-        // Network error patterns
-        let retryable_patterns = [
-            "ECONNREFUSED",
-            "ENOTFOUND",
-            "ETIMEDOUT",
-            "ECONNRESET",
-            "socket hang up",
-            "network error",
-            "timeout",
-            "connection refused",
-            "connection reset",
-        ];
+/// Whether a failed publish attempt may still have uploaded the package
+///
+/// A command killed at its timeout was possibly waiting for the registry's
+/// answer to a finished upload.
+fn is_ambiguous_publish_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| matches!(cause.downcast_ref(), Some(CommandError::Timeout(_))))
+}
 
-        retryable_patterns
-            .iter()
-            .any(|pattern| error_msg.to_lowercase().contains(&pattern.to_lowercase()))
+/// Check if an error message describes a transient failure
+///
+/// Network errors, timeouts, HTTP 5xx responses and rate limiting are
+/// retryable; anything else (validation, authentication, conflicts) is not.
+pub fn is_transient_error(error_msg: &str) -> bool {
+    if TRANSIENT_STATUS.is_match(error_msg) {
+        return true;
     }
+
+    // Network error patterns
+    let retryable_patterns = [
+        "ECONNREFUSED",
+        "ENOTFOUND",
+        "ETIMEDOUT",
+        "ECONNRESET",
+        "socket hang up",
+        "network error",
+        "timeout",
+        "connection refused",
+        "connection reset",
+        "EAI_AGAIN",
+        "too many requests",
+        "rate limit",
+        "service unavailable",
+        "bad gateway",
+        "gateway timeout",
+        "temporarily unavailable",
+    ];
+
+    retryable_patterns
+        .iter()
+        .any(|pattern| error_msg.to_lowercase().contains(&pattern.to_lowercase()))
 }
 
 #[cfg(test)]
@@ -181,6 +285,7 @@ mod tests {
             initial_delay: Duration::from_millis(10),
             max_delay: Duration::from_millis(100),
             backoff_multiplier: 2.0,
+            jitter: false,
         });
 
         let counter = Arc::new(AtomicU32::new(0));
@@ -211,6 +316,7 @@ mod tests {
             initial_delay: Duration::from_millis(10),
             max_delay: Duration::from_millis(100),
             backoff_multiplier: 2.0,
+            jitter: false,
         });
 
         let counter = Arc::new(AtomicU32::new(0));
@@ -227,6 +333,85 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 3);
     }
 
+    #[tokio::test]
+    async fn test_retry_publish_version_exists_after_retry() {
+        let manager = RetryManager::new(RetryOptions {
+            initial_delay: Duration::from_millis(10),
+            jitter: false,
+            ..Default::default()
+        });
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+        let result = manager
+            .retry_publish(
+                move || {
+                    let count = counter_clone.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        if count == 0 {
+                            Err(anyhow::anyhow!("npm ERR! code ECONNRESET"))
+                        } else {
+                            Err(anyhow::anyhow!(
+                                "npm ERR! You cannot publish over the previously published versions"
+                            ))
+                        }
+                    }
+                },
+                |_| "published by the first attempt",
+            )
+            .await;
+
+        assert_eq!(result.unwrap(), "published by the first attempt");
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_publish_version_exists_on_first_attempt() {
+        let manager = RetryManager::new(RetryOptions::default());
+
+        let result = manager
+            .retry_publish(
+                || async {
+                    Err::<&str, _>(anyhow::anyhow!(
+                        "npm ERR! You cannot publish over the previously published versions"
+                    ))
+                },
+                |_| "unreachable",
+            )
+            .await;
+
+        assert_eq!(
+            PublishErrorKind::of(&result.unwrap_err()),
+            PublishErrorKind::VersionExists
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_publish_timeout_not_retried() {
+        let manager = RetryManager::new(RetryOptions {
+            initial_delay: Duration::from_millis(10),
+            jitter: false,
+            ..Default::default()
+        });
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+        let result = manager
+            .retry_publish(
+                move || {
+                    counter_clone.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        Err::<&str, _>(CommandError::Timeout(Duration::from_secs(1800)).into())
+                    }
+                },
+                |_| "unreachable",
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_non_retryable_error() {
         let manager = RetryManager::new(RetryOptions::default());
@@ -277,6 +462,7 @@ mod tests {
             initial_delay: Duration::from_millis(10),
             max_delay: Duration::from_millis(50),
             backoff_multiplier: 2.0,
+            jitter: false,
         });
 
         let start = std::time::Instant::now();
@@ -302,6 +488,7 @@ mod tests {
             initial_delay: Duration::from_millis(100),
             max_delay: Duration::from_millis(200),
             backoff_multiplier: 3.0,
+            jitter: false,
         });
 
         let start = std::time::Instant::now();
@@ -329,6 +516,26 @@ mod tests {
         assert_eq!(options.initial_delay, Duration::from_secs(1));
         assert_eq!(options.max_delay, Duration::from_secs(30));
         assert_eq!(options.backoff_multiplier, 2.0);
+        assert!(options.jitter);
+    }
+
+    #[test]
+    fn test_transient_http_errors() {
+        assert!(is_transient_error("npm ERR! code E503"));
+        assert!(is_transient_error(
+            "failed to get successful HTTP response from `https://crates.io`, got 502"
+        ));
+        assert!(is_transient_error(
+            "HTTP status server error (504 Gateway Timeout) for url"
+        ));
+        assert!(is_transient_error(
+            "Package demo not found on npm (HTTP 429 Too Many Requests)"
+        ));
+        assert!(is_transient_error("You have exceeded the rate limit"));
+
+        assert!(!is_transient_error("npm ERR! code E403"));
+        assert!(!is_transient_error("HTTP 404 Not Found"));
+        assert!(!is_transient_error("expected 500 bytes"));
     }
 
     #[tokio::test]
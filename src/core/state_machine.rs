@@ -1,6 +1,10 @@
 //! State machine for tracking publishing workflow with resume capability
 //!
 //! This module provides state management with atomic file operations.
+//! Besides the current state, the state file keeps the results of completed
+//! stages ([`StageArtifacts`]) so `--resume` continues where a publish
+//! stopped; batch publishes additionally record finished targets in
+//! [`BatchProgress`].
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -11,6 +15,9 @@ use tokio::fs;
 /// State file name
 const STATE_FILE: &str = ".publish-state.json";
 
+/// Batch progress file name
+const BATCH_STATE_FILE: &str = ".publish-batch-state.json";
+
 /// Publishing state
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -20,6 +27,8 @@ pub enum PublishState {
     Validating,
     DryRun,
     Confirming,
+    /// Waiting for a second person's approval (`approval`)
+    WaitingApproval,
     Publishing,
     Verifying,
     Success,
@@ -69,6 +78,58 @@ pub struct PublishStateData {
     /// Last error message (if failed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+
+    /// Results of completed stages
+    #[serde(default, skip_serializing_if = "StageArtifacts::is_empty")]
+    pub artifacts: StageArtifacts,
+}
+
+/// Results of completed stages, reused by `--resume` instead of running the
+/// stages again
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StageArtifacts {
+    /// Stages that finished successfully
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub completed: Vec<PublishState>,
+
+    /// Validation result of the package (`ValidationResult`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<serde_json::Value>,
+
+    /// Registry response of the publish (`PublishResult`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish: Option<serde_json::Value>,
+
+    /// Dependency audit run before the validation (`AuditReport`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit: Option<serde_json::Value>,
+
+    /// Dependency confusion check run before the validation
+    /// (`ConfusionReport`)
+    #[serde(
+        rename = "dependencyConfusion",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dependency_confusion: Option<serde_json::Value>,
+
+    /// `HEAD` commit the stages ran on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+
+    /// SHA-256 of the registry manifest the stages ran on
+    #[serde(rename = "manifestHash", skip_serializing_if = "Option::is_none")]
+    pub manifest_hash: Option<String>,
+}
+
+impl StageArtifacts {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether `stage` finished successfully
+    pub fn is_completed(&self, stage: PublishState) -> bool {
+        self.completed.contains(&stage)
+    }
 }
 
 /// State machine for tracking publishing workflow
@@ -79,6 +140,7 @@ pub struct PublishStateMachine {
     registry: Option<String>,
     version: Option<String>,
     error: Option<String>,
+    artifacts: StageArtifacts,
 }
 
 impl PublishStateMachine {
@@ -93,9 +155,69 @@ impl PublishStateMachine {
             registry: None,
             version: None,
             error: None,
+            artifacts: StageArtifacts::default(),
+        }
+    }
+
+    /// State machine with its own state file (`.publish-state-{key}.json`),
+    /// for batch publishes running several registries in one project
+    pub fn with_state_key<P: AsRef<Path>>(project_path: P, key: &str) -> Self {
+        let key: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Self {
+            state_file_path: project_path
+                .as_ref()
+                .join(format!(".publish-state-{}.json", key)),
+            ..Self::new(project_path)
         }
     }
 
+    /// Path of the state file
+    pub fn state_file_path(&self) -> &Path {
+        &self.state_file_path
+    }
+
+    /// Whether a state file exists to restore
+    pub fn has_saved_state(&self) -> bool {
+        self.state_file_path.exists()
+    }
+
+    /// Record the registry being published to (saved with the next transition)
+    pub fn set_registry(&mut self, registry: &str) {
+        self.registry = Some(registry.to_string());
+    }
+
+    /// Record the version being published (saved with the next transition)
+    pub fn set_version(&mut self, version: &str) {
+        self.version = Some(version.to_string());
+    }
+
+    /// Results of completed stages
+    pub fn artifacts(&self) -> &StageArtifacts {
+        &self.artifacts
+    }
+
+    /// Results of completed stages, to fill in before [`complete_stage`](Self::complete_stage)
+    pub fn artifacts_mut(&mut self) -> &mut StageArtifacts {
+        &mut self.artifacts
+    }
+
+    /// Mark `stage` as finished and persist its artifacts
+    pub async fn complete_stage(&mut self, stage: PublishState) -> Result<(), std::io::Error> {
+        if !self.artifacts.is_completed(stage) {
+            self.artifacts.completed.push(stage);
+        }
+        self.save().await
+    }
+
     /// Transition to a new state
     pub async fn transition(
         &mut self,
@@ -112,6 +234,10 @@ impl PublishStateMachine {
         self.transitions.push(transition);
         self.current_state = to;
 
+        if to == PublishState::Success {
+            self.error = None;
+        }
+
         // Update metadata if provided
         if let Some(meta) = metadata {
             if let Some(serde_json::Value::String(registry)) = meta.get("registry") {
@@ -145,6 +271,7 @@ impl PublishStateMachine {
             transitions: self.transitions.clone(),
             can_resume: self.can_resume(),
             error: self.error.clone(),
+            artifacts: self.artifacts.clone(),
         }
     }
 
@@ -172,6 +299,7 @@ impl PublishStateMachine {
         self.version = data.version;
         self.error = data.error;
         self.transitions = data.transitions;
+        self.artifacts = data.artifacts;
 
         Ok(true)
     }
@@ -204,10 +332,21 @@ impl PublishStateMachine {
         self.registry = None;
         self.version = None;
         self.error = None;
+        self.artifacts = StageArtifacts::default();
 
         Ok(())
     }
 
+    /// Record the error that stopped the publish, keeping the current
+    /// state so it can still be resumed
+    pub async fn record_error(&mut self, error: &str) -> Result<(), std::io::Error> {
+        self.error = Some(error.to_string());
+        if self.current_state == PublishState::Initial {
+            return Ok(());
+        }
+        self.save().await
+    }
+
     /// Get last error
     pub fn get_last_error(&self) -> Option<&str> {
         self.error.as_deref()
@@ -243,6 +382,149 @@ impl PublishStateMachine {
     }
 }
 
+/// State file found in a project
+#[derive(Debug, Clone)]
+pub struct SavedPublishState {
+    /// Key of the state file (`.publish-state-{key}.json`), `None` for the
+    /// default one
+    pub key: Option<String>,
+
+    /// Path of the state file
+    pub path: PathBuf,
+
+    /// Saved state
+    pub data: PublishStateData,
+}
+
+impl SavedPublishState {
+    /// When the run started (first transition)
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.data.transitions.first().map(|t| t.timestamp)
+    }
+
+    /// When the state was last updated (last transition)
+    pub fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.data.transitions.last().map(|t| t.timestamp)
+    }
+}
+
+/// Saved publish states of a project, most recently updated first
+///
+/// Unreadable state files are skipped.
+pub async fn saved_publish_states<P: AsRef<Path>>(
+    project_path: P,
+) -> Result<Vec<SavedPublishState>, std::io::Error> {
+    let mut states = Vec::new();
+    let mut entries = fs::read_dir(project_path.as_ref()).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let key = if name == STATE_FILE {
+            None
+        } else if let Some(key) = name
+            .strip_prefix(".publish-state-")
+            .and_then(|rest| rest.strip_suffix(".json"))
+        {
+            Some(key.to_string())
+        } else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(entry.path()).await else {
+            continue;
+        };
+        let Ok(data) = serde_json::from_str::<PublishStateData>(&content) else {
+            continue;
+        };
+        states.push(SavedPublishState {
+            key,
+            path: entry.path(),
+            data,
+        });
+    }
+    states.sort_by_key(|state| std::cmp::Reverse(state.updated_at()));
+    Ok(states)
+}
+
+/// Batch progress data
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BatchProgressData {
+    /// Registries or packages of the batch
+    pub targets: Vec<String>,
+
+    /// Targets published successfully
+    #[serde(default)]
+    pub completed: Vec<String>,
+}
+
+/// Targets a batch publish finished, so `--resume` continues with the
+/// remaining ones
+pub struct BatchProgress {
+    path: PathBuf,
+    data: tokio::sync::Mutex<BatchProgressData>,
+}
+
+impl BatchProgress {
+    /// Start tracking a new batch (replaces earlier progress)
+    pub async fn start<P: AsRef<Path>>(
+        project_path: P,
+        targets: Vec<String>,
+    ) -> Result<Self, std::io::Error> {
+        let progress = Self {
+            path: project_path.as_ref().join(BATCH_STATE_FILE),
+            data: tokio::sync::Mutex::new(BatchProgressData {
+                targets,
+                completed: Vec::new(),
+            }),
+        };
+        progress.save(&*progress.data.lock().await).await?;
+        Ok(progress)
+    }
+
+    /// Progress of an interrupted batch, if any
+    pub async fn restore<P: AsRef<Path>>(project_path: P) -> Result<Option<Self>, std::io::Error> {
+        let path = project_path.as_ref().join(BATCH_STATE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).await?;
+        let data: BatchProgressData = serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(Some(Self {
+            path,
+            data: tokio::sync::Mutex::new(data),
+        }))
+    }
+
+    /// Current progress
+    pub async fn data(&self) -> BatchProgressData {
+        self.data.lock().await.clone()
+    }
+
+    /// Record a successfully published target
+    pub async fn complete(&self, target: &str) -> Result<(), std::io::Error> {
+        let mut data = self.data.lock().await;
+        if !data.completed.iter().any(|t| t == target) {
+            data.completed.push(target.to_string());
+        }
+        self.save(&data).await
+    }
+
+    /// Remove the progress file once the batch is done
+    pub async fn clear(self) -> Result<(), std::io::Error> {
+        if self.path.exists() {
+            fs::remove_file(&self.path).await?;
+        }
+        Ok(())
+    }
+
+    async fn save(&self, data: &BatchProgressData) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let temp_file = self.path.with_extension("json.tmp");
+        fs::write(&temp_file, json).await?;
+        fs::rename(&temp_file, &self.path).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -403,4 +685,83 @@ mod tests {
         assert!(history.contains("Initial → Detecting"));
         assert!(history.contains("Detecting → Validating"));
     }
+
+    #[tokio::test]
+    async fn test_stage_artifacts_survive_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut state_machine = PublishStateMachine::with_state_key(temp_dir.path(), "crates.io");
+        state_machine.set_registry("crates.io");
+        state_machine.set_version("1.2.0");
+        state_machine.artifacts_mut().validation = Some(serde_json::json!({"valid": true}));
+        state_machine
+            .complete_stage(PublishState::Validating)
+            .await
+            .unwrap();
+        assert!(
+            temp_dir
+                .path()
+                .join(".publish-state-crates.io.json")
+                .exists()
+        );
+
+        let mut restored = PublishStateMachine::with_state_key(temp_dir.path(), "crates.io");
+        assert!(restored.restore().await.unwrap());
+        assert!(restored.artifacts().is_completed(PublishState::Validating));
+        assert!(!restored.artifacts().is_completed(PublishState::Publishing));
+        assert_eq!(restored.get_state_data().version.as_deref(), Some("1.2.0"));
+
+        restored.clear().await.unwrap();
+        assert!(restored.artifacts().is_empty());
+        assert!(!restored.has_saved_state());
+    }
+
+    #[tokio::test]
+    async fn test_batch_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let progress =
+            BatchProgress::start(temp_dir.path(), vec!["npm".to_string(), "pypi".to_string()])
+                .await
+                .unwrap();
+        progress.complete("npm").await.unwrap();
+
+        let restored = BatchProgress::restore(temp_dir.path())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.data().await.completed, vec!["npm"]);
+        restored.clear().await.unwrap();
+        assert!(
+            BatchProgress::restore(temp_dir.path())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saved_publish_states() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut default = PublishStateMachine::new(temp_dir.path());
+        default
+            .transition(PublishState::Detecting, None)
+            .await
+            .unwrap();
+        default.record_error("network down").await.unwrap();
+
+        let mut npm = PublishStateMachine::with_state_key(temp_dir.path(), "npm");
+        npm.set_registry("npm");
+        npm.transition(PublishState::Publishing, None)
+            .await
+            .unwrap();
+        std::fs::write(temp_dir.path().join(".publish-state-broken.json"), "{").unwrap();
+
+        let states = saved_publish_states(temp_dir.path()).await.unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].key.as_deref(), Some("npm"));
+        assert_eq!(states[0].data.registry.as_deref(), Some("npm"));
+        assert!(states[0].data.can_resume);
+        assert_eq!(states[1].key, None);
+        assert_eq!(states[1].data.error.as_deref(), Some("network down"));
+        assert_eq!(states[1].data.current_state, PublishState::Detecting);
+    }
 }
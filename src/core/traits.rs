@@ -3,6 +3,7 @@
 //! This module defines the fundamental abstractions for registry plugins,
 //! validation, publishing, and verification workflows.
 
+use super::error::PublishErrorKind;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -117,6 +118,9 @@ pub struct PublishResult {
     pub output: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Category of the failure (classified from `error` when not set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<PublishErrorKind>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -137,6 +141,36 @@ pub struct VerificationResult {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<PackageIntegrity>,
+}
+
+/// Integrity data reported by the registry for a published artifact
+///
+/// Stored in publish reports and analytics so that the artifact can later be
+/// re-downloaded and compared against the recorded hashes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageIntegrity {
+    /// Download URL of the published artifact (npm tarball, .crate file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// SHA-1 hex digest (npm dist.shasum)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shasum: Option<String>,
+    /// Subresource Integrity string (npm dist.integrity, e.g. "sha512-...")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<String>,
+    /// SHA-256 hex digest (crates.io checksum)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl PackageIntegrity {
+    /// Whether any hash is available
+    pub fn has_hash(&self) -> bool {
+        self.shasum.is_some() || self.integrity.is_some() || self.checksum.is_some()
+    }
 }
 
 // ============================================================================
@@ -248,6 +282,24 @@ pub trait RegistryPlugin: Send + Sync {
             error: Some(format!("Rollback not supported for version {}", version)),
         })
     }
+
+    /// List published prerelease versions superseded by a stable version
+    ///
+    /// Default implementation returns an empty list (cleanup not supported).
+    async fn superseded_prereleases(&self, _version: &str) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Deprecate (or yank) a published version (if supported)
+    ///
+    /// Default implementation returns an error indicating deprecation is not supported.
+    async fn deprecate(&self, version: &str, _message: &str) -> anyhow::Result<RollbackResult> {
+        Ok(RollbackResult {
+            success: false,
+            message: format!("{} does not support deprecation", self.name()),
+            error: Some(format!("Deprecation not supported for version {}", version)),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -341,6 +393,7 @@ mod tests {
             output: None,
             error: None,
             metadata: None,
+            error_kind: None,
         };
 
         assert!(result.success);
@@ -356,6 +409,7 @@ mod tests {
             output: None,
             error: Some("Authentication failed".to_string()),
             metadata: None,
+            error_kind: None,
         };
 
         assert!(!result.success);
@@ -370,6 +424,7 @@ mod tests {
             url: Some("https://registry.example.com/pkg".to_string()),
             error: None,
             metadata: None,
+            integrity: None,
         };
 
         assert!(result.verified);
@@ -0,0 +1,126 @@
+//! User-defined config variables
+//!
+//! `variables:` defines values that other strings of the configuration
+//! reference as `{{name}}`: hook commands, custom registry command
+//! templates, notification templates, tag and tap names, ... Variables may
+//! reference each other; a reference cycle is a configuration error.
+
+use super::error::PublishError;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `{{name}}` reference (whitespace inside the braces is allowed)
+static VARIABLE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_-]*)\s*\}\}").expect("valid variable pattern")
+});
+
+/// Resolve references between variables
+///
+/// Returns every variable with its references substituted; references to
+/// undefined names are kept as written.
+pub fn resolve(
+    variables: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, PublishError> {
+    let mut resolved = HashMap::new();
+    let mut names: Vec<&String> = variables.keys().collect();
+    names.sort();
+    for name in names {
+        resolve_one(name, variables, &mut resolved, &mut Vec::new())?;
+    }
+    Ok(resolved)
+}
+
+/// Resolve `name`, with `stack` holding the variables being resolved
+fn resolve_one(
+    name: &str,
+    variables: &HashMap<String, String>,
+    resolved: &mut HashMap<String, String>,
+    stack: &mut Vec<String>,
+) -> Result<(), PublishError> {
+    if resolved.contains_key(name) {
+        return Ok(());
+    }
+    if let Some(start) = stack.iter().position(|n| n == name) {
+        let mut cycle = stack[start..].to_vec();
+        cycle.push(name.to_string());
+        return Err(PublishError::ConfigError(format!(
+            "Variable reference cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    let value = &variables[name];
+    stack.push(name.to_string());
+    for reference in references(value) {
+        if variables.contains_key(&reference) {
+            resolve_one(&reference, variables, resolved, stack)?;
+        }
+    }
+    stack.pop();
+
+    let (value, _) = substitute(value, resolved);
+    resolved.insert(name.to_string(), value);
+    Ok(())
+}
+
+/// Names referenced by `input`
+pub fn references(input: &str) -> Vec<String> {
+    VARIABLE_PATTERN
+        .captures_iter(input)
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
+/// Substitute `{{name}}` references, returning the result and the names
+/// that are not defined (left as written)
+pub fn substitute(input: &str, variables: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut unknown = Vec::new();
+    let result =
+        VARIABLE_PATTERN.replace_all(input, |cap: &Captures| match variables.get(&cap[1]) {
+            Some(value) => value.clone(),
+            None => {
+                unknown.push(cap[1].to_string());
+                cap[0].to_string()
+            }
+        });
+    (result.into_owned(), unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_nested_variables() {
+        let resolved = resolve(&vars(&[
+            ("org", "acme"),
+            ("tap", "{{org}}/homebrew-tap"),
+            ("image", "ghcr.io/{{ tap }}:{{channel}}"),
+        ]))
+        .unwrap();
+        assert_eq!(resolved["tap"], "acme/homebrew-tap");
+        assert_eq!(resolved["image"], "ghcr.io/acme/homebrew-tap:{{channel}}");
+
+        let (result, unknown) = substitute("brew tap {{tap}} ({{missing}})", &resolved);
+        assert_eq!(result, "brew tap acme/homebrew-tap ({{missing}})");
+        assert_eq!(unknown, vec!["missing"]);
+    }
+
+    #[test]
+    fn test_reference_cycles_are_errors() {
+        let error = resolve(&vars(&[("a", "{{b}}"), ("b", "x-{{c}}"), ("c", "{{a}}")]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("a -> b -> c -> a"), "{}", error);
+
+        assert!(resolve(&vars(&[("self", "{{self}}")])).is_err());
+    }
+}
@@ -2,16 +2,22 @@ pub mod core;
 pub mod orchestration;
 pub mod plugins;
 pub mod security;
+pub mod supply_chain;
 pub mod validation;
 
 pub use core::*;
 pub use orchestration::{
-    AnalyticsOptions, AnalyticsRecord, BatchPublishOptions, BatchPublishResult, BatchPublisher,
-    PackagePublisher, PublishAnalytics, PublishOptions, PublishReport, PublishStatistics,
+    AnalyticsOptions, AnalyticsRecord, AuditExporter, BatchPublishOptions, BatchPublishResult,
+    BatchPublisher, ChangelogGenerator, PackagePublisher, PublishAnalytics, PublishOptions,
+    PublishReport, PublishStatistics, RollbackOptions, RollbackReport, WorkspacePackage,
+    WorkspaceScanner,
 };
 pub use plugins::{PluginLoader, RegistryType};
 pub use security::{
-    CommandError, SafeCommandExecutor, ScanReport, SecretFinding, SecretsScanner,
-    SecureTokenManager,
+    AuditReport, CommandError, CredentialCheck, CredentialPreflight, CredentialStatus,
+    CredentialValidator, DependencyAuditor, SafeCommandExecutor, ScanReport, SecretFinding,
+    SecretsScanner, SecureTokenManager,
+};
+pub use validation::{
+    BumpLevel, DependencyChecker, ManifestValidator, VersionBumper, VersionValidator, WarningBudget,
 };
-pub use validation::{DependencyChecker, ManifestValidator, VersionValidator};
@@ -4,16 +4,29 @@
 //! - Record publish attempts with detailed metadata
 //! - Filter and query records by various criteria
 //! - Calculate statistics (success rate, duration, etc.)
+//! - Per-day/week trends, p50/p95 durations and failure reasons grouped
+//!   across attempts, to spot flaky registries
 //! - Generate reports in Markdown and JSON formats
 //! - Persistent storage in JSON format
-
-use crate::orchestration::package_publisher::PublishReport;
-use chrono::{DateTime, Utc};
+//! - CSV/JSON export and import, merging the history of several CI runners
+//!   (deduplicated by record id)
+
+use crate::core::error::PublishErrorKind;
+use crate::core::traits::PackageIntegrity;
+use crate::orchestration::package_publisher::{PublishReport, RollbackReport};
+use crate::orchestration::prometheus;
+use crate::security::redaction::redact;
+use crate::validation::bundle_size::PackageSize;
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use tokio::fs;
 
+/// Analytics state recorded for rollback attempts
+pub const ROLLBACK_STATE: &str = "ROLLBACK";
+
 /// Analytics record for a single publish attempt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsRecord {
@@ -23,6 +36,9 @@ pub struct AnalyticsRecord {
     pub version: String,
     pub success: bool,
     pub error: Option<String>,
+    /// Category of the failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<PublishErrorKind>,
     pub duration: u64,
     pub timestamp: DateTime<Utc>,
     pub metadata: AnalyticsMetadata,
@@ -34,6 +50,11 @@ pub struct AnalyticsMetadata {
     pub state: String,
     pub warnings: Vec<String>,
     pub verification_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<PackageIntegrity>,
+    /// Packed/unpacked package size (npm `bundleSize`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_size: Option<PackageSize>,
 }
 
 /// Options for filtering analytics records
@@ -46,6 +67,72 @@ pub struct AnalyticsOptions {
     pub success_only: bool,
     pub failures_only: bool,
     pub limit: Option<usize>,
+    /// Period of the trend buckets in statistics
+    pub bucket: TrendBucket,
+}
+
+/// Period of a trend bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendBucket {
+    Day,
+    #[default]
+    Week,
+}
+
+impl TrendBucket {
+    /// Start of the bucket containing `timestamp` (weeks start on Monday)
+    pub fn start_of(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let date = timestamp.date_naive();
+        let date = match self {
+            TrendBucket::Day => date,
+            TrendBucket::Week => {
+                date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+        };
+        date.and_time(chrono::NaiveTime::MIN).and_utc()
+    }
+}
+
+impl FromStr for TrendBucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(TrendBucket::Day),
+            "week" | "weekly" => Ok(TrendBucket::Week),
+            other => Err(format!(
+                "Unknown trend bucket: {} (expected day or week)",
+                other
+            )),
+        }
+    }
+}
+
+/// Publish attempts of one trend bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendStatistics {
+    /// Start of the day or week
+    pub start: DateTime<Utc>,
+    pub attempts: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub success_rate: f64,
+    /// Median duration (ms)
+    pub duration_p50: u64,
+    /// 95th percentile duration (ms)
+    pub duration_p95: u64,
+}
+
+/// Failed attempts sharing an error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureReason {
+    /// First error, with numbers masked so repeated failures group together
+    pub reason: String,
+    pub count: usize,
+    /// Registries the failure occurred on
+    pub registries: Vec<String>,
+    pub last_seen: DateTime<Utc>,
 }
 
 /// Registry-specific statistics
@@ -59,6 +146,12 @@ pub struct RegistryStatistics {
     pub average_duration: f64,
     pub last_publish: DateTime<Utc>,
     pub last_version: String,
+    /// Median duration (ms)
+    #[serde(default)]
+    pub duration_p50: u64,
+    /// 95th percentile duration (ms)
+    #[serde(default)]
+    pub duration_p95: u64,
 }
 
 /// Overall publishing statistics
@@ -71,6 +164,21 @@ pub struct PublishStatistics {
     pub average_duration: f64,
     pub by_registry: HashMap<String, RegistryStatistics>,
     pub time_range: TimeRange,
+    /// Rollback attempts (not counted as publish attempts)
+    #[serde(default)]
+    pub rollback_count: usize,
+    /// Median duration (ms)
+    #[serde(default)]
+    pub duration_p50: u64,
+    /// 95th percentile duration (ms)
+    #[serde(default)]
+    pub duration_p95: u64,
+    /// Attempts per day or week, oldest first
+    #[serde(default)]
+    pub trends: Vec<TrendStatistics>,
+    /// Failed attempts grouped by error, most frequent first
+    #[serde(default)]
+    pub failure_reasons: Vec<FailureReason>,
 }
 
 /// Time range for statistics
@@ -91,6 +199,65 @@ pub struct AnalyticsReport {
     pub json_data: String,
 }
 
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Same structure as `analytics.json`; keeps every field
+    #[default]
+    Json,
+    /// One row per record with the [`CSV_COLUMNS`]
+    Csv,
+}
+
+impl ExportFormat {
+    /// Format implied by the file extension (`.csv`: CSV, else JSON)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => ExportFormat::Csv,
+            _ => ExportFormat::Json,
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            other => Err(format!(
+                "Unknown export format: {} (expected csv or json)",
+                other
+            )),
+        }
+    }
+}
+
+/// Columns of CSV exports (integrity and package size are not exported)
+pub const CSV_COLUMNS: [&str; 11] = [
+    "id",
+    "timestamp",
+    "registry",
+    "package_name",
+    "version",
+    "success",
+    "state",
+    "duration",
+    "error",
+    "warnings",
+    "verification_url",
+];
+
+/// Result of merging imported records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Records added to the history
+    pub imported: usize,
+    /// Records skipped because their id was already recorded
+    pub duplicates: usize,
+}
+
 /// Data file structure
 #[derive(Debug, Serialize, Deserialize)]
 struct AnalyticsDataFile {
@@ -140,7 +307,34 @@ impl PublishAnalytics {
     ///
     /// * `report` - Publishing report to record
     pub async fn record_publish(&mut self, report: &PublishReport) -> Result<(), anyhow::Error> {
-        let record = AnalyticsRecord {
+        let record = self.publish_record(report);
+        self.records.push(record);
+        self.save_records().await?;
+
+        Ok(())
+    }
+
+    /// Markdown summary of `reports` alone (e.g. the publishes of one run),
+    /// without loading or saving the analytics data
+    pub fn summarize(reports: &[PublishReport]) -> String {
+        let mut analytics = Self {
+            records: Vec::new(),
+            data_file_path: PathBuf::new(),
+        };
+        analytics.records = reports
+            .iter()
+            .map(|r| analytics.publish_record(r))
+            .collect();
+
+        let options = AnalyticsOptions::default();
+        analytics.generate_markdown_summary(
+            &analytics.get_statistics(&options),
+            &analytics.get_records(&options),
+        )
+    }
+
+    fn publish_record(&self, report: &PublishReport) -> AnalyticsRecord {
+        AnalyticsRecord {
             id: self.generate_id(),
             registry: report.registry.clone(),
             package_name: report.package_name.clone(),
@@ -151,12 +345,44 @@ impl PublishAnalytics {
             } else {
                 Some(report.errors.join("; "))
             },
+            error_kind: report.error_kind.clone(),
             duration: report.duration,
             timestamp: report.published_at.unwrap_or_else(Utc::now),
             metadata: AnalyticsMetadata {
                 state: report.state.clone(),
                 warnings: report.warnings.clone(),
                 verification_url: report.verification_url.clone(),
+                integrity: report.integrity.clone(),
+                package_size: report.package_size,
+            },
+        }
+    }
+
+    /// Record a rollback attempt
+    ///
+    /// Rollbacks are stored with state `ROLLBACK` and are counted separately
+    /// from publish attempts in statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - Rollback report to record
+    pub async fn record_rollback(&mut self, report: &RollbackReport) -> Result<(), anyhow::Error> {
+        let record = AnalyticsRecord {
+            id: self.generate_id(),
+            registry: report.registry.clone(),
+            package_name: report.package_name.clone(),
+            version: report.version.clone(),
+            success: report.success,
+            error: report.error.clone(),
+            error_kind: None,
+            duration: report.duration,
+            timestamp: report.rolled_back_at,
+            metadata: AnalyticsMetadata {
+                state: ROLLBACK_STATE.to_string(),
+                warnings: vec![report.message.clone()],
+                verification_url: None,
+                integrity: None,
+                package_size: None,
             },
         };
 
@@ -218,7 +444,7 @@ impl PublishAnalytics {
             .collect();
 
         // Sort by timestamp descending (most recent first)
-        filtered.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        filtered.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
 
         // Apply limit
         if let Some(limit) = options.limit {
@@ -228,6 +454,32 @@ impl PublishAnalytics {
         filtered
     }
 
+    /// Package size of the latest successful release before `version`
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - Registry name
+    /// * `package_name` - Package name
+    /// * `version` - Version being published (excluded)
+    pub fn previous_package_size(
+        &self,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+    ) -> Option<PackageSize> {
+        self.records
+            .iter()
+            .filter(|r| {
+                r.success
+                    && r.metadata.state == "SUCCESS"
+                    && r.registry == registry
+                    && r.package_name == package_name
+                    && r.version != version
+            })
+            .max_by_key(|r| r.timestamp)
+            .and_then(|r| r.metadata.package_size)
+    }
+
     /// Calculate statistics from records
     ///
     /// # Arguments
@@ -238,10 +490,16 @@ impl PublishAnalytics {
     ///
     /// Comprehensive publishing statistics
     pub fn get_statistics(&self, options: &AnalyticsOptions) -> PublishStatistics {
-        let records = self.get_records(options);
+        let (rollbacks, records): (Vec<_>, Vec<_>) = self
+            .get_records(options)
+            .into_iter()
+            .partition(|r| r.metadata.state == ROLLBACK_STATE);
 
         if records.is_empty() {
-            return self.get_empty_statistics();
+            return PublishStatistics {
+                rollback_count: rollbacks.len(),
+                ..self.get_empty_statistics()
+            };
         }
 
         let success_count = records.iter().filter(|r| r.success).count();
@@ -256,6 +514,7 @@ impl PublishAnalytics {
         let timestamps: Vec<_> = records.iter().map(|r| r.timestamp).collect();
         let start = *timestamps.iter().min().unwrap();
         let end = *timestamps.iter().max().unwrap();
+        let durations: Vec<u64> = records.iter().map(|r| r.duration).collect();
 
         PublishStatistics {
             total_attempts: records.len(),
@@ -265,6 +524,11 @@ impl PublishAnalytics {
             average_duration,
             by_registry,
             time_range: TimeRange { start, end },
+            rollback_count: rollbacks.len(),
+            duration_p50: percentile(&durations, 50),
+            duration_p95: percentile(&durations, 95),
+            trends: calculate_trends(&records, options.bucket),
+            failure_reasons: group_failure_reasons(&records),
         }
     }
 
@@ -309,6 +573,143 @@ impl PublishAnalytics {
         Ok(())
     }
 
+    /// Export the records matching `options`, oldest first
+    pub fn export(
+        &self,
+        options: &AnalyticsOptions,
+        format: ExportFormat,
+    ) -> Result<String, anyhow::Error> {
+        let mut records = self.get_records(options);
+        records.reverse();
+        match format {
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(&AnalyticsDataFile {
+                version: "1.0".to_string(),
+                records,
+                last_updated: Utc::now().to_rfc3339(),
+            })?),
+            ExportFormat::Csv => {
+                let mut csv = CSV_COLUMNS.join(",");
+                csv.push('\n');
+                for record in &records {
+                    let fields = [
+                        record.id.clone(),
+                        record.timestamp.to_rfc3339(),
+                        record.registry.clone(),
+                        record.package_name.clone(),
+                        record.version.clone(),
+                        record.success.to_string(),
+                        record.metadata.state.clone(),
+                        record.duration.to_string(),
+                        record.error.clone().unwrap_or_default(),
+                        record.metadata.warnings.join("\n"),
+                        record.metadata.verification_url.clone().unwrap_or_default(),
+                    ];
+                    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                    csv.push_str(&row.join(","));
+                    csv.push('\n');
+                }
+                Ok(csv)
+            }
+        }
+    }
+
+    /// Records of an export (or another project's `analytics.json`)
+    pub fn parse_export(
+        content: &str,
+        format: ExportFormat,
+    ) -> Result<Vec<AnalyticsRecord>, anyhow::Error> {
+        match format {
+            ExportFormat::Json => {
+                let value: serde_json::Value = serde_json::from_str(content)?;
+                let records = match value {
+                    serde_json::Value::Array(_) => value,
+                    mut data => data
+                        .get_mut("records")
+                        .map(serde_json::Value::take)
+                        .ok_or_else(|| anyhow::anyhow!("JSON export has no records"))?,
+                };
+                Ok(serde_json::from_value(records)?)
+            }
+            ExportFormat::Csv => {
+                let mut rows = parse_csv(content).into_iter();
+                let header = rows
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("CSV export is empty"))?;
+                let column = |name: &str| {
+                    header
+                        .iter()
+                        .position(|h| h == name)
+                        .ok_or_else(|| anyhow::anyhow!("CSV export has no {} column", name))
+                };
+                let columns = CSV_COLUMNS
+                    .iter()
+                    .map(|name| column(name))
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows.enumerate()
+                    .filter(|(_, row)| row.iter().any(|field| !field.is_empty()))
+                    .map(|(index, row)| {
+                        let field = |i: usize| row.get(columns[i]).cloned().unwrap_or_default();
+                        let optional = |i: usize| Some(field(i)).filter(|f| !f.is_empty());
+                        let line = index + 2;
+                        Ok(AnalyticsRecord {
+                            id: field(0),
+                            timestamp: DateTime::parse_from_rfc3339(&field(1))
+                                .map_err(|e| anyhow::anyhow!("line {}: timestamp: {}", line, e))?
+                                .with_timezone(&Utc),
+                            registry: field(2),
+                            package_name: field(3),
+                            version: field(4),
+                            success: field(5)
+                                .parse()
+                                .map_err(|e| anyhow::anyhow!("line {}: success: {}", line, e))?,
+                            duration: field(7)
+                                .parse()
+                                .map_err(|e| anyhow::anyhow!("line {}: duration: {}", line, e))?,
+                            error: optional(8),
+                            error_kind: None,
+                            metadata: AnalyticsMetadata {
+                                state: field(6),
+                                warnings: optional(9)
+                                    .map(|w| w.lines().map(str::to_string).collect())
+                                    .unwrap_or_default(),
+                                verification_url: optional(10),
+                                integrity: None,
+                                package_size: None,
+                            },
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// All records in the Prometheus text format (see [`prometheus::render`])
+    pub fn prometheus_metrics(&self) -> String {
+        prometheus::render(&self.records)
+    }
+
+    /// Merge `records` into the history, skipping ids already recorded
+    pub async fn import(
+        &mut self,
+        records: Vec<AnalyticsRecord>,
+    ) -> Result<ImportSummary, anyhow::Error> {
+        let mut ids: HashSet<String> = self.records.iter().map(|r| r.id.clone()).collect();
+        let mut summary = ImportSummary::default();
+        for record in records {
+            if ids.insert(record.id.clone()) {
+                self.records.push(record);
+                summary.imported += 1;
+            } else {
+                summary.duplicates += 1;
+            }
+        }
+        if summary.imported > 0 {
+            self.records.sort_by_key(|r| r.timestamp);
+            self.save_records().await?;
+        }
+        Ok(summary)
+    }
+
     // Private methods
 
     fn generate_id(&self) -> String {
@@ -332,7 +733,8 @@ impl PublishAnalytics {
             last_updated: Utc::now().to_rfc3339(),
         };
 
-        let json = serde_json::to_string_pretty(&data)?;
+        // Errors and warnings carry command output, which may embed tokens
+        let json = redact(&serde_json::to_string_pretty(&data)?);
         fs::write(&self.data_file_path, json).await?;
 
         Ok(())
@@ -362,6 +764,7 @@ impl PublishAnalytics {
 
                 // Find the most recent publish
                 let most_recent = reg_records.iter().max_by_key(|r| r.timestamp).unwrap();
+                let durations: Vec<u64> = reg_records.iter().map(|r| r.duration).collect();
 
                 let stats = RegistryStatistics {
                     registry: registry.clone(),
@@ -372,6 +775,8 @@ impl PublishAnalytics {
                     average_duration: total_duration as f64 / attempts as f64,
                     last_publish: most_recent.timestamp,
                     last_version: most_recent.version.clone(),
+                    duration_p50: percentile(&durations, 50),
+                    duration_p95: percentile(&durations, 95),
                 };
 
                 (registry, stats)
@@ -391,6 +796,11 @@ impl PublishAnalytics {
                 start: Utc::now(),
                 end: Utc::now(),
             },
+            rollback_count: 0,
+            duration_p50: 0,
+            duration_p95: 0,
+            trends: Vec::new(),
+            failure_reasons: Vec::new(),
         }
     }
 
@@ -426,14 +836,22 @@ impl PublishAnalytics {
         ));
         lines.push(format!("- **Successful**: {}", statistics.success_count));
         lines.push(format!("- **Failed**: {}", statistics.failure_count));
+        if statistics.rollback_count > 0 {
+            lines.push(format!("- **Rollbacks**: {}", statistics.rollback_count));
+        }
         lines.push(format!(
             "- **Success Rate**: {:.2}%",
             statistics.success_rate
         ));
         lines.push(format!(
-            "- **Average Duration**: {:.2}s\n",
+            "- **Average Duration**: {:.2}s",
             statistics.average_duration / 1000.0
         ));
+        lines.push(format!(
+            "- **Duration p50 / p95**: {:.2}s / {:.2}s\n",
+            statistics.duration_p50 as f64 / 1000.0,
+            statistics.duration_p95 as f64 / 1000.0
+        ));
 
         // Time Range
         if statistics.total_attempts > 0 {
@@ -452,23 +870,70 @@ impl PublishAnalytics {
         if !statistics.by_registry.is_empty() {
             lines.push("## Registry Statistics\n".to_string());
             lines.push(
-                "| Registry | Attempts | Successes | Failures | Success Rate | Avg Duration |"
+                "| Registry | Attempts | Successes | Failures | Success Rate | Avg Duration | p50 | p95 |"
                     .to_string(),
             );
             lines.push(
-                "|----------|----------|-----------|----------|--------------|--------------|"
+                "|----------|----------|-----------|----------|--------------|--------------|-----|-----|"
                     .to_string(),
             );
 
             for stats in statistics.by_registry.values() {
                 lines.push(format!(
-                    "| {} | {} | {} | {} | {:.1}% | {:.2}s |",
+                    "| {} | {} | {} | {} | {:.1}% | {:.2}s | {:.2}s | {:.2}s |",
                     stats.registry,
                     stats.attempts,
                     stats.successes,
                     stats.failures,
                     stats.success_rate,
-                    stats.average_duration / 1000.0
+                    stats.average_duration / 1000.0,
+                    stats.duration_p50 as f64 / 1000.0,
+                    stats.duration_p95 as f64 / 1000.0
+                ));
+            }
+            lines.push(String::new());
+        }
+
+        // Trends
+        if statistics.trends.len() > 1 {
+            let rates: Vec<f64> = statistics.trends.iter().map(|t| t.success_rate).collect();
+            let p95: Vec<f64> = statistics
+                .trends
+                .iter()
+                .map(|t| t.duration_p95 as f64)
+                .collect();
+            lines.push("## Trends\n".to_string());
+            lines.push(format!("- **Success Rate**: `{}`", sparkline(&rates)));
+            lines.push(format!("- **Duration p95**: `{}`\n", sparkline(&p95)));
+            lines.push("| Period | Attempts | Failures | Success Rate | p50 | p95 |".to_string());
+            lines.push("|--------|----------|----------|--------------|-----|-----|".to_string());
+            for trend in &statistics.trends {
+                lines.push(format!(
+                    "| {} | {} | {} | {:.1}% {} | {:.2}s | {:.2}s |",
+                    trend.start.format("%Y-%m-%d"),
+                    trend.attempts,
+                    trend.failures,
+                    trend.success_rate,
+                    rate_bar(trend.success_rate),
+                    trend.duration_p50 as f64 / 1000.0,
+                    trend.duration_p95 as f64 / 1000.0
+                ));
+            }
+            lines.push(String::new());
+        }
+
+        // Failure Reasons
+        if !statistics.failure_reasons.is_empty() {
+            lines.push("## Failure Reasons\n".to_string());
+            lines.push("| Count | Reason | Registries | Last Seen |".to_string());
+            lines.push("|-------|--------|------------|-----------|".to_string());
+            for failure in &statistics.failure_reasons {
+                lines.push(format!(
+                    "| {} | {} | {} | {} |",
+                    failure.count,
+                    failure.reason.replace('|', "\\|"),
+                    failure.registries.join(", "),
+                    failure.last_seen.format("%Y-%m-%d")
                 ));
             }
             lines.push(String::new());
@@ -520,10 +985,15 @@ impl PublishAnalytics {
                 "totalAttempts": statistics.total_attempts,
                 "successCount": statistics.success_count,
                 "failureCount": statistics.failure_count,
+                "rollbackCount": statistics.rollback_count,
                 "successRate": statistics.success_rate,
                 "averageDuration": statistics.average_duration,
+                "durationP50": statistics.duration_p50,
+                "durationP95": statistics.duration_p95,
                 "byRegistry": statistics.by_registry.values().collect::<Vec<_>>(),
                 "timeRange": statistics.time_range,
+                "trends": statistics.trends,
+                "failureReasons": statistics.failure_reasons,
             },
             "recentPublishes": recent_publishes,
         });
@@ -532,7 +1002,162 @@ impl PublishAnalytics {
     }
 }
 
+/// Nearest-rank percentile of `values` (0 when empty)
+fn percentile(values: &[u64], percent: usize) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Publish attempts per bucket, oldest first
+fn calculate_trends(records: &[AnalyticsRecord], bucket: TrendBucket) -> Vec<TrendStatistics> {
+    let mut buckets: std::collections::BTreeMap<DateTime<Utc>, Vec<&AnalyticsRecord>> =
+        std::collections::BTreeMap::new();
+    for record in records {
+        buckets
+            .entry(bucket.start_of(record.timestamp))
+            .or_default()
+            .push(record);
+    }
+    buckets
+        .into_iter()
+        .map(|(start, records)| {
+            let successes = records.iter().filter(|r| r.success).count();
+            let durations: Vec<u64> = records.iter().map(|r| r.duration).collect();
+            TrendStatistics {
+                start,
+                attempts: records.len(),
+                successes,
+                failures: records.len() - successes,
+                success_rate: (successes as f64 / records.len() as f64) * 100.0,
+                duration_p50: percentile(&durations, 50),
+                duration_p95: percentile(&durations, 95),
+            }
+        })
+        .collect()
+}
+
+/// First error of a failed attempt with numbers masked (`E403` stays,
+/// `took 1532ms` becomes `took Nms`)
+fn failure_reason(record: &AnalyticsRecord) -> String {
+    let Some(error) = record.error.as_deref() else {
+        return "(no error recorded)".to_string();
+    };
+    let first = error.split("; ").next().unwrap_or(error).trim();
+    let mut reason = String::new();
+    let mut in_word = false;
+    let mut chars = first.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() && !in_word {
+            while chars.next_if(|n| n.is_ascii_digit()).is_some() {}
+            reason.push('N');
+            in_word = true;
+        } else {
+            reason.push(c);
+            in_word = c.is_alphanumeric();
+        }
+    }
+    reason.chars().take(120).collect()
+}
+
+/// Failed attempts grouped by [`failure_reason`], most frequent first
+fn group_failure_reasons(records: &[AnalyticsRecord]) -> Vec<FailureReason> {
+    let mut groups: Vec<FailureReason> = Vec::new();
+    for record in records.iter().filter(|r| !r.success) {
+        let reason = failure_reason(record);
+        let group = match groups.iter_mut().find(|g| g.reason == reason) {
+            Some(group) => group,
+            None => {
+                groups.push(FailureReason {
+                    reason,
+                    count: 0,
+                    registries: Vec::new(),
+                    last_seen: record.timestamp,
+                });
+                groups.last_mut().unwrap()
+            }
+        };
+        group.count += 1;
+        group.last_seen = group.last_seen.max(record.timestamp);
+        if !group.registries.contains(&record.registry) {
+            group.registries.push(record.registry.clone());
+        }
+    }
+    for group in &mut groups {
+        group.registries.sort();
+    }
+    groups.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+    groups
+}
+
+/// Sparkline of `values` scaled between their minimum and maximum
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max > min {
+                BARS[(((v - min) / (max - min)) * 7.0).round() as usize]
+            } else {
+                BARS[3]
+            }
+        })
+        .collect()
+}
+
+/// Ten-character bar of a percentage
+fn rate_bar(rate: f64) -> String {
+    let filled = (rate / 10.0).round().clamp(0.0, 10.0) as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(10 - filled))
+}
+
+/// Quote a CSV field when needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Rows of a CSV document (quoted fields may contain commas and newlines)
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -542,6 +1167,32 @@ mod tests {
         assert_eq!(analytics.records.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_rollbacks_counted_separately() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut analytics = PublishAnalytics::new(temp_dir.path());
+
+        let report = RollbackReport {
+            success: true,
+            registry: "crates.io".to_string(),
+            package_name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            message: "yanked".to_string(),
+            error: None,
+            duration: 120,
+            rolled_back_at: Utc::now(),
+        };
+        analytics.record_rollback(&report).await.unwrap();
+
+        let records = analytics.get_records(&AnalyticsOptions::default());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].metadata.state, ROLLBACK_STATE);
+
+        let statistics = analytics.get_statistics(&AnalyticsOptions::default());
+        assert_eq!(statistics.total_attempts, 0);
+        assert_eq!(statistics.rollback_count, 1);
+    }
+
     #[test]
     fn test_analytics_options_default() {
         let options = AnalyticsOptions::default();
@@ -549,4 +1200,125 @@ mod tests {
         assert_eq!(options.success_only, false);
         assert_eq!(options.failures_only, false);
     }
+
+    fn record(id: &str, minutes_ago: i64) -> AnalyticsRecord {
+        AnalyticsRecord {
+            id: id.to_string(),
+            registry: "npm".to_string(),
+            package_name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            success: false,
+            error: Some("E403, \"forbidden\"".to_string()),
+            error_kind: Some(PublishErrorKind::AuthFailed),
+            duration: 1500,
+            timestamp: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            metadata: AnalyticsMetadata {
+                state: "FAILED".to_string(),
+                warnings: vec!["no README".to_string(), "no license".to_string()],
+                verification_url: None,
+                integrity: None,
+                package_size: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let runner = tempfile::TempDir::new().unwrap();
+        let mut analytics = PublishAnalytics::new(runner.path());
+        analytics
+            .import(vec![record("a", 10), record("b", 5)])
+            .await
+            .unwrap();
+
+        for format in [ExportFormat::Csv, ExportFormat::Json] {
+            let export = analytics
+                .export(&AnalyticsOptions::default(), format)
+                .unwrap();
+            let records = PublishAnalytics::parse_export(&export, format).unwrap();
+            assert_eq!(records.len(), 2);
+            assert_eq!(records[0].id, "a");
+            assert_eq!(records[0].error.as_deref(), Some("E403, \"forbidden\""));
+            assert_eq!(records[0].metadata.warnings.len(), 2);
+            assert_eq!(records[1].timestamp, analytics.records[1].timestamp);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_deduplicates_by_id() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut analytics = PublishAnalytics::new(temp_dir.path());
+        analytics.import(vec![record("a", 10)]).await.unwrap();
+
+        let summary = analytics
+            .import(vec![record("b", 20), record("a", 10), record("b", 20)])
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 1,
+                duplicates: 2
+            }
+        );
+
+        let mut reloaded = PublishAnalytics::new(temp_dir.path());
+        reloaded.initialize().await.unwrap();
+        let ids: Vec<_> = reloaded.records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_trends_percentiles_and_failure_reasons() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut analytics = PublishAnalytics::new(temp_dir.path());
+        let monday = DateTime::parse_from_rfc3339("2026-03-02T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut records = Vec::new();
+        for (i, day) in [0, 1, 2, 7, 8].into_iter().enumerate() {
+            let mut record = record(&i.to_string(), 0);
+            record.timestamp = monday + chrono::Duration::days(day);
+            record.duration = (i as u64 + 1) * 1000;
+            record.success = i % 2 == 1;
+            record.error = (!record.success).then(|| format!("E503 timeout after {}ms; retry", i));
+            if i == 4 {
+                record.registry = "pypi".to_string();
+            }
+            records.push(record);
+        }
+        analytics.import(records).await.unwrap();
+
+        let statistics = analytics.get_statistics(&AnalyticsOptions::default());
+        assert_eq!(statistics.duration_p50, 3000);
+        assert_eq!(statistics.duration_p95, 5000);
+        assert_eq!(statistics.trends.len(), 2);
+        assert_eq!(statistics.trends[0].attempts, 3);
+        assert_eq!(
+            statistics.trends[0].start.to_rfc3339(),
+            "2026-03-02T00:00:00+00:00"
+        );
+        assert_eq!(statistics.trends[1].failures, 1);
+        assert_eq!(statistics.failure_reasons.len(), 1);
+        assert_eq!(
+            statistics.failure_reasons[0].reason,
+            "E503 timeout after Nms"
+        );
+        assert_eq!(statistics.failure_reasons[0].count, 3);
+        assert_eq!(statistics.failure_reasons[0].registries, ["npm", "pypi"]);
+
+        let daily = analytics.get_statistics(&AnalyticsOptions {
+            bucket: TrendBucket::Day,
+            ..Default::default()
+        });
+        assert_eq!(daily.trends.len(), 5);
+
+        let report = analytics
+            .generate_report(&AnalyticsOptions::default())
+            .await
+            .unwrap();
+        assert!(report.markdown_summary.contains("## Trends"));
+        assert!(report.markdown_summary.contains("## Failure Reasons"));
+        assert!(report.json_data.contains("failureReasons"));
+    }
 }
@@ -0,0 +1,748 @@
+//! Approval gate - a second person signs off before publishing
+//!
+//! When `approval.enabled` is set, publishing pauses in the
+//! `WAITING_APPROVAL` state after confirmation and posts an approval request:
+//! - `slack`: a bot message (`chat.postMessage`) in `approval.channel`;
+//!   approvers react with ✅ (`white_check_mark`) to approve or ❌ (`x`) to
+//!   reject. Reactions are polled, so no public endpoint for interactive
+//!   message callbacks is needed.
+//! - `github`: an issue in the repository; approvers comment `/approve` or
+//!   `/reject`.
+//!
+//! The first decision of one of `approvers` (anyone, if unset) counts. The
+//! request is closed with the decision; without one before `timeout`,
+//! publishing is aborted.
+
+use crate::core::config::ApprovalConfig;
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::orchestration::duration_budget::parse_duration;
+use crate::orchestration::release::parse_github_repository;
+use crate::validation::VersionValidator;
+use reqwest::Method;
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const SLACK_API_URL: &str = "https://slack.com/api";
+const DEFAULT_TIMEOUT: &str = "30m";
+const DEFAULT_POLL_INTERVAL: &str = "15s";
+
+/// Reactions approving a Slack request (skin tones are ignored)
+const APPROVE_REACTIONS: &[&str] = &["white_check_mark", "heavy_check_mark", "+1"];
+/// Reactions rejecting a Slack request
+const REJECT_REACTIONS: &[&str] = &["x", "no_entry", "-1"];
+
+/// Issue comments approving a GitHub request
+const APPROVE_COMMENTS: &[&str] = &["/approve", "approve", "approved"];
+/// Issue comments rejecting a GitHub request
+const REJECT_COMMENTS: &[&str] = &["/reject", "reject", "rejected"];
+
+/// Where approval requests are posted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalProvider {
+    Slack,
+    GitHub,
+}
+
+impl ApprovalProvider {
+    fn default_token_env(&self) -> &'static str {
+        match self {
+            ApprovalProvider::Slack => "SLACK_BOT_TOKEN",
+            ApprovalProvider::GitHub => "GITHUB_TOKEN",
+        }
+    }
+}
+
+impl FromStr for ApprovalProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "slack" => Ok(ApprovalProvider::Slack),
+            "github" => Ok(ApprovalProvider::GitHub),
+            other => anyhow::bail!(
+                "Unknown approval provider \"{}\" (expected slack or github)",
+                other
+            ),
+        }
+    }
+}
+
+/// Outcome of an approval request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalDecision {
+    Approved { approver: String },
+    Rejected { approver: String },
+    TimedOut,
+}
+
+impl ApprovalDecision {
+    pub fn is_approved(&self) -> bool {
+        matches!(self, ApprovalDecision::Approved { .. })
+    }
+}
+
+impl fmt::Display for ApprovalDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalDecision::Approved { approver } => write!(f, "approved by {}", approver),
+            ApprovalDecision::Rejected { approver } => write!(f, "rejected by {}", approver),
+            ApprovalDecision::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+/// A posted approval request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalTicket {
+    /// Slack message `ts` in `channel`
+    Slack { channel: String, ts: String },
+    /// GitHub issue
+    GitHub {
+        repository: String,
+        number: u64,
+        url: String,
+    },
+}
+
+impl fmt::Display for ApprovalTicket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalTicket::Slack { channel, .. } => write!(f, "Slack {}", channel),
+            ApprovalTicket::GitHub { url, .. } => write!(f, "{}", url),
+        }
+    }
+}
+
+/// `chat.postMessage` response (subset)
+#[derive(Debug, Deserialize)]
+struct SlackMessageResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    ts: Option<String>,
+}
+
+/// `reactions.get` response (subset)
+#[derive(Debug, Deserialize)]
+struct SlackReactionsResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    message: Option<SlackReactedMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackReactedMessage {
+    #[serde(default)]
+    reactions: Vec<SlackReaction>,
+}
+
+/// A reaction on the request message
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlackReaction {
+    pub name: String,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// `POST /repos/{owner}/{repo}/issues` response (subset)
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    number: u64,
+    html_url: String,
+}
+
+/// A comment on the request issue
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueComment {
+    pub user: IssueCommentUser,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueCommentUser {
+    pub login: String,
+}
+
+/// Posts approval requests and waits for the decision
+pub struct ApprovalGate {
+    project_path: PathBuf,
+    config: ApprovalConfig,
+    provider: ApprovalProvider,
+    timeout: Duration,
+    poll_interval: Duration,
+    remote: String,
+    slack_api_url: String,
+    github: GitHubClient,
+    client: reqwest::Client,
+}
+
+impl ApprovalGate {
+    /// Create a gate from the `approval` section
+    pub fn from_config(
+        project_path: impl AsRef<Path>,
+        config: ApprovalConfig,
+    ) -> anyhow::Result<Self> {
+        let provider: ApprovalProvider = config.provider.parse()?;
+        if provider == ApprovalProvider::Slack && config.channel.is_none() {
+            anyhow::bail!("approval.channel is required for Slack approvals");
+        }
+        let timeout = parse_duration(config.timeout.as_deref().unwrap_or(DEFAULT_TIMEOUT))?;
+        let poll_interval = parse_duration(
+            config
+                .poll_interval
+                .as_deref()
+                .unwrap_or(DEFAULT_POLL_INTERVAL),
+        )?;
+
+        Ok(Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            config,
+            provider,
+            timeout: Duration::from_millis(timeout),
+            poll_interval: Duration::from_millis(poll_interval),
+            remote: "origin".to_string(),
+            slack_api_url: SLACK_API_URL.to_string(),
+            github: GitHubClient::new(),
+            client: http_client(),
+        })
+    }
+
+    /// Git remote used to find the GitHub repository (`release.remote`)
+    pub fn with_remote(mut self, remote: impl Into<String>) -> Self {
+        self.remote = remote.into();
+        self
+    }
+
+    /// Use another Slack Web API endpoint
+    pub fn with_slack_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.slack_api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    /// How long [`wait`](Self::wait) waits for a decision
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether publishing `version` to `registry` needs approval
+    pub fn is_required(&self, registry: &str, version: &str) -> bool {
+        if !self.config.enabled.unwrap_or(false) {
+            return false;
+        }
+        if let Some(ref registries) = self.config.registries
+            && !registries.iter().any(|r| r == registry)
+        {
+            return false;
+        }
+        self.config.prereleases.unwrap_or(false) || !VersionValidator::new().is_prerelease(version)
+    }
+
+    fn token(&self) -> anyhow::Result<String> {
+        let token_env = self
+            .config
+            .token_env
+            .as_deref()
+            .unwrap_or(self.provider.default_token_env());
+        std::env::var(token_env)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{} is not set", token_env))
+    }
+
+    fn approvers(&self) -> &[String] {
+        self.config.approvers.as_deref().unwrap_or_default()
+    }
+
+    /// Post the approval request
+    pub async fn post(
+        &self,
+        package_name: &str,
+        version: &str,
+        registry: &str,
+    ) -> anyhow::Result<ApprovalTicket> {
+        let token = self.token()?;
+        let title = format!(
+            "Approve publishing {}@{} to {}",
+            package_name, version, registry
+        );
+        match self.provider {
+            ApprovalProvider::Slack => {
+                let channel = self.config.channel.as_deref().unwrap_or_default();
+                let text = format!(
+                    "🔐 *{}*\nReact with :white_check_mark: to approve or :x: to reject{}.",
+                    title,
+                    approvers_note(self.approvers(), |u| format!("<@{}>", u))
+                );
+                let message = self
+                    .slack_post(
+                        &token,
+                        serde_json::json!({ "channel": channel, "text": text }),
+                    )
+                    .await?;
+                Ok(ApprovalTicket::Slack {
+                    channel: message.channel.unwrap_or_else(|| channel.to_string()),
+                    ts: message
+                        .ts
+                        .ok_or_else(|| anyhow::anyhow!("Slack response has no message ts"))?,
+                })
+            }
+            ApprovalProvider::GitHub => {
+                let repository = self.repository().await?;
+                let body = format!(
+                    "package-publisher is waiting to publish **{}@{}** to **{}**.\n\n\
+                     Comment `/approve` to approve or `/reject` to reject{}.",
+                    package_name,
+                    version,
+                    registry,
+                    approvers_note(self.approvers(), |u| format!("@{}", u))
+                );
+                let response = self
+                    .github
+                    .authenticated(&token)
+                    .request(Method::POST, &format!("/repos/{}/issues", repository))
+                    .json(&serde_json::json!({ "title": title, "body": body }))
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    anyhow::bail!("GitHub issue creation failed (HTTP {}): {}", status, text);
+                }
+                let issue: IssueResponse = response.json().await?;
+                Ok(ApprovalTicket::GitHub {
+                    repository,
+                    number: issue.number,
+                    url: issue.html_url,
+                })
+            }
+        }
+    }
+
+    /// Check the request for a decision
+    pub async fn poll(&self, ticket: &ApprovalTicket) -> anyhow::Result<Option<ApprovalDecision>> {
+        let token = self.token()?;
+        match ticket {
+            ApprovalTicket::Slack { channel, ts } => {
+                let response: SlackReactionsResponse = self
+                    .client
+                    .get(format!("{}/reactions.get", self.slack_api_url))
+                    .bearer_auth(&token)
+                    .query(&[("channel", channel.as_str()), ("timestamp", ts.as_str())])
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+                if !response.ok {
+                    anyhow::bail!(
+                        "Slack reactions.get failed: {}",
+                        response.error.unwrap_or_default()
+                    );
+                }
+                let reactions = response.message.map(|m| m.reactions).unwrap_or_default();
+                Ok(reaction_decision(&reactions, self.approvers()))
+            }
+            ApprovalTicket::GitHub {
+                repository, number, ..
+            } => {
+                let response = self
+                    .github
+                    .authenticated(&token)
+                    .request(
+                        Method::GET,
+                        &format!(
+                            "/repos/{}/issues/{}/comments?per_page=100",
+                            repository, number
+                        ),
+                    )
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("GitHub issue comments failed (HTTP {})", response.status());
+                }
+                let comments: Vec<IssueComment> = response.json().await?;
+                Ok(comment_decision(&comments, self.approvers()))
+            }
+        }
+    }
+
+    /// Poll the request until a decision or the timeout
+    pub async fn wait(&self, ticket: &ApprovalTicket) -> anyhow::Result<ApprovalDecision> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let Some(decision) = self.poll(ticket).await? {
+                return Ok(decision);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(ApprovalDecision::TimedOut);
+            }
+            tokio::time::sleep(self.poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Record the decision on the request (Slack thread reply / closed issue)
+    pub async fn close(
+        &self,
+        ticket: &ApprovalTicket,
+        decision: &ApprovalDecision,
+    ) -> anyhow::Result<()> {
+        let token = self.token()?;
+        match ticket {
+            ApprovalTicket::Slack { channel, ts } => {
+                let text = match decision {
+                    ApprovalDecision::Approved { approver } => {
+                        format!("✅ Approved by <@{}>, publishing", approver)
+                    }
+                    ApprovalDecision::Rejected { approver } => {
+                        format!("❌ Rejected by <@{}>, publishing aborted", approver)
+                    }
+                    ApprovalDecision::TimedOut => {
+                        "⌛ No decision in time, publishing aborted".to_string()
+                    }
+                };
+                self.slack_post(
+                    &token,
+                    serde_json::json!({ "channel": channel, "thread_ts": ts, "text": text }),
+                )
+                .await?;
+            }
+            ApprovalTicket::GitHub {
+                repository, number, ..
+            } => {
+                let (text, reason) = match decision {
+                    ApprovalDecision::Approved { approver } => (
+                        format!("✅ Approved by @{}, publishing.", approver),
+                        "completed",
+                    ),
+                    ApprovalDecision::Rejected { approver } => (
+                        format!("❌ Rejected by @{}, publishing aborted.", approver),
+                        "not_planned",
+                    ),
+                    ApprovalDecision::TimedOut => (
+                        "⌛ No decision in time, publishing aborted.".to_string(),
+                        "not_planned",
+                    ),
+                };
+                let github = self.github.authenticated(&token);
+                let issue_path = format!("/repos/{}/issues/{}", repository, number);
+                let response = github
+                    .request(Method::POST, &format!("{}/comments", issue_path))
+                    .json(&serde_json::json!({ "body": text }))
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("GitHub issue comment failed (HTTP {})", response.status());
+                }
+                let response = github
+                    .request(Method::PATCH, &issue_path)
+                    .json(&serde_json::json!({ "state": "closed", "state_reason": reason }))
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("GitHub issue update failed (HTTP {})", response.status());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn slack_post(
+        &self,
+        token: &str,
+        body: serde_json::Value,
+    ) -> anyhow::Result<SlackMessageResponse> {
+        let response: SlackMessageResponse = self
+            .client
+            .post(format!("{}/chat.postMessage", self.slack_api_url))
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+        if !response.ok {
+            anyhow::bail!(
+                "Slack chat.postMessage failed: {}",
+                response.error.as_deref().unwrap_or_default()
+            );
+        }
+        Ok(response)
+    }
+
+    /// `owner/repo` from config or the remote URL
+    async fn repository(&self) -> anyhow::Result<String> {
+        if let Some(ref repository) = self.config.repository {
+            return Ok(repository.clone());
+        }
+        let url = run_git(&self.project_path, &["remote", "get-url", &self.remote]).await?;
+        parse_github_repository(&url).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot determine GitHub repository from remote {} ({}); set approval.repository",
+                self.remote,
+                url
+            )
+        })
+    }
+}
+
+/// " (approvers: a, b)" suffix of the request text
+fn approvers_note(approvers: &[String], mention: impl Fn(&str) -> String) -> String {
+    if approvers.is_empty() {
+        return String::new();
+    }
+    let mentions: Vec<String> = approvers.iter().map(|a| mention(a)).collect();
+    format!(" (approvers: {})", mentions.join(", "))
+}
+
+fn is_approver(approvers: &[String], user: &str) -> bool {
+    approvers.is_empty() || approvers.iter().any(|a| a.eq_ignore_ascii_case(user))
+}
+
+/// Decision from the reactions on a Slack request; a rejection wins
+pub fn reaction_decision(
+    reactions: &[SlackReaction],
+    approvers: &[String],
+) -> Option<ApprovalDecision> {
+    let by = |names: &[&str]| {
+        reactions
+            .iter()
+            .filter(|r| names.contains(&r.name.split("::").next().unwrap_or_default()))
+            .flat_map(|r| &r.users)
+            .find(|user| is_approver(approvers, user))
+            .cloned()
+    };
+    if let Some(approver) = by(REJECT_REACTIONS) {
+        return Some(ApprovalDecision::Rejected { approver });
+    }
+    by(APPROVE_REACTIONS).map(|approver| ApprovalDecision::Approved { approver })
+}
+
+/// Decision from the comments on a GitHub request; the first one counts
+pub fn comment_decision(
+    comments: &[IssueComment],
+    approvers: &[String],
+) -> Option<ApprovalDecision> {
+    comments
+        .iter()
+        .filter(|c| is_approver(approvers, &c.user.login))
+        .find_map(|c| {
+            let command = c
+                .body
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase();
+            let approver = c.user.login.clone();
+            if APPROVE_COMMENTS.contains(&command.as_str()) {
+                Some(ApprovalDecision::Approved { approver })
+            } else if REJECT_COMMENTS.contains(&command.as_str()) {
+                Some(ApprovalDecision::Rejected { approver })
+            } else {
+                None
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn config(provider: &str) -> ApprovalConfig {
+        ApprovalConfig {
+            enabled: Some(true),
+            provider: provider.to_string(),
+            channel: Some("C0RELEASE".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn comment(login: &str, body: &str) -> IssueComment {
+        IssueComment {
+            user: IssueCommentUser {
+                login: login.to_string(),
+            },
+            body: body.to_string(),
+        }
+    }
+
+    /// Serve one canned JSON response per connection, returning the requests
+    async fn serve(listener: TcpListener, responses: Vec<&'static str>) -> Vec<String> {
+        let mut requests = Vec::new();
+        for body in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request).to_string());
+        }
+        requests
+    }
+
+    #[test]
+    fn test_config_and_is_required() {
+        assert!(ApprovalGate::from_config(".", config("email")).is_err());
+        assert!(
+            ApprovalGate::from_config(
+                ".",
+                ApprovalConfig {
+                    channel: None,
+                    ..config("slack")
+                }
+            )
+            .is_err()
+        );
+
+        let gate = ApprovalGate::from_config(
+            ".",
+            ApprovalConfig {
+                registries: Some(vec!["npm".to_string()]),
+                timeout: Some("2h".to_string()),
+                ..config("github")
+            },
+        )
+        .unwrap();
+        assert_eq!(gate.timeout(), Duration::from_secs(7200));
+        assert!(gate.is_required("npm", "1.2.0"));
+        assert!(!gate.is_required("npm", "1.2.0-rc.1"));
+        assert!(!gate.is_required("pypi", "1.2.0"));
+    }
+
+    #[test]
+    fn test_decisions_respect_approvers() {
+        let approvers = vec!["U2".to_string()];
+        let reactions = vec![
+            SlackReaction {
+                name: "white_check_mark".to_string(),
+                users: vec!["U1".to_string()],
+            },
+            SlackReaction {
+                name: "+1::skin-tone-3".to_string(),
+                users: vec!["U2".to_string()],
+            },
+        ];
+        assert_eq!(
+            reaction_decision(&reactions, &approvers),
+            Some(ApprovalDecision::Approved {
+                approver: "U2".to_string()
+            })
+        );
+        assert_eq!(reaction_decision(&reactions[..1], &approvers), None);
+
+        let comments = vec![
+            comment("mallory", "/approve"),
+            comment("octocat", "Looks good?"),
+            comment("Octocat", "/reject\nchangelog is missing"),
+            comment("octocat", "/approve"),
+        ];
+        let approvers = vec!["octocat".to_string()];
+        assert_eq!(
+            comment_decision(&comments, &approvers),
+            Some(ApprovalDecision::Rejected {
+                approver: "Octocat".to_string()
+            })
+        );
+        assert_eq!(
+            comment_decision(&comments, &[]),
+            Some(ApprovalDecision::Approved {
+                approver: "mallory".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_github_request_is_approved_and_closed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                r#"{"number":7,"html_url":"https://github.com/acme/demo/issues/7"}"#,
+                "[]",
+                r#"[{"user":{"login":"octocat"},"body":"/approve"}]"#,
+                "{}",
+                "{}",
+            ],
+        ));
+
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_APPROVAL_TOKEN", "t0ken");
+        }
+        let gate = ApprovalGate::from_config(
+            ".",
+            ApprovalConfig {
+                repository: Some("acme/demo".to_string()),
+                token_env: Some("PACKAGE_PUBLISHER_TEST_APPROVAL_TOKEN".to_string()),
+                poll_interval: Some("10ms".to_string()),
+                ..config("github")
+            },
+        )
+        .unwrap()
+        .with_github_api_url(url);
+
+        let ticket = gate.post("demo", "1.2.0", "npm").await.unwrap();
+        assert_eq!(ticket.to_string(), "https://github.com/acme/demo/issues/7");
+        let decision = gate.wait(&ticket).await.unwrap();
+        assert_eq!(
+            decision,
+            ApprovalDecision::Approved {
+                approver: "octocat".to_string()
+            }
+        );
+        gate.close(&ticket, &decision).await.unwrap();
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /repos/acme/demo/issues "));
+        assert!(requests[0].contains("Approve publishing demo@1.2.0 to npm"));
+        assert!(requests[1].starts_with("GET /repos/acme/demo/issues/7/comments"));
+        assert!(requests[3].starts_with("POST /repos/acme/demo/issues/7/comments"));
+        assert!(requests[4].starts_with("PATCH /repos/acme/demo/issues/7 "));
+        assert!(requests[4].contains(r#""state":"closed""#));
+    }
+}
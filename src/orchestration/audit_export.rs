@@ -0,0 +1,593 @@
+//! Release audit bundle export
+//!
+//! Collects everything recorded for a single release into one zip file:
+//! - Publish reports (per registry)
+//! - Secrets scan report
+//! - Validation results
+//! - SBOM documents (if generated)
+//! - Audit log (state transitions and analytics records)
+//! - Configuration snapshot (secrets redacted)
+//! - SHA-256 checksums of every bundled file plus registry integrity hashes
+//!
+//! Release artifacts are written by `PackagePublisher` during publishing into
+//! `.package-publisher/releases/<version>/` and bundled on demand by
+//! [`AuditExporter`].
+
+use crate::core::config::PublishConfig;
+use crate::orchestration::analytics::{AnalyticsOptions, AnalyticsRecord, PublishAnalytics};
+use crate::orchestration::package_publisher::PublishReport;
+use crate::security::SecureTokenManager;
+use crate::security::redaction::redact;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Directory (relative to project root) holding per-release artifacts
+const RELEASES_DIR: &str = ".package-publisher/releases";
+
+/// File name of the configuration snapshot inside a release directory
+const CONFIG_SNAPSHOT_FILE: &str = "config.yaml";
+
+/// Storage for artifacts produced while publishing a release
+pub struct ReleaseArtifactStore {
+    releases_dir: PathBuf,
+}
+
+impl ReleaseArtifactStore {
+    /// Create a new store rooted at the project directory
+    pub fn new<P: AsRef<Path>>(project_path: P) -> Self {
+        Self {
+            releases_dir: project_path.as_ref().join(RELEASES_DIR),
+        }
+    }
+
+    /// Directory holding artifacts for a version
+    pub fn release_dir(&self, version: &str) -> PathBuf {
+        self.releases_dir.join(sanitize_file_component(version))
+    }
+
+    /// Save a serializable artifact as pretty-printed JSON
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Release version
+    /// * `name` - Artifact name without extension (e.g. "scan-report")
+    /// * `value` - Artifact contents
+    pub async fn save_json<T: Serialize>(
+        &self,
+        version: &str,
+        name: &str,
+        value: &T,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let content = redact(&serde_json::to_string_pretty(value)?);
+        self.save_raw(
+            version,
+            &format!("{}.json", sanitize_file_component(name)),
+            content.as_bytes(),
+        )
+        .await
+    }
+
+    /// Save a configuration snapshot with secrets redacted
+    pub async fn save_config_snapshot(
+        &self,
+        version: &str,
+        config: &PublishConfig,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let content = redacted_config_yaml(config)?;
+        self.save_raw(version, CONFIG_SNAPSHOT_FILE, content.as_bytes())
+            .await
+    }
+
+    /// Save raw bytes under the given file name
+    pub async fn save_raw(
+        &self,
+        version: &str,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<PathBuf, anyhow::Error> {
+        let dir = self.release_dir(version);
+        fs::create_dir_all(&dir).await?;
+
+        let path = dir.join(file_name);
+        fs::write(&path, content).await?;
+        Ok(path)
+    }
+
+    /// List artifact files recorded for a version (sorted by name)
+    pub async fn list(&self, version: &str) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let dir = self.release_dir(version);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_file() {
+                files.push(entry.path());
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Summary of an exported audit bundle
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditBundleManifest {
+    pub version: String,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    /// Files contained in the bundle
+    pub files: Vec<String>,
+    /// Expected sections that had no recorded data
+    pub missing: Vec<String>,
+}
+
+/// Result of an audit export
+#[derive(Debug, Clone)]
+pub struct AuditExportResult {
+    /// Path of the written zip file
+    pub output_path: PathBuf,
+    pub manifest: AuditBundleManifest,
+}
+
+/// Audit log entry bundled as `audit-log.json`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditLog {
+    version: String,
+    analytics_records: Vec<AnalyticsRecord>,
+    state_history: Vec<serde_json::Value>,
+}
+
+/// Exports release audit bundles
+pub struct AuditExporter {
+    project_path: PathBuf,
+    store: ReleaseArtifactStore,
+}
+
+impl AuditExporter {
+    /// Create a new AuditExporter
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - Path to the project directory
+    pub fn new<P: AsRef<Path>>(project_path: P) -> Self {
+        let project_path = project_path.as_ref().to_path_buf();
+        Self {
+            store: ReleaseArtifactStore::new(&project_path),
+            project_path,
+        }
+    }
+
+    /// Default output path: `<project>/audit-<version>.zip`
+    pub fn default_output_path(&self, version: &str) -> PathBuf {
+        self.project_path
+            .join(format!("audit-{}.zip", sanitize_file_component(version)))
+    }
+
+    /// Export the audit bundle for a version
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Release version to export
+    /// * `output` - Output zip path (defaults to `audit-<version>.zip`)
+    /// * `config` - Current configuration, used when no snapshot was recorded
+    pub async fn export(
+        &self,
+        version: &str,
+        output: Option<PathBuf>,
+        config: Option<&PublishConfig>,
+    ) -> Result<AuditExportResult, anyhow::Error> {
+        let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+        let mut state_history = Vec::new();
+
+        for path in self.store.list(version).await? {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let content = fs::read(&path).await?;
+
+            // State snapshots are folded into the audit log
+            if name.starts_with("state-") {
+                if let Ok(value) = serde_json::from_slice(&content) {
+                    state_history.push(value);
+                }
+                continue;
+            }
+            entries.push((name.to_string(), content));
+        }
+
+        let analytics_records = self.analytics_records(version).await;
+        if entries.is_empty() && analytics_records.is_empty() && state_history.is_empty() {
+            anyhow::bail!("No release data recorded for version {}", version);
+        }
+
+        if !entries.iter().any(|(name, _)| name == CONFIG_SNAPSHOT_FILE)
+            && let Some(config) = config
+        {
+            entries.push((
+                CONFIG_SNAPSHOT_FILE.to_string(),
+                redacted_config_yaml(config)?.into_bytes(),
+            ));
+        }
+
+        let audit_log = AuditLog {
+            version: version.to_string(),
+            analytics_records,
+            state_history,
+        };
+        entries.push((
+            "audit-log.json".to_string(),
+            serde_json::to_string_pretty(&audit_log)?.into_bytes(),
+        ));
+
+        let reports: Vec<PublishReport> = entries
+            .iter()
+            .filter(|(name, _)| name.starts_with("publish-report"))
+            .filter_map(|(_, content)| serde_json::from_slice(content).ok())
+            .collect();
+        entries.push((
+            "checksums.txt".to_string(),
+            build_checksums(&entries, &reports).into_bytes(),
+        ));
+
+        let mut files: Vec<String> = entries.iter().map(|(name, _)| name.clone()).collect();
+        files.push("manifest.json".to_string());
+
+        let manifest = AuditBundleManifest {
+            version: version.to_string(),
+            generated_at: chrono::Utc::now(),
+            missing: missing_sections(&files),
+            files,
+        };
+        entries.push((
+            "manifest.json".to_string(),
+            serde_json::to_string_pretty(&manifest)?.into_bytes(),
+        ));
+
+        let output_path = output.unwrap_or_else(|| self.default_output_path(version));
+        if let Some(parent) = output_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&output_path, write_zip(version, &entries)?).await?;
+
+        Ok(AuditExportResult {
+            output_path,
+            manifest,
+        })
+    }
+
+    /// Analytics records for the version (empty if analytics are unavailable)
+    async fn analytics_records(&self, version: &str) -> Vec<AnalyticsRecord> {
+        let mut analytics = PublishAnalytics::new(&self.project_path);
+        if analytics.initialize().await.is_err() {
+            return Vec::new();
+        }
+
+        analytics
+            .get_records(&AnalyticsOptions::default())
+            .into_iter()
+            .filter(|r| r.version == version)
+            .collect()
+    }
+}
+
+/// Serialize config to YAML with webhook URLs, SMTP passwords and registry tokens masked
+fn redacted_config_yaml(config: &PublishConfig) -> Result<String, anyhow::Error> {
+    let mut config = config.clone();
+    if let Some(notifications) = config.notifications.as_mut() {
+        if let Some(slack) = notifications.slack.as_mut() {
+            slack.webhook_url = "****".to_string();
+        }
+        for chat in [
+            &mut notifications.discord,
+            &mut notifications.teams,
+            &mut notifications.mattermost,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            chat.webhook_url = "****".to_string();
+        }
+    }
+    if let Some(password) = config
+        .notifications
+        .as_mut()
+        .and_then(|n| n.email.as_mut())
+        .and_then(|e| e.password.as_mut())
+    {
+        *password = "****".to_string();
+    }
+    for webhook in config.webhooks.iter_mut().flatten() {
+        if let Some(secret) = webhook.secret.as_mut() {
+            *secret = "****".to_string();
+        }
+        mask_headers(webhook.headers.as_mut());
+    }
+    if let Some(telemetry) = config.telemetry.as_mut() {
+        mask_headers(telemetry.headers.as_mut());
+    }
+
+    // Expanded `${VAR}` values elsewhere (custom registry commands, URLs)
+    let yaml = serde_yaml::to_string(&config)?;
+    Ok(redact(
+        &SecureTokenManager::new().mask_tokens_in_string(&yaml),
+    ))
+}
+
+/// Mask every header value (headers mostly carry authentication)
+fn mask_headers(headers: Option<&mut HashMap<String, String>>) {
+    for value in headers.into_iter().flat_map(|h| h.values_mut()) {
+        *value = "****".to_string();
+    }
+}
+
+/// Build `checksums.txt` (sha256sum format) plus registry integrity hashes
+fn build_checksums(entries: &[(String, Vec<u8>)], reports: &[PublishReport]) -> String {
+    let mut lines: Vec<String> = entries
+        .iter()
+        .map(|(name, content)| format!("{}  {}", hex::encode(Sha256::digest(content)), name))
+        .collect();
+
+    for report in reports {
+        let Some(ref integrity) = report.integrity else {
+            continue;
+        };
+        let target = integrity
+            .download_url
+            .clone()
+            .unwrap_or_else(|| format!("{}@{}", report.package_name, report.version));
+
+        if let Some(ref checksum) = integrity.checksum {
+            lines.push(format!(
+                "# {} sha256 {} {}",
+                report.registry, checksum, target
+            ));
+        }
+        if let Some(ref shasum) = integrity.shasum {
+            lines.push(format!("# {} sha1 {} {}", report.registry, shasum, target));
+        }
+        if let Some(ref sri) = integrity.integrity {
+            lines.push(format!(
+                "# {} integrity {} {}",
+                report.registry, sri, target
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Sections expected in a complete bundle that were not found
+fn missing_sections(files: &[String]) -> Vec<String> {
+    [
+        ("publish-report", "publish report"),
+        ("scan-report", "secrets scan report"),
+        ("validation", "validation results"),
+        ("sbom", "SBOM"),
+        ("config", "config snapshot"),
+    ]
+    .iter()
+    .filter(|(prefix, _)| !files.iter().any(|f| f.starts_with(prefix)))
+    .map(|(_, label)| label.to_string())
+    .collect()
+}
+
+/// Write entries into an in-memory zip under a `audit-<version>/` folder
+fn write_zip(version: &str, entries: &[(String, Vec<u8>)]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let folder = format!("audit-{}", sanitize_file_component(version));
+
+    for (name, content) in entries {
+        writer.start_file(format!("{}/{}", folder, name), options)?;
+        writer.write_all(content)?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+/// Replace characters that are unsafe in file names
+pub(crate) fn sanitize_file_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{
+        NotificationsConfig, SlackNotificationConfig, TelemetryConfig, WebhookConfig,
+    };
+    use crate::core::traits::PackageIntegrity;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn sample_report() -> PublishReport {
+        PublishReport {
+            success: true,
+            registry: "npm".to_string(),
+            package_name: "test-pkg".to_string(),
+            version: "1.2.3".to_string(),
+            published_at: Some(chrono::Utc::now()),
+            verification_url: Some("https://www.npmjs.com/package/test-pkg".to_string()),
+            errors: Vec::new(),
+            error_kind: None,
+            warnings: Vec::new(),
+            duration: 1200,
+            state: "SUCCESS".to_string(),
+            integrity: Some(PackageIntegrity {
+                download_url: None,
+                shasum: Some("abc123".to_string()),
+                integrity: None,
+                checksum: None,
+            }),
+            hooks: Vec::new(),
+            deprecated_prereleases: Vec::new(),
+            quality_score: None,
+            release_url: None,
+            submission_url: None,
+            sbom: None,
+            signatures: Vec::new(),
+            provenance: None,
+            audit: None,
+            dependency_confusion: None,
+            malware_scan: Vec::new(),
+            artifact_inspection: Vec::new(),
+            package_size: None,
+            verification_wait: None,
+            policy_violations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_file_component() {
+        assert_eq!(sanitize_file_component("1.2.3-beta.1"), "1.2.3-beta.1");
+        assert_eq!(sanitize_file_component("../etc/passwd"), ".._etc_passwd");
+        assert_eq!(sanitize_file_component("crates.io"), "crates.io");
+    }
+
+    #[test]
+    fn test_redacted_config_masks_webhook() {
+        let config = PublishConfig {
+            notifications: Some(NotificationsConfig {
+                enabled: Some(true),
+                on_success: None,
+                on_failure: None,
+                templates: None,
+                slack: Some(SlackNotificationConfig {
+                    webhook_url: "https://hooks.slack.com/services/T000/B000/XXXX".to_string(),
+                }),
+                discord: None,
+                teams: None,
+                mattermost: None,
+                email: None,
+                jira: None,
+                linear: None,
+            }),
+            webhooks: Some(vec![WebhookConfig {
+                url: "https://releases.example.com/hook".to_string(),
+                secret: Some("webhook-signing-secret".to_string()),
+                headers: Some(HashMap::from([(
+                    "Authorization".to_string(),
+                    "Bearer expanded-otel-token".to_string(),
+                )])),
+                ..Default::default()
+            }]),
+            telemetry: Some(TelemetryConfig {
+                endpoint: "https://otel.example.com".to_string(),
+                headers: Some(HashMap::from([(
+                    "x-api-key".to_string(),
+                    "expanded-api-key".to_string(),
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let yaml = redacted_config_yaml(&config).unwrap();
+        assert!(!yaml.contains("hooks.slack.com"));
+        assert!(!yaml.contains("webhook-signing-secret"));
+        assert!(!yaml.contains("expanded-otel-token"));
+        assert!(!yaml.contains("expanded-api-key"));
+        assert!(yaml.contains("****"));
+    }
+
+    #[test]
+    fn test_missing_sections() {
+        let files = vec![
+            "publish-report-npm.json".to_string(),
+            "config.yaml".to_string(),
+        ];
+        let missing = missing_sections(&files);
+        assert!(missing.contains(&"secrets scan report".to_string()));
+        assert!(missing.contains(&"SBOM".to_string()));
+        assert!(!missing.contains(&"publish report".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_export_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ReleaseArtifactStore::new(temp_dir.path());
+        store
+            .save_json("1.2.3", "publish-report-npm", &sample_report())
+            .await
+            .unwrap();
+        store
+            .save_json(
+                "1.2.3",
+                "state-npm",
+                &serde_json::json!({"currentState": "SUCCESS"}),
+            )
+            .await
+            .unwrap();
+
+        let exporter = AuditExporter::new(temp_dir.path());
+        let result = exporter
+            .export("1.2.3", None, Some(&PublishConfig::default()))
+            .await
+            .unwrap();
+
+        assert_eq!(result.output_path, temp_dir.path().join("audit-1.2.3.zip"));
+        assert!(
+            result
+                .manifest
+                .files
+                .contains(&"publish-report-npm.json".to_string())
+        );
+        assert!(result.manifest.files.contains(&"config.yaml".to_string()));
+        assert!(
+            !result
+                .manifest
+                .files
+                .iter()
+                .any(|f| f.starts_with("state-"))
+        );
+
+        let file = std::fs::File::open(&result.output_path).unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+        let mut checksums = String::new();
+        archive
+            .by_name("audit-1.2.3/checksums.txt")
+            .unwrap()
+            .read_to_string(&mut checksums)
+            .unwrap();
+        assert!(checksums.contains("publish-report-npm.json"));
+        assert!(checksums.contains("# npm sha1 abc123"));
+
+        let mut audit_log = String::new();
+        archive
+            .by_name("audit-1.2.3/audit-log.json")
+            .unwrap()
+            .read_to_string(&mut audit_log)
+            .unwrap();
+        assert!(audit_log.contains("SUCCESS"));
+    }
+
+    #[tokio::test]
+    async fn test_export_without_data_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporter = AuditExporter::new(temp_dir.path());
+        let result = exporter
+            .export("9.9.9", None, Some(&PublishConfig::default()))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -5,8 +5,21 @@
 //! - Error handling with continueOnError option
 //! - Concurrency control
 //! - Detailed reporting for each registry
-
-use crate::orchestration::package_publisher::{PackagePublisher, PublishOptions, PublishReport};
+//! - Monorepo packages discovered by `WorkspaceScanner`
+//! - `--resume` skips targets an interrupted batch already published and
+//!   resumes the one in flight
+
+use crate::core::config_loader::{ConfigLoadOptions, ConfigLoader};
+use crate::core::error::PublishErrorKind;
+use crate::core::reporter::{Reporter, default_reporter};
+use crate::core::state_machine::{BatchProgress, PublishStateMachine};
+use crate::orchestration::events::{ConfirmationPrompt, EventSink, EventSinks};
+use crate::orchestration::notifier::Notifier;
+use crate::orchestration::package_publisher::{
+    PackagePublisher, PublishOptions, PublishReport, acquire_publish_lock,
+};
+use crate::orchestration::workspace::WorkspacePackage;
+use crate::security::redaction::RedactingReporter;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -58,9 +71,54 @@ pub struct BatchPublishResult {
     pub results: HashMap<String, PublishReport>,
 }
 
+impl BatchPublishResult {
+    /// Process exit code: `0` on success, the code of the failures' kind
+    /// when they all share one, `1` otherwise
+    pub fn exit_code(&self) -> i32 {
+        if self.success {
+            return 0;
+        }
+        let mut kinds = self.failed.keys().map(|label| {
+            self.results
+                .get(label)
+                .and_then(|report| report.error_kind.clone())
+                .unwrap_or(PublishErrorKind::Other)
+        });
+        match kinds.next() {
+            Some(first) if kinds.all(|kind| kind.code() == first.code()) => first.exit_code(),
+            _ => 1,
+        }
+    }
+}
+
+/// One publish of a batch
+struct BatchTarget {
+    /// Key in the batch result (registry or package name)
+    label: String,
+    project_path: PathBuf,
+    /// Registry to publish to (`None`: first detected)
+    registry: Option<String>,
+    /// Own state file for targets sharing a project directory
+    state_key: Option<String>,
+    /// Published from the batch directory, under the batch's lock
+    batch_locked: bool,
+}
+
+impl BatchTarget {
+    fn state_machine(&self) -> PublishStateMachine {
+        match self.state_key {
+            Some(ref key) => PublishStateMachine::with_state_key(&self.project_path, key),
+            None => PublishStateMachine::new(&self.project_path),
+        }
+    }
+}
+
 /// BatchPublisher - Manages publishing to multiple registries
 pub struct BatchPublisher {
     project_path: PathBuf,
+    reporter: Arc<dyn Reporter>,
+    events: EventSinks,
+    prompt: Option<Arc<dyn ConfirmationPrompt>>,
 }
 
 impl BatchPublisher {
@@ -72,9 +130,37 @@ impl BatchPublisher {
     pub fn new<P: Into<PathBuf>>(project_path: P) -> Self {
         Self {
             project_path: project_path.into(),
+            reporter: RedactingReporter::wrap(default_reporter()),
+            events: EventSinks::default(),
+            prompt: None,
         }
     }
 
+    /// Send progress and diagnostics to `reporter` instead of the terminal
+    ///
+    /// The reporter is shared with the per-registry publishers; credentials
+    /// in messages are masked.
+    pub fn with_reporter(mut self, reporter: Arc<dyn Reporter>) -> Self {
+        self.reporter = RedactingReporter::wrap(reporter);
+        self
+    }
+
+    /// Send the [`PublishEvent`](crate::orchestration::PublishEvent)s of
+    /// every per-registry publish to `sink`
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.events.push(sink);
+        self
+    }
+
+    /// Ask the confirmation questions of every per-registry publish
+    /// through `prompt`
+    ///
+    /// Without a prompt, batch publishes are non-interactive.
+    pub fn with_prompt(mut self, prompt: Arc<dyn ConfirmationPrompt>) -> Self {
+        self.prompt = Some(prompt);
+        self
+    }
+
     /// Publish to multiple registries
     ///
     /// # Arguments
@@ -95,28 +181,93 @@ impl BatchPublisher {
             return Err(anyhow::anyhow!("At least one registry must be specified"));
         }
 
-        println!(
+        self.reporter.info(&format!(
             "\n📦 Batch Publishing to {} registries: {}",
             registries.len(),
             registries.join(", ")
-        );
-        println!(
+        ));
+        self.reporter.info(&format!(
             "Mode: {}",
             if options.sequential {
                 "Sequential".to_string()
             } else {
                 format!("Parallel (max {} concurrent)", options.max_concurrency)
             }
-        );
-        println!(
+        ));
+        self.reporter.info(&format!(
             "Continue on error: {}\n",
             if options.continue_on_error {
                 "Yes"
             } else {
                 "No"
             }
-        );
+        ));
+
+        let targets = registries
+            .into_iter()
+            .map(|registry| BatchTarget {
+                label: registry.clone(),
+                project_path: self.project_path.clone(),
+                state_key: Some(registry.clone()),
+                registry: Some(registry),
+                batch_locked: true,
+            })
+            .collect();
+        self.run_batch(targets, options).await
+    }
 
+    /// Publish workspace packages, each from its own directory
+    ///
+    /// Results are keyed by package name. Each package uses
+    /// `publish_options.registry` or the first registry detected in its
+    /// directory. Set `sequential` to publish in the dependency order
+    /// returned by [`WorkspaceScanner::scan`](crate::orchestration::workspace::WorkspaceScanner::scan).
+    pub async fn publish_workspace(
+        &self,
+        packages: Vec<WorkspacePackage>,
+        options: BatchPublishOptions,
+    ) -> Result<BatchPublishResult, anyhow::Error> {
+        if packages.is_empty() {
+            return Err(anyhow::anyhow!("No workspace packages selected"));
+        }
+
+        self.reporter.info(&format!(
+            "\n📦 Workspace Publishing {} packages: {}",
+            packages.len(),
+            packages
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        self.reporter.info(&format!(
+            "Mode: {}\n",
+            if options.sequential {
+                "Sequential (dependency order)".to_string()
+            } else {
+                format!("Parallel (max {} concurrent)", options.max_concurrency)
+            }
+        ));
+
+        let targets = packages
+            .into_iter()
+            .map(|package| BatchTarget {
+                label: package.name,
+                batch_locked: package.path == self.project_path,
+                project_path: package.path,
+                registry: options.publish_options.registry.clone(),
+                state_key: None,
+            })
+            .collect();
+        self.run_batch(targets, options).await
+    }
+
+    /// Publish every target and summarize the results
+    async fn run_batch(
+        &self,
+        targets: Vec<BatchTarget>,
+        options: BatchPublishOptions,
+    ) -> Result<BatchPublishResult, anyhow::Error> {
         // Initialize result
         let mut result = BatchPublishResult {
             succeeded: Vec::new(),
@@ -126,66 +277,129 @@ impl BatchPublisher {
             results: HashMap::new(),
         };
 
+        let publish_options = &options.publish_options;
+        let _lock = acquire_publish_lock(
+            &self.project_path,
+            publish_options.wait_for_lock,
+            &*self.reporter,
+        )
+        .await?;
+        let progress = if publish_options.dry_run || publish_options.prepare_only {
+            None
+        } else if publish_options.resume
+            && let Some(progress) = BatchProgress::restore(&self.project_path).await?
+        {
+            Some(progress)
+        } else {
+            let labels = targets.iter().map(|t| t.label.clone()).collect();
+            Some(BatchProgress::start(&self.project_path, labels).await?)
+        };
+        let progress = progress.map(Arc::new);
+        let mut targets = targets;
+        if let Some(ref progress) = progress {
+            let completed = progress.data().await.completed;
+            targets.retain(|target| {
+                if completed.contains(&target.label) {
+                    self.reporter.info(&format!(
+                        "⏭️  {}: already published by the interrupted batch",
+                        target.label
+                    ));
+                    result.succeeded.push(target.label.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
         if options.sequential {
             // Sequential publishing
-            self.publish_sequentially(registries, &options, &mut result)
+            self.publish_sequentially(targets, &options, progress.clone(), &mut result)
                 .await?;
         } else {
             // Parallel publishing with concurrency control
-            self.publish_in_parallel(registries, &options, &mut result)
+            self.publish_in_parallel(targets, &options, progress.clone(), &mut result)
                 .await?;
         }
+        if result.failed.is_empty()
+            && result.skipped.is_empty()
+            && let Some(progress) = progress.and_then(Arc::into_inner)
+        {
+            progress.clear().await?;
+        }
 
         // Set overall success status
         result.success = result.failed.is_empty() && result.skipped.is_empty();
 
         // Print summary
-        Self::print_summary(&result);
+        self.print_summary(&result);
+
+        // Send one summary notification for the whole batch
+        if !options.publish_options.dry_run && !options.publish_options.prepare_only {
+            self.send_notifications(&result, options.publish_options.profile.clone())
+                .await;
+        }
 
         Ok(result)
     }
 
-    /// Publish to registries sequentially
+    /// Publish targets sequentially
     async fn publish_sequentially(
         &self,
-        registries: Vec<String>,
+        targets: Vec<BatchTarget>,
         options: &BatchPublishOptions,
+        progress: Option<Arc<BatchProgress>>,
         result: &mut BatchPublishResult,
     ) -> Result<(), anyhow::Error> {
-        for registry in registries {
+        for target in targets {
             // Skip if we had a failure and continueOnError is false
             if !result.failed.is_empty() && !options.continue_on_error {
-                println!("⏭️  Skipping {} due to previous failure", registry);
-                result.skipped.push(registry);
+                self.reporter.info(&format!(
+                    "⏭️  Skipping {} due to previous failure",
+                    target.label
+                ));
+                result.skipped.push(target.label);
                 continue;
             }
 
-            self.publish_to_registry(&registry, options, result).await;
+            self.publish_to_registry(&target, options, progress.clone(), result)
+                .await;
         }
 
         Ok(())
     }
 
-    /// Publish to registries in parallel with concurrency control
+    /// Publish targets in parallel with concurrency control
     async fn publish_in_parallel(
         &self,
-        registries: Vec<String>,
+        targets: Vec<BatchTarget>,
         options: &BatchPublishOptions,
+        progress: Option<Arc<BatchProgress>>,
         result: &mut BatchPublishResult,
     ) -> Result<(), anyhow::Error> {
         let semaphore = Arc::new(Semaphore::new(options.max_concurrency));
         let mut tasks = Vec::new();
 
-        for registry in registries {
+        for target in targets {
             let semaphore = Arc::clone(&semaphore);
-            let registry_for_task = registry.clone();
-            let project_path = self.project_path.clone();
             let publish_options = options.publish_options.clone();
+            let reporter = Arc::clone(&self.reporter);
+            let events = self.events.clone();
+            let prompt = self.prompt.clone();
+            let progress = progress.clone();
+            let registry = target.label.clone();
 
             let task = tokio::spawn(async move {
                 let _permit = semaphore.acquire().await.unwrap();
-                Self::publish_single_registry(&project_path, &registry_for_task, &publish_options)
-                    .await
+                Self::publish_single_registry(
+                    &target,
+                    &publish_options,
+                    reporter,
+                    events,
+                    prompt,
+                    progress,
+                )
+                .await
             });
 
             tasks.push((registry, task));
@@ -198,10 +412,10 @@ impl BatchPublisher {
                     match publish_result {
                         Ok(report) => {
                             if report.success {
-                                println!(
+                                self.reporter.success(&format!(
                                     "✅ {}: Published successfully in {}ms",
                                     registry, report.duration
-                                );
+                                ));
                                 result.succeeded.push(registry.clone());
                             } else {
                                 let error = report
@@ -209,14 +423,16 @@ impl BatchPublisher {
                                     .first()
                                     .cloned()
                                     .unwrap_or_else(|| "Unknown error".to_string());
-                                println!("❌ {}: Failed - {}", registry, error);
+                                self.reporter
+                                    .error(&format!("❌ {}: Failed - {}", registry, error));
                                 result.failed.insert(registry.clone(), error);
                             }
                             result.results.insert(registry, report);
                         }
                         Err(e) => {
                             let error_msg = e.to_string();
-                            println!("❌ {}: Failed - {}", registry, error_msg);
+                            self.reporter
+                                .error(&format!("❌ {}: Failed - {}", registry, error_msg));
                             result.failed.insert(registry.clone(), error_msg.clone());
 
                             // Create error report
@@ -228,9 +444,26 @@ impl BatchPublisher {
                                 published_at: None,
                                 verification_url: None,
                                 errors: vec![error_msg],
+                                error_kind: Some(PublishErrorKind::of(&e)),
                                 warnings: Vec::new(),
                                 duration: 0,
                                 state: "FAILED".to_string(),
+                                integrity: None,
+                                hooks: Vec::new(),
+                                deprecated_prereleases: Vec::new(),
+                                quality_score: None,
+                                release_url: None,
+                                submission_url: None,
+                                sbom: None,
+                                signatures: Vec::new(),
+                                provenance: None,
+                                audit: None,
+                                dependency_confusion: None,
+                                malware_scan: Vec::new(),
+                                artifact_inspection: Vec::new(),
+                                package_size: None,
+                                verification_wait: None,
+                                policy_violations: Vec::new(),
                             };
                             result.results.insert(registry, report);
                         }
@@ -238,7 +471,8 @@ impl BatchPublisher {
                 }
                 Err(e) => {
                     let error_msg = format!("Task failed: {}", e);
-                    println!("❌ {}: {}", registry, error_msg);
+                    self.reporter
+                        .error(&format!("❌ {}: {}", registry, error_msg));
                     result.failed.insert(registry.clone(), error_msg.clone());
 
                     let report = PublishReport {
@@ -249,9 +483,26 @@ impl BatchPublisher {
                         published_at: None,
                         verification_url: None,
                         errors: vec![error_msg],
+                        error_kind: Some(PublishErrorKind::Other),
                         warnings: Vec::new(),
                         duration: 0,
                         state: "FAILED".to_string(),
+                        integrity: None,
+                        hooks: Vec::new(),
+                        deprecated_prereleases: Vec::new(),
+                        quality_score: None,
+                        release_url: None,
+                        submission_url: None,
+                        sbom: None,
+                        signatures: Vec::new(),
+                        provenance: None,
+                        audit: None,
+                        dependency_confusion: None,
+                        malware_scan: Vec::new(),
+                        artifact_inspection: Vec::new(),
+                        package_size: None,
+                        verification_wait: None,
+                        policy_violations: Vec::new(),
                     };
                     result.results.insert(registry, report);
                 }
@@ -268,24 +519,34 @@ impl BatchPublisher {
         Ok(())
     }
 
-    /// Publish to a single registry
+    /// Publish a single target
     async fn publish_to_registry(
         &self,
-        registry: &str,
+        target: &BatchTarget,
         options: &BatchPublishOptions,
+        progress: Option<Arc<BatchProgress>>,
         result: &mut BatchPublishResult,
     ) {
-        println!("\n🚀 Publishing to {}...", registry);
-
-        match Self::publish_single_registry(&self.project_path, registry, &options.publish_options)
-            .await
+        let registry = target.label.as_str();
+        self.reporter
+            .info(&format!("\n🚀 Publishing to {}...", registry));
+
+        match Self::publish_single_registry(
+            target,
+            &options.publish_options,
+            Arc::clone(&self.reporter),
+            self.events.clone(),
+            self.prompt.clone(),
+            progress,
+        )
+        .await
         {
             Ok(report) => {
                 if report.success {
-                    println!(
+                    self.reporter.success(&format!(
                         "✅ {}: Published successfully in {}ms",
                         registry, report.duration
-                    );
+                    ));
                     result.succeeded.push(registry.to_string());
                 } else {
                     let error = report
@@ -293,14 +554,16 @@ impl BatchPublisher {
                         .first()
                         .cloned()
                         .unwrap_or_else(|| "Unknown error".to_string());
-                    println!("❌ {}: Failed - {}", registry, error);
+                    self.reporter
+                        .error(&format!("❌ {}: Failed - {}", registry, error));
                     result.failed.insert(registry.to_string(), error);
                 }
                 result.results.insert(registry.to_string(), report);
             }
             Err(e) => {
                 let error_msg = e.to_string();
-                println!("❌ {}: Failed - {}", registry, error_msg);
+                self.reporter
+                    .error(&format!("❌ {}: Failed - {}", registry, error_msg));
                 result
                     .failed
                     .insert(registry.to_string(), error_msg.clone());
@@ -313,9 +576,26 @@ impl BatchPublisher {
                     published_at: None,
                     verification_url: None,
                     errors: vec![error_msg],
+                    error_kind: Some(PublishErrorKind::of(&e)),
                     warnings: Vec::new(),
                     duration: 0,
                     state: "FAILED".to_string(),
+                    integrity: None,
+                    hooks: Vec::new(),
+                    deprecated_prereleases: Vec::new(),
+                    quality_score: None,
+                    release_url: None,
+                    submission_url: None,
+                    sbom: None,
+                    signatures: Vec::new(),
+                    provenance: None,
+                    audit: None,
+                    dependency_confusion: None,
+                    malware_scan: Vec::new(),
+                    artifact_inspection: Vec::new(),
+                    package_size: None,
+                    verification_wait: None,
+                    policy_violations: Vec::new(),
                 };
                 result.results.insert(registry.to_string(), report);
             }
@@ -323,68 +603,143 @@ impl BatchPublisher {
     }
 
     /// Helper function to publish to a single registry (used by parallel tasks)
+    ///
+    /// Successful publishes are recorded in `progress`.
     async fn publish_single_registry(
-        project_path: &PathBuf,
-        registry: &str,
+        target: &BatchTarget,
         publish_options: &PublishOptions,
+        reporter: Arc<dyn Reporter>,
+        events: EventSinks,
+        prompt: Option<Arc<dyn ConfirmationPrompt>>,
+        progress: Option<Arc<BatchProgress>>,
     ) -> Result<PublishReport, anyhow::Error> {
-        let mut publisher = PackagePublisher::new(project_path);
+        let mut publisher = PackagePublisher::new(&target.project_path)
+            .with_reporter(reporter)
+            .with_event_sinks(events);
+        if let Some(ref key) = target.state_key {
+            publisher = publisher.with_state_key(key);
+        }
+        if target.batch_locked {
+            publisher = publisher.with_lock_held();
+        }
 
-        // Force non-interactive for batch operations
+        // Force non-interactive for batch operations unless the questions
+        // have somewhere to go; the batch notifies once
         let mut batch_options = publish_options.clone();
-        batch_options.non_interactive = true;
-        batch_options.registry = Some(registry.to_string());
+        match prompt {
+            Some(prompt) => publisher = publisher.with_prompt(prompt),
+            None => batch_options.non_interactive = true,
+        }
+        batch_options.skip_notifications = true;
+        batch_options.registry = target.registry.clone();
+        // Targets the interrupted batch did not reach start from scratch
+        if batch_options.resume {
+            let mut state = target.state_machine();
+            batch_options.resume = state.restore().await.unwrap_or(false) && state.can_resume();
+        }
 
-        publisher
+        let report = publisher
             .publish(batch_options)
             .await
-            .map_err(|e| anyhow::anyhow!("{}", e))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        if report.success
+            && let Some(progress) = progress
+        {
+            progress.complete(&target.label).await?;
+        }
+        Ok(report)
+    }
+
+    /// Send a batch summary notification if enabled in config
+    async fn send_notifications(&self, result: &BatchPublishResult, profile: Option<String>) {
+        let config = match ConfigLoader::load(ConfigLoadOptions {
+            project_path: self.project_path.clone(),
+            cli_args: None,
+            env: std::env::vars().collect(),
+            reporter: Some(self.reporter.clone()),
+            profile,
+        })
+        .await
+        {
+            Ok(config) => config,
+            Err(e) => {
+                self.reporter.warning(&format!(
+                    "⚠️  Failed to load config for notifications: {}",
+                    e
+                ));
+                return;
+            }
+        };
+        let Some(notifier) = Notifier::from_config(Some(&config)) else {
+            return;
+        };
+
+        let mut reports: Vec<PublishReport> = result.results.values().cloned().collect();
+        reports.sort_by(|a, b| a.registry.cmp(&b.registry));
+
+        for outcome in notifier.notify_batch(&reports).await {
+            match outcome.error {
+                None => self
+                    .reporter
+                    .info(&format!("📣 Notification sent ({})", outcome.channel)),
+                Some(error) => self.reporter.warning(&format!(
+                    "⚠️  Failed to send {} notification: {}",
+                    outcome.channel, error
+                )),
+            }
+        }
     }
 
     /// Print batch publish summary
-    fn print_summary(result: &BatchPublishResult) {
-        println!("\n{}", "=".repeat(60));
-        println!("📊 Batch Publish Summary");
-        println!("{}", "=".repeat(60));
+    fn print_summary(&self, result: &BatchPublishResult) {
+        self.reporter.info(&format!("\n{}", "=".repeat(60)));
+        self.reporter.info("📊 Batch Publish Summary");
+        self.reporter.info(&"=".repeat(60));
 
-        println!("\n✅ Succeeded: {}", result.succeeded.len());
+        self.reporter
+            .info(&format!("\n✅ Succeeded: {}", result.succeeded.len()));
         if !result.succeeded.is_empty() {
             for registry in &result.succeeded {
                 let report = result.results.get(registry).unwrap();
-                println!("   - {} ({}ms)", registry, report.duration);
+                self.reporter
+                    .info(&format!("   - {} ({}ms)", registry, report.duration));
             }
         }
 
-        println!("\n❌ Failed: {}", result.failed.len());
+        self.reporter
+            .info(&format!("\n❌ Failed: {}", result.failed.len()));
         if !result.failed.is_empty() {
             for (registry, error) in &result.failed {
                 let report = result.results.get(registry);
                 let duration = report.map(|r| r.duration).unwrap_or(0);
-                println!("   - {}: {} ({}ms)", registry, error, duration);
+                self.reporter
+                    .info(&format!("   - {}: {} ({}ms)", registry, error, duration));
             }
         }
 
         if !result.skipped.is_empty() {
-            println!("\n⏭️  Skipped: {}", result.skipped.len());
+            self.reporter
+                .info(&format!("\n⏭️  Skipped: {}", result.skipped.len()));
             for registry in &result.skipped {
-                println!("   - {}", registry);
+                self.reporter.info(&format!("   - {}", registry));
             }
         }
 
-        println!("\n{}", "=".repeat(60));
-        println!(
+        self.reporter.info(&format!("\n{}", "=".repeat(60)));
+        self.reporter.info(&format!(
             "Overall Status: {}",
             if result.success {
                 "✅ SUCCESS"
             } else {
                 "❌ FAILED"
             }
-        );
-        println!("{}\n", "=".repeat(60));
+        ));
+        self.reporter.info(&format!("{}\n", "=".repeat(60)));
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
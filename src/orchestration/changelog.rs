@@ -0,0 +1,353 @@
+//! Changelog Generator - CHANGELOG.md from conventional commits
+//!
+//! Parses [Conventional Commits](https://www.conventionalcommits.org/) since
+//! the last git tag, groups them by type and prepends a release section to
+//! `CHANGELOG.md` (replacing the section if the version is already present).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::orchestration::ChangelogGenerator;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let generator = ChangelogGenerator::new(".");
+//! let result = generator.generate("1.2.0", false).await?;
+//! println!("{} commits → {}", result.commit_count, result.path.display());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::ChangelogConfig;
+use crate::core::git::run_git;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Commit types included when `changelog.types` is not configured
+const DEFAULT_TYPES: &[&str] = &["feat", "fix", "perf", "revert"];
+
+const DEFAULT_PATH: &str = "CHANGELOG.md";
+
+/// Section heading for a commit type
+fn type_heading(commit_type: &str) -> String {
+    match commit_type {
+        "feat" => "Features".to_string(),
+        "fix" => "Bug Fixes".to_string(),
+        "perf" => "Performance Improvements".to_string(),
+        "revert" => "Reverts".to_string(),
+        "refactor" => "Code Refactoring".to_string(),
+        "docs" => "Documentation".to_string(),
+        "build" => "Build System".to_string(),
+        "ci" => "Continuous Integration".to_string(),
+        "test" => "Tests".to_string(),
+        "style" => "Styles".to_string(),
+        "chore" => "Chores".to_string(),
+        other => {
+            let mut chars = other.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// A parsed conventional commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    pub hash: String,
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+}
+
+impl ConventionalCommit {
+    /// Parse `type(scope)!: description`; returns None for non-conventional subjects
+    pub fn parse(hash: &str, subject: &str, body: &str) -> Option<Self> {
+        let caps = Regex::new(r"^(\w+)(?:\(([^)]+)\))?(!)?:\s+(.+)$")
+            .unwrap()
+            .captures(subject.trim())?;
+
+        Some(Self {
+            hash: hash.to_string(),
+            commit_type: caps[1].to_lowercase(),
+            scope: caps.get(2).map(|s| s.as_str().to_string()),
+            description: caps[4].trim().to_string(),
+            breaking: caps.get(3).is_some()
+                || body.contains("BREAKING CHANGE:")
+                || body.contains("BREAKING-CHANGE:"),
+        })
+    }
+
+    fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    fn render(&self) -> String {
+        match self.scope {
+            Some(ref scope) => format!(
+                "- **{}:** {} ({})",
+                scope,
+                self.description,
+                self.short_hash()
+            ),
+            None => format!("- {} ({})", self.description, self.short_hash()),
+        }
+    }
+}
+
+/// Result of changelog generation
+#[derive(Debug, Clone)]
+pub struct ChangelogResult {
+    pub path: PathBuf,
+    /// Generated release section (Markdown)
+    pub section: String,
+    /// Number of conventional commits included
+    pub commit_count: usize,
+    /// Tag the commits were collected from (None = whole history)
+    pub since_tag: Option<String>,
+    /// Whether the changelog file was written
+    pub written: bool,
+}
+
+/// Generates CHANGELOG.md sections from git history
+pub struct ChangelogGenerator {
+    project_path: PathBuf,
+    path: PathBuf,
+    types: Vec<String>,
+}
+
+impl ChangelogGenerator {
+    /// Create a generator with default settings
+    pub fn new(project_path: impl AsRef<Path>) -> Self {
+        let project_path = project_path.as_ref().to_path_buf();
+        Self {
+            path: project_path.join(DEFAULT_PATH),
+            project_path,
+            types: DEFAULT_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Apply `changelog` settings
+    pub fn with_config(mut self, config: &ChangelogConfig) -> Self {
+        if let Some(ref path) = config.path {
+            self.path = self.project_path.join(path);
+        }
+        if let Some(ref types) = config.types {
+            self.types = types.clone();
+        }
+        self
+    }
+
+    /// Most recent tag reachable from HEAD
+    pub async fn last_tag(&self) -> Option<String> {
+        run_git(&self.project_path, &["describe", "--tags", "--abbrev=0"])
+            .await
+            .ok()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    /// Conventional commits since `tag` (or the whole history), newest first
+    pub async fn commits_since(
+        &self,
+        tag: Option<&str>,
+    ) -> anyhow::Result<Vec<ConventionalCommit>> {
+        let range = tag.map(|t| format!("{}..HEAD", t));
+        let mut args = vec!["log", "--format=%H%x1f%s%x1f%b%x1e"];
+        if let Some(ref range) = range {
+            args.push(range);
+        }
+
+        let log = run_git(&self.project_path, &args).await?;
+        Ok(log
+            .split('\x1e')
+            .filter_map(|entry| {
+                let mut fields = entry.trim_start_matches('\n').splitn(3, '\x1f');
+                let hash = fields.next()?;
+                let subject = fields.next()?;
+                let body = fields.next().unwrap_or_default();
+                ConventionalCommit::parse(hash, subject, body)
+            })
+            .collect())
+    }
+
+    /// Render a release section; breaking changes are always listed
+    pub fn render_section(
+        &self,
+        version: &str,
+        date: &str,
+        commits: &[ConventionalCommit],
+    ) -> String {
+        let mut section = format!("## [{}] - {}\n", version, date);
+
+        let breaking: Vec<_> = commits.iter().filter(|c| c.breaking).collect();
+        if !breaking.is_empty() {
+            section.push_str("\n### ⚠ BREAKING CHANGES\n\n");
+            for commit in breaking {
+                section.push_str(&commit.render());
+                section.push('\n');
+            }
+        }
+
+        for commit_type in &self.types {
+            let group: Vec<_> = commits
+                .iter()
+                .filter(|c| &c.commit_type == commit_type)
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+
+            section.push_str(&format!("\n### {}\n\n", type_heading(commit_type)));
+            for commit in group {
+                section.push_str(&commit.render());
+                section.push('\n');
+            }
+        }
+
+        section
+    }
+
+    /// Byte range of the release section for `version` (heading up to the next release)
+    fn section_range(content: &str, version: &str) -> Option<std::ops::Range<usize>> {
+        let heading = Regex::new(r"(?m)^## ").unwrap();
+        let starts: Vec<usize> = heading.find_iter(content).map(|m| m.start()).collect();
+        let same_version =
+            Regex::new(&format!(r"^## \[?v?{}\]?(\s|$)", regex::escape(version))).unwrap();
+
+        starts.iter().enumerate().find_map(|(i, &start)| {
+            same_version.is_match(&content[start..]).then(|| {
+                let end = starts.get(i + 1).copied().unwrap_or(content.len());
+                start..end
+            })
+        })
+    }
+
+    /// Release section for `version` in an existing changelog, without its heading
+    pub fn section_for(content: &str, version: &str) -> Option<String> {
+        let range = Self::section_range(content, version)?;
+        let section = &content[range];
+        let body = section.split_once('\n').map(|(_, body)| body).unwrap_or("");
+        Some(body.trim().to_string())
+    }
+
+    /// Insert `section` into an existing changelog
+    ///
+    /// The section goes above the newest release; an existing section for the
+    /// same version is replaced.
+    pub fn merge_section(existing: &str, version: &str, section: &str) -> String {
+        if existing.trim().is_empty() {
+            return format!("# Changelog\n\n{}", section);
+        }
+
+        if let Some(range) = Self::section_range(existing, version) {
+            return format!(
+                "{}{}\n{}",
+                &existing[..range.start],
+                section,
+                &existing[range.end..]
+            )
+            .trim_end()
+            .to_string()
+                + "\n";
+        }
+
+        let first_release = Regex::new(r"(?m)^## ").unwrap().find(existing);
+        match first_release.map(|m| m.start()) {
+            Some(start) => format!("{}{}\n{}", &existing[..start], section, &existing[start..]),
+            None => format!("{}\n\n{}", existing.trim_end(), section),
+        }
+    }
+
+    /// Generate the section for `version` and (unless `preview`) write it
+    pub async fn generate(&self, version: &str, preview: bool) -> anyhow::Result<ChangelogResult> {
+        let since_tag = self.last_tag().await;
+        let commits = self.commits_since(since_tag.as_deref()).await?;
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let section = self.render_section(version, &date, &commits);
+
+        if !preview {
+            let existing = fs::read_to_string(&self.path).await.unwrap_or_default();
+            fs::write(
+                &self.path,
+                Self::merge_section(&existing, version, &section),
+            )
+            .await?;
+        }
+
+        Ok(ChangelogResult {
+            path: self.path.clone(),
+            section,
+            commit_count: commits.len(),
+            since_tag,
+            written: !preview,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conventional_commit() {
+        let commit =
+            ConventionalCommit::parse("abcdef123", "feat(cli)!: add bump command", "").unwrap();
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("cli"));
+        assert!(commit.breaking);
+
+        let commit =
+            ConventionalCommit::parse("abc", "fix: handle empty tags", "BREAKING CHANGE: x")
+                .unwrap();
+        assert!(commit.breaking);
+        assert!(commit.scope.is_none());
+
+        assert!(ConventionalCommit::parse("abc", "Merge branch 'main'", "").is_none());
+    }
+
+    #[test]
+    fn test_render_section_groups_by_type() {
+        let commits = vec![
+            ConventionalCommit::parse("1111111aaa", "fix: b", "").unwrap(),
+            ConventionalCommit::parse("2222222bbb", "feat(api): a", "").unwrap(),
+            ConventionalCommit::parse("3333333ccc", "chore: c", "").unwrap(),
+        ];
+
+        let section = ChangelogGenerator::new(".").render_section("1.1.0", "2024-01-01", &commits);
+        assert!(section.starts_with("## [1.1.0] - 2024-01-01\n"));
+        let features = section.find("### Features").unwrap();
+        let fixes = section.find("### Bug Fixes").unwrap();
+        assert!(features < fixes);
+        assert!(section.contains("- **api:** a (2222222)"));
+        assert!(!section.contains("chore"));
+    }
+
+    #[test]
+    fn test_merge_section() {
+        let existing = "# Changelog\n\nIntro.\n\n## [1.0.0] - 2023-12-01\n\n- old\n";
+        let merged =
+            ChangelogGenerator::merge_section(existing, "1.1.0", "## [1.1.0] - 2024-01-01\n");
+        assert!(merged.starts_with("# Changelog\n\nIntro.\n\n## [1.1.0]"));
+        assert!(merged.contains("## [1.0.0]"));
+
+        // Regenerating the same version replaces its section
+        let replaced =
+            ChangelogGenerator::merge_section(&merged, "1.1.0", "## [1.1.0] - 2024-01-02\n");
+        assert_eq!(replaced.matches("## [1.1.0]").count(), 1);
+        assert!(replaced.contains("2024-01-02"));
+        assert!(replaced.contains("## [1.0.0]"));
+
+        assert_eq!(
+            ChangelogGenerator::section_for(&replaced, "1.0.0").as_deref(),
+            Some("- old")
+        );
+
+        assert_eq!(
+            ChangelogGenerator::merge_section("", "1.0.0", "## [1.0.0]\n"),
+            "# Changelog\n\n## [1.0.0]\n"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Check Runner - concurrent per-registry validation for `check`
+//!
+//! Validations for the detected registries run concurrently. Registries that
+//! share a build toolchain (npm, UPM and Expo all build through `npm`, for
+//! example) take the same build lock, so two validations never write to the
+//! same `node_modules`, `target/` or `dist/` directory at once.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::check_runner::build_group;
+//! use package_publisher::plugins::plugin_loader::RegistryType;
+//!
+//! assert_eq!(build_group(RegistryType::Npm), build_group(RegistryType::Expo));
+//! assert_ne!(build_group(RegistryType::Npm), build_group(RegistryType::Crates));
+//! ```
+
+use crate::core::traits::ValidationResult;
+use crate::plugins::plugin_loader::{PluginLoader, RegistryType};
+use crate::validation::warning_budget::WarningBudget;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+/// How the runner reacts to a failing registry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckMode {
+    /// Validate every registry and report all failures
+    #[default]
+    All,
+    /// Cancel the remaining validations after the first failure
+    FailFast,
+}
+
+/// Outcome of one registry validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    Error,
+    Skipped,
+}
+
+/// Validation result for one registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryCheck {
+    pub registry: String,
+    pub status: CheckStatus,
+    /// Duration in milliseconds
+    pub duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<ValidationResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Combined result of `check` across registries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckSummary {
+    pub success: bool,
+    pub mode: CheckMode,
+    /// Results in detection order
+    pub registries: Vec<RegistryCheck>,
+}
+
+/// Toolchain whose build output a registry's validation may touch
+pub fn build_group(registry: RegistryType) -> &'static str {
+    match registry {
+        RegistryType::Npm | RegistryType::Upm | RegistryType::Expo => "node",
+        RegistryType::Crates => "cargo",
+        RegistryType::PyPI => "python",
+        other => other.as_str(),
+    }
+}
+
+/// Runs registry validations concurrently
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CheckRunner {
+    mode: CheckMode,
+    warning_budget: WarningBudget,
+}
+
+impl CheckRunner {
+    pub fn new(mode: CheckMode, warning_budget: WarningBudget) -> Self {
+        Self {
+            mode,
+            warning_budget,
+        }
+    }
+
+    /// Validate the registries with their plugins
+    pub async fn run(&self, project_path: &Path, registries: &[RegistryType]) -> CheckSummary {
+        let project_path = project_path.to_string_lossy().to_string();
+        self.run_with(registries, move |registry| {
+            let project_path = project_path.clone();
+            async move {
+                PluginLoader::new()
+                    .load_plugin(registry, &project_path)?
+                    .validate()
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Validate the registries with a custom validation function
+    pub async fn run_with<F, Fut>(&self, registries: &[RegistryType], validate: F) -> CheckSummary
+    where
+        F: Fn(RegistryType) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<ValidationResult>> + Send + 'static,
+    {
+        let validate = Arc::new(validate);
+        let mut locks: HashMap<&'static str, Arc<Mutex<()>>> = HashMap::new();
+        let mut tasks = JoinSet::new();
+
+        for (index, &registry) in registries.iter().enumerate() {
+            let lock = Arc::clone(locks.entry(build_group(registry)).or_default());
+            let validate = Arc::clone(&validate);
+            let warning_budget = self.warning_budget;
+            tasks.spawn(async move {
+                let _guard = lock.lock().await;
+                let start = Instant::now();
+                let outcome = validate(registry).await;
+                let duration = start.elapsed().as_millis() as u64;
+                (
+                    index,
+                    Self::to_check(registry, outcome, duration, warning_budget),
+                )
+            });
+        }
+
+        let mut results: Vec<Option<RegistryCheck>> = vec![None; registries.len()];
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((index, check)) = joined else {
+                continue;
+            };
+            let failed = check.status != CheckStatus::Passed;
+            results[index] = Some(check);
+            if failed && self.mode == CheckMode::FailFast {
+                tasks.abort_all();
+            }
+        }
+
+        let registries: Vec<RegistryCheck> = results
+            .into_iter()
+            .zip(registries)
+            .map(|(check, registry)| {
+                check.unwrap_or_else(|| RegistryCheck {
+                    registry: registry.as_str().to_string(),
+                    status: CheckStatus::Skipped,
+                    duration: 0,
+                    validation: None,
+                    error: None,
+                })
+            })
+            .collect();
+
+        CheckSummary {
+            success: registries
+                .iter()
+                .all(|check| check.status == CheckStatus::Passed),
+            mode: self.mode,
+            registries,
+        }
+    }
+
+    fn to_check(
+        registry: RegistryType,
+        outcome: anyhow::Result<ValidationResult>,
+        duration: u64,
+        warning_budget: WarningBudget,
+    ) -> RegistryCheck {
+        let registry = registry.as_str().to_string();
+        match outcome {
+            Ok(validation) => {
+                let budget_error = warning_budget
+                    .check(validation.warnings.len())
+                    .err()
+                    .map(|exceeded| exceeded.to_string());
+                let status = if validation.valid && budget_error.is_none() {
+                    CheckStatus::Passed
+                } else {
+                    CheckStatus::Failed
+                };
+                RegistryCheck {
+                    registry,
+                    status,
+                    duration,
+                    validation: Some(validation),
+                    error: budget_error,
+                }
+            }
+            Err(e) => RegistryCheck {
+                registry,
+                status: CheckStatus::Error,
+                duration,
+                validation: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn validation(valid: bool) -> ValidationResult {
+        ValidationResult {
+            valid,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_concurrently_and_keeps_detection_order() {
+        let registries = [RegistryType::Crates, RegistryType::Npm, RegistryType::PyPI];
+        let start = Instant::now();
+        let summary = CheckRunner::default()
+            .run_with(&registries, |registry| async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                if registry == RegistryType::Npm {
+                    anyhow::bail!("npm is not installed");
+                }
+                Ok(validation(true))
+            })
+            .await;
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(!summary.success);
+        let statuses: Vec<_> = summary.registries.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![CheckStatus::Passed, CheckStatus::Error, CheckStatus::Passed]
+        );
+        assert_eq!(summary.registries[0].registry, "crates.io");
+        assert_eq!(
+            summary.registries[1].error.as_deref(),
+            Some("npm is not installed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_skips_remaining_and_shares_build_lock() {
+        // npm and expo share the node build lock, so expo waits for npm
+        let registries = [RegistryType::Npm, RegistryType::Expo, RegistryType::Crates];
+        let summary = CheckRunner::new(CheckMode::FailFast, WarningBudget::default())
+            .run_with(&registries, |registry| async move {
+                let delay = if registry == RegistryType::Npm {
+                    50
+                } else {
+                    10
+                };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Ok(validation(registry != RegistryType::Crates))
+            })
+            .await;
+
+        let statuses: Vec<_> = summary.registries.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                CheckStatus::Skipped,
+                CheckStatus::Skipped,
+                CheckStatus::Failed
+            ]
+        );
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["mode"], "fail-fast");
+        assert_eq!(json["registries"][2]["status"], "failed");
+    }
+}
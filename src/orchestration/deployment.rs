@@ -0,0 +1,309 @@
+//! GitHub Deployments - publish status in the repository's Environments UI
+//!
+//! When `release.githubDeployment` is enabled, a GitHub Deployment is created
+//! for the release environment (`{registry}-production` by default) when
+//! publishing starts and marked `in_progress`; at the end it is marked
+//! `success` (with the package page as environment URL) or `failure`.
+//! Environment protection rules and workflows triggered by `deployment_status`
+//! can then gate on the publish.
+
+use crate::core::config::GitHubDeploymentConfig;
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::orchestration::release::parse_github_repository;
+use reqwest::Method;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+const DEFAULT_ENVIRONMENT: &str = "{registry}-production";
+
+/// A created deployment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubDeployment {
+    pub id: u64,
+    pub repository: String,
+    pub environment: String,
+}
+
+/// `POST /repos/{owner}/{repo}/deployments` response (subset)
+#[derive(Debug, Deserialize)]
+struct DeploymentResponse {
+    id: u64,
+}
+
+/// Creates GitHub Deployments and reports their status
+pub struct DeploymentManager {
+    project_path: PathBuf,
+    config: GitHubDeploymentConfig,
+    remote: String,
+    github: GitHubClient,
+}
+
+impl DeploymentManager {
+    /// Create a manager from `release.githubDeployment`
+    pub fn new(project_path: impl AsRef<Path>, config: GitHubDeploymentConfig) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            config,
+            remote: "origin".to_string(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Git remote used to find the repository (`release.remote`)
+    pub fn with_remote(mut self, remote: impl Into<String>) -> Self {
+        self.remote = remote.into();
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled.unwrap_or(false)
+    }
+
+    /// Environment for a registry (`environment`, default "{registry}-production")
+    pub fn environment_name(&self, registry: &str) -> String {
+        self.config
+            .environment
+            .as_deref()
+            .unwrap_or(DEFAULT_ENVIRONMENT)
+            .replace("{registry}", registry)
+    }
+
+    fn token(&self) -> anyhow::Result<String> {
+        let token_env = self
+            .config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV);
+        std::env::var(token_env)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{} is not set", token_env))
+    }
+
+    /// `owner/repo` from config or the remote URL
+    async fn repository(&self) -> anyhow::Result<String> {
+        if let Some(ref repository) = self.config.repository {
+            return Ok(repository.clone());
+        }
+        let url = run_git(&self.project_path, &["remote", "get-url", &self.remote]).await?;
+        parse_github_repository(&url).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot determine GitHub repository from remote {} ({}); set release.githubDeployment.repository",
+                self.remote,
+                url
+            )
+        })
+    }
+
+    /// Create the deployment for the current commit and mark it in progress
+    pub async fn start(
+        &self,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+    ) -> anyhow::Result<GitHubDeployment> {
+        let token = self.token()?;
+        let repository = self.repository().await?;
+        let git_ref = run_git(&self.project_path, &["rev-parse", "HEAD"]).await?;
+        let environment = self.environment_name(registry);
+
+        let body = deployment_request(
+            &git_ref,
+            &environment,
+            registry,
+            package_name,
+            version,
+            self.config.production_environment.unwrap_or(true),
+        );
+        let response = self
+            .github
+            .authenticated(&token)
+            .request(Method::POST, &format!("/repos/{}/deployments", repository))
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "GitHub Deployment creation failed (HTTP {}): {}",
+                status,
+                text
+            );
+        }
+        let deployment = GitHubDeployment {
+            id: response.json::<DeploymentResponse>().await?.id,
+            repository,
+            environment,
+        };
+
+        self.set_status(&deployment, "in_progress", None, &token)
+            .await?;
+        Ok(deployment)
+    }
+
+    /// Mark the deployment `success` or `failure`
+    pub async fn finish(
+        &self,
+        deployment: &GitHubDeployment,
+        success: bool,
+        environment_url: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let token = self.token()?;
+        let state = if success { "success" } else { "failure" };
+        self.set_status(deployment, state, environment_url, &token)
+            .await
+    }
+
+    async fn set_status(
+        &self,
+        deployment: &GitHubDeployment,
+        state: &str,
+        environment_url: Option<&str>,
+        token: &str,
+    ) -> anyhow::Result<()> {
+        let mut body = serde_json::json!({
+            "state": state,
+            "description": format!("package-publisher: {}", state.replace('_', " ")),
+        });
+        if let Some(url) = environment_url {
+            body["environment_url"] = serde_json::json!(url);
+        }
+
+        let response = self
+            .github
+            .authenticated(token)
+            .request(
+                Method::POST,
+                &format!(
+                    "/repos/{}/deployments/{}/statuses",
+                    deployment.repository, deployment.id
+                ),
+            )
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "GitHub Deployment status update failed (HTTP {})",
+                response.status()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Body of `POST /repos/{owner}/{repo}/deployments`
+fn deployment_request(
+    git_ref: &str,
+    environment: &str,
+    registry: &str,
+    package_name: &str,
+    version: &str,
+    production: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "ref": git_ref,
+        "task": "publish",
+        "environment": environment,
+        "description": format!("Publish {}@{} to {}", package_name, version, registry),
+        // Publishing is not a merge; status checks are enforced by the CI run itself
+        "auto_merge": false,
+        "required_contexts": [],
+        "production_environment": production,
+        "payload": {
+            "package": package_name,
+            "version": version,
+            "registry": registry,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_environment_name_and_request() {
+        let manager = DeploymentManager::new(".", GitHubDeploymentConfig::default());
+        assert_eq!(manager.environment_name("npm"), "npm-production");
+
+        let manager = DeploymentManager::new(
+            ".",
+            GitHubDeploymentConfig {
+                environment: Some("release-{registry}".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(manager.environment_name("pypi"), "release-pypi");
+
+        let body = deployment_request("abc123", "npm-production", "npm", "demo", "1.2.0", true);
+        assert_eq!(body["ref"], "abc123");
+        assert_eq!(body["environment"], "npm-production");
+        assert_eq!(body["payload"]["version"], "1.2.0");
+        assert_eq!(body["required_contexts"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_finish_posts_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("environment_url") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 201 Created\r\ncontent-length: 2\r\n\r\n{}")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        let manager = DeploymentManager::new(
+            ".",
+            GitHubDeploymentConfig {
+                enabled: Some(true),
+                token_env: Some("PACKAGE_PUBLISHER_TEST_DEPLOY_TOKEN".to_string()),
+                ..Default::default()
+            },
+        )
+        .with_github_api_url(url);
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_DEPLOY_TOKEN", "t0ken");
+        }
+        let deployment = GitHubDeployment {
+            id: 42,
+            repository: "acme/demo".to_string(),
+            environment: "npm-production".to_string(),
+        };
+        manager
+            .finish(
+                &deployment,
+                true,
+                Some("https://www.npmjs.com/package/demo"),
+            )
+            .await
+            .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /repos/acme/demo/deployments/42/statuses"));
+        assert!(request.contains(r#""state":"success""#));
+    }
+}
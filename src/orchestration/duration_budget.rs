@@ -0,0 +1,252 @@
+//! Duration Budget - publish time alerts with a per-phase breakdown
+//!
+//! `publish.durationBudget` sets how long a publish may take, by default and
+//! per registry. When a publish exceeds its budget, a warning with the time
+//! spent in each phase (state machine states, plus lifecycle hooks such as
+//! test runs) is reported and sent to the notification channels, so slow
+//! steps are investigated before release times quietly double.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::duration_budget::parse_duration;
+//!
+//! assert_eq!(parse_duration("1h30m").unwrap(), 5_400_000);
+//! assert_eq!(parse_duration("90s").unwrap(), 90_000);
+//! assert_eq!(parse_duration("120").unwrap(), 120_000);
+//! ```
+
+use crate::core::config::DurationBudgetConfig;
+use crate::core::state_machine::{PublishState, StateTransition};
+use crate::orchestration::hook_runner::HookResult;
+use crate::tr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Time spent in one phase of a publish
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    /// Duration in milliseconds
+    pub duration: u64,
+}
+
+/// Parse a duration such as `90s`, `5m`, `1h30m`, `1500ms` or `120` (seconds)
+/// into milliseconds
+pub fn parse_duration(value: &str) -> anyhow::Result<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(seconds * 1000);
+    }
+
+    let mut total = 0u64;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit_len = rest[digits..]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len() - digits);
+        let number: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid duration: {}", value))?;
+        let multiplier = match &rest[digits..digits + unit_len] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            unit => anyhow::bail!(
+                "Invalid duration unit \"{}\" in {} (expected ms, s, m or h)",
+                unit,
+                value
+            ),
+        };
+        total += number * multiplier;
+        rest = &rest[digits + unit_len..];
+    }
+    Ok(total)
+}
+
+/// Human readable duration (`850ms`, `42s`, `3m 12s`, `1h 05m`)
+pub fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1000;
+    match seconds {
+        _ if ms < 1000 => format!("{}ms", ms),
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {:02}s", s / 60, s % 60),
+        s => format!("{}h {:02}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+fn state_name(state: PublishState) -> &'static str {
+    match state {
+        PublishState::Initial => "initial",
+        PublishState::Detecting => "detect",
+        PublishState::Validating => "validate",
+        PublishState::DryRun => "dry-run",
+        PublishState::Confirming => "confirm",
+        PublishState::WaitingApproval => "approval",
+        PublishState::Publishing => "publish",
+        PublishState::Verifying => "verify",
+        PublishState::Success => "success",
+        PublishState::Failed => "failed",
+        PublishState::RolledBack => "rollback",
+    }
+}
+
+/// Time spent in each state until `end`, followed by hook totals per stage
+///
+/// States entered more than once (resumed publishes) are summed. Hooks run
+/// inside the states, so their times are a subset of the state times.
+pub fn phase_timings(
+    transitions: &[StateTransition],
+    end: DateTime<Utc>,
+    hooks: &[HookResult],
+) -> Vec<PhaseTiming> {
+    let mut timings: Vec<PhaseTiming> = Vec::new();
+    let mut add = |phase: String, duration: u64| match timings.iter_mut().find(|t| t.phase == phase)
+    {
+        Some(timing) => timing.duration += duration,
+        None => timings.push(PhaseTiming { phase, duration }),
+    };
+
+    for (i, transition) in transitions.iter().enumerate() {
+        if matches!(
+            transition.to,
+            PublishState::Success | PublishState::Failed | PublishState::RolledBack
+        ) {
+            continue;
+        }
+        let until = transitions.get(i + 1).map(|t| t.timestamp).unwrap_or(end);
+        let duration = (until - transition.timestamp).num_milliseconds().max(0) as u64;
+        add(state_name(transition.to).to_string(), duration);
+    }
+    for hook in hooks {
+        add(format!("hooks ({})", hook.stage), hook.duration);
+    }
+    timings
+}
+
+/// Duration limits from `publish.durationBudget`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DurationBudget {
+    default: Option<u64>,
+    registries: HashMap<String, u64>,
+}
+
+impl DurationBudget {
+    pub fn from_config(config: &DurationBudgetConfig) -> anyhow::Result<Self> {
+        let default = config.default.as_deref().map(parse_duration).transpose()?;
+        let registries = config
+            .registries
+            .iter()
+            .flatten()
+            .map(|(registry, value)| Ok((registry.clone(), parse_duration(value)?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            default,
+            registries,
+        })
+    }
+
+    /// Budget in milliseconds for a registry (registry override, else default)
+    pub fn budget_for(&self, registry: &str) -> Option<u64> {
+        self.registries.get(registry).copied().or(self.default)
+    }
+
+    /// Alert message with the phase breakdown when `duration` exceeds the budget
+    pub fn check(&self, registry: &str, duration: u64, timings: &[PhaseTiming]) -> Option<String> {
+        let budget = self.budget_for(registry)?;
+        if duration <= budget {
+            return None;
+        }
+
+        let mut message = tr!(
+            "duration_budget.exceeded",
+            registry = registry,
+            duration = format_duration(duration),
+            budget = format_duration(budget)
+        );
+        let mut sorted: Vec<&PhaseTiming> = timings.iter().filter(|t| t.duration > 0).collect();
+        sorted.sort_by_key(|t| std::cmp::Reverse(t.duration));
+        if !sorted.is_empty() {
+            message.push_str(&format!("\n{}", tr!("duration_budget.by_phase")));
+        }
+        for timing in sorted {
+            message.push_str(&format!(
+                "\n  {:<22} {:>8} ({:.0}%)",
+                timing.phase,
+                format_duration(timing.duration),
+                timing.duration as f64 / duration.max(1) as f64 * 100.0
+            ));
+        }
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::hook_runner::HookStage;
+
+    #[test]
+    fn test_parse_and_format_duration() {
+        assert_eq!(parse_duration("5m").unwrap(), 300_000);
+        assert_eq!(parse_duration("1m30s").unwrap(), 90_000);
+        assert_eq!(parse_duration("250ms").unwrap(), 250);
+        assert!(parse_duration("5 minutes").is_err());
+        assert!(parse_duration("m").is_err());
+        assert_eq!(format_duration(192_000), "3m 12s");
+        assert_eq!(format_duration(3_900_000), "1h 05m");
+    }
+
+    #[test]
+    fn test_phase_timings_and_budget() {
+        let start: DateTime<Utc> = "2026-10-15T01:00:00Z".parse().unwrap();
+        let transition = |to, seconds| StateTransition {
+            from: PublishState::Initial,
+            to,
+            timestamp: start + chrono::Duration::seconds(seconds),
+            metadata: None,
+        };
+        let transitions = vec![
+            transition(PublishState::Initial, 0),
+            transition(PublishState::Validating, 2),
+            transition(PublishState::DryRun, 62),
+            transition(PublishState::Publishing, 92),
+            transition(PublishState::Success, 392),
+        ];
+        let hooks = vec![HookResult {
+            stage: HookStage::PrePublish,
+            command: "npm test".to_string(),
+            success: true,
+            exit_code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+            duration: 55_000,
+            error: None,
+        }];
+
+        let timings = phase_timings(&transitions, start + chrono::Duration::seconds(400), &hooks);
+        assert_eq!(
+            timings.iter().map(|t| t.duration).collect::<Vec<_>>(),
+            vec![2_000, 60_000, 30_000, 300_000, 55_000]
+        );
+        assert_eq!(timings[4].phase, "hooks (prePublish)");
+
+        let budget = DurationBudget::from_config(&DurationBudgetConfig {
+            default: Some("10m".to_string()),
+            registries: Some(HashMap::from([("npm".to_string(), "5m".to_string())])),
+        })
+        .unwrap();
+        assert_eq!(budget.budget_for("pypi"), Some(600_000));
+        assert!(budget.check("pypi", 400_000, &timings).is_none());
+
+        let message = budget.check("npm", 400_000, &timings).unwrap();
+        assert!(message.starts_with("Publishing to npm took 6m 40s, over the 5m 00s budget"));
+        // Slowest phase first
+        assert!(message.lines().nth(2).unwrap().contains("publish"));
+    }
+}
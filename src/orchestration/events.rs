@@ -0,0 +1,207 @@
+//! Publish Events - progress events for embedding applications
+//!
+//! [`PackagePublisher`](crate::orchestration::PackagePublisher) and
+//! [`BatchPublisher`](crate::orchestration::BatchPublisher) emit a
+//! [`PublishEvent`] for every state transition, validation finding, detected
+//! secret, dry-run output and publish output line, so a TUI or GUI can render
+//! progress without parsing the reporter output. Sinks are channels or
+//! callbacks:
+//!
+//! ```no_run
+//! use package_publisher::orchestration::{PackagePublisher, PublishEvent};
+//! use std::sync::Arc;
+//!
+//! let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<PublishEvent>();
+//! let publisher = PackagePublisher::new(".")
+//!     .with_event_sink(Arc::new(sender))
+//!     .with_event_sink(Arc::new(|event: &PublishEvent| eprintln!("{:?}", event)));
+//! ```
+//!
+//! Confirmation prompts are answered on the terminal unless a
+//! [`ConfirmationPrompt`] is registered with `with_prompt`.
+
+use crate::core::state_machine::PublishState;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// Severity of a validation finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    Error,
+    Warning,
+}
+
+/// Progress of a publish
+///
+/// `registry` is the selected registry, which tells the publishes of a batch
+/// apart; it is `None` before a registry has been selected.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PublishEvent {
+    /// The publish moved to another state
+    StateChanged {
+        registry: Option<String>,
+        from: PublishState,
+        to: PublishState,
+    },
+    /// An error or warning reported by the registry plugin's validation
+    ValidationFinding {
+        registry: String,
+        severity: FindingSeverity,
+        field: String,
+        message: String,
+    },
+    /// A potential secret found by the pre-publish scan
+    SecretDetected {
+        registry: String,
+        file: String,
+        line: usize,
+        secret_type: String,
+    },
+    /// Output of the dry-run
+    DryRunOutput { registry: String, chunk: String },
+    /// A line of publish command output
+    PublishOutput { registry: String, line: String },
+    /// The publish ended
+    Finished {
+        registry: Option<String>,
+        success: bool,
+        error: Option<String>,
+    },
+}
+
+/// Receiver of publish events
+///
+/// Sinks are called synchronously from the publish task and must not block.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: &PublishEvent);
+}
+
+impl<F> EventSink for F
+where
+    F: Fn(&PublishEvent) + Send + Sync,
+{
+    fn emit(&self, event: &PublishEvent) {
+        self(event)
+    }
+}
+
+/// Events are dropped once the receiver is closed
+impl EventSink for tokio::sync::mpsc::UnboundedSender<PublishEvent> {
+    fn emit(&self, event: &PublishEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+impl EventSink for std::sync::mpsc::Sender<PublishEvent> {
+    fn emit(&self, event: &PublishEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Answers the yes/no questions of a publish (pre-publish confirmation,
+/// publishing despite warnings)
+#[async_trait]
+pub trait ConfirmationPrompt: Send + Sync {
+    /// `registry` is `None` before a registry has been selected
+    async fn confirm(&self, registry: Option<&str>, message: &str) -> anyhow::Result<bool>;
+
+    /// Ask for a one-time password (`None` when it cannot be asked for or
+    /// the answer is empty)
+    async fn ask_otp(&self, _registry: &str, _message: &str) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Asks on stdin/stdout
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TerminalPrompt;
+
+#[async_trait]
+impl ConfirmationPrompt for TerminalPrompt {
+    async fn confirm(&self, _registry: Option<&str>, message: &str) -> anyhow::Result<bool> {
+        let mut stdout = tokio::io::stdout();
+        stdout
+            .write_all(format!("{} (yes/no): ", message).as_bytes())
+            .await?;
+        stdout.flush().await?;
+
+        let mut reader = BufReader::new(tokio::io::stdin());
+        let mut answer = String::new();
+        reader.read_line(&mut answer).await?;
+
+        let answer = answer.trim().to_lowercase();
+        Ok(answer == "yes" || answer == "y")
+    }
+
+    async fn ask_otp(&self, _registry: &str, message: &str) -> anyhow::Result<Option<String>> {
+        let mut stdout = tokio::io::stdout();
+        stdout
+            .write_all(format!("{}: ", message).as_bytes())
+            .await?;
+        stdout.flush().await?;
+
+        let mut reader = BufReader::new(tokio::io::stdin());
+        let mut answer = String::new();
+        reader.read_line(&mut answer).await?;
+
+        let answer = answer.trim();
+        Ok((!answer.is_empty()).then(|| answer.to_string()))
+    }
+}
+
+/// Registered sinks of a publisher
+#[derive(Clone, Default)]
+pub(crate) struct EventSinks(Vec<Arc<dyn EventSink>>);
+
+impl EventSinks {
+    pub(crate) fn push(&mut self, sink: Arc<dyn EventSink>) {
+        self.0.push(sink);
+    }
+
+    pub(crate) fn emit(&self, event: PublishEvent) {
+        for sink in &self.0 {
+            sink.emit(&event);
+        }
+    }
+}
+
+impl std::fmt::Debug for EventSinks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EventSinks({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_sinks_receive_events() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let callback_seen = seen.clone();
+
+        let mut sinks = EventSinks::default();
+        sinks.push(Arc::new(sender));
+        sinks.push(Arc::new(move |event: &PublishEvent| {
+            callback_seen.lock().unwrap().push(event.clone());
+        }));
+        let event = PublishEvent::PublishOutput {
+            registry: "npm".to_string(),
+            line: "+ demo@1.0.0".to_string(),
+        };
+        sinks.emit(event.clone());
+
+        assert_eq!(receiver.try_recv().unwrap(), event);
+        assert_eq!(*seen.lock().unwrap(), vec![event.clone()]);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({"type": "publish_output", "registry": "npm", "line": "+ demo@1.0.0"})
+        );
+    }
+}
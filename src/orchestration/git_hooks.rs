@@ -0,0 +1,305 @@
+//! Git Hooks - pre-commit / pre-push hooks that run package-publisher checks
+//!
+//! `package-publisher hooks install` writes hook scripts into the repository's
+//! hooks directory (`git rev-parse --git-path hooks`, so `core.hooksPath` is
+//! respected). The checks each stage runs come from `gitHooks` in the
+//! configuration and are baked into the script at install time:
+//!
+//! - `secrets`: `scan --staged` on pre-commit, `scan` on pre-push
+//! - `manifest`: `lint` (manifest validation without builds)
+//! - `check`: the full `check` command
+//!
+//! Hooks written by package-publisher carry a marker line; existing hooks
+//! from other tools are only replaced with `--force`.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::git_hooks::{GitHookInstaller, GitHookStage};
+//! use std::path::Path;
+//!
+//! let installer = GitHookInstaller::new(Path::new("."), None);
+//! let script = installer.script(GitHookStage::PreCommit, "").unwrap();
+//! assert!(script.contains("scan --staged"));
+//! ```
+
+use crate::core::config::GitHooksConfig;
+use crate::core::git::run_git;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::fs;
+
+/// Marker identifying hooks written by package-publisher
+pub const HOOK_MARKER: &str = "# Installed by package-publisher (hooks install)";
+
+/// Git hook a set of checks is installed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHookStage {
+    PreCommit,
+    PrePush,
+}
+
+impl GitHookStage {
+    pub const ALL: [GitHookStage; 2] = [GitHookStage::PreCommit, GitHookStage::PrePush];
+
+    /// Hook file name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GitHookStage::PreCommit => "pre-commit",
+            GitHookStage::PrePush => "pre-push",
+        }
+    }
+}
+
+impl fmt::Display for GitHookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Check a git hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHookCheck {
+    Secrets,
+    Manifest,
+    Check,
+}
+
+impl FromStr for GitHookCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "secrets" => Ok(GitHookCheck::Secrets),
+            "manifest" => Ok(GitHookCheck::Manifest),
+            "check" => Ok(GitHookCheck::Check),
+            other => Err(format!(
+                "Unknown git hook check: {} (expected secrets, manifest or check)",
+                other
+            )),
+        }
+    }
+}
+
+impl GitHookCheck {
+    /// package-publisher arguments for this check at `stage`
+    fn command(&self, stage: GitHookStage) -> &'static str {
+        match (self, stage) {
+            (GitHookCheck::Secrets, GitHookStage::PreCommit) => "scan --staged",
+            (GitHookCheck::Secrets, GitHookStage::PrePush) => "scan",
+            (GitHookCheck::Manifest, _) => "lint",
+            (GitHookCheck::Check, _) => "check",
+        }
+    }
+}
+
+/// Outcome of installing or removing one hook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitHookChange {
+    Written(PathBuf),
+    Removed(PathBuf),
+    /// A hook from another tool exists (install without `--force`)
+    Kept(PathBuf),
+    /// No checks configured for the stage
+    Skipped(GitHookStage),
+}
+
+/// Installs and removes package-publisher git hooks
+pub struct GitHookInstaller {
+    project_path: PathBuf,
+    config: GitHooksConfig,
+}
+
+impl GitHookInstaller {
+    pub fn new(project_path: &Path, config: Option<&GitHooksConfig>) -> Self {
+        Self {
+            project_path: project_path.to_path_buf(),
+            config: config.cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Checks configured for a stage (default: secrets and manifest)
+    pub fn checks(&self, stage: GitHookStage) -> anyhow::Result<Vec<GitHookCheck>> {
+        let configured = match stage {
+            GitHookStage::PreCommit => self.config.pre_commit.as_ref(),
+            GitHookStage::PrePush => self.config.pre_push.as_ref(),
+        };
+        match configured {
+            Some(checks) => checks
+                .iter()
+                .map(|c| c.parse().map_err(anyhow::Error::msg))
+                .collect(),
+            None => Ok(vec![GitHookCheck::Secrets, GitHookCheck::Manifest]),
+        }
+    }
+
+    /// Hook script for a stage
+    ///
+    /// `prefix` is the project directory relative to the repository root
+    /// (`git rev-parse --show-prefix`); git runs hooks from the root.
+    pub fn script(&self, stage: GitHookStage, prefix: &str) -> anyhow::Result<String> {
+        let mut script = format!(
+            "#!/bin/sh\n{}\n# Remove with `package-publisher hooks uninstall`; skip once with `git {} --no-verify`.\nset -e\n",
+            HOOK_MARKER,
+            if stage == GitHookStage::PreCommit {
+                "commit"
+            } else {
+                "push"
+            }
+        );
+        script.push_str("PACKAGE_PUBLISHER=\"${PACKAGE_PUBLISHER:-package-publisher}\"\n");
+        let prefix = prefix.trim_end_matches('/');
+        if !prefix.is_empty() {
+            script.push_str(&format!("cd '{}'\n", prefix.replace('\'', "'\\''")));
+        }
+        for check in self.checks(stage)? {
+            script.push_str(&format!(
+                "\"$PACKAGE_PUBLISHER\" {}\n",
+                check.command(stage)
+            ));
+        }
+        Ok(script)
+    }
+
+    /// Write the hooks; hooks from other tools are kept unless `force`
+    pub async fn install(&self, force: bool) -> anyhow::Result<Vec<GitHookChange>> {
+        let hooks_dir = self.hooks_dir().await?;
+        let prefix = self.git(&["rev-parse", "--show-prefix"]).await?;
+        fs::create_dir_all(&hooks_dir).await?;
+
+        let mut changes = Vec::new();
+        for stage in GitHookStage::ALL {
+            if self.checks(stage)?.is_empty() {
+                changes.push(GitHookChange::Skipped(stage));
+                continue;
+            }
+
+            let path = hooks_dir.join(stage.as_str());
+            if !force && !Self::is_ours_or_missing(&path).await {
+                changes.push(GitHookChange::Kept(path));
+                continue;
+            }
+
+            fs::write(&path, self.script(stage, &prefix)?).await?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).await?;
+            }
+            changes.push(GitHookChange::Written(path));
+        }
+        Ok(changes)
+    }
+
+    /// Remove the hooks written by package-publisher
+    pub async fn uninstall(&self) -> anyhow::Result<Vec<GitHookChange>> {
+        let hooks_dir = self.hooks_dir().await?;
+        let mut changes = Vec::new();
+        for stage in GitHookStage::ALL {
+            let path = hooks_dir.join(stage.as_str());
+            if !fs::try_exists(&path).await.unwrap_or(false) {
+                continue;
+            }
+            if Self::is_ours_or_missing(&path).await {
+                fs::remove_file(&path).await?;
+                changes.push(GitHookChange::Removed(path));
+            } else {
+                changes.push(GitHookChange::Kept(path));
+            }
+        }
+        Ok(changes)
+    }
+
+    async fn is_ours_or_missing(path: &Path) -> bool {
+        match fs::read_to_string(path).await {
+            Ok(content) => content.contains(HOOK_MARKER),
+            Err(e) => e.kind() == std::io::ErrorKind::NotFound,
+        }
+    }
+
+    async fn hooks_dir(&self) -> anyhow::Result<PathBuf> {
+        let path = PathBuf::from(self.git(&["rev-parse", "--git-path", "hooks"]).await?);
+        Ok(if path.is_absolute() {
+            path
+        } else {
+            self.project_path.join(path)
+        })
+    }
+
+    async fn git(&self, args: &[&str]) -> anyhow::Result<String> {
+        run_git(&self.project_path, args).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Not a git repository ({}): {}",
+                self.project_path.display(),
+                e
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_script_uses_configured_checks() {
+        let config = GitHooksConfig {
+            pre_commit: Some(vec!["secrets".to_string()]),
+            pre_push: Some(vec!["manifest".to_string(), "check".to_string()]),
+        };
+        let installer = GitHookInstaller::new(Path::new("."), Some(&config));
+
+        let pre_commit = installer.script(GitHookStage::PreCommit, "").unwrap();
+        assert!(pre_commit.starts_with("#!/bin/sh\n"));
+        assert!(pre_commit.contains("\"$PACKAGE_PUBLISHER\" scan --staged\n"));
+        assert!(!pre_commit.contains(" lint"));
+        assert!(!pre_commit.contains("cd "));
+
+        let pre_push = installer
+            .script(GitHookStage::PrePush, "packages/core/")
+            .unwrap();
+        assert!(pre_push.contains("cd 'packages/core'\n\"$PACKAGE_PUBLISHER\" lint\n"));
+        assert!(pre_push.ends_with("\"$PACKAGE_PUBLISHER\" check\n"));
+
+        let invalid = GitHooksConfig {
+            pre_commit: Some(vec!["lint".to_string()]),
+            pre_push: None,
+        };
+        assert!(
+            GitHookInstaller::new(Path::new("."), Some(&invalid))
+                .checks(GitHookStage::PreCommit)
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_install_keeps_foreign_hooks_and_uninstalls_own() {
+        let temp_dir = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        let hooks = temp_dir.path().join(".git/hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("pre-push"), "#!/bin/sh\nhusky run\n").unwrap();
+
+        let installer = GitHookInstaller::new(temp_dir.path(), None);
+        let changes = installer.install(false).await.unwrap();
+        assert!(matches!(&changes[0], GitHookChange::Written(p) if p.ends_with("pre-commit")));
+        assert!(matches!(&changes[1], GitHookChange::Kept(p) if p.ends_with("pre-push")));
+        assert!(
+            std::fs::read_to_string(hooks.join("pre-commit"))
+                .unwrap()
+                .contains(HOOK_MARKER)
+        );
+
+        let changes = installer.uninstall().await.unwrap();
+        assert!(matches!(&changes[0], GitHookChange::Removed(_)));
+        assert!(matches!(&changes[1], GitHookChange::Kept(_)));
+        assert!(!hooks.join("pre-commit").exists());
+        assert!(hooks.join("pre-push").exists());
+    }
+}
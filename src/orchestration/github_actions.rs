@@ -0,0 +1,309 @@
+//! GitHub Actions - workflow annotations and job summary
+//!
+//! When running under GitHub Actions (`GITHUB_ACTIONS=true`), validation
+//! findings and detected secrets are printed as `::error` / `::warning`
+//! workflow commands, so they show up as annotations on the run and the pull
+//! request diff, and the publish results are appended to the job summary
+//! (`$GITHUB_STEP_SUMMARY`) as Markdown.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::github_actions::{AnnotationLevel, annotation};
+//!
+//! let command = annotation(AnnotationLevel::Warning, "Potential secret", Some(("src/main.rs", Some(3))), None);
+//! assert_eq!(command, "::warning file=src/main.rs,line=3::Potential secret");
+//! ```
+
+use crate::orchestration::analytics::PublishAnalytics;
+use crate::orchestration::events::{EventSink, FindingSeverity, PublishEvent};
+use crate::orchestration::package_publisher::PublishReport;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// Annotation severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    fn command(&self) -> &'static str {
+        match self {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        }
+    }
+}
+
+impl From<FindingSeverity> for AnnotationLevel {
+    fn from(severity: FindingSeverity) -> Self {
+        match severity {
+            FindingSeverity::Error => AnnotationLevel::Error,
+            FindingSeverity::Warning => AnnotationLevel::Warning,
+        }
+    }
+}
+
+/// Format a workflow command annotation
+///
+/// `location` is a workspace-relative file and optional line.
+pub fn annotation(
+    level: AnnotationLevel,
+    message: &str,
+    location: Option<(&str, Option<usize>)>,
+    title: Option<&str>,
+) -> String {
+    let mut properties = Vec::new();
+    if let Some((file, line)) = location {
+        properties.push(format!("file={}", escape_property(file)));
+        if let Some(line) = line {
+            properties.push(format!("line={}", line));
+        }
+    }
+    if let Some(title) = title {
+        properties.push(format!("title={}", escape_property(title)));
+    }
+
+    let mut command = format!("::{}", level.command());
+    if !properties.is_empty() {
+        command.push(' ');
+        command.push_str(&properties.join(","));
+    }
+    command.push_str("::");
+    command.push_str(&escape_data(message));
+    command
+}
+
+/// Escape a workflow command message
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Manifest that validation findings of a registry refer to
+fn registry_manifest(registry: &str) -> Option<&'static str> {
+    match registry {
+        "npm" | "github-packages" | "upm" | "expo" => Some("package.json"),
+        "crates.io" => Some("Cargo.toml"),
+        "pypi" => Some("pyproject.toml"),
+        _ => None,
+    }
+}
+
+/// GitHub Actions integration of a run
+#[derive(Debug, Clone)]
+pub struct GitHubActions {
+    project_path: PathBuf,
+    /// Checkout directory annotation paths are relative to (`GITHUB_WORKSPACE`)
+    workspace: Option<PathBuf>,
+    /// Job summary file (`GITHUB_STEP_SUMMARY`)
+    step_summary: Option<PathBuf>,
+}
+
+impl GitHubActions {
+    /// Integration for `project_path`, when running under GitHub Actions
+    pub fn detect(project_path: &Path) -> Option<Self> {
+        Self::from_env(project_path, |name| std::env::var(name).ok())
+    }
+
+    /// Like [`detect`](Self::detect), reading variables through `var`
+    pub fn from_env(project_path: &Path, var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if var("GITHUB_ACTIONS").as_deref() != Some("true") {
+            return None;
+        }
+        let non_empty = |name| var(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        Some(Self {
+            project_path: project_path.to_path_buf(),
+            workspace: non_empty("GITHUB_WORKSPACE"),
+            step_summary: non_empty("GITHUB_STEP_SUMMARY"),
+        })
+    }
+
+    /// Path of a project file relative to the workspace
+    fn workspace_path(&self, file: &Path) -> String {
+        let absolute = if file.is_absolute() {
+            file.to_path_buf()
+        } else {
+            self.project_path.join(file)
+        };
+        let absolute = absolute.canonicalize().unwrap_or(absolute);
+        let relative = self
+            .workspace
+            .as_ref()
+            .and_then(|workspace| {
+                let workspace = workspace.canonicalize().unwrap_or(workspace.clone());
+                absolute.strip_prefix(workspace).ok().map(Path::to_path_buf)
+            })
+            .unwrap_or_else(|| file.to_path_buf());
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Annotation for a validation finding of `registry`
+    pub fn validation_annotation(
+        &self,
+        registry: &str,
+        severity: FindingSeverity,
+        field: &str,
+        message: &str,
+    ) -> String {
+        let manifest = registry_manifest(registry).map(|m| self.workspace_path(Path::new(m)));
+        annotation(
+            severity.into(),
+            &format!("[{}] {}", field, message),
+            manifest.as_deref().map(|m| (m, None)),
+            Some(&format!("{} validation", registry)),
+        )
+    }
+
+    /// Annotation for a potential secret
+    pub fn secret_annotation(&self, file: &Path, line: usize, secret_type: &str) -> String {
+        let file = self.workspace_path(file);
+        annotation(
+            AnnotationLevel::Error,
+            &format!("Potential secret detected: {}", secret_type),
+            Some((&file, Some(line))),
+            Some("Secrets scan"),
+        )
+    }
+
+    /// Append the Markdown summary of `reports` to the job summary
+    pub async fn write_summary(&self, reports: &[PublishReport]) -> anyhow::Result<()> {
+        let Some(ref path) = self.step_summary else {
+            return Ok(());
+        };
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(PublishAnalytics::summarize(reports).as_bytes())
+            .await?;
+        file.write_all(b"\n").await?;
+        Ok(())
+    }
+}
+
+/// Prints annotations for validation findings and detected secrets
+impl EventSink for GitHubActions {
+    fn emit(&self, event: &PublishEvent) {
+        match event {
+            PublishEvent::ValidationFinding {
+                registry,
+                severity,
+                field,
+                message,
+            } => println!(
+                "{}",
+                self.validation_annotation(registry, *severity, field, message)
+            ),
+            PublishEvent::SecretDetected {
+                file,
+                line,
+                secret_type,
+                ..
+            } => println!(
+                "{}",
+                self.secret_annotation(Path::new(file), *line, secret_type)
+            ),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn actions(project: &Path, summary: Option<&Path>) -> GitHubActions {
+        let mut env = HashMap::from([
+            ("GITHUB_ACTIONS", "true".to_string()),
+            (
+                "GITHUB_WORKSPACE",
+                project.parent().unwrap().display().to_string(),
+            ),
+        ]);
+        if let Some(summary) = summary {
+            env.insert("GITHUB_STEP_SUMMARY", summary.display().to_string());
+        }
+        GitHubActions::from_env(project, |name| env.get(name).cloned()).unwrap()
+    }
+
+    #[test]
+    fn test_detect_requires_github_actions() {
+        assert!(GitHubActions::from_env(Path::new("."), |_| None).is_none());
+        assert!(
+            GitHubActions::from_env(Path::new("."), |name| {
+                (name == "GITHUB_ACTIONS").then(|| "false".to_string())
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_annotations_are_workspace_relative_and_escaped() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("pkg");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        std::fs::write(project.join("src/config.rs"), "").unwrap();
+        std::fs::write(project.join("package.json"), "{}").unwrap();
+        let actions = actions(&project, None);
+
+        assert_eq!(
+            actions.secret_annotation(&project.join("src/config.rs"), 12, "AWS Access Key"),
+            "::error file=pkg/src/config.rs,line=12,title=Secrets scan::Potential secret detected: AWS Access Key"
+        );
+        assert_eq!(
+            actions.validation_annotation(
+                "npm",
+                FindingSeverity::Warning,
+                "license",
+                "100%\nmissing"
+            ),
+            "::warning file=pkg/package.json,title=npm validation::[license] 100%25%0Amissing"
+        );
+        assert_eq!(
+            annotation(AnnotationLevel::Notice, "done", None, Some("a:b,c")),
+            "::notice title=a%3Ab%2Cc::done"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_summary_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        let summary = temp_dir.path().join("summary.md");
+        std::fs::write(&summary, "# Build\n").unwrap();
+        let actions = actions(&temp_dir.path().join("pkg"), Some(&summary));
+
+        let report: PublishReport = serde_json::from_value(serde_json::json!({
+            "success": true,
+            "registry": "npm",
+            "package_name": "demo",
+            "version": "1.0.0",
+            "published_at": null,
+            "verification_url": null,
+            "errors": [],
+            "warnings": [],
+            "duration": 1200,
+            "state": "SUCCESS",
+            "integrity": null,
+        }))
+        .unwrap();
+        actions.write_summary(&[report]).await.unwrap();
+
+        let contents = std::fs::read_to_string(&summary).unwrap();
+        assert!(contents.starts_with("# Build\n"));
+        assert!(contents.contains("| npm | demo | 1.0.0 | ✅ Success |"));
+    }
+}
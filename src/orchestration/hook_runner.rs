@@ -0,0 +1,466 @@
+//! Hook Runner - Executes configured lifecycle hooks
+//!
+//! Runs the commands configured under `hooks:` (preBuild, prePublish,
+//! postPublish, onError) through [`SafeCommandExecutor`]:
+//! - Commands are split into program + arguments (no shell involved)
+//! - The program must be listed in the hook's `allowedCommands`
+//! - `timeout` (seconds, default: 300) and `workingDirectory` are honored
+//! - With [`HookRunner::with_sandbox`], hooks only see an allowlist of
+//!   environment variables (plus the hook's `allowEnv`), and
+//!   `workingDirectory` must stay inside the project
+//! - `${VERSION}`, `${PACKAGE_NAME}` and `${REGISTRY}` are substituted
+
+use crate::core::config::{HookCommand, HooksConfig};
+use crate::security::command_executor::{CommandError, EnvSandbox, SafeCommandExecutor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default hook timeout in seconds
+const DEFAULT_HOOK_TIMEOUT_SECS: u32 = 300;
+
+/// Placeholders substituted when a hook runs (left alone by config env expansion)
+pub const HOOK_PLACEHOLDERS: [&str; 3] = ["VERSION", "PACKAGE_NAME", "REGISTRY"];
+
+/// Hook lifecycle stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookStage {
+    PreBuild,
+    PrePublish,
+    PostPublish,
+    OnError,
+}
+
+impl HookStage {
+    /// Config key for this stage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::PreBuild => "preBuild",
+            HookStage::PrePublish => "prePublish",
+            HookStage::PostPublish => "postPublish",
+            HookStage::OnError => "onError",
+        }
+    }
+
+    /// Hooks configured for this stage
+    pub fn commands(self, hooks: &HooksConfig) -> &[HookCommand] {
+        let commands = match self {
+            HookStage::PreBuild => &hooks.pre_build,
+            HookStage::PrePublish => &hooks.pre_publish,
+            HookStage::PostPublish => &hooks.post_publish,
+            HookStage::OnError => &hooks.on_error,
+        };
+        commands.as_deref().unwrap_or(&[])
+    }
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Values substituted into hook commands
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    pub version: Option<String>,
+    pub package_name: Option<String>,
+    pub registry: Option<String>,
+}
+
+impl HookContext {
+    /// Substitute `${VERSION}`, `${PACKAGE_NAME}` and `${REGISTRY}` placeholders
+    pub fn substitute(&self, command: &str) -> String {
+        let mut result = command.to_string();
+        for (name, value) in
+            HOOK_PLACEHOLDERS
+                .into_iter()
+                .zip([&self.version, &self.package_name, &self.registry])
+        {
+            if let Some(value) = value {
+                result = result.replace(&format!("${{{}}}", name), value);
+            }
+        }
+        result
+    }
+}
+
+/// Result of a single hook command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookResult {
+    pub stage: HookStage,
+    pub command: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// Duration in milliseconds
+    pub duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Executes lifecycle hooks
+pub struct HookRunner {
+    project_path: PathBuf,
+    hooks: HooksConfig,
+    env_sandbox: Option<EnvSandbox>,
+    jail: bool,
+}
+
+impl HookRunner {
+    /// Create a new HookRunner
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - Project directory (base for `workingDirectory`)
+    /// * `hooks` - Hooks configuration
+    pub fn new<P: AsRef<Path>>(project_path: P, hooks: HooksConfig) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            hooks,
+            env_sandbox: None,
+            jail: false,
+        }
+    }
+
+    /// Run hooks with the minimal environment of `env_sandbox` (inherited
+    /// when `None`) and, with `jail`, only inside the project
+    pub fn with_sandbox(mut self, env_sandbox: Option<EnvSandbox>, jail: bool) -> Self {
+        self.env_sandbox = env_sandbox;
+        self.jail = jail;
+        self
+    }
+
+    /// Whether any hook is configured for the stage
+    pub fn has_hooks(&self, stage: HookStage) -> bool {
+        !stage.commands(&self.hooks).is_empty()
+    }
+
+    /// Run all hooks of a stage in order, stopping at the first failure
+    ///
+    /// Returns the results of every executed hook; check `success` on the
+    /// last entry to know whether the stage passed.
+    pub async fn run_stage(&self, stage: HookStage, context: &HookContext) -> Vec<HookResult> {
+        let mut results = Vec::new();
+
+        for hook in stage.commands(&self.hooks) {
+            let result = self.run_hook(stage, hook, context).await;
+            let failed = !result.success;
+            results.push(result);
+            if failed {
+                break;
+            }
+        }
+
+        results
+    }
+
+    /// Run a single hook command
+    async fn run_hook(
+        &self,
+        stage: HookStage,
+        hook: &HookCommand,
+        context: &HookContext,
+    ) -> HookResult {
+        let command = context.substitute(&hook.command);
+        let started = Instant::now();
+
+        let outcome = match self.prepare(hook, &command) {
+            Ok((executor, program, args)) => tokio::task::spawn_blocking(move || {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                executor.execute(&program, &args)
+            })
+            .await
+            .map_err(|e| CommandError::ExecutionFailed(e.to_string()))
+            .and_then(|r| r),
+            Err(e) => Err(e),
+        };
+
+        let duration = started.elapsed().as_millis() as u64;
+        match outcome {
+            Ok(output) => {
+                let success = output.status.success();
+                HookResult {
+                    stage,
+                    command,
+                    success,
+                    exit_code: output.status.code(),
+                    stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                    duration,
+                    error: (!success).then(|| {
+                        format!("Exited with status {}", output.status.code().unwrap_or(-1))
+                    }),
+                }
+            }
+            Err(e) => HookResult {
+                stage,
+                command,
+                success: false,
+                exit_code: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                duration,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Build an executor for the hook and split the command line
+    fn prepare(
+        &self,
+        hook: &HookCommand,
+        command: &str,
+    ) -> Result<(SafeCommandExecutor, String, Vec<String>), CommandError> {
+        let mut parts = split_command(command).map_err(CommandError::ExecutionFailed)?;
+        if parts.is_empty() {
+            return Err(CommandError::ExecutionFailed(
+                "Empty hook command".to_string(),
+            ));
+        }
+        let program = parts.remove(0);
+
+        if !hook.allowed_commands.contains(&program) {
+            return Err(CommandError::CommandNotAllowed(program));
+        }
+
+        let working_dir = match hook.working_directory.as_deref() {
+            Some(dir) => self.project_path.join(dir),
+            None => self.project_path.clone(),
+        };
+
+        let mut executor = SafeCommandExecutor::new(working_dir)?;
+        if self.jail {
+            executor.check_jail(&self.project_path)?;
+        }
+        if let Some(sandbox) = &self.env_sandbox {
+            executor.set_env_sandbox(
+                sandbox
+                    .clone()
+                    .with_allowed(hook.allow_env.as_deref().unwrap_or_default()),
+            );
+        }
+        executor.allow_commands(&hook.allowed_commands);
+        executor.set_timeout(Duration::from_secs(u64::from(
+            hook.timeout.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS),
+        )));
+
+        Ok((executor, program, parts))
+    }
+}
+
+/// Split a command line into arguments, honoring single and double quotes
+///
+/// Shell operators (`&&`, `||`, `|`, `;`) are rejected since hooks never run
+/// through a shell.
+pub(crate) fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    parts.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(format!("Unterminated quote in hook command: {}", command));
+    }
+    if in_token {
+        parts.push(current);
+    }
+
+    if let Some(op) = parts
+        .iter()
+        .find(|p| matches!(p.as_str(), "&&" | "||" | "|" | ";"))
+    {
+        return Err(format!(
+            "Shell operator '{}' is not supported in hook commands; split into separate hooks",
+            op
+        ));
+    }
+
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hook(command: &str, allowed: &[&str]) -> HookCommand {
+        HookCommand {
+            command: command.to_string(),
+            allowed_commands: allowed.iter().map(|s| s.to_string()).collect(),
+            timeout: None,
+            working_directory: None,
+            allow_env: None,
+        }
+    }
+
+    fn hooks_config(pre_build: Vec<HookCommand>) -> HooksConfig {
+        HooksConfig {
+            pre_build: Some(pre_build),
+            pre_publish: None,
+            post_publish: None,
+            on_error: None,
+        }
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command("npm run test").unwrap(),
+            vec!["npm", "run", "test"]
+        );
+        assert_eq!(
+            split_command(r#"git commit -m "release v1.0.0""#).unwrap(),
+            vec!["git", "commit", "-m", "release v1.0.0"]
+        );
+        assert_eq!(split_command("echo ''").unwrap(), vec!["echo", ""]);
+        assert!(split_command("echo \"unterminated").is_err());
+        assert!(split_command("npm test && rm -rf /").is_err());
+    }
+
+    #[test]
+    fn test_context_substitution() {
+        let context = HookContext {
+            version: Some("1.2.3".to_string()),
+            package_name: Some("my-pkg".to_string()),
+            registry: Some("npm".to_string()),
+        };
+        assert_eq!(
+            context.substitute("git tag v${VERSION} -m ${PACKAGE_NAME}@${REGISTRY}"),
+            "git tag v1.2.3 -m my-pkg@npm"
+        );
+        assert_eq!(
+            HookContext::default().substitute("git tag v${VERSION}"),
+            "git tag v${VERSION}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_stage_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = HookRunner::new(
+            temp_dir.path(),
+            hooks_config(vec![hook("cargo --version", &["cargo"])]),
+        );
+
+        assert!(runner.has_hooks(HookStage::PreBuild));
+        assert!(!runner.has_hooks(HookStage::PostPublish));
+
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+        assert_eq!(results[0].exit_code, Some(0));
+        assert!(results[0].stdout.contains("cargo"));
+    }
+
+    #[tokio::test]
+    async fn test_run_stage_rejects_unlisted_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = HookRunner::new(
+            temp_dir.path(),
+            hooks_config(vec![
+                hook("cargo --version", &["npm"]),
+                hook("cargo --version", &["cargo"]),
+            ]),
+        );
+
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        // Stops at the first failure
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].success);
+        assert!(
+            results[0]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("not in the allowed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_stage_timeout_and_working_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut slow = hook("sleep 5", &["sleep"]);
+        slow.timeout = Some(0);
+        let mut missing_dir = hook("cargo --version", &["cargo"]);
+        missing_dir.working_directory = Some("does-not-exist".to_string());
+
+        let runner = HookRunner::new(temp_dir.path(), hooks_config(vec![slow]));
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        assert!(!results[0].success);
+        assert!(results[0].error.as_ref().unwrap().contains("timeout"));
+
+        let runner = HookRunner::new(temp_dir.path(), hooks_config(vec![missing_dir]));
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        assert!(!results[0].success);
+        assert!(
+            results[0]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("Working directory")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sandboxed_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        let mut escaping = hook("cargo --version", &["cargo"]);
+        escaping.working_directory = Some("..".to_string());
+        let mut env = hook("env", &["env"]);
+        env.allow_env = Some(vec!["CARGO_PKG_NAME".to_string()]);
+
+        let runner = HookRunner::new(&project, hooks_config(vec![escaping]))
+            .with_sandbox(Some(EnvSandbox::new::<&str>(&[])), true);
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        assert!(!results[0].success);
+        assert!(results[0].error.as_ref().unwrap().contains("outside"));
+
+        let runner = HookRunner::new(&project, hooks_config(vec![env]))
+            .with_sandbox(Some(EnvSandbox::new::<&str>(&[])), true);
+        let results = runner
+            .run_stage(HookStage::PreBuild, &HookContext::default())
+            .await;
+        assert!(results[0].success);
+        assert!(results[0].stdout.contains("CARGO_PKG_NAME="));
+        assert!(!results[0].stdout.contains("CARGO_PKG_VERSION="));
+    }
+}
@@ -0,0 +1,502 @@
+//! Issue Tracker - Jira / Linear release tracking
+//!
+//! After a successful publish, issues referenced by commit messages since the
+//! previous tag (`ABC-123` style keys) are moved to the release state and get
+//! a comment with the version and registry URLs:
+//! - Jira: REST API v2 (workflow transition by name + comment)
+//! - Linear: GraphQL API (workflow state by name + comment)
+//!
+//! API tokens come from [`SecureTokenManager`] (`JIRA_API_TOKEN`,
+//! `LINEAR_API_KEY`, or `tokenEnv`). Like notifications, failures never fail
+//! a publish; they are returned as [`IssueUpdate`]s for the caller to report.
+
+use crate::core::config::{JiraNotificationConfig, LinearNotificationConfig, NotificationsConfig};
+use crate::core::git::run_git;
+use crate::core::http::http_client;
+use crate::orchestration::notifier::NotificationContext;
+use crate::security::SecureTokenManager;
+use regex::Regex;
+use secrecy::{ExposeSecret, SecretString};
+use std::path::Path;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+const DEFAULT_RELEASE_STATE: &str = "Released";
+
+const DEFAULT_COMMENT_TEMPLATE: &str = "Released in {package}@{version} ({registry})\n{urls}";
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Request timeout per API call
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+static ISSUE_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z][A-Z0-9_]{0,9}-[1-9][0-9]*)\b").unwrap());
+
+/// Issue keys (`ABC-123`) referenced in commit messages, in first-seen order
+///
+/// With `prefixes`, only keys of those projects/teams are returned.
+pub fn extract_issue_keys(messages: &[String], prefixes: Option<&[String]>) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for message in messages {
+        for capture in ISSUE_KEY.captures_iter(message) {
+            let key = &capture[1];
+            let prefix = key.split('-').next().unwrap_or_default();
+            if prefixes.is_some_and(|p| !p.iter().any(|p| p == prefix)) {
+                continue;
+            }
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.to_string());
+            }
+        }
+    }
+    keys
+}
+
+/// Full commit messages since the tag before `version` (or the whole history)
+///
+/// Tags of the version being released (`v1.2.0`, `1.2.0`) are skipped, so
+/// the range is right whether or not the release tag was already created.
+pub async fn commit_messages_since_last_release(
+    project_path: &Path,
+    version: &str,
+) -> anyhow::Result<Vec<String>> {
+    let last_tag = run_git(
+        project_path,
+        &[
+            "describe",
+            "--tags",
+            "--abbrev=0",
+            &format!("--exclude=v{}", version),
+            &format!("--exclude={}", version),
+        ],
+    )
+    .await
+    .ok()
+    .filter(|t| !t.is_empty());
+
+    let range = last_tag.map(|tag| format!("{}..HEAD", tag));
+    let mut args = vec!["log", "--format=%B%x1e"];
+    if let Some(ref range) = range {
+        args.push(range);
+    }
+    let log = run_git(project_path, &args).await?;
+    Ok(log
+        .split('\x1e')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect())
+}
+
+/// Result of updating one issue
+#[derive(Debug, Clone)]
+pub struct IssueUpdate {
+    /// Tracker name ("jira" or "linear")
+    pub tracker: &'static str,
+    pub key: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Values for release comments
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseInfo {
+    pub context: NotificationContext,
+    /// Registry / release URLs listed in the comment
+    pub urls: Vec<String>,
+}
+
+impl ReleaseInfo {
+    /// Render a comment template (`{urls}` plus the notification placeholders)
+    pub fn render(&self, template: &str) -> String {
+        self.context
+            .render(template)
+            .replace("{urls}", &self.urls.join("\n"))
+            .trim_end()
+            .to_string()
+    }
+}
+
+/// Updates Jira / Linear issues configured under `notifications:`
+pub struct IssueTracker {
+    jira: Option<JiraNotificationConfig>,
+    linear: Option<LinearNotificationConfig>,
+    linear_api_url: String,
+    tokens: SecureTokenManager,
+    client: reqwest::Client,
+}
+
+impl IssueTracker {
+    /// Create a tracker for the `jira` / `linear` settings
+    pub fn new(config: &NotificationsConfig) -> Self {
+        Self {
+            jira: config.jira.clone(),
+            linear: config.linear.clone(),
+            linear_api_url: LINEAR_API_URL.to_string(),
+            tokens: SecureTokenManager::new(),
+            client: http_client(),
+        }
+    }
+
+    /// Build a tracker when notifications are enabled and a tracker is configured
+    pub fn from_config(config: Option<&NotificationsConfig>) -> Option<Self> {
+        config
+            .filter(|n| n.enabled.unwrap_or(false))
+            .filter(|n| n.jira.is_some() || n.linear.is_some())
+            .map(Self::new)
+    }
+
+    /// Use another Linear API endpoint
+    pub fn with_linear_api_url(mut self, url: impl Into<String>) -> Self {
+        self.linear_api_url = url.into();
+        self
+    }
+
+    /// Transition and comment on every issue referenced by `messages`
+    pub async fn release(&self, messages: &[String], release: &ReleaseInfo) -> Vec<IssueUpdate> {
+        let mut updates = Vec::new();
+
+        if let Some(ref jira) = self.jira {
+            let keys = extract_issue_keys(messages, jira.project_keys.as_deref());
+            let token = self
+                .tokens
+                .get_service_token("jira", jira.token_env.as_deref());
+            for key in keys {
+                let result = match token {
+                    Some(ref token) => self.release_jira(jira, token, &key, release).await,
+                    None => Err(anyhow::anyhow!("Jira API token is not set")),
+                };
+                updates.push(update("jira", key, result));
+            }
+        }
+
+        if let Some(ref linear) = self.linear {
+            let keys = extract_issue_keys(messages, linear.team_keys.as_deref());
+            let token = self
+                .tokens
+                .get_service_token("linear", linear.token_env.as_deref());
+            for key in keys {
+                let result = match token {
+                    Some(ref token) => self.release_linear(linear, token, &key, release).await,
+                    None => Err(anyhow::anyhow!("Linear API key is not set")),
+                };
+                updates.push(update("linear", key, result));
+            }
+        }
+
+        updates
+    }
+
+    fn jira_request(
+        &self,
+        config: &JiraNotificationConfig,
+        token: &SecretString,
+        method: reqwest::Method,
+        path: &str,
+    ) -> reqwest::RequestBuilder {
+        let url = format!(
+            "{}/rest/api/2/{}",
+            config.base_url.trim_end_matches('/'),
+            path
+        );
+        let request = self
+            .client
+            .request(method, url)
+            .timeout(REQUEST_TIMEOUT)
+            .header("Accept", "application/json");
+        match config.email {
+            Some(ref email) => request.basic_auth(email, Some(token.expose_secret())),
+            None => request.bearer_auth(token.expose_secret()),
+        }
+    }
+
+    async fn release_jira(
+        &self,
+        config: &JiraNotificationConfig,
+        token: &SecretString,
+        key: &str,
+        release: &ReleaseInfo,
+    ) -> anyhow::Result<()> {
+        let wanted = config
+            .transition
+            .as_deref()
+            .unwrap_or(DEFAULT_RELEASE_STATE);
+        let response = self
+            .jira_request(
+                config,
+                token,
+                reqwest::Method::GET,
+                &format!("issue/{}/transitions", key),
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Jira returned {} for {}", response.status(), key);
+        }
+        let transitions: serde_json::Value = response.json().await?;
+        let transition_id = transitions["transitions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|t| {
+                t["name"]
+                    .as_str()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(wanted))
+                    || t["to"]["name"]
+                        .as_str()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(wanted))
+            })
+            .and_then(|t| t["id"].as_str())
+            .map(str::to_string);
+
+        // Issues already in the release state have no matching transition
+        if let Some(id) = transition_id {
+            let response = self
+                .jira_request(
+                    config,
+                    token,
+                    reqwest::Method::POST,
+                    &format!("issue/{}/transitions", key),
+                )
+                .json(&serde_json::json!({ "transition": { "id": id } }))
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                anyhow::bail!("Jira transition failed ({})", response.status());
+            }
+        }
+
+        let comment = release.render(
+            config
+                .comment
+                .as_deref()
+                .unwrap_or(DEFAULT_COMMENT_TEMPLATE),
+        );
+        let response = self
+            .jira_request(
+                config,
+                token,
+                reqwest::Method::POST,
+                &format!("issue/{}/comment", key),
+            )
+            .json(&serde_json::json!({ "body": comment }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Jira comment failed ({})", response.status());
+        }
+        Ok(())
+    }
+
+    async fn linear_graphql(
+        &self,
+        token: &SecretString,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let response = self
+            .client
+            .post(&self.linear_api_url)
+            .timeout(REQUEST_TIMEOUT)
+            .header("Authorization", token.expose_secret())
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Linear returned {}", response.status());
+        }
+        let body: serde_json::Value = response.json().await?;
+        if let Some(message) = body["errors"][0]["message"].as_str() {
+            anyhow::bail!("Linear: {}", message);
+        }
+        Ok(body["data"].clone())
+    }
+
+    async fn release_linear(
+        &self,
+        config: &LinearNotificationConfig,
+        token: &SecretString,
+        key: &str,
+        release: &ReleaseInfo,
+    ) -> anyhow::Result<()> {
+        let wanted = config.state.as_deref().unwrap_or(DEFAULT_RELEASE_STATE);
+        let data = self
+            .linear_graphql(
+                token,
+                "query($id: String!) { issue(id: $id) { id team { states { nodes { id name } } } } }",
+                serde_json::json!({ "id": key }),
+            )
+            .await?;
+        let issue_id = data["issue"]["id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("issue not found"))?;
+        let state_id = data["issue"]["team"]["states"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|s| {
+                s["name"]
+                    .as_str()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(wanted))
+            })
+            .and_then(|s| s["id"].as_str())
+            .ok_or_else(|| anyhow::anyhow!("workflow state \"{}\" not found", wanted))?;
+
+        self.linear_graphql(
+            token,
+            "mutation($id: String!, $stateId: String!) { issueUpdate(id: $id, input: { stateId: $stateId }) { success } }",
+            serde_json::json!({ "id": issue_id, "stateId": state_id }),
+        )
+        .await?;
+
+        let comment = release.render(
+            config
+                .comment
+                .as_deref()
+                .unwrap_or(DEFAULT_COMMENT_TEMPLATE),
+        );
+        self.linear_graphql(
+            token,
+            "mutation($issueId: String!, $body: String!) { commentCreate(input: { issueId: $issueId, body: $body }) { success } }",
+            serde_json::json!({ "issueId": issue_id, "body": comment }),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn update(tracker: &'static str, key: String, result: anyhow::Result<()>) -> IssueUpdate {
+    IssueUpdate {
+        tracker,
+        key,
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned response per connection, returning the requests
+    async fn serve(listener: TcpListener, responses: Vec<&'static str>) -> Vec<String> {
+        let mut requests = Vec::new();
+        for body in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request).to_string());
+        }
+        requests
+    }
+
+    fn release_info() -> ReleaseInfo {
+        ReleaseInfo {
+            context: NotificationContext {
+                package_name: "my-pkg".to_string(),
+                version: "1.2.3".to_string(),
+                registry: "npm".to_string(),
+                success: true,
+                ..Default::default()
+            },
+            urls: vec!["https://www.npmjs.com/package/my-pkg/v/1.2.3".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_extract_issue_keys() {
+        let messages = vec![
+            "feat: add retries (PROJ-12)\n\nCloses PROJ-12, relates to ENG-7".to_string(),
+            "fix: handle UTF-8 names\n\nRefs OPS-3".to_string(),
+        ];
+        assert_eq!(
+            extract_issue_keys(&messages, None),
+            vec!["PROJ-12", "ENG-7", "UTF-8", "OPS-3"]
+        );
+        assert_eq!(
+            extract_issue_keys(&messages, Some(&["PROJ".to_string(), "OPS".to_string()])),
+            vec!["PROJ-12", "OPS-3"]
+        );
+        assert_eq!(
+            release_info().render("{package}@{version}: {urls}"),
+            "my-pkg@1.2.3: https://www.npmjs.com/package/my-pkg/v/1.2.3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_release_jira_issue() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                r#"{"transitions":[{"id":"21","name":"Start"},{"id":"31","name":"Ship","to":{"name":"Released"}}]}"#,
+                "{}",
+                r#"{"id":"10001"}"#,
+            ],
+        ));
+
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_JIRA_TOKEN", "jira-token-123456");
+        }
+        let tracker = IssueTracker::new(&NotificationsConfig {
+            enabled: Some(true),
+            on_success: None,
+            on_failure: None,
+            templates: None,
+            slack: None,
+            discord: None,
+            teams: None,
+            mattermost: None,
+            email: None,
+            jira: Some(JiraNotificationConfig {
+                base_url,
+                email: Some("bot@example.com".to_string()),
+                token_env: Some("PACKAGE_PUBLISHER_TEST_JIRA_TOKEN".to_string()),
+                project_keys: Some(vec!["PROJ".to_string()]),
+                ..Default::default()
+            }),
+            linear: None,
+        });
+        let updates = tracker
+            .release(&["fix: PROJ-5 and ENG-9".to_string()], &release_info())
+            .await;
+
+        assert_eq!(updates.len(), 1);
+        assert!(updates[0].success, "{:?}", updates[0].error);
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /rest/api/2/issue/PROJ-5/transitions"));
+        assert!(requests[1].contains(r#"{"transition":{"id":"31"}}"#));
+        assert!(requests[2].starts_with("POST /rest/api/2/issue/PROJ-5/comment"));
+        assert!(requests[2].contains("Released in my-pkg@1.2.3 (npm)"));
+    }
+}
@@ -4,10 +4,65 @@
 //! managing package publishing workflows across multiple registries.
 
 pub mod analytics;
+pub mod approval;
+pub mod audit_export;
 pub mod batch_publisher;
+pub mod changelog;
+pub mod check_runner;
+pub mod deployment;
+pub mod duration_budget;
+pub mod events;
+pub mod git_hooks;
+pub mod github_actions;
+pub mod hook_runner;
+pub mod issue_tracker;
+pub mod notifier;
+pub mod otp;
 pub mod package_publisher;
+pub mod policy;
+pub mod prometheus;
+pub mod release;
+pub mod release_window;
+pub mod report_file;
+pub mod scheduler;
+pub mod setup_wizard;
+pub mod telemetry;
+pub mod tui;
+pub mod verify_poller;
+pub mod watch;
+pub mod webhook;
+pub mod workspace;
 
 // Re-export main types for convenience
-pub use analytics::{AnalyticsOptions, AnalyticsRecord, PublishAnalytics, PublishStatistics};
+pub use analytics::{
+    AnalyticsOptions, AnalyticsRecord, ExportFormat, PublishAnalytics, PublishStatistics,
+};
+pub use approval::{ApprovalDecision, ApprovalGate, ApprovalTicket};
+pub use audit_export::{AuditExportResult, AuditExporter, ReleaseArtifactStore};
 pub use batch_publisher::{BatchPublishOptions, BatchPublishResult, BatchPublisher};
-pub use package_publisher::{PackagePublisher, PublishOptions, PublishReport};
+pub use changelog::{ChangelogGenerator, ChangelogResult, ConventionalCommit};
+pub use check_runner::{CheckMode, CheckRunner, CheckSummary};
+pub use deployment::{DeploymentManager, GitHubDeployment};
+pub use events::{ConfirmationPrompt, EventSink, FindingSeverity, PublishEvent, TerminalPrompt};
+pub use git_hooks::{GitHookInstaller, GitHookStage};
+pub use github_actions::GitHubActions;
+pub use hook_runner::{HookContext, HookResult, HookRunner, HookStage};
+pub use issue_tracker::{IssueTracker, IssueUpdate};
+pub use notifier::{
+    MessageLevel, NotificationContext, NotificationMessage, NotificationOutcome, Notifier,
+};
+pub use package_publisher::{
+    PackagePublisher, PublishOptions, PublishReport, RollbackOptions, RollbackReport,
+};
+pub use policy::{PolicyEngine, PolicyReport, PolicyViolation};
+pub use release::{ReleaseManager, ReleaseOutcome};
+pub use release_window::{CronWindow, ReleaseWindows};
+pub use report_file::{PublishResults, ReportDocument, ReportFormat};
+pub use scheduler::{ReleaseScheduler, ScheduledRelease};
+pub use setup_wizard::{SetupProposal, SetupWizard};
+pub use telemetry::{PublishOutcome, PublishTrace, TelemetryExporter};
+pub use tui::PublishTui;
+pub use verify_poller::{VerifyOutcome, VerifyPoller};
+pub use watch::ProjectWatcher;
+pub use webhook::{WebhookDelivery, WebhookEmitter, WebhookEvent, WebhookPayload};
+pub use workspace::{WorkspaceEcosystem, WorkspacePackage, WorkspaceScanner, with_dependents};
@@ -0,0 +1,621 @@
+//! Notifier - Sends publish notifications
+//!
+//! Dispatches the `notifications:` config to its channels:
+//! - Slack: incoming webhook (`{"text": ...}`)
+//! - Discord: webhook (`{"content": ...}`, mentions disabled)
+//! - Microsoft Teams: incoming webhook / workflow (Adaptive Card)
+//! - Mattermost: incoming webhook (Slack-compatible `{"text": ...}`)
+//! - Email: SMTP (STARTTLS on 587 by default, implicit TLS on 465)
+//!
+//! Messages are rendered from templates with `{package}`, `{version}`,
+//! `{registry}`, `{duration}`, `{status}` and `{error}` placeholders into a
+//! [`NotificationMessage`] that every channel formats the same way.
+//! Notification failures never fail a publish; they are returned as
+//! [`NotificationOutcome`]s for the caller to report.
+
+use crate::core::config::{EmailNotificationConfig, NotificationsConfig, PublishConfig};
+use crate::core::http::http_client;
+use crate::orchestration::package_publisher::PublishReport;
+use crate::security::redaction::redact;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+
+/// Default template for successful publishes
+const DEFAULT_SUCCESS_TEMPLATE: &str =
+    "✅ {package}@{version} published to {registry} in {duration}";
+
+/// Default template for failed publishes
+const DEFAULT_FAILURE_TEMPLATE: &str =
+    "❌ Failed to publish {package}@{version} to {registry} after {duration}: {error}";
+
+/// Default email subject template
+const DEFAULT_SUBJECT_TEMPLATE: &str = "[package-publisher] {package}@{version} {status}";
+
+/// Default SMTP port (STARTTLS)
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// Webhook request timeout
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum length of a Discord message
+const DISCORD_MAX_CONTENT: usize = 2000;
+
+/// Values available to notification templates
+#[derive(Debug, Clone, Default)]
+pub struct NotificationContext {
+    pub package_name: String,
+    pub version: String,
+    pub registry: String,
+    /// Duration in milliseconds
+    pub duration: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl NotificationContext {
+    /// Build a context from a publish report
+    pub fn from_report(report: &PublishReport) -> Self {
+        Self {
+            package_name: report.package_name.clone(),
+            version: report.version.clone(),
+            registry: report.registry.clone(),
+            duration: report.duration,
+            success: report.success,
+            error: report.errors.first().cloned(),
+        }
+    }
+
+    /// Substitute placeholders in a template (credentials in `{error}` are
+    /// masked)
+    pub fn render(&self, template: &str) -> String {
+        let error = self.error.as_deref().map(redact);
+        template
+            .replace("{package}", &self.package_name)
+            .replace("{version}", &self.version)
+            .replace("{registry}", &self.registry)
+            .replace("{duration}", &format_duration(self.duration))
+            .replace(
+                "{status}",
+                if self.success { "published" } else { "failed" },
+            )
+            .replace("{error}", error.as_deref().unwrap_or("unknown error"))
+    }
+}
+
+/// Kind of a notification, used for channel colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLevel {
+    Success,
+    Failure,
+    Warning,
+}
+
+/// Rendered notification shared by all channels
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationMessage {
+    pub level: MessageLevel,
+    /// Message lines; the first one is the headline
+    pub lines: Vec<String>,
+}
+
+impl NotificationMessage {
+    /// Message of a publish outcome
+    pub fn new(success: bool, lines: Vec<String>) -> Self {
+        let level = if success {
+            MessageLevel::Success
+        } else {
+            MessageLevel::Failure
+        };
+        Self { level, lines }
+    }
+
+    /// Plain text of the message
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Slack incoming webhook payload (also accepted by Mattermost)
+    pub fn slack_payload(&self) -> serde_json::Value {
+        serde_json::json!({ "text": self.text() })
+    }
+
+    /// Discord webhook payload
+    ///
+    /// Mentions are disabled so that an error message containing
+    /// `@everyone` does not ping the channel.
+    pub fn discord_payload(&self) -> serde_json::Value {
+        let mut content = self.text();
+        if content.chars().count() > DISCORD_MAX_CONTENT {
+            content = content.chars().take(DISCORD_MAX_CONTENT - 1).collect();
+            content.push('…');
+        }
+        serde_json::json!({
+            "content": content,
+            "allowed_mentions": { "parse": [] },
+        })
+    }
+
+    /// Microsoft Teams payload: an Adaptive Card with the headline in bold
+    pub fn teams_payload(&self) -> serde_json::Value {
+        let color = match self.level {
+            MessageLevel::Success => "Good",
+            MessageLevel::Failure => "Attention",
+            MessageLevel::Warning => "Warning",
+        };
+        let mut body = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            body.push(if index == 0 {
+                serde_json::json!({
+                    "type": "TextBlock",
+                    "text": line,
+                    "weight": "Bolder",
+                    "color": color,
+                    "wrap": true,
+                })
+            } else {
+                serde_json::json!({ "type": "TextBlock", "text": line, "wrap": true })
+            });
+        }
+        serde_json::json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": body,
+                },
+            }],
+        })
+    }
+}
+
+/// Result of sending to a single channel
+#[derive(Debug, Clone)]
+pub struct NotificationOutcome {
+    /// Channel name ("slack", "discord", "teams", "mattermost" or "email")
+    pub channel: &'static str,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Sends notifications configured under `notifications:`
+pub struct Notifier {
+    config: NotificationsConfig,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    /// Create a new Notifier
+    pub fn new(config: NotificationsConfig) -> Self {
+        Self {
+            config,
+            client: http_client(),
+        }
+    }
+
+    /// Build a notifier when notifications are enabled in the config
+    pub fn from_config(config: Option<&PublishConfig>) -> Option<Self> {
+        config
+            .and_then(|c| c.notifications.as_ref())
+            .filter(|n| n.enabled.unwrap_or(false))
+            .map(|n| Self::new(n.clone()))
+    }
+
+    /// Whether an event with the given outcome should be sent
+    pub fn should_notify(&self, success: bool) -> bool {
+        if success {
+            self.config.on_success.unwrap_or(true)
+        } else {
+            self.config.on_failure.unwrap_or(true)
+        }
+    }
+
+    /// Render the message for a context using the configured templates
+    pub fn render(&self, context: &NotificationContext) -> String {
+        let templates = self.config.templates.as_ref();
+        let template = if context.success {
+            templates.and_then(|t| t.success.as_deref())
+        } else {
+            templates.and_then(|t| t.failure.as_deref())
+        };
+        let default = if context.success {
+            DEFAULT_SUCCESS_TEMPLATE
+        } else {
+            DEFAULT_FAILURE_TEMPLATE
+        };
+        context.render(template.unwrap_or(default))
+    }
+
+    /// Notify about a single publish
+    pub async fn notify(&self, context: &NotificationContext) -> Vec<NotificationOutcome> {
+        if !self.should_notify(context.success) {
+            return Vec::new();
+        }
+        let message = NotificationMessage::new(context.success, vec![self.render(context)]);
+        self.send(context, &message).await
+    }
+
+    /// Send a warning about a publish (e.g. an exceeded duration budget)
+    ///
+    /// Warnings are sent whenever notifications are enabled, regardless of
+    /// `onSuccess` / `onFailure`.
+    pub async fn warn(
+        &self,
+        context: &NotificationContext,
+        message: &str,
+    ) -> Vec<NotificationOutcome> {
+        let message = NotificationMessage {
+            level: MessageLevel::Warning,
+            lines: vec![format!("⚠️ {}", message)],
+        };
+        self.send(context, &message).await
+    }
+
+    /// Notify about a batch publish with one line per registry
+    ///
+    /// The summary counts as a failure when any registry failed; the
+    /// `{registry}` placeholder of the subject becomes the registry list.
+    pub async fn notify_batch(&self, reports: &[PublishReport]) -> Vec<NotificationOutcome> {
+        let Some(first) = reports.first() else {
+            return Vec::new();
+        };
+        let success = reports.iter().all(|r| r.success);
+        if !self.should_notify(success) {
+            return Vec::new();
+        }
+
+        let succeeded = reports.iter().filter(|r| r.success).count();
+        let mut lines = vec![format!(
+            "📦 Batch publish: {}/{} registries succeeded",
+            succeeded,
+            reports.len()
+        )];
+        lines.extend(
+            reports
+                .iter()
+                .map(|r| self.render(&NotificationContext::from_report(r))),
+        );
+
+        let summary = NotificationContext {
+            package_name: first.package_name.clone(),
+            version: first.version.clone(),
+            registry: reports
+                .iter()
+                .map(|r| r.registry.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            duration: reports.iter().map(|r| r.duration).max().unwrap_or(0),
+            success,
+            error: reports
+                .iter()
+                .find(|r| !r.success)
+                .and_then(|r| r.errors.first().cloned()),
+        };
+        let message = NotificationMessage::new(success, lines);
+        self.send(&summary, &message).await
+    }
+
+    /// Send a rendered message to every configured channel
+    async fn send(
+        &self,
+        context: &NotificationContext,
+        message: &NotificationMessage,
+    ) -> Vec<NotificationOutcome> {
+        let mut outcomes = Vec::new();
+
+        if let Some(ref slack) = self.config.slack {
+            let result = self
+                .post_webhook("Slack", &slack.webhook_url, &message.slack_payload())
+                .await;
+            outcomes.push(outcome("slack", result));
+        }
+
+        if let Some(ref discord) = self.config.discord {
+            let result = self
+                .post_webhook("Discord", &discord.webhook_url, &message.discord_payload())
+                .await;
+            outcomes.push(outcome("discord", result));
+        }
+
+        if let Some(ref teams) = self.config.teams {
+            let result = self
+                .post_webhook("Teams", &teams.webhook_url, &message.teams_payload())
+                .await;
+            outcomes.push(outcome("teams", result));
+        }
+
+        if let Some(ref mattermost) = self.config.mattermost {
+            let result = self
+                .post_webhook(
+                    "Mattermost",
+                    &mattermost.webhook_url,
+                    &message.slack_payload(),
+                )
+                .await;
+            outcomes.push(outcome("mattermost", result));
+        }
+
+        if let Some(ref email) = self.config.email {
+            let result = send_email(email, context, &message.text()).await;
+            outcomes.push(outcome("email", result));
+        }
+
+        outcomes
+    }
+
+    /// Post a JSON payload to a chat webhook
+    async fn post_webhook(
+        &self,
+        service: &str,
+        webhook_url: &str,
+        payload: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        let response = self
+            .client
+            .post(webhook_url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "{} webhook returned {}",
+                service,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Send a message by SMTP
+async fn send_email(
+    config: &EmailNotificationConfig,
+    context: &NotificationContext,
+    message: &str,
+) -> Result<(), anyhow::Error> {
+    let host = config
+        .smtp_host
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("smtpHost is not configured"))?;
+    let email = build_email(config, context, message)?;
+
+    let port = config.smtp_port.unwrap_or(DEFAULT_SMTP_PORT);
+    let mut transport = if port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(host)?
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?
+    }
+    .port(port);
+
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    transport.build().send(email).await?;
+    Ok(())
+}
+
+/// Build the email message for a notification
+fn build_email(
+    config: &EmailNotificationConfig,
+    context: &NotificationContext,
+    message: &str,
+) -> Result<Message, anyhow::Error> {
+    if config.recipients.is_empty() {
+        return Err(anyhow::anyhow!("No email recipients configured"));
+    }
+
+    let from = config
+        .from
+        .as_deref()
+        .unwrap_or(config.recipients[0].as_str());
+    let subject = context.render(
+        config
+            .subject
+            .as_deref()
+            .unwrap_or(DEFAULT_SUBJECT_TEMPLATE),
+    );
+
+    let mut builder = Message::builder()
+        .from(from.parse::<Mailbox>()?)
+        .subject(subject);
+    for recipient in &config.recipients {
+        builder = builder.to(recipient.parse::<Mailbox>()?);
+    }
+
+    Ok(builder.body(message.to_string())?)
+}
+
+/// Convert a channel result into an outcome
+fn outcome(channel: &'static str, result: Result<(), anyhow::Error>) -> NotificationOutcome {
+    NotificationOutcome {
+        channel,
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Format milliseconds for messages (e.g. "850ms", "12.3s")
+fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{NotificationTemplates, SlackNotificationConfig};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn notifications(slack: Option<SlackNotificationConfig>) -> NotificationsConfig {
+        NotificationsConfig {
+            enabled: Some(true),
+            on_success: None,
+            on_failure: None,
+            templates: None,
+            slack,
+            discord: None,
+            teams: None,
+            mattermost: None,
+            email: None,
+            jira: None,
+            linear: None,
+        }
+    }
+
+    fn context(success: bool) -> NotificationContext {
+        NotificationContext {
+            package_name: "my-pkg".to_string(),
+            version: "1.2.3".to_string(),
+            registry: "npm".to_string(),
+            duration: 12_345,
+            success,
+            error: (!success).then(|| "E403 Forbidden".to_string()),
+        }
+    }
+
+    fn email_config() -> EmailNotificationConfig {
+        EmailNotificationConfig {
+            recipients: vec!["team@example.com".to_string()],
+            from: Some("release-bot@example.com".to_string()),
+            smtp_host: None,
+            smtp_port: None,
+            username: None,
+            password: None,
+            subject: None,
+        }
+    }
+
+    #[test]
+    fn test_render_default_templates() {
+        let notifier = Notifier::new(notifications(None));
+        assert_eq!(
+            notifier.render(&context(true)),
+            "✅ my-pkg@1.2.3 published to npm in 12.3s"
+        );
+        assert_eq!(
+            notifier.render(&context(false)),
+            "❌ Failed to publish my-pkg@1.2.3 to npm after 12.3s: E403 Forbidden"
+        );
+    }
+
+    #[test]
+    fn test_render_custom_templates_and_filters() {
+        let mut config = notifications(None);
+        config.on_success = Some(false);
+        config.templates = Some(NotificationTemplates {
+            success: None,
+            failure: Some("{registry}: {package} {version} {status}".to_string()),
+        });
+        let notifier = Notifier::new(config);
+
+        assert_eq!(notifier.render(&context(false)), "npm: my-pkg 1.2.3 failed");
+        assert!(!notifier.should_notify(true));
+        assert!(notifier.should_notify(false));
+    }
+
+    #[test]
+    fn test_chat_payloads_share_the_message() {
+        let message = NotificationMessage::new(
+            false,
+            vec![
+                "📦 Batch publish: 1/2 registries succeeded".to_string(),
+                "❌ @everyone npm failed".to_string(),
+            ],
+        );
+        let text = "📦 Batch publish: 1/2 registries succeeded\n❌ @everyone npm failed";
+        assert_eq!(message.slack_payload()["text"], text);
+
+        let discord = message.discord_payload();
+        assert_eq!(discord["content"], text);
+        assert_eq!(discord["allowed_mentions"]["parse"], serde_json::json!([]));
+
+        let teams = message.teams_payload();
+        let card = &teams["attachments"][0]["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][0]["weight"], "Bolder");
+        assert_eq!(card["body"][0]["color"], "Attention");
+        assert_eq!(card["body"][1]["text"], "❌ @everyone npm failed");
+
+        let long = NotificationMessage::new(true, vec!["x".repeat(3000)]);
+        let content = long.discord_payload()["content"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(content.chars().count(), DISCORD_MAX_CONTENT);
+    }
+
+    #[test]
+    fn test_from_config_requires_enabled() {
+        let mut config = PublishConfig {
+            notifications: Some(notifications(None)),
+            ..Default::default()
+        };
+        assert!(Notifier::from_config(Some(&config)).is_some());
+
+        config.notifications.as_mut().unwrap().enabled = None;
+        assert!(Notifier::from_config(Some(&config)).is_none());
+        assert!(Notifier::from_config(None).is_none());
+    }
+
+    #[test]
+    fn test_build_email() {
+        let email = build_email(&email_config(), &context(true), "body").unwrap();
+        let formatted = String::from_utf8(email.formatted()).unwrap();
+        assert!(formatted.contains("Subject: [package-publisher] my-pkg@1.2.3 published"));
+        assert!(formatted.contains("To: team@example.com"));
+        assert!(formatted.contains("From: release-bot@example.com"));
+
+        let mut config = email_config();
+        config.recipients.clear();
+        assert!(build_email(&config, &context(true), "body").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notify_posts_to_slack_and_reports_email_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("published to npm") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        let mut config = notifications(Some(SlackNotificationConfig { webhook_url: url }));
+        config.email = Some(email_config());
+        let outcomes = Notifier::new(config).notify(&context(true)).await;
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert!(request.contains(r#""text":"✅ my-pkg@1.2.3 published to npm in 12.3s""#));
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].channel, "slack");
+        assert!(outcomes[0].success);
+        assert_eq!(outcomes[1].channel, "email");
+        assert!(!outcomes[1].success);
+        assert!(outcomes[1].error.as_ref().unwrap().contains("smtpHost"));
+    }
+}
@@ -0,0 +1,130 @@
+//! One-time passwords for registries with two-factor authentication
+//!
+//! A publish rejected because npm requires a one-time password is retried
+//! once with a fresh code. The code comes from an OTP command
+//! (`--otp-command`, `registries.npm.otp.command`) such as a password manager
+//! (`op item get npm --otp`, `pass otp npm`) or a TOTP generator
+//! (`oathtool --totp -b ...`), or is entered at the
+//! [`ConfirmationPrompt`](crate::orchestration::ConfirmationPrompt) unless
+//! `otp.prompt` is `"never"`.
+
+use crate::core::traits::PublishResult;
+use crate::orchestration::hook_runner::split_command;
+use crate::security::command_executor::SafeCommandExecutor;
+use crate::tr;
+use std::path::Path;
+use std::time::Duration;
+
+/// Metadata key of a [`PublishResult`] rejected for a missing or expired
+/// one-time password
+pub const OTP_REQUIRED: &str = "otpRequired";
+
+/// Time allowed for an OTP command (password managers may ask to unlock)
+const OTP_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Whether `result` failed because a one-time password is required
+pub fn otp_required(result: &PublishResult) -> bool {
+    !result.success
+        && result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(OTP_REQUIRED))
+            .and_then(|v| v.as_bool())
+            == Some(true)
+}
+
+/// One-time password printed by `command`, run in `working_dir`
+///
+/// The command is split like hook commands (no shell) and runs with the
+/// caller's environment, which password manager sessions depend on.
+pub async fn run_otp_command(command: &str, working_dir: &Path) -> anyhow::Result<String> {
+    let mut parts = split_command(command).map_err(|e| anyhow::anyhow!(e))?;
+    if parts.is_empty() {
+        anyhow::bail!("{}", tr!("otp.command_empty"));
+    }
+    let program = parts.remove(0);
+    let mut executor = SafeCommandExecutor::new(working_dir)?;
+    executor.allow_commands(std::slice::from_ref(&program));
+    let output = tokio::task::spawn_blocking(move || {
+        let args: Vec<&str> = parts.iter().map(String::as_str).collect();
+        executor.execute_with_timeout(&program, &args, Some(OTP_COMMAND_TIMEOUT))
+    })
+    .await??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "{}",
+            tr!(
+                "otp.command_failed",
+                command = command,
+                message = stderr.trim()
+            )
+        );
+    }
+    parse_otp(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// One-time password in command output or a prompt answer (its last
+/// non-empty line: an authenticator code or a recovery code)
+pub fn parse_otp(text: &str) -> anyhow::Result<String> {
+    let code = text
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or_default();
+    // The message never echoes the input, which may be a mistyped code
+    if code.len() < 6 || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        anyhow::bail!("{}", tr!("otp.invalid"));
+    }
+    Ok(code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_otp() {
+        assert_eq!(parse_otp("123456\n").unwrap(), "123456");
+        assert_eq!(
+            parse_otp("Unlocking vault...\n  654321  \n\n").unwrap(),
+            "654321"
+        );
+        assert_eq!(parse_otp("a1b2c3d4e5").unwrap(), "a1b2c3d4e5");
+        assert!(parse_otp("").is_err());
+        assert!(parse_otp("1234").is_err());
+        assert!(parse_otp("12 34 56").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_otp_command() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            run_otp_command("echo 246810", dir.path()).await.unwrap(),
+            "246810"
+        );
+        assert!(run_otp_command("false", dir.path()).await.is_err());
+        assert!(run_otp_command("", dir.path()).await.is_err());
+    }
+
+    #[test]
+    fn test_otp_required() {
+        let mut result = PublishResult {
+            success: false,
+            version: None,
+            package_url: None,
+            output: None,
+            error: Some("OTP required".to_string()),
+            metadata: Some(HashMap::from([(
+                OTP_REQUIRED.to_string(),
+                serde_json::Value::Bool(true),
+            )])),
+            error_kind: None,
+        };
+        assert!(otp_required(&result));
+        result.metadata = None;
+        assert!(!otp_required(&result));
+    }
+}
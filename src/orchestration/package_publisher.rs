@@ -8,15 +8,95 @@
 //! - State management and error recovery
 //! - Verification and analytics recording
 
-use crate::core::config::PublishConfig;
-use crate::core::config_loader::ConfigLoader;
-use crate::core::state_machine::{PublishState, PublishStateMachine};
-use crate::plugins::plugin_loader::{DetectedPlugin, PluginLoader};
+use crate::core::config::{
+    CustomRegistryConfig, DryRunMode, NPMAccess, NPMRegistryConfig, OTPConfig, ProjectConfig,
+    ProvenanceConfig, PublishConfig, PublishOptionsConfig, RetryConfig, SbomConfig, SigningConfig,
+};
+use crate::core::config_loader::{ConfigLoader, empty_layer, empty_publish_options};
+use crate::core::error::PublishErrorKind;
+use crate::core::git::run_git;
+use crate::core::http::HttpClientFactory;
+use crate::core::publish_lock::{DEFAULT_LOCK_TTL, PublishLock};
+use crate::core::reporter::{Reporter, default_reporter};
+use crate::core::retry::RetryOptions;
+use crate::core::state_machine::{PublishState, PublishStateMachine, StageArtifacts};
+use crate::core::traits::{
+    PackageIntegrity, PublishResult as PluginPublishResult, RegistryPlugin, ValidationResult,
+};
+use crate::orchestration::analytics::PublishAnalytics;
+use crate::orchestration::approval::{ApprovalDecision, ApprovalGate};
+use crate::orchestration::audit_export::ReleaseArtifactStore;
+use crate::orchestration::changelog::ChangelogGenerator;
+use crate::orchestration::deployment::{DeploymentManager, GitHubDeployment};
+use crate::orchestration::duration_budget::{self, DurationBudget};
+use crate::orchestration::events::{
+    ConfirmationPrompt, EventSink, EventSinks, FindingSeverity, PublishEvent, TerminalPrompt,
+};
+use crate::orchestration::hook_runner::{HookContext, HookResult, HookRunner, HookStage};
+use crate::orchestration::issue_tracker::{self, IssueTracker, ReleaseInfo};
+use crate::orchestration::notifier::{NotificationContext, Notifier};
+use crate::orchestration::otp;
+use crate::orchestration::policy::{
+    self, Enforcement, PolicyContext, PolicyEngine, PolicyViolation,
+};
+use crate::orchestration::release::ReleaseManager;
+use crate::orchestration::release_window::ReleaseWindows;
+use crate::orchestration::telemetry::{PublishOutcome, PublishTrace, TelemetryExporter};
+use crate::orchestration::verify_poller::VerifyPoller;
+use crate::orchestration::webhook::{WebhookEmitter, WebhookEvent, WebhookPayload};
+use crate::plugins::aur_plugin::AurPlugin;
+use crate::plugins::bazel_plugin::BazelPlugin;
+use crate::plugins::bucket_plugin::{BucketPlugin, sha256_file};
+use crate::plugins::cloudsmith_plugin::CloudsmithPlugin;
+use crate::plugins::cpan_plugin::CpanPlugin;
+use crate::plugins::cran_plugin::CranPlugin;
+use crate::plugins::crates_io_plugin::CratesIoPlugin;
+use crate::plugins::custom_command_plugin::CustomCommandPlugin;
+use crate::plugins::debian_plugin::DebianPlugin;
+use crate::plugins::expo_plugin::ExpoPlugin;
+use crate::plugins::gemfury_plugin::GemfuryPlugin;
+use crate::plugins::github_packages_plugin::GitHubPackagesPlugin;
+use crate::plugins::hackage_plugin::HackagePlugin;
+use crate::plugins::homebrew_plugin::HomebrewPlugin;
+use crate::plugins::hosted_package::release_artifacts;
+use crate::plugins::julia_plugin::JuliaPlugin;
+use crate::plugins::npm_plugin::NpmPlugin;
+use crate::plugins::plugin_loader::{DetectedPlugin, PluginLoader, RegistryType};
+use crate::plugins::pypi_plugin::PyPiPlugin;
+use crate::plugins::repository_manager_plugin::{RepositoryManagerKind, RepositoryManagerPlugin};
+use crate::plugins::upm_plugin::UpmPlugin;
+use crate::plugins::windows_manifest_plugin::{WindowsManifestKind, WindowsManifestPlugin};
+use crate::security::artifact_inspector::{ArtifactInspection, ArtifactInspector};
+use crate::security::audit::{AuditBackend, AuditFinding, AuditReport, DependencyAuditor};
+use crate::security::command_executor::{
+    CommandLimits, DEFAULT_REGISTRY_COMMAND_TIMEOUT, EnvSandbox,
+};
+use crate::security::credential_preflight::CredentialPreflight;
 use crate::security::credential_validator::CredentialValidator;
+use crate::security::dependency_confusion::{
+    ConfusionFinding, ConfusionReport, DependencyConfusionChecker, InternalNames,
+};
+use crate::security::malware_scanner::{
+    ArtifactScanResult, MalwareScanner, ScanVerdict, ScannerBackend,
+};
+use crate::security::redaction::{RedactingReporter, redact};
+use crate::security::secrets_baseline::SecretsBaseline;
 use crate::security::secrets_scanner::SecretsScanner;
+use crate::supply_chain::provenance::{ProvenanceBuilder, ProvenanceReference, ResourceDescriptor};
+use crate::supply_chain::sbom::{SbomFormat, SbomGenerator, SbomReference, purl};
+use crate::supply_chain::signing::{ArtifactSignature, ArtifactSigner};
+use crate::tr;
+use crate::validation::bundle_size::{self, BundleSizeGate, PackageSize};
+use crate::validation::dependency_checker::IssueSeverity;
+use crate::validation::package_contents::{self, PackageContentValidator};
+use crate::validation::size_budget::{self, SizeMeasurement};
+use crate::validation::{VersionValidator, WarningBudget};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
-use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
 
 /// Publishing options passed from CLI or config
 #[derive(Debug, Clone, Default)]
@@ -42,14 +122,78 @@ pub struct PublishOptions {
     /// One-time password for 2FA (npm)
     pub otp: Option<String>,
 
+    /// Command printing a one-time password when npm requires one
+    /// (`--otp-command`, default: `registries.npm.otp.command`)
+    pub otp_command: Option<String>,
+
     /// Publish with tag
     pub tag: Option<String>,
 
     /// Access level (public|restricted)
     pub access: Option<String>,
+
+    /// Treat validation warnings as errors
+    pub strict: bool,
+
+    /// Do not send notifications (batch publishes send a summary instead)
+    pub skip_notifications: bool,
+
+    /// Validate and run the registry dry-run, then stop like `dry_run`
+    /// (releases scheduled with `publish --at`)
+    pub prepare_only: bool,
+
+    /// Wait up to this long for another publish of the project to finish
+    /// instead of failing (`--wait-for-lock`)
+    pub wait_for_lock: Option<Duration>,
+
+    /// Config profile to overlay (`--profile`, default: `PUBLISH_PROFILE`)
+    pub profile: Option<String>,
 }
 
 impl PublishOptions {
+    /// Config layer of the CLI arguments, overriding file and environment
+    /// settings (`None` when no argument maps to the config)
+    ///
+    /// `--registry` sets `project.defaultRegistry`, `--tag`/`--access` the npm
+    /// settings, `--dry-run` `publish.dryRun: always` and `--non-interactive`
+    /// `publish.interactive: false`.
+    pub fn to_config_overrides(&self) -> anyhow::Result<Option<PublishConfig>> {
+        let mut config = empty_layer();
+
+        if let Some(registry) = &self.registry {
+            config.project = Some(ProjectConfig {
+                name: None,
+                default_registry: Some(registry.clone()),
+            });
+        }
+
+        if self.tag.is_some() || self.access.is_some() {
+            let access = match self.access.as_deref() {
+                None => None,
+                Some("public") => Some(NPMAccess::Public),
+                Some("restricted") => Some(NPMAccess::Restricted),
+                Some(other) => {
+                    anyhow::bail!(tr!("publisher.invalid_access", access = other))
+                }
+            };
+            config.registries.npm = Some(NPMRegistryConfig {
+                tag: self.tag.clone(),
+                access,
+                ..Default::default()
+            });
+        }
+
+        if self.dry_run || self.non_interactive {
+            config.publish = Some(PublishOptionsConfig {
+                dry_run: self.dry_run.then_some(DryRunMode::Always),
+                interactive: self.non_interactive.then_some(false),
+                ..empty_publish_options()
+            });
+        }
+
+        Ok((config != empty_layer()).then_some(config))
+    }
+
     /// Convert to core::traits::PublishOptions for plugin interface
     fn to_plugin_options(&self) -> crate::core::traits::PublishOptions {
         use std::collections::HashMap;
@@ -70,7 +214,7 @@ impl PublishOptions {
 }
 
 /// Publishing report returned after publish operation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishReport {
     pub success: bool,
     pub registry: String,
@@ -79,9 +223,195 @@ pub struct PublishReport {
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
     pub verification_url: Option<String>,
     pub errors: Vec<String>,
+    /// Category of the failure (failed publishes only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<PublishErrorKind>,
     pub warnings: Vec<String>,
     pub duration: u64,
     pub state: String,
+    /// Registry integrity data (download URL and hashes) captured on verification
+    pub integrity: Option<PackageIntegrity>,
+    /// Results of executed lifecycle hooks
+    #[serde(default)]
+    pub hooks: Vec<HookResult>,
+    /// Superseded prereleases deprecated (or yanked) after this release
+    #[serde(default)]
+    pub deprecated_prereleases: Vec<String>,
+    /// Package metadata quality score (0-100), when the registry provides one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_score: Option<u32>,
+    /// GitHub Release page created after publishing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_url: Option<String>,
+    /// Submission URL for registries with human review (CRAN, Julia General);
+    /// the version is not available until the submission is accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_url: Option<String>,
+    /// SBOM generated before publishing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sbom: Option<SbomReference>,
+    /// Artifact signatures created before publishing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<ArtifactSignature>,
+    /// SLSA provenance statement written after publishing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ProvenanceReference>,
+    /// Dependency audit run before publishing (`security.audit`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit: Option<AuditReport>,
+    /// Dependency confusion check (`security.dependencyConfusion`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency_confusion: Option<ConfusionReport>,
+    /// Malware scan verdicts for the release artifacts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub malware_scan: Vec<ArtifactScanResult>,
+    /// Contents checks of the packed artifacts (`security.artifactInspection`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifact_inspection: Vec<ArtifactInspection>,
+    /// Packed npm package size (`registries.npm.bundleSize`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_size: Option<PackageSize>,
+    /// Time spent waiting for the version to appear in the registry (ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_wait: Option<u64>,
+    /// Violated publish policies (`policies`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_violations: Vec<PolicyViolation>,
+}
+
+/// What a provenance statement is built from
+struct ProvenanceInput<'a> {
+    registry: &'a str,
+    package_name: &'a str,
+    version: &'a str,
+    signatures: &'a [ArtifactSignature],
+    integrity: Option<&'a PackageIntegrity>,
+    sbom: Option<&'a SbomReference>,
+    started_on: chrono::DateTime<chrono::Utc>,
+}
+
+/// Provenance subjects: signed artifacts, else the package with registry digests
+fn provenance_subjects(input: &ProvenanceInput<'_>) -> Vec<ResourceDescriptor> {
+    if !input.signatures.is_empty() {
+        return input
+            .signatures
+            .iter()
+            .map(|s| {
+                let name = Path::new(&s.artifact)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| s.artifact.clone());
+                ResourceDescriptor::new(name, "sha256", s.sha256.clone())
+            })
+            .collect();
+    }
+
+    let Some(integrity) = input.integrity else {
+        return Vec::new();
+    };
+    let name = match input.registry {
+        "npm" => purl("npm", input.package_name, input.version),
+        "crates.io" => purl("cargo", input.package_name, input.version),
+        "pypi" => purl("pypi", input.package_name, input.version),
+        _ => format!("{}@{}", input.package_name, input.version),
+    };
+    let mut subject = ResourceDescriptor {
+        name,
+        digest: Default::default(),
+    };
+    if let Some(ref checksum) = integrity.checksum {
+        subject
+            .digest
+            .insert("sha256".to_string(), checksum.clone());
+    }
+    if let Some(ref shasum) = integrity.shasum {
+        subject.digest.insert("sha1".to_string(), shasum.clone());
+    }
+    if let Some((algorithm, digest)) = integrity
+        .integrity
+        .as_deref()
+        .and_then(|sri| sri.split_once('-'))
+        && let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(digest)
+    {
+        subject
+            .digest
+            .insert(algorithm.to_string(), hex::encode(bytes));
+    }
+
+    if subject.digest.is_empty() {
+        Vec::new()
+    } else {
+        vec![subject]
+    }
+}
+
+/// Retry options of a registry from `publish.retry` (registry overrides win)
+fn retry_options(config: Option<&RetryConfig>, registry: &str) -> anyhow::Result<RetryOptions> {
+    let mut options = RetryOptions::default();
+    let Some(config) = config else {
+        return Ok(options);
+    };
+    let overrides = config.registries.as_ref().and_then(|r| r.get(registry));
+    for layer in std::iter::once(config).chain(overrides) {
+        if let Some(max_attempts) = layer.max_attempts {
+            options.max_attempts = max_attempts.max(1);
+        }
+        if let Some(ref delay) = layer.initial_delay {
+            options.initial_delay = Duration::from_millis(duration_budget::parse_duration(delay)?);
+        }
+        if let Some(ref delay) = layer.max_delay {
+            options.max_delay = Duration::from_millis(duration_budget::parse_duration(delay)?);
+        }
+        if let Some(jitter) = layer.jitter {
+            options.jitter = jitter;
+        }
+    }
+    Ok(options)
+}
+
+/// Timeout of npm, cargo and twine commands from `publish.commandTimeout`
+fn command_timeout(config: Option<&PublishOptionsConfig>) -> anyhow::Result<Duration> {
+    match config.and_then(|c| c.command_timeout.as_deref()) {
+        Some(timeout) => Ok(Duration::from_millis(duration_budget::parse_duration(
+            timeout,
+        )?)),
+        None => Ok(DEFAULT_REGISTRY_COMMAND_TIMEOUT),
+    }
+}
+
+/// Take the project lock before a publish, reporting when it has to wait
+pub(crate) async fn acquire_publish_lock(
+    project_path: &Path,
+    wait: Option<Duration>,
+    reporter: &dyn Reporter,
+) -> anyhow::Result<PublishLock> {
+    PublishLock::acquire(project_path, DEFAULT_LOCK_TTL, wait, |owner| {
+        reporter.info(&tr!("publisher.waiting_for_lock", owner = owner))
+    })
+    .await
+}
+
+/// Options for rolling back a published version
+#[derive(Debug, Clone, Default)]
+pub struct RollbackOptions {
+    /// Registry to roll back (default: first detected)
+    pub registry: Option<String>,
+
+    /// Skip the confirmation prompt
+    pub non_interactive: bool,
+}
+
+/// Rollback report returned after a rollback attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackReport {
+    pub success: bool,
+    pub registry: String,
+    pub package_name: String,
+    pub version: String,
+    pub message: String,
+    pub error: Option<String>,
+    pub duration: u64,
+    pub rolled_back_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Main package publisher orchestrator
@@ -92,6 +422,26 @@ pub struct PackagePublisher {
     secrets_scanner: SecretsScanner,
     credential_validator: CredentialValidator,
     config: Option<PublishConfig>,
+    hook_context: HookContext,
+    hook_results: Vec<HookResult>,
+    /// GitHub Deployment of the running publish (`release.githubDeployment`)
+    deployment: Option<GitHubDeployment>,
+    /// External (shared library / subprocess) plugins configured under `plugins:`
+    dynamic_plugins: Vec<Arc<dyn RegistryPlugin>>,
+    reporter: Arc<dyn Reporter>,
+    events: EventSinks,
+    /// Answers confirmation questions
+    prompt: Arc<dyn ConfirmationPrompt>,
+    /// Registry of the running publish, attached to events
+    event_registry: Option<String>,
+    /// `publish` span of the running publish
+    publish_span: tracing::Span,
+    /// Span of the current stage, closed by the next transition
+    stage_span: Option<tracing::Span>,
+    /// Stage timings of the running publish, exported as telemetry
+    trace: PublishTrace,
+    /// The project lock is held by the caller (batch publishes)
+    lock_held: bool,
 }
 
 impl PackagePublisher {
@@ -110,7 +460,86 @@ impl PackagePublisher {
             credential_validator: CredentialValidator::new(),
             project_path,
             config: None,
+            hook_context: HookContext::default(),
+            hook_results: Vec::new(),
+            deployment: None,
+            dynamic_plugins: Vec::new(),
+            reporter: RedactingReporter::wrap(default_reporter()),
+            events: EventSinks::default(),
+            prompt: Arc::new(TerminalPrompt),
+            event_registry: None,
+            publish_span: tracing::Span::none(),
+            stage_span: None,
+            trace: PublishTrace::start(),
+            lock_held: false,
+        }
+    }
+
+    /// Send progress and diagnostics to `reporter` instead of the terminal
+    /// (credentials in messages are masked)
+    pub fn with_reporter(mut self, reporter: Arc<dyn Reporter>) -> Self {
+        self.reporter = RedactingReporter::wrap(reporter);
+        self
+    }
+
+    /// Send [`PublishEvent`]s to `sink` (a channel sender or a callback)
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.events.push(sink);
+        self
+    }
+
+    /// Ask confirmation questions through `prompt` instead of stdin
+    pub fn with_prompt(mut self, prompt: Arc<dyn ConfirmationPrompt>) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Keep the publish state in `.publish-state-{key}.json` (batch
+    /// publishes of several registries in one project)
+    pub(crate) fn with_state_key(mut self, key: &str) -> Self {
+        self.state_machine = PublishStateMachine::with_state_key(&self.project_path, key);
+        self
+    }
+
+    /// Publish under the project lock the batch already holds
+    pub(crate) fn with_lock_held(mut self) -> Self {
+        self.lock_held = true;
+        self
+    }
+
+    /// Share the sinks of a batch
+    pub(crate) fn with_event_sinks(mut self, events: EventSinks) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Move the state machine to `to` and emit the transition
+    async fn transition(&mut self, to: PublishState) -> Result<(), std::io::Error> {
+        let from = self.state_machine.get_state();
+        self.state_machine.transition(to, None).await?;
+        self.trace.enter(to);
+        self.stage_span = None;
+        if !matches!(
+            to,
+            PublishState::Initial
+                | PublishState::Success
+                | PublishState::Failed
+                | PublishState::RolledBack
+        ) {
+            self.publish_span.record("stage", tracing::field::debug(to));
+            self.stage_span = Some(tracing::info_span!(
+                target: "package_publisher",
+                parent: &self.publish_span,
+                "stage",
+                state = ?to
+            ));
         }
+        self.events.emit(PublishEvent::StateChanged {
+            registry: self.event_registry.clone(),
+            from,
+            to,
+        });
+        Ok(())
     }
 
     /// Load configuration from file and CLI arguments
@@ -120,41 +549,113 @@ impl PackagePublisher {
     /// * `cli_args` - Optional CLI arguments to override config file
     pub async fn load_config(
         &mut self,
-        _cli_args: Option<PublishOptions>,
+        cli_args: Option<PublishOptions>,
     ) -> Result<(), anyhow::Error> {
         // Load configuration
         use crate::core::config_loader::ConfigLoadOptions;
-        use std::collections::HashMap;
 
+        let overrides = match &cli_args {
+            Some(options) => options.to_config_overrides()?,
+            None => None,
+        };
         let options = ConfigLoadOptions {
             project_path: self.project_path.clone(),
-            cli_args: None, // TODO: Convert PublishOptions to PublishConfig
-            env: HashMap::new(),
+            cli_args: overrides,
+            env: std::env::vars().collect(),
+            reporter: Some(self.reporter.clone()),
+            profile: cli_args.and_then(|options| options.profile),
         };
 
-        self.config = Some(
-            ConfigLoader::load(options)
-                .await
-                .map_err(|e| anyhow::anyhow!("{}", e))?,
-        );
+        let config = ConfigLoader::load(options)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        HttpClientFactory::from_config(config.http.as_ref(), &self.project_path)?.install()?;
+        self.dynamic_plugins = self
+            .plugin_loader
+            .load_dynamic_plugins(
+                config.plugins.as_deref().unwrap_or_default(),
+                &self.project_path,
+            )
+            .await?;
+        self.config = Some(config);
 
         Ok(())
     }
 
     /// Auto-detect applicable registries (parallel execution for performance)
     pub async fn detect_registries(&self) -> Result<Vec<DetectedPlugin>, anyhow::Error> {
-        let detected = self
+        let mut detected = self
             .plugin_loader
             .detect_plugins(&self.project_path)
             .await?;
 
-        if detected.is_empty() {
-            return Err(anyhow::anyhow!("No registries detected"));
+        // Buckets and hosted registries have no manifest; they are enabled
+        // through configuration
+        if let Some(registries) = self.config.as_ref().map(|c| &c.registries) {
+            let configured = [
+                (
+                    RegistryType::Bucket,
+                    registries.bucket.as_ref().map(|b| b.enabled),
+                ),
+                (
+                    RegistryType::Cloudsmith,
+                    registries.cloudsmith.as_ref().map(|c| c.enabled),
+                ),
+                (
+                    RegistryType::Gemfury,
+                    registries.gemfury.as_ref().map(|g| g.enabled),
+                ),
+                (
+                    RegistryType::Debian,
+                    registries.debian.as_ref().map(|d| d.enabled),
+                ),
+                (
+                    RegistryType::Scoop,
+                    registries.scoop.as_ref().map(|s| s.enabled),
+                ),
+                (
+                    RegistryType::Winget,
+                    registries.winget.as_ref().map(|w| w.enabled),
+                ),
+            ];
+            for (registry_type, enabled) in configured {
+                if let Some(enabled) = enabled
+                    && enabled != Some(false)
+                {
+                    detected.push(DetectedPlugin {
+                        manifest_path: format!("registries.{}", registry_type.as_str()),
+                        registry_type,
+                        confidence: 1.0,
+                    });
+                }
+            }
+        }
+
+        if detected.is_empty()
+            && self.custom_registries().is_empty()
+            && self.dynamic_plugins.is_empty()
+        {
+            return Err(anyhow::anyhow!("{}", tr!("publisher.no_registries")));
         }
 
         Ok(detected)
     }
 
+    /// Enabled `registries.custom` entries with a publish command, by name
+    fn custom_registries(&self) -> Vec<(&str, &CustomRegistryConfig)> {
+        let mut custom: Vec<_> = self
+            .config
+            .as_ref()
+            .and_then(|c| c.registries.custom.as_ref())
+            .into_iter()
+            .flatten()
+            .filter(|(_, config)| config.enabled != Some(false) && config.publish_command.is_some())
+            .map(|(name, config)| (name.as_str(), config))
+            .collect();
+        custom.sort_by_key(|(name, _)| *name);
+        custom
+    }
+
     /// Publish a package
     ///
     /// # Arguments
@@ -168,179 +669,1224 @@ impl PackagePublisher {
         &mut self,
         options: PublishOptions,
     ) -> Result<PublishReport, anyhow::Error> {
+        let _lock = if self.lock_held {
+            None
+        } else {
+            Some(
+                acquire_publish_lock(&self.project_path, options.wait_for_lock, &*self.reporter)
+                    .await?,
+            )
+        };
+        self.hook_context = HookContext::default();
+        self.hook_results.clear();
+        self.event_registry = None;
+        self.publish_span = tracing::info_span!(
+            target: "package_publisher",
+            "publish",
+            registry = tracing::field::Empty,
+            package = tracing::field::Empty,
+            version = tracing::field::Empty,
+            stage = tracing::field::Empty,
+        );
+        let span = self.publish_span.clone();
+        self.trace = PublishTrace::start();
+        let dry_run = options.dry_run || options.prepare_only;
+        let skip_hooks = options.skip_hooks;
+        let skip_notifications = options.skip_notifications;
         let start_time = Instant::now();
-        let mut errors = Vec::new();
-        let mut warnings = Vec::new();
 
-        // Load config if not already loaded
-        if self.config.is_none() {
-            self.load_config(Some(options.clone())).await?;
+        let mut result = match self.run_publish(options).instrument(span.clone()).await {
+            Ok(mut report) => {
+                report.hooks.append(&mut self.hook_results);
+                Ok(report)
+            }
+            Err(e) => {
+                if let Err(state_error) = self
+                    .state_machine
+                    .record_error(&redact(&e.to_string()))
+                    .await
+                {
+                    self.reporter.warning(&format!("⚠️  {}", state_error));
+                }
+                if !skip_hooks && let Err(hook_error) = self.run_hooks(HookStage::OnError).await {
+                    self.reporter.warning(&format!("⚠️  {}", hook_error));
+                }
+                Err(e)
+            }
+        };
+
+        self.stage_span = None;
+        span.in_scope(|| match &result {
+            Ok(report) => tracing::info!(target: "package_publisher", success = report.success, "publish finished"),
+            Err(e) => tracing::error!(target: "package_publisher", error = %e, "publish failed"),
+        });
+
+        self.events.emit(PublishEvent::Finished {
+            registry: self.event_registry.clone(),
+            success: result.as_ref().is_ok_and(|r| r.success),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        self.trace.finish();
+        self.export_telemetry(&result, dry_run).await;
+
+        if let Some(deployment) = self.deployment.take() {
+            self.finish_deployment(&deployment, &result).await;
         }
 
-        // Merge CLI options with config (CLI takes priority)
-        let effective_options = self.merge_options_with_config(options.clone());
+        let budget_alert =
+            self.check_duration_budget(&mut result, start_time.elapsed().as_millis() as u64);
 
-        // 1. Restore state if resume requested
-        if effective_options.resume {
-            self.state_machine
-                .transition(PublishState::Initial, None)
-                .await?;
-            let restored = self.state_machine.restore().await?;
-            if !restored {
-                return Err(anyhow::anyhow!("State file not found or corrupted"));
+        let context = match &result {
+            Ok(report) if report.state == "DRY_RUN" => None,
+            Ok(report) => Some(NotificationContext::from_report(report)),
+            Err(e) => Some(NotificationContext {
+                package_name: self
+                    .hook_context
+                    .package_name
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                version: self
+                    .hook_context
+                    .version
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                registry: self
+                    .hook_context
+                    .registry
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                duration: start_time.elapsed().as_millis() as u64,
+                success: false,
+                error: Some(e.to_string()),
+            }),
+        };
+        if !dry_run && let Some(ref context) = context {
+            let event = if context.success {
+                WebhookEvent::PublishSucceeded
+            } else {
+                WebhookEvent::PublishFailed
+            };
+            self.emit_webhook(event, context).await;
+        }
+
+        if !skip_notifications {
+            if let Some(ref context) = context {
+                self.send_notifications(context).await;
+                if let Some(ref alert) = budget_alert {
+                    self.send_warning_notification(context, alert).await;
+                }
+            }
+            if let Ok(report) = &result
+                && report.success
+                && report.state != "DRY_RUN"
+            {
+                self.update_issue_trackers(report).await;
             }
-        } else {
-            self.state_machine.clear().await?;
-            self.state_machine
-                .transition(PublishState::Initial, None)
-                .await?;
         }
 
-        // 2. Detect registries
-        self.state_machine
-            .transition(PublishState::Detecting, None)
-            .await?;
-        let detected_registries = self.detect_registries().await?;
+        result
+    }
 
-        println!("\nDetected registries:");
-        for plugin in &detected_registries {
-            println!(
-                "  - {} (confidence: {:.0}%)",
-                plugin.registry_type.as_str(),
-                plugin.confidence * 100.0
-            );
+    /// Send the trace and metrics of the finished publish to the configured
+    /// OTLP receiver
+    async fn export_telemetry(&self, result: &Result<PublishReport, anyhow::Error>, dry_run: bool) {
+        let Some(exporter) =
+            TelemetryExporter::from_config(self.config.as_ref().and_then(|c| c.telemetry.as_ref()))
+        else {
+            return;
+        };
+        let unknown =
+            |value: &Option<String>| value.clone().unwrap_or_else(|| "unknown".to_string());
+        let outcome = match result {
+            Ok(report) => PublishOutcome {
+                registry: report.registry.clone(),
+                package_name: report.package_name.clone(),
+                version: report.version.clone(),
+                success: report.success,
+                dry_run,
+                error: None,
+            },
+            Err(e) => PublishOutcome {
+                registry: self
+                    .event_registry
+                    .clone()
+                    .unwrap_or_else(|| unknown(&self.hook_context.registry)),
+                package_name: unknown(&self.hook_context.package_name),
+                version: unknown(&self.hook_context.version),
+                success: false,
+                dry_run,
+                error: Some(e.to_string()),
+            },
+        };
+        if let Err(e) = exporter.export(&self.trace, &outcome).await {
+            self.reporter
+                .warning(&tr!("publisher.telemetry_export_failed", error = e));
         }
-        println!();
+    }
 
-        // Use specified registry or first detected
-        let registry_name = effective_options
-            .registry
-            .clone()
-            .unwrap_or_else(|| detected_registries[0].registry_type.as_str().to_string());
+    /// Roll back a published version
+    ///
+    /// Invokes the registry plugin's rollback (npm unpublish/deprecate,
+    /// cargo yank, PyPI yank instructions, Homebrew formula revert), saves the
+    /// result with the release artifacts and records it in analytics.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version to roll back
+    /// * `options` - Rollback options
+    pub async fn rollback(
+        &mut self,
+        version: &str,
+        options: RollbackOptions,
+    ) -> Result<RollbackReport, anyhow::Error> {
+        let start_time = Instant::now();
 
-        let plugin_info = detected_registries
-            .iter()
-            .find(|p| p.registry_type.as_str() == registry_name)
-            .ok_or_else(|| anyhow::anyhow!("Registry not detected: {}", registry_name))?;
+        if self.config.is_none() {
+            self.load_config(None).await?;
+        }
 
-        let plugin = self.plugin_loader.load_plugin(
-            plugin_info.registry_type,
-            self.project_path.to_str().unwrap(),
-        )?;
+        let detected_registries = self.detect_registries().await?;
+        let (registry_name, plugin) = self.select_plugin(&detected_registries, options.registry)?;
 
-        println!("📦 Registry selected: {}\n", registry_name);
+        let package_name = plugin
+            .validate()
+            .await
+            .ok()
+            .and_then(|v| v.metadata)
+            .and_then(|m| m.get("packageName").cloned())
+            .and_then(|n| n.as_str().map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
 
-        // 3. Security scan (if enabled)
-        let secrets_scanning_enabled = true; // TODO: Read from config
+        self.reporter.info(&tr!(
+            "publisher.rolling_back",
+            package = package_name,
+            version = version,
+            registry = registry_name
+        ));
 
-        if secrets_scanning_enabled {
-            println!("🔒 Security scan...");
+        let question = tr!(
+            "publisher.confirm_rollback",
+            package = package_name,
+            version = version
+        );
+        if !options.non_interactive && !self.confirm(&question).await? {
+            return Err(anyhow::anyhow!("{}", tr!("publisher.rollback_cancelled")));
+        }
 
-            let scan_result = self
-                .secrets_scanner
-                .scan_project(&self.project_path)
-                .await?;
+        let (success, message, error) = match plugin.rollback(version).await {
+            Ok(result) => (result.success, result.message, result.error),
+            Err(e) => (false, tr!("publisher.rollback_failed"), Some(e.to_string())),
+        };
 
-            if !scan_result.findings.is_empty() {
-                warnings.push(format!(
-                    "{} potential secrets detected",
-                    scan_result.findings.len()
-                ));
+        let report = RollbackReport {
+            success,
+            registry: registry_name,
+            package_name,
+            version: version.to_string(),
+            message,
+            error,
+            duration: start_time.elapsed().as_millis() as u64,
+            rolled_back_at: chrono::Utc::now(),
+        };
 
-                if !effective_options.non_interactive {
-                    println!("⚠️  Potential secrets detected:");
-                    for finding in &scan_result.findings {
-                        println!("  - {} in {}", finding.secret_type, finding.file.display());
-                    }
+        let store = ReleaseArtifactStore::new(&self.project_path);
+        if let Err(e) = store
+            .save_json(version, &format!("rollback-{}", report.registry), &report)
+            .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_rollback_report_failed", error = e));
+        }
 
-                    if !self.confirm("⚠️  Continue with publishing?").await? {
-                        return Err(anyhow::anyhow!(
-                            "{} secrets detected",
-                            scan_result.findings.len()
-                        ));
-                    }
-                } else {
-                    println!(
-                        "  ⚠️  {} potential secrets detected (non-interactive mode, continuing...)",
-                        scan_result.findings.len()
-                    );
-                }
-            } else {
-                println!("  ✅ No secrets detected\n");
-            }
+        let mut analytics = PublishAnalytics::new(&self.project_path);
+        if let Err(e) = analytics.initialize().await {
+            self.reporter
+                .warning(&tr!("publisher.analytics_init_failed", error = e));
+        }
+        if let Err(e) = analytics.record_rollback(&report).await {
+            self.reporter
+                .warning(&tr!("publisher.analytics_record_failed", error = e));
         }
 
-        // 4. Validation
-        self.state_machine
-            .transition(PublishState::Validating, None)
-            .await?;
-        println!("🔍 Validating package...");
+        let event = if report.success {
+            WebhookEvent::RollbackSucceeded
+        } else {
+            WebhookEvent::RollbackFailed
+        };
+        let context = NotificationContext {
+            package_name: report.package_name.clone(),
+            version: report.version.clone(),
+            registry: report.registry.clone(),
+            duration: report.duration,
+            success: report.success,
+            error: report.error.clone(),
+        };
+        self.emit_webhook(event, &context).await;
+
+        Ok(report)
+    }
 
-        let validation_result = plugin.validate().await?;
+    /// Select the plugin for the requested registry (or the first detected one)
+    fn select_plugin(
+        &self,
+        detected_registries: &[DetectedPlugin],
+        registry: Option<String>,
+    ) -> Result<(String, Arc<dyn RegistryPlugin>), anyhow::Error> {
+        let custom = self.custom_registries();
+        let registry_name = match (registry, detected_registries.first(), custom.first()) {
+            (Some(registry), _, _) => registry,
+            (None, Some(detected), _) => detected.registry_type.as_str().to_string(),
+            (None, None, Some((name, _))) => name.to_string(),
+            (None, None, None) => self
+                .dynamic_plugins
+                .first()
+                .map(|plugin| plugin.name().to_string())
+                .ok_or_else(|| anyhow::anyhow!("{}", tr!("publisher.no_registries")))?,
+        };
 
-        if !validation_result.valid {
-            println!("  ❌ Validation failed:");
-            for error in &validation_result.errors {
-                println!("    - [{}] {}", error.field, error.message);
-                errors.push(format!("{}: {}", error.field, error.message));
-            }
-            return Err(anyhow::anyhow!("Validation failed for {}", registry_name));
+        // Shared library plugins are selected by the name they report
+        if let Some(plugin) = self
+            .dynamic_plugins
+            .iter()
+            .find(|plugin| plugin.name() == registry_name)
+        {
+            return Ok((registry_name, plugin.clone()));
         }
 
-        if !validation_result.warnings.is_empty() {
-            println!("  ⚠️  Warnings:");
-            for warning in &validation_result.warnings {
-                println!("    - [{}] {}", warning.field, warning.message);
-                warnings.push(format!("{}: {}", warning.field, warning.message));
+        // Custom registries are selected by their `registries.custom` key
+        if let Some((_, config)) = custom.iter().find(|(name, _)| *name == registry_name) {
+            let mut plugin = CustomCommandPlugin::new(
+                self.project_path.clone(),
+                registry_name.clone(),
+                (*config).clone(),
+            );
+            if let Some(sandbox) = self.env_sandbox() {
+                plugin = plugin.with_sandbox(sandbox);
             }
+            return Ok((registry_name, Arc::new(plugin)));
         }
 
-        println!("  ✅ Validation successful\n");
+        let plugin_info = detected_registries
+            .iter()
+            .find(|p| p.registry_type.as_str() == registry_name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("publisher.registry_not_detected", registry = registry_name)
+                )
+            })?;
 
-        let package_version = validation_result
-            .metadata
-            .as_ref()
-            .and_then(|m| m.get("version"))
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "unknown".to_string());
-        let package_name = validation_result
-            .metadata
-            .as_ref()
-            .and_then(|m| m.get("packageName").or_else(|| m.get("name")))
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "unknown".to_string());
+        // Plugins with registry-specific settings get their config section
+        let registries = self.config.as_ref().map(|c| &c.registries);
+        let retry = retry_options(
+            self.config
+                .as_ref()
+                .and_then(|c| c.publish.as_ref())
+                .and_then(|p| p.retry.as_ref()),
+            &registry_name,
+        )?;
+        let command_timeout =
+            command_timeout(self.config.as_ref().and_then(|c| c.publish.as_ref()))?;
+        let sandbox = self.env_sandbox();
+        let limits = CommandLimits::new(sandbox.clone(), command_timeout);
+        let plugin: Arc<dyn RegistryPlugin> = match plugin_info.registry_type {
+            RegistryType::Npm => {
+                let plugin = NpmPlugin::new(self.project_path.clone())
+                    .with_config(registries.and_then(|r| r.npm.clone()).unwrap_or_default())
+                    .with_retry(retry)
+                    .with_command_timeout(command_timeout);
+                Arc::new(match sandbox {
+                    Some(sandbox) => plugin.with_sandbox(sandbox),
+                    None => plugin,
+                })
+            }
+            RegistryType::PyPI => {
+                let plugin = PyPiPlugin::new(self.project_path.clone())
+                    .with_retry(retry)
+                    .with_command_timeout(command_timeout);
+                Arc::new(match sandbox {
+                    Some(sandbox) => plugin.with_sandbox(sandbox),
+                    None => plugin,
+                })
+            }
+            RegistryType::GitHubPackages => Arc::new(
+                GitHubPackagesPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.github_packages.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Crates => {
+                let plugin = CratesIoPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.crates.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_retry(retry)
+                    .with_command_timeout(command_timeout);
+                Arc::new(match sandbox {
+                    Some(sandbox) => plugin.with_sandbox(sandbox),
+                    None => plugin,
+                })
+            }
+            RegistryType::Homebrew => Arc::new(
+                HomebrewPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.homebrew.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Upm => Arc::new(
+                UpmPlugin::new(self.project_path.clone())
+                    .with_config(registries.and_then(|r| r.upm.clone()).unwrap_or_default())
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Bazel if let Some(config) = registries.and_then(|r| r.bazel.clone()) => {
+                Arc::new(BazelPlugin::new(self.project_path.clone()).with_config(config))
+            }
+            RegistryType::Julia => Arc::new(
+                JuliaPlugin::new(self.project_path.clone())
+                    .with_config(registries.and_then(|r| r.julia.clone()).unwrap_or_default())
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Hackage => Arc::new(
+                HackagePlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.hackage.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Expo => Arc::new(
+                ExpoPlugin::new(self.project_path.clone())
+                    .with_config(registries.and_then(|r| r.expo.clone()).unwrap_or_default())
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Bucket => Arc::new(
+                BucketPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.bucket.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Artifactory
+                if let Some(config) = registries.and_then(|r| r.artifactory.clone()) =>
+            {
+                Arc::new(
+                    RepositoryManagerPlugin::new(
+                        self.project_path.clone(),
+                        RepositoryManagerKind::Artifactory,
+                    )
+                    .with_config(config),
+                )
+            }
+            RegistryType::Nexus if let Some(config) = registries.and_then(|r| r.nexus.clone()) => {
+                Arc::new(
+                    RepositoryManagerPlugin::new(
+                        self.project_path.clone(),
+                        RepositoryManagerKind::Nexus,
+                    )
+                    .with_config(config),
+                )
+            }
+            RegistryType::Cloudsmith => Arc::new(
+                CloudsmithPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.cloudsmith.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Gemfury => Arc::new(
+                GemfuryPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.gemfury.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Aur => Arc::new(
+                AurPlugin::new(self.project_path.clone())
+                    .with_config(registries.and_then(|r| r.aur.clone()).unwrap_or_default())
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Debian => Arc::new(
+                DebianPlugin::new(self.project_path.clone())
+                    .with_config(
+                        registries
+                            .and_then(|r| r.debian.clone())
+                            .unwrap_or_default(),
+                    )
+                    .with_command_limits(limits.clone()),
+            ),
+            RegistryType::Scoop if let Some(config) = registries.and_then(|r| r.scoop.clone()) => {
+                Arc::new(
+                    WindowsManifestPlugin::new(
+                        self.project_path.clone(),
+                        WindowsManifestKind::Scoop,
+                    )
+                    .with_config(config),
+                )
+            }
+            RegistryType::Winget
+                if let Some(config) = registries.and_then(|r| r.winget.clone()) =>
+            {
+                Arc::new(
+                    WindowsManifestPlugin::new(
+                        self.project_path.clone(),
+                        WindowsManifestKind::Winget,
+                    )
+                    .with_config(config),
+                )
+            }
+            RegistryType::Cpan => Arc::new(
+                CpanPlugin::new(self.project_path.clone()).with_command_limits(limits.clone()),
+            ),
+            RegistryType::Cran => Arc::new(
+                CranPlugin::new(self.project_path.clone()).with_command_limits(limits.clone()),
+            ),
+            registry_type => self
+                .plugin_loader
+                .load_plugin(registry_type, self.project_path.to_str().unwrap())?,
+        };
 
-        // 5. Dry-run (if not skipped)
-        let should_skip_dry_run = effective_options.dry_run || effective_options.resume;
+        Ok((registry_name, plugin))
+    }
 
-        if !should_skip_dry_run {
-            self.state_machine
-                .transition(PublishState::DryRun, None)
-                .await?;
-            println!("🧪 Executing dry-run...");
+    /// Compare the publish duration with `publish.durationBudget`
+    ///
+    /// Returns the alert (with the per-phase breakdown) when the budget is
+    /// exceeded; it is reported and added to the report warnings. Dry-runs
+    /// are not measured.
+    fn check_duration_budget(
+        &self,
+        result: &mut Result<PublishReport, anyhow::Error>,
+        duration: u64,
+    ) -> Option<String> {
+        let config = self
+            .config
+            .as_ref()?
+            .publish
+            .as_ref()?
+            .duration_budget
+            .as_ref()?;
+        let budget = match DurationBudget::from_config(config) {
+            Ok(budget) => budget,
+            Err(e) => {
+                self.reporter
+                    .warning(&format!("⚠️  publish.durationBudget: {}", e));
+                return None;
+            }
+        };
 
-            let dry_run_result = plugin.dry_run().await?;
+        let (registry, hooks) = match result {
+            Ok(report) if report.state == "DRY_RUN" => return None,
+            Ok(report) => (report.registry.clone(), report.hooks.clone()),
+            Err(_) => (
+                self.hook_context.registry.clone()?,
+                self.hook_results.clone(),
+            ),
+        };
+        let timings = duration_budget::phase_timings(
+            &self.state_machine.get_state_data().transitions,
+            chrono::Utc::now(),
+            &hooks,
+        );
+        let alert = budget.check(&registry, duration, &timings)?;
 
-            if !dry_run_result.success {
-                println!("  ❌ Dry-run failed:");
-                if let Some(ref dry_errors) = dry_run_result.errors {
-                    for error in dry_errors {
-                        println!("    - {}", error.message);
-                        errors.push(error.message.clone());
-                    }
-                }
-                return Err(anyhow::anyhow!("Dry-run failed for {}", registry_name));
+        for line in alert.lines() {
+            self.reporter.warning(&format!("⏱️  {}", line));
+        }
+        if let Ok(report) = result {
+            report.warnings.push(tr!(
+                "publisher.duration_budget_warning",
+                alert = alert.lines().next().unwrap_or_default()
+            ));
+        }
+        Some(alert)
+    }
+
+    /// Send a warning to the configured notification channels
+    async fn send_warning_notification(&self, context: &NotificationContext, message: &str) {
+        let Some(notifier) = Notifier::from_config(self.config.as_ref()) else {
+            return;
+        };
+
+        for outcome in notifier.warn(context, message).await {
+            if let Some(error) = outcome.error {
+                self.reporter.warning(&tr!(
+                    "publisher.notification_failed",
+                    channel = outcome.channel,
+                    error = error
+                ));
             }
+        }
+    }
 
-            println!("  ✅ Dry-run successful");
-            if let Some(ref size) = dry_run_result.estimated_size {
-                println!("    Package size: {}", size);
+    /// Send a lifecycle event to the configured webhooks, reporting delivery
+    /// failures as warnings
+    async fn emit_webhook(&self, event: WebhookEvent, context: &NotificationContext) {
+        let Some(emitter) = WebhookEmitter::from_config(self.config.as_ref()) else {
+            return;
+        };
+
+        for delivery in emitter.emit(&WebhookPayload::new(event, context)).await {
+            if let Some(error) = delivery.error {
+                self.reporter.warning(&tr!(
+                    "publisher.webhook_failed",
+                    event = event,
+                    url = delivery.url,
+                    attempts = delivery.attempts,
+                    error = error
+                ));
             }
-            println!();
         }
+    }
 
-        // Return if dry-run only
-        if effective_options.dry_run {
+    /// Send configured notifications, reporting channel failures as warnings
+    async fn send_notifications(&self, context: &NotificationContext) {
+        let Some(notifier) = Notifier::from_config(self.config.as_ref()) else {
+            return;
+        };
+
+        for outcome in notifier.notify(context).await {
+            match outcome.error {
+                None => self.reporter.info(&tr!(
+                    "publisher.notification_sent",
+                    channel = outcome.channel
+                )),
+                Some(error) => self.reporter.warning(&tr!(
+                    "publisher.notification_failed",
+                    channel = outcome.channel,
+                    error = error
+                )),
+            }
+        }
+    }
+
+    /// Move issues referenced since the last tag to the release state
+    /// (`notifications.jira` / `notifications.linear`)
+    async fn update_issue_trackers(&self, report: &PublishReport) {
+        let Some(tracker) =
+            IssueTracker::from_config(self.config.as_ref().and_then(|c| c.notifications.as_ref()))
+        else {
+            return;
+        };
+        let messages = match issue_tracker::commit_messages_since_last_release(
+            &self.project_path,
+            &report.version,
+        )
+        .await
+        {
+            Ok(messages) => messages,
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.issue_commits_failed", error = e));
+                return;
+            }
+        };
+
+        let release = ReleaseInfo {
+            context: NotificationContext::from_report(report),
+            urls: [&report.verification_url, &report.release_url]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+        };
+        for update in tracker.release(&messages, &release).await {
+            match update.error {
+                None => self.reporter.info(&tr!(
+                    "publisher.issue_released",
+                    issue = update.key,
+                    tracker = update.tracker
+                )),
+                Some(error) => self.reporter.warning(&tr!(
+                    "publisher.issue_update_failed",
+                    tracker = update.tracker,
+                    issue = update.key,
+                    error = error
+                )),
+            }
+        }
+    }
+
+    /// Refuse to publish outside `releaseWindows` or during a freeze
+    fn check_release_window(&self) -> Result<(), anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.release_windows.as_ref())
+            .filter(|w| w.enabled.unwrap_or(true))
+        else {
+            return Ok(());
+        };
+        let windows = ReleaseWindows::from_config(config)
+            .map_err(|e| anyhow::anyhow!("releaseWindows: {}", e))?;
+        if let Some(reason) = windows.check(chrono::Utc::now()) {
+            self.reporter.error(&format!("🗓️  {}", reason));
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.release_window_closed", reason = reason)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Publishing workflow (hooks on error are handled by `publish`)
+    async fn run_publish(
+        &mut self,
+        options: PublishOptions,
+    ) -> Result<PublishReport, anyhow::Error> {
+        let start_time = Instant::now();
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        // Load config if not already loaded
+        if self.config.is_none() {
+            self.load_config(Some(options.clone())).await?;
+        }
+
+        // Merge CLI options with config (CLI takes priority)
+        let mut effective_options = self.merge_options_with_config(options.clone());
+        effective_options.dry_run |= effective_options.prepare_only;
+
+        // Release windows / freeze periods (dry-runs are never restricted)
+        if !effective_options.dry_run {
+            self.check_release_window()?;
+        }
+
+        // 1. Restore state if resume requested
+        let resumed = if effective_options.resume {
+            let restored = self.state_machine.restore().await?;
+            if !restored {
+                return Err(anyhow::anyhow!("{}", tr!("publisher.state_missing")));
+            }
+            let state = self.state_machine.get_state_data();
+            if !state.can_resume {
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!(
+                        "publisher.nothing_to_resume",
+                        state = format!("{:?}", state.current_state)
+                    )
+                ));
+            }
+            match (&effective_options.registry, &state.registry) {
+                (Some(requested), Some(previous)) if requested != previous => {
+                    return Err(anyhow::anyhow!(
+                        "{}",
+                        tr!(
+                            "publisher.resume_registry_mismatch",
+                            previous = previous,
+                            requested = requested
+                        )
+                    ));
+                }
+                (None, Some(previous)) => effective_options.registry = Some(previous.clone()),
+                _ => {}
+            }
+            let completed: Vec<String> = state
+                .artifacts
+                .completed
+                .iter()
+                .map(|s| format!("{:?}", s))
+                .collect();
+            let from = format!("{:?}", state.current_state);
+            self.reporter.info(&if completed.is_empty() {
+                tr!("publisher.resuming", state = from)
+            } else {
+                tr!(
+                    "publisher.resuming_completed",
+                    state = from,
+                    stages = completed.join(", ")
+                )
+            });
+            self.transition(PublishState::Initial).await?;
+            state.artifacts
+        } else {
+            self.state_machine.clear().await?;
+            self.transition(PublishState::Initial).await?;
+            StageArtifacts::default()
+        };
+        let resumed_validation: Option<ValidationResult> = resumed
+            .validation
+            .clone()
+            .filter(|_| resumed.is_completed(PublishState::Validating))
+            .map(serde_json::from_value)
+            .transpose()?;
+        let resumed_publish: Option<PluginPublishResult> = resumed
+            .publish
+            .clone()
+            .filter(|_| resumed.is_completed(PublishState::Publishing))
+            .map(serde_json::from_value)
+            .transpose()?;
+
+        // 2. Detect registries
+        self.transition(PublishState::Detecting).await?;
+        let detected_registries = self.detect_registries().await?;
+
+        self.reporter.info(&tr!("publisher.detected_registries"));
+        for plugin in &detected_registries {
+            self.reporter.info(&tr!(
+                "publisher.detected_registry",
+                registry = plugin.registry_type.as_str(),
+                confidence = format!("{:.0}", plugin.confidence * 100.0)
+            ));
+        }
+        for (name, _) in self.custom_registries() {
+            self.reporter
+                .info(&tr!("publisher.custom_registry", registry = name));
+        }
+        for plugin in &self.dynamic_plugins {
+            self.reporter.info(&tr!(
+                "publisher.plugin_registry",
+                registry = plugin.name(),
+                version = plugin.version()
+            ));
+        }
+        self.reporter.info("");
+
+        // Use specified registry or first detected
+        let (registry_name, plugin) =
+            self.select_plugin(&detected_registries, effective_options.registry.clone())?;
+
+        self.reporter.info(&tr!(
+            "publisher.registry_selected",
+            registry = registry_name
+        ));
+        self.apply_registry_options(&registry_name, &mut effective_options);
+        self.event_registry = Some(registry_name.clone());
+        self.publish_span.record("registry", registry_name.as_str());
+        self.state_machine.set_registry(&registry_name);
+
+        // Completed stages are only reused for the same commit and manifest
+        let manifest = detected_registries
+            .iter()
+            .find(|d| d.registry_type.as_str() == registry_name)
+            .map(|d| PathBuf::from(&d.manifest_path));
+        let (head, manifest_hash) = self.source_fingerprint(manifest.as_deref()).await;
+        if !resumed.completed.is_empty()
+            && (resumed.head != head || resumed.manifest_hash != manifest_hash)
+        {
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.resume_source_changed")
+            ));
+        }
+        let artifacts = self.state_machine.artifacts_mut();
+        artifacts.head = head;
+        artifacts.manifest_hash = manifest_hash;
+
+        // 3. Security scan (if enabled)
+        let secrets_scanning_enabled = true; // TODO: Read from config
+        let mut scan_report = None;
+
+        if resumed_validation.is_some() {
+            self.reporter.info(&tr!("publisher.scan_already_passed"));
+        } else if secrets_scanning_enabled {
+            self.reporter.info(&tr!("publisher.security_scan"));
+
+            let baseline = SecretsBaseline::load(&self.project_path).await?;
+            self.secrets_scanner.set_baseline(baseline);
+            if let Some(scanning) = self
+                .config
+                .as_ref()
+                .and_then(|c| c.security.as_ref())
+                .and_then(|s| s.secrets_scanning.as_ref())
+            {
+                if let Some(parallelism) = scanning.parallelism {
+                    self.secrets_scanner.set_parallelism(parallelism);
+                }
+                if let Some(max_file_size) = scanning.max_file_size {
+                    self.secrets_scanner.set_max_file_size(max_file_size);
+                }
+                if let Some(respect) = scanning.respect_gitignore {
+                    self.secrets_scanner.set_respect_gitignore(respect);
+                }
+            }
+
+            let scan_result = self
+                .secrets_scanner
+                .scan_project(&self.project_path)
+                .await?;
+
+            for finding in &scan_result.findings {
+                self.events.emit(PublishEvent::SecretDetected {
+                    registry: registry_name.clone(),
+                    file: finding.file.display().to_string(),
+                    line: finding.line,
+                    secret_type: finding.secret_type.clone(),
+                });
+            }
+            if !scan_result.findings.is_empty() {
+                warnings.push(tr!(
+                    "publisher.secrets_detected",
+                    count = scan_result.findings.len()
+                ));
+
+                if !effective_options.non_interactive {
+                    self.reporter
+                        .warning(&tr!("publisher.secrets_detected_header"));
+                    for finding in &scan_result.findings {
+                        self.reporter.warning(&tr!(
+                            "publisher.secret_finding",
+                            secret_type = finding.secret_type,
+                            file = finding.file.display()
+                        ));
+                    }
+
+                    let question = tr!("publisher.confirm_despite_secrets");
+                    if !self.confirm(&question).await? {
+                        return Err(anyhow::anyhow!(
+                            "{}",
+                            tr!(
+                                "publisher.secrets_rejected",
+                                count = scan_result.findings.len()
+                            )
+                        ));
+                    }
+                } else {
+                    self.reporter.warning(&tr!(
+                        "publisher.secrets_detected_continuing",
+                        count = scan_result.findings.len()
+                    ));
+                }
+            } else {
+                self.reporter.success(&tr!("publisher.no_secrets"));
+            }
+            if scan_result.suppressed > 0 {
+                self.reporter.info(&tr!(
+                    "publisher.secrets_suppressed",
+                    count = scan_result.suppressed
+                ));
+            }
+
+            scan_report = Some(scan_result);
+
+            if let Some(depth) = self
+                .config
+                .as_ref()
+                .and_then(|c| c.security.as_ref())
+                .and_then(|s| s.secrets_scanning.as_ref())
+                .and_then(|s| s.history_depth)
+            {
+                self.scan_git_history(depth, effective_options.non_interactive, &mut warnings)
+                    .await?;
+            }
+        }
+
+        let registry_type = detected_registries
+            .iter()
+            .map(|d| d.registry_type)
+            .find(|r| r.as_str() == registry_name);
+        let (audit_report, confusion_report) = if resumed_validation.is_some() {
+            (
+                resumed
+                    .audit
+                    .clone()
+                    .map(serde_json::from_value::<AuditReport>)
+                    .transpose()?,
+                resumed
+                    .dependency_confusion
+                    .clone()
+                    .map(serde_json::from_value)
+                    .transpose()?,
+            )
+        } else {
+            (
+                self.audit_dependencies(registry_type, &mut warnings)
+                    .await?,
+                self.check_dependency_confusion(registry_type, &mut warnings)
+                    .await?,
+            )
+        };
+
+        // 4. Validation
+        self.transition(PublishState::Validating).await?;
+        let validation_result = match resumed_validation {
+            Some(validation_result) => validation_result,
+            None => {
+                self.reporter.info(&tr!("publisher.validating"));
+                plugin.validate().await?
+            }
+        };
+        let findings = validation_result
+            .errors
+            .iter()
+            .map(|e| (FindingSeverity::Error, &e.field, &e.message))
+            .chain(
+                validation_result
+                    .warnings
+                    .iter()
+                    .map(|w| (FindingSeverity::Warning, &w.field, &w.message)),
+            );
+        for (severity, field, message) in findings {
+            self.events.emit(PublishEvent::ValidationFinding {
+                registry: registry_name.clone(),
+                severity,
+                field: field.clone(),
+                message: message.clone(),
+            });
+        }
+
+        if !validation_result.valid {
+            self.reporter
+                .error(&tr!("publisher.validation_failed_header"));
+            for error in &validation_result.errors {
+                self.reporter
+                    .error(&format!("    - [{}] {}", error.field, error.message));
+                errors.push(format!("{}: {}", error.field, error.message));
+            }
+            let fields = validation_result
+                .errors
+                .iter()
+                .map(|error| error.field.clone())
+                .collect();
+            return Err(PublishErrorKind::ValidationFailed { fields }
+                .error(tr!("publisher.validation_failed", registry = registry_name)));
+        }
+
+        if !validation_result.warnings.is_empty() {
+            self.reporter.warning(&tr!("publisher.warnings_header"));
+            for warning in &validation_result.warnings {
+                self.reporter
+                    .warning(&format!("    - [{}] {}", warning.field, warning.message));
+                warnings.push(format!("{}: {}", warning.field, warning.message));
+            }
+        }
+
+        let warning_budget = WarningBudget::from_config(
+            self.config.as_ref().and_then(|c| c.validation.as_ref()),
+            effective_options.strict,
+        );
+        if let Err(exceeded) = warning_budget.check(validation_result.warnings.len()) {
+            self.reporter.error(&format!("  ❌ {}", exceeded));
+            return Err(
+                PublishErrorKind::ValidationFailed { fields: Vec::new() }.error(tr!(
+                    "publisher.warning_budget_exceeded",
+                    message = exceeded,
+                    registry = registry_name
+                )),
+            );
+        }
+
+        self.reporter.success(&tr!("publisher.validation_passed"));
+
+        let package_version = validation_result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("version"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| "unknown".to_string());
+        let package_name = validation_result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("packageName").or_else(|| m.get("name")))
+            .and_then(|n| n.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| "unknown".to_string());
+        self.publish_span
+            .record("package", package_name.as_str())
+            .record("version", package_version.as_str());
+        let quality_score = validation_result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("qualityScore"))
+            .and_then(|s| s.as_u64())
+            .map(|s| s as u32);
+        self.state_machine.set_version(&package_version);
+        let artifacts = self.state_machine.artifacts_mut();
+        artifacts.validation = Some(serde_json::to_value(&validation_result)?);
+        artifacts.audit = audit_report
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        artifacts.dependency_confusion = confusion_report
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+        self.state_machine
+            .complete_stage(PublishState::Validating)
+            .await?;
+        if let Some(score) = quality_score {
+            self.reporter
+                .info(&tr!("publisher.quality_score", score = score));
+        }
+
+        let policy_violations = self
+            .check_policies(
+                &registry_name,
+                registry_type,
+                &package_version,
+                audit_report.as_ref(),
+                effective_options.dry_run,
+                &mut warnings,
+            )
+            .await?;
+
+        if !effective_options.dry_run && !effective_options.hooks_only {
+            self.start_deployment(&registry_name, &package_name, &package_version)
+                .await;
+        }
+
+        // Keep scan and validation results for the release audit trail
+        let artifact_store = ReleaseArtifactStore::new(&self.project_path);
+        if let Some(ref scan_report) = scan_report
+            && let Err(e) = artifact_store
+                .save_json(&package_version, "scan-report", scan_report)
+                .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_scan_report_failed", error = e));
+        }
+        if let Some(ref audit_report) = audit_report
+            && let Err(e) = artifact_store
+                .save_json(&package_version, "dependency-audit", audit_report)
+                .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_audit_report_failed", error = e));
+        }
+        if let Some(ref confusion_report) = confusion_report
+            && let Err(e) = artifact_store
+                .save_json(&package_version, "dependency-confusion", confusion_report)
+                .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_confusion_report_failed", error = e));
+        }
+        if let Err(e) = artifact_store
+            .save_json(
+                &package_version,
+                &format!("validation-{}", registry_name),
+                &validation_result,
+            )
+            .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_validation_failed", error = e));
+        }
+
+        self.hook_context = HookContext {
+            version: Some(package_version.clone()),
+            package_name: Some(package_name.clone()),
+            registry: Some(registry_name.clone()),
+        };
+
+        if !effective_options.dry_run && !effective_options.prepare_only {
+            let context = NotificationContext {
+                package_name: package_name.clone(),
+                version: package_version.clone(),
+                registry: registry_name.clone(),
+                success: true,
+                ..Default::default()
+            };
+            self.emit_webhook(WebhookEvent::PublishStarted, &context)
+                .await;
+        }
+
+        // Pre-build hooks
+        if !effective_options.skip_hooks {
+            self.run_hooks(HookStage::PreBuild).await?;
+        }
+
+        // 5. Dry-run (if not skipped)
+        let should_skip_dry_run = (effective_options.dry_run && !effective_options.prepare_only)
+            || resumed.is_completed(PublishState::DryRun);
+        let mut estimated_size = None;
+
+        if !should_skip_dry_run {
+            self.transition(PublishState::DryRun).await?;
+            self.reporter.info(&tr!("publisher.dry_run"));
+
+            let dry_run_result = plugin.dry_run().await?;
+            if !dry_run_result.output.is_empty() {
+                self.events.emit(PublishEvent::DryRunOutput {
+                    registry: registry_name.clone(),
+                    chunk: redact(&dry_run_result.output),
+                });
+            }
+
+            if !dry_run_result.success {
+                self.reporter.error(&tr!("publisher.dry_run_failed_header"));
+                if let Some(ref dry_errors) = dry_run_result.errors {
+                    for error in dry_errors {
+                        self.reporter.error(&format!("    - {}", error.message));
+                        errors.push(error.message.clone());
+                    }
+                }
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!("publisher.dry_run_failed", registry = registry_name)
+                ));
+            }
+
+            self.state_machine
+                .complete_stage(PublishState::DryRun)
+                .await?;
+            self.reporter.success(&tr!("publisher.dry_run_passed"));
+            if let Some(ref size) = dry_run_result.estimated_size {
+                self.reporter
+                    .info(&tr!("publisher.package_size", size = size));
+            }
+            self.reporter.info("");
+            estimated_size = dry_run_result.estimated_size;
+        }
+
+        // Package size gate (npm)
+        let package_size = self
+            .check_bundle_size(
+                registry_type,
+                &registry_name,
+                &package_name,
+                &package_version,
+                &mut warnings,
+            )
+            .await?;
+
+        // Package size budget (validation.maxPackageSize)
+        self.check_package_size(
+            &artifact_store,
+            &registry_name,
+            &package_name,
+            &package_version,
+            estimated_size.as_deref(),
+            &mut warnings,
+        )
+        .await?;
+
+        // Published file list rules (validation.packageContents)
+        self.check_package_contents(registry_type, &mut warnings)
+            .await?;
+
+        // SBOM (before anything is published)
+        let sbom = self
+            .generate_sbom(
+                &artifact_store,
+                &package_name,
+                &package_version,
+                &mut warnings,
+            )
+            .await;
+
+        // Return if dry-run only
+        if effective_options.dry_run {
+            self.update_changelog(&package_version, true, &mut warnings)
+                .await;
+            self.cleanup_prereleases(plugin.as_ref(), &package_version, true, &mut warnings)
+                .await;
             return Ok(PublishReport {
                 success: true,
                 registry: registry_name,
@@ -349,153 +1895,1861 @@ impl PackagePublisher {
                 published_at: None,
                 verification_url: None,
                 errors,
+                error_kind: None,
                 warnings,
                 duration: start_time.elapsed().as_millis() as u64,
                 state: "DRY_RUN".to_string(),
+                integrity: None,
+                hooks: Vec::new(),
+                deprecated_prereleases: Vec::new(),
+                quality_score,
+                release_url: None,
+                submission_url: None,
+                sbom: sbom.clone(),
+                signatures: Vec::new(),
+                provenance: None,
+                audit: audit_report.clone(),
+                dependency_confusion: confusion_report.clone(),
+                malware_scan: Vec::new(),
+                artifact_inspection: Vec::new(),
+                package_size,
+                verification_wait: None,
+                policy_violations: policy_violations.clone(),
             });
         }
 
-        // 6. Confirmation (interactive mode)
-        let should_confirm = !effective_options.non_interactive
-            && !effective_options.resume
-            && self
-                .config
-                .as_ref()
-                .and_then(|c| c.publish.as_ref())
-                .and_then(|p| p.confirm)
-                .unwrap_or(true);
+        // Credential preflight: report missing credentials before anything is published
+        if let Some(registry_type) = detected_registries
+            .iter()
+            .find(|p| p.registry_type.as_str() == registry_name)
+            .map(|p| p.registry_type)
+        {
+            let check = CredentialPreflight::new(&self.project_path, &self.credential_validator)
+                .with_config(self.config.as_ref())
+                .check(registry_type)
+                .await;
+            if check.status.is_problem() {
+                self.reporter.warning(&tr!(
+                    "publisher.credentials_problem",
+                    registry = registry_name,
+                    source = check.source,
+                    detail = check.detail
+                ));
+                warnings.push(format!("credentials: {}", check.detail));
+            }
+        }
+
+        // 6. Confirmation (interactive mode)
+        let should_confirm = !effective_options.non_interactive
+            && !resumed.is_completed(PublishState::Confirming)
+            && self
+                .config
+                .as_ref()
+                .and_then(|c| c.publish.as_ref())
+                .and_then(|p| p.confirm)
+                .unwrap_or(true);
+
+        if should_confirm {
+            self.transition(PublishState::Confirming).await?;
+
+            self.reporter.info(&tr!("publisher.checklist"));
+            self.reporter.success(&tr!(
+                "publisher.checklist_registry",
+                registry = registry_name
+            ));
+            self.reporter.success(&tr!(
+                "publisher.checklist_version",
+                version = package_version
+            ));
+            self.reporter
+                .success(&tr!("publisher.checklist_validation"));
+            self.reporter.success(&tr!("publisher.checklist_dry_run"));
+            if !warnings.is_empty() {
+                self.reporter
+                    .warning(&tr!("publisher.checklist_warnings", count = warnings.len()));
+            }
+            self.reporter.info("");
+
+            let question = tr!("publisher.confirm_publish");
+            if !self.confirm(&question).await? {
+                self.reporter.info(&tr!("publisher.cancelled"));
+                self.transition(PublishState::Failed).await?;
+                return Ok(PublishReport {
+                    success: false,
+                    registry: registry_name,
+                    package_name,
+                    version: package_version,
+                    published_at: None,
+                    verification_url: None,
+                    errors: vec![tr!("publisher.user_cancelled")],
+                    error_kind: Some(PublishErrorKind::Other),
+                    warnings,
+                    duration: start_time.elapsed().as_millis() as u64,
+                    state: "FAILED".to_string(),
+                    integrity: None,
+                    hooks: Vec::new(),
+                    deprecated_prereleases: Vec::new(),
+                    quality_score,
+                    release_url: None,
+                    submission_url: None,
+                    sbom: sbom.clone(),
+                    signatures: Vec::new(),
+                    provenance: None,
+                    audit: audit_report.clone(),
+                    dependency_confusion: confusion_report.clone(),
+                    malware_scan: Vec::new(),
+                    artifact_inspection: Vec::new(),
+                    package_size,
+                    verification_wait: None,
+                    policy_violations: policy_violations.clone(),
+                });
+            }
+            self.state_machine
+                .complete_stage(PublishState::Confirming)
+                .await?;
+        }
+
+        // Second-person approval
+        if !effective_options.hooks_only && !resumed.is_completed(PublishState::WaitingApproval) {
+            self.wait_for_approval(&registry_name, &package_name, &package_version)
+                .await?;
+        }
+
+        // Pre-publish hooks
+        if !effective_options.skip_hooks && resumed_publish.is_none() {
+            self.run_hooks(HookStage::PrePublish).await?;
+        }
+
+        // Return if hooks-only mode
+        if effective_options.hooks_only {
+            self.reporter.info(&tr!("publisher.hooks_only"));
+            return Ok(PublishReport {
+                success: true,
+                registry: registry_name,
+                package_name,
+                version: package_version,
+                published_at: None,
+                verification_url: None,
+                errors,
+                error_kind: None,
+                warnings,
+                duration: start_time.elapsed().as_millis() as u64,
+                state: "DRY_RUN".to_string(),
+                integrity: None,
+                hooks: Vec::new(),
+                deprecated_prereleases: Vec::new(),
+                quality_score,
+                release_url: None,
+                submission_url: None,
+                sbom: sbom.clone(),
+                signatures: Vec::new(),
+                provenance: None,
+                audit: audit_report.clone(),
+                dependency_confusion: confusion_report.clone(),
+                malware_scan: Vec::new(),
+                artifact_inspection: Vec::new(),
+                package_size,
+                verification_wait: None,
+                policy_violations: policy_violations.clone(),
+            });
+        }
+
+        // Everything up to the publish itself already ran when resuming after it
+        let (artifact_inspection, malware_scan, signatures) = if resumed_publish.is_some() {
+            (Vec::new(), Vec::new(), Vec::new())
+        } else {
+            self.update_changelog(&package_version, false, &mut warnings)
+                .await;
+
+            // Scan and sign artifacts (after pre-publish hooks, which may build them)
+            let artifact_inspection = self
+                .inspect_artifacts(
+                    &artifact_store,
+                    &registry_name,
+                    &package_name,
+                    &package_version,
+                    &mut warnings,
+                )
+                .await?;
+            let malware_scan = self
+                .scan_artifacts(
+                    &artifact_store,
+                    &registry_name,
+                    &package_name,
+                    &package_version,
+                    &mut warnings,
+                )
+                .await?;
+            let signatures = self
+                .sign_artifacts(
+                    &artifact_store,
+                    &registry_name,
+                    &package_name,
+                    &package_version,
+                    sbom.as_ref(),
+                    &mut warnings,
+                )
+                .await?;
+            (artifact_inspection, malware_scan, signatures)
+        };
+
+        // 7. Publish
+        let publish_result = match resumed_publish {
+            Some(publish_result) => {
+                self.reporter.info(&tr!("publisher.already_published"));
+                publish_result
+            }
+            None => {
+                self.transition(PublishState::Publishing).await?;
+                self.reporter.info(&tr!("publisher.publishing"));
+
+                // `otp.required` asks before npm rejects the publish
+                if effective_options.otp.is_none()
+                    && registry_name == "npm"
+                    && self.otp_config().and_then(|otp| otp.required) == Some(true)
+                {
+                    effective_options.otp =
+                        self.fetch_otp(&registry_name, &effective_options).await?;
+                }
+                let mut publish_result = plugin
+                    .publish(Some(effective_options.to_plugin_options()))
+                    .await?;
+                // One retry with a fresh code when the OTP is missing or expired
+                if otp::otp_required(&publish_result)
+                    && let Some(code) = self.fetch_otp(&registry_name, &effective_options).await?
+                {
+                    self.reporter.info(&tr!("publisher.otp_retry"));
+                    effective_options.otp = Some(code);
+                    publish_result = plugin
+                        .publish(Some(effective_options.to_plugin_options()))
+                        .await?;
+                }
+                for line in publish_result.output.iter().flat_map(|o| o.lines()) {
+                    self.events.emit(PublishEvent::PublishOutput {
+                        registry: registry_name.clone(),
+                        line: redact(line),
+                    });
+                }
+
+                if !publish_result.success {
+                    let error_msg = publish_result
+                        .error
+                        .unwrap_or_else(|| tr!("publisher.publish_failed_unknown"));
+                    let kind = publish_result
+                        .error_kind
+                        .unwrap_or_else(|| PublishErrorKind::classify(&error_msg));
+                    return Err(kind.error(tr!(
+                        "publisher.publish_failed",
+                        registry = registry_name,
+                        message = error_msg
+                    )));
+                }
+                // The state file stays in the project; command output and
+                // errors can carry tokens
+                let mut saved = publish_result.clone();
+                saved.output = saved.output.as_deref().map(redact);
+                saved.error = saved.error.as_deref().map(redact);
+                self.state_machine.artifacts_mut().publish = Some(serde_json::to_value(&saved)?);
+                self.state_machine
+                    .complete_stage(PublishState::Publishing)
+                    .await?;
+                publish_result
+            }
+        };
+
+        // Registries with human review only accept a submission here
+        let submission_url = publish_result
+            .metadata
+            .as_ref()
+            .filter(|m| m.get("pendingReview").and_then(|v| v.as_bool()) == Some(true))
+            .map(|m| {
+                m.get("submissionUrl")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or_else(|| publish_result.package_url.clone())
+                    .unwrap_or_default()
+            });
+
+        match submission_url {
+            Some(ref url) => {
+                self.reporter.info(&tr!("publisher.submitted_for_review"));
+                self.reporter
+                    .info(&tr!("publisher.submission_url", url = url));
+                if let Some(ref output) = publish_result.output {
+                    self.reporter
+                        .info(&format!("    {}", output.trim().replace('\n', "\n    ")));
+                }
+            }
+            None => self.reporter.success(&tr!("publisher.published")),
+        }
+        if let Some(ref metadata) = publish_result.metadata
+            && metadata.get("backport").and_then(|v| v.as_bool()) == Some(true)
+        {
+            let latest = metadata
+                .get("latestVersion")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            match metadata.get("distTag").and_then(|v| v.as_str()) {
+                Some(tag) => self.reporter.info(&tr!(
+                    "publisher.backport_tagged",
+                    tag = tag,
+                    latest = latest
+                )),
+                None => self
+                    .reporter
+                    .info(&tr!("publisher.backport", latest = latest)),
+            }
+        }
+        self.reporter.info("");
+
+        // 8. Verify (if enabled)
+        let should_verify = self
+            .config
+            .as_ref()
+            .and_then(|c| c.publish.as_ref())
+            .and_then(|p| p.verify)
+            .unwrap_or(true)
+            && submission_url.is_none();
+
+        let mut verification_url = None;
+        let mut integrity = None;
+        let mut verification_wait = None;
+        if should_verify {
+            self.transition(PublishState::Verifying).await?;
+            self.reporter.info(&tr!("publisher.verifying"));
+
+            let poller =
+                VerifyPoller::from_config(self.config.as_ref().and_then(|c| c.publish.as_ref()))?;
+            let reporter = &self.reporter;
+            let outcome = poller
+                .verify(plugin.as_ref(), |attempt, delay| {
+                    reporter.info(&tr!(
+                        "publisher.verify_retry",
+                        attempt = attempt,
+                        delay = duration_budget::format_duration(delay.as_millis() as u64)
+                    ));
+                })
+                .await;
+            if outcome.attempts > 1 {
+                verification_wait = Some(outcome.waited);
+            }
+
+            match outcome.result {
+                Ok(verify_result) => {
+                    if verify_result.verified {
+                        self.reporter.success(&tr!("publisher.verified"));
+                        if let Some(waited) = verification_wait {
+                            self.reporter.info(&tr!(
+                                "publisher.verified_after",
+                                waited = duration_budget::format_duration(waited),
+                                attempts = outcome.attempts
+                            ));
+                        }
+                        if let Some(ref url) = verify_result.url {
+                            self.reporter.info(&format!("    URL: {}", url));
+                            verification_url = Some(url.clone());
+                        }
+                        if let Some(ref data) = verify_result.integrity {
+                            if let Some(ref shasum) = data.shasum {
+                                self.reporter.info(&format!("    shasum: {}", shasum));
+                            }
+                            if let Some(ref sri) = data.integrity {
+                                self.reporter.info(&format!("    integrity: {}", sri));
+                            }
+                            if let Some(ref checksum) = data.checksum {
+                                self.reporter.info(&format!("    checksum: {}", checksum));
+                            }
+                        }
+                        self.reporter.info("");
+                        integrity = verify_result.integrity;
+                    } else {
+                        let error_msg = verify_result
+                            .error
+                            .unwrap_or_else(|| tr!("publisher.unknown_error"));
+                        warnings.push(tr!("publisher.verification_failed", error = error_msg));
+                        self.reporter
+                            .warning(&tr!("publisher.verification_failed_published"));
+                        self.reporter.warning(&format!("    {}", error_msg));
+                    }
+                }
+                Err(e) => {
+                    warnings.push(tr!("publisher.verification_error", error = e));
+                    self.reporter
+                        .warning(&tr!("publisher.verification_error_published"));
+                }
+            }
+        }
+
+        // Post-publish hooks (failures do not undo a successful publish)
+        if !effective_options.skip_hooks
+            && let Err(e) = self.run_hooks(HookStage::PostPublish).await
+        {
+            self.reporter
+                .warning(&tr!("publisher.post_publish_failed", error = e));
+            warnings.push(e.to_string());
+        }
+
+        // Clean up prereleases superseded by this stable release
+        let deprecated_prereleases = self
+            .cleanup_prereleases(plugin.as_ref(), &package_version, false, &mut warnings)
+            .await;
+
+        // SLSA provenance for what was published
+        let provenance = self
+            .generate_provenance(
+                ProvenanceInput {
+                    registry: &registry_name,
+                    package_name: &package_name,
+                    version: &package_version,
+                    signatures: &signatures,
+                    integrity: integrity.as_ref(),
+                    sbom: sbom.as_ref(),
+                    started_on: chrono::Utc::now()
+                        - chrono::Duration::from_std(start_time.elapsed()).unwrap_or_default(),
+                },
+                &mut warnings,
+            )
+            .await;
+
+        // Tag the release and create the GitHub Release
+        let mut release_assets = sbom
+            .as_ref()
+            .filter(|_| self.sbom_config().and_then(|c| c.attach_to_release) != Some(false))
+            .map(|s| vec![PathBuf::from(&s.path)])
+            .unwrap_or_default();
+        if let Some(ref provenance) = provenance
+            && self.provenance_config().and_then(|c| c.attach_to_release) != Some(false)
+        {
+            release_assets.push(PathBuf::from(&provenance.path));
+        }
+        if self.signing_config().and_then(|c| c.attach_to_release) != Some(false) {
+            for signature in &signatures {
+                release_assets.push(PathBuf::from(&signature.bundle));
+                release_assets.extend(signature.attestation.as_ref().map(PathBuf::from));
+            }
+        }
+        let release_url = self
+            .create_release(
+                &package_name,
+                &package_version,
+                release_assets,
+                &mut warnings,
+            )
+            .await;
+
+        // Success
+        self.transition(PublishState::Success).await?;
+
+        let report = PublishReport {
+            success: true,
+            registry: registry_name,
+            package_name,
+            version: package_version,
+            published_at: Some(chrono::Utc::now()),
+            verification_url,
+            errors,
+            error_kind: None,
+            warnings,
+            duration: start_time.elapsed().as_millis() as u64,
+            state: "SUCCESS".to_string(),
+            integrity,
+            hooks: std::mem::take(&mut self.hook_results),
+            deprecated_prereleases,
+            quality_score,
+            release_url,
+            submission_url,
+            sbom,
+            signatures,
+            provenance,
+            audit: audit_report,
+            dependency_confusion: confusion_report,
+            malware_scan,
+            artifact_inspection,
+            package_size,
+            verification_wait,
+            policy_violations,
+        };
+
+        self.save_release_artifacts(&artifact_store, &report).await;
+
+        Ok(report)
+    }
+
+    /// Environment of hooks and publish commands (`security.sandbox`;
+    /// `None` inherits the full environment)
+    fn env_sandbox(&self) -> Option<EnvSandbox> {
+        let sandbox = self
+            .config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.sandbox.clone())
+            .unwrap_or_default();
+        sandbox
+            .enabled
+            .unwrap_or(true)
+            .then(|| EnvSandbox::new(sandbox.allow_env.as_deref().unwrap_or_default()))
+    }
+
+    /// `HEAD` commit and hash of the registry manifest (`None` outside a
+    /// git checkout or without a manifest)
+    async fn source_fingerprint(
+        &self,
+        manifest: Option<&Path>,
+    ) -> (Option<String>, Option<String>) {
+        let head = run_git(&self.project_path, &["rev-parse", "HEAD"])
+            .await
+            .ok();
+        let manifest_hash = match manifest {
+            Some(manifest) => sha256_file(manifest).await.ok().map(|(hash, _)| hash),
+            None => None,
+        };
+        (head, manifest_hash)
+    }
+
+    /// Sandbox and timeout of package builds (`security.sandbox`,
+    /// `publish.commandTimeout`)
+    fn command_limits(&self) -> anyhow::Result<CommandLimits> {
+        let timeout = command_timeout(self.config.as_ref().and_then(|c| c.publish.as_ref()))?;
+        Ok(CommandLimits::new(self.env_sandbox(), timeout))
+    }
+
+    /// Whether hook working directories must stay inside the project
+    fn sandbox_jail(&self) -> bool {
+        self.config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.sandbox.as_ref())
+            .and_then(|s| s.jail)
+            .unwrap_or(true)
+    }
+
+    /// Run configured hooks of a stage, recording their results
+    async fn run_hooks(&mut self, stage: HookStage) -> Result<(), anyhow::Error> {
+        let Some(hooks) = self.config.as_ref().and_then(|c| c.hooks.clone()) else {
+            return Ok(());
+        };
+        let runner = HookRunner::new(&self.project_path, hooks)
+            .with_sandbox(self.env_sandbox(), self.sandbox_jail());
+        if !runner.has_hooks(stage) {
+            return Ok(());
+        }
+
+        self.reporter
+            .info(&tr!("publisher.running_hooks", stage = stage));
+        let results = runner.run_stage(stage, &self.hook_context).await;
+
+        for result in &results {
+            if result.success {
+                self.reporter
+                    .success(&format!("  ✅ {} ({}ms)", result.command, result.duration));
+            } else {
+                self.reporter.error(&format!("  ❌ {}", result.command));
+                if let Some(ref error) = result.error {
+                    self.reporter.error(&format!("    {}", error));
+                }
+                for line in result.stderr.lines().take(20) {
+                    self.reporter.error(&format!("    {}", line));
+                }
+            }
+        }
+
+        let failure = results.iter().find(|r| !r.success).map(|r| {
+            PublishErrorKind::HookFailed {
+                stage: stage.to_string(),
+                command: r.command.clone(),
+            }
+            .error(tr!(
+                "publisher.hook_failed",
+                stage = stage,
+                command = r.command
+            ))
+        });
+        self.hook_results.extend(results);
+
+        match failure {
+            Some(error) => Err(error),
+            None => {
+                self.reporter.info("");
+                Ok(())
+            }
+        }
+    }
+
+    fn sbom_config(&self) -> Option<&SbomConfig> {
+        self.config
+            .as_ref()
+            .and_then(|c| c.supply_chain.as_ref())
+            .and_then(|s| s.sbom.as_ref())
+    }
+
+    /// Generate the SBOM and store it with the release artifacts (if `supplyChain.sbom.enabled`)
+    ///
+    /// Failures become warnings.
+    async fn generate_sbom(
+        &self,
+        store: &ReleaseArtifactStore,
+        package_name: &str,
+        version: &str,
+        warnings: &mut Vec<String>,
+    ) -> Option<SbomReference> {
+        let config = self.sbom_config()?;
+        if !config.enabled.unwrap_or(false) {
+            return None;
+        }
+
+        let format = match config.format.as_deref().map(str::parse::<SbomFormat>) {
+            Some(Err(e)) => {
+                self.reporter.warning(&format!("  ⚠️  {}\n", e));
+                warnings.push(format!("SBOM: {}", e));
+                return None;
+            }
+            Some(Ok(format)) => format,
+            None => SbomFormat::default(),
+        };
+
+        self.reporter
+            .info(&tr!("publisher.generating_sbom", format = format));
+        let result = async {
+            let document = SbomGenerator::new(&self.project_path)
+                .with_subject(package_name, version)
+                .generate(format)
+                .await?;
+            let path = store
+                .save_raw(version, &document.file_name, document.content.as_bytes())
+                .await?;
+            Ok::<_, anyhow::Error>(document.reference(&path))
+        }
+        .await;
+
+        match result {
+            Ok(reference) => {
+                self.reporter.success(&tr!(
+                    "publisher.sbom_generated",
+                    components = reference.components,
+                    sha256 = reference.sha256
+                ));
+                Some(reference)
+            }
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.sbom_failed", error = e));
+                warnings.push(format!("SBOM: {}", e));
+                None
+            }
+        }
+    }
+
+    fn provenance_config(&self) -> Option<&ProvenanceConfig> {
+        self.config
+            .as_ref()
+            .and_then(|c| c.supply_chain.as_ref())
+            .and_then(|s| s.provenance.as_ref())
+            .filter(|c| c.enabled.unwrap_or(false))
+    }
+
+    /// Write the SLSA provenance statement (if `supplyChain.provenance.enabled`)
+    ///
+    /// Subjects are the signed artifacts, or the package with the digests
+    /// reported by the registry. Failures become warnings.
+    async fn generate_provenance(
+        &self,
+        input: ProvenanceInput<'_>,
+        warnings: &mut Vec<String>,
+    ) -> Option<ProvenanceReference> {
+        let config = self.provenance_config()?;
+        self.reporter.info(&tr!("publisher.generating_provenance"));
+
+        let mut builder = ProvenanceBuilder::new(&self.project_path)
+            .with_builder_id(config.builder_id.clone())
+            .with_parameter("registry", input.registry)
+            .with_parameter("package", input.package_name)
+            .with_parameter("version", input.version)
+            .started_on(input.started_on);
+        for subject in provenance_subjects(&input) {
+            builder = builder.with_subject(subject);
+        }
+        if let Some(sbom) = input.sbom {
+            let file_name = Path::new(&sbom.path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| sbom.path.clone());
+            builder = builder.with_byproduct(ResourceDescriptor::new(
+                file_name,
+                "sha256",
+                sbom.sha256.clone(),
+            ));
+        }
+
+        match builder.write(input.package_name, input.version).await {
+            Ok(reference) => {
+                self.reporter.success(&tr!(
+                    "publisher.provenance_generated",
+                    subjects = reference.subjects,
+                    builder = reference.builder_id
+                ));
+                Some(reference)
+            }
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.provenance_failed", error = e));
+                warnings.push(format!("provenance: {}", e));
+                None
+            }
+        }
+    }
+
+    fn signing_config(&self) -> Option<&SigningConfig> {
+        self.config
+            .as_ref()
+            .and_then(|c| c.signing.as_ref())
+            .filter(|c| c.enabled.unwrap_or(false))
+    }
+
+    /// Scan the last `depth` commits for secrets
+    /// (if `security.secretsScanning.historyDepth` is set)
+    ///
+    /// Secrets removed from the tree still leak through the repository history,
+    /// so findings need confirmation like current ones.
+    async fn scan_git_history(
+        &self,
+        depth: usize,
+        non_interactive: bool,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        self.reporter
+            .info(&tr!("publisher.history_scan", depth = depth));
+
+        let history = match self
+            .secrets_scanner
+            .scan_git_history(&self.project_path, depth)
+            .await
+        {
+            Ok(history) => history,
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.history_scan_failed", error = e));
+                warnings.push(format!("git history scan: {}", e));
+                return Ok(());
+            }
+        };
+
+        if !history.has_secrets {
+            self.reporter.success(&tr!(
+                "publisher.history_clean",
+                count = history.scanned_commits
+            ));
+            return Ok(());
+        }
+
+        warnings.push(tr!(
+            "publisher.history_secrets_detected",
+            count = history.findings.len()
+        ));
+        self.reporter
+            .warning(&tr!("publisher.history_secrets_header"));
+        for finding in &history.findings {
+            self.reporter.warning(&tr!(
+                "publisher.history_secret_finding",
+                secret_type = finding.finding.secret_type,
+                file = finding.finding.file.display(),
+                line = finding.finding.line,
+                commit = &finding.commit[..finding.commit.len().min(12)],
+                author = finding.author
+            ));
+        }
+
+        if non_interactive {
+            self.reporter
+                .warning(&tr!("publisher.history_secrets_continuing"));
+        } else {
+            let question = tr!("publisher.confirm_despite_secrets");
+            if !self.confirm(&question).await? {
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!(
+                        "publisher.history_secrets_rejected",
+                        count = history.findings.len()
+                    )
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Audit dependencies (if `security.audit.enabled`)
+    ///
+    /// Findings at or above `failOn` (default: high) abort publishing; the
+    /// rest, and backends that cannot run, become warnings.
+    async fn audit_dependencies(
+        &self,
+        registry_type: Option<RegistryType>,
+        warnings: &mut Vec<String>,
+    ) -> Result<Option<AuditReport>, anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.audit.as_ref())
+            .filter(|a| a.enabled.unwrap_or(false))
+        else {
+            return Ok(None);
+        };
+        let Some(registry_type) =
+            registry_type.filter(|r| AuditBackend::for_registry(*r).is_some())
+        else {
+            return Ok(None);
+        };
+
+        let threshold = match config.fail_on.as_deref() {
+            Some(fail_on) => fail_on.parse().map_err(anyhow::Error::msg)?,
+            None => IssueSeverity::High,
+        };
+        let ignore = config.ignore.clone().unwrap_or_default();
+
+        self.reporter.info(&tr!("publisher.dependency_audit"));
+        let report = DependencyAuditor::new(&self.project_path)
+            .audit_registries(&[registry_type])
+            .await;
+
+        for error in &report.errors {
+            self.reporter
+                .warning(&tr!("publisher.audit_not_run", error = error));
+            warnings.push(format!("audit: {}", error));
+        }
+
+        let blocking: Vec<&AuditFinding> = report.blocking(threshold, &ignore).collect();
+        if !blocking.is_empty() {
+            self.reporter.error(&tr!(
+                "publisher.audit_blocking_header",
+                count = blocking.len()
+            ));
+            for finding in &blocking {
+                self.reporter.error(&format!(
+                    "    - {} {} ({:?}): {} - {}",
+                    finding.package,
+                    finding.advisory_id,
+                    finding.severity,
+                    finding.title,
+                    finding.remediation
+                ));
+            }
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.audit_failed", count = blocking.len())
+            ));
+        }
+
+        if report.findings.is_empty() {
+            self.reporter.success(&tr!("publisher.no_vulnerabilities"));
+        } else {
+            self.reporter.warning(&tr!(
+                "publisher.audit_below_threshold",
+                summary = report.summary()
+            ));
+            warnings.push(format!("audit: {}", report.summary()));
+        }
+
+        Ok(Some(report))
+    }
+
+    /// Evaluate `policies` for the package about to be published
+    ///
+    /// Violated `block` policies abort publishing; dry-runs only report them.
+    /// Vulnerability policies use the `security.audit` report, or run their
+    /// own audit when the audit gate is disabled.
+    async fn check_policies(
+        &self,
+        registry: &str,
+        registry_type: Option<RegistryType>,
+        version: &str,
+        audit: Option<&AuditReport>,
+        dry_run: bool,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<PolicyViolation>, anyhow::Error> {
+        let Some(configs) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.policies.as_ref())
+            .filter(|p| !p.is_empty())
+        else {
+            return Ok(Vec::new());
+        };
+        let engine = PolicyEngine::from_config(configs)?;
+
+        self.reporter.info(&tr!("publisher.checking_policies"));
+        let policy_audit = match (audit, registry_type) {
+            (None, Some(registry_type))
+                if engine.needs_audit(registry)
+                    && AuditBackend::for_registry(registry_type).is_some() =>
+            {
+                Some(
+                    DependencyAuditor::new(&self.project_path)
+                        .audit_registries(&[registry_type])
+                        .await,
+                )
+            }
+            _ => None,
+        };
+        let branch = policy::current_branch(&self.project_path).await;
+        let report = engine.evaluate(&PolicyContext {
+            now: chrono::Utc::now(),
+            registry,
+            version,
+            branch: branch.as_deref(),
+            audit: audit.or(policy_audit.as_ref()),
+        });
+
+        let blocked = report.is_blocked() && !dry_run;
+        for violation in &report.violations {
+            if blocked && violation.enforcement == Enforcement::Block {
+                self.reporter.error(&format!("  ❌ {}", violation));
+            } else {
+                self.reporter.warning(&format!("  ⚠️  {}", violation));
+                warnings.push(format!("policy: {}", violation));
+            }
+        }
+        if blocked {
+            let names: Vec<&str> = report.blocking().map(|v| v.policy.as_str()).collect();
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.policy_blocked", policies = names.join(", "))
+            ));
+        }
+        if report.violations.is_empty() {
+            self.reporter.success(&tr!(
+                "publisher.policies_passed",
+                evaluated = report.evaluated,
+                total = configs.len()
+            ));
+        } else {
+            self.reporter.info("");
+        }
+
+        Ok(report.violations)
+    }
+
+    /// Wait for a second person's approval (`approval`)
+    ///
+    /// Rejections, timeouts and failures to post the request abort publishing.
+    async fn wait_for_approval(
+        &mut self,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+    ) -> Result<(), anyhow::Error> {
+        let Some(config) = self.config.as_ref().and_then(|c| c.approval.clone()) else {
+            return Ok(());
+        };
+        let mut gate = ApprovalGate::from_config(&self.project_path, config)?;
+        if let Some(remote) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.release.as_ref())
+            .and_then(|r| r.remote.as_deref())
+        {
+            gate = gate.with_remote(remote);
+        }
+        if !gate.is_required(registry, version) {
+            return Ok(());
+        }
+
+        self.transition(PublishState::WaitingApproval).await?;
+        let ticket = gate
+            .post(package_name, version, registry)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("{}", tr!("publisher.approval_request_failed", error = e))
+            })?;
+        self.reporter.info(&tr!(
+            "publisher.waiting_for_approval",
+            ticket = ticket,
+            timeout = duration_budget::format_duration(gate.timeout().as_millis() as u64)
+        ));
+        let decision = gate.wait(&ticket).await?;
+        if let Err(e) = gate.close(&ticket, &decision).await {
+            self.reporter
+                .warning(&tr!("publisher.approval_not_closed", error = e));
+        }
+
+        if let ApprovalDecision::Approved { ref approver } = decision {
+            self.reporter
+                .success(&tr!("publisher.approved", approver = approver));
+            self.state_machine
+                .complete_stage(PublishState::WaitingApproval)
+                .await?;
+            return Ok(());
+        }
+        self.reporter
+            .error(&tr!("publisher.approval_denied", decision = decision));
+        self.transition(PublishState::Failed).await?;
+        Err(anyhow::anyhow!(
+            "{}",
+            tr!("publisher.not_approved", decision = decision)
+        ))
+    }
+
+    /// Check internal package names for dependency confusion
+    /// (if `security.dependencyConfusion.enabled`)
+    ///
+    /// The package itself counts as internal when it goes to GitHub Packages.
+    /// Findings at or above `failOn` (default: high) abort publishing.
+    fn deployment_manager(&self) -> Option<DeploymentManager> {
+        let release = self.config.as_ref()?.release.as_ref()?;
+        let manager =
+            DeploymentManager::new(&self.project_path, release.github_deployment.clone()?)
+                .with_remote(release.remote.as_deref().unwrap_or("origin"));
+        manager.is_enabled().then_some(manager)
+    }
+
+    /// Create the GitHub Deployment for this publish (`release.githubDeployment`)
+    ///
+    /// Failures are reported as warnings; they never block publishing.
+    async fn start_deployment(&mut self, registry: &str, package_name: &str, version: &str) {
+        let Some(manager) = self.deployment_manager() else {
+            return;
+        };
+        match manager.start(registry, package_name, version).await {
+            Ok(deployment) => {
+                self.reporter.info(&tr!(
+                    "publisher.deployment_created",
+                    id = deployment.id,
+                    environment = deployment.environment
+                ));
+                self.deployment = Some(deployment);
+            }
+            Err(e) => self
+                .reporter
+                .warning(&tr!("publisher.deployment_failed", error = e)),
+        }
+    }
+
+    /// Mark the GitHub Deployment success/failure from the publish result
+    async fn finish_deployment(
+        &self,
+        deployment: &GitHubDeployment,
+        result: &Result<PublishReport, anyhow::Error>,
+    ) {
+        let Some(manager) = self.deployment_manager() else {
+            return;
+        };
+        let (success, url) = match result {
+            Ok(report) => (report.success, report.verification_url.as_deref()),
+            Err(_) => (false, None),
+        };
+        if let Err(e) = manager.finish(deployment, success, url).await {
+            self.reporter.warning(&tr!(
+                "publisher.deployment_status_failed",
+                id = deployment.id,
+                error = e
+            ));
+        }
+    }
+
+    /// Enforce `validation.maxPackageSize`
+    ///
+    /// The size comes from the packed release artifacts (with the largest
+    /// files listed when over budget); registries without a default artifact
+    /// fall back to the dry-run estimate.
+    async fn check_package_size(
+        &self,
+        store: &ReleaseArtifactStore,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+        estimated_size: Option<&str>,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(validation) = self.config.as_ref().and_then(|c| c.validation.as_ref()) else {
+            return Ok(());
+        };
+        let Some(ref max) = validation.max_package_size else {
+            return Ok(());
+        };
+        let max = size_budget::parse_size(max)
+            .map_err(|e| anyhow::anyhow!("validation.maxPackageSize: {}", e))?;
+
+        self.reporter.info(&tr!("publisher.package_size_budget"));
+        let measurement = match release_artifacts(
+            &self.project_path,
+            &store.release_dir(version),
+            None,
+            registry,
+            package_name,
+            version,
+            &self.command_limits()?,
+        )
+        .await
+        {
+            Ok(artifacts) => Some(size_budget::measure_artifacts(&artifacts).await?),
+            Err(_) => estimated_size
+                .and_then(|size| size_budget::parse_size(size).ok())
+                .map(|total| SizeMeasurement {
+                    total,
+                    largest_files: Vec::new(),
+                }),
+        };
+        let Some(measurement) = measurement else {
+            self.reporter
+                .warning(&tr!("publisher.package_size_unknown"));
+            warnings.push(format!(
+                "package size: {}",
+                tr!("publisher.package_size_unknown_warning")
+            ));
+            return Ok(());
+        };
+
+        match measurement.check(max) {
+            None => self.reporter.success(&tr!(
+                "publisher.package_size_ok",
+                size = bundle_size::format_size(measurement.total),
+                max = bundle_size::format_size(max)
+            )),
+            Some(message) if validation.package_size_level.as_deref() == Some("warning") => {
+                for line in message.lines() {
+                    self.reporter.warning(&format!("  ⚠️  {}", line));
+                }
+                warnings.push(format!(
+                    "package size: {}",
+                    message.lines().next().unwrap_or_default()
+                ));
+            }
+            Some(message) => {
+                for line in message.lines() {
+                    self.reporter.error(&format!("  ❌ {}", line));
+                }
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!(
+                        "publisher.package_size_exceeded",
+                        size = bundle_size::format_size(measurement.total),
+                        max = bundle_size::format_size(max)
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the published file list against `validation.packageContents`
+    async fn check_package_contents(
+        &self,
+        registry_type: Option<RegistryType>,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.validation.as_ref())
+            .and_then(|v| v.package_contents.as_ref())
+            .filter(|p| p.enabled.unwrap_or(true))
+        else {
+            return Ok(());
+        };
+        let Some(registry_type @ (RegistryType::Npm | RegistryType::Crates)) = registry_type else {
+            return Ok(());
+        };
+
+        let validator = PackageContentValidator::from_config(config)
+            .map_err(|e| anyhow::anyhow!("validation.packageContents: {}", e))?;
+        self.reporter.info(&tr!("publisher.package_contents"));
+        let files = match package_contents::published_files(&self.project_path, registry_type).await
+        {
+            Ok(files) => files,
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.package_files_failed", error = e));
+                warnings.push(format!("package contents: {}", e));
+                return Ok(());
+            }
+        };
+
+        let report = validator.validate(&files);
+        if report.is_clean() {
+            self.reporter.success(&tr!(
+                "publisher.package_files_checked",
+                count = report.files
+            ));
+            return Ok(());
+        }
+        let problems = report.problems();
+        if config.level.as_deref() == Some("warning") {
+            for problem in problems {
+                self.reporter.warning(&format!("  ⚠️  {}", problem));
+                warnings.push(format!("package contents: {}", problem));
+            }
+            self.reporter.info("");
+            return Ok(());
+        }
+        for problem in &problems {
+            self.reporter.error(&format!("  ❌ {}", problem));
+        }
+        Err(anyhow::anyhow!(
+            "{}",
+            tr!(
+                "publisher.package_contents_failed",
+                missing = report.missing.len(),
+                forbidden = report.forbidden.len()
+            )
+        ))
+    }
+
+    /// Measure the npm package and compare it with the previous release
+    /// (`registries.npm.bundleSize`)
+    ///
+    /// Exceeded limits fail publishing with `level: "error"` and are warnings
+    /// otherwise. The measured size is recorded in analytics via the report.
+    async fn check_bundle_size(
+        &self,
+        registry_type: Option<RegistryType>,
+        registry_name: &str,
+        package_name: &str,
+        package_version: &str,
+        warnings: &mut Vec<String>,
+    ) -> Result<Option<PackageSize>, anyhow::Error> {
+        if registry_type != Some(RegistryType::Npm) {
+            return Ok(None);
+        }
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.registries.npm.as_ref())
+            .and_then(|n| n.bundle_size.as_ref())
+            .filter(|b| b.enabled.unwrap_or(true))
+        else {
+            return Ok(None);
+        };
+
+        self.reporter.info(&tr!("publisher.bundle_size"));
+        let size = match bundle_size::measure(&self.project_path).await {
+            Ok(size) => size,
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.bundle_size_failed", error = e));
+                warnings.push(format!("bundle size: {}", e));
+                return Ok(None);
+            }
+        };
+
+        let mut analytics = PublishAnalytics::new(&self.project_path);
+        let _ = analytics.initialize().await;
+        let previous =
+            match analytics.previous_package_size(registry_name, package_name, package_version) {
+                Some(previous) => Some(previous),
+                None => bundle_size::published_size(&self.project_path, package_name).await,
+            };
+
+        let mut line = tr!(
+            "publisher.bundle_size_measured",
+            packed = bundle_size::format_size(size.packed),
+            unpacked = bundle_size::format_size(size.unpacked),
+            files = size.files
+        );
+        if let Some(ref previous) = previous {
+            let (before, after) = if previous.packed > 0 {
+                (previous.packed, size.packed)
+            } else {
+                (previous.unpacked, size.unpacked)
+            };
+            if let Some(growth) = bundle_size::growth_percent(before, after) {
+                line.push_str(&tr!(
+                    "publisher.bundle_size_growth",
+                    growth = format!("{:+.1}", growth)
+                ));
+            }
+        }
+        self.reporter.info(&line);
+
+        let violations = BundleSizeGate::from_config(config).check(&size, previous.as_ref());
+        if config.level.as_deref() == Some("error") && !violations.is_empty() {
+            for violation in &violations {
+                self.reporter.error(&format!("  ❌ {}", violation));
+            }
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.bundle_size_exceeded", count = violations.len())
+            ));
+        }
+        for violation in violations {
+            self.reporter.warning(&format!("  ⚠️  {}", violation));
+            warnings.push(format!("bundle size: {}", violation));
+        }
+        self.reporter.info("");
+
+        Ok(Some(size))
+    }
+
+    async fn check_dependency_confusion(
+        &self,
+        registry_type: Option<RegistryType>,
+        warnings: &mut Vec<String>,
+    ) -> Result<Option<ConfusionReport>, anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.dependency_confusion.as_ref())
+            .filter(|d| d.enabled.unwrap_or(false))
+        else {
+            return Ok(None);
+        };
+        let Some(registry_type) = registry_type else {
+            return Ok(None);
+        };
+
+        let threshold = match config.fail_on.as_deref() {
+            Some(fail_on) => fail_on.parse().map_err(anyhow::Error::msg)?,
+            None => IssueSeverity::High,
+        };
+        let internal = InternalNames::new(
+            config.internal_packages.as_deref().unwrap_or_default(),
+            config.internal_scopes.as_deref().unwrap_or_default(),
+        );
+
+        self.reporter.info(&tr!("publisher.dependency_confusion"));
+        let report = DependencyConfusionChecker::new(&self.project_path, internal)
+            .check(registry_type, registry_type == RegistryType::GitHubPackages)
+            .await;
+
+        for error in &report.errors {
+            self.reporter.warning(&tr!(
+                "publisher.dependency_confusion_not_run",
+                error = error
+            ));
+            warnings.push(format!("dependency confusion: {}", error));
+        }
+
+        let blocking: Vec<&ConfusionFinding> = report.blocking(threshold).collect();
+        for finding in &report.findings {
+            if blocking.contains(&finding) {
+                self.reporter.error(&format!("  ❌ {}", finding.message));
+            } else {
+                self.reporter.warning(&format!("  ⚠️  {}", finding.message));
+                warnings.push(format!("dependency confusion: {}", finding.message));
+            }
+        }
+        if !blocking.is_empty() {
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!(
+                    "publisher.dependency_confusion_failed",
+                    count = blocking.len()
+                )
+            ));
+        }
+
+        if report.findings.is_empty() {
+            self.reporter.success(&tr!(
+                "publisher.internal_names_checked",
+                count = report.checked.len()
+            ));
+        } else {
+            self.reporter.info("");
+        }
+
+        Ok(Some(report))
+    }
+
+    /// Unpack the release artifacts and check what is really published
+    /// (`security.artifactInspection`)
+    ///
+    /// Secrets and forbidden files abort publishing unless `level` is
+    /// "warning". Results are kept with the release artifacts.
+    async fn inspect_artifacts(
+        &self,
+        store: &ReleaseArtifactStore,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<ArtifactInspection>, anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.artifact_inspection.as_ref())
+            .filter(|a| a.enabled.unwrap_or(false))
+        else {
+            return Ok(Vec::new());
+        };
+        let blocking = config.level.as_deref() != Some("warning");
+
+        self.reporter.info(&tr!("publisher.artifact_inspection"));
+        let inspector = ArtifactInspector::new(config.forbidden_files.as_deref())?;
+        let artifacts = release_artifacts(
+            &self.project_path,
+            &store.release_dir(version),
+            config.artifacts.as_deref(),
+            registry,
+            package_name,
+            version,
+            &self.command_limits()?,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", tr!("publisher.no_artifacts_to_inspect", error = e)))?;
+
+        let mut inspections = Vec::new();
+        for artifact in artifacts {
+            let inspection = inspector.inspect(&artifact).await?;
+            if inspection.is_clean() {
+                self.reporter.success(&tr!(
+                    "publisher.artifact_clean",
+                    artifact = inspection.artifact,
+                    files = inspection.files
+                ));
+            }
+            let mut problems: Vec<String> = inspection
+                .forbidden_files
+                .iter()
+                .map(|file| {
+                    tr!(
+                        "publisher.artifact_forbidden_file",
+                        artifact = inspection.artifact,
+                        file = file
+                    )
+                })
+                .collect();
+            problems.extend(inspection.secrets.iter().map(|finding| {
+                tr!(
+                    "publisher.artifact_secret",
+                    artifact = inspection.artifact,
+                    secret_type = finding.secret_type,
+                    file = finding.file.display(),
+                    line = finding.line
+                )
+            }));
+            for problem in problems {
+                if blocking {
+                    self.reporter.error(&format!("  ❌ {}", problem));
+                } else {
+                    self.reporter.warning(&format!("  ⚠️  {}", problem));
+                    warnings.push(format!("artifact inspection: {}", problem));
+                }
+            }
+            inspections.push(inspection);
+        }
+
+        if let Err(e) = store
+            .save_json(version, "artifact-inspection", &inspections)
+            .await
+        {
+            self.reporter
+                .warning(&tr!("publisher.save_artifact_inspection_failed", error = e));
+        }
 
-        if should_confirm {
-            self.state_machine
-                .transition(PublishState::Confirming, None)
-                .await?;
+        let dirty = inspections.iter().filter(|i| !i.is_clean()).count();
+        if blocking && dirty > 0 {
+            return Err(anyhow::anyhow!(
+                "{}",
+                tr!("publisher.artifact_inspection_failed", count = dirty)
+            ));
+        }
+        self.reporter.info("");
 
-            println!("📋 Pre-publish checklist:");
-            println!("  ✅ Registry: {}", registry_name);
-            println!("  ✅ Version: {}", package_version);
-            println!("  ✅ Validation: passed");
-            println!("  ✅ Dry-run: passed");
-            if !warnings.is_empty() {
-                println!("  ⚠️  Warnings: {}", warnings.len());
+        Ok(inspections)
+    }
+
+    /// Scan artifacts for malware (if `security.malwareScanning.enabled`)
+    ///
+    /// Detections always abort publishing; scanner errors do unless
+    /// `failOnError` is false. Verdicts are kept with the release artifacts.
+    async fn scan_artifacts(
+        &self,
+        store: &ReleaseArtifactStore,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<ArtifactScanResult>, anyhow::Error> {
+        let Some(config) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.security.as_ref())
+            .and_then(|s| s.malware_scanning.as_ref())
+            .filter(|m| m.enabled.unwrap_or(false))
+        else {
+            return Ok(Vec::new());
+        };
+        let fail_on_error = config.fail_on_error != Some(false);
+
+        self.reporter.info(&tr!("publisher.malware_scan"));
+        let scanner = MalwareScanner::new(ScannerBackend::from_config(config)?);
+        let artifacts = match release_artifacts(
+            &self.project_path,
+            &store.release_dir(version),
+            config.artifacts.as_deref(),
+            registry,
+            package_name,
+            version,
+            &self.command_limits()?,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", tr!("publisher.malware_artifacts_hint", error = e)))
+        {
+            Ok(artifacts) => artifacts,
+            Err(e) if fail_on_error => {
+                self.reporter
+                    .error(&tr!("publisher.no_artifacts_to_scan", error = e));
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!("publisher.malware_scan_failed", error = e)
+                ));
             }
-            println!();
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.no_artifacts_to_scan_warning", error = e));
+                warnings.push(format!("malware scan: {}", e));
+                return Ok(Vec::new());
+            }
+        };
 
-            if !self.confirm("Proceed with publishing?").await? {
-                println!("Publishing cancelled by user");
-                self.state_machine
-                    .transition(PublishState::Failed, None)
-                    .await?;
-                return Ok(PublishReport {
-                    success: false,
-                    registry: registry_name,
-                    package_name,
-                    version: package_version,
-                    published_at: None,
-                    verification_url: None,
-                    errors: vec!["User cancelled".to_string()],
-                    warnings,
-                    duration: start_time.elapsed().as_millis() as u64,
-                    state: "FAILED".to_string(),
-                });
+        let mut results = Vec::new();
+        for artifact in artifacts {
+            let result = scanner.scan(&artifact).await?;
+            match result.verdict {
+                ScanVerdict::Clean => self.reporter.success(&tr!(
+                    "publisher.artifact_scan_clean",
+                    artifact = result.artifact
+                )),
+                ScanVerdict::Infected => self.reporter.error(&tr!(
+                    "publisher.artifact_infected",
+                    artifact = result.artifact,
+                    threat = result
+                        .threat
+                        .clone()
+                        .unwrap_or_else(|| tr!("publisher.unknown_threat"))
+                )),
+                ScanVerdict::Error => self.reporter.warning(&tr!(
+                    "publisher.artifact_not_scanned",
+                    artifact = result.artifact,
+                    detail = result
+                        .detail
+                        .clone()
+                        .unwrap_or_else(|| tr!("publisher.scanner_error"))
+                )),
             }
+            results.push(result);
         }
 
-        // Return if hooks-only mode
-        if effective_options.hooks_only {
-            println!("🪝 Hooks-only mode: skipping actual publishing\n");
-            return Ok(PublishReport {
-                success: true,
-                registry: registry_name,
-                package_name,
-                version: package_version,
-                published_at: None,
-                verification_url: None,
-                errors,
-                warnings,
-                duration: start_time.elapsed().as_millis() as u64,
-                state: "DRY_RUN".to_string(),
-            });
+        if let Err(e) = store.save_json(version, "malware-scan", &results).await {
+            self.reporter
+                .warning(&tr!("publisher.save_malware_scan_failed", error = e));
         }
 
-        // 7. Publish
-        self.state_machine
-            .transition(PublishState::Publishing, None)
-            .await?;
-        println!("📤 Publishing...");
-
-        let publish_result = plugin
-            .publish(Some(effective_options.to_plugin_options()))
-            .await?;
-
-        if !publish_result.success {
-            let error_msg = publish_result
-                .error
-                .unwrap_or_else(|| "Publishing failed".to_string());
+        let infected = results
+            .iter()
+            .filter(|r| r.verdict == ScanVerdict::Infected)
+            .count();
+        let errors = results
+            .iter()
+            .filter(|r| r.verdict == ScanVerdict::Error)
+            .count();
+        if infected > 0 {
             return Err(anyhow::anyhow!(
-                "Publishing failed for {}: {}",
-                registry_name,
-                error_msg
+                "{}",
+                tr!("publisher.malware_detected", count = infected)
+            ));
+        }
+        if errors > 0 {
+            if fail_on_error {
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    tr!("publisher.malware_scan_incomplete", count = errors)
+                ));
+            }
+            warnings.push(format!(
+                "malware scan: {}",
+                tr!("publisher.artifacts_not_scanned", count = errors)
             ));
         }
 
-        println!("  ✅ Published successfully\n");
+        self.reporter.info("");
+        Ok(results)
+    }
 
-        // 8. Verify (if enabled)
-        let should_verify = self
+    /// Sign artifacts with cosign (if `signing.enabled`)
+    ///
+    /// Bundles are stored with the release artifacts. Failures abort
+    /// publishing unless `signing.required` is false.
+    async fn sign_artifacts(
+        &self,
+        store: &ReleaseArtifactStore,
+        registry: &str,
+        package_name: &str,
+        version: &str,
+        sbom: Option<&SbomReference>,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<ArtifactSignature>, anyhow::Error> {
+        let Some(config) = self.signing_config() else {
+            return Ok(Vec::new());
+        };
+
+        let signer = ArtifactSigner::new(&self.project_path, store.release_dir(version))
+            .with_key(config.key.clone())
+            .with_command_limits(self.command_limits()?);
+        self.reporter
+            .info(&tr!("publisher.signing", mode = signer.mode()));
+
+        let sbom = sbom.filter(|_| config.attest_sbom != Some(false));
+        let result = async {
+            let artifacts = signer
+                .collect_artifacts(config.artifacts.as_deref(), registry, package_name, version)
+                .await?;
+            let mut signatures = Vec::new();
+            for artifact in artifacts {
+                let signature = signer.sign(&artifact, sbom).await?;
+                self.reporter.success(&format!(
+                    "  ✅ {} (sha256 {})",
+                    signature.artifact, signature.sha256
+                ));
+                signatures.push(signature);
+            }
+            Ok::<_, anyhow::Error>(signatures)
+        }
+        .await;
+
+        match result {
+            Ok(signatures) => {
+                self.reporter.info("");
+                Ok(signatures)
+            }
+            Err(e) if config.required == Some(false) => {
+                self.reporter
+                    .warning(&tr!("publisher.signing_failed_warning", error = e));
+                warnings.push(format!("signing: {}", e));
+                Ok(Vec::new())
+            }
+            Err(e) => {
+                self.reporter
+                    .error(&tr!("publisher.signing_failed_error", error = e));
+                Err(anyhow::anyhow!(
+                    "{}",
+                    tr!("publisher.signing_failed", error = e)
+                ))
+            }
+        }
+    }
+
+    /// Create the git tag and GitHub Release (if `release.enabled`)
+    ///
+    /// Failures become warnings. Returns the GitHub Release URL.
+    async fn create_release(
+        &self,
+        package_name: &str,
+        version: &str,
+        assets: Vec<PathBuf>,
+        warnings: &mut Vec<String>,
+    ) -> Option<String> {
+        let config = self.config.as_ref()?;
+        let mut manager =
+            ReleaseManager::new(&self.project_path, config.release.clone()?).with_assets(assets);
+        if !manager.is_enabled() {
+            return None;
+        }
+        if let Some(ref changelog) = config.changelog {
+            manager = manager.with_changelog(changelog);
+        }
+
+        self.reporter.info(&tr!(
+            "publisher.creating_release",
+            tag = manager.tag_name(version)
+        ));
+        match manager.release(package_name, version).await {
+            Ok(outcome) => {
+                if outcome.tag_created {
+                    self.reporter
+                        .success(&tr!("publisher.tag_created", tag = outcome.tag));
+                } else {
+                    self.reporter
+                        .success(&tr!("publisher.tag_exists", tag = outcome.tag));
+                }
+                if outcome.tag_pushed {
+                    self.reporter.success(&tr!("publisher.tag_pushed"));
+                }
+                if let Some(ref url) = outcome.release_url {
+                    self.reporter
+                        .success(&format!("  ✅ GitHub Release: {}", url));
+                }
+                for asset in &outcome.assets {
+                    self.reporter.info(&format!("    📎 {}", asset));
+                }
+                for warning in &outcome.warnings {
+                    self.reporter.warning(&format!("  ⚠️  {}", warning));
+                }
+                self.reporter.info("");
+                warnings.extend(outcome.warnings);
+                outcome.release_url
+            }
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.release_failed", error = e));
+                warnings.push(format!("Release: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Update the changelog before publishing (if `changelog.enabled`)
+    ///
+    /// With `preview` (publish `--dry-run`) the section is only printed.
+    /// Failures become warnings.
+    async fn update_changelog(&self, version: &str, preview: bool, warnings: &mut Vec<String>) {
+        let Some(config) = self
             .config
             .as_ref()
-            .and_then(|c| c.publish.as_ref())
-            .and_then(|p| p.verify)
-            .unwrap_or(true);
+            .and_then(|c| c.changelog.as_ref())
+            .filter(|c| c.enabled.unwrap_or(false))
+        else {
+            return;
+        };
 
-        let mut verification_url = None;
-        if should_verify {
-            self.state_machine
-                .transition(PublishState::Verifying, None)
-                .await?;
-            println!("🔍 Verifying publication...");
+        self.reporter.info(&tr!("publisher.generating_changelog"));
+        let generator = ChangelogGenerator::new(&self.project_path).with_config(config);
+        match generator.generate(version, preview).await {
+            Ok(result) if preview => {
+                self.reporter.info(&tr!(
+                    "publisher.changelog_preview",
+                    path = result.path.display()
+                ));
+                self.reporter.info(&result.section);
+            }
+            Ok(result) => {
+                let since = result
+                    .since_tag
+                    .clone()
+                    .unwrap_or_else(|| tr!("publisher.first_commit"));
+                self.reporter.success(&tr!(
+                    "publisher.changelog_updated",
+                    path = result.path.display(),
+                    count = result.commit_count,
+                    since = since
+                ));
+            }
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.changelog_failed", error = e));
+                warnings.push(format!("Changelog: {}", e));
+            }
+        }
+    }
 
-            match plugin.verify().await {
-                Ok(verify_result) => {
-                    if verify_result.verified {
-                        println!("  ✅ Verification successful");
-                        if let Some(ref url) = verify_result.url {
-                            println!("    URL: {}\n", url);
-                            verification_url = Some(url.clone());
-                        }
-                    } else {
-                        let error_msg = verify_result
-                            .error
-                            .unwrap_or_else(|| "Unknown error".to_string());
-                        warnings.push(format!("Verification failed: {}", error_msg));
-                        println!("  ⚠️  Verification failed (but publishing succeeded)");
-                        println!("    {}", error_msg);
-                    }
+    /// Deprecate prereleases superseded by a stable release (`postRelease.cleanupPrereleases`)
+    ///
+    /// With `list_only` (publish `--dry-run`) or `dryRun: true` the candidates
+    /// are only listed. Failures become warnings. Returns the versions that
+    /// were actually deprecated.
+    async fn cleanup_prereleases(
+        &self,
+        plugin: &dyn RegistryPlugin,
+        version: &str,
+        list_only: bool,
+        warnings: &mut Vec<String>,
+    ) -> Vec<String> {
+        let Some(cleanup) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.post_release.as_ref())
+            .and_then(|p| p.cleanup_prereleases.as_ref())
+            .filter(|c| c.enabled.unwrap_or(false))
+        else {
+            return Vec::new();
+        };
+        if VersionValidator::new().is_prerelease(version) {
+            return Vec::new();
+        }
+
+        let candidates = match plugin.superseded_prereleases(version).await {
+            Ok(candidates) => candidates,
+            Err(e) => {
+                self.reporter
+                    .warning(&tr!("publisher.prereleases_failed", error = e));
+                warnings.push(format!("Prerelease cleanup: {}", e));
+                return Vec::new();
+            }
+        };
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        if list_only || cleanup.dry_run.unwrap_or(false) {
+            self.reporter.info(&tr!("publisher.prereleases_preview"));
+            for candidate in &candidates {
+                self.reporter.info(&format!("  - {}", candidate));
+            }
+            self.reporter.info("");
+            return Vec::new();
+        }
+
+        let message = cleanup
+            .message
+            .as_deref()
+            .unwrap_or("Superseded by {version}")
+            .replace("{version}", version);
+
+        self.reporter
+            .info(&tr!("publisher.deprecating_prereleases"));
+        let mut deprecated = Vec::new();
+        for candidate in candidates {
+            match plugin.deprecate(&candidate, &message).await {
+                Ok(result) if result.success => {
+                    self.reporter.success(&format!("  ✅ {}", candidate));
+                    deprecated.push(candidate);
+                }
+                Ok(result) => {
+                    let error = result.error.unwrap_or(result.message);
+                    self.reporter
+                        .warning(&format!("  ⚠️  {}: {}", candidate, error));
+                    warnings.push(tr!(
+                        "publisher.deprecate_failed",
+                        version = candidate,
+                        error = error
+                    ));
                 }
                 Err(e) => {
-                    warnings.push(format!("Verification error: {}", e));
-                    println!("  ⚠️  Verification error (but publishing succeeded)");
+                    self.reporter
+                        .warning(&format!("  ⚠️  {}: {}", candidate, e));
+                    warnings.push(tr!(
+                        "publisher.deprecate_failed",
+                        version = candidate,
+                        error = e
+                    ));
                 }
             }
         }
+        self.reporter.info("");
 
-        // Success
-        self.state_machine
-            .transition(PublishState::Success, None)
-            .await?;
+        deprecated
+    }
 
-        Ok(PublishReport {
-            success: true,
-            registry: registry_name,
-            package_name,
-            version: package_version,
-            published_at: Some(chrono::Utc::now()),
-            verification_url,
-            errors,
-            warnings,
-            duration: start_time.elapsed().as_millis() as u64,
-            state: "SUCCESS".to_string(),
-        })
+    /// Save publish report, state history and config snapshot for auditing
+    async fn save_release_artifacts(&self, store: &ReleaseArtifactStore, report: &PublishReport) {
+        let version = &report.version;
+        let result = async {
+            store
+                .save_json(
+                    version,
+                    &format!("publish-report-{}", report.registry),
+                    report,
+                )
+                .await?;
+            store
+                .save_json(
+                    version,
+                    &format!("state-{}", report.registry),
+                    &self.state_machine.get_state_data(),
+                )
+                .await?;
+            if let Some(ref config) = self.config {
+                store.save_config_snapshot(version, config).await?;
+            }
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            self.reporter
+                .warning(&tr!("publisher.save_release_artifacts_failed", error = e));
+        }
     }
 
     /// Merge CLI options with configuration (CLI takes priority)
@@ -512,28 +3766,94 @@ impl PackagePublisher {
             options.registry = Some(default_reg.clone());
         }
 
+        // Publish mode from config (CLI flags are already part of it)
+        if let Some(publish) = &config.publish {
+            options.dry_run |= publish.dry_run == Some(DryRunMode::Always);
+            options.non_interactive |= publish.interactive == Some(false);
+        }
+
         options
     }
 
-    /// Prompt user for confirmation
-    async fn confirm(&self, message: &str) -> Result<bool, anyhow::Error> {
-        print!("{} (yes/no): ", message);
-        io::stdout().flush().await?;
+    /// Fill npm tag, access and OTP command from `registries.npm` when not
+    /// given
+    fn apply_registry_options(&self, registry_name: &str, options: &mut PublishOptions) {
+        let Some(npm) = self
+            .config
+            .as_ref()
+            .and_then(|c| c.registries.npm.as_ref())
+            .filter(|_| registry_name == "npm")
+        else {
+            return;
+        };
+        if options.tag.is_none() {
+            options.tag = npm.tag.clone();
+        }
+        if options.access.is_none() {
+            options.access = npm.access.as_ref().map(|access| {
+                match access {
+                    NPMAccess::Public => "public",
+                    NPMAccess::Restricted => "restricted",
+                }
+                .to_string()
+            });
+        }
+        if options.otp_command.is_none() {
+            options.otp_command = npm.otp.as_ref().and_then(|otp| otp.command.clone());
+        }
+    }
 
-        let stdin = io::stdin();
-        let mut reader = BufReader::new(stdin);
-        let mut answer = String::new();
+    /// `registries.npm.otp` of the config
+    fn otp_config(&self) -> Option<&OTPConfig> {
+        self.config
+            .as_ref()
+            .and_then(|c| c.registries.npm.as_ref())
+            .and_then(|npm| npm.otp.as_ref())
+    }
 
-        reader.read_line(&mut answer).await?;
+    /// Fresh one-time password from the OTP command, or asked for unless
+    /// the publish is non-interactive or `otp.prompt` is "never" (`None`
+    /// when neither is available)
+    async fn fetch_otp(
+        &self,
+        registry_name: &str,
+        options: &PublishOptions,
+    ) -> anyhow::Result<Option<String>> {
+        if let Some(command) = &options.otp_command {
+            self.reporter.info(&format!(
+                "🔑 Getting a one-time password from `{}`",
+                command
+            ));
+            return otp::run_otp_command(command, &self.project_path)
+                .await
+                .map(Some);
+        }
+        let never = self
+            .otp_config()
+            .and_then(|otp| otp.prompt.as_deref())
+            .is_some_and(|prompt| prompt == "never");
+        if options.non_interactive || never {
+            return Ok(None);
+        }
+        let message = tr!("otp.prompt", registry = registry_name);
+        match self.prompt.ask_otp(registry_name, &message).await? {
+            Some(answer) => otp::parse_otp(&answer).map(Some),
+            None => Ok(None),
+        }
+    }
 
-        let answer = answer.trim().to_lowercase();
-        Ok(answer == "yes" || answer == "y")
+    /// Prompt user for confirmation
+    async fn confirm(&self, message: &str) -> Result<bool, anyhow::Error> {
+        self.prompt
+            .confirm(self.event_registry.as_deref(), message)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::security::redaction::REDACTED;
 
     #[test]
     fn test_new_package_publisher() {
@@ -541,11 +3861,371 @@ mod tests {
         assert_eq!(publisher.project_path, PathBuf::from("."));
     }
 
+    #[test]
+    fn test_retry_options_per_registry() {
+        let config: RetryConfig = serde_yaml::from_str(
+            "maxAttempts: 4\ninitialDelay: 2s\nregistries:\n  npm:\n    maxAttempts: 6\n    jitter: false\n",
+        )
+        .unwrap();
+
+        let npm = retry_options(Some(&config), "npm").unwrap();
+        assert_eq!(npm.max_attempts, 6);
+        assert_eq!(npm.initial_delay, Duration::from_secs(2));
+        assert!(!npm.jitter);
+
+        let crates = retry_options(Some(&config), "crates.io").unwrap();
+        assert_eq!(crates.max_attempts, 4);
+        assert!(crates.jitter);
+        assert_eq!(retry_options(None, "npm").unwrap().max_attempts, 3);
+    }
+
+    #[test]
+    fn test_command_timeout() {
+        let config: PublishOptionsConfig = serde_yaml::from_str("commandTimeout: 10m\n").unwrap();
+        assert_eq!(
+            command_timeout(Some(&config)).unwrap(),
+            Duration::from_secs(600)
+        );
+        assert_eq!(
+            command_timeout(None).unwrap(),
+            DEFAULT_REGISTRY_COMMAND_TIMEOUT
+        );
+        let invalid: PublishOptionsConfig = serde_yaml::from_str("commandTimeout: soon\n").unwrap();
+        assert!(command_timeout(Some(&invalid)).is_err());
+    }
+
+    #[test]
+    fn test_cli_options_override_config_layers() {
+        assert_eq!(
+            PublishOptions::default().to_config_overrides().unwrap(),
+            None
+        );
+
+        let options = PublishOptions {
+            registry: Some("npm".to_string()),
+            tag: Some("next".to_string()),
+            dry_run: true,
+            non_interactive: true,
+            ..Default::default()
+        };
+        let overrides = options.to_config_overrides().unwrap().unwrap();
+        let project: PublishConfig = serde_yaml::from_str(
+            "version: \"1.0\"\nproject:\n  defaultRegistry: crates.io\nregistries:\n  npm:\n    tag: latest\n    access: restricted\npublish:\n  confirm: false\n",
+        )
+        .unwrap();
+        let config =
+            ConfigLoader::merge_configs(vec![PublishConfig::default(), project, overrides]);
+
+        assert_eq!(
+            config.project.unwrap().default_registry.as_deref(),
+            Some("npm")
+        );
+        let npm = config.registries.npm.unwrap();
+        assert_eq!(npm.tag.as_deref(), Some("next"));
+        assert_eq!(npm.access, Some(NPMAccess::Restricted));
+        let publish = config.publish.unwrap();
+        assert_eq!(publish.dry_run, Some(DryRunMode::Always));
+        assert_eq!(publish.interactive, Some(false));
+        assert_eq!(publish.confirm, Some(false));
+
+        let invalid = PublishOptions {
+            access: Some("private".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.to_config_overrides().is_err());
+    }
+
     #[test]
     fn test_publish_options_default() {
         let options = PublishOptions::default();
         assert_eq!(options.registry, None);
-        assert_eq!(options.dry_run, false);
-        assert_eq!(options.non_interactive, false);
+        assert!(!options.dry_run);
+        assert!(!options.non_interactive);
+    }
+
+    #[tokio::test]
+    async fn test_with_reporter_captures_output() {
+        use crate::core::reporter::BufferedReporter;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 1.0.0\n",
+        )
+        .unwrap();
+
+        let reporter = Arc::new(BufferedReporter::new());
+        let mut publisher = PackagePublisher::new(temp_dir.path()).with_reporter(reporter.clone());
+        let _ = publisher
+            .publish(PublishOptions {
+                dry_run: true,
+                non_interactive: true,
+                skip_notifications: true,
+                ..Default::default()
+            })
+            .await;
+
+        let entries = reporter.entries();
+        assert!(
+            entries
+                .iter()
+                .any(|e| e.message.contains("Detected registries"))
+        );
+        assert!(
+            entries
+                .iter()
+                .any(|e| e.message.contains("Registry selected: cran"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_reuses_completed_stages() {
+        use crate::core::reporter::BufferedReporter;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 1.0.0\nTitle: Tools for Things.\nDescription: This package does things.\nLicense: MIT\nAuthors@R: person(\"A\", \"B\", role = c(\"aut\", \"cre\"))\n",
+        )
+        .unwrap();
+        let options = PublishOptions {
+            dry_run: true,
+            non_interactive: true,
+            skip_notifications: true,
+            ..Default::default()
+        };
+        PackagePublisher::new(temp_dir.path())
+            .with_reporter(Arc::new(BufferedReporter::new()))
+            .publish(options.clone())
+            .await
+            .unwrap();
+
+        let mut state = PublishStateMachine::new(temp_dir.path());
+        assert!(state.restore().await.unwrap());
+        assert!(state.artifacts().is_completed(PublishState::Validating));
+        assert_eq!(state.get_state_data().registry.as_deref(), Some("cran"));
+
+        let reporter = Arc::new(BufferedReporter::new());
+        let report = PackagePublisher::new(temp_dir.path())
+            .with_reporter(reporter.clone())
+            .publish(PublishOptions {
+                resume: true,
+                ..options.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(report.version, "1.0.0");
+        let entries = reporter.entries();
+        assert!(entries.iter().any(|e| e.message.contains("♻️  Resuming")));
+        assert!(
+            !entries
+                .iter()
+                .any(|e| e.message.contains("Security scan..."))
+        );
+
+        let error = PackagePublisher::new(temp_dir.path())
+            .with_reporter(Arc::new(BufferedReporter::new()))
+            .publish(PublishOptions {
+                resume: true,
+                registry: Some("npm".to_string()),
+                ..options.clone()
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("was to cran"));
+
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 1.0.1\n",
+        )
+        .unwrap();
+        let error = PackagePublisher::new(temp_dir.path())
+            .with_reporter(Arc::new(BufferedReporter::new()))
+            .publish(PublishOptions {
+                resume: true,
+                ..options
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("changed since"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_output_redacted() {
+        use crate::core::reporter::BufferedReporter;
+
+        let token = format!("npm_{}", "a1B2".repeat(9));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "demo", "version": "1.0.0"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join(".publish-config.yaml"),
+            format!(
+                r#"
+version: "1.0"
+publish:
+  verify: false
+registries:
+  custom:
+    internal:
+      type: command
+      config: {{}}
+      publishCommand: echo uploaded with {}
+      allowedCommands: [echo]
+"#,
+                token
+            ),
+        )
+        .unwrap();
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        PackagePublisher::new(temp_dir.path())
+            .with_reporter(Arc::new(BufferedReporter::new()))
+            .with_event_sink(Arc::new(sender))
+            .publish(PublishOptions {
+                registry: Some("internal".to_string()),
+                non_interactive: true,
+                skip_notifications: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let mut outputs = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                PublishEvent::DryRunOutput { chunk, .. } => outputs.push(chunk),
+                PublishEvent::PublishOutput { line, .. } => outputs.push(line),
+                _ => {}
+            }
+        }
+        assert_eq!(outputs.len(), 2);
+        for output in outputs {
+            assert!(output.contains(REDACTED), "{}", output);
+            assert!(!output.contains(&token), "{}", output);
+        }
+
+        let state = std::fs::read_to_string(temp_dir.path().join(".publish-state.json")).unwrap();
+        assert!(state.contains("uploaded with"));
+        assert!(!state.contains(&token));
+    }
+
+    #[tokio::test]
+    async fn test_event_sink_receives_progress() {
+        use crate::core::reporter::BufferedReporter;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 1.0.0\n",
+        )
+        .unwrap();
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut publisher = PackagePublisher::new(temp_dir.path())
+            .with_reporter(Arc::new(BufferedReporter::new()))
+            .with_event_sink(Arc::new(sender));
+        let _ = publisher
+            .publish(PublishOptions {
+                dry_run: true,
+                non_interactive: true,
+                skip_notifications: true,
+                ..Default::default()
+            })
+            .await;
+
+        let mut events = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        assert!(events.contains(&PublishEvent::StateChanged {
+            registry: Some("cran".to_string()),
+            from: PublishState::Detecting,
+            to: PublishState::Validating,
+        }));
+        assert!(matches!(
+            events.last(),
+            Some(PublishEvent::Finished { registry: Some(registry), .. }) if registry == "cran"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_sbom_stores_document() {
+        use crate::core::config::{SbomConfig, SupplyChainConfig};
+        use crate::core::reporter::QuietReporter;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "app", "version": "1.0.0", "dependencies": {"left-pad": "1.3.0"}}"#,
+        )
+        .unwrap();
+
+        let mut publisher =
+            PackagePublisher::new(temp_dir.path()).with_reporter(Arc::new(QuietReporter));
+        publisher.config = Some(PublishConfig {
+            supply_chain: Some(SupplyChainConfig {
+                sbom: Some(SbomConfig {
+                    enabled: Some(true),
+                    format: Some("spdx".to_string()),
+                    attach_to_release: None,
+                }),
+                provenance: None,
+            }),
+            ..Default::default()
+        });
+
+        let store = ReleaseArtifactStore::new(temp_dir.path());
+        let mut warnings = Vec::new();
+        let sbom = publisher
+            .generate_sbom(&store, "app", "1.0.0", &mut warnings)
+            .await
+            .unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(sbom.format, SbomFormat::Spdx);
+        assert_eq!(sbom.components, 1);
+        assert!(sbom.path.ends_with("sbom.spdx.json"));
+        let stored = std::fs::read(&sbom.path).unwrap();
+        assert_eq!(
+            sbom.sha256,
+            hex::encode(<sha2::Sha256 as sha2::Digest>::digest(&stored))
+        );
+    }
+
+    #[test]
+    fn test_provenance_subjects_from_integrity() {
+        let integrity = PackageIntegrity {
+            shasum: Some("da39a3ee5e6b4b0d3255bfef95601890afd80709".to_string()),
+            integrity: Some("sha512-AAEC".to_string()),
+            ..Default::default()
+        };
+        let input = ProvenanceInput {
+            registry: "npm",
+            package_name: "@acme/app",
+            version: "1.0.0",
+            signatures: &[],
+            integrity: Some(&integrity),
+            sbom: None,
+            started_on: chrono::Utc::now(),
+        };
+
+        let subjects = provenance_subjects(&input);
+        assert_eq!(subjects.len(), 1);
+        assert_eq!(subjects[0].name, "pkg:npm/%40acme/app@1.0.0");
+        assert_eq!(subjects[0].digest["sha512"], "000102");
+        assert_eq!(
+            subjects[0].digest["sha1"],
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+
+        let no_digest = ProvenanceInput {
+            integrity: None,
+            ..input
+        };
+        assert!(provenance_subjects(&no_digest).is_empty());
     }
 }
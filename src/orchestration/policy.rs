@@ -0,0 +1,472 @@
+//! Publish Policies - gating rules evaluated before publishing
+//!
+//! Teams express rules in the `policies` config section, for example:
+//!
+//! ```yaml
+//! policies:
+//!   - name: no-friday-afternoon
+//!     rule: schedule
+//!     blocked: ["* 16-23 * * FRI"]
+//!     timezone: "+09:00"
+//!     message: No publishes on Friday after 16:00
+//!   - name: prereleases-off-main
+//!     rule: branch
+//!     versions: prerelease
+//!     excludeBranches: [main]
+//!   - name: no-critical-vulnerabilities
+//!     rule: vulnerabilities
+//!     failOn: critical
+//! ```
+//!
+//! Every policy is evaluated and all violations are reported together; a
+//! violated `block` policy aborts the publish, a `warn` policy only reports.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::core::config::PolicyConfig;
+//! use package_publisher::orchestration::policy::{PolicyContext, PolicyEngine};
+//!
+//! let engine = PolicyEngine::from_config(&[PolicyConfig {
+//!     name: "prereleases-off-main".to_string(),
+//!     rule: "branch".to_string(),
+//!     versions: Some("prerelease".to_string()),
+//!     exclude_branches: Some(vec!["main".to_string()]),
+//!     ..Default::default()
+//! }])
+//! .unwrap();
+//! let context = PolicyContext {
+//!     now: chrono::Utc::now(),
+//!     registry: "npm",
+//!     version: "2.0.0-beta.1",
+//!     branch: Some("main"),
+//!     audit: None,
+//! };
+//! assert!(engine.evaluate(&context).is_blocked());
+//! ```
+
+use crate::core::config::{PolicyConfig, ReleaseWindowsConfig};
+use crate::core::git::run_git;
+use crate::orchestration::release_window::ReleaseWindows;
+use crate::orchestration::workspace::glob_regex;
+use crate::security::audit::AuditReport;
+use crate::validation::dependency_checker::IssueSeverity;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// What a violated policy does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Enforcement {
+    Block,
+    Warn,
+}
+
+/// Versions a policy applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionScope {
+    All,
+    Prerelease,
+    Stable,
+}
+
+impl VersionScope {
+    fn includes(self, version: &str) -> bool {
+        let prerelease = semver::Version::parse(version.trim_start_matches('v'))
+            .map(|v| !v.pre.is_empty())
+            .unwrap_or(false);
+        match self {
+            VersionScope::All => true,
+            VersionScope::Prerelease => prerelease,
+            VersionScope::Stable => !prerelease,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Rule {
+    Schedule(ReleaseWindows),
+    Branch {
+        branches: Vec<Regex>,
+        exclude: Vec<Regex>,
+    },
+    Vulnerabilities {
+        threshold: IssueSeverity,
+        ignore: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+struct Policy {
+    name: String,
+    rule: Rule,
+    enforcement: Enforcement,
+    message: Option<String>,
+    registries: Option<Vec<String>>,
+    versions: VersionScope,
+}
+
+/// Facts a publish is judged on
+#[derive(Debug, Clone)]
+pub struct PolicyContext<'a> {
+    pub now: DateTime<Utc>,
+    pub registry: &'a str,
+    pub version: &'a str,
+    /// Current git branch (`None` if unknown)
+    pub branch: Option<&'a str>,
+    /// Dependency audit of the package (`None` if no audit ran)
+    pub audit: Option<&'a AuditReport>,
+}
+
+/// A violated policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub policy: String,
+    pub rule: String,
+    pub enforcement: Enforcement,
+    pub message: String,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.policy, self.rule, self.message)
+    }
+}
+
+/// Result of evaluating all policies
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    /// Policies that applied to the publish
+    pub evaluated: usize,
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyReport {
+    /// Violations of `block` policies
+    pub fn blocking(&self) -> impl Iterator<Item = &PolicyViolation> {
+        self.violations
+            .iter()
+            .filter(|v| v.enforcement == Enforcement::Block)
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.blocking().next().is_some()
+    }
+}
+
+/// Evaluates the configured policies
+#[derive(Debug, Default)]
+pub struct PolicyEngine {
+    policies: Vec<Policy>,
+}
+
+impl PolicyEngine {
+    pub fn from_config(configs: &[PolicyConfig]) -> anyhow::Result<Self> {
+        let policies = configs
+            .iter()
+            .map(|config| {
+                Policy::from_config(config)
+                    .map_err(|e| anyhow::anyhow!("policies \"{}\": {}", config.name, e))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { policies })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Whether a policy for `registry` needs a dependency audit
+    pub fn needs_audit(&self, registry: &str) -> bool {
+        self.policies
+            .iter()
+            .any(|p| matches!(p.rule, Rule::Vulnerabilities { .. }) && p.applies_to(registry))
+    }
+
+    pub fn evaluate(&self, context: &PolicyContext<'_>) -> PolicyReport {
+        let mut report = PolicyReport::default();
+        for policy in &self.policies {
+            if !policy.applies_to(context.registry) || !policy.versions.includes(context.version) {
+                continue;
+            }
+            report.evaluated += 1;
+            if let Some(reason) = policy.rule.check(context) {
+                report.violations.push(PolicyViolation {
+                    policy: policy.name.clone(),
+                    rule: policy.rule.name().to_string(),
+                    enforcement: policy.enforcement,
+                    message: policy.message.clone().unwrap_or(reason),
+                });
+            }
+        }
+        report
+    }
+}
+
+impl Policy {
+    fn from_config(config: &PolicyConfig) -> anyhow::Result<Self> {
+        let patterns = |globs: &Option<Vec<String>>| -> Vec<Regex> {
+            globs.iter().flatten().map(|g| glob_regex(g)).collect()
+        };
+        let rule = match config.rule.as_str() {
+            "schedule" => Rule::Schedule(ReleaseWindows::from_config(&ReleaseWindowsConfig {
+                timezone: config.timezone.clone(),
+                allowed: config.allowed.clone(),
+                blocked: config.blocked.clone(),
+                ..Default::default()
+            })?),
+            "branch" => Rule::Branch {
+                branches: patterns(&config.branches),
+                exclude: patterns(&config.exclude_branches),
+            },
+            "vulnerabilities" => Rule::Vulnerabilities {
+                threshold: match config.fail_on.as_deref() {
+                    Some(fail_on) => fail_on.parse().map_err(anyhow::Error::msg)?,
+                    None => IssueSeverity::Critical,
+                },
+                ignore: config.ignore.clone().unwrap_or_default(),
+            },
+            other => anyhow::bail!(
+                "unknown rule \"{}\" (expected schedule, branch or vulnerabilities)",
+                other
+            ),
+        };
+        let enforcement = match config.enforcement.as_deref() {
+            None | Some("block") => Enforcement::Block,
+            Some("warn") => Enforcement::Warn,
+            Some(other) => {
+                anyhow::bail!("unknown enforcement \"{}\" (expected block or warn)", other)
+            }
+        };
+        let versions = match config.versions.as_deref() {
+            None | Some("all") => VersionScope::All,
+            Some("prerelease") => VersionScope::Prerelease,
+            Some("stable") => VersionScope::Stable,
+            Some(other) => anyhow::bail!(
+                "unknown versions \"{}\" (expected prerelease, stable or all)",
+                other
+            ),
+        };
+
+        Ok(Self {
+            name: config.name.clone(),
+            rule,
+            enforcement,
+            message: config.message.clone(),
+            registries: config.registries.clone(),
+            versions,
+        })
+    }
+
+    fn applies_to(&self, registry: &str) -> bool {
+        self.registries
+            .as_ref()
+            .is_none_or(|registries| registries.iter().any(|r| r == registry))
+    }
+}
+
+impl Rule {
+    fn name(&self) -> &'static str {
+        match self {
+            Rule::Schedule(_) => "schedule",
+            Rule::Branch { .. } => "branch",
+            Rule::Vulnerabilities { .. } => "vulnerabilities",
+        }
+    }
+
+    /// Reason the publish violates the rule
+    fn check(&self, context: &PolicyContext<'_>) -> Option<String> {
+        match self {
+            Rule::Schedule(windows) => windows.check(context.now),
+            Rule::Branch { branches, exclude } => {
+                let Some(branch) = context.branch else {
+                    return (!branches.is_empty())
+                        .then(|| "the current branch could not be determined".to_string());
+                };
+                if exclude.iter().any(|r| r.is_match(branch)) {
+                    Some(format!("publishing from branch {} is not allowed", branch))
+                } else if !branches.is_empty() && !branches.iter().any(|r| r.is_match(branch)) {
+                    Some(format!(
+                        "branch {} is not one of the allowed branches",
+                        branch
+                    ))
+                } else {
+                    None
+                }
+            }
+            Rule::Vulnerabilities { threshold, ignore } => {
+                let Some(audit) = context.audit else {
+                    return Some("no dependency audit is available".to_string());
+                };
+                let blocking: Vec<String> = audit
+                    .blocking(*threshold, ignore)
+                    .map(|f| format!("{} {}", f.package, f.advisory_id))
+                    .collect();
+                (!blocking.is_empty()).then(|| {
+                    format!(
+                        "{} {:?}+ vulnerabilit{} ({})",
+                        blocking.len(),
+                        threshold,
+                        if blocking.len() == 1 { "y" } else { "ies" },
+                        blocking.join(", ")
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Current git branch of `project_path`
+///
+/// Detached checkouts in CI fall back to the branch reported by the CI
+/// (`GITHUB_HEAD_REF`, `GITHUB_REF_NAME`, `CI_COMMIT_REF_NAME`).
+pub async fn current_branch(project_path: &Path) -> Option<String> {
+    let branch = run_git(project_path, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .await
+        .ok()
+        .filter(|b| !b.is_empty() && b != "HEAD");
+
+    branch.or_else(|| {
+        ["GITHUB_HEAD_REF", "GITHUB_REF_NAME", "CI_COMMIT_REF_NAME"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::audit::{AuditBackend, AuditFinding};
+    use chrono::TimeZone;
+
+    fn policy(name: &str, rule: &str) -> PolicyConfig {
+        PolicyConfig {
+            name: name.to_string(),
+            rule: rule.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn context<'a>(version: &'a str, branch: Option<&'a str>) -> PolicyContext<'a> {
+        PolicyContext {
+            // Friday 2026-10-16 17:30 UTC
+            now: Utc.with_ymd_and_hms(2026, 10, 16, 17, 30, 0).unwrap(),
+            registry: "npm",
+            version,
+            branch,
+            audit: None,
+        }
+    }
+
+    #[test]
+    fn test_schedule_and_branch_rules() {
+        let engine = PolicyEngine::from_config(&[
+            PolicyConfig {
+                blocked: Some(vec!["* 16-23 * * FRI".to_string()]),
+                message: Some("No publishes on Friday after 16:00".to_string()),
+                ..policy("no-friday-afternoon", "schedule")
+            },
+            PolicyConfig {
+                versions: Some("prerelease".to_string()),
+                exclude_branches: Some(vec!["main".to_string()]),
+                ..policy("prereleases-off-main", "branch")
+            },
+            PolicyConfig {
+                versions: Some("stable".to_string()),
+                branches: Some(vec!["main".to_string(), "release/*".to_string()]),
+                enforcement: Some("warn".to_string()),
+                ..policy("stable-from-release-branches", "branch")
+            },
+        ])
+        .unwrap();
+
+        let report = engine.evaluate(&context("2.0.0-beta.1", Some("main")));
+        assert_eq!(report.evaluated, 2);
+        assert_eq!(
+            report
+                .violations
+                .iter()
+                .map(|v| v.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "No publishes on Friday after 16:00",
+                "publishing from branch main is not allowed"
+            ]
+        );
+
+        let report = engine.evaluate(&PolicyContext {
+            now: Utc.with_ymd_and_hms(2026, 10, 15, 10, 0, 0).unwrap(),
+            ..context("2.0.0", Some("feature/x"))
+        });
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].enforcement, Enforcement::Warn);
+        assert!(!report.is_blocked());
+        assert!(
+            engine
+                .evaluate(&PolicyContext {
+                    now: Utc.with_ymd_and_hms(2026, 10, 15, 10, 0, 0).unwrap(),
+                    ..context("2.0.0", Some("release/2.0"))
+                })
+                .violations
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_vulnerabilities_rule() {
+        let engine = PolicyEngine::from_config(&[PolicyConfig {
+            registries: Some(vec!["npm".to_string()]),
+            ..policy("no-critical", "vulnerabilities")
+        }])
+        .unwrap();
+        assert!(engine.needs_audit("npm"));
+        assert!(!engine.needs_audit("pypi"));
+
+        let finding = |severity| AuditFinding {
+            backend: AuditBackend::NpmAudit,
+            package: "lodash".to_string(),
+            version: None,
+            affected_range: None,
+            advisory_id: "GHSA-1".to_string(),
+            title: String::new(),
+            severity,
+            fixed_versions: Vec::new(),
+            remediation: String::new(),
+            url: None,
+        };
+        let mut audit = AuditReport {
+            backends: vec![AuditBackend::NpmAudit],
+            findings: vec![finding(IssueSeverity::High)],
+            errors: Vec::new(),
+        };
+        let report = engine.evaluate(&PolicyContext {
+            audit: Some(&audit),
+            ..context("1.0.0", None)
+        });
+        assert!(report.violations.is_empty());
+
+        audit.findings.push(finding(IssueSeverity::Critical));
+        let report = engine.evaluate(&PolicyContext {
+            audit: Some(&audit),
+            ..context("1.0.0", None)
+        });
+        assert!(report.is_blocked());
+        assert_eq!(
+            report.violations[0].to_string(),
+            "no-critical (vulnerabilities): 1 Critical+ vulnerability (lodash GHSA-1)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        let error = PolicyEngine::from_config(&[policy("typo", "shedule")]).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("policies \"typo\": unknown rule")
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Prometheus metrics of the publish history
+//!
+//! Renders the analytics records in the Prometheus text exposition format:
+//! publish and rollback counters by registry, package and outcome, a
+//! duration histogram per registry, and the time and result of the last
+//! publish of each package, so monitoring can alert on failed releases. The
+//! metrics are written to a node_exporter textfile (`metrics.textfile`)
+//! after every publish, or printed / served by `package-publisher stats
+//! metrics`.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::prometheus;
+//!
+//! let metrics = prometheus::render(&[]);
+//! assert!(metrics.contains("# TYPE package_publisher_publishes_total counter"));
+//! ```
+
+use crate::core::config::MetricsConfig;
+use crate::orchestration::analytics::{AnalyticsRecord, ROLLBACK_STATE};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Upper bounds of the duration histogram buckets (seconds)
+const DURATION_BUCKETS: [f64; 9] = [1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0];
+
+/// Content type of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Outcome label of a record
+fn outcome(record: &AnalyticsRecord) -> &'static str {
+    match (record.success, record.metadata.state.as_str()) {
+        (_, "DRY_RUN") => "dry_run",
+        (true, _) => "success",
+        (false, _) => "failure",
+    }
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Duration histogram of one registry
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; DURATION_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Metrics of `records` in the text exposition format
+pub fn render(records: &[AnalyticsRecord]) -> String {
+    let mut publishes: BTreeMap<(&str, &str, &str), u64> = BTreeMap::new();
+    let mut rollbacks: BTreeMap<(&str, &str, &str), u64> = BTreeMap::new();
+    let mut durations: BTreeMap<&str, Histogram> = BTreeMap::new();
+    let mut last: BTreeMap<(&str, &str), &AnalyticsRecord> = BTreeMap::new();
+
+    for record in records {
+        let key = (
+            record.registry.as_str(),
+            record.package_name.as_str(),
+            outcome(record),
+        );
+        if record.metadata.state == ROLLBACK_STATE {
+            *rollbacks.entry(key).or_default() += 1;
+            continue;
+        }
+        *publishes.entry(key).or_default() += 1;
+        durations
+            .entry(record.registry.as_str())
+            .or_default()
+            .observe(record.duration as f64 / 1000.0);
+        if key.2 != "dry_run" {
+            let entry = last.entry((key.0, key.1)).or_insert(record);
+            if record.timestamp > entry.timestamp {
+                *entry = record;
+            }
+        }
+    }
+
+    let mut out = String::new();
+    header(
+        &mut out,
+        "package_publisher_publishes_total",
+        "counter",
+        "Publish attempts by registry, package and outcome",
+    );
+    for ((registry, package, outcome), count) in &publishes {
+        let _ = writeln!(
+            out,
+            "package_publisher_publishes_total{{registry=\"{}\",package=\"{}\",outcome=\"{}\"}} {}",
+            escape(registry),
+            escape(package),
+            outcome,
+            count
+        );
+    }
+
+    header(
+        &mut out,
+        "package_publisher_rollbacks_total",
+        "counter",
+        "Rollback attempts by registry, package and outcome",
+    );
+    for ((registry, package, outcome), count) in &rollbacks {
+        let _ = writeln!(
+            out,
+            "package_publisher_rollbacks_total{{registry=\"{}\",package=\"{}\",outcome=\"{}\"}} {}",
+            escape(registry),
+            escape(package),
+            outcome,
+            count
+        );
+    }
+
+    header(
+        &mut out,
+        "package_publisher_publish_duration_seconds",
+        "histogram",
+        "Duration of publish attempts",
+    );
+    for (registry, histogram) in &durations {
+        let registry = escape(registry);
+        for (bound, count) in DURATION_BUCKETS.iter().zip(histogram.buckets) {
+            let _ = writeln!(
+                out,
+                "package_publisher_publish_duration_seconds_bucket{{registry=\"{}\",le=\"{}\"}} {}",
+                registry, bound, count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "package_publisher_publish_duration_seconds_bucket{{registry=\"{}\",le=\"+Inf\"}} {}",
+            registry, histogram.count
+        );
+        let _ = writeln!(
+            out,
+            "package_publisher_publish_duration_seconds_sum{{registry=\"{}\"}} {}",
+            registry, histogram.sum
+        );
+        let _ = writeln!(
+            out,
+            "package_publisher_publish_duration_seconds_count{{registry=\"{}\"}} {}",
+            registry, histogram.count
+        );
+    }
+
+    header(
+        &mut out,
+        "package_publisher_last_publish_timestamp_seconds",
+        "gauge",
+        "Time of the last publish attempt (dry-runs excluded)",
+    );
+    for ((registry, package), record) in &last {
+        let _ = writeln!(
+            out,
+            "package_publisher_last_publish_timestamp_seconds{{registry=\"{}\",package=\"{}\",version=\"{}\"}} {}",
+            escape(registry),
+            escape(package),
+            escape(&record.version),
+            record.timestamp.timestamp()
+        );
+    }
+
+    header(
+        &mut out,
+        "package_publisher_last_publish_success",
+        "gauge",
+        "Whether the last publish attempt succeeded (1) or failed (0)",
+    );
+    for ((registry, package), record) in &last {
+        let _ = writeln!(
+            out,
+            "package_publisher_last_publish_success{{registry=\"{}\",package=\"{}\"}} {}",
+            escape(registry),
+            escape(package),
+            u8::from(record.success)
+        );
+    }
+    out
+}
+
+/// Textfile configured under `metrics`, relative paths resolved against the
+/// project
+pub fn textfile_path(project_path: &Path, config: Option<&MetricsConfig>) -> Option<PathBuf> {
+    let config = config?;
+    if config.enabled == Some(false) {
+        return None;
+    }
+    Some(project_path.join(&config.textfile))
+}
+
+/// Write `metrics` to `path` atomically (the collector never reads a partial
+/// file)
+pub async fn write_textfile(path: &Path, metrics: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    tokio::fs::write(&temp, metrics).await?;
+    tokio::fs::rename(&temp, path).await?;
+    Ok(())
+}
+
+/// Answer scrapes of `/metrics` on `listener` with the output of `render`
+///
+/// With `once`, returns after the first scrape; otherwise serves until the
+/// task is dropped.
+pub async fn serve(
+    listener: TcpListener,
+    once: bool,
+    render: impl AsyncFn() -> anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 16 * 1024 {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        let request = String::from_utf8_lossy(&request);
+        let path = request.split_whitespace().nth(1).unwrap_or("/");
+        let scraped = path == "/metrics" || path.starts_with("/metrics?");
+
+        let (status, content_type, body) = if scraped {
+            match render().await {
+                Ok(metrics) => ("200 OK", CONTENT_TYPE, metrics),
+                Err(e) => (
+                    "500 Internal Server Error",
+                    "text/plain",
+                    format!("{}\n", e),
+                ),
+            }
+        } else {
+            ("404 Not Found", "text/plain", "Not Found\n".to_string())
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+
+        if once && scraped {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::analytics::AnalyticsMetadata;
+    use chrono::{TimeZone, Utc};
+
+    fn record(
+        registry: &str,
+        success: bool,
+        state: &str,
+        duration: u64,
+        at: i64,
+    ) -> AnalyticsRecord {
+        AnalyticsRecord {
+            id: format!("{}-{}", registry, at),
+            registry: registry.to_string(),
+            package_name: "demo".to_string(),
+            version: format!("1.0.{}", at),
+            success,
+            error: None,
+            error_kind: None,
+            duration,
+            timestamp: Utc.timestamp_opt(at, 0).unwrap(),
+            metadata: AnalyticsMetadata {
+                state: state.to_string(),
+                warnings: Vec::new(),
+                verification_url: None,
+                integrity: None,
+                package_size: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let metrics = render(&[
+            record("npm", true, "SUCCESS", 4000, 100),
+            record("npm", false, "FAILED", 45000, 200),
+            record("npm", true, "DRY_RUN", 2000, 300),
+            record("npm", true, ROLLBACK_STATE, 1000, 400),
+        ]);
+
+        assert!(metrics.contains(
+            "package_publisher_publishes_total{registry=\"npm\",package=\"demo\",outcome=\"success\"} 1"
+        ));
+        assert!(metrics.contains(
+            "package_publisher_publishes_total{registry=\"npm\",package=\"demo\",outcome=\"dry_run\"} 1"
+        ));
+        assert!(metrics.contains(
+            "package_publisher_rollbacks_total{registry=\"npm\",package=\"demo\",outcome=\"success\"} 1"
+        ));
+        assert!(metrics.contains(
+            "package_publisher_publish_duration_seconds_bucket{registry=\"npm\",le=\"5\"} 2"
+        ));
+        assert!(metrics.contains(
+            "package_publisher_publish_duration_seconds_bucket{registry=\"npm\",le=\"+Inf\"} 3"
+        ));
+        assert!(
+            metrics.contains("package_publisher_publish_duration_seconds_sum{registry=\"npm\"} 51")
+        );
+        assert!(metrics.contains(
+            "package_publisher_last_publish_timestamp_seconds{registry=\"npm\",package=\"demo\",version=\"1.0.200\"} 200"
+        ));
+        assert!(metrics.contains(
+            "package_publisher_last_publish_success{registry=\"npm\",package=\"demo\"} 0"
+        ));
+        assert_eq!(escape("a\"b\\c"), "a\\\"b\\\\c");
+    }
+
+    #[tokio::test]
+    async fn test_serve_once() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(listener, true, async || Ok("up 1\n".to_string())));
+
+        let client = reqwest::Client::new();
+        let missing = client
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 404);
+        let response = client
+            .get(format!("http://{}/metrics", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "up 1\n");
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_textfile() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = MetricsConfig {
+            enabled: None,
+            textfile: "metrics/publisher.prom".to_string(),
+        };
+        let path = textfile_path(temp_dir.path(), Some(&config)).unwrap();
+        write_textfile(&path, "up 1\n").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "up 1\n");
+        assert!(
+            textfile_path(
+                temp_dir.path(),
+                Some(&MetricsConfig {
+                    enabled: Some(false),
+                    ..config
+                })
+            )
+            .is_none()
+        );
+    }
+}
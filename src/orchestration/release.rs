@@ -0,0 +1,388 @@
+//! Release Manager - Git tags and GitHub Releases after publishing
+//!
+//! After a successful publish this creates an annotated tag (`v{version}` by
+//! default), pushes it, and optionally creates a GitHub Release whose body is
+//! the version's CHANGELOG.md section, with built artifacts attached.
+//!
+//! Every step is idempotent: an existing tag or release is reused, so batch
+//! publishes to several registries produce a single tag and release.
+
+use crate::core::config::{ChangelogConfig, ReleaseConfig};
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::orchestration::changelog::ChangelogGenerator;
+use regex::Regex;
+use reqwest::Method;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const DEFAULT_TAG_FORMAT: &str = "v{version}";
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Result of the release step
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOutcome {
+    pub tag: String,
+    /// Tag was created by this run (false if it already existed)
+    pub tag_created: bool,
+    pub tag_pushed: bool,
+    /// GitHub Release page
+    pub release_url: Option<String>,
+    /// Uploaded asset file names
+    pub assets: Vec<String>,
+    /// Non-fatal problems (e.g. a failed asset upload)
+    pub warnings: Vec<String>,
+}
+
+/// `POST /repos/{owner}/{repo}/releases` response (subset)
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    html_url: String,
+    upload_url: String,
+}
+
+/// Creates git tags and GitHub Releases for published versions
+pub struct ReleaseManager {
+    project_path: PathBuf,
+    config: ReleaseConfig,
+    changelog_path: String,
+    extra_assets: Vec<PathBuf>,
+    github: GitHubClient,
+}
+
+impl ReleaseManager {
+    /// Create a release manager from the `release` config section
+    pub fn new(project_path: impl AsRef<Path>, config: ReleaseConfig) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            config,
+            changelog_path: "CHANGELOG.md".to_string(),
+            extra_assets: Vec::new(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Attach generated files (e.g. the SBOM) in addition to `githubRelease.assets`
+    pub fn with_assets(mut self, assets: Vec<PathBuf>) -> Self {
+        self.extra_assets = assets;
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    /// Read release notes from the configured changelog file
+    pub fn with_changelog(mut self, changelog: &ChangelogConfig) -> Self {
+        if let Some(ref path) = changelog.path {
+            self.changelog_path = path.clone();
+        }
+        self
+    }
+
+    /// Whether tagging is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled.unwrap_or(false)
+    }
+
+    /// Tag name for a version (`release.tagFormat`, default "v{version}")
+    pub fn tag_name(&self, version: &str) -> String {
+        self.config
+            .tag_format
+            .as_deref()
+            .unwrap_or(DEFAULT_TAG_FORMAT)
+            .replace("{version}", version)
+    }
+
+    fn remote(&self) -> &str {
+        self.config.remote.as_deref().unwrap_or("origin")
+    }
+
+    /// Create the tag, push it and create the GitHub Release (as configured)
+    pub async fn release(
+        &self,
+        package_name: &str,
+        version: &str,
+    ) -> anyhow::Result<ReleaseOutcome> {
+        let tag = self.tag_name(version);
+        let mut outcome = ReleaseOutcome {
+            tag: tag.clone(),
+            ..Default::default()
+        };
+
+        let exists = run_git(
+            &self.project_path,
+            &["rev-parse", "-q", "--verify", &format!("refs/tags/{}", tag)],
+        )
+        .await
+        .is_ok();
+        if !exists {
+            let message = format!("{} {}", package_name, version);
+            run_git(&self.project_path, &["tag", "-a", &tag, "-m", &message]).await?;
+            outcome.tag_created = true;
+        }
+
+        if self.config.push.unwrap_or(true) {
+            run_git(
+                &self.project_path,
+                &["push", self.remote(), &format!("refs/tags/{}", tag)],
+            )
+            .await?;
+            outcome.tag_pushed = true;
+        }
+
+        if let Some(ref github) = self.config.github_release
+            && github.enabled.unwrap_or(false)
+        {
+            self.create_github_release(&tag, version, &mut outcome)
+                .await?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// `owner/repo` from config or the remote URL
+    async fn repository(&self) -> anyhow::Result<String> {
+        if let Some(repository) = self
+            .config
+            .github_release
+            .as_ref()
+            .and_then(|g| g.repository.clone())
+        {
+            return Ok(repository);
+        }
+
+        let url = run_git(&self.project_path, &["remote", "get-url", self.remote()]).await?;
+        parse_github_repository(&url).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot determine GitHub repository from remote {} ({}); set release.githubRelease.repository",
+                self.remote(),
+                url
+            )
+        })
+    }
+
+    /// Release notes: the version's CHANGELOG.md section, if any
+    async fn release_body(&self, tag: &str, version: &str) -> String {
+        fs::read_to_string(self.project_path.join(&self.changelog_path))
+            .await
+            .ok()
+            .and_then(|content| ChangelogGenerator::section_for(&content, version))
+            .filter(|body| !body.is_empty())
+            .unwrap_or_else(|| format!("Release {}", tag))
+    }
+
+    async fn create_github_release(
+        &self,
+        tag: &str,
+        version: &str,
+        outcome: &mut ReleaseOutcome,
+    ) -> anyhow::Result<()> {
+        let github = self.config.github_release.clone().unwrap_or_default();
+        let token_env = github.token_env.as_deref().unwrap_or(DEFAULT_TOKEN_ENV);
+        let token = std::env::var(token_env)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{} is not set", token_env))?;
+        let repository = self.repository().await?;
+        let api = self.github.authenticated(&token);
+
+        // Reuse an existing release for this tag
+        let existing = api
+            .request(
+                Method::GET,
+                &format!("/repos/{}/releases/tags/{}", repository, tag),
+            )
+            .send()
+            .await?;
+        if existing.status().is_success() {
+            let release = existing.json::<GitHubRelease>().await?;
+            outcome.release_url = Some(release.html_url);
+            return Ok(());
+        }
+
+        let prerelease = semver::Version::parse(version)
+            .map(|v| !v.pre.is_empty())
+            .unwrap_or(false);
+        let body = serde_json::json!({
+            "tag_name": tag,
+            "name": tag,
+            "body": self.release_body(tag, version).await,
+            "draft": github.draft.unwrap_or(false),
+            "prerelease": prerelease,
+        });
+
+        let response = api
+            .request(Method::POST, &format!("/repos/{}/releases", repository))
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("GitHub Release creation failed (HTTP {}): {}", status, text);
+        }
+        let release = response.json::<GitHubRelease>().await?;
+        outcome.release_url = Some(release.html_url.clone());
+
+        // Upload URL is a URI template: .../assets{?name,label}
+        let upload_url = release
+            .upload_url
+            .split('{')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let mut assets = resolve_assets(
+            &self.project_path,
+            github.assets.as_deref().unwrap_or_default(),
+        )
+        .await;
+        assets.extend(self.extra_assets.iter().cloned());
+        for path in assets {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let result = async {
+                let data = fs::read(&path).await?;
+                let response = http_client()
+                    .post(&upload_url)
+                    .query(&[("name", name.as_str())])
+                    .header("Content-Type", "application/octet-stream")
+                    .bearer_auth(&token)
+                    .body(data)
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("HTTP {}", response.status());
+                }
+                Ok(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => outcome.assets.push(name),
+                Err(e) => outcome
+                    .warnings
+                    .push(format!("Failed to upload asset {}: {}", name, e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Expand asset patterns (files, directories, or `*` in the file name)
+pub(crate) async fn resolve_assets(project_path: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut assets = Vec::new();
+
+    for pattern in patterns {
+        let path = project_path.join(pattern);
+        let file_pattern = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let (dir, matcher) = if file_pattern.contains('*') {
+            let regex = format!("^{}$", regex::escape(&file_pattern).replace(r"\*", ".*"));
+            (
+                path.parent().map(Path::to_path_buf).unwrap_or_default(),
+                Some(Regex::new(&regex).unwrap()),
+            )
+        } else if fs::metadata(&path).await.is_ok_and(|m| m.is_dir()) {
+            (path, None)
+        } else {
+            if fs::metadata(&path).await.is_ok() {
+                assets.push(path);
+            }
+            continue;
+        };
+
+        let Ok(mut entries) = fs::read_dir(&dir).await else {
+            continue;
+        };
+        let mut matched = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let entry_path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry_path.is_file() && matcher.as_ref().is_none_or(|m| m.is_match(&name)) {
+                matched.push(entry_path);
+            }
+        }
+        matched.sort();
+        assets.extend(matched);
+    }
+
+    assets
+}
+
+/// `owner/repo` from a GitHub remote URL (https or ssh)
+pub(crate) fn parse_github_repository(url: &str) -> Option<String> {
+    let caps = Regex::new(r"github\.com[/:]([^/\s]+)/([^/\s]+?)(?:\.git)?/?$")
+        .unwrap()
+        .captures(url.trim())?;
+    Some(format!("{}/{}", &caps[1], &caps[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tag_name() {
+        let manager = ReleaseManager::new(".", ReleaseConfig::default());
+        assert_eq!(manager.tag_name("1.2.0"), "v1.2.0");
+
+        let manager = ReleaseManager::new(
+            ".",
+            ReleaseConfig {
+                tag_format: Some("my-pkg@{version}".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(manager.tag_name("1.2.0"), "my-pkg@1.2.0");
+    }
+
+    #[test]
+    fn test_parse_github_repository() {
+        assert_eq!(
+            parse_github_repository("https://github.com/octo/app.git").as_deref(),
+            Some("octo/app")
+        );
+        assert_eq!(
+            parse_github_repository("git@github.com:octo/app.git\n").as_deref(),
+            Some("octo/app")
+        );
+        assert!(parse_github_repository("https://gitlab.com/octo/app.git").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        let dist = temp_dir.path().join("dist");
+        std::fs::create_dir(&dist).unwrap();
+        std::fs::write(dist.join("app-1.0.0.tar.gz"), "a").unwrap();
+        std::fs::write(dist.join("app-1.0.0.zip"), "b").unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "c").unwrap();
+
+        let assets = resolve_assets(
+            temp_dir.path(),
+            &[
+                "dist/*.tar.gz".to_string(),
+                "README.md".to_string(),
+                "missing.txt".to_string(),
+            ],
+        )
+        .await;
+        assert_eq!(assets.len(), 2);
+        assert!(assets[0].ends_with("app-1.0.0.tar.gz"));
+
+        let assets = resolve_assets(temp_dir.path(), &["dist".to_string()]).await;
+        assert_eq!(assets.len(), 2);
+    }
+}
@@ -0,0 +1,503 @@
+//! Release Windows - allowed/blocked publish times and freeze periods
+//!
+//! `releaseWindows` restricts when real publishes may run (dry-runs are never
+//! blocked):
+//! - `allowed`: cron-style windows; publishing must fall inside one of them
+//! - `blocked`: cron-style windows; publishing must not fall inside any
+//! - `freeze`: date ranges (inclusive) during which nothing is published
+//!
+//! Windows use the five cron fields `minute hour day-of-month month
+//! day-of-week` and match every minute they describe, e.g.
+//! `"* 9-16 * * MON-THU"` is Monday to Thursday, 09:00–16:59.
+//!
+//! Past releases from analytics and the freeze periods can be exported as an
+//! iCalendar (`.ics`) feed with [`releases_to_ics`].
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::release_window::CronWindow;
+//! use chrono::{FixedOffset, TimeZone};
+//!
+//! let window = CronWindow::parse("* 9-16 * * MON-THU").unwrap();
+//! let utc = FixedOffset::east_opt(0).unwrap();
+//! assert!(window.matches(&utc.with_ymd_and_hms(2026, 10, 15, 10, 30, 0).unwrap()));
+//! assert!(!window.matches(&utc.with_ymd_and_hms(2026, 10, 16, 10, 30, 0).unwrap()));
+//! ```
+
+use crate::core::config::{FreezePeriodConfig, ReleaseWindowsConfig};
+use crate::orchestration::analytics::{AnalyticsRecord, ROLLBACK_STATE};
+use crate::tr;
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, Offset, TimeZone, Timelike, Utc};
+
+const DAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// A cron-style time window (minute resolution)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronWindow {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Both day fields restricted: cron matches either of them
+    day_or: bool,
+}
+
+impl CronWindow {
+    /// Parse `minute hour day-of-month month day-of-week`
+    ///
+    /// Fields accept `*`, values, ranges (`9-17`), steps (`*/15`, `0-30/10`)
+    /// and lists (`1,15`); months and weekdays also accept names (`JAN`, `MON`).
+    pub fn parse(expression: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            anyhow::bail!(
+                "Invalid window \"{}\": expected 5 fields (minute hour day-of-month month day-of-week)",
+                expression
+            );
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7, DAY_NAMES, 0)
+            .map_err(|e| anyhow::anyhow!("Invalid window \"{}\": {}", expression, e))?;
+        // 7 is Sunday as well
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        let field = |index: usize, min: u32, max: u32, names: &[&str], name_base: u32| {
+            parse_field(fields[index], min, max, names, name_base)
+                .map_err(|e| anyhow::anyhow!("Invalid window \"{}\": {}", expression, e))
+        };
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: field(0, 0, 59, &[], 0)?,
+            hours: field(1, 0, 23, &[], 0)?,
+            days_of_month: field(2, 1, 31, &[], 0)?,
+            months: field(3, 1, 12, MONTH_NAMES, 1)?,
+            days_of_week,
+            day_or: fields[2] != "*" && fields[4] != "*",
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Whether the minute containing `time` is inside the window
+    pub fn matches(&self, time: &DateTime<FixedOffset>) -> bool {
+        let bit = |set: u64, value: u32| set & (1 << value) != 0;
+        let day_of_month = bit(self.days_of_month, time.day());
+        let day_of_week = bit(self.days_of_week, time.weekday().num_days_from_sunday());
+        let day = if self.day_or {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && day
+    }
+}
+
+/// Bit set of the values of one cron field
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_base: u32,
+) -> anyhow::Result<u64> {
+    let value = |token: &str| -> anyhow::Result<u32> {
+        if let Some(index) = names.iter().position(|n| n.eq_ignore_ascii_case(token)) {
+            return Ok(index as u32 + name_base);
+        }
+        let value: u32 = token
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid value \"{}\"", token))?;
+        if value < min || value > max {
+            anyhow::bail!("{} is out of range {}-{}", value, min, max);
+        }
+        Ok(value)
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| anyhow::anyhow!("invalid step \"{}\"", step))?,
+            ),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                None => {
+                    let start = value(range)?;
+                    // `5/15` means from 5 to the end of the range
+                    (start, if step > 1 { max } else { start })
+                }
+            },
+        };
+        if start > end {
+            anyhow::bail!("invalid range \"{}\"", range);
+        }
+        for v in (start..=end).step_by(step as usize) {
+            set |= 1 << v;
+        }
+    }
+    Ok(set)
+}
+
+/// A release freeze (inclusive dates)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreezePeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub reason: Option<String>,
+}
+
+impl FreezePeriod {
+    fn from_config(config: &FreezePeriodConfig) -> anyhow::Result<Self> {
+        let date = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                anyhow::anyhow!("Invalid freeze date \"{}\" (expected YYYY-MM-DD)", value)
+            })
+        };
+        let start = date(&config.start)?;
+        let end = date(config.end.as_deref().unwrap_or(&config.start))?;
+        if end < start {
+            anyhow::bail!("Freeze period ends before it starts: {} - {}", start, end);
+        }
+        Ok(Self {
+            start,
+            end,
+            reason: config.reason.clone(),
+        })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Publish time restrictions from `releaseWindows`
+#[derive(Debug, Clone)]
+pub struct ReleaseWindows {
+    offset: Option<FixedOffset>,
+    allowed: Vec<CronWindow>,
+    blocked: Vec<CronWindow>,
+    freeze: Vec<FreezePeriod>,
+}
+
+impl ReleaseWindows {
+    pub fn from_config(config: &ReleaseWindowsConfig) -> anyhow::Result<Self> {
+        let offset = match config.timezone.as_deref() {
+            None | Some("UTC") | Some("utc") | Some("Z") => Some(Utc.fix()),
+            Some("local") => None,
+            Some(offset) => Some(offset.parse::<FixedOffset>().map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid releaseWindows.timezone \"{}\" (UTC, local or an offset like +09:00)",
+                    offset
+                )
+            })?),
+        };
+        let windows = |expressions: &Option<Vec<String>>| {
+            expressions
+                .iter()
+                .flatten()
+                .map(|e| CronWindow::parse(e))
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            offset,
+            allowed: windows(&config.allowed)?,
+            blocked: windows(&config.blocked)?,
+            freeze: config
+                .freeze
+                .iter()
+                .flatten()
+                .map(FreezePeriod::from_config)
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+
+    pub fn freeze_periods(&self) -> &[FreezePeriod] {
+        &self.freeze
+    }
+
+    /// `now` in the configured timezone
+    pub fn localize(&self, now: DateTime<Utc>) -> DateTime<FixedOffset> {
+        let offset = self
+            .offset
+            .unwrap_or_else(|| Local.offset_from_utc_datetime(&now.naive_utc()));
+        now.with_timezone(&offset)
+    }
+
+    /// Reason publishing is not allowed at `now`, if any
+    pub fn check(&self, now: DateTime<Utc>) -> Option<String> {
+        let time = self.localize(now);
+
+        if let Some(freeze) = self.freeze.iter().find(|f| f.contains(time.date_naive())) {
+            return Some(match freeze.reason {
+                Some(ref reason) => tr!(
+                    "release_window.freeze_reason",
+                    start = freeze.start,
+                    end = freeze.end,
+                    reason = reason
+                ),
+                None => tr!(
+                    "release_window.freeze",
+                    start = freeze.start,
+                    end = freeze.end
+                ),
+            });
+        }
+        if let Some(window) = self.blocked.iter().find(|w| w.matches(&time)) {
+            return Some(tr!(
+                "release_window.blocked",
+                window = window.expression(),
+                now = time.format("%Y-%m-%d %H:%M %:z")
+            ));
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|w| w.matches(&time)) {
+            return Some(tr!(
+                "release_window.outside_allowed",
+                windows = self
+                    .allowed
+                    .iter()
+                    .map(CronWindow::expression)
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+                now = time.format("%Y-%m-%d %H:%M %:z")
+            ));
+        }
+        None
+    }
+}
+
+/// iCalendar feed of releases (one event per publish) and freeze periods
+///
+/// Rollbacks and dry-runs are skipped; failed publishes are included and
+/// marked as such.
+pub fn releases_to_ics(records: &[AnalyticsRecord], freeze: &[FreezePeriod]) -> String {
+    let stamp = ics_time(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//package-publisher//releases//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Releases".to_string(),
+    ];
+
+    for record in records
+        .iter()
+        .filter(|r| r.metadata.state != ROLLBACK_STATE && r.metadata.state != "DRY_RUN")
+    {
+        let end =
+            record.timestamp + chrono::Duration::milliseconds(record.duration.max(60_000) as i64);
+        let summary = if record.success {
+            format!(
+                "📦 {}@{} → {}",
+                record.package_name, record.version, record.registry
+            )
+        } else {
+            format!(
+                "❌ {}@{} → {} (failed)",
+                record.package_name, record.version, record.registry
+            )
+        };
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@package-publisher", record.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", ics_time(record.timestamp)));
+        lines.push(format!("DTEND:{}", ics_time(end)));
+        lines.push(format!("SUMMARY:{}", ics_escape(&summary)));
+        lines.push("CATEGORIES:RELEASE".to_string());
+        if let Some(ref url) = record.metadata.verification_url {
+            lines.push(format!("URL:{}", url));
+        }
+        if let Some(ref error) = record.error {
+            lines.push(format!("DESCRIPTION:{}", ics_escape(error)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    for period in freeze {
+        let summary = match period.reason {
+            Some(ref reason) => format!("🧊 Release freeze: {}", reason),
+            None => "🧊 Release freeze".to_string(),
+        };
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:freeze-{}-{}@package-publisher",
+            period.start.format("%Y%m%d"),
+            period.end.format("%Y%m%d")
+        ));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!(
+            "DTSTART;VALUE=DATE:{}",
+            period.start.format("%Y%m%d")
+        ));
+        // DTEND of all-day events is exclusive
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (period.end + chrono::Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", ics_escape(&summary)));
+        lines.push("CATEGORIES:FREEZE".to_string());
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn ics_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape TEXT values (RFC 5545 3.3.11)
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold lines longer than 75 octets (RFC 5545 3.1)
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestration::analytics::AnalyticsMetadata;
+
+    fn config(timezone: &str) -> ReleaseWindowsConfig {
+        ReleaseWindowsConfig {
+            enabled: Some(true),
+            timezone: Some(timezone.to_string()),
+            allowed: Some(vec!["* 9-16 * * MON-THU".to_string()]),
+            blocked: Some(vec!["* 12 * * *".to_string()]),
+            freeze: Some(vec![FreezePeriodConfig {
+                start: "2026-12-21".to_string(),
+                end: Some("2027-01-04".to_string()),
+                reason: Some("Year-end freeze".to_string()),
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_cron_fields() {
+        assert!(CronWindow::parse("* * * *").is_err());
+        assert!(CronWindow::parse("60 * * * *").is_err());
+        assert!(CronWindow::parse("* * * * FUNDAY").is_err());
+
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let window = CronWindow::parse("*/15 9 1,15 * 7").unwrap();
+        // Day of month OR day of week when both are restricted
+        assert!(window.matches(&utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap()));
+        assert!(window.matches(&utc.with_ymd_and_hms(2026, 10, 18, 9, 45, 0).unwrap()));
+        assert!(!window.matches(&utc.with_ymd_and_hms(2026, 10, 18, 9, 46, 0).unwrap()));
+        assert!(!window.matches(&utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_check_windows_and_freeze() {
+        let windows = ReleaseWindows::from_config(&config("+09:00")).unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        // Thursday 10:00 JST
+        assert_eq!(windows.check(at("2026-10-15T01:00:00Z")), None);
+        // Thursday 12:30 JST (blocked lunch hour)
+        assert!(
+            windows
+                .check(at("2026-10-15T03:30:00Z"))
+                .unwrap()
+                .contains("blocked")
+        );
+        // Friday 10:00 JST
+        assert!(
+            windows
+                .check(at("2026-10-16T01:00:00Z"))
+                .unwrap()
+                .contains("outside the allowed")
+        );
+        // 2026-12-21 09:30 JST is inside the freeze, although 2026-12-20 in UTC
+        assert!(
+            windows
+                .check(at("2026-12-21T00:30:00Z"))
+                .unwrap()
+                .contains("Year-end freeze")
+        );
+        assert!(ReleaseWindows::from_config(&config("Tokyo")).is_err());
+    }
+
+    #[test]
+    fn test_releases_to_ics() {
+        let record = |id: &str, state: &str, success: bool| AnalyticsRecord {
+            id: id.to_string(),
+            registry: "npm".to_string(),
+            package_name: "demo".to_string(),
+            version: "1.2.0".to_string(),
+            success,
+            error: (!success).then(|| "E403, forbidden".to_string()),
+            error_kind: None,
+            duration: 90_000,
+            timestamp: "2026-10-15T01:00:00Z".parse().unwrap(),
+            metadata: AnalyticsMetadata {
+                state: state.to_string(),
+                warnings: Vec::new(),
+                verification_url: Some("https://www.npmjs.com/package/demo".to_string()),
+                integrity: None,
+                package_size: None,
+            },
+        };
+        let windows = ReleaseWindows::from_config(&config("UTC")).unwrap();
+        let ics = releases_to_ics(
+            &[
+                record("a", "SUCCESS", true),
+                record("b", "FAILED", false),
+                record("c", "ROLLBACK", true),
+            ],
+            windows.freeze_periods(),
+        );
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains("DTSTART:20261015T010000Z\r\nDTEND:20261015T010130Z"));
+        assert!(ics.contains("DESCRIPTION:E403\\, forbidden"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261221\r\nDTEND;VALUE=DATE:20270105"));
+        assert!(ics.lines().all(|l| l.len() <= 75));
+    }
+}
@@ -0,0 +1,480 @@
+//! Report File - machine-readable results for `--report-file`
+//!
+//! `publish` and `check` write their results to a file CI systems can archive
+//! and parse:
+//! - JSON: the full [`PublishReport`]s (one per registry or package in batch
+//!   mode) or the [`CheckSummary`], tagged with the command
+//! - JUnit XML: one test case per registry, so CI test result views show
+//!   failures, skipped registries and validation warnings
+//!
+//! The format follows the file extension (`.xml` is JUnit) unless given
+//! explicitly.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::report_file::{ReportDocument, ReportFormat};
+//! use package_publisher::orchestration::check_runner::{CheckMode, CheckSummary};
+//! use std::path::Path;
+//!
+//! assert_eq!(ReportFormat::from_path(Path::new("results.xml")), ReportFormat::Junit);
+//! let document = ReportDocument::Check(CheckSummary {
+//!     success: true,
+//!     mode: CheckMode::All,
+//!     registries: Vec::new(),
+//! });
+//! assert!(document.render(ReportFormat::Junit).unwrap().contains("<testsuites"));
+//! ```
+
+use crate::core::error::PublishErrorKind;
+use crate::orchestration::batch_publisher::BatchPublishResult;
+use crate::orchestration::check_runner::{CheckStatus, CheckSummary};
+use crate::orchestration::package_publisher::PublishReport;
+use crate::security::redaction::redact;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Report file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Junit,
+}
+
+impl ReportFormat {
+    /// Format implied by the file extension (`.xml`: JUnit, else JSON)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("xml") => ReportFormat::Junit,
+            _ => ReportFormat::Json,
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "junit" | "xml" => Ok(ReportFormat::Junit),
+            other => Err(format!(
+                "Unknown report format: {} (expected json or junit)",
+                other
+            )),
+        }
+    }
+}
+
+/// Results of a `publish` run
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishResults {
+    pub success: bool,
+    /// Reports keyed by registry (or package name in workspace mode)
+    pub reports: BTreeMap<String, PublishReport>,
+    /// Publishes that failed, with their errors
+    pub failed: BTreeMap<String, String>,
+    /// Categories of the failed publishes
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub error_kinds: BTreeMap<String, PublishErrorKind>,
+    /// Publishes skipped after an earlier failure
+    pub skipped: Vec<String>,
+}
+
+impl PublishResults {
+    /// Results of a publish that failed before producing a report
+    pub fn from_error(label: &str, error: &anyhow::Error) -> Self {
+        Self {
+            success: false,
+            reports: BTreeMap::new(),
+            failed: BTreeMap::from([(label.to_string(), error.to_string())]),
+            error_kinds: BTreeMap::from([(label.to_string(), PublishErrorKind::of(error))]),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+impl From<&PublishReport> for PublishResults {
+    fn from(report: &PublishReport) -> Self {
+        let mut failed = BTreeMap::new();
+        if !report.success && !report.errors.is_empty() {
+            failed.insert(report.registry.clone(), report.errors.join("; "));
+        }
+        Self {
+            success: report.success,
+            reports: BTreeMap::from([(report.registry.clone(), report.clone())]),
+            failed,
+            error_kinds: failed_kinds([(&report.registry, report)]),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+impl From<&BatchPublishResult> for PublishResults {
+    fn from(result: &BatchPublishResult) -> Self {
+        Self {
+            success: result.success,
+            reports: result
+                .results
+                .iter()
+                .map(|(label, report)| (label.clone(), report.clone()))
+                .collect(),
+            failed: result
+                .failed
+                .iter()
+                .map(|(label, error)| (label.clone(), error.clone()))
+                .collect(),
+            error_kinds: failed_kinds(&result.results),
+            skipped: result.skipped.clone(),
+        }
+    }
+}
+
+/// Error kinds of the failed reports, by label
+fn failed_kinds<'a>(
+    reports: impl IntoIterator<Item = (&'a String, &'a PublishReport)>,
+) -> BTreeMap<String, PublishErrorKind> {
+    reports
+        .into_iter()
+        .filter(|(_, report)| !report.success)
+        .filter_map(|(label, report)| Some((label.clone(), report.error_kind.clone()?)))
+        .collect()
+}
+
+/// Contents of a report file
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum ReportDocument {
+    Publish(PublishResults),
+    Check(CheckSummary),
+}
+
+impl ReportDocument {
+    /// Render the report in `format`
+    pub fn render(&self, format: ReportFormat) -> anyhow::Result<String> {
+        match format {
+            ReportFormat::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+            ReportFormat::Junit => Ok(self.to_junit()),
+        }
+    }
+
+    /// Write the report to `path`, creating missing parent directories
+    ///
+    /// Credentials in captured command output are masked.
+    pub async fn write(&self, path: &Path, format: ReportFormat) -> anyhow::Result<()> {
+        let contents = redact(&self.render(format)?);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, contents)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write report file {}: {}", path.display(), e))
+    }
+
+    fn to_junit(&self) -> String {
+        let (name, cases) = match self {
+            ReportDocument::Publish(results) => ("publish", publish_cases(results)),
+            ReportDocument::Check(summary) => ("check", check_cases(summary)),
+        };
+        let failures = cases
+            .iter()
+            .filter(|c| matches!(c.outcome, CaseOutcome::Failure(_)))
+            .count();
+        let skipped = cases
+            .iter()
+            .filter(|c| matches!(c.outcome, CaseOutcome::Skipped(_)))
+            .count();
+        let time: f64 = cases.iter().map(|c| c.seconds).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"package-publisher\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            cases.len(),
+            failures,
+            skipped,
+            time
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">\n",
+            name,
+            cases.len(),
+            failures,
+            skipped,
+            time,
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S")
+        ));
+        for case in &cases {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                xml_escape(&case.name),
+                xml_escape(&case.classname),
+                case.seconds
+            ));
+            if matches!(case.outcome, CaseOutcome::Passed) && case.output.is_empty() {
+                xml.push_str("/>\n");
+                continue;
+            }
+            xml.push_str(">\n");
+            match &case.outcome {
+                CaseOutcome::Passed => {}
+                CaseOutcome::Failure(message) => xml.push_str(&format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    xml_escape(message.lines().next().unwrap_or_default()),
+                    xml_escape(message)
+                )),
+                CaseOutcome::Skipped(message) => xml.push_str(&format!(
+                    "      <skipped message=\"{}\"/>\n",
+                    xml_escape(message)
+                )),
+            }
+            if !case.output.is_empty() {
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&case.output.join("\n"))
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+enum CaseOutcome {
+    Passed,
+    Failure(String),
+    Skipped(String),
+}
+
+struct TestCase {
+    name: String,
+    classname: String,
+    seconds: f64,
+    outcome: CaseOutcome,
+    /// Warnings and other details
+    output: Vec<String>,
+}
+
+fn publish_cases(results: &PublishResults) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    for (label, report) in &results.reports {
+        let outcome = match results.failed.get(label) {
+            Some(error) => CaseOutcome::Failure(error.clone()),
+            None if !report.success => CaseOutcome::Failure(report.errors.join("\n")),
+            None => CaseOutcome::Passed,
+        };
+        let mut output = Vec::new();
+        if !report.version.is_empty() {
+            output.push(format!(
+                "{}@{} ({})",
+                report.package_name, report.version, report.state
+            ));
+        }
+        if let Some(ref url) = report.verification_url {
+            output.push(url.clone());
+        }
+        output.extend(report.warnings.iter().map(|w| format!("warning: {}", w)));
+        cases.push(TestCase {
+            name: label.clone(),
+            classname: format!("publish.{}", report.registry),
+            seconds: report.duration as f64 / 1000.0,
+            outcome,
+            output,
+        });
+    }
+    for (label, error) in &results.failed {
+        if !results.reports.contains_key(label) {
+            cases.push(TestCase {
+                name: label.clone(),
+                classname: "publish".to_string(),
+                seconds: 0.0,
+                outcome: CaseOutcome::Failure(error.clone()),
+                output: Vec::new(),
+            });
+        }
+    }
+    for label in &results.skipped {
+        cases.push(TestCase {
+            name: label.clone(),
+            classname: "publish".to_string(),
+            seconds: 0.0,
+            outcome: CaseOutcome::Skipped("Skipped after an earlier failure".to_string()),
+            output: Vec::new(),
+        });
+    }
+    cases
+}
+
+fn check_cases(summary: &CheckSummary) -> Vec<TestCase> {
+    summary
+        .registries
+        .iter()
+        .map(|check| {
+            let errors: Vec<String> = check
+                .validation
+                .iter()
+                .flat_map(|v| &v.errors)
+                .map(|e| format!("[{}] {}", e.field, e.message))
+                .chain(check.error.clone())
+                .collect();
+            let outcome = match check.status {
+                CheckStatus::Passed if check.error.is_none() => CaseOutcome::Passed,
+                CheckStatus::Skipped => {
+                    CaseOutcome::Skipped("Skipped after an earlier failure".to_string())
+                }
+                _ => CaseOutcome::Failure(errors.join("\n")),
+            };
+            TestCase {
+                name: check.registry.clone(),
+                classname: format!("check.{}", check.registry),
+                seconds: check.duration as f64 / 1000.0,
+                outcome,
+                output: check
+                    .validation
+                    .iter()
+                    .flat_map(|v| &v.warnings)
+                    .map(|w| format!("warning: [{}] {}", w.field, w.message))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Escape text and attribute values
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::{ValidationError, ValidationResult, ValidationWarning};
+    use crate::orchestration::check_runner::{CheckMode, RegistryCheck};
+    use std::collections::HashMap;
+
+    fn report(registry: &str, success: bool) -> PublishReport {
+        serde_json::from_value(serde_json::json!({
+            "success": success,
+            "registry": registry,
+            "package_name": "demo",
+            "version": "1.0.0",
+            "published_at": null,
+            "verification_url": null,
+            "errors": if success { vec![] } else { vec!["403 Forbidden"] },
+            "error_kind": if success { None } else { Some(serde_json::json!({"kind": "auth_failed"})) },
+            "warnings": ["no README"],
+            "duration": 1500,
+            "state": if success { "SUCCESS" } else { "FAILED" },
+            "integrity": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_batch_publish_report() {
+        let batch = BatchPublishResult {
+            succeeded: vec!["npm".to_string()],
+            failed: HashMap::from([
+                ("pypi".to_string(), "403 Forbidden".to_string()),
+                ("crates.io".to_string(), "cargo not found".to_string()),
+            ]),
+            skipped: vec!["homebrew".to_string()],
+            success: false,
+            results: HashMap::from([
+                ("npm".to_string(), report("npm", true)),
+                ("pypi".to_string(), report("pypi", false)),
+            ]),
+        };
+        let document = ReportDocument::Publish(PublishResults::from(&batch));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&document.render(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["command"], "publish");
+        assert_eq!(json["reports"]["npm"]["version"], "1.0.0");
+        assert_eq!(json["failed"]["crates.io"], "cargo not found");
+        assert_eq!(json["errorKinds"]["pypi"]["kind"], "auth_failed");
+        assert_eq!(json["reports"]["pypi"]["error_kind"]["kind"], "auth_failed");
+        // crates.io failed without a report, so the kinds differ
+        assert_eq!(batch.exit_code(), 1);
+        assert_eq!(json["skipped"][0], "homebrew");
+
+        let xml = document.render(ReportFormat::Junit).unwrap();
+        assert!(xml.contains("tests=\"4\" failures=\"2\" skipped=\"1\""));
+        assert!(xml.contains("<testcase name=\"npm\" classname=\"publish.npm\" time=\"1.500\">"));
+        assert!(xml.contains("<failure message=\"403 Forbidden\">403 Forbidden</failure>"));
+        assert!(xml.contains("<skipped message=\"Skipped after an earlier failure\"/>"));
+    }
+
+    #[test]
+    fn test_check_report_junit() {
+        let summary = CheckSummary {
+            success: false,
+            mode: CheckMode::All,
+            registries: vec![RegistryCheck {
+                registry: "npm".to_string(),
+                status: CheckStatus::Failed,
+                duration: 20,
+                validation: Some(ValidationResult {
+                    valid: false,
+                    errors: vec![ValidationError {
+                        field: "name".to_string(),
+                        message: "must not contain <uppercase>".to_string(),
+                        severity: "error".to_string(),
+                    }],
+                    warnings: vec![ValidationWarning {
+                        field: "license".to_string(),
+                        message: "missing".to_string(),
+                        severity: "warning".to_string(),
+                    }],
+                    metadata: None,
+                }),
+                error: None,
+            }],
+        };
+
+        let xml = ReportDocument::Check(summary)
+            .render(ReportFormat::Junit)
+            .unwrap();
+        assert!(xml.contains("<testsuite name=\"check\" tests=\"1\" failures=\"1\""));
+        assert!(xml.contains("[name] must not contain &lt;uppercase&gt;"));
+        assert!(xml.contains("<system-out>warning: [license] missing</system-out>"));
+    }
+
+    #[tokio::test]
+    async fn test_written_report_masks_credentials() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("report.json");
+        let error = anyhow::anyhow!(
+            "npm ERR! 401 (//registry.npmjs.org/:_authToken=0f8e6a2c-94b1-4bd2-a1f3-7e2d9c1b5a44)"
+        );
+        ReportDocument::Publish(PublishResults::from_error("npm", &error))
+            .write(&path, ReportFormat::Json)
+            .await
+            .unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("_authToken=[REDACTED]"), "{}", written);
+        assert!(!written.contains("0f8e6a2c"));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("out/report.json")),
+            ReportFormat::Json
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("junit.XML")),
+            ReportFormat::Junit
+        );
+        assert_eq!("junit".parse::<ReportFormat>(), Ok(ReportFormat::Junit));
+    }
+}
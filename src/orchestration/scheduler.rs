@@ -0,0 +1,486 @@
+//! Scheduled publishing - prepare now, publish later
+//!
+//! `publish --at <time>` / `--after-ci-green` validates and dry-runs the
+//! release right away and records it under `.package-publisher/scheduled/`.
+//! `run-scheduled` (from cron or a scheduled CI workflow) then publishes the
+//! releases that are due: their time has come and, with `afterCiGreen`, the
+//! GitHub checks of the prepared commit passed. A release only runs from the
+//! commit it was prepared on.
+
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::orchestration::package_publisher::PublishOptions;
+use crate::orchestration::release::parse_github_repository;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Directory of pending release records
+const SCHEDULED_DIR: &str = ".package-publisher/scheduled";
+
+/// A release prepared by `publish --at` / `--after-ci-green`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledRelease {
+    pub id: String,
+    pub package_name: String,
+    pub version: String,
+    /// Registries to publish to (one runs a single publish, more a batch)
+    pub registries: Vec<String>,
+    /// Commit the release was validated on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Do not publish before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<DateTime<Utc>>,
+    /// Wait for the commit's GitHub checks to pass
+    #[serde(default)]
+    pub after_ci_green: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<String>,
+    #[serde(default)]
+    pub skip_hooks: bool,
+    #[serde(default)]
+    pub strict: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Set when a `run-scheduled` attempt failed; the release is not retried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduledRelease {
+    /// Record for `package_name@version`, created now
+    pub fn new(package_name: &str, version: &str, registries: Vec<String>) -> Self {
+        let created_at = Utc::now();
+        let id = format!(
+            "{}-{}-{}",
+            package_name,
+            version,
+            created_at.format("%Y%m%d%H%M%S")
+        )
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+        Self {
+            id,
+            package_name: package_name.to_string(),
+            version: version.to_string(),
+            registries,
+            commit: None,
+            created_at,
+            at: None,
+            after_ci_green: false,
+            tag: None,
+            access: None,
+            skip_hooks: false,
+            strict: false,
+            profile: None,
+            failed_at: None,
+        }
+    }
+
+    /// Options of the non-interactive publish to `registry`
+    pub fn publish_options(&self, registry: Option<String>) -> PublishOptions {
+        PublishOptions {
+            registry,
+            non_interactive: true,
+            skip_hooks: self.skip_hooks,
+            tag: self.tag.clone(),
+            access: self.access.clone(),
+            strict: self.strict,
+            profile: self.profile.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// When and under which condition the release runs
+    pub fn condition(&self) -> String {
+        match (self.at, self.after_ci_green) {
+            (Some(at), true) => format!("at {} once CI is green", at.to_rfc3339()),
+            (Some(at), false) => format!("at {}", at.to_rfc3339()),
+            (None, true) => "once CI is green".to_string(),
+            (None, false) => "on the next run".to_string(),
+        }
+    }
+}
+
+/// Whether a scheduled release can run now
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Readiness {
+    Due,
+    /// Not yet; checked again on the next run
+    Waiting(String),
+    /// Will not run without intervention
+    Blocked(String),
+}
+
+impl fmt::Display for Readiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Readiness::Due => write!(f, "due"),
+            Readiness::Waiting(reason) => write!(f, "waiting: {}", reason),
+            Readiness::Blocked(reason) => write!(f, "blocked: {}", reason),
+        }
+    }
+}
+
+/// Combined state of a commit's GitHub checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiStatus {
+    Success,
+    Pending,
+    Failure,
+}
+
+/// `GET /repos/{owner}/{repo}/commits/{ref}/status` response (subset)
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    state: String,
+    total_count: u64,
+}
+
+/// `GET /repos/{owner}/{repo}/commits/{ref}/check-runs` response (subset)
+#[derive(Debug, Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
+/// A check run of the commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    #[serde(default)]
+    pub conclusion: Option<String>,
+    #[serde(default)]
+    pub details_url: Option<String>,
+}
+
+/// Combine commit statuses (`None` when there are none) and check runs
+///
+/// Check runs of `current_run` (the GitHub Actions run executing
+/// `run-scheduled`) are ignored, since they cannot finish before it does.
+pub fn ci_status(
+    combined_state: Option<&str>,
+    check_runs: &[CheckRun],
+    current_run: Option<&str>,
+) -> CiStatus {
+    let own_run = |run: &&CheckRun| {
+        current_run.is_some_and(|id| {
+            run.details_url
+                .as_deref()
+                .is_some_and(|url| url.contains(&format!("/actions/runs/{}/", id)))
+        })
+    };
+    let runs: Vec<&CheckRun> = check_runs.iter().filter(|r| !own_run(r)).collect();
+    if combined_state.is_none() && runs.is_empty() {
+        // Nothing reported yet
+        return CiStatus::Pending;
+    }
+
+    let failed_run = runs.iter().any(|r| {
+        r.status == "completed"
+            && !matches!(
+                r.conclusion.as_deref(),
+                Some("success" | "neutral" | "skipped")
+            )
+    });
+    if failed_run || matches!(combined_state, Some("failure" | "error")) {
+        return CiStatus::Failure;
+    }
+    if runs.iter().any(|r| r.status != "completed") || combined_state == Some("pending") {
+        return CiStatus::Pending;
+    }
+    CiStatus::Success
+}
+
+/// Parse a `--at` time: RFC 3339 (seconds optional, `Z` or an offset), or
+/// `YYYY-MM-DD HH:MM` in UTC
+pub fn parse_schedule_time(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z") {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let naive = value.strip_suffix(['Z', 'z']).unwrap_or(value);
+    for format in ["%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(naive, format) {
+            return Ok(time.and_utc());
+        }
+    }
+    anyhow::bail!(
+        "Invalid time \"{}\" (expected e.g. 2024-06-01T10:00Z or 2024-06-01T19:00+09:00)",
+        value
+    )
+}
+
+/// Stores scheduled releases and decides which are due
+pub struct ReleaseScheduler {
+    project_path: PathBuf,
+    github: GitHubClient,
+}
+
+impl ReleaseScheduler {
+    pub fn new(project_path: impl AsRef<Path>) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.project_path.join(SCHEDULED_DIR)
+    }
+
+    /// Write (or update) a release record
+    pub async fn save(&self, release: &ScheduledRelease) -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(self.dir()).await?;
+        let path = self.dir().join(format!("{}.json", release.id));
+        fs::write(&path, serde_json::to_string_pretty(release)?).await?;
+        Ok(path)
+    }
+
+    /// Pending releases, earliest first
+    pub async fn list(&self) -> anyhow::Result<Vec<ScheduledRelease>> {
+        let mut releases = Vec::new();
+        let mut entries = match fs::read_dir(self.dir()).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(releases),
+            Err(e) => return Err(e.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                let content = fs::read_to_string(&path).await?;
+                let release: ScheduledRelease = serde_json::from_str(&content)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+                releases.push(release);
+            }
+        }
+        releases.sort_by_key(|r| (r.at.unwrap_or(r.created_at), r.created_at));
+        Ok(releases)
+    }
+
+    /// Delete a release record
+    pub async fn remove(&self, id: &str) -> anyhow::Result<()> {
+        fs::remove_file(self.dir().join(format!("{}.json", id))).await?;
+        Ok(())
+    }
+
+    /// Current commit, if the project is a git repository
+    pub async fn head_commit(&self) -> Option<String> {
+        run_git(&self.project_path, &["rev-parse", "HEAD"])
+            .await
+            .ok()
+    }
+
+    /// Whether `release` can run at `now` from the current checkout
+    pub async fn readiness(&self, release: &ScheduledRelease, now: DateTime<Utc>) -> Readiness {
+        if let Some(failed_at) = release.failed_at {
+            return Readiness::Blocked(format!(
+                "publishing failed at {}; schedule it again",
+                failed_at.to_rfc3339()
+            ));
+        }
+        if let Some(ref commit) = release.commit {
+            match self.head_commit().await {
+                Some(head) if &head == commit => {}
+                Some(head) => {
+                    return Readiness::Blocked(format!(
+                        "prepared on {} but HEAD is {}",
+                        short_sha(commit),
+                        short_sha(&head)
+                    ));
+                }
+                None => return Readiness::Blocked("not a git checkout".to_string()),
+            }
+        }
+        if let Some(at) = release.at
+            && at > now
+        {
+            return Readiness::Waiting(format!("scheduled for {}", at.to_rfc3339()));
+        }
+        if release.after_ci_green {
+            let Some(ref commit) = release.commit else {
+                return Readiness::Blocked("no commit recorded to check CI for".to_string());
+            };
+            return match self.commit_ci_status(commit).await {
+                Ok(CiStatus::Success) => Readiness::Due,
+                Ok(CiStatus::Pending) => Readiness::Waiting("CI checks are not finished".into()),
+                Ok(CiStatus::Failure) => {
+                    Readiness::Blocked(format!("CI failed for {}", short_sha(commit)))
+                }
+                Err(e) => Readiness::Waiting(format!("CI status unavailable: {}", e)),
+            };
+        }
+        Readiness::Due
+    }
+
+    /// CI status of `commit` from the GitHub statuses and checks APIs
+    pub async fn commit_ci_status(&self, commit: &str) -> anyhow::Result<CiStatus> {
+        let token = std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("GITHUB_TOKEN is not set"))?;
+        let repository = match std::env::var("GITHUB_REPOSITORY") {
+            Ok(repository) if !repository.is_empty() => repository,
+            _ => {
+                let url = run_git(&self.project_path, &["remote", "get-url", "origin"]).await?;
+                parse_github_repository(&url).ok_or_else(|| {
+                    anyhow::anyhow!("Cannot determine GitHub repository from {}", url)
+                })?
+            }
+        };
+
+        let github = self.github.authenticated(&token);
+        let get = |path: String| {
+            github
+                .request(
+                    reqwest::Method::GET,
+                    &format!("/repos/{}/commits/{}/{}", repository, commit, path),
+                )
+                .send()
+        };
+        let response = get("status".to_string()).await?;
+        if !response.status().is_success() {
+            anyhow::bail!("commit status request failed (HTTP {})", response.status());
+        }
+        let combined: CombinedStatus = response.json().await?;
+        let response = get("check-runs?per_page=100".to_string()).await?;
+        if !response.status().is_success() {
+            anyhow::bail!("check runs request failed (HTTP {})", response.status());
+        }
+        let checks: CheckRuns = response.json().await?;
+
+        let current_run = std::env::var("GITHUB_RUN_ID").ok();
+        Ok(ci_status(
+            (combined.total_count > 0).then_some(combined.state.as_str()),
+            &checks.check_runs,
+            current_run.as_deref(),
+        ))
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(name: &str, status: &str, conclusion: Option<&str>, url: &str) -> CheckRun {
+        CheckRun {
+            name: name.to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            details_url: Some(url.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_schedule_time() {
+        let expected = "2024-06-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(parse_schedule_time("2024-06-01T10:00Z").unwrap(), expected);
+        assert_eq!(
+            parse_schedule_time("2024-06-01T19:00+09:00").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_schedule_time("2024-06-01T10:00:00Z").unwrap(),
+            expected
+        );
+        assert_eq!(parse_schedule_time("2024-06-01 10:00").unwrap(), expected);
+        assert!(parse_schedule_time("tomorrow").is_err());
+    }
+
+    #[test]
+    fn test_ci_status() {
+        let own = "https://github.com/acme/demo/actions/runs/99/job/1";
+        let other = "https://github.com/acme/demo/actions/runs/98/job/2";
+        assert_eq!(ci_status(None, &[], None), CiStatus::Pending);
+        assert_eq!(
+            ci_status(
+                Some("success"),
+                &[
+                    run("test", "completed", Some("success"), other),
+                    run("publish", "in_progress", None, own),
+                ],
+                Some("99"),
+            ),
+            CiStatus::Success
+        );
+        assert_eq!(
+            ci_status(None, &[run("test", "in_progress", None, other)], Some("99")),
+            CiStatus::Pending
+        );
+        assert_eq!(
+            ci_status(
+                Some("pending"),
+                &[run("lint", "completed", Some("failure"), other)],
+                None
+            ),
+            CiStatus::Failure
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_list_and_readiness() {
+        let temp_dir = TempDir::new().unwrap();
+        let scheduler = ReleaseScheduler::new(temp_dir.path());
+        let now = Utc::now();
+
+        let mut later = ScheduledRelease::new("@acme/demo", "1.2.0", vec!["npm".to_string()]);
+        later.at = Some(now + chrono::Duration::hours(1));
+        let mut due = ScheduledRelease::new("demo", "1.1.0", vec!["npm".to_string()]);
+        due.id.push_str("-a");
+        due.at = Some(now - chrono::Duration::minutes(5));
+        scheduler.save(&later).await.unwrap();
+        scheduler.save(&due).await.unwrap();
+        assert!(later.id.starts_with("_acme_demo-1.2.0-"));
+
+        let releases = scheduler.list().await.unwrap();
+        assert_eq!(releases, vec![due.clone(), later.clone()]);
+        assert_eq!(scheduler.readiness(&due, now).await, Readiness::Due);
+        assert!(matches!(
+            scheduler.readiness(&later, now).await,
+            Readiness::Waiting(_)
+        ));
+
+        due.failed_at = Some(now);
+        assert!(matches!(
+            scheduler.readiness(&due, now).await,
+            Readiness::Blocked(_)
+        ));
+        let mut moved = later.clone();
+        moved.commit = Some("0123456789abcdef".to_string());
+        assert!(matches!(
+            scheduler.readiness(&moved, now).await,
+            Readiness::Blocked(_)
+        ));
+
+        scheduler.remove(&later.id).await.unwrap();
+        assert_eq!(scheduler.list().await.unwrap().len(), 1);
+    }
+}
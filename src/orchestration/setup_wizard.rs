@@ -0,0 +1,313 @@
+//! Setup Wizard - first-run guided setup
+//!
+//! When a project has no configuration, the CLI offers a guided
+//! setup: registries are detected, credentials checked, and a configuration
+//! (starting from a matching built-in preset) is proposed. Git hooks and a
+//! GitHub Actions workflow can be added on top. Declining records a marker in
+//! `.package-publisher/` so the offer is not repeated; `package-publisher init`
+//! runs the wizard explicitly.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::orchestration::setup_wizard::SetupProposal;
+//! use package_publisher::plugins::plugin_loader::RegistryType;
+//!
+//! let proposal = SetupProposal::new(vec![RegistryType::Crates], Vec::new());
+//! assert_eq!(proposal.preset, Some("rust-cli"));
+//! assert!(proposal.config_yaml().contains("preset: \"rust-cli\""));
+//! ```
+
+use crate::core::config_loader::{CONFIG_FILENAME, ConfigSource};
+use crate::plugins::plugin_loader::{PluginLoader, RegistryType};
+use crate::security::credential_preflight::{CredentialCheck, CredentialPreflight};
+use crate::security::credential_validator::CredentialValidator;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// GitHub Actions workflow written by the wizard
+pub const CI_WORKFLOW_FILE: &str = ".github/workflows/publish.yml";
+
+/// Marker recording that the user declined the first-run setup
+const SKIP_MARKER: &str = ".package-publisher/setup-skipped";
+
+/// Detection results and the configuration proposed from them
+#[derive(Debug, Clone)]
+pub struct SetupProposal {
+    /// Detected registries in detection order
+    pub registries: Vec<RegistryType>,
+    pub credentials: Vec<CredentialCheck>,
+    /// Built-in preset matching the primary registry
+    pub preset: Option<&'static str>,
+}
+
+impl SetupProposal {
+    pub fn new(registries: Vec<RegistryType>, credentials: Vec<CredentialCheck>) -> Self {
+        let preset = registries.first().and_then(|registry| match registry {
+            RegistryType::Crates => Some("rust-cli"),
+            RegistryType::Npm => Some("ts-library"),
+            RegistryType::PyPI => Some("python-package"),
+            _ => None,
+        });
+        Self {
+            registries,
+            credentials,
+            preset,
+        }
+    }
+
+    /// Proposed `.publish-config.yaml`
+    pub fn config_yaml(&self) -> String {
+        let mut yaml = String::from(
+            "# Generated by `package-publisher init`; see .publish-config.example.yaml\n\
+             version: \"1.0\"\n",
+        );
+        if let Some(preset) = self.preset {
+            yaml.push_str(&format!("preset: \"{}\"\n", preset));
+        }
+        if let Some(primary) = self.registries.first() {
+            yaml.push_str(&format!(
+                "\nproject:\n  defaultRegistry: \"{}\"\n",
+                primary.as_str()
+            ));
+        }
+
+        yaml.push_str("\nregistries:\n");
+        if self.registries.is_empty() {
+            yaml.push_str("  {}\n");
+        }
+        for registry in &self.registries {
+            match config_key(*registry) {
+                Some(key) => yaml.push_str(&format!("  {}:\n    enabled: true\n", key)),
+                None => yaml.push_str(&format!(
+                    "  # {}: needs registry-specific settings (see the example config)\n",
+                    registry.as_str()
+                )),
+            }
+        }
+        yaml
+    }
+
+    /// Environment variables the detected registries read credentials from
+    pub fn secret_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .credentials
+            .iter()
+            .map(|check| check.source.clone())
+            .filter(|source| {
+                !source.is_empty()
+                    && source
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// GitHub Actions workflow publishing on version tags
+    pub fn ci_workflow(&self) -> String {
+        let registries: Vec<&str> = self.registries.iter().map(|r| r.as_str()).collect();
+        let publish = if registries.len() > 1 {
+            format!(
+                "package-publisher publish --non-interactive --registries {}",
+                registries.join(",")
+            )
+        } else {
+            "package-publisher publish --non-interactive".to_string()
+        };
+
+        let mut workflow = format!(
+            "name: Publish\n\
+             \n\
+             on:\n  push:\n    tags:\n      - \"v*\"\n\
+             \n\
+             jobs:\n  publish:\n    runs-on: ubuntu-latest\n    steps:\n\
+             \x20     - uses: actions/checkout@v4\n        with:\n          fetch-depth: 0\n\
+             \x20     - name: Install package-publisher\n        run: cargo install package-publisher --locked\n\
+             \x20     - name: Check\n        run: package-publisher check\n\
+             \x20     - name: Publish\n        run: {}\n",
+            publish
+        );
+        let secrets = self.secret_names();
+        if !secrets.is_empty() {
+            workflow.push_str("        env:\n");
+            for name in secrets {
+                workflow.push_str(&format!(
+                    "          {}: ${{{{ secrets.{} }}}}\n",
+                    name, name
+                ));
+            }
+        }
+        workflow
+    }
+}
+
+/// Section name under `registries:` for registries usable with defaults only
+fn config_key(registry: RegistryType) -> Option<&'static str> {
+    match registry {
+        RegistryType::Npm => Some("npm"),
+        RegistryType::Crates => Some("crates"),
+        RegistryType::PyPI => Some("pypi"),
+        RegistryType::Homebrew => Some("homebrew"),
+        RegistryType::GitHubPackages => Some("githubPackages"),
+        RegistryType::Upm => Some("upm"),
+        RegistryType::Julia => Some("julia"),
+        RegistryType::Hackage => Some("hackage"),
+        RegistryType::Expo => Some("expo"),
+        _ => None,
+    }
+}
+
+/// Detects the project setup and writes the accepted files
+pub struct SetupWizard {
+    project_path: PathBuf,
+}
+
+impl SetupWizard {
+    pub fn new(project_path: &Path) -> Self {
+        Self {
+            project_path: project_path.to_path_buf(),
+        }
+    }
+
+    /// No configuration yet and the setup was not declined before
+    pub async fn is_first_run(&self) -> bool {
+        matches!(ConfigSource::find(&self.project_path).await, Ok(None))
+            && !fs::try_exists(self.project_path.join(SKIP_MARKER))
+                .await
+                .unwrap_or(true)
+    }
+
+    /// Remember that the first-run setup was declined
+    pub async fn skip(&self) -> anyhow::Result<()> {
+        let marker = self.project_path.join(SKIP_MARKER);
+        if let Some(parent) = marker.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(marker, "").await?;
+        Ok(())
+    }
+
+    /// Detect registries and check their credentials
+    pub async fn detect(&self) -> anyhow::Result<SetupProposal> {
+        let detected = PluginLoader::new()
+            .detect_plugins(&self.project_path)
+            .await?;
+        let validator = CredentialValidator::new();
+        let credentials = CredentialPreflight::new(&self.project_path, &validator)
+            .check_all(&detected)
+            .await;
+        Ok(SetupProposal::new(
+            detected.iter().map(|d| d.registry_type).collect(),
+            credentials,
+        ))
+    }
+
+    /// Write the proposed configuration; an existing file is only replaced with `force`
+    pub async fn write_config(
+        &self,
+        proposal: &SetupProposal,
+        force: bool,
+    ) -> anyhow::Result<PathBuf> {
+        let path = self.project_path.join(CONFIG_FILENAME);
+        if force {
+            fs::write(&path, proposal.config_yaml()).await?;
+        } else {
+            Self::write_new(&path, &proposal.config_yaml()).await?;
+        }
+        Ok(path)
+    }
+
+    /// Write the GitHub Actions workflow; an existing file is never replaced
+    pub async fn write_ci_workflow(&self, proposal: &SetupProposal) -> anyhow::Result<PathBuf> {
+        let path = self.project_path.join(CI_WORKFLOW_FILE);
+        Self::write_new(&path, &proposal.ci_workflow()).await?;
+        Ok(path)
+    }
+
+    async fn write_new(path: &Path, content: &str) -> anyhow::Result<()> {
+        if fs::try_exists(path).await? {
+            anyhow::bail!("{} already exists", path.display());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, content).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::PublishConfig;
+    use crate::security::credential_preflight::CredentialStatus;
+    use tempfile::TempDir;
+
+    fn credential(registry: &str, source: &str) -> CredentialCheck {
+        CredentialCheck {
+            registry: registry.to_string(),
+            source: source.to_string(),
+            status: CredentialStatus::Missing,
+            detail: String::new(),
+            account: None,
+        }
+    }
+
+    #[test]
+    fn test_proposal_config_and_workflow() {
+        let proposal = SetupProposal::new(
+            vec![RegistryType::Npm, RegistryType::Crates, RegistryType::Nexus],
+            vec![
+                credential("npm", "NPM_TOKEN"),
+                credential("crates.io", "CARGO_REGISTRY_TOKEN"),
+                credential("homebrew", "git push --dry-run"),
+            ],
+        );
+        assert_eq!(proposal.preset, Some("ts-library"));
+
+        let config: PublishConfig = serde_yaml::from_str(&proposal.config_yaml()).unwrap();
+        assert_eq!(config.preset.as_deref(), Some("ts-library"));
+        assert_eq!(
+            config.project.unwrap().default_registry.as_deref(),
+            Some("npm")
+        );
+        assert!(config.registries.npm.is_some());
+        assert!(config.registries.crates.is_some());
+        assert!(config.registries.nexus.is_none());
+
+        let workflow = proposal.ci_workflow();
+        assert!(workflow.contains("--registries npm,crates.io,nexus\n"));
+        assert!(workflow.contains("          NPM_TOKEN: ${{ secrets.NPM_TOKEN }}\n"));
+        assert!(workflow.contains("CARGO_REGISTRY_TOKEN: ${{ secrets.CARGO_REGISTRY_TOKEN }}"));
+        assert!(!workflow.contains("git push"));
+        serde_yaml::from_str::<serde_yaml::Value>(&workflow).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_first_run_until_configured_or_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let wizard = SetupWizard::new(temp_dir.path());
+        assert!(wizard.is_first_run().await);
+
+        let proposal = wizard.detect().await.unwrap();
+        assert_eq!(proposal.registries, vec![RegistryType::Crates]);
+        wizard.write_config(&proposal, false).await.unwrap();
+        assert!(!wizard.is_first_run().await);
+        assert!(wizard.write_config(&proposal, false).await.is_err());
+        wizard.write_config(&proposal, true).await.unwrap();
+
+        let other = TempDir::new().unwrap();
+        let wizard = SetupWizard::new(other.path());
+        wizard.skip().await.unwrap();
+        assert!(!wizard.is_first_run().await);
+    }
+}
@@ -0,0 +1,560 @@
+//! Telemetry - OpenTelemetry export of publish pipelines
+//!
+//! Every publish is recorded as a trace (a `publish` span with a child span
+//! per stage) together with metrics: the duration of every stage, success
+//! and failure counters and the latency of the registry API requests. When a
+//! `telemetry` section is configured they are sent to an OTLP/HTTP receiver
+//! (OpenTelemetry Collector, Jaeger, Grafana Tempo, Honeycomb, …) using the
+//! JSON encoding:
+//!
+//! ```yaml
+//! telemetry:
+//!   endpoint: "http://localhost:4318"
+//!   headers:
+//!     x-honeycomb-team: "your-api-key"
+//!   serviceName: "package-publisher"
+//! ```
+//!
+//! Export failures are reported as warnings and never fail the publish.
+
+use crate::core::config::TelemetryConfig;
+use crate::core::http::http_client;
+use crate::core::state_machine::PublishState;
+use crate::security::redaction::redact;
+use crate::tr;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default `service.name`
+const DEFAULT_SERVICE_NAME: &str = "package-publisher";
+
+/// Instrumentation scope of the exported data
+const SCOPE_NAME: &str = "package-publisher";
+
+/// `AGGREGATION_TEMPORALITY_DELTA`: every export covers one publish
+const DELTA: u32 = 1;
+
+/// Latency of registry API requests per host, drained by every export
+static LATENCY: LazyLock<Mutex<BTreeMap<String, LatencyStats>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Latency of the requests sent to a host
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyStats {
+    pub count: u64,
+    /// Total, in milliseconds
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Record the latency of a registry API request
+pub(crate) fn record_latency(host: &str, latency: Duration) {
+    let Ok(mut stats) = LATENCY.lock() else {
+        return;
+    };
+    let ms = latency.as_secs_f64() * 1000.0;
+    let entry = stats.entry(host.to_string()).or_default();
+    if entry.count == 0 || ms < entry.min {
+        entry.min = ms;
+    }
+    entry.max = entry.max.max(ms);
+    entry.sum += ms;
+    entry.count += 1;
+}
+
+/// Latency recorded since the last call, per host
+fn take_latency() -> BTreeMap<String, LatencyStats> {
+    LATENCY
+        .lock()
+        .map(|mut stats| std::mem::take(&mut *stats))
+        .unwrap_or_default()
+}
+
+/// Time spent in a stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageTiming {
+    pub stage: PublishState,
+    pub started_at: SystemTime,
+    pub ended_at: SystemTime,
+}
+
+/// Stages of one publish
+#[derive(Debug, Clone)]
+pub struct PublishTrace {
+    started_at: SystemTime,
+    ended_at: Option<SystemTime>,
+    current: Option<(PublishState, SystemTime)>,
+    stages: Vec<StageTiming>,
+}
+
+impl Default for PublishTrace {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
+impl PublishTrace {
+    /// Start recording a publish
+    pub fn start() -> Self {
+        Self {
+            started_at: SystemTime::now(),
+            ended_at: None,
+            current: None,
+            stages: Vec::new(),
+        }
+    }
+
+    /// The publish moved to `stage`; final states only end the current stage
+    pub fn enter(&mut self, stage: PublishState) {
+        let now = SystemTime::now();
+        self.end_stage(now);
+        if !matches!(
+            stage,
+            PublishState::Initial
+                | PublishState::Success
+                | PublishState::Failed
+                | PublishState::RolledBack
+        ) {
+            self.current = Some((stage, now));
+        }
+    }
+
+    /// End the publish
+    pub fn finish(&mut self) {
+        let now = SystemTime::now();
+        self.end_stage(now);
+        self.ended_at = Some(now);
+    }
+
+    fn end_stage(&mut self, now: SystemTime) {
+        if let Some((stage, started_at)) = self.current.take() {
+            self.stages.push(StageTiming {
+                stage,
+                started_at,
+                ended_at: now,
+            });
+        }
+    }
+
+    /// Completed stages in order
+    pub fn stages(&self) -> &[StageTiming] {
+        &self.stages
+    }
+
+    fn ended_at(&self) -> SystemTime {
+        self.ended_at.unwrap_or_else(SystemTime::now)
+    }
+}
+
+/// What was published, attached to the exported data
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishOutcome {
+    pub registry: String,
+    pub package_name: String,
+    pub version: String,
+    pub success: bool,
+    pub dry_run: bool,
+    pub error: Option<String>,
+}
+
+/// Sends publish traces and metrics to an OTLP/HTTP receiver
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryExporter {
+    endpoint: String,
+    headers: HashMap<String, String>,
+    service_name: String,
+}
+
+impl TelemetryExporter {
+    /// Exporter for the `telemetry` section, or `None` if it is disabled
+    pub fn from_config(config: Option<&TelemetryConfig>) -> Option<Self> {
+        let config = config?;
+        if config.enabled == Some(false) || config.endpoint.trim().is_empty() {
+            return None;
+        }
+        Some(Self {
+            endpoint: config.endpoint.trim_end_matches('/').to_string(),
+            headers: config.headers.clone().unwrap_or_default(),
+            service_name: config
+                .service_name
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string()),
+        })
+    }
+
+    /// Send the trace and metrics of a publish
+    pub async fn export(
+        &self,
+        trace: &PublishTrace,
+        outcome: &PublishOutcome,
+    ) -> anyhow::Result<()> {
+        let latency = take_latency();
+        self.post("v1/traces", &self.traces_payload(trace, outcome))
+            .await?;
+        self.post(
+            "v1/metrics",
+            &self.metrics_payload(trace, outcome, &latency),
+        )
+        .await
+    }
+
+    async fn post(&self, path: &str, payload: &Value) -> anyhow::Result<()> {
+        let url = format!("{}/{}", self.endpoint, path);
+        let mut request = http_client().post(&url).json(payload);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        // Not `send_rate_limited`: the receiver is not a registry and its
+        // latency must not be reported as registry latency
+        let response = request.send().await.map_err(|e| {
+            anyhow::anyhow!("{}", tr!("telemetry.send_error", url = url, error = e))
+        })?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "telemetry.send_failed",
+                    url = url,
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    fn resource(&self) -> Value {
+        json!({
+            "attributes": [
+                attribute("service.name", &self.service_name),
+                attribute("service.version", env!("CARGO_PKG_VERSION")),
+            ]
+        })
+    }
+
+    /// `ExportTraceServiceRequest` with the publish span and its stage spans
+    fn traces_payload(&self, trace: &PublishTrace, outcome: &PublishOutcome) -> Value {
+        let trace_id = uuid::Uuid::new_v4().simple().to_string();
+        let root_id = span_id();
+        let mut attributes = publish_attributes(outcome);
+        attributes.push(json!({"key": "publish.dry_run", "value": {"boolValue": outcome.dry_run}}));
+        if let Some(ref error) = outcome.error {
+            // Errors carry registry output, which may include credentials
+            attributes.push(attribute("error.message", &redact(error)));
+        }
+
+        let mut spans = vec![json!({
+            "traceId": trace_id,
+            "spanId": root_id,
+            "name": "publish",
+            "kind": 1,
+            "startTimeUnixNano": unix_nanos(trace.started_at),
+            "endTimeUnixNano": unix_nanos(trace.ended_at()),
+            "attributes": attributes,
+            "status": status(outcome),
+        })];
+        for stage in trace.stages() {
+            spans.push(json!({
+                "traceId": trace_id,
+                "spanId": span_id(),
+                "parentSpanId": root_id,
+                "name": format!("publish.{}", stage_name(stage.stage)),
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(stage.started_at),
+                "endTimeUnixNano": unix_nanos(stage.ended_at),
+                "attributes": [attribute("publish.stage", &stage_name(stage.stage))],
+            }));
+        }
+
+        json!({
+            "resourceSpans": [{
+                "resource": self.resource(),
+                "scopeSpans": [{
+                    "scope": {"name": SCOPE_NAME},
+                    "spans": spans,
+                }],
+            }]
+        })
+    }
+
+    /// `ExportMetricsServiceRequest` with stage durations, the outcome
+    /// counter and registry latency
+    fn metrics_payload(
+        &self,
+        trace: &PublishTrace,
+        outcome: &PublishOutcome,
+        latency: &BTreeMap<String, LatencyStats>,
+    ) -> Value {
+        let start = unix_nanos(trace.started_at);
+        let end = unix_nanos(trace.ended_at());
+
+        let stage_points: Vec<Value> = trace
+            .stages()
+            .iter()
+            .map(|stage| {
+                let ms = stage
+                    .ended_at
+                    .duration_since(stage.started_at)
+                    .unwrap_or_default()
+                    .as_secs_f64()
+                    * 1000.0;
+                let mut attributes = publish_attributes(outcome);
+                attributes.push(attribute("publish.stage", &stage_name(stage.stage)));
+                json!({
+                    "attributes": attributes,
+                    "startTimeUnixNano": unix_nanos(stage.started_at),
+                    "timeUnixNano": unix_nanos(stage.ended_at),
+                    "count": "1",
+                    "sum": ms,
+                    "min": ms,
+                    "max": ms,
+                    "bucketCounts": ["1"],
+                    "explicitBounds": [],
+                })
+            })
+            .collect();
+
+        let mut counter_attributes = publish_attributes(outcome);
+        counter_attributes.push(attribute(
+            "publish.outcome",
+            if outcome.success {
+                "success"
+            } else {
+                "failure"
+            },
+        ));
+
+        let latency_points: Vec<Value> = latency
+            .iter()
+            .map(|(host, stats)| {
+                json!({
+                    "attributes": [attribute("server.address", host)],
+                    "startTimeUnixNano": start,
+                    "timeUnixNano": end,
+                    "count": stats.count.to_string(),
+                    "sum": stats.sum,
+                    "min": stats.min,
+                    "max": stats.max,
+                    "bucketCounts": [stats.count.to_string()],
+                    "explicitBounds": [],
+                })
+            })
+            .collect();
+
+        let mut metrics = vec![
+            json!({
+                "name": "package_publisher.stage.duration",
+                "description": "Time spent in a publish stage",
+                "unit": "ms",
+                "histogram": {"dataPoints": stage_points, "aggregationTemporality": DELTA},
+            }),
+            json!({
+                "name": "package_publisher.publishes",
+                "description": "Publishes by outcome",
+                "unit": "{publish}",
+                "sum": {
+                    "dataPoints": [{
+                        "attributes": counter_attributes,
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": end,
+                        "asInt": "1",
+                    }],
+                    "aggregationTemporality": DELTA,
+                    "isMonotonic": true,
+                },
+            }),
+        ];
+        if !latency_points.is_empty() {
+            metrics.push(json!({
+                "name": "package_publisher.registry.latency",
+                "description": "Latency of registry API requests",
+                "unit": "ms",
+                "histogram": {"dataPoints": latency_points, "aggregationTemporality": DELTA},
+            }));
+        }
+
+        json!({
+            "resourceMetrics": [{
+                "resource": self.resource(),
+                "scopeMetrics": [{
+                    "scope": {"name": SCOPE_NAME},
+                    "metrics": metrics,
+                }],
+            }]
+        })
+    }
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({"key": key, "value": {"stringValue": value}})
+}
+
+fn publish_attributes(outcome: &PublishOutcome) -> Vec<Value> {
+    vec![
+        attribute("publish.registry", &outcome.registry),
+        attribute("publish.package", &outcome.package_name),
+        attribute("publish.version", &outcome.version),
+    ]
+}
+
+/// `STATUS_CODE_OK` / `STATUS_CODE_ERROR`
+fn status(outcome: &PublishOutcome) -> Value {
+    if outcome.success {
+        json!({"code": 1})
+    } else {
+        let message = outcome.error.as_deref().map(redact).unwrap_or_default();
+        json!({"code": 2, "message": message})
+    }
+}
+
+/// Stage name as stored in the state file (`DRY_RUN`), lowercased
+fn stage_name(stage: PublishState) -> String {
+    serde_json::to_value(stage)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_lowercase))
+        .unwrap_or_else(|| format!("{:?}", stage).to_lowercase())
+}
+
+/// 8 random bytes as hex
+fn span_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..16].to_string()
+}
+
+/// Nanoseconds since the epoch (OTLP JSON encodes 64-bit integers as strings)
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn outcome() -> PublishOutcome {
+        PublishOutcome {
+            registry: "npm".to_string(),
+            package_name: "demo".to_string(),
+            version: "1.2.3".to_string(),
+            success: false,
+            dry_run: false,
+            error: Some(
+                "E403 forbidden (//registry.npmjs.org/:_authToken=abcdef123456)".to_string(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_payloads() {
+        let exporter = TelemetryExporter::from_config(Some(&TelemetryConfig {
+            endpoint: "http://localhost:4318/".to_string(),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(exporter.endpoint, "http://localhost:4318");
+
+        let mut trace = PublishTrace::start();
+        trace.enter(PublishState::Detecting);
+        trace.enter(PublishState::DryRun);
+        trace.enter(PublishState::Failed);
+        trace.finish();
+        assert_eq!(trace.stages().len(), 2);
+
+        let traces = exporter.traces_payload(&trace, &outcome());
+        let spans = &traces["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans.as_array().unwrap().len(), 3);
+        assert_eq!(spans[0]["status"]["code"], 2);
+        assert_eq!(
+            spans[0]["status"]["message"],
+            "E403 forbidden (//registry.npmjs.org/:_authToken=[REDACTED])"
+        );
+        assert!(!traces.to_string().contains("abcdef123456"));
+        assert_eq!(spans[2]["name"], "publish.dry_run");
+        assert_eq!(spans[2]["parentSpanId"], spans[0]["spanId"]);
+        assert_eq!(spans[0]["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(spans[0]["spanId"].as_str().unwrap().len(), 16);
+
+        let mut latency = BTreeMap::new();
+        latency.insert(
+            "registry.npmjs.org".to_string(),
+            LatencyStats {
+                count: 2,
+                sum: 300.0,
+                min: 100.0,
+                max: 200.0,
+            },
+        );
+        let metrics = exporter.metrics_payload(&trace, &outcome(), &latency);
+        let metrics = &metrics["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(
+            metrics[0]["histogram"]["dataPoints"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            metrics[1]["sum"]["dataPoints"][0]["attributes"][3]["value"]["stringValue"],
+            "failure"
+        );
+        assert_eq!(metrics[2]["histogram"]["dataPoints"][0]["count"], "2");
+
+        assert!(
+            TelemetryExporter::from_config(Some(&TelemetryConfig {
+                enabled: Some(false),
+                endpoint: "http://localhost:4318".to_string(),
+                ..Default::default()
+            }))
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_posts_with_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for _ in 0..2 {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 65536];
+                let n = socket.read(&mut buf).await.unwrap();
+                socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+                    )
+                    .await
+                    .unwrap();
+                requests.push(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+            }
+            requests
+        });
+
+        let exporter = TelemetryExporter::from_config(Some(&TelemetryConfig {
+            endpoint: format!("http://{}", address),
+            headers: Some(HashMap::from([(
+                "x-api-key".to_string(),
+                "secret".to_string(),
+            )])),
+            ..Default::default()
+        }))
+        .unwrap();
+        let mut trace = PublishTrace::start();
+        trace.enter(PublishState::Publishing);
+        trace.finish();
+        exporter.export(&trace, &outcome()).await.unwrap();
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("post /v1/traces"));
+        assert!(requests[1].starts_with("post /v1/metrics"));
+        assert!(requests.iter().all(|r| r.contains("x-api-key: secret")));
+    }
+}
@@ -0,0 +1,638 @@
+//! Publish TUI - interactive terminal view for `publish --tui`
+//!
+//! [`PublishTui`] is an [`EventSink`], a [`Reporter`] and a
+//! [`ConfirmationPrompt`] at once: register it with a publisher and drive the
+//! publish through [`PublishTui::run`], which renders the state of every
+//! registry, the live output of the selected one, validation warnings and a
+//! panel for confirmations and one-time passwords until the user leaves the
+//! screen.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::orchestration::{BatchPublisher, BatchPublishOptions, PublishTui};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let tui = Arc::new(PublishTui::new());
+//! let publisher = BatchPublisher::new(".")
+//!     .with_reporter(tui.clone())
+//!     .with_event_sink(tui.clone())
+//!     .with_prompt(tui.clone());
+//! let registries = vec!["npm".to_string(), "crates.io".to_string()];
+//! let result = tui
+//!     .run(publisher.publish_to_multiple(registries, BatchPublishOptions::default()))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::reporter::{ReportLevel, Reporter};
+use crate::core::state_machine::PublishState;
+use crate::orchestration::events::{ConfirmationPrompt, EventSink, FindingSeverity, PublishEvent};
+use async_trait::async_trait;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Output lines kept per registry
+const MAX_OUTPUT_LINES: usize = 1000;
+
+/// Reporter messages kept for the log pane
+const MAX_LOG_LINES: usize = 500;
+
+/// Redraw and input polling interval
+const TICK: Duration = Duration::from_millis(100);
+
+/// Progress of one registry
+#[derive(Debug)]
+struct RegistryRow {
+    name: String,
+    state: PublishState,
+    output: VecDeque<String>,
+    findings: Vec<(FindingSeverity, String)>,
+}
+
+impl RegistryRow {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: PublishState::Initial,
+            output: VecDeque::new(),
+            findings: Vec::new(),
+        }
+    }
+
+    fn push_output(&mut self, text: &str) {
+        for line in text.lines() {
+            if self.output.len() == MAX_OUTPUT_LINES {
+                self.output.pop_front();
+            }
+            self.output.push_back(line.to_string());
+        }
+    }
+}
+
+/// A question waiting for y/n
+#[derive(Debug)]
+struct PendingConfirmation {
+    registry: Option<String>,
+    message: String,
+    answer: oneshot::Sender<bool>,
+}
+
+/// A one-time password being typed
+#[derive(Debug)]
+struct PendingOtp {
+    registry: String,
+    message: String,
+    input: String,
+    answer: oneshot::Sender<Option<String>>,
+}
+
+/// What a key press did to the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyOutcome {
+    Continue,
+    /// Leave the screen (only once the publish has finished)
+    Exit,
+    /// Ctrl-C: stop the publish
+    Interrupt,
+}
+
+#[derive(Debug, Default)]
+struct TuiState {
+    rows: Vec<RegistryRow>,
+    selected: usize,
+    log: VecDeque<(ReportLevel, String)>,
+    confirmations: VecDeque<PendingConfirmation>,
+    otp_requests: VecDeque<PendingOtp>,
+    /// Stage of the publish before a registry has been selected
+    stage: Option<PublishState>,
+    finished: bool,
+}
+
+impl TuiState {
+    fn row_mut(&mut self, registry: &str) -> &mut RegistryRow {
+        let index = match self.rows.iter().position(|row| row.name == registry) {
+            Some(index) => index,
+            None => {
+                self.rows.push(RegistryRow::new(registry));
+                self.rows.len() - 1
+            }
+        };
+        &mut self.rows[index]
+    }
+
+    fn apply(&mut self, event: &PublishEvent) {
+        match event {
+            PublishEvent::StateChanged { registry, to, .. } => match registry {
+                Some(registry) => self.row_mut(registry).state = *to,
+                None => self.stage = Some(*to),
+            },
+            PublishEvent::ValidationFinding {
+                registry,
+                severity,
+                field,
+                message,
+            } => self
+                .row_mut(registry)
+                .findings
+                .push((*severity, format!("{}: {}", field, message))),
+            PublishEvent::SecretDetected {
+                registry,
+                file,
+                line,
+                secret_type,
+            } => self.row_mut(registry).findings.push((
+                FindingSeverity::Warning,
+                format!("{} in {}:{}", secret_type, file, line),
+            )),
+            PublishEvent::DryRunOutput { registry, chunk } => {
+                self.row_mut(registry).push_output(chunk)
+            }
+            PublishEvent::PublishOutput { registry, line } => {
+                self.row_mut(registry).push_output(line)
+            }
+            PublishEvent::Finished {
+                registry: None,
+                error: Some(error),
+                ..
+            } => self.push_log(ReportLevel::Error, error),
+            PublishEvent::Finished { .. } => {}
+        }
+    }
+
+    fn push_log(&mut self, level: ReportLevel, message: &str) {
+        let message = message.trim();
+        if message.is_empty() {
+            return;
+        }
+        for line in message.lines() {
+            if self.log.len() == MAX_LOG_LINES {
+                self.log.pop_front();
+            }
+            self.log.push_back((level, line.to_string()));
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> KeyOutcome {
+        if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+            return KeyOutcome::Interrupt;
+        }
+
+        // A one-time password takes every key until it is submitted
+        if let Some(pending) = self.otp_requests.front_mut() {
+            match code {
+                KeyCode::Char(c) if !c.is_whitespace() => pending.input.push(c),
+                KeyCode::Backspace => {
+                    pending.input.pop();
+                }
+                KeyCode::Enter | KeyCode::Esc => {
+                    if let Some(pending) = self.otp_requests.pop_front() {
+                        let otp = pending.input.trim().to_string();
+                        let answer = (code == KeyCode::Enter && !otp.is_empty()).then_some(otp);
+                        let _ = pending.answer.send(answer);
+                    }
+                }
+                _ => {}
+            }
+            return KeyOutcome::Continue;
+        }
+
+        if !self.confirmations.is_empty() {
+            let answer = match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Some(true),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Some(false),
+                _ => None,
+            };
+            if let Some(answer) = answer
+                && let Some(pending) = self.confirmations.pop_front()
+            {
+                let _ = pending.answer.send(answer);
+                return KeyOutcome::Continue;
+            }
+        }
+
+        match code {
+            KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') if !self.rows.is_empty() => {
+                self.selected = (self.selected + 1) % self.rows.len();
+            }
+            KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') if !self.rows.is_empty() => {
+                self.selected = (self.selected + self.rows.len() - 1) % self.rows.len();
+            }
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter if self.finished => {
+                return KeyOutcome::Exit;
+            }
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    /// Decline everything still waiting for an answer
+    fn decline_all(&mut self) {
+        for pending in self.confirmations.drain(..) {
+            let _ = pending.answer.send(false);
+        }
+        for pending in self.otp_requests.drain(..) {
+            let _ = pending.answer.send(None);
+        }
+    }
+}
+
+/// Interactive terminal view of a publish
+#[derive(Debug, Default)]
+pub struct PublishTui {
+    state: Mutex<TuiState>,
+}
+
+impl PublishTui {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drive `publish` while rendering its progress
+    ///
+    /// The screen stays up after the publish finished until the user presses
+    /// `q`, so the final state can be read; a summary line per registry is
+    /// printed once the terminal is restored. Ctrl-C stops the publish.
+    pub async fn run<F: Future>(&self, publish: F) -> anyhow::Result<F::Output> {
+        let mut terminal = ratatui::try_init()?;
+        let result = self.event_loop(&mut terminal, publish).await;
+        ratatui::try_restore()?;
+
+        let state = self.state.lock().unwrap();
+        for row in &state.rows {
+            println!("{} {}: {:?}", state_icon(row.state), row.name, row.state);
+        }
+        result
+    }
+
+    async fn event_loop<F: Future>(
+        &self,
+        terminal: &mut DefaultTerminal,
+        publish: F,
+    ) -> anyhow::Result<F::Output> {
+        let mut publish = std::pin::pin!(publish);
+        let mut output = None;
+        let mut tick = tokio::time::interval(TICK);
+
+        loop {
+            tokio::select! {
+                result = &mut publish, if output.is_none() => {
+                    output = Some(result);
+                    self.state.lock().unwrap().finished = true;
+                }
+                _ = tick.tick() => {}
+            }
+
+            terminal.draw(|frame| draw(frame, &self.state.lock().unwrap()))?;
+
+            while event::poll(Duration::ZERO)? {
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                let mut state = self.state.lock().unwrap();
+                match state.handle_key(key.code, key.modifiers) {
+                    KeyOutcome::Continue => {}
+                    KeyOutcome::Exit => {
+                        if let Some(output) = output.take() {
+                            return Ok(output);
+                        }
+                    }
+                    KeyOutcome::Interrupt => {
+                        state.decline_all();
+                        return match output.take() {
+                            Some(output) => Ok(output),
+                            None => Err(anyhow::anyhow!("Publish interrupted")),
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EventSink for PublishTui {
+    fn emit(&self, event: &PublishEvent) {
+        self.state.lock().unwrap().apply(event);
+    }
+}
+
+impl Reporter for PublishTui {
+    fn report(&self, level: ReportLevel, message: &str) {
+        self.state.lock().unwrap().push_log(level, message);
+    }
+}
+
+#[async_trait]
+impl ConfirmationPrompt for PublishTui {
+    async fn confirm(&self, registry: Option<&str>, message: &str) -> anyhow::Result<bool> {
+        let (answer, receiver) = oneshot::channel();
+        self.state
+            .lock()
+            .unwrap()
+            .confirmations
+            .push_back(PendingConfirmation {
+                registry: registry.map(str::to_string),
+                message: message.to_string(),
+                answer,
+            });
+        // A dropped sender means the screen was closed
+        Ok(receiver.await.unwrap_or(false))
+    }
+
+    async fn ask_otp(&self, registry: &str, message: &str) -> anyhow::Result<Option<String>> {
+        let (answer, receiver) = oneshot::channel();
+        self.state
+            .lock()
+            .unwrap()
+            .otp_requests
+            .push_back(PendingOtp {
+                registry: registry.to_string(),
+                message: message.to_string(),
+                input: String::new(),
+                answer,
+            });
+        Ok(receiver.await.unwrap_or(None))
+    }
+}
+
+fn state_icon(state: PublishState) -> &'static str {
+    match state {
+        PublishState::Success => "✅",
+        PublishState::Failed => "❌",
+        PublishState::RolledBack => "↩️",
+        PublishState::Confirming => "❓",
+        PublishState::WaitingApproval => "🔐",
+        _ => "⏳",
+    }
+}
+
+fn level_style(level: ReportLevel) -> Style {
+    match level {
+        ReportLevel::Info => Style::default(),
+        ReportLevel::Success => Style::default().fg(Color::Green),
+        ReportLevel::Warning => Style::default().fg(Color::Yellow),
+        ReportLevel::Error => Style::default().fg(Color::Red),
+    }
+}
+
+/// Last lines that fit into `area` inside its border
+fn tail<'a>(lines: impl ExactSizeIterator<Item = Line<'a>>, area: Rect) -> Vec<Line<'a>> {
+    let visible = area.height.saturating_sub(2) as usize;
+    let skip = lines.len().saturating_sub(visible);
+    lines.skip(skip).collect()
+}
+
+fn draw(frame: &mut Frame, state: &TuiState) {
+    let [header, body, warnings, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(8),
+        Constraint::Length(7),
+        Constraint::Length(4),
+    ])
+    .areas(frame.area());
+    let [registries, right] =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(body);
+    let [output, log] =
+        Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(right);
+
+    let status = if state.finished {
+        "finished".to_string()
+    } else {
+        match state.stage {
+            Some(stage) if state.rows.is_empty() => format!("{:?}", stage),
+            _ => "publishing".to_string(),
+        }
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                "📦 package-publisher",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" — {}", status)),
+        ])),
+        header,
+    );
+
+    let items: Vec<ListItem> = state
+        .rows
+        .iter()
+        .map(|row| {
+            ListItem::new(format!(
+                "{} {} ({:?})",
+                state_icon(row.state),
+                row.name,
+                row.state
+            ))
+        })
+        .collect();
+    let mut list_state =
+        ListState::default().with_selected((!state.rows.is_empty()).then_some(state.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(" Registries "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        registries,
+        &mut list_state,
+    );
+
+    let selected = state.rows.get(state.selected);
+    let output_lines: Vec<Line> = selected
+        .map(|row| {
+            row.output
+                .iter()
+                .map(|line| Line::raw(line.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let title = match selected {
+        Some(row) => format!(" Output: {} ", row.name),
+        None => " Output ".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(tail(output_lines.into_iter(), output))
+            .block(Block::bordered().title(title)),
+        output,
+    );
+
+    let log_lines: Vec<Line> = state
+        .log
+        .iter()
+        .map(|(level, message)| Line::styled(message.as_str(), level_style(*level)))
+        .collect();
+    frame.render_widget(
+        Paragraph::new(tail(log_lines.into_iter(), log)).block(Block::bordered().title(" Log ")),
+        log,
+    );
+
+    let finding_lines: Vec<Line> = state
+        .rows
+        .iter()
+        .flat_map(|row| {
+            row.findings.iter().map(move |(severity, message)| {
+                let level = match severity {
+                    FindingSeverity::Error => ReportLevel::Error,
+                    FindingSeverity::Warning => ReportLevel::Warning,
+                };
+                Line::styled(format!("[{}] {}", row.name, message), level_style(level))
+            })
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(tail(finding_lines.into_iter(), warnings))
+            .block(Block::bordered().title(" Validation ")),
+        warnings,
+    );
+
+    let (title, text, style) = match (state.otp_requests.front(), state.confirmations.front()) {
+        (Some(pending), _) => (
+            format!(" One-time password: {} ", pending.registry),
+            format!(
+                "{}: {}▏  Enter: submit  Esc: skip",
+                pending.message, pending.input
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
+        (None, Some(pending)) => (
+            match &pending.registry {
+                Some(registry) => format!(" Confirm: {} ", registry),
+                None => " Confirm ".to_string(),
+            },
+            format!("{}  [y]es / [n]o", pending.message),
+            Style::default().fg(Color::Yellow),
+        ),
+        (None, None) if state.finished => (
+            String::new(),
+            "Publish finished — q: exit  ↑/↓: select registry".to_string(),
+            Style::default(),
+        ),
+        (None, None) => (
+            String::new(),
+            "↑/↓: select registry  Ctrl-C: stop".to_string(),
+            Style::default(),
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(text)
+            .style(style)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(title)),
+        footer,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn key(tui: &PublishTui, code: KeyCode) -> KeyOutcome {
+        tui.state
+            .lock()
+            .unwrap()
+            .handle_key(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_events_update_rows() {
+        let tui = PublishTui::new();
+        tui.emit(&PublishEvent::StateChanged {
+            registry: Some("npm".to_string()),
+            from: PublishState::Detecting,
+            to: PublishState::Publishing,
+        });
+        tui.emit(&PublishEvent::PublishOutput {
+            registry: "crates.io".to_string(),
+            line: "Uploading demo v1.0.0".to_string(),
+        });
+        tui.emit(&PublishEvent::ValidationFinding {
+            registry: "npm".to_string(),
+            severity: FindingSeverity::Warning,
+            field: "license".to_string(),
+            message: "missing".to_string(),
+        });
+
+        let state = tui.state.lock().unwrap();
+        assert_eq!(state.rows.len(), 2);
+        assert_eq!(state.rows[0].state, PublishState::Publishing);
+        assert_eq!(state.rows[0].findings[0].1, "license: missing");
+        assert_eq!(state.rows[1].output, vec!["Uploading demo v1.0.0"]);
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_answered_by_key() {
+        let tui = std::sync::Arc::new(PublishTui::new());
+        let prompt = tui.clone();
+        let answer =
+            tokio::spawn(async move { prompt.confirm(Some("npm"), "Publish?").await.unwrap() });
+        while tui.state.lock().unwrap().confirmations.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, &tui.state.lock().unwrap()))
+            .unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Confirm: npm"));
+
+        // Leaving is refused while the publish is running
+        assert_eq!(key(&tui, KeyCode::Char('q')), KeyOutcome::Continue);
+        assert_eq!(key(&tui, KeyCode::Char('y')), KeyOutcome::Continue);
+        assert!(answer.await.unwrap());
+
+        tui.state.lock().unwrap().finished = true;
+        assert_eq!(key(&tui, KeyCode::Char('q')), KeyOutcome::Exit);
+    }
+
+    #[tokio::test]
+    async fn test_otp_typed_in_footer() {
+        let tui = std::sync::Arc::new(PublishTui::new());
+        let prompt = tui.clone();
+        let answer = tokio::spawn(async move { prompt.ask_otp("npm", "npm OTP").await.unwrap() });
+        while tui.state.lock().unwrap().otp_requests.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        for c in "1234".chars() {
+            assert_eq!(key(&tui, KeyCode::Char(c)), KeyOutcome::Continue);
+        }
+        // Keys are taken by the input, not by navigation or confirmations
+        key(&tui, KeyCode::Char('q'));
+        key(&tui, KeyCode::Backspace);
+        key(&tui, KeyCode::Char('5'));
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, &tui.state.lock().unwrap()))
+            .unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("One-time password: npm"));
+
+        key(&tui, KeyCode::Enter);
+        assert_eq!(answer.await.unwrap().as_deref(), Some("12345"));
+
+        let prompt = tui.clone();
+        let skipped = tokio::spawn(async move { prompt.ask_otp("npm", "npm OTP").await.unwrap() });
+        while tui.state.lock().unwrap().otp_requests.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        key(&tui, KeyCode::Esc);
+        assert_eq!(skipped.await.unwrap(), None);
+    }
+}
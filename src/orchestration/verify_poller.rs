@@ -0,0 +1,199 @@
+//! Verify Poller - waits for a published version to become available
+//!
+//! Registry indexes lag behind uploads (crates.io sparse index, npm CDN,
+//! PyPI JSON API), so an immediate `verify()` often fails for a publish that
+//! succeeded. `publish.verifyTimeout` keeps retrying verification until the
+//! version appears or the timeout expires; `publish.verifyInterval` is the
+//! first delay, doubled after every attempt up to one minute.
+//!
+//! ```yaml
+//! publish:
+//!   verifyTimeout: "5m"   # default: a single attempt
+//!   verifyInterval: "5s"
+//! ```
+
+use crate::core::config::PublishOptionsConfig;
+use crate::core::traits::{RegistryPlugin, VerificationResult};
+use crate::orchestration::duration_budget::parse_duration;
+use std::time::{Duration, Instant};
+
+/// Default first delay between attempts
+const DEFAULT_VERIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Upper bound of the delay between attempts
+const MAX_VERIFY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Result of polling verification
+#[derive(Debug)]
+pub struct VerifyOutcome {
+    /// Result of the last attempt
+    pub result: anyhow::Result<VerificationResult>,
+    /// Number of `verify()` calls
+    pub attempts: u32,
+    /// Time spent waiting for the version to appear, in milliseconds
+    pub waited: u64,
+}
+
+/// Verification polling with exponential backoff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyPoller {
+    timeout: Duration,
+    interval: Duration,
+}
+
+impl Default for VerifyPoller {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::ZERO,
+            interval: DEFAULT_VERIFY_INTERVAL,
+        }
+    }
+}
+
+impl VerifyPoller {
+    /// Poll for up to `timeout`, starting with `interval` between attempts
+    pub fn new(timeout: Duration, interval: Duration) -> Self {
+        Self { timeout, interval }
+    }
+
+    /// Read `publish.verifyTimeout` and `publish.verifyInterval`
+    pub fn from_config(config: Option<&PublishOptionsConfig>) -> anyhow::Result<Self> {
+        let mut poller = Self::default();
+        let Some(config) = config else {
+            return Ok(poller);
+        };
+        if let Some(ref timeout) = config.verify_timeout {
+            poller.timeout = Duration::from_millis(parse_duration(timeout)?);
+        }
+        if let Some(ref interval) = config.verify_interval {
+            poller.interval = Duration::from_millis(parse_duration(interval)?);
+        }
+        Ok(poller)
+    }
+
+    /// Verify until the version is available or the timeout expires
+    ///
+    /// `on_retry` is called before each wait with the failed attempt number
+    /// and the delay.
+    pub async fn verify(
+        &self,
+        plugin: &dyn RegistryPlugin,
+        mut on_retry: impl FnMut(u32, Duration),
+    ) -> VerifyOutcome {
+        let started = Instant::now();
+        let mut interval = self.interval;
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let result = plugin.verify().await;
+            let verified = result.as_ref().is_ok_and(|r| r.verified);
+            let remaining = self.timeout.saturating_sub(started.elapsed());
+
+            if verified || remaining.is_zero() {
+                return VerifyOutcome {
+                    result,
+                    attempts,
+                    waited: started.elapsed().as_millis() as u64,
+                };
+            }
+
+            let delay = interval.min(remaining);
+            on_retry(attempts, delay);
+            tokio::time::sleep(delay).await;
+            interval = (interval * 2).min(MAX_VERIFY_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::{DryRunResult, PublishOptions, PublishResult, ValidationResult};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Plugin whose version appears after `available_after` verify calls
+    struct LaggingPlugin {
+        calls: AtomicU32,
+        available_after: u32,
+    }
+
+    #[async_trait]
+    impl RegistryPlugin for LaggingPlugin {
+        fn name(&self) -> &str {
+            "lagging"
+        }
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+        async fn detect(&self, _: &str) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+        async fn validate(&self) -> anyhow::Result<ValidationResult> {
+            unimplemented!()
+        }
+        async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+            unimplemented!()
+        }
+        async fn publish(&self, _: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+            unimplemented!()
+        }
+        async fn verify(&self) -> anyhow::Result<VerificationResult> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call < self.available_after {
+                anyhow::bail!("index not updated yet");
+            }
+            Ok(VerificationResult {
+                verified: true,
+                version: Some("1.0.0".to_string()),
+                url: None,
+                error: None,
+                metadata: None,
+                integrity: None,
+            })
+        }
+    }
+
+    fn plugin(available_after: u32) -> LaggingPlugin {
+        LaggingPlugin {
+            calls: AtomicU32::new(0),
+            available_after,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_polls_until_available() {
+        let poller = VerifyPoller::new(Duration::from_secs(5), Duration::from_millis(10));
+        let mut delays = Vec::new();
+        let outcome = poller
+            .verify(&plugin(3), |_, delay| delays.push(delay))
+            .await;
+
+        assert!(outcome.result.unwrap().verified);
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(
+            delays,
+            vec![Duration::from_millis(10), Duration::from_millis(20)]
+        );
+        assert!(outcome.waited >= 30);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_timeout() {
+        let config = PublishOptionsConfig {
+            verify_timeout: Some("50ms".to_string()),
+            verify_interval: Some("20ms".to_string()),
+            ..Default::default()
+        };
+        let poller = VerifyPoller::from_config(Some(&config)).unwrap();
+        let outcome = poller.verify(&plugin(100), |_, _| {}).await;
+        assert!(outcome.result.is_err());
+        assert!((2..=4).contains(&outcome.attempts));
+
+        // Without a timeout there is a single attempt
+        let outcome = VerifyPoller::default().verify(&plugin(2), |_, _| {}).await;
+        assert!(outcome.result.is_err());
+        assert_eq!(outcome.attempts, 1);
+    }
+}
@@ -0,0 +1,145 @@
+//! Project Watcher - file change notifications for `check --watch`
+//!
+//! Changes are debounced so that an editor saving several files (or a
+//! formatter rewriting them) triggers a single re-check. Paths ignored by the
+//! project's `.gitignore` and common build output directories are filtered
+//! out, so builds run by the validation itself do not re-trigger the watch.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::orchestration::watch::ProjectWatcher;
+//! use std::path::Path;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let mut watcher = ProjectWatcher::new(Path::new("."), Duration::from_millis(500))?;
+//! while let Some(changed) = watcher.next_change().await {
+//!     println!("{} file(s) changed", changed.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Default quiet period before a batch of changes triggers a re-check
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Directories that never trigger a re-check (VCS data, dependencies, build output)
+const IGNORED_DIRECTORIES: &[&str] = &[
+    ".git/",
+    "target/",
+    "node_modules/",
+    "dist/",
+    "build/",
+    "__pycache__/",
+    ".venv/",
+    ".tox/",
+    ".pytest_cache/",
+    ".mypy_cache/",
+];
+
+/// Debounced, filtered file change notifications for a project directory
+pub struct ProjectWatcher {
+    root: PathBuf,
+    ignore: Gitignore,
+    receiver: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl ProjectWatcher {
+    /// Start watching `project_path` recursively
+    pub fn new(project_path: &Path, debounce: Duration) -> anyhow::Result<Self> {
+        let root = project_path.canonicalize()?;
+
+        let mut builder = GitignoreBuilder::new(&root);
+        for directory in IGNORED_DIRECTORIES {
+            builder.add_line(None, directory)?;
+        }
+        builder.add(root.join(".gitignore"));
+        let ignore = builder.build()?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut debouncer = new_debouncer(debounce, move |result: DebounceEventResult| {
+            if let Ok(events) = result {
+                let _ = sender.send(events.into_iter().map(|e| e.path).collect());
+            }
+        })?;
+        debouncer.watcher().watch(&root, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            root,
+            ignore,
+            receiver,
+            _debouncer: debouncer,
+        })
+    }
+
+    /// Whether a change to `path` should trigger a re-check
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        !relative.as_os_str().is_empty()
+            && !self
+                .ignore
+                .matched_path_or_any_parents(relative, path.is_dir())
+                .is_ignore()
+    }
+
+    /// Wait for the next batch of relevant changes (paths relative to the project)
+    ///
+    /// Returns `None` when the watcher has stopped.
+    pub async fn next_change(&mut self) -> Option<Vec<PathBuf>> {
+        loop {
+            let paths = self.receiver.recv().await?;
+            let mut changed: Vec<PathBuf> = paths
+                .iter()
+                .filter(|path| self.is_relevant(path))
+                .filter_map(|path| path.strip_prefix(&self.root).ok())
+                .map(Path::to_path_buf)
+                .collect();
+            changed.sort();
+            changed.dedup();
+            if !changed.is_empty() {
+                return Some(changed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_reports_relevant_changes_only() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "*.log\n").unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+
+        let mut watcher = ProjectWatcher::new(temp_dir.path(), Duration::from_millis(50)).unwrap();
+        let root = watcher.root.clone();
+        assert!(watcher.is_relevant(&root.join("src/lib.rs")));
+        assert!(!watcher.is_relevant(&root.join("target/debug/build.rs")));
+        assert!(!watcher.is_relevant(&root.join("node_modules/pkg/index.js")));
+        assert!(!watcher.is_relevant(&root.join("debug.log")));
+        assert!(!watcher.is_relevant(Path::new("/elsewhere/Cargo.toml")));
+
+        std::fs::write(temp_dir.path().join("target/output.bin"), "ignored").unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "[package]").unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(5), watcher.next_change())
+            .await
+            .expect("no change reported")
+            .unwrap();
+        assert_eq!(changed, vec![PathBuf::from("Cargo.toml")]);
+    }
+}
@@ -0,0 +1,396 @@
+//! Webhook - Sends signed publish lifecycle events
+//!
+//! Every webhook under `webhooks:` receives a JSON POST when a publish
+//! starts, succeeds or fails and when a rollback finishes. With a `secret`
+//! the body is signed with HMAC-SHA256:
+//!
+//! ```text
+//! X-Package-Publisher-Event: publish.succeeded
+//! X-Package-Publisher-Delivery: 6f1c...
+//! X-Package-Publisher-Signature: sha256=<hex digest of the body>
+//! ```
+//!
+//! Network errors and 5xx/429 responses are retried; delivery failures never
+//! fail a publish and are returned as [`WebhookDelivery`]s.
+
+use crate::core::config::{PublishConfig, WebhookConfig};
+use crate::core::http::http_client;
+use crate::core::retry::{RetryManager, RetryOptions};
+use crate::orchestration::notifier::NotificationContext;
+use crate::security::redaction::redact;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// Webhook request timeout
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default delivery attempts per event
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// SHA-256 block size, used by HMAC
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// Publish lifecycle event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "publish.started")]
+    PublishStarted,
+    #[serde(rename = "publish.succeeded")]
+    PublishSucceeded,
+    #[serde(rename = "publish.failed")]
+    PublishFailed,
+    #[serde(rename = "rollback.succeeded")]
+    RollbackSucceeded,
+    #[serde(rename = "rollback.failed")]
+    RollbackFailed,
+}
+
+impl WebhookEvent {
+    /// Event name, as used in `events:` and the event header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PublishStarted => "publish.started",
+            Self::PublishSucceeded => "publish.succeeded",
+            Self::PublishFailed => "publish.failed",
+            Self::RollbackSucceeded => "rollback.succeeded",
+            Self::RollbackFailed => "rollback.failed",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// JSON body of a webhook delivery
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub delivery_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub package_name: String,
+    pub version: String,
+    pub registry: String,
+    /// Duration in milliseconds (not sent with `publish.started`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl WebhookPayload {
+    /// Build the payload of `event` from a notification context
+    pub fn new(event: WebhookEvent, context: &NotificationContext) -> Self {
+        Self {
+            event,
+            delivery_id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            package_name: context.package_name.clone(),
+            version: context.version.clone(),
+            registry: context.registry.clone(),
+            duration: (event != WebhookEvent::PublishStarted).then_some(context.duration),
+            error: context.error.as_deref().map(redact),
+        }
+    }
+}
+
+/// Result of delivering an event to a single webhook
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub url: String,
+    pub success: bool,
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+
+/// Sends events to the webhooks configured under `webhooks:`
+pub struct WebhookEmitter {
+    webhooks: Vec<WebhookConfig>,
+    client: reqwest::Client,
+    retry_delay: Duration,
+}
+
+impl WebhookEmitter {
+    /// Create a new WebhookEmitter
+    pub fn new(webhooks: Vec<WebhookConfig>) -> Self {
+        Self {
+            webhooks,
+            client: http_client(),
+            retry_delay: RetryOptions::default().initial_delay,
+        }
+    }
+
+    /// Build an emitter when webhooks are configured
+    pub fn from_config(config: Option<&PublishConfig>) -> Option<Self> {
+        config
+            .and_then(|c| c.webhooks.as_ref())
+            .filter(|webhooks| !webhooks.is_empty())
+            .map(|webhooks| Self::new(webhooks.clone()))
+    }
+
+    /// Set the delay before the first retry (doubled for each further one)
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Send `payload` to every webhook subscribed to its event
+    pub async fn emit(&self, payload: &WebhookPayload) -> Vec<WebhookDelivery> {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                return vec![WebhookDelivery {
+                    url: String::new(),
+                    success: false,
+                    attempts: 0,
+                    error: Some(e.to_string()),
+                }];
+            }
+        };
+
+        let mut deliveries = Vec::new();
+        for webhook in self
+            .webhooks
+            .iter()
+            .filter(|w| subscribed(w, payload.event))
+        {
+            deliveries.push(self.deliver(webhook, payload, &body).await);
+        }
+        deliveries
+    }
+
+    /// POST the body to one webhook, retrying transient failures
+    async fn deliver(
+        &self,
+        webhook: &WebhookConfig,
+        payload: &WebhookPayload,
+        body: &[u8],
+    ) -> WebhookDelivery {
+        let attempts = AtomicU32::new(0);
+        let manager = RetryManager::new(RetryOptions {
+            max_attempts: webhook.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            initial_delay: self.retry_delay,
+            ..Default::default()
+        });
+        let result = manager
+            .retry(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                self.post(webhook, payload, body)
+            })
+            .await;
+
+        WebhookDelivery {
+            url: webhook.url.clone(),
+            success: result.is_ok(),
+            attempts: attempts.into_inner(),
+            error: result.err().map(|e| e.to_string()),
+        }
+    }
+
+    /// Single delivery attempt
+    async fn post(
+        &self,
+        webhook: &WebhookConfig,
+        payload: &WebhookPayload,
+        body: &[u8],
+    ) -> Result<(), anyhow::Error> {
+        let mut request = self
+            .client
+            .post(&webhook.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Package-Publisher-Event", payload.event.as_str())
+            .header("X-Package-Publisher-Delivery", &payload.delivery_id);
+        if let Some(secret) = webhook.secret.as_deref() {
+            request = request.header("X-Package-Publisher-Signature", sign(secret, body));
+        }
+        for (name, value) in webhook.headers.iter().flatten() {
+            request = request.header(name, value);
+        }
+
+        // Send errors carry "network error" so that the retry treats them as
+        // transient
+        let response = request
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Webhook network error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Webhook returned HTTP {}",
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether a webhook receives `event`
+fn subscribed(webhook: &WebhookConfig, event: WebhookEvent) -> bool {
+    webhook
+        .events
+        .as_ref()
+        .is_none_or(|events| events.iter().any(|e| e == event.as_str()))
+}
+
+/// Signature header value of a body: `sha256=<hex HMAC-SHA256>`
+///
+/// # Examples
+///
+/// ```
+/// use package_publisher::orchestration::webhook::sign;
+///
+/// assert_eq!(
+///     sign("Jefe", b"what do ya want for nothing?"),
+///     "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+/// );
+/// ```
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    format!(
+        "sha256={}",
+        hex::encode(hmac_sha256(secret.as_bytes(), body))
+    )
+}
+
+/// HMAC-SHA256 (RFC 2104)
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; SHA256_BLOCK_SIZE];
+    if key.len() > SHA256_BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner = Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn context() -> NotificationContext {
+        NotificationContext {
+            package_name: "my-pkg".to_string(),
+            version: "1.2.3".to_string(),
+            registry: "npm".to_string(),
+            duration: 4_200,
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // RFC 4231 test case 6: key longer than the block size
+        let key = [0xaa; 131];
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &key,
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_event_subscription_and_payload() {
+        let mut webhook = WebhookConfig {
+            url: "http://localhost".to_string(),
+            ..Default::default()
+        };
+        assert!(subscribed(&webhook, WebhookEvent::PublishStarted));
+        webhook.events = Some(vec!["publish.failed".to_string()]);
+        assert!(!subscribed(&webhook, WebhookEvent::PublishStarted));
+        assert!(subscribed(&webhook, WebhookEvent::PublishFailed));
+
+        let started = serde_json::to_value(WebhookPayload::new(
+            WebhookEvent::PublishStarted,
+            &context(),
+        ))
+        .unwrap();
+        assert_eq!(started["event"], "publish.started");
+        assert_eq!(started["packageName"], "my-pkg");
+        assert!(started.get("duration").is_none());
+        let succeeded = serde_json::to_value(WebhookPayload::new(
+            WebhookEvent::PublishSucceeded,
+            &context(),
+        ))
+        .unwrap();
+        assert_eq!(succeeded["duration"], 4_200);
+    }
+
+    #[tokio::test]
+    async fn test_signed_delivery_is_retried() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n")
+                        && let Some(length) = head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length: "))
+                        && body.len() >= length.trim().parse::<usize>().unwrap()
+                    {
+                        break;
+                    }
+                }
+                received
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(request).unwrap());
+                let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\n\r\n", status);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let emitter = WebhookEmitter::new(vec![WebhookConfig {
+            url: url.clone(),
+            secret: Some("s3cret".to_string()),
+            ..Default::default()
+        }])
+        .with_retry_delay(Duration::from_millis(10));
+        let payload = WebhookPayload::new(WebhookEvent::PublishSucceeded, &context());
+        let deliveries = emitter.emit(&payload).await;
+
+        assert_eq!(deliveries.len(), 1);
+        assert!(deliveries[0].success, "{:?}", deliveries[0].error);
+        assert_eq!(deliveries[0].attempts, 2);
+
+        let requests = requests.lock().unwrap();
+        let (head, body) = requests[1].split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("x-package-publisher-event: publish.succeeded"));
+        assert!(head.contains(&format!(
+            "x-package-publisher-signature: {}",
+            sign("s3cret", body.as_bytes())
+        )));
+        assert!(body.contains(&payload.delivery_id));
+    }
+}
@@ -0,0 +1,726 @@
+//! Workspace Scanner - Discovers publishable packages in a monorepo
+//!
+//! Supported layouts:
+//! - npm / yarn workspaces (`workspaces` in the root package.json)
+//! - pnpm workspaces (`packages` in pnpm-workspace.yaml, `!` excludes)
+//! - Cargo workspaces (`[workspace] members` / `exclude`)
+//! - Python packages (`[tool.uv.workspace] members`, otherwise every
+//!   pyproject.toml up to two levels below the root)
+//!
+//! Private packages (`"private": true`, `publish = false`, the
+//! `Private :: Do Not Upload` classifier) are skipped. Packages are returned
+//! in dependency order so workspace dependencies are published first.
+
+use crate::core::git::run_git;
+use crate::plugins::plugin_loader::{PluginLoader, RegistryType};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for workspace members
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "venv"];
+
+/// Depth searched for pyproject.toml files without a uv workspace
+const PYTHON_SEARCH_DEPTH: usize = 2;
+
+/// Package ecosystem of a workspace member
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceEcosystem {
+    Npm,
+    Cargo,
+    Python,
+}
+
+impl WorkspaceEcosystem {
+    /// Manifest file of a package
+    pub fn manifest(&self) -> &'static str {
+        match self {
+            WorkspaceEcosystem::Npm => "package.json",
+            WorkspaceEcosystem::Cargo => "Cargo.toml",
+            WorkspaceEcosystem::Python => "pyproject.toml",
+        }
+    }
+
+    /// Default registry of the ecosystem
+    pub fn registry_type(&self) -> RegistryType {
+        match self {
+            WorkspaceEcosystem::Npm => RegistryType::Npm,
+            WorkspaceEcosystem::Cargo => RegistryType::Crates,
+            WorkspaceEcosystem::Python => RegistryType::PyPI,
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceEcosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WorkspaceEcosystem::Npm => "npm",
+            WorkspaceEcosystem::Cargo => "cargo",
+            WorkspaceEcosystem::Python => "python",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A publishable package of the workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub version: Option<String>,
+    pub ecosystem: WorkspaceEcosystem,
+    /// Package directory
+    pub path: PathBuf,
+    /// Directory relative to the workspace root (`/`-separated, `.` for the root)
+    pub relative_path: String,
+    /// Names of other workspace packages this package depends on
+    pub dependencies: Vec<String>,
+}
+
+/// Manifest data read from a member directory
+struct Manifest {
+    name: String,
+    version: Option<String>,
+    private: bool,
+    dependencies: Vec<String>,
+}
+
+/// WorkspaceScanner - discovers and selects packages under a root
+pub struct WorkspaceScanner {
+    root: PathBuf,
+}
+
+impl WorkspaceScanner {
+    /// Create a scanner for the workspace at `root`
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Discover all publishable packages, in dependency order
+    pub async fn scan(&self) -> anyhow::Result<Vec<WorkspacePackage>> {
+        let root = self.root.clone();
+        tokio::task::spawn_blocking(move || scan_workspace(&root)).await?
+    }
+
+    /// Packages whose name or directory matches one of the glob patterns
+    ///
+    /// `*` matches within a path segment, `**` across segments.
+    pub fn select(packages: &[WorkspacePackage], patterns: &[String]) -> Vec<WorkspacePackage> {
+        let matchers: Vec<Regex> = patterns.iter().map(|p| glob_regex(p)).collect();
+        packages
+            .iter()
+            .filter(|package| {
+                matchers
+                    .iter()
+                    .any(|m| m.is_match(&package.name) || m.is_match(&package.relative_path))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Packages changed since `git_ref` and the packages depending on them
+    pub async fn affected_since(
+        &self,
+        packages: &[WorkspacePackage],
+        git_ref: &str,
+    ) -> anyhow::Result<Vec<WorkspacePackage>> {
+        let changed = self.changed_since(packages, git_ref).await?;
+        Ok(with_dependents(packages, &changed))
+    }
+
+    /// Whether the package's current version is already on its registry
+    ///
+    /// Uses the registry plugin's verification; lookup failures count as
+    /// not published so the publish itself reports the problem.
+    pub async fn is_published(package: &WorkspacePackage) -> bool {
+        let Ok(plugin) = PluginLoader::new().load_plugin(
+            package.ecosystem.registry_type(),
+            &package.path.to_string_lossy(),
+        ) else {
+            return false;
+        };
+        plugin.verify().await.is_ok_and(|result| result.verified)
+    }
+
+    /// Packages with files changed between `git_ref` and the working tree
+    pub async fn changed_since(
+        &self,
+        packages: &[WorkspacePackage],
+        git_ref: &str,
+    ) -> anyhow::Result<Vec<WorkspacePackage>> {
+        let diff = run_git(
+            &self.root,
+            &["diff", "--name-only", "--relative", git_ref, "--"],
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("git diff {} failed: {}", git_ref, e))?;
+
+        let changed: BTreeSet<String> = diff
+            .lines()
+            .filter_map(|file| owning_package(packages, file))
+            .collect();
+        Ok(packages
+            .iter()
+            .filter(|package| changed.contains(&package.name))
+            .cloned()
+            .collect())
+    }
+}
+
+/// `changed` plus every package that depends on one of them, transitively
+/// (in the order of `packages`)
+pub fn with_dependents(
+    packages: &[WorkspacePackage],
+    changed: &[WorkspacePackage],
+) -> Vec<WorkspacePackage> {
+    let mut affected: HashSet<&str> = changed.iter().map(|p| p.name.as_str()).collect();
+    loop {
+        let before = affected.len();
+        for package in packages {
+            if package
+                .dependencies
+                .iter()
+                .any(|dep| affected.contains(dep.as_str()))
+            {
+                affected.insert(&package.name);
+            }
+        }
+        if affected.len() == before {
+            break;
+        }
+    }
+    packages
+        .iter()
+        .filter(|package| affected.contains(package.name.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Name of the package whose directory contains `file` (deepest match wins)
+pub(crate) fn owning_package(packages: &[WorkspacePackage], file: &str) -> Option<String> {
+    packages
+        .iter()
+        .filter(|package| {
+            package.relative_path == "."
+                || file
+                    .strip_prefix(&package.relative_path)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|package| match package.relative_path.as_str() {
+            "." => 0,
+            path => path.len(),
+        })
+        .map(|package| package.name.clone())
+}
+
+/// Regex for a workspace glob (`*` within a segment, `**` across segments)
+pub(crate) fn glob_regex(pattern: &str) -> Regex {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches zero segments
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("escaped glob is a valid regex")
+}
+
+/// Member directories matching include patterns but no exclude pattern
+fn expand_members(root: &Path, includes: &[String], excludes: &[String]) -> Vec<PathBuf> {
+    let includes: Vec<Regex> = includes.iter().map(|p| glob_regex(p)).collect();
+    let excludes: Vec<Regex> = excludes.iter().map(|p| glob_regex(p)).collect();
+    if includes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut members: Vec<PathBuf> = list_dirs(root, usize::MAX)
+        .into_iter()
+        .filter(|relative| {
+            includes.iter().any(|m| m.is_match(relative))
+                && !excludes.iter().any(|m| m.is_match(relative))
+        })
+        .map(|relative| root.join(relative))
+        .collect();
+    members.sort();
+    members
+}
+
+/// Directories below `root` (relative, `/`-separated), skipping hidden and build dirs
+fn list_dirs(root: &Path, max_depth: usize) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut stack = vec![(root.to_path_buf(), String::new(), 0)];
+    while let Some((dir, relative, depth)) = stack.pop() {
+        if depth >= max_depth {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.')
+                || SKIPPED_DIRS.contains(&name.as_str())
+                || !entry.file_type().is_ok_and(|t| t.is_dir())
+            {
+                continue;
+            }
+            let child = if relative.is_empty() {
+                name
+            } else {
+                format!("{}/{}", relative, name)
+            };
+            dirs.push(child.clone());
+            stack.push((entry.path(), child, depth + 1));
+        }
+    }
+    dirs
+}
+
+fn string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn toml_string_list(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Split patterns into includes and `!` excludes
+fn partition_patterns(patterns: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let (excludes, includes): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let excludes = excludes
+        .into_iter()
+        .map(|p| p.trim_start_matches('!').to_string())
+        .collect();
+    (includes, excludes)
+}
+
+/// Member directories of each ecosystem declared at the root
+fn workspace_members(root: &Path) -> Vec<(WorkspaceEcosystem, PathBuf)> {
+    let mut members = Vec::new();
+
+    // npm / yarn: "workspaces": [...] or { "packages": [...] }
+    if let Ok(content) = std::fs::read_to_string(root.join("package.json"))
+        && let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content)
+    {
+        let workspaces = &manifest["workspaces"];
+        let patterns = match workspaces.get("packages") {
+            Some(packages) => string_list(Some(packages)),
+            None => string_list(Some(workspaces)),
+        };
+        let (includes, excludes) = partition_patterns(patterns);
+        for dir in expand_members(root, &includes, &excludes) {
+            members.push((WorkspaceEcosystem::Npm, dir));
+        }
+        members.push((WorkspaceEcosystem::Npm, root.to_path_buf()));
+    }
+
+    // pnpm
+    if let Ok(content) = std::fs::read_to_string(root.join("pnpm-workspace.yaml"))
+        && let Ok(manifest) = serde_yaml::from_str::<serde_json::Value>(&content)
+    {
+        let (includes, excludes) = partition_patterns(string_list(manifest.get("packages")));
+        for dir in expand_members(root, &includes, &excludes) {
+            members.push((WorkspaceEcosystem::Npm, dir));
+        }
+    }
+
+    // Cargo
+    if let Ok(content) = std::fs::read_to_string(root.join("Cargo.toml"))
+        && let Ok(manifest) = toml::from_str::<toml::Table>(&content)
+    {
+        if let Some(workspace) = manifest.get("workspace") {
+            let includes = toml_string_list(workspace.get("members"));
+            let excludes = toml_string_list(workspace.get("exclude"));
+            for dir in expand_members(root, &includes, &excludes) {
+                members.push((WorkspaceEcosystem::Cargo, dir));
+            }
+        }
+        members.push((WorkspaceEcosystem::Cargo, root.to_path_buf()));
+    }
+
+    // Python: uv workspace, otherwise nearby pyproject.toml files
+    let uv_workspace = std::fs::read_to_string(root.join("pyproject.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+        .and_then(|manifest| {
+            manifest
+                .get("tool")
+                .and_then(|t| t.get("uv"))
+                .and_then(|u| u.get("workspace"))
+                .cloned()
+        });
+    match uv_workspace {
+        Some(workspace) => {
+            let includes = toml_string_list(workspace.get("members"));
+            let excludes = toml_string_list(workspace.get("exclude"));
+            for dir in expand_members(root, &includes, &excludes) {
+                members.push((WorkspaceEcosystem::Python, dir));
+            }
+        }
+        None => {
+            let mut dirs = list_dirs(root, PYTHON_SEARCH_DEPTH);
+            dirs.sort();
+            for relative in dirs {
+                members.push((WorkspaceEcosystem::Python, root.join(relative)));
+            }
+        }
+    }
+    members.push((WorkspaceEcosystem::Python, root.to_path_buf()));
+
+    members
+}
+
+/// Read the manifest of `ecosystem` in `dir`, if there is one
+fn read_manifest(
+    ecosystem: WorkspaceEcosystem,
+    dir: &Path,
+    root_cargo: Option<&toml::Table>,
+) -> Option<Manifest> {
+    let content = std::fs::read_to_string(dir.join(ecosystem.manifest())).ok()?;
+    match ecosystem {
+        WorkspaceEcosystem::Npm => {
+            let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let dependencies = [
+                "dependencies",
+                "devDependencies",
+                "peerDependencies",
+                "optionalDependencies",
+            ]
+            .iter()
+            .filter_map(|section| manifest[*section].as_object())
+            .flat_map(|deps| deps.keys().cloned())
+            .collect();
+            Some(Manifest {
+                name: manifest["name"].as_str()?.to_string(),
+                version: manifest["version"].as_str().map(str::to_string),
+                private: manifest["private"].as_bool() == Some(true),
+                dependencies,
+            })
+        }
+        WorkspaceEcosystem::Cargo => {
+            let manifest: toml::Table = toml::from_str(&content).ok()?;
+            let package = manifest.get("package")?;
+            let version = match package.get("version") {
+                Some(toml::Value::String(version)) => Some(version.clone()),
+                // version.workspace = true
+                Some(_) => root_cargo
+                    .and_then(|root| root.get("workspace"))
+                    .and_then(|w| w.get("package"))
+                    .and_then(|p| p.get("version"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                None => None,
+            };
+            let dependencies = ["dependencies", "dev-dependencies", "build-dependencies"]
+                .iter()
+                .filter_map(|section| manifest.get(*section).and_then(|d| d.as_table()))
+                .flat_map(|deps| {
+                    deps.iter().map(|(key, spec)| {
+                        spec.get("package")
+                            .and_then(|p| p.as_str())
+                            .unwrap_or(key)
+                            .to_string()
+                    })
+                })
+                .collect();
+            Some(Manifest {
+                name: package.get("name")?.as_str()?.to_string(),
+                version,
+                private: package.get("publish").and_then(|p| p.as_bool()) == Some(false),
+                dependencies,
+            })
+        }
+        WorkspaceEcosystem::Python => {
+            let manifest: toml::Table = toml::from_str(&content).ok()?;
+            let project = manifest.get("project")?;
+            let requirement_name = Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)").unwrap();
+            let dependencies = toml_string_list(project.get("dependencies"))
+                .iter()
+                .filter_map(|req| requirement_name.captures(req))
+                .map(|caps| normalize_python_name(&caps[1]))
+                .collect();
+            let private = toml_string_list(project.get("classifiers"))
+                .iter()
+                .any(|c| c.starts_with("Private ::"));
+            Some(Manifest {
+                name: project.get("name")?.as_str()?.to_string(),
+                version: project
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                private,
+                dependencies,
+            })
+        }
+    }
+}
+
+/// PEP 503 normalized project name
+fn normalize_python_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+fn scan_workspace(root: &Path) -> anyhow::Result<Vec<WorkspacePackage>> {
+    if !root.is_dir() {
+        anyhow::bail!("Workspace root not found: {}", root.display());
+    }
+    let root_cargo = std::fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Table>(&content).ok());
+
+    let mut packages: Vec<WorkspacePackage> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut manifests = Vec::new();
+    for (ecosystem, dir) in workspace_members(root) {
+        if !seen.insert((ecosystem, dir.clone())) {
+            continue;
+        }
+        let Some(manifest) = read_manifest(ecosystem, &dir, root_cargo.as_ref()) else {
+            continue;
+        };
+        if manifest.private {
+            continue;
+        }
+        let relative_path = dir
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        manifests.push(manifest.dependencies.clone());
+        packages.push(WorkspacePackage {
+            name: manifest.name,
+            version: manifest.version,
+            ecosystem,
+            path: dir,
+            relative_path: if relative_path.is_empty() {
+                ".".to_string()
+            } else {
+                relative_path
+            },
+            dependencies: Vec::new(),
+        });
+    }
+
+    // Keep only dependencies on other workspace packages of the same ecosystem
+    let names: HashMap<(WorkspaceEcosystem, String), String> = packages
+        .iter()
+        .map(|p| {
+            (
+                (p.ecosystem, dependency_key(p.ecosystem, &p.name)),
+                p.name.clone(),
+            )
+        })
+        .collect();
+    for (package, dependencies) in packages.iter_mut().zip(manifests) {
+        let mut internal: Vec<String> = dependencies
+            .iter()
+            .filter_map(|dep| {
+                names
+                    .get(&(package.ecosystem, dependency_key(package.ecosystem, dep)))
+                    .cloned()
+            })
+            .filter(|name| *name != package.name)
+            .collect();
+        internal.sort();
+        internal.dedup();
+        package.dependencies = internal;
+    }
+
+    Ok(dependency_order(packages))
+}
+
+fn dependency_key(ecosystem: WorkspaceEcosystem, name: &str) -> String {
+    match ecosystem {
+        WorkspaceEcosystem::Python => normalize_python_name(name),
+        _ => name.to_string(),
+    }
+}
+
+/// Order packages so dependencies come first (stable; cycles keep their order)
+fn dependency_order(packages: Vec<WorkspacePackage>) -> Vec<WorkspacePackage> {
+    let mut remaining = packages;
+    let mut ordered: Vec<WorkspacePackage> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|package| {
+            package
+                .dependencies
+                .iter()
+                .all(|dep| !remaining.iter().any(|other| other.name == *dep))
+        });
+        ordered.push(remaining.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scan_npm_and_cargo_workspaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "package.json",
+            r#"{"name": "root", "private": true, "workspaces": ["packages/*", "!packages/internal"]}"#,
+        );
+        write(
+            root,
+            "packages/ui/package.json",
+            r#"{"name": "@acme/ui", "version": "1.0.0", "dependencies": {"@acme/core": "^1.0.0", "react": "^18"}}"#,
+        );
+        write(
+            root,
+            "packages/core/package.json",
+            r#"{"name": "@acme/core", "version": "1.1.0"}"#,
+        );
+        write(
+            root,
+            "packages/internal/package.json",
+            r#"{"name": "@acme/internal", "version": "0.0.1"}"#,
+        );
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.4.0\"\n",
+        );
+        write(
+            root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"acme-cli\"\nversion.workspace = true\n\n[dependencies]\nacme = { path = \"../lib\", package = \"acme-lib\" }\n",
+        );
+        write(
+            root,
+            "crates/lib/Cargo.toml",
+            "[package]\nname = \"acme-lib\"\nversion = \"0.4.1\"\n",
+        );
+        write(
+            root,
+            "crates/bench/Cargo.toml",
+            "[package]\nname = \"acme-bench\"\nversion = \"0.0.0\"\npublish = false\n",
+        );
+
+        let packages = WorkspaceScanner::new(root).scan().await.unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["@acme/core", "@acme/ui", "acme-lib", "acme-cli"]
+        );
+
+        let cli = &packages[3];
+        assert_eq!(cli.relative_path, "crates/cli");
+        assert_eq!(cli.version.as_deref(), Some("0.4.0"));
+        assert_eq!(cli.dependencies, vec!["acme-lib"]);
+        assert_eq!(packages[1].dependencies, vec!["@acme/core"]);
+
+        let selected = WorkspaceScanner::select(&packages, &["@acme/*".to_string()]);
+        assert_eq!(selected.len(), 2);
+        let selected = WorkspaceScanner::select(&packages, &["crates/**".to_string()]);
+        assert_eq!(selected.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_changed_since_maps_files_to_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "pyproject.toml",
+            "[tool.uv.workspace]\nmembers = [\"libs/*\"]\n",
+        );
+        write(
+            root,
+            "libs/alpha/pyproject.toml",
+            "[project]\nname = \"acme_alpha\"\nversion = \"1.0.0\"\n",
+        );
+        write(
+            root,
+            "libs/beta/pyproject.toml",
+            "[project]\nname = \"acme-beta\"\nversion = \"1.0.0\"\ndependencies = [\"Acme.Alpha>=1.0\"]\n",
+        );
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "{:?}", status);
+        };
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "init",
+        ]);
+        write(root, "libs/beta/src/acme_beta/__init__.py", "");
+        git(&["add", "."]);
+
+        let scanner = WorkspaceScanner::new(root);
+        let packages = scanner.scan().await.unwrap();
+        assert_eq!(packages[1].dependencies, vec!["acme_alpha"]);
+
+        let changed = scanner.changed_since(&packages, "HEAD").await.unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].name, "acme-beta");
+
+        // A change to alpha also affects beta, which depends on it
+        write(root, "libs/alpha/README.md", "docs\n");
+        git(&["add", "."]);
+        let affected = scanner.affected_since(&packages, "HEAD").await.unwrap();
+        let names: Vec<&str> = affected.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["acme_alpha", "acme-beta"]);
+        assert!(
+            scanner
+                .changed_since(&packages, "no-such-ref")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,807 @@
+//! AUR Plugin - Arch User Repository package publishing
+//!
+//! Provides AUR publishing support:
+//! - PKGBUILD detection (project root or `aur/`)
+//! - `pkgver` update from the project manifest (`pkgrel` is reset to 1)
+//! - `sha256sums` recomputed from the (downloaded) sources
+//! - `.SRCINFO` regeneration with `makepkg --printsrcinfo`, or from the
+//!   PKGBUILD fields when makepkg is not installed (non-Arch CI runners)
+//! - Commit and push to the package's AUR git remote over SSH
+//! - Verification through the AUR RPC API
+
+use crate::core::config::AurRegistryConfig;
+use crate::core::git::git_stdout;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::plugins::bucket_plugin::{manifest_identity, sha256_file};
+use crate::plugins::homebrew_plugin::HomebrewPlugin;
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const AUR_URL: &str = "https://aur.archlinux.org";
+
+const DEFAULT_REMOTE: &str = "ssh://aur@aur.archlinux.org/{pkgbase}.git";
+
+/// Scalar `.SRCINFO` fields, in makepkg order
+const SRCINFO_SCALARS: &[&str] = &[
+    "pkgdesc",
+    "pkgver",
+    "pkgrel",
+    "epoch",
+    "url",
+    "install",
+    "changelog",
+];
+
+/// Array `.SRCINFO` fields, in makepkg order
+const SRCINFO_ARRAYS: &[&str] = &[
+    "arch",
+    "groups",
+    "license",
+    "checkdepends",
+    "makedepends",
+    "depends",
+    "optdepends",
+    "provides",
+    "conflicts",
+    "replaces",
+    "backup",
+    "options",
+    "source",
+    "validpgpkeys",
+    "noextract",
+    "b2sums",
+    "sha256sums",
+    "sha512sums",
+];
+
+/// Split a shell word list, honouring quotes and `#` comments
+fn shell_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for line in input.lines() {
+        let mut word = String::new();
+        let mut in_word = false;
+        let mut quote = None;
+        for c in line.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), c) => word.push(c),
+                (None, '\'' | '"') => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                (None, '#') if !in_word => break,
+                (None, c) if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                (None, c) => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        if in_word {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// A PKGBUILD, read and updated through its plain variable assignments
+///
+/// Only top-level `key=value` / `key=(...)` assignments are understood;
+/// anything computed in bash is left to makepkg.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pkgbuild {
+    pub content: String,
+}
+
+impl Pkgbuild {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+
+    fn raw(&self, key: &str) -> Option<String> {
+        let array = Regex::new(&format!(r"(?ms)^{}=\((.*?)\)", regex::escape(key))).unwrap();
+        if let Some(captures) = array.captures(&self.content) {
+            return Some(captures[1].to_string());
+        }
+        Regex::new(&format!(r"(?m)^{}=(.*)$", regex::escape(key)))
+            .unwrap()
+            .captures(&self.content)
+            .map(|c| c[1].to_string())
+    }
+
+    /// Expand `$var` / `${var}` references to other plain assignments
+    fn expand(&self, value: &str) -> String {
+        let reference = Regex::new(r"\$\{(\w+)\}|\$(\w+)").unwrap();
+        let mut expanded = value.to_string();
+        for _ in 0..4 {
+            if !expanded.contains('$') {
+                break;
+            }
+            expanded = reference
+                .replace_all(&expanded, |c: &regex::Captures| {
+                    let name = c.get(1).or(c.get(2)).unwrap().as_str();
+                    match self.raw(name) {
+                        Some(raw) => shell_words(&raw).join(" "),
+                        None => c[0].to_string(),
+                    }
+                })
+                .to_string();
+        }
+        expanded
+    }
+
+    /// Values of an assignment (one for scalars), with variables expanded
+    pub fn values(&self, key: &str) -> Vec<String> {
+        self.raw(key)
+            .map(|raw| {
+                shell_words(&raw)
+                    .iter()
+                    .map(|word| self.expand(word))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn value(&self, key: &str) -> Option<String> {
+        self.values(key).into_iter().next()
+    }
+
+    /// `pkgbase`, or the first `pkgname`
+    pub fn pkgbase(&self) -> Option<String> {
+        self.value("pkgbase").or_else(|| self.value("pkgname"))
+    }
+
+    /// Full version as reported by the AUR (`[epoch:]pkgver-pkgrel`)
+    pub fn full_version(&self) -> Option<String> {
+        let version = format!("{}-{}", self.value("pkgver")?, self.value("pkgrel")?);
+        Some(match self.value("epoch") {
+            Some(epoch) => format!("{}:{}", epoch, version),
+            None => version,
+        })
+    }
+
+    /// Replace (or append) a scalar assignment
+    pub fn set_value(&mut self, key: &str, value: &str) {
+        let pattern = Regex::new(&format!(r"(?m)^{}=.*$", regex::escape(key))).unwrap();
+        let line = format!("{}={}", key, value);
+        if pattern.is_match(&self.content) {
+            self.content = pattern
+                .replace(&self.content, regex::NoExpand(&line))
+                .to_string();
+        } else {
+            self.content = format!("{}\n{}\n", self.content.trim_end(), line);
+        }
+    }
+
+    /// Replace (or append) an array assignment
+    pub fn set_array(&mut self, key: &str, values: &[String]) {
+        let indent = " ".repeat(key.len() + 2);
+        let items: Vec<String> = values.iter().map(|v| format!("'{}'", v)).collect();
+        let line = format!("{}=({})", key, items.join(&format!("\n{}", indent)));
+        let pattern = Regex::new(&format!(r"(?ms)^{}=\(.*?\)", regex::escape(key))).unwrap();
+        if pattern.is_match(&self.content) {
+            self.content = pattern
+                .replace(&self.content, regex::NoExpand(&line))
+                .to_string();
+        } else {
+            self.content = format!("{}\n{}\n", self.content.trim_end(), line);
+        }
+    }
+
+    /// `.SRCINFO` from the PKGBUILD fields (what `makepkg --printsrcinfo`
+    /// prints for a PKGBUILD without per-package overrides)
+    pub fn srcinfo(&self) -> String {
+        let mut out = format!("pkgbase = {}\n", self.pkgbase().unwrap_or_default());
+        for key in SRCINFO_SCALARS {
+            if let Some(value) = self.value(key) {
+                out.push_str(&format!("\t{} = {}\n", key, value));
+            }
+        }
+        for key in SRCINFO_ARRAYS {
+            for value in self.values(key) {
+                out.push_str(&format!("\t{} = {}\n", key, value));
+            }
+        }
+        for name in self.values("pkgname") {
+            out.push_str(&format!("\npkgname = {}\n", name));
+        }
+        out
+    }
+}
+
+/// Source entry location (`name::url` entries use the URL part)
+fn source_location(entry: &str) -> &str {
+    entry.split_once("::").map(|(_, url)| url).unwrap_or(entry)
+}
+
+/// Local file name of a source entry
+fn source_file_name(entry: &str) -> &str {
+    match entry.split_once("::") {
+        Some((name, _)) => name,
+        None => entry.rsplit('/').next().unwrap_or(entry),
+    }
+}
+
+/// VCS sources are not checksummed
+fn is_vcs_source(location: &str) -> bool {
+    ["git+", "git://", "svn+", "hg+", "bzr+", "fossil+"]
+        .iter()
+        .any(|prefix| location.starts_with(prefix))
+}
+
+/// AUR version for a project version (`-` is not allowed in pkgver)
+pub fn aur_pkgver(version: &str) -> String {
+    version.trim_start_matches('v').replace('-', "_")
+}
+
+/// Environment variables makepkg and git need on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &[
+    "PACKAGER",
+    "MAKEFLAGS",
+    "GNUPGHOME",
+    "SSH_AUTH_SOCK",
+    "GIT_*",
+];
+
+/// AUR plugin
+pub struct AurPlugin {
+    project_path: PathBuf,
+    config: AurRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for AurPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl AurPlugin {
+    /// Create a new AUR plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: AurRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.aur` settings
+    pub fn with_config(mut self, config: AurRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run makepkg and git with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Directory containing the PKGBUILD
+    pub async fn package_dir(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.config.path {
+            return Some(self.project_path.join(path));
+        }
+        for dir in [self.project_path.clone(), self.project_path.join("aur")] {
+            if fs::try_exists(dir.join("PKGBUILD")).await.unwrap_or(false) {
+                return Some(dir);
+            }
+        }
+        None
+    }
+
+    async fn load_pkgbuild(&self) -> anyhow::Result<(PathBuf, Pkgbuild)> {
+        let dir = self
+            .package_dir()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("aur.pkgbuild_not_found")))?;
+        let content = fs::read_to_string(dir.join("PKGBUILD")).await?;
+        Ok((dir, Pkgbuild::new(content)))
+    }
+
+    fn remote(&self, pkgbase: &str) -> String {
+        self.config
+            .remote
+            .as_deref()
+            .unwrap_or(DEFAULT_REMOTE)
+            .replace("{pkgbase}", pkgbase)
+    }
+
+    /// Target pkgver: the project version, or the current pkgver for
+    /// PKGBUILD-only repositories
+    async fn target_pkgver(&self, pkgbuild: &Pkgbuild) -> Option<String> {
+        match manifest_identity(&self.project_path).await {
+            (_, Some(version)) => Some(aur_pkgver(&version)),
+            _ => pkgbuild.value("pkgver"),
+        }
+    }
+
+    /// Set pkgver (resetting pkgrel) and recompute sha256sums
+    pub async fn update_pkgbuild(
+        &self,
+        dir: &Path,
+        pkgbuild: &mut Pkgbuild,
+        pkgver: &str,
+    ) -> anyhow::Result<()> {
+        if pkgbuild.value("pkgver").as_deref() != Some(pkgver) {
+            pkgbuild.set_value("pkgver", pkgver);
+            pkgbuild.set_value("pkgrel", "1");
+        }
+
+        let mut sums = Vec::new();
+        for entry in pkgbuild.values("source") {
+            let location = source_location(&entry);
+            let sum = if is_vcs_source(location) {
+                "SKIP".to_string()
+            } else if location.contains("://") {
+                HomebrewPlugin::fetch_sha256(location).await?
+            } else {
+                sha256_file(&dir.join(location)).await?.0
+            };
+            sums.push(sum);
+        }
+        if !sums.is_empty() {
+            pkgbuild.set_array("sha256sums", &sums);
+        }
+        Ok(())
+    }
+
+    /// `.SRCINFO` from makepkg when available, generated otherwise
+    async fn srcinfo(&self, dir: &Path, pkgbuild: &Pkgbuild) -> String {
+        let mut command = self.limits.command("makepkg");
+        command.arg("--printsrcinfo").current_dir(dir);
+        let output = self.limits.output(&mut command).await;
+        match output {
+            Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+                String::from_utf8_lossy(&output.stdout).to_string()
+            }
+            _ => pkgbuild.srcinfo(),
+        }
+    }
+
+    async fn run_git(&self, dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = self.limits.command("git");
+        command.args(args).current_dir(dir);
+        if let Some(key) = &self.config.ssh_key {
+            command.env(
+                "GIT_SSH_COMMAND",
+                format!("ssh -i '{}' -o IdentitiesOnly=yes", key.replace('\'', "")),
+            );
+        }
+        git_stdout(self.limits.output(&mut command).await?).map_err(|e| {
+            anyhow::anyhow!(
+                "{}",
+                tr!(
+                    "aur.git_failed",
+                    command = args.first().unwrap_or(&""),
+                    output = e
+                )
+            )
+        })
+    }
+
+    /// Clone the AUR repository, commit PKGBUILD, .SRCINFO and local sources,
+    /// and push; returns false when the AUR already had this content
+    async fn push(
+        &self,
+        dir: &Path,
+        pkgbuild: &Pkgbuild,
+        srcinfo: &str,
+        remote: &str,
+    ) -> anyhow::Result<bool> {
+        let clone_dir =
+            std::env::temp_dir().join(format!("package-publisher-aur-{}", uuid::Uuid::new_v4()));
+        let result = async {
+            self.run_git(
+                &std::env::temp_dir(),
+                &["clone", "-q", remote, clone_dir.to_str().unwrap_or(".")],
+            )
+            .await?;
+
+            fs::write(clone_dir.join("PKGBUILD"), &pkgbuild.content).await?;
+            fs::write(clone_dir.join(".SRCINFO"), srcinfo).await?;
+            for entry in pkgbuild.values("source") {
+                let location = source_location(&entry);
+                if !location.contains("://") && !is_vcs_source(location) {
+                    fs::copy(dir.join(location), clone_dir.join(source_file_name(&entry))).await?;
+                }
+            }
+            if let Some(install) = pkgbuild.value("install") {
+                fs::copy(dir.join(&install), clone_dir.join(&install)).await?;
+            }
+
+            self.run_git(&clone_dir, &["add", "-A"]).await?;
+            if self
+                .run_git(&clone_dir, &["status", "--porcelain"])
+                .await?
+                .is_empty()
+            {
+                return Ok(false);
+            }
+            let message = format!("Update to {}", pkgbuild.full_version().unwrap_or_default());
+            self.run_git(&clone_dir, &["commit", "-q", "-m", &message])
+                .await?;
+            self.run_git(&clone_dir, &["push", "-q", "origin", "HEAD:master"])
+                .await?;
+            Ok::<_, anyhow::Error>(true)
+        }
+        .await;
+        let _ = fs::remove_dir_all(&clone_dir).await;
+        result
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for AurPlugin {
+    fn name(&self) -> &str {
+        "aur"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        Ok(AurPlugin::new(PathBuf::from(project_path))
+            .with_config(self.config.clone())
+            .package_dir()
+            .await
+            .is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let (_, pkgbuild) = self.load_pkgbuild().await?;
+        for field in ["pkgname", "pkgver", "pkgrel"] {
+            if pkgbuild.value(field).is_none() {
+                errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("aur.pkgbuild_missing_field", field = field),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+        if let Some(pkgbase) = pkgbuild.pkgbase() {
+            metadata.insert(
+                "packageName".to_string(),
+                serde_json::Value::String(pkgbase.clone()),
+            );
+            metadata.insert(
+                "remote".to_string(),
+                serde_json::Value::String(self.remote(&pkgbase)),
+            );
+        }
+        if let Some(pkgver) = self.target_pkgver(&pkgbuild).await {
+            metadata.insert("version".to_string(), serde_json::Value::String(pkgver));
+        }
+
+        let sources = pkgbuild.values("source").len();
+        let sums = pkgbuild.values("sha256sums").len();
+        if sources > 0 && sums > 0 && sources != sums {
+            warnings.push(ValidationWarning {
+                field: "sha256sums".to_string(),
+                message: tr!(
+                    "aur.checksum_count_mismatch",
+                    sources = sources,
+                    checksums = sums
+                ),
+                severity: "warning".to_string(),
+            });
+        }
+        if let Some(key) = &self.config.ssh_key
+            && !fs::try_exists(key).await.unwrap_or(false)
+        {
+            errors.push(ValidationError {
+                field: "sshKey".to_string(),
+                message: tr!("aur.ssh_key_not_found", path = key),
+                severity: "error".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let (_, mut pkgbuild) = self.load_pkgbuild().await?;
+        let current = pkgbuild.full_version().unwrap_or_default();
+        if let Some(pkgver) = self.target_pkgver(&pkgbuild).await
+            && pkgbuild.value("pkgver").as_deref() != Some(pkgver.as_str())
+        {
+            pkgbuild.set_value("pkgver", &pkgver);
+            pkgbuild.set_value("pkgrel", "1");
+        }
+        let pkgbase = pkgbuild.pkgbase().unwrap_or_default();
+
+        let mut lines = vec![format!(
+            "{} {} -> {} ({})",
+            pkgbase,
+            current,
+            pkgbuild.full_version().unwrap_or_default(),
+            self.remote(&pkgbase)
+        )];
+        for entry in pkgbuild.values("source") {
+            lines.push(format!("  source: {}", entry));
+        }
+        Ok(DryRunResult {
+            success: true,
+            output: lines.join("\n"),
+            estimated_size: None,
+            errors: None,
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let (dir, mut pkgbuild) = self.load_pkgbuild().await?;
+        let pkgbase = pkgbuild
+            .pkgbase()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("aur.pkgname_missing")))?;
+        let remote = self.remote(&pkgbase);
+
+        let result = async {
+            let pkgver = self
+                .target_pkgver(&pkgbuild)
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+            self.update_pkgbuild(&dir, &mut pkgbuild, &pkgver).await?;
+            fs::write(dir.join("PKGBUILD"), &pkgbuild.content).await?;
+            let srcinfo = self.srcinfo(&dir, &pkgbuild).await;
+            fs::write(dir.join(".SRCINFO"), &srcinfo).await?;
+            self.push(&dir, &pkgbuild, &srcinfo, &remote).await
+        }
+        .await;
+
+        let version = pkgbuild.full_version();
+        match result {
+            Ok(pushed) => Ok(PublishResult {
+                success: true,
+                version: version.clone(),
+                package_url: Some(format!("{}/packages/{}", AUR_URL, pkgbase)),
+                output: Some(if pushed {
+                    tr!(
+                        "aur.pushed",
+                        package = pkgbase,
+                        version = version.unwrap_or_default(),
+                        remote = remote
+                    )
+                } else {
+                    tr!(
+                        "aur.up_to_date",
+                        package = pkgbase,
+                        version = version.unwrap_or_default()
+                    )
+                }),
+                error: None,
+                metadata: None,
+                error_kind: None,
+            }),
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let (_, pkgbuild) = self.load_pkgbuild().await?;
+        let pkgbase = pkgbuild
+            .pkgbase()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("aur.pkgname_missing")))?;
+        let expected = pkgbuild.full_version().unwrap_or_default();
+        let url = format!("{}/packages/{}", AUR_URL, pkgbase);
+
+        let response = http_client()
+            .get(format!("{}/rpc/v5/info", AUR_URL))
+            .query(&[("arg[]", pkgbase.as_str())])
+            .send_rate_limited()
+            .await;
+        let error = match response {
+            Ok(response) if response.status().is_success() => {
+                let body = response
+                    .json::<serde_json::Value>()
+                    .await
+                    .unwrap_or_default();
+                let published = body["results"]
+                    .as_array()
+                    .and_then(|results| results.first())
+                    .and_then(|package| package["Version"].as_str())
+                    .map(str::to_string);
+                match published {
+                    Some(version) if version == expected => {
+                        return Ok(VerificationResult {
+                            verified: true,
+                            version: Some(version),
+                            url: Some(url),
+                            error: None,
+                            metadata: None,
+                            integrity: None,
+                        });
+                    }
+                    Some(version) => tr!(
+                        "aur.version_mismatch",
+                        expected = expected,
+                        actual = version
+                    ),
+                    None => tr!("aur.package_not_found", package = pkgbase),
+                }
+            }
+            Ok(response) => tr!("aur.rpc_status", status = response.status()),
+            Err(e) => tr!("aur.rpc_failed", error = e),
+        };
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(expected),
+            url: Some(url),
+            error: Some(error),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const PKGBUILD: &str = r#"# Maintainer: Me <me@example.com>
+pkgname=my-tool
+pkgver=1.0.0
+pkgrel=3
+pkgdesc="A tool (with parens)"
+arch=('x86_64' 'aarch64')
+url="https://example.com/$pkgname"
+license=('MIT')
+depends=('gcc-libs')
+makedepends=('cargo')
+source=("$pkgname-$pkgver.tar.gz::https://example.com/${pkgname}/v${pkgver}.tar.gz"
+        'my-tool.desktop')
+sha256sums=('aaa'
+            'bbb')
+
+build() {
+  cd "$pkgname-$pkgver"
+  cargo build --release
+}
+"#;
+
+    #[test]
+    fn test_pkgbuild_fields_and_srcinfo() {
+        let mut pkgbuild = Pkgbuild::new(PKGBUILD);
+        assert_eq!(pkgbuild.pkgbase().as_deref(), Some("my-tool"));
+        assert_eq!(pkgbuild.full_version().as_deref(), Some("1.0.0-3"));
+        assert_eq!(
+            pkgbuild.value("pkgdesc").as_deref(),
+            Some("A tool (with parens)")
+        );
+        assert_eq!(
+            pkgbuild.values("source"),
+            vec![
+                "my-tool-1.0.0.tar.gz::https://example.com/my-tool/v1.0.0.tar.gz",
+                "my-tool.desktop"
+            ]
+        );
+
+        pkgbuild.set_value("pkgver", "1.2.0");
+        pkgbuild.set_value("pkgrel", "1");
+        pkgbuild.set_array("sha256sums", &["ccc".to_string(), "ddd".to_string()]);
+        assert!(
+            pkgbuild
+                .content
+                .contains("sha256sums=('ccc'\n            'ddd')\n\nbuild()")
+        );
+        assert_eq!(
+            pkgbuild.values("source")[0],
+            "my-tool-1.2.0.tar.gz::https://example.com/my-tool/v1.2.0.tar.gz"
+        );
+
+        let srcinfo = pkgbuild.srcinfo();
+        assert!(srcinfo.starts_with(
+            "pkgbase = my-tool\n\tpkgdesc = A tool (with parens)\n\tpkgver = 1.2.0\n\tpkgrel = 1\n\turl = https://example.com/my-tool\n\tarch = x86_64\n\tarch = aarch64\n"
+        ));
+        assert!(srcinfo.contains("\tsha256sums = ccc\n\tsha256sums = ddd\n"));
+        assert!(srcinfo.ends_with("\npkgname = my-tool\n"));
+        assert_eq!(aur_pkgver("v1.0.0-beta.1"), "1.0.0_beta.1");
+    }
+
+    #[tokio::test]
+    async fn test_publish_pushes_to_aur_remote() {
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let remote = TempDir::new().unwrap();
+        git(remote.path(), &["init", "-q", "--bare", "-b", "master"]);
+        unsafe {
+            std::env::set_var("GIT_AUTHOR_NAME", "Test");
+            std::env::set_var("GIT_AUTHOR_EMAIL", "test@example.com");
+            std::env::set_var("GIT_COMMITTER_NAME", "Test");
+            std::env::set_var("GIT_COMMITTER_EMAIL", "test@example.com");
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"my-tool\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+        let aur = temp_dir.path().join("aur");
+        std::fs::create_dir(&aur).unwrap();
+        std::fs::write(
+            aur.join("PKGBUILD"),
+            "pkgname=my-tool\npkgver=1.0.0\npkgrel=2\narch=('any')\nsource=('my-tool.sh')\nsha256sums=('old')\n",
+        )
+        .unwrap();
+        std::fs::write(aur.join("my-tool.sh"), "echo hi\n").unwrap();
+
+        let plugin = AurPlugin::new(temp_dir.path().to_path_buf()).with_config(AurRegistryConfig {
+            remote: Some(remote.path().display().to_string()),
+            ..Default::default()
+        });
+        assert!(
+            plugin
+                .detect(temp_dir.path().to_str().unwrap())
+                .await
+                .unwrap()
+        );
+
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.version.as_deref(), Some("1.2.0-1"));
+
+        let pkgbuild = std::fs::read_to_string(aur.join("PKGBUILD")).unwrap();
+        assert!(pkgbuild.contains("pkgver=1.2.0\npkgrel=1\n"));
+        assert!(pkgbuild.contains(&format!(
+            "sha256sums=('{}')",
+            hex::encode(<sha2::Sha256 as sha2::Digest>::digest(b"echo hi\n"))
+        )));
+
+        assert_eq!(
+            git(remote.path(), &["log", "-1", "--format=%s", "master"]),
+            "Update to 1.2.0-1"
+        );
+        let files = git(remote.path(), &["ls-tree", "--name-only", "master"]);
+        assert_eq!(files, ".SRCINFO\nPKGBUILD\nmy-tool.sh");
+
+        // Nothing changed: no second commit
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.unwrap().contains("up to date"));
+    }
+}
@@ -0,0 +1,650 @@
+//! Bazel Plugin - Bazel Central Registry (BCR) module publishing
+//!
+//! BCR modules are added by pull request against `bazelbuild/bazel-central-registry`,
+//! so publishing means opening that PR:
+//! - MODULE.bazel detection and `module()` name/version validation
+//! - BCR entry generation (`MODULE.bazel`, `source.json` with an SRI integrity
+//!   hash of the source archive, `presubmit.yml`, updated `metadata.json`)
+//! - `.bcr/` templates (as used by publish-to-bcr) when present
+//! - Pushing the entry to a registry fork and opening the PR
+//! - Reporting the PR URL (the module is not available until the PR merges)
+
+use crate::core::config::BazelRegistryConfig;
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::parse_github_repository;
+use crate::tr;
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const DEFAULT_REGISTRY: &str = "bazelbuild/bazel-central-registry";
+
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+const DEFAULT_TAG_FORMAT: &str = "v{version}";
+
+const DEFAULT_SOURCE_TEMPLATE: &str = r#"{
+    "url": "https://github.com/{OWNER}/{REPO}/archive/refs/tags/{TAG}.tar.gz",
+    "integrity": "",
+    "strip_prefix": "{REPO}-{VERSION}"
+}"#;
+
+const DEFAULT_PRESUBMIT: &str = r#"matrix:
+  platform: ["debian10", "macos", "ubuntu2004", "windows"]
+  bazel: ["7.x"]
+tasks:
+  verify_targets:
+    name: Verify build targets
+    platform: ${{ platform }}
+    bazel: ${{ bazel }}
+    build_targets:
+      - "@{MODULE}//..."
+"#;
+
+/// `module()` declaration from MODULE.bazel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BazelModule {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub compatibility_level: Option<u32>,
+}
+
+impl BazelModule {
+    /// Parse the `module(...)` call of a MODULE.bazel file
+    pub fn parse(content: &str) -> Option<Self> {
+        let args = Regex::new(r"(?s)(?:^|\n)\s*module\s*\((.*?)\)")
+            .unwrap()
+            .captures(content)?[1]
+            .to_string();
+        let string_arg = |key: &str| {
+            Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, key))
+                .unwrap()
+                .captures(&args)
+                .map(|c| c[1].to_string())
+        };
+
+        Some(Self {
+            name: string_arg("name"),
+            version: string_arg("version"),
+            compatibility_level: Regex::new(r"\bcompatibility_level\s*=\s*(\d+)")
+                .unwrap()
+                .captures(&args)
+                .and_then(|c| c[1].parse().ok()),
+        })
+    }
+}
+
+/// Generated BCR entry: registry-relative paths and file contents
+#[derive(Debug, Clone)]
+pub struct BcrEntry {
+    pub module: String,
+    pub version: String,
+    pub files: Vec<(String, String)>,
+}
+
+/// Bazel Central Registry plugin
+pub struct BazelPlugin {
+    project_path: PathBuf,
+    config: BazelRegistryConfig,
+    github: GitHubClient,
+}
+
+impl Default for BazelPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl BazelPlugin {
+    /// Create a new Bazel plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: BazelRegistryConfig::default(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Apply `registries.bazel` settings
+    pub fn with_config(mut self, config: BazelRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    async fn load_module(&self) -> anyhow::Result<BazelModule> {
+        let content = fs::read_to_string(self.project_path.join("MODULE.bazel")).await?;
+        BazelModule::parse(&content)
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("bazel.module_not_found")))
+    }
+
+    fn registry(&self) -> &str {
+        self.config.registry.as_deref().unwrap_or(DEFAULT_REGISTRY)
+    }
+
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    fn tag_name(&self, version: &str) -> String {
+        self.config
+            .tag_format
+            .as_deref()
+            .unwrap_or(DEFAULT_TAG_FORMAT)
+            .replace("{version}", version)
+    }
+
+    /// Validate module name and version
+    fn validate_module(&self, module: &BazelModule, errors: &mut Vec<ValidationError>) {
+        let name_pattern = Regex::new(r"^[a-z]([a-z0-9._-]*[a-z0-9])?$").unwrap();
+        match module.name {
+            Some(ref name) if !name_pattern.is_match(name) => errors.push(ValidationError {
+                field: "module.name".to_string(),
+                message: tr!("bazel.invalid_module_name", name = name),
+                severity: "error".to_string(),
+            }),
+            Some(_) => {}
+            None => errors.push(ValidationError {
+                field: "module.name".to_string(),
+                message: tr!("bazel.name_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        // BCR accepts a relaxed semver (e.g. "1.2.3.bcr.1")
+        let version_pattern =
+            Regex::new(r"^[0-9]+(\.[0-9A-Za-z]+)*(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$").unwrap();
+        match module.version.as_deref() {
+            Some(version) if !version.is_empty() && version_pattern.is_match(version) => {}
+            Some("") | None => errors.push(ValidationError {
+                field: "module.version".to_string(),
+                message: tr!("bazel.version_required"),
+                severity: "error".to_string(),
+            }),
+            Some(version) => errors.push(ValidationError {
+                field: "module.version".to_string(),
+                message: tr!("bazel.invalid_version", version = version),
+                severity: "error".to_string(),
+            }),
+        }
+    }
+
+    /// Source repository (`owner/repo`) from config or the `origin` remote
+    async fn repository(&self) -> anyhow::Result<String> {
+        if let Some(ref repository) = self.config.repository {
+            return Ok(repository.clone());
+        }
+
+        let url = run_git(&self.project_path, &["remote", "get-url", "origin"]).await?;
+        parse_github_repository(&url).ok_or_else(|| {
+            anyhow::anyhow!("{}", tr!("bazel.repository_unknown", remote = url.trim()))
+        })
+    }
+
+    /// `.bcr/<file>` template, if present
+    async fn template(&self, file: &str) -> Option<String> {
+        fs::read_to_string(self.project_path.join(".bcr").join(file))
+            .await
+            .ok()
+    }
+
+    /// Generate the registry entry, downloading the source archive for its integrity hash
+    pub async fn generate_entry(&self) -> anyhow::Result<BcrEntry> {
+        let module = self.load_module().await?;
+        let name = module
+            .name
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Module name not found"))?;
+        let version = module
+            .version
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Module version not found"))?;
+        let repository = self.repository().await?;
+        let (owner, repo) = repository
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Invalid repository: {}", repository))?;
+        let tag = self.tag_name(&version);
+
+        let substitute = |template: &str| {
+            template
+                .replace("{OWNER}", owner)
+                .replace("{REPO}", repo)
+                .replace("{VERSION}", &version)
+                .replace("{TAG}", &tag)
+                .replace("{MODULE}", &name)
+        };
+
+        let source_template = self
+            .template("source.template.json")
+            .await
+            .unwrap_or_else(|| DEFAULT_SOURCE_TEMPLATE.to_string());
+        let mut source: serde_json::Value = serde_json::from_str(&substitute(&source_template))?;
+        let url = source
+            .get("url")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("bazel.source_url_missing")))?
+            .to_string();
+        source["integrity"] = serde_json::Value::String(self.fetch_integrity(&url).await?);
+
+        let presubmit = self
+            .template("presubmit.yml")
+            .await
+            .unwrap_or_else(|| substitute(DEFAULT_PRESUBMIT));
+
+        let existing_metadata = self
+            .fetch_registry_file(&format!("modules/{}/metadata.json", name))
+            .await;
+        let metadata_template = match existing_metadata {
+            Some(content) => content,
+            None => match self.template("metadata.template.json").await {
+                Some(template) => substitute(&template),
+                None => serde_json::json!({
+                    "homepage": format!("https://github.com/{}", repository),
+                    "maintainers": [],
+                    "repository": [format!("github:{}", repository)],
+                    "versions": [],
+                    "yanked_versions": {},
+                })
+                .to_string(),
+            },
+        };
+        let metadata = add_version_to_metadata(&metadata_template, &version)?;
+
+        let module_file = fs::read_to_string(self.project_path.join("MODULE.bazel")).await?;
+        let base = format!("modules/{}", name);
+        Ok(BcrEntry {
+            files: vec![
+                (format!("{}/metadata.json", base), metadata),
+                (format!("{}/{}/MODULE.bazel", base, version), module_file),
+                (
+                    format!("{}/{}/source.json", base, version),
+                    serde_json::to_string_pretty(&source)? + "\n",
+                ),
+                (format!("{}/{}/presubmit.yml", base, version), presubmit),
+            ],
+            module: name,
+            version,
+        })
+    }
+
+    /// SRI (`sha256-<base64>`) hash of the source archive
+    async fn fetch_integrity(&self, url: &str) -> anyhow::Result<String> {
+        let response = http_client().get(url).send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "bazel.archive_fetch_failed",
+                    status = response.status(),
+                    url = url
+                )
+            );
+        }
+        Ok(sri_integrity(&response.bytes().await?))
+    }
+
+    /// Current content of a registry file on the default branch
+    async fn fetch_registry_file(&self, path: &str) -> Option<String> {
+        let response = http_client()
+            .get(format!(
+                "https://raw.githubusercontent.com/{}/main/{}",
+                self.registry(),
+                path
+            ))
+            .send_rate_limited()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().await.ok()
+    }
+
+    /// Push the entry to a branch of the fork and open the PR; returns the PR URL
+    async fn open_pull_request(&self, entry: &BcrEntry, token: &str) -> anyhow::Result<String> {
+        let fork = self
+            .config
+            .fork
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("bazel.fork_not_set")))?;
+        let fork_owner = fork.split('/').next().unwrap_or(fork);
+        let branch = format!("{}-{}", entry.module, entry.version);
+        let github = self.github.authenticated(token);
+
+        // Bring the fork up to date, then branch from the registry's main
+        let _ = github.merge_upstream(fork, "main").await;
+        let sha = github
+            .branch_sha(self.registry(), "main")
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("bazel.main_branch_not_found", repository = self.registry())
+                )
+            })?;
+        github.create_branch(fork, &branch, &sha).await?;
+
+        for (path, content) in &entry.files {
+            github
+                .put_file(
+                    fork,
+                    &branch,
+                    path,
+                    content,
+                    &format!("{}@{}: {}", entry.module, entry.version, path),
+                )
+                .await?;
+        }
+
+        github
+            .open_pull_request(
+                self.registry(),
+                &format!("{}:{}", fork_owner, branch),
+                "main",
+                &format!("{}@{}", entry.module, entry.version),
+                &format!(
+                    "Release: {}@{}\n\n_Automated by package-publisher_",
+                    entry.module, entry.version
+                ),
+            )
+            .await
+    }
+}
+
+/// Subresource Integrity string for `data` (`sha256-<base64>`)
+pub fn sri_integrity(data: &[u8]) -> String {
+    format!("sha256-{}", BASE64.encode(Sha256::digest(data)))
+}
+
+/// Add `version` to a BCR `metadata.json` (sorted, without duplicates)
+fn add_version_to_metadata(content: &str, version: &str) -> anyhow::Result<String> {
+    let mut metadata: serde_json::Value = serde_json::from_str(content)?;
+    let mut versions: Vec<String> = metadata
+        .get("versions")
+        .and_then(|v| v.as_array())
+        .map(|v| {
+            v.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !versions.iter().any(|v| v == version) {
+        versions.push(version.to_string());
+    }
+    versions.sort_by(
+        |a, b| match (semver::Version::parse(a), semver::Version::parse(b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        },
+    );
+    metadata["versions"] = serde_json::json!(versions);
+
+    Ok(serde_json::to_string_pretty(&metadata)? + "\n")
+}
+
+#[async_trait]
+impl RegistryPlugin for BazelPlugin {
+    fn name(&self) -> &str {
+        "bazel"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        let path = Path::new(project_path).join("MODULE.bazel");
+        let Ok(content) = fs::read_to_string(&path).await else {
+            return Ok(false);
+        };
+        Ok(BazelModule::parse(&content).is_some_and(|m| m.name.is_some()))
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let module = self.load_module().await?;
+        self.validate_module(&module, &mut errors);
+
+        if let Some(ref name) = module.name {
+            metadata.insert(
+                "packageName".to_string(),
+                serde_json::Value::String(name.clone()),
+            );
+        }
+        if let Some(ref version) = module.version {
+            metadata.insert(
+                "version".to_string(),
+                serde_json::Value::String(version.clone()),
+            );
+        }
+
+        if self.config.fork.is_none() {
+            errors.push(ValidationError {
+                field: "registries.bazel.fork".to_string(),
+                message: tr!("bazel.fork_required"),
+                severity: "error".to_string(),
+            });
+        }
+
+        for template in [
+            "source.template.json",
+            "presubmit.yml",
+            "metadata.template.json",
+        ] {
+            if self.template(template).await.is_none() {
+                warnings.push(ValidationWarning {
+                    field: format!(".bcr/{}", template),
+                    message: tr!("bazel.default_template", file = template),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        match self.generate_entry().await {
+            Ok(entry) => Ok(DryRunResult {
+                success: true,
+                output: entry
+                    .files
+                    .iter()
+                    .map(|(path, content)| format!("--- {}\n{}", path, content))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "entry".to_string(),
+                    message: tr!("bazel.entry_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let token = std::env::var(self.token_env())
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("registry.not_configured", name = self.token_env())
+                )
+            })?;
+        let entry = self.generate_entry().await?;
+
+        match self.open_pull_request(&entry, &token).await {
+            Ok(pr_url) => {
+                let mut metadata = HashMap::new();
+                metadata.insert("pendingReview".to_string(), serde_json::Value::Bool(true));
+                metadata.insert(
+                    "submissionUrl".to_string(),
+                    serde_json::Value::String(pr_url.clone()),
+                );
+
+                Ok(PublishResult {
+                    success: true,
+                    version: Some(entry.version.clone()),
+                    package_url: Some(pr_url),
+                    output: Some(tr!(
+                        "bazel.pr_created",
+                        module = entry.module,
+                        version = entry.version,
+                        registry = self.registry()
+                    )),
+                    error: None,
+                    metadata: Some(metadata),
+                    error_kind: None,
+                })
+            }
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let module = self.load_module().await?;
+        let name = module
+            .name
+            .ok_or_else(|| anyhow::anyhow!("Module name not found"))?;
+        let version = module
+            .version
+            .ok_or_else(|| anyhow::anyhow!("Module version not found"))?;
+        let url = format!("https://registry.bazel.build/modules/{}", name);
+
+        let registered = self
+            .fetch_registry_file(&format!("modules/{}/{}/source.json", name, version))
+            .await
+            .is_some();
+
+        Ok(VerificationResult {
+            verified: registered,
+            version: Some(version.clone()),
+            url: Some(url),
+            error: (!registered).then(|| {
+                tr!(
+                    "bazel.not_registered",
+                    module = name,
+                    version = version,
+                    registry = self.registry()
+                )
+            }),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module() {
+        let module = BazelModule::parse(
+            r#"
+# Example module
+module(
+    name = "rules_example",
+    version = "1.2.0",
+    compatibility_level = 1,
+)
+
+bazel_dep(name = "platforms", version = "0.0.8")
+"#,
+        )
+        .unwrap();
+        assert_eq!(module.name.as_deref(), Some("rules_example"));
+        assert_eq!(module.version.as_deref(), Some("1.2.0"));
+        assert_eq!(module.compatibility_level, Some(1));
+
+        assert!(BazelModule::parse("bazel_dep(name = \"x\")").is_none());
+    }
+
+    #[test]
+    fn test_validate_module() {
+        let plugin = BazelPlugin::default();
+        let mut errors = Vec::new();
+        plugin.validate_module(
+            &BazelModule {
+                name: Some("Rules-Example".to_string()),
+                version: Some("".to_string()),
+                compatibility_level: None,
+            },
+            &mut errors,
+        );
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["module.name", "module.version"]);
+
+        let mut errors = Vec::new();
+        plugin.validate_module(
+            &BazelModule {
+                name: Some("rules_example".to_string()),
+                version: Some("1.2.3.bcr.1".to_string()),
+                compatibility_level: None,
+            },
+            &mut errors,
+        );
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_sri_integrity_and_metadata() {
+        assert_eq!(
+            sri_integrity(b"hello"),
+            "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="
+        );
+
+        let metadata =
+            add_version_to_metadata(r#"{"versions": ["1.10.0", "1.2.0"]}"#, "1.9.0").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(
+            json["versions"],
+            serde_json::json!(["1.2.0", "1.9.0", "1.10.0"])
+        );
+    }
+}
@@ -0,0 +1,849 @@
+//! Bucket Plugin - versioned artifacts in S3 / GCS / Azure Blob storage
+//!
+//! For internal distribution without a package registry:
+//! - Uploads the configured artifacts under a key template (`{name}/{version}/{file}`)
+//! - Upserts an index object (JSON, or an APT-style `Packages` file)
+//! - Verifies that every object exists and its SHA-256 matches the local file
+//!
+//! Transfers use the provider CLI (`aws`, `gcloud`, `az`) and its configured
+//! credentials. The registry is selected by the `registries.bucket` config
+//! section rather than by a manifest.
+
+use crate::core::config::BucketRegistryConfig;
+use crate::core::traits::{
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, ValidationError,
+    ValidationResult, ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::resolve_assets;
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const DEFAULT_KEY_TEMPLATE: &str = "{name}/{version}/{file}";
+
+const DEFAULT_ARTIFACTS: &str = "dist";
+
+/// Storage provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketProvider {
+    S3,
+    Gcs,
+    Azure,
+}
+
+impl BucketProvider {
+    /// Parse the `provider` setting (default: S3)
+    pub fn parse(value: Option<&str>) -> anyhow::Result<Self> {
+        match value.map(str::to_lowercase).as_deref() {
+            None | Some("s3") => Ok(BucketProvider::S3),
+            Some("gcs") | Some("gs") => Ok(BucketProvider::Gcs),
+            Some("azure") => Ok(BucketProvider::Azure),
+            Some(other) => anyhow::bail!(
+                "Unknown bucket provider: {} (expected s3, gcs or azure)",
+                other
+            ),
+        }
+    }
+
+    /// CLI used for transfers
+    pub fn cli(&self) -> &'static str {
+        match self {
+            BucketProvider::S3 => "aws",
+            BucketProvider::Gcs => "gcloud",
+            BucketProvider::Azure => "az",
+        }
+    }
+}
+
+/// Index object format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFormat {
+    /// `index.json` listing every version and its objects
+    Json,
+    /// Debian flat repository `Packages` file
+    Apt,
+}
+
+impl IndexFormat {
+    /// Parse the `index` setting (`None` = no index)
+    pub fn parse(value: Option<&str>) -> anyhow::Result<Option<Self>> {
+        match value.map(str::to_lowercase).as_deref() {
+            None | Some("none") => Ok(None),
+            Some("json") => Ok(Some(IndexFormat::Json)),
+            Some("apt") => Ok(Some(IndexFormat::Apt)),
+            Some("yum") => anyhow::bail!(
+                "yum indexes are not generated; run createrepo_c in a postPublish hook instead"
+            ),
+            Some(other) => anyhow::bail!("Unknown index format: {} (expected json or apt)", other),
+        }
+    }
+
+    fn default_key(&self) -> &'static str {
+        match self {
+            IndexFormat::Json => "{name}/index.json",
+            IndexFormat::Apt => "{name}/Packages",
+        }
+    }
+}
+
+/// Uploaded object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BucketObject {
+    pub key: String,
+    /// SHA-256 hex digest
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// Version entry of the JSON index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketIndexVersion {
+    pub version: String,
+    pub published_at: String,
+    pub objects: Vec<BucketObject>,
+}
+
+/// JSON index (`index.json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BucketIndex {
+    pub name: String,
+    pub versions: Vec<BucketIndexVersion>,
+}
+
+impl BucketIndex {
+    /// Replace (or add) a version entry, keeping versions sorted
+    pub fn upsert(&mut self, entry: BucketIndexVersion) {
+        self.versions.retain(|v| v.version != entry.version);
+        self.versions.push(entry);
+        self.versions.sort_by(|a, b| {
+            match (
+                semver::Version::parse(&a.version),
+                semver::Version::parse(&b.version),
+            ) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.version.cmp(&b.version),
+            }
+        });
+    }
+}
+
+/// Replace the stanzas for `objects` in an APT `Packages` file
+///
+/// Only `.deb` objects are listed; the architecture is taken from the
+/// `name_version_arch.deb` file name (default: "all").
+pub fn upsert_apt_packages(
+    existing: &str,
+    name: &str,
+    version: &str,
+    objects: &[BucketObject],
+) -> String {
+    let debs: Vec<_> = objects.iter().filter(|o| o.key.ends_with(".deb")).collect();
+    let keys: Vec<_> = debs.iter().map(|o| o.key.as_str()).collect();
+
+    let mut stanzas: Vec<String> = existing
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter(|stanza| {
+            !stanza.lines().any(|l| {
+                l.strip_prefix("Filename: ")
+                    .is_some_and(|f| keys.contains(&f.trim()))
+            })
+        })
+        .map(str::to_string)
+        .collect();
+
+    for object in debs {
+        let architecture = object
+            .key
+            .rsplit('/')
+            .next()
+            .and_then(|file| file.strip_suffix(".deb"))
+            .and_then(|stem| stem.rsplit_once('_'))
+            .map(|(_, arch)| arch)
+            .unwrap_or("all");
+        stanzas.push(format!(
+            "Package: {}\nVersion: {}\nArchitecture: {}\nFilename: {}\nSize: {}\nSHA256: {}",
+            name, version, architecture, object.key, object.size, object.sha256
+        ));
+    }
+
+    stanzas.join("\n\n") + "\n"
+}
+
+/// Render a key template
+pub fn render_key(template: &str, name: &str, version: &str, file: &str) -> String {
+    template
+        .replace("{name}", name)
+        .replace("{version}", version)
+        .replace("{file}", file)
+}
+
+/// Name and version from package.json, Cargo.toml or pyproject.toml
+/// (version falls back to a `VERSION` file)
+pub(crate) async fn manifest_identity(project_path: &Path) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut version = None;
+
+    if let Ok(content) = fs::read_to_string(project_path.join("package.json")).await
+        && let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content)
+    {
+        name = manifest["name"].as_str().map(str::to_string);
+        version = manifest["version"].as_str().map(str::to_string);
+    } else if let Ok(content) = fs::read_to_string(project_path.join("Cargo.toml")).await
+        && let Ok(manifest) = toml::from_str::<toml::Table>(&content)
+    {
+        let package = manifest.get("package");
+        name = package
+            .and_then(|p| p.get("name"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        version = package
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+    } else if let Ok(content) = fs::read_to_string(project_path.join("pyproject.toml")).await
+        && let Ok(manifest) = toml::from_str::<toml::Table>(&content)
+    {
+        let project = manifest.get("project");
+        name = project
+            .and_then(|p| p.get("name"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        version = project
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+    }
+
+    if version.is_none()
+        && let Ok(content) = fs::read_to_string(project_path.join("VERSION")).await
+    {
+        version = Some(content.trim().to_string()).filter(|v| !v.is_empty());
+    }
+
+    (name, version)
+}
+
+pub(crate) async fn sha256_file(path: &Path) -> anyhow::Result<(String, u64)> {
+    let content = fs::read(path).await?;
+    Ok((hex::encode(Sha256::digest(&content)), content.len() as u64))
+}
+
+/// Environment variables the storage CLIs need on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &[
+    "AWS_*",
+    "BOTO_*",
+    "GOOGLE_*",
+    "CLOUDSDK_*",
+    "AZURE_*",
+    "AZCOPY_*",
+];
+
+/// Bucket plugin
+pub struct BucketPlugin {
+    project_path: PathBuf,
+    config: BucketRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for BucketPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl BucketPlugin {
+    /// Create a new bucket plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: BucketRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.bucket` settings
+    pub fn with_config(mut self, config: BucketRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run the storage CLIs with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    fn provider(&self) -> anyhow::Result<BucketProvider> {
+        BucketProvider::parse(self.config.provider.as_deref())
+    }
+
+    /// Package name and version from config or the project manifest
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        match (self.config.name.clone().or(name), version) {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!("Package name not found (set registries.bucket.name)"),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    async fn artifacts(&self) -> Vec<PathBuf> {
+        let patterns = self
+            .config
+            .artifacts
+            .clone()
+            .unwrap_or_else(|| vec![DEFAULT_ARTIFACTS.to_string()]);
+        resolve_assets(&self.project_path, &patterns).await
+    }
+
+    fn object_key(&self, name: &str, version: &str, path: &Path) -> String {
+        let file = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        render_key(
+            self.config
+                .key_template
+                .as_deref()
+                .unwrap_or(DEFAULT_KEY_TEMPLATE),
+            name,
+            version,
+            &file,
+        )
+    }
+
+    /// Human-readable object location
+    fn object_url(&self, provider: BucketProvider, key: &str) -> String {
+        match provider {
+            BucketProvider::S3 => format!("s3://{}/{}", self.config.bucket, key),
+            BucketProvider::Gcs => format!("gs://{}/{}", self.config.bucket, key),
+            BucketProvider::Azure => format!(
+                "https://{}.blob.core.windows.net/{}/{}",
+                self.config.account.as_deref().unwrap_or("<account>"),
+                self.config.bucket,
+                key
+            ),
+        }
+    }
+
+    /// CLI arguments copying `from` to `to` (one side is a bucket key)
+    fn transfer_args(
+        &self,
+        provider: BucketProvider,
+        local: &Path,
+        key: &str,
+        upload: bool,
+    ) -> Vec<String> {
+        let local = local.display().to_string();
+        match provider {
+            BucketProvider::S3 | BucketProvider::Gcs => {
+                let remote = self.object_url(provider, key);
+                let mut args = match provider {
+                    BucketProvider::S3 => vec!["s3".to_string(), "cp".to_string()],
+                    _ => vec!["storage".to_string(), "cp".to_string()],
+                };
+                if upload {
+                    args.extend([local, remote]);
+                } else {
+                    args.extend([remote, local]);
+                }
+                if provider == BucketProvider::S3 {
+                    if let Some(ref region) = self.config.region {
+                        args.extend(["--region".to_string(), region.clone()]);
+                    }
+                    if let Some(ref endpoint) = self.config.endpoint {
+                        args.extend(["--endpoint-url".to_string(), endpoint.clone()]);
+                    }
+                }
+                args
+            }
+            BucketProvider::Azure => {
+                let mut args = vec![
+                    "storage".to_string(),
+                    "blob".to_string(),
+                    if upload { "upload" } else { "download" }.to_string(),
+                    "--account-name".to_string(),
+                    self.config.account.clone().unwrap_or_default(),
+                    "--container-name".to_string(),
+                    self.config.bucket.clone(),
+                    "--name".to_string(),
+                    key.to_string(),
+                    "--file".to_string(),
+                    local,
+                    "--auth-mode".to_string(),
+                    "login".to_string(),
+                ];
+                if upload {
+                    args.push("--overwrite".to_string());
+                }
+                args
+            }
+        }
+    }
+
+    async fn run_cli(&self, provider: BucketProvider, args: &[String]) -> anyhow::Result<String> {
+        let mut command = self.limits.command(provider.cli());
+        command.args(args).current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
+
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn upload(
+        &self,
+        provider: BucketProvider,
+        local: &Path,
+        key: &str,
+    ) -> anyhow::Result<()> {
+        let args = self.transfer_args(provider, local, key, true);
+        self.run_cli(provider, &args).await.map(|_| ())
+    }
+
+    /// Download an object to a temporary file (None if it does not exist)
+    async fn download(&self, provider: BucketProvider, key: &str) -> Option<PathBuf> {
+        let path =
+            std::env::temp_dir().join(format!("package-publisher-bucket-{}", uuid::Uuid::new_v4()));
+        let args = self.transfer_args(provider, &path, key, false);
+        match self.run_cli(provider, &args).await {
+            Ok(_) if path.exists() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Upsert the index object for `version`
+    async fn update_index(
+        &self,
+        provider: BucketProvider,
+        format: IndexFormat,
+        name: &str,
+        version: &str,
+        objects: &[BucketObject],
+    ) -> anyhow::Result<String> {
+        let key = render_key(
+            self.config
+                .index_key
+                .as_deref()
+                .unwrap_or(format.default_key()),
+            name,
+            version,
+            "",
+        );
+
+        let existing = match self.download(provider, &key).await {
+            Some(path) => {
+                let content = fs::read_to_string(&path).await.unwrap_or_default();
+                let _ = fs::remove_file(&path).await;
+                content
+            }
+            None => String::new(),
+        };
+
+        let content = match format {
+            IndexFormat::Json => {
+                let mut index = if existing.trim().is_empty() {
+                    BucketIndex {
+                        name: name.to_string(),
+                        versions: Vec::new(),
+                    }
+                } else {
+                    serde_json::from_str(&existing)?
+                };
+                index.upsert(BucketIndexVersion {
+                    version: version.to_string(),
+                    published_at: chrono::Utc::now().to_rfc3339(),
+                    objects: objects.to_vec(),
+                });
+                serde_json::to_string_pretty(&index)?
+            }
+            IndexFormat::Apt => upsert_apt_packages(&existing, name, version, objects),
+        };
+
+        let staged =
+            std::env::temp_dir().join(format!("package-publisher-index-{}", uuid::Uuid::new_v4()));
+        fs::write(&staged, content).await?;
+        let result = self.upload(provider, &staged, &key).await;
+        let _ = fs::remove_file(&staged).await;
+        result.map(|_| key)
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for BucketPlugin {
+    fn name(&self) -> &str {
+        "bucket"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.bucket`, not a manifest
+        Ok(!self.config.bucket.is_empty())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        if self.config.bucket.is_empty() {
+            errors.push(ValidationError {
+                field: "bucket".to_string(),
+                message: tr!("bucket.bucket_required"),
+                severity: "error".to_string(),
+            });
+        }
+
+        match self.provider() {
+            Ok(BucketProvider::Azure) if self.config.account.is_none() => {
+                errors.push(ValidationError {
+                    field: "account".to_string(),
+                    message: tr!("bucket.account_required"),
+                    severity: "error".to_string(),
+                })
+            }
+            Ok(provider) => {
+                let mut command = self.limits.command(provider.cli());
+                command.arg("--version");
+                if self.limits.output(&mut command).await.is_err() {
+                    errors.push(ValidationError {
+                        field: "provider".to_string(),
+                        message: tr!("bucket.cli_not_found", cli = provider.cli()),
+                        severity: "error".to_string(),
+                    });
+                }
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "provider".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        if let Err(e) = IndexFormat::parse(self.config.index.as_deref()) {
+            errors.push(ValidationError {
+                field: "index".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            });
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        let artifacts = self.artifacts().await;
+        if artifacts.is_empty() {
+            errors.push(ValidationError {
+                field: "artifacts".to_string(),
+                message: tr!("registry.no_artifacts_to_upload"),
+                severity: "error".to_string(),
+            });
+        }
+
+        let template = self
+            .config
+            .key_template
+            .as_deref()
+            .unwrap_or(DEFAULT_KEY_TEMPLATE);
+        if !template.contains("{version}") {
+            warnings.push(ValidationWarning {
+                field: "keyTemplate".to_string(),
+                message: tr!("bucket.key_without_version"),
+                severity: "warning".to_string(),
+            });
+        }
+        if artifacts.len() > 1 && !template.contains("{file}") {
+            errors.push(ValidationError {
+                field: "keyTemplate".to_string(),
+                message: tr!("bucket.key_template_needs_file"),
+                severity: "error".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let provider = self.provider()?;
+        let (name, version) = self.package_identity().await?;
+
+        let mut lines = Vec::new();
+        let mut total = 0;
+        for path in self.artifacts().await {
+            let size = fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+            total += size;
+            lines.push(format!(
+                "{} -> {}",
+                path.display(),
+                self.object_url(provider, &self.object_key(&name, &version, &path))
+            ));
+        }
+
+        Ok(DryRunResult {
+            success: !lines.is_empty(),
+            output: lines.join("\n"),
+            estimated_size: Some(format!("{:.1} kB", total as f64 / 1024.0)),
+            errors: None,
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let provider = self.provider()?;
+        let index_format = IndexFormat::parse(self.config.index.as_deref())?;
+        let (name, version) = self.package_identity().await?;
+
+        let mut objects = Vec::new();
+        for path in self.artifacts().await {
+            let key = self.object_key(&name, &version, &path);
+            let (sha256, size) = sha256_file(&path).await?;
+            if let Err(e) = self.upload(provider, &path, &key).await {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!(
+                        "registry.upload_failed",
+                        file = self.object_url(provider, &key),
+                        error = e
+                    )),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+            objects.push(BucketObject { key, sha256, size });
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("objects".to_string(), serde_json::to_value(&objects)?);
+
+        let mut output = objects
+            .iter()
+            .map(|o| format!("Uploaded {}", self.object_url(provider, &o.key)))
+            .collect::<Vec<_>>();
+
+        if let Some(format) = index_format {
+            match self
+                .update_index(provider, format, &name, &version, &objects)
+                .await
+            {
+                Ok(key) => {
+                    output.push(format!("Updated index {}", self.object_url(provider, &key)));
+                    metadata.insert("index".to_string(), serde_json::Value::String(key));
+                }
+                Err(e) => {
+                    return Ok(PublishResult {
+                        success: false,
+                        version: Some(version),
+                        package_url: None,
+                        output: Some(output.join("\n")),
+                        error: Some(tr!("bucket.index_update_failed", error = e)),
+                        metadata: Some(metadata),
+                        error_kind: None,
+                    });
+                }
+            }
+        }
+
+        Ok(PublishResult {
+            success: true,
+            version: Some(version),
+            package_url: objects.first().map(|o| self.object_url(provider, &o.key)),
+            output: Some(output.join("\n")),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let provider = self.provider()?;
+        let (name, version) = self.package_identity().await?;
+
+        let mut problems = Vec::new();
+        let mut first = None;
+        for path in self.artifacts().await {
+            let key = self.object_key(&name, &version, &path);
+            let (expected, _) = sha256_file(&path).await?;
+
+            let Some(downloaded) = self.download(provider, &key).await else {
+                problems.push(tr!(
+                    "registry.not_found",
+                    name = self.object_url(provider, &key)
+                ));
+                continue;
+            };
+            let actual = sha256_file(&downloaded).await.map(|(hash, _)| hash);
+            let _ = fs::remove_file(&downloaded).await;
+
+            match actual {
+                Ok(actual) if actual == expected => {
+                    first.get_or_insert((self.object_url(provider, &key), actual));
+                }
+                Ok(actual) => problems.push(tr!(
+                    "registry.sha256_mismatch",
+                    file = self.object_url(provider, &key),
+                    expected = expected,
+                    actual = actual
+                )),
+                Err(e) => problems.push(format!("{}: {}", key, e)),
+            }
+        }
+
+        if problems.is_empty()
+            && let Some((url, checksum)) = first
+        {
+            return Ok(VerificationResult {
+                verified: true,
+                version: Some(version),
+                url: Some(url.clone()),
+                error: None,
+                metadata: None,
+                integrity: Some(PackageIntegrity {
+                    download_url: Some(url),
+                    checksum: Some(checksum),
+                    ..Default::default()
+                }),
+            });
+        }
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(version),
+            url: None,
+            error: Some(if problems.is_empty() {
+                tr!("registry.no_artifacts_to_verify")
+            } else {
+                problems.join("; ")
+            }),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::command_executor::EnvSandbox;
+
+    fn object(key: &str) -> BucketObject {
+        BucketObject {
+            key: key.to_string(),
+            sha256: "ab".repeat(32),
+            size: 10,
+        }
+    }
+
+    #[test]
+    fn test_render_key_and_transfer_args() {
+        assert_eq!(
+            render_key(DEFAULT_KEY_TEMPLATE, "app", "1.2.0", "app.tar.gz"),
+            "app/1.2.0/app.tar.gz"
+        );
+
+        let plugin = BucketPlugin::default().with_config(BucketRegistryConfig {
+            bucket: "releases".to_string(),
+            endpoint: Some("http://localhost:9000".to_string()),
+            ..Default::default()
+        });
+        let args = plugin.transfer_args(BucketProvider::S3, Path::new("a.tgz"), "app/a.tgz", true);
+        assert_eq!(
+            args,
+            vec![
+                "s3",
+                "cp",
+                "a.tgz",
+                "s3://releases/app/a.tgz",
+                "--endpoint-url",
+                "http://localhost:9000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_index_upsert() {
+        let mut index = BucketIndex::default();
+        for version in ["1.10.0", "1.2.0", "1.10.0"] {
+            index.upsert(BucketIndexVersion {
+                version: version.to_string(),
+                published_at: String::new(),
+                objects: vec![object(&format!("app/{}/app.tgz", version))],
+            });
+        }
+        let versions: Vec<_> = index.versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.0", "1.10.0"]);
+    }
+
+    #[test]
+    fn test_apt_packages_upsert() {
+        let existing = "Package: app\nVersion: 1.0.0\nArchitecture: amd64\nFilename: pool/app_1.0.0_amd64.deb\nSize: 1\nSHA256: x\n";
+        let objects = vec![
+            object("pool/app_1.1.0_amd64.deb"),
+            object("pool/app-1.1.0.tar.gz"),
+        ];
+        let packages = upsert_apt_packages(existing, "app", "1.1.0", &objects);
+        assert_eq!(packages.matches("Package: app").count(), 2);
+        assert!(packages.contains("Filename: pool/app_1.1.0_amd64.deb\nSize: 10"));
+        assert!(!packages.contains("tar.gz"));
+
+        // Re-publishing the same version replaces its stanza
+        let again = upsert_apt_packages(&packages, "app", "1.1.0", &objects[..1]);
+        assert_eq!(again.matches("Package: app").count(), 2);
+    }
+
+    #[test]
+    fn test_parse_settings() {
+        assert_eq!(BucketProvider::parse(None).unwrap(), BucketProvider::S3);
+        assert_eq!(
+            BucketProvider::parse(Some("GCS")).unwrap(),
+            BucketProvider::Gcs
+        );
+        assert!(BucketProvider::parse(Some("ftp")).is_err());
+        assert_eq!(IndexFormat::parse(None).unwrap(), None);
+        assert_eq!(
+            IndexFormat::parse(Some("apt")).unwrap(),
+            Some(IndexFormat::Apt)
+        );
+        assert!(IndexFormat::parse(Some("yum")).is_err());
+    }
+
+    #[test]
+    fn test_command_limits_keep_cloud_credentials() {
+        let limits = CommandLimits::new(
+            Some(EnvSandbox::new::<&str>(&[])),
+            std::time::Duration::from_secs(60),
+        );
+        let plugin = BucketPlugin::new(PathBuf::from(".")).with_command_limits(limits);
+        assert!(plugin.limits.allows("AWS_PROFILE"));
+        assert!(plugin.limits.allows("GOOGLE_APPLICATION_CREDENTIALS"));
+        assert!(!plugin.limits.allows("NPM_TOKEN"));
+    }
+}
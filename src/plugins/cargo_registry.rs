@@ -0,0 +1,414 @@
+//! Cargo registry resolution for alternative registries (Kellnr, Artifactory, ...)
+//!
+//! The registry a crate publishes to is, in order:
+//! 1. `registries.crates.registry` in the publish configuration
+//! 2. a single-entry `package.publish = ["name"]` in Cargo.toml
+//! 3. `[registry] default` in `.cargo/config.toml`
+//! 4. crates.io
+//!
+//! Alternative registries are looked up the way cargo does it: the
+//! `CARGO_REGISTRIES_<NAME>_INDEX` environment variable, then
+//! `[registries.<name>] index` in `.cargo/config.toml` files from the project
+//! directory upwards and `$CARGO_HOME/config.toml`. Tokens come from
+//! `CARGO_REGISTRIES_<NAME>_TOKEN` or `$CARGO_HOME/credentials.toml`.
+//!
+//! Published versions are read from the registry's sparse index, which every
+//! sparse registry serves, rather than a crates.io-style web API.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::plugins::cargo_registry::index_path;
+//!
+//! assert_eq!(index_path("serde"), "se/rd/serde");
+//! assert_eq!(index_path("syn"), "3/s/syn");
+//! ```
+
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::tr;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// One version line of a sparse index file
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub name: String,
+    pub vers: String,
+    /// SHA-256 of the .crate file
+    pub cksum: String,
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+/// `config.json` at the root of a registry index
+#[derive(Debug, Deserialize)]
+struct IndexConfig {
+    dl: String,
+    #[serde(default)]
+    api: Option<String>,
+}
+
+/// Path of a crate's file in a registry index (`se/rd/serde`, `3/s/syn`)
+pub fn index_path(crate_name: &str) -> String {
+    let name = crate_name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// Registry a crate is published to and verified against
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CargoRegistry {
+    /// Registry name from `.cargo/config.toml` (None for crates.io)
+    pub name: Option<String>,
+    /// Index URL as configured (`sparse+https://...` or a git URL)
+    pub index: Option<String>,
+    token: Option<String>,
+}
+
+impl CargoRegistry {
+    /// crates.io
+    pub fn crates_io() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the registry from an explicit name or `.cargo/config.toml`
+    pub async fn resolve(project_path: &Path, name: Option<&str>) -> anyhow::Result<Self> {
+        let configs = Self::load_configs(project_path).await;
+        let name = name.map(String::from).or_else(|| {
+            configs.iter().find_map(|c| {
+                c.get("registry")?
+                    .get("default")?
+                    .as_str()
+                    .map(String::from)
+            })
+        });
+        let Some(name) = name.filter(|n| n != "crates-io") else {
+            return Ok(Self::crates_io());
+        };
+
+        let env_name = name.to_uppercase().replace('-', "_");
+        let index = std::env::var(format!("CARGO_REGISTRIES_{}_INDEX", env_name))
+            .ok()
+            .or_else(|| {
+                configs.iter().find_map(|c| {
+                    c.get("registries")?
+                        .get(&name)?
+                        .get("index")?
+                        .as_str()
+                        .map(String::from)
+                })
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!(
+                        "cargo_registry.index_not_found",
+                        name = name,
+                        env_name = env_name
+                    )
+                )
+            })?;
+
+        let token = match std::env::var(format!("CARGO_REGISTRIES_{}_TOKEN", env_name)) {
+            Ok(token) => Some(token),
+            Err(_) => Self::credentials_token(&name).await,
+        };
+
+        Ok(Self {
+            name: Some(name),
+            index: Some(index),
+            token,
+        })
+    }
+
+    /// `.cargo/config.toml` files, most specific first
+    async fn load_configs(project_path: &Path) -> Vec<toml::Table> {
+        let start = project_path
+            .canonicalize()
+            .unwrap_or_else(|_| project_path.to_path_buf());
+        let mut dirs: Vec<PathBuf> = start.ancestors().map(|d| d.join(".cargo")).collect();
+        if let Some(cargo_home) = cargo_home() {
+            dirs.push(cargo_home);
+        }
+
+        let mut configs = Vec::new();
+        for dir in dirs {
+            for file in ["config.toml", "config"] {
+                if let Ok(content) = fs::read_to_string(dir.join(file)).await
+                    && let Ok(table) = toml::from_str::<toml::Table>(&content)
+                {
+                    configs.push(table);
+                    break;
+                }
+            }
+        }
+        configs
+    }
+
+    /// `[registries.<name>] token` from `$CARGO_HOME/credentials.toml`
+    async fn credentials_token(name: &str) -> Option<String> {
+        let cargo_home = cargo_home()?;
+        for file in ["credentials.toml", "credentials"] {
+            if let Ok(content) = fs::read_to_string(cargo_home.join(file)).await
+                && let Ok(table) = toml::from_str::<toml::Table>(&content)
+                && let Some(token) = table
+                    .get("registries")
+                    .and_then(|r| r.get(name))
+                    .and_then(|r| r.get("token"))
+                    .and_then(|t| t.as_str())
+            {
+                return Some(token.to_string());
+            }
+        }
+        None
+    }
+
+    /// Whether a token was found in the environment or credentials.toml
+    pub fn has_token(&self) -> bool {
+        self.token.as_deref().is_some_and(|t| !t.trim().is_empty())
+    }
+
+    pub fn is_crates_io(&self) -> bool {
+        self.name.is_none()
+    }
+
+    /// `--registry <name>` for cargo publish/yank (empty for crates.io)
+    pub fn args(&self) -> Vec<String> {
+        match &self.name {
+            Some(name) => vec!["--registry".to_string(), name.clone()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Name used in messages (`crates.io` or the registry name)
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("crates.io")
+    }
+
+    /// Environment variable holding the publish token
+    pub fn token_env(&self) -> String {
+        match &self.name {
+            Some(name) => format!(
+                "CARGO_REGISTRIES_{}_TOKEN",
+                name.to_uppercase().replace('-', "_")
+            ),
+            None => "CARGO_REGISTRY_TOKEN".to_string(),
+        }
+    }
+
+    /// Sparse index URL (with trailing slash); git indexes are not supported
+    fn sparse_index(&self) -> anyhow::Result<String> {
+        let index = self.index.as_deref().unwrap_or_default();
+        match index.strip_prefix("sparse+") {
+            Some(url) => Ok(format!("{}/", url.trim_end_matches('/'))),
+            None => anyhow::bail!(
+                "{}",
+                tr!(
+                    "cargo_registry.git_index",
+                    registry = self.display_name(),
+                    index = index
+                )
+            ),
+        }
+    }
+
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = http_client().get(url);
+        match &self.token {
+            Some(token) => request.header("Authorization", token),
+            None => request,
+        }
+    }
+
+    /// Published versions of a crate from the sparse index
+    pub async fn fetch_index(&self, crate_name: &str) -> anyhow::Result<Vec<IndexEntry>> {
+        let url = format!("{}{}", self.sparse_index()?, index_path(crate_name));
+        let response = self.get(&url).send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.package_not_found",
+                    package = crate_name,
+                    registry = self.display_name(),
+                    status = response.status()
+                )
+            );
+        }
+        response
+            .text()
+            .await?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Download URL of a version (the index `dl` template)
+    pub async fn download_url(&self, crate_name: &str, version: &str) -> Option<String> {
+        let url = format!("{}config.json", self.sparse_index().ok()?);
+        let config: IndexConfig = self
+            .get(&url)
+            .send_rate_limited()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+        let name = crate_name.to_lowercase();
+        let markers = [
+            "{crate}",
+            "{version}",
+            "{prefix}",
+            "{lowerprefix}",
+            "{sha256-checksum}",
+        ];
+        if !markers.iter().any(|m| config.dl.contains(m)) {
+            return Some(format!(
+                "{}/{}/{}/download",
+                config.dl.trim_end_matches('/'),
+                crate_name,
+                version
+            ));
+        }
+        let prefix = index_path(&name);
+        let prefix = prefix.rsplit_once('/').map(|(p, _)| p).unwrap_or_default();
+        Some(
+            config
+                .dl
+                .replace("{crate}", crate_name)
+                .replace("{version}", version)
+                .replace("{lowerprefix}", prefix)
+                .replace("{prefix}", prefix),
+        )
+    }
+
+    /// Web or API URL for the crate (`api` from the index config, else the index file)
+    pub async fn package_url(&self, crate_name: &str) -> String {
+        if self.is_crates_io() {
+            return format!("https://crates.io/crates/{}", crate_name);
+        }
+        let Ok(index) = self.sparse_index() else {
+            return self.index.clone().unwrap_or_default();
+        };
+        let api = async {
+            let config: IndexConfig = self
+                .get(&format!("{}config.json", index))
+                .send_rate_limited()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            config.api
+        };
+        match api.await {
+            Some(api) => format!("{}/api/v1/crates/{}", api.trim_end_matches('/'), crate_name),
+            None => format!("{}{}", index, index_path(crate_name)),
+        }
+    }
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".cargo"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_index_path() {
+        assert_eq!(index_path("a"), "1/a");
+        assert_eq!(index_path("cc"), "2/cc");
+        assert_eq!(index_path("Syn"), "3/s/syn");
+        assert_eq!(index_path("my-crate"), "my/-c/my-crate");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_from_cargo_config_and_fetch_sparse_index() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let body = concat!(
+                r#"{"name":"acme-core","vers":"0.1.0","cksum":"aa","yanked":true}"#,
+                "\n",
+                r#"{"name":"acme-core","vers":"0.2.0","cksum":"bb","deps":[]}"#,
+                "\n"
+            );
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("crates/core");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".cargo/config.toml"),
+            format!(
+                "[registry]\ndefault = \"kellnr\"\n\n[registries.kellnr]\nindex = \"sparse+http://{}/api/v1/crates/\"\n",
+                address
+            ),
+        )
+        .unwrap();
+        unsafe {
+            std::env::set_var("CARGO_REGISTRIES_KELLNR_TOKEN", "kellnr-token");
+        }
+
+        let registry = CargoRegistry::resolve(&project, None).await.unwrap();
+        assert_eq!(registry.name.as_deref(), Some("kellnr"));
+        assert_eq!(registry.args(), vec!["--registry", "kellnr"]);
+        assert_eq!(registry.token_env(), "CARGO_REGISTRIES_KELLNR_TOKEN");
+
+        let entries = registry.fetch_index("acme-core").await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].yanked);
+        assert_eq!(entries[1].cksum, "bb");
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /api/v1/crates/ac/me/acme-core "));
+        assert!(request.contains("authorization: kellnr-token"));
+
+        let git = CargoRegistry {
+            name: Some("corp".to_string()),
+            index: Some("https://git.example.com/index.git".to_string()),
+            token: None,
+        };
+        assert!(git.fetch_index("acme-core").await.is_err());
+        assert!(
+            CargoRegistry::resolve(&project, Some("crates-io"))
+                .await
+                .unwrap()
+                .is_crates_io()
+        );
+    }
+}
@@ -0,0 +1,599 @@
+//! Cloudsmith Plugin - multi-format hosted repositories
+//!
+//! Provides Cloudsmith publishing support:
+//! - Package format (npm / cargo / python / deb) detected from the project
+//!   or set in `registries.cloudsmith.format`
+//! - Two-step upload: file upload with its SHA-256, then package creation
+//!   through the format's upload endpoint
+//! - Verification by polling the packages API until synchronisation completes
+//! - Install URLs for private repositories using an entitlement token
+//!
+//! The API key comes from the token manager (`CLOUDSMITH_API_KEY`).
+
+use crate::core::config::CloudsmithRegistryConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, ValidationError,
+    ValidationResult, VerificationResult,
+};
+use crate::plugins::bucket_plugin::{manifest_identity, sha256_file};
+use crate::plugins::hosted_package::{HostedPackageFormat, package_files};
+use crate::security::command_executor::CommandLimits;
+use crate::security::token_manager::SecureTokenManager;
+use crate::tr;
+use async_trait::async_trait;
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+
+/// Cloudsmith REST API
+const API_URL: &str = "https://api.cloudsmith.io/v1";
+
+/// File upload endpoint
+const UPLOAD_URL: &str = "https://upload.cloudsmith.io";
+
+const DEFAULT_ENTITLEMENT_ENV: &str = "CLOUDSMITH_ENTITLEMENT";
+
+/// Sync polls before giving up (packages usually sync within a minute)
+const VERIFY_ATTEMPTS: u32 = 10;
+
+const VERIFY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Package entry returned by the packages API (subset)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CloudsmithPackage {
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub checksum_sha256: Option<String>,
+    #[serde(default)]
+    pub is_sync_completed: bool,
+    #[serde(default)]
+    pub is_sync_failed: bool,
+    #[serde(default)]
+    pub status_str: Option<String>,
+    #[serde(default)]
+    pub self_html_url: Option<String>,
+    #[serde(default)]
+    pub cdn_url: Option<String>,
+}
+
+/// Cloudsmith plugin
+pub struct CloudsmithPlugin {
+    project_path: PathBuf,
+    config: CloudsmithRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for CloudsmithPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl CloudsmithPlugin {
+    /// Create a new Cloudsmith plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: CloudsmithRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.cloudsmith` settings
+    pub fn with_config(mut self, config: CloudsmithRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Build npm, cargo and python packages with the sandbox and timeout of
+    /// registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    async fn format(&self) -> anyhow::Result<HostedPackageFormat> {
+        HostedPackageFormat::resolve(self.config.format.as_deref(), &self.project_path).await
+    }
+
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        match (self.config.name.clone().or(name), version) {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!("Package name not found (set registries.cloudsmith.name)"),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    fn repo_path(&self) -> String {
+        format!("{}/{}", self.config.owner, self.config.repository)
+    }
+
+    fn api_key(&self) -> anyhow::Result<String> {
+        SecureTokenManager::new()
+            .get_token("cloudsmith")
+            .map(|t| t.expose_secret().to_string())
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("cloudsmith.api_key_missing")))
+    }
+
+    fn entitlement(&self) -> Option<String> {
+        let env = self
+            .config
+            .entitlement_env
+            .as_deref()
+            .unwrap_or(DEFAULT_ENTITLEMENT_ENV);
+        std::env::var(env).ok().filter(|v| !v.is_empty())
+    }
+
+    /// Where consumers install from (`public` unless an entitlement token is given)
+    pub fn install_url(&self, format: HostedPackageFormat, entitlement: Option<&str>) -> String {
+        let repo = self.repo_path();
+        let token = entitlement.unwrap_or("public");
+        match format {
+            HostedPackageFormat::Npm => format!("https://npm.cloudsmith.io/{}/", repo),
+            HostedPackageFormat::Cargo => format!("sparse+https://cargo.cloudsmith.io/{}/", repo),
+            HostedPackageFormat::Python => {
+                format!("https://dl.cloudsmith.io/{}/{}/python/simple/", token, repo)
+            }
+            HostedPackageFormat::Deb => format!(
+                "https://dl.cloudsmith.io/{}/{}/deb/{}",
+                token,
+                repo,
+                self.config
+                    .distribution
+                    .as_deref()
+                    .and_then(|d| d.split('/').next())
+                    .unwrap_or("debian")
+            ),
+        }
+    }
+
+    /// Package creation request body for the format's upload endpoint
+    pub fn create_request(
+        &self,
+        format: HostedPackageFormat,
+        identifier: &str,
+    ) -> serde_json::Value {
+        let mut body = serde_json::json!({ "package_file": identifier });
+        if format == HostedPackageFormat::Deb
+            && let Some(ref distribution) = self.config.distribution
+        {
+            body["distribution"] = serde_json::Value::String(distribution.clone());
+        }
+        body
+    }
+
+    /// Upload one file and create the package; returns the package entry
+    async fn upload(
+        &self,
+        format: HostedPackageFormat,
+        file: &Path,
+        api_key: &str,
+    ) -> anyhow::Result<CloudsmithPackage> {
+        let file_name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Invalid file: {}", file.display()))?;
+        let (sha256, _) = sha256_file(file).await?;
+        let client = http_client();
+
+        let response = client
+            .put(format!("{}/{}/{}", UPLOAD_URL, self.repo_path(), file_name))
+            .header("X-Api-Key", api_key)
+            .header("Content-Sha256", &sha256)
+            .body(fs::read(file).await?)
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "cloudsmith.file_upload_failed",
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+        let uploaded: serde_json::Value = response.json().await?;
+        let identifier = uploaded["identifier"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("cloudsmith.no_upload_id")))?;
+
+        let response = client
+            .post(format!(
+                "{}/packages/{}/upload/{}/",
+                API_URL,
+                self.repo_path(),
+                format
+            ))
+            .header("X-Api-Key", api_key)
+            .json(&self.create_request(format, identifier))
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "cloudsmith.package_create_status",
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn find_packages(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> anyhow::Result<Vec<CloudsmithPackage>> {
+        let response = http_client()
+            .get(format!("{}/packages/{}/", API_URL, self.repo_path()))
+            .query(&[("query", format!("name:{} AND version:{}", name, version))])
+            .header("X-Api-Key", self.api_key()?)
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Sync state of the packages of a version: Ok(true) when all completed
+pub fn sync_state(packages: &[CloudsmithPackage]) -> Result<bool, String> {
+    if let Some(failed) = packages.iter().find(|p| p.is_sync_failed) {
+        return Err(tr!(
+            "cloudsmith.sync_failed",
+            package = failed.filename.as_deref().unwrap_or("package"),
+            reason = failed.status_str.as_deref().unwrap_or("unknown")
+        ));
+    }
+    Ok(!packages.is_empty() && packages.iter().all(|p| p.is_sync_completed))
+}
+
+#[async_trait]
+impl RegistryPlugin for CloudsmithPlugin {
+    fn name(&self) -> &str {
+        "cloudsmith"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.cloudsmith`, not a manifest
+        Ok(!self.config.owner.is_empty())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut metadata = HashMap::new();
+
+        if self.config.owner.is_empty() || self.config.repository.is_empty() {
+            errors.push(ValidationError {
+                field: "repository".to_string(),
+                message: tr!("cloudsmith.owner_required"),
+                severity: "error".to_string(),
+            });
+        }
+        if let Err(e) = self.api_key() {
+            errors.push(ValidationError {
+                field: "token".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            });
+        }
+
+        match self.format().await {
+            Ok(format) => {
+                if format == HostedPackageFormat::Deb && self.config.distribution.is_none() {
+                    errors.push(ValidationError {
+                        field: "distribution".to_string(),
+                        message: tr!("cloudsmith.distribution_required"),
+                        severity: "error".to_string(),
+                    });
+                }
+                metadata.insert(
+                    "format".to_string(),
+                    serde_json::Value::String(format.to_string()),
+                );
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "format".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let format = self.format().await?;
+        let (name, version) = self.package_identity().await?;
+        let out_dir = std::env::temp_dir().join(format!(
+            "package-publisher-cloudsmith-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let result = package_files(
+            &self.project_path,
+            format,
+            self.config.artifacts.as_deref(),
+            &out_dir,
+            &name,
+            &version,
+            &self.limits,
+        )
+        .await;
+        let output = match result {
+            Ok(files) => {
+                let mut lines = vec![format!(
+                    "{} package -> {}/{}",
+                    format,
+                    self.repo_path(),
+                    format
+                )];
+                for file in &files {
+                    let size = fs::metadata(file).await.map(|m| m.len()).unwrap_or(0);
+                    lines.push(format!("  {} ({} bytes)", file.display(), size));
+                }
+                Ok(lines.join("\n"))
+            }
+            Err(e) => Err(e),
+        };
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        Ok(match output {
+            Ok(output) => DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            },
+            Err(e) => DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "artifacts".to_string(),
+                    message: tr!("registry.package_create_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            },
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let format = self.format().await?;
+        let (name, version) = self.package_identity().await?;
+        let api_key = self.api_key()?;
+        let out_dir = std::env::temp_dir().join(format!(
+            "package-publisher-cloudsmith-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let result = async {
+            let files = package_files(
+                &self.project_path,
+                format,
+                self.config.artifacts.as_deref(),
+                &out_dir,
+                &name,
+                &version,
+                &self.limits,
+            )
+            .await?;
+            let mut packages = Vec::new();
+            for file in files {
+                packages.push(self.upload(format, &file, &api_key).await?);
+            }
+            Ok::<_, anyhow::Error>(packages)
+        }
+        .await;
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        let packages = match result {
+            Ok(packages) => packages,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(e.to_string()),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+        };
+
+        let entitlement = self.entitlement();
+        let install_url = self.install_url(format, entitlement.as_deref());
+        let masked_install_url = match entitlement {
+            Some(ref token) => {
+                install_url.replace(token, &SecureTokenManager::new().mask_token(token))
+            }
+            None => install_url,
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "installUrl".to_string(),
+            serde_json::Value::String(masked_install_url.clone()),
+        );
+
+        Ok(PublishResult {
+            success: true,
+            version: Some(version),
+            package_url: packages.iter().find_map(|p| p.self_html_url.clone()),
+            output: Some(format!(
+                "{}\nInstall from: {}",
+                packages
+                    .iter()
+                    .map(|p| format!(
+                        "Uploaded {} ({})",
+                        p.filename.as_deref().unwrap_or("package"),
+                        p.status_str.as_deref().unwrap_or("awaiting sync")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                masked_install_url
+            )),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let (name, version) = self.package_identity().await?;
+
+        let mut last_error = None;
+        for attempt in 1..=VERIFY_ATTEMPTS {
+            match self.find_packages(&name, &version).await {
+                Ok(packages) => match sync_state(&packages) {
+                    Ok(true) => {
+                        let package = &packages[0];
+                        return Ok(VerificationResult {
+                            verified: true,
+                            version: Some(version),
+                            url: package.self_html_url.clone(),
+                            error: None,
+                            metadata: None,
+                            integrity: Some(PackageIntegrity {
+                                download_url: package.cdn_url.clone(),
+                                checksum: package.checksum_sha256.clone(),
+                                ..Default::default()
+                            }),
+                        });
+                    }
+                    Ok(false) => {
+                        last_error = Some(if packages.is_empty() {
+                            tr!(
+                                "cloudsmith.package_not_found",
+                                package = name,
+                                version = version
+                            )
+                        } else {
+                            tr!("cloudsmith.not_synchronized")
+                        })
+                    }
+                    Err(e) => {
+                        return Ok(VerificationResult {
+                            verified: false,
+                            version: Some(version),
+                            url: None,
+                            error: Some(e),
+                            metadata: None,
+                            integrity: None,
+                        });
+                    }
+                },
+                Err(e) => last_error = Some(e.to_string()),
+            }
+
+            if attempt < VERIFY_ATTEMPTS {
+                tokio::time::sleep(VERIFY_INTERVAL).await;
+            }
+        }
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(version),
+            url: None,
+            error: last_error,
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(distribution: Option<&str>) -> CloudsmithPlugin {
+        CloudsmithPlugin::default().with_config(CloudsmithRegistryConfig {
+            owner: "acme".to_string(),
+            repository: "internal".to_string(),
+            distribution: distribution.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_install_url_and_create_request() {
+        let deb = plugin(Some("ubuntu/jammy"));
+        assert_eq!(
+            deb.install_url(HostedPackageFormat::Deb, Some("ent0123456789")),
+            "https://dl.cloudsmith.io/ent0123456789/acme/internal/deb/ubuntu"
+        );
+        assert_eq!(
+            deb.create_request(HostedPackageFormat::Deb, "abc"),
+            serde_json::json!({ "package_file": "abc", "distribution": "ubuntu/jammy" })
+        );
+
+        let python = plugin(None);
+        assert_eq!(
+            python.install_url(HostedPackageFormat::Python, None),
+            "https://dl.cloudsmith.io/public/acme/internal/python/simple/"
+        );
+        assert_eq!(
+            python.create_request(HostedPackageFormat::Python, "abc"),
+            serde_json::json!({ "package_file": "abc" })
+        );
+    }
+
+    #[test]
+    fn test_sync_state() {
+        let packages: Vec<CloudsmithPackage> = serde_json::from_str(
+            r#"[
+                {"filename": "app-1.0.0.tgz", "is_sync_completed": true, "is_sync_failed": false},
+                {"filename": "app-1.0.0.tar.gz", "is_sync_completed": false, "is_sync_failed": false}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(sync_state(&packages), Ok(false));
+        assert_eq!(sync_state(&packages[..1]), Ok(true));
+        assert_eq!(sync_state(&[]), Ok(false));
+
+        let failed: Vec<CloudsmithPackage> = serde_json::from_str(
+            r#"[{"filename": "app.deb", "is_sync_failed": true, "status_str": "Failed"}]"#,
+        )
+        .unwrap();
+        assert!(sync_state(&failed).unwrap_err().contains("app.deb"));
+    }
+}
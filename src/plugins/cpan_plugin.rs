@@ -0,0 +1,734 @@
+//! CPAN Plugin - Perl distribution upload to PAUSE
+//!
+//! Provides CPAN publishing support:
+//! - Makefile.PL (ExtUtils::MakeMaker), Build.PL (Module::Build) and dist.ini (Dist::Zilla) detection
+//! - Distribution name/version validation
+//! - Test run and dist tarball build as the dry-run
+//! - Upload to PAUSE over HTTPS (`PAUSE_USER` / `PAUSE_PASSWORD`)
+//! - Verification by polling MetaCPAN until the release is indexed
+
+use crate::core::error::PublishErrorKind;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::security::command_executor::CommandLimits;
+use crate::security::token_manager::SecureTokenManager;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::multipart::{Form, Part};
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+
+/// PAUSE upload endpoint
+const PAUSE_UPLOAD_URL: &str = "https://pause.perl.org/pause/authenquery";
+
+/// MetaCPAN API
+const METACPAN_API_URL: &str = "https://fastapi.metacpan.org/v1";
+
+/// MetaCPAN polls before giving up (PAUSE usually indexes within a few minutes)
+const VERIFY_ATTEMPTS: u32 = 10;
+
+const VERIFY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Build tool used by the distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpanBuildTool {
+    /// dist.ini
+    DistZilla,
+    /// Build.PL
+    ModuleBuild,
+    /// Makefile.PL
+    MakeMaker,
+}
+
+impl CpanBuildTool {
+    /// Manifest file of the build tool
+    pub fn manifest(&self) -> &'static str {
+        match self {
+            CpanBuildTool::DistZilla => "dist.ini",
+            CpanBuildTool::ModuleBuild => "Build.PL",
+            CpanBuildTool::MakeMaker => "Makefile.PL",
+        }
+    }
+
+    /// Commands that run the test suite
+    fn test_steps(&self) -> Vec<(&'static str, Vec<&'static str>)> {
+        match self {
+            CpanBuildTool::DistZilla => vec![("dzil", vec!["test"])],
+            CpanBuildTool::ModuleBuild => vec![
+                ("perl", vec!["Build.PL"]),
+                ("./Build", vec![]),
+                ("./Build", vec!["test"]),
+            ],
+            CpanBuildTool::MakeMaker => vec![
+                ("perl", vec!["Makefile.PL"]),
+                ("make", vec![]),
+                ("make", vec!["test"]),
+            ],
+        }
+    }
+
+    /// Commands that produce `<Dist>-<version>.tar.gz` in the project root
+    fn build_steps(&self) -> Vec<(&'static str, Vec<&'static str>)> {
+        match self {
+            CpanBuildTool::DistZilla => vec![("dzil", vec!["build"])],
+            CpanBuildTool::ModuleBuild => vec![
+                ("perl", vec!["Build.PL"]),
+                ("./Build", vec!["manifest"]),
+                ("./Build", vec!["dist"]),
+            ],
+            CpanBuildTool::MakeMaker => vec![
+                ("perl", vec!["Makefile.PL"]),
+                ("make", vec!["manifest"]),
+                ("make", vec!["dist"]),
+            ],
+        }
+    }
+}
+
+/// Distribution metadata read from the build manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpanDist {
+    /// Distribution name (`Foo-Bar`)
+    pub name: Option<String>,
+    pub version: Option<String>,
+    /// Module file the version is read from (`VERSION_FROM` / `dist_version_from`)
+    pub version_from: Option<String>,
+    pub build_tool: CpanBuildTool,
+}
+
+impl CpanDist {
+    /// Tarball name produced by the build tool
+    pub fn tarball_name(&self) -> Option<String> {
+        Some(format!(
+            "{}-{}.tar.gz",
+            self.name.as_ref()?,
+            self.version.as_ref()?
+        ))
+    }
+}
+
+/// Parse the top-level `name` / `version` of a dist.ini
+pub fn parse_dist_ini(content: &str) -> CpanDist {
+    let mut dist = CpanDist {
+        name: None,
+        version: None,
+        version_from: None,
+        build_tool: CpanBuildTool::DistZilla,
+    };
+
+    for line in content.lines().map(str::trim) {
+        // Plugin sections follow the top-level settings
+        if line.starts_with('[') {
+            break;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().to_string();
+            match key.trim() {
+                "name" => dist.name = Some(value),
+                "version" => dist.version = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    dist
+}
+
+/// Parse a Makefile.PL (`NAME`, `DISTNAME`, `VERSION`, `VERSION_FROM`) or a
+/// Build.PL (`module_name`, `dist_name`, `dist_version`, `dist_version_from`)
+pub fn parse_perl_manifest(content: &str, build_tool: CpanBuildTool) -> CpanDist {
+    let value = |key: &str| {
+        Regex::new(&format!(r#"['"]?\b{}\b['"]?\s*=>\s*['"]([^'"]+)['"]"#, key))
+            .unwrap()
+            .captures(content)
+            .map(|c| c[1].to_string())
+    };
+
+    let (module, dist_name, version, version_from) = match build_tool {
+        CpanBuildTool::ModuleBuild => (
+            value("module_name"),
+            value("dist_name"),
+            value("dist_version"),
+            value("dist_version_from"),
+        ),
+        _ => (
+            value("NAME"),
+            value("DISTNAME"),
+            value("VERSION"),
+            value("VERSION_FROM"),
+        ),
+    };
+
+    CpanDist {
+        name: dist_name.or_else(|| module.map(|m| m.replace("::", "-"))),
+        version,
+        version_from,
+        build_tool,
+    }
+}
+
+/// Extract `$VERSION` (or a `package Foo 1.23;` declaration) from a module
+pub fn parse_module_version(content: &str) -> Option<String> {
+    let assignment = Regex::new(r#"\$VERSION\s*=\s*['"]?(v?[0-9][0-9._]*)"#).unwrap();
+    let package = Regex::new(r"(?m)^\s*package\s+[\w:]+\s+(v?[0-9][0-9._]*)\s*[;{]").unwrap();
+
+    assignment
+        .captures(content)
+        .or_else(|| package.captures(content))
+        .map(|c| c[1].to_string())
+}
+
+/// Read the PAUSE user from `PAUSE_USER` or `~/.pause` (`user NAME`)
+fn pause_user(pause_file: Option<&str>) -> Option<String> {
+    if let Ok(user) = std::env::var("PAUSE_USER")
+        && !user.trim().is_empty()
+    {
+        return Some(user.trim().to_uppercase());
+    }
+
+    pause_file?.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once(char::is_whitespace)?;
+        (key == "user").then(|| value.trim().to_uppercase())
+    })
+}
+
+/// MetaCPAN release document (subset)
+#[derive(Debug, Deserialize)]
+struct MetaCpanRelease {
+    version: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    download_url: Option<String>,
+}
+
+/// Environment variables the Perl build tools need on top of the sandbox
+/// allowlist
+const TOOL_ENV: &[&str] = &["PERL*"];
+
+/// CPAN (PAUSE) plugin
+pub struct CpanPlugin {
+    project_path: PathBuf,
+    limits: CommandLimits,
+}
+
+impl Default for CpanPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl CpanPlugin {
+    /// Create a new CPAN plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Run the Perl build tools with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Detect the build tool (dist.ini takes precedence over generated Makefile.PL)
+    async fn build_tool(project_path: &Path) -> Option<CpanBuildTool> {
+        for tool in [
+            CpanBuildTool::DistZilla,
+            CpanBuildTool::ModuleBuild,
+            CpanBuildTool::MakeMaker,
+        ] {
+            if fs::metadata(project_path.join(tool.manifest()))
+                .await
+                .is_ok()
+            {
+                return Some(tool);
+            }
+        }
+        None
+    }
+
+    async fn load_dist(&self) -> anyhow::Result<CpanDist> {
+        let build_tool = Self::build_tool(&self.project_path)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("dist.ini, Build.PL or Makefile.PL not found"))?;
+        let content = fs::read_to_string(self.project_path.join(build_tool.manifest())).await?;
+
+        let mut dist = match build_tool {
+            CpanBuildTool::DistZilla => parse_dist_ini(&content),
+            tool => parse_perl_manifest(&content, tool),
+        };
+
+        if dist.version.is_none()
+            && let Some(ref module) = dist.version_from
+            && let Ok(source) = fs::read_to_string(self.project_path.join(module)).await
+        {
+            dist.version = parse_module_version(&source);
+        }
+
+        Ok(dist)
+    }
+
+    /// Validate distribution name (`Foo-Bar`, no `::`)
+    fn validate_dist_name(&self, name: &str) -> Vec<ValidationError> {
+        let valid = Regex::new(r"^[A-Za-z][A-Za-z0-9_]*(-[A-Za-z0-9_]+)*$").unwrap();
+        if valid.is_match(name) {
+            Vec::new()
+        } else {
+            vec![ValidationError {
+                field: "name".to_string(),
+                message: tr!("cpan.invalid_dist_name", name = name),
+                severity: "error".to_string(),
+            }]
+        }
+    }
+
+    /// Validate version (decimal `1.23`, dotted `v1.2.3`, optional `_NN` trial suffix)
+    fn validate_version(&self, version: &str) -> Vec<ValidationError> {
+        let valid = Regex::new(r"^v?\d+(\.\d+)*(_\d+)?$").unwrap();
+        if valid.is_match(version) {
+            Vec::new()
+        } else {
+            vec![ValidationError {
+                field: "version".to_string(),
+                message: tr!("cpan.invalid_version", version = version),
+                severity: "error".to_string(),
+            }]
+        }
+    }
+
+    async fn run_steps(&self, steps: &[(&str, Vec<&str>)]) -> anyhow::Result<String> {
+        let mut log = String::new();
+        for (program, args) in steps {
+            let mut command = self.limits.command(program);
+            command.args(args).current_dir(&self.project_path);
+            let output = self.limits.output(&mut command).await?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            log.push_str(&stdout);
+            log.push_str(&stderr);
+
+            if !output.status.success() {
+                anyhow::bail!(
+                    "{} {} failed:\n{}{}",
+                    program,
+                    args.join(" "),
+                    stdout,
+                    stderr
+                );
+            }
+        }
+        Ok(log)
+    }
+
+    /// Build the dist tarball and return its path
+    async fn build_dist(&self, dist: &CpanDist) -> anyhow::Result<PathBuf> {
+        self.run_steps(&dist.build_tool.build_steps()).await?;
+
+        let tarball = dist
+            .tarball_name()
+            .ok_or_else(|| anyhow::anyhow!("Distribution name or version not found"))?;
+        let path = self.project_path.join(&tarball);
+        if fs::metadata(&path).await.is_err() {
+            anyhow::bail!("Dist tarball not found: {}", tarball);
+        }
+        Ok(path)
+    }
+
+    async fn pause_credentials(&self) -> Option<(String, String)> {
+        let pause_file = match std::env::var_os("HOME") {
+            Some(home) => fs::read_to_string(PathBuf::from(home).join(".pause"))
+                .await
+                .ok(),
+            None => None,
+        };
+        let user = pause_user(pause_file.as_deref())?;
+        let password = SecureTokenManager::new().get_token("cpan")?;
+        Some((user, password.expose_secret().to_string()))
+    }
+
+    async fn fetch_release(&self, name: &str) -> anyhow::Result<Option<MetaCpanRelease>> {
+        let response = http_client()
+            .get(format!("{}/release/{}", METACPAN_API_URL, name))
+            .send_rate_limited()
+            .await?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        Ok(response.json::<MetaCpanRelease>().await.ok())
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for CpanPlugin {
+    fn name(&self) -> &str {
+        "cpan"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        Ok(Self::build_tool(Path::new(project_path)).await.is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let dist = self.load_dist().await?;
+
+        match dist.name {
+            Some(ref name) => {
+                errors.extend(self.validate_dist_name(name));
+                metadata.insert(
+                    "packageName".to_string(),
+                    serde_json::Value::String(name.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("cpan.dist_name_missing", file = dist.build_tool.manifest()),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match dist.version {
+            Some(ref version) => {
+                errors.extend(self.validate_version(version));
+                if version.contains('_') {
+                    warnings.push(ValidationWarning {
+                        field: "version".to_string(),
+                        message: tr!("cpan.trial_release", version = version),
+                        severity: "warning".to_string(),
+                    });
+                }
+                metadata.insert(
+                    "version".to_string(),
+                    serde_json::Value::String(version.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "version".to_string(),
+                message: tr!("cpan.version_not_found", file = dist.build_tool.manifest()),
+                severity: "error".to_string(),
+            }),
+        }
+
+        for (file, key) in [
+            ("Changes", "cpan.changes_recommended"),
+            ("LICENSE", "cpan.license_recommended"),
+        ] {
+            if dist.build_tool != CpanBuildTool::DistZilla
+                && fs::metadata(self.project_path.join(file)).await.is_err()
+            {
+                warnings.push(ValidationWarning {
+                    field: file.to_string(),
+                    message: tr!(key),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+
+        if fs::metadata(self.project_path.join("t")).await.is_err() {
+            warnings.push(ValidationWarning {
+                field: "t".to_string(),
+                message: tr!("cpan.no_tests"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if self.pause_credentials().await.is_none() {
+            warnings.push(ValidationWarning {
+                field: "PAUSE_PASSWORD".to_string(),
+                message: tr!("cpan.credentials_missing"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let dist = self.load_dist().await?;
+
+        let tested = match self.run_steps(&dist.build_tool.test_steps()).await {
+            Ok(output) => output,
+            Err(e) => {
+                return Ok(DryRunResult {
+                    success: false,
+                    output: e.to_string(),
+                    estimated_size: None,
+                    errors: Some(vec![ValidationError {
+                        field: "test".to_string(),
+                        message: tr!("cpan.tests_failed"),
+                        severity: "error".to_string(),
+                    }]),
+                });
+            }
+        };
+
+        match self.build_dist(&dist).await {
+            Ok(tarball) => {
+                let size = fs::metadata(&tarball).await.map(|m| m.len()).ok();
+                Ok(DryRunResult {
+                    success: true,
+                    output: format!("{}\nBuilt {}", tested, tarball.display()),
+                    estimated_size: size.map(|s| format!("{:.1} kB", s as f64 / 1024.0)),
+                    errors: None,
+                })
+            }
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "dist".to_string(),
+                    message: tr!("cpan.dist_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let dist = self.load_dist().await?;
+
+        let Some((user, password)) = self.pause_credentials().await else {
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("cpan.credentials_required")),
+                metadata: None,
+                error_kind: None,
+            });
+        };
+
+        let tarball = match self.build_dist(&dist).await {
+            Ok(path) => path,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!("cpan.dist_failed", error = e)),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+        };
+        let file_name = tarball
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = fs::read(&tarball).await?;
+
+        let form = Form::new()
+            .text("HIDDENNAME", user.clone())
+            .text("CAN_MULTIPART", "1")
+            .text("pause99_add_uri_subdirtext", "")
+            .text(
+                "SUBMIT_pause99_add_uri_httpupload",
+                " Upload this file from my disk ",
+            )
+            .part(
+                "pause99_add_uri_httpupload",
+                Part::bytes(content)
+                    .file_name(file_name.clone())
+                    .mime_str("application/octet-stream")?,
+            );
+
+        let response = http_client()
+            .post(PAUSE_UPLOAD_URL)
+            .basic_auth(&user, Some(&password))
+            .multipart(form)
+            .send_rate_limited()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => Ok(PublishResult {
+                success: true,
+                version: dist.version.clone(),
+                package_url: dist
+                    .name
+                    .as_ref()
+                    .map(|name| format!("https://metacpan.org/dist/{}", name)),
+                output: Some(tr!("cpan.uploaded", file = file_name, user = user)),
+                error: None,
+                metadata: None,
+                error_kind: None,
+            }),
+            Ok(response) => {
+                let status = response.status();
+                Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(if status == reqwest::StatusCode::UNAUTHORIZED {
+                        tr!("cpan.auth_failed")
+                    } else {
+                        tr!("cpan.upload_status", status = status)
+                    }),
+                    metadata: None,
+                    error_kind: (status == reqwest::StatusCode::UNAUTHORIZED)
+                        .then_some(PublishErrorKind::AuthFailed),
+                })
+            }
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("cpan.upload_failed", error = e)),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let dist = self.load_dist().await?;
+        let name = dist
+            .name
+            .ok_or_else(|| anyhow::anyhow!("Distribution name not found"))?;
+        let expected_version = dist
+            .version
+            .ok_or_else(|| anyhow::anyhow!("Distribution version not found"))?;
+        let url = format!("https://metacpan.org/dist/{}", name);
+
+        let mut latest = None;
+        for attempt in 1..=VERIFY_ATTEMPTS {
+            match self.fetch_release(&name).await {
+                Ok(Some(release)) if release.version == expected_version => {
+                    let mut metadata = HashMap::new();
+                    if let Some(author) = release.author {
+                        metadata.insert("author".to_string(), serde_json::Value::String(author));
+                    }
+                    if let Some(download_url) = release.download_url {
+                        metadata.insert(
+                            "downloadUrl".to_string(),
+                            serde_json::Value::String(download_url),
+                        );
+                    }
+                    return Ok(VerificationResult {
+                        verified: true,
+                        version: Some(expected_version),
+                        url: Some(url),
+                        error: None,
+                        metadata: Some(metadata),
+                        integrity: None,
+                    });
+                }
+                Ok(release) => latest = release.map(|r| r.version),
+                Err(e) if attempt == VERIFY_ATTEMPTS => {
+                    return Ok(VerificationResult {
+                        verified: false,
+                        version: Some(expected_version),
+                        url: Some(url),
+                        error: Some(tr!("registry.verify_failed", error = e)),
+                        metadata: None,
+                        integrity: None,
+                    });
+                }
+                Err(_) => {}
+            }
+
+            if attempt < VERIFY_ATTEMPTS {
+                tokio::time::sleep(VERIFY_INTERVAL).await;
+            }
+        }
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(expected_version.clone()),
+            url: Some(url),
+            error: Some(tr!(
+                "cpan.not_indexed",
+                version = expected_version,
+                latest = latest.unwrap_or_else(|| tr!("registry.none"))
+            )),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifests() {
+        let dist = parse_dist_ini("name    = Acme-Widget\nversion = 0.42\n\n[@Basic]\nname = x\n");
+        assert_eq!(dist.name.as_deref(), Some("Acme-Widget"));
+        assert_eq!(dist.version.as_deref(), Some("0.42"));
+        assert_eq!(
+            dist.tarball_name().as_deref(),
+            Some("Acme-Widget-0.42.tar.gz")
+        );
+
+        let makefile = r#"
+use ExtUtils::MakeMaker;
+WriteMakefile(
+    NAME         => 'Acme::Widget',
+    VERSION_FROM => 'lib/Acme/Widget.pm',
+    LICENSE      => 'perl_5',
+);
+"#;
+        let dist = parse_perl_manifest(makefile, CpanBuildTool::MakeMaker);
+        assert_eq!(dist.name.as_deref(), Some("Acme-Widget"));
+        assert_eq!(dist.version, None);
+        assert_eq!(dist.version_from.as_deref(), Some("lib/Acme/Widget.pm"));
+
+        let build = "Module::Build->new(module_name => 'Acme::Widget', dist_version => '1.2.3')";
+        let dist = parse_perl_manifest(build, CpanBuildTool::ModuleBuild);
+        assert_eq!(dist.name.as_deref(), Some("Acme-Widget"));
+        assert_eq!(dist.version.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn test_parse_module_version() {
+        assert_eq!(
+            parse_module_version("package Acme::Widget;\nour $VERSION = '0.05';\n").as_deref(),
+            Some("0.05")
+        );
+        assert_eq!(
+            parse_module_version("package Acme::Widget v1.2.0;\n").as_deref(),
+            Some("v1.2.0")
+        );
+        assert_eq!(parse_module_version("package Acme::Widget;\n"), None);
+    }
+
+    #[test]
+    fn test_validate_version() {
+        let plugin = CpanPlugin::default();
+        assert!(plugin.validate_version("1.23").is_empty());
+        assert!(plugin.validate_version("v1.2.3").is_empty());
+        assert!(plugin.validate_version("1.23_01").is_empty());
+        assert!(!plugin.validate_version("1.2-beta").is_empty());
+    }
+}
@@ -0,0 +1,460 @@
+//! CRAN Plugin - R package submission to CRAN
+//!
+//! CRAN releases are reviewed by humans, so this plugin prepares a submission
+//! instead of publishing directly:
+//! - DESCRIPTION detection and validation against common CRAN policy checks
+//! - `devtools::check(remote = TRUE, manual = TRUE)` as the dry-run
+//! - Building the submission bundle with `devtools::build()`
+//! - Reporting the CRAN submission URL (the package is not available until accepted)
+//! - Verification that the version has appeared on CRAN
+
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// CRAN web submission form
+pub const CRAN_SUBMISSION_URL: &str = "https://cran.r-project.org/submit.html";
+
+/// CRAN package metadata API
+const CRANDB_URL: &str = "https://crandb.r-pkg.org";
+
+/// Parse a Debian Control File (DESCRIPTION) into fields
+///
+/// Continuation lines (starting with whitespace) are joined to the previous field.
+pub fn parse_description(content: &str) -> HashMap<String, String> {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(ref key) = current
+                && let Some(value) = fields.get_mut(key)
+            {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            fields.insert(key.clone(), value.trim().to_string());
+            current = Some(key);
+        }
+    }
+
+    fields
+}
+
+/// crandb package document (subset)
+#[derive(Debug, Deserialize)]
+struct CranPackageInfo {
+    #[serde(rename = "Version")]
+    version: String,
+}
+
+/// Environment variables R needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["R_*"];
+
+/// CRAN submission plugin
+pub struct CranPlugin {
+    project_path: PathBuf,
+    limits: CommandLimits,
+}
+
+impl Default for CranPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl CranPlugin {
+    /// Create a new CRAN plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Run Rscript with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    async fn load_description(&self) -> anyhow::Result<HashMap<String, String>> {
+        let content = fs::read_to_string(self.project_path.join("DESCRIPTION")).await?;
+        Ok(parse_description(&content))
+    }
+
+    /// Validate package name (letters, digits and dots; starts with a letter)
+    fn validate_package_name(&self, name: &str) -> Vec<ValidationError> {
+        let valid = Regex::new(r"^[A-Za-z][A-Za-z0-9.]*[A-Za-z0-9]$").unwrap();
+        if valid.is_match(name) {
+            Vec::new()
+        } else {
+            vec![ValidationError {
+                field: "Package".to_string(),
+                message: tr!("cran.invalid_name"),
+                severity: "error".to_string(),
+            }]
+        }
+    }
+
+    /// Validate version (`x.y-z` / `x.y.z`); development versions are rejected
+    fn validate_version(&self, version: &str) -> Vec<ValidationError> {
+        let valid = Regex::new(r"^\d+[.-]\d+([.-]\d+)*$").unwrap();
+        if !valid.is_match(version) {
+            return vec![ValidationError {
+                field: "Version".to_string(),
+                message: tr!("cran.invalid_version", version = version),
+                severity: "error".to_string(),
+            }];
+        }
+
+        let is_development = version
+            .split(['.', '-'])
+            .skip(3)
+            .any(|part| part.parse::<u64>().is_ok_and(|n| n >= 9000));
+        if is_development {
+            return vec![ValidationError {
+                field: "Version".to_string(),
+                message: tr!("cran.development_version", version = version),
+                severity: "error".to_string(),
+            }];
+        }
+
+        Vec::new()
+    }
+
+    async fn run_rscript(&self, expression: &str) -> anyhow::Result<String> {
+        let mut command = self.limits.command("Rscript");
+        command
+            .args(["-e", expression])
+            .current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}{}", stdout, stderr);
+        }
+
+        Ok(stdout + &stderr)
+    }
+}
+
+/// Escape a path for use inside an R string literal
+fn r_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[async_trait]
+impl RegistryPlugin for CranPlugin {
+    fn name(&self) -> &str {
+        "cran"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        let path = Path::new(project_path).join("DESCRIPTION");
+        let Ok(content) = fs::read_to_string(&path).await else {
+            return Ok(false);
+        };
+        Ok(parse_description(&content).contains_key("Package"))
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let description = self.load_description().await?;
+
+        for field in ["Package", "Version", "Title", "Description", "License"] {
+            if !description.contains_key(field) {
+                errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("registry.field_required", field = field),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        if let Some(name) = description.get("Package") {
+            errors.extend(self.validate_package_name(name));
+            metadata.insert(
+                "packageName".to_string(),
+                serde_json::Value::String(name.clone()),
+            );
+        }
+
+        if let Some(version) = description.get("Version") {
+            errors.extend(self.validate_version(version));
+            metadata.insert(
+                "version".to_string(),
+                serde_json::Value::String(version.clone()),
+            );
+        }
+
+        if let Some(title) = description.get("Title")
+            && title.ends_with('.')
+        {
+            warnings.push(ValidationWarning {
+                field: "Title".to_string(),
+                message: tr!("cran.title_period"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if let Some(text) = description.get("Description") {
+            let package = description.get("Package").map(String::as_str);
+            if text.starts_with("This package") || package.is_some_and(|p| text.starts_with(p)) {
+                warnings.push(ValidationWarning {
+                    field: "Description".to_string(),
+                    message: tr!("cran.description_start"),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+
+        // A maintainer is either given directly or via the "cre" role in Authors@R
+        match description.get("Authors@R") {
+            Some(authors) if !authors.contains("\"cre\"") => errors.push(ValidationError {
+                field: "Authors@R".to_string(),
+                message: tr!("cran.no_maintainer_role"),
+                severity: "error".to_string(),
+            }),
+            Some(_) => {}
+            None if !description.contains_key("Maintainer") => errors.push(ValidationError {
+                field: "Authors@R".to_string(),
+                message: tr!("cran.maintainer_required"),
+                severity: "error".to_string(),
+            }),
+            None => {}
+        }
+
+        for (file, key) in [
+            ("cran-comments.md", "cran.comments_recommended"),
+            ("NEWS.md", "cran.news_recommended"),
+        ] {
+            if fs::metadata(self.project_path.join(file)).await.is_err() {
+                warnings.push(ValidationWarning {
+                    field: file.to_string(),
+                    message: tr!(key),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        match self
+            .run_rscript("devtools::check(remote = TRUE, manual = TRUE, error_on = \"warning\")")
+            .await
+        {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "check".to_string(),
+                    message: tr!("cran.check_failed"),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let description = self.load_description().await?;
+        let bundle_dir = std::env::temp_dir().join("package-publisher-cran");
+        fs::create_dir_all(&bundle_dir).await?;
+
+        let expression = format!(
+            "cat(devtools::build(path = {}))",
+            r_string(&bundle_dir.display().to_string())
+        );
+
+        match self.run_rscript(&expression).await {
+            Ok(output) => {
+                let bundle = output
+                    .lines()
+                    .rev()
+                    .find(|line| line.trim().ends_with(".tar.gz"))
+                    .map(|line| line.trim().to_string())
+                    .unwrap_or_else(|| bundle_dir.display().to_string());
+
+                let mut metadata = HashMap::new();
+                metadata.insert("pendingReview".to_string(), serde_json::Value::Bool(true));
+                metadata.insert(
+                    "submissionUrl".to_string(),
+                    serde_json::Value::String(CRAN_SUBMISSION_URL.to_string()),
+                );
+                metadata.insert(
+                    "bundle".to_string(),
+                    serde_json::Value::String(bundle.clone()),
+                );
+
+                Ok(PublishResult {
+                    success: true,
+                    version: description.get("Version").cloned(),
+                    package_url: Some(CRAN_SUBMISSION_URL.to_string()),
+                    output: Some(tr!(
+                        "cran.bundle_created",
+                        bundle = bundle,
+                        url = CRAN_SUBMISSION_URL
+                    )),
+                    error: None,
+                    metadata: Some(metadata),
+                    error_kind: None,
+                })
+            }
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("cran.bundle_failed", error = e)),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let description = self.load_description().await?;
+        let name = description
+            .get("Package")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+        let expected_version = description
+            .get("Version")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+        let url = format!("https://cran.r-project.org/package={}", name);
+
+        let response = http_client()
+            .get(format!("{}/{}", CRANDB_URL, name))
+            .send_rate_limited()
+            .await;
+
+        let published = match response {
+            Ok(response) if response.status().is_success() => response
+                .json::<CranPackageInfo>()
+                .await
+                .ok()
+                .map(|info| info.version),
+            Ok(_) => None,
+            Err(e) => {
+                return Ok(VerificationResult {
+                    verified: false,
+                    version: Some(expected_version),
+                    url: Some(url),
+                    error: Some(tr!("registry.verify_failed", error = e)),
+                    metadata: None,
+                    integrity: None,
+                });
+            }
+        };
+
+        if published.as_deref() == Some(expected_version.as_str()) {
+            return Ok(VerificationResult {
+                verified: true,
+                version: Some(expected_version),
+                url: Some(url),
+                error: None,
+                metadata: None,
+                integrity: None,
+            });
+        }
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(expected_version.clone()),
+            url: Some(url),
+            error: Some(tr!(
+                "cran.not_published",
+                version = expected_version,
+                current = published.unwrap_or_else(|| tr!("registry.none"))
+            )),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_description() {
+        let fields = parse_description(
+            "Package: mypkg\nTitle: Tools for Things\nDescription: Does things\n    across lines.\nVersion: 1.0.0\n",
+        );
+        assert_eq!(fields["Package"], "mypkg");
+        assert_eq!(fields["Description"], "Does things across lines.");
+        assert_eq!(fields["Version"], "1.0.0");
+    }
+
+    #[test]
+    fn test_validate_version() {
+        let plugin = CranPlugin::default();
+        assert!(plugin.validate_version("1.2-3").is_empty());
+        assert!(plugin.validate_version("0.1.0").is_empty());
+        assert!(!plugin.validate_version("1").is_empty());
+        assert!(!plugin.validate_version("0.1.0.9000").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_description() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 0.2.0\nTitle: Tools for Things.\nDescription: This package does things.\nLicense: MIT + file LICENSE\nAuthors@R: person(\"A\", \"B\", role = \"aut\")\n",
+        )
+        .unwrap();
+
+        let plugin = CranPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.validate().await.unwrap();
+
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].field, "Authors@R");
+        let fields: Vec<_> = result.warnings.iter().map(|w| w.field.as_str()).collect();
+        assert!(fields.contains(&"Title"));
+        assert!(fields.contains(&"Description"));
+        assert!(fields.contains(&"cran-comments.md"));
+    }
+}
@@ -5,22 +5,43 @@
 //! - Crates.io naming rules enforcement
 //! - SemVer version validation
 //! - cargo check/clippy integration
+//! - Optional feature matrix checks (`registries.crates.featureMatrix`)
+//! - Optional API compatibility checks (`registries.crates.semverChecks`)
 //! - Dry-run and publish operations
-//! - Package verification on crates.io
+//! - Package verification on crates.io, or the sparse index of an alternative
+//!   registry (`registries.crates.registry`, `package.publish`)
 //! - Yank support for rollback
 
+use crate::core::config::{CratesAuthMode, CratesRegistryConfig};
+use crate::core::error::PublishErrorKind;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::retry::{RetryManager, RetryOptions};
 use crate::core::traits::{
-    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
-    ValidationWarning, VerificationResult,
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, RollbackResult,
+    ValidationError, ValidationResult, ValidationWarning, VerificationResult,
 };
+use crate::plugins::cargo_registry::CargoRegistry;
+use crate::plugins::trusted_publishing::{CiEnvironment, TrustedPublishingToken};
+use crate::security::command_executor::{
+    CI_IDENTITY_ENV, DEFAULT_REGISTRY_COMMAND_TIMEOUT, EnvSandbox, output_with_timeout,
+};
+use crate::tr;
+use crate::validation::feature_matrix::FeatureMatrixChecker;
+use crate::validation::semver_checks::SemverChecker;
+use crate::validation::version_validator::VersionValidator;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
+use std::time::Duration;
 use tokio::fs;
 use tokio::process::Command;
 
+/// Environment variables cargo needs on top of the sandbox allowlist
+pub(crate) const CARGO_ENV: &[&str] =
+    &["CARGO_*", "RUSTUP_*", "RUSTC*", "RUSTFLAGS", "RUSTDOCFLAGS"];
+
 /// Cargo.toml package section
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CargoPackage {
@@ -34,6 +55,8 @@ pub struct CargoPackage {
     pub keywords: Option<Vec<String>>,
     pub categories: Option<Vec<String>>,
     pub edition: Option<String>,
+    /// `false` or the registries the crate may be published to
+    pub publish: Option<toml::Value>,
 }
 
 /// Cargo.toml structure (simplified)
@@ -60,11 +83,40 @@ struct CrateData {
 #[derive(Debug, Deserialize)]
 struct VersionData {
     num: String,
+    #[serde(default)]
+    checksum: Option<String>,
+    #[serde(default)]
+    dl_path: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+impl VersionData {
+    /// Integrity data for this version (`checksum` is the SHA-256 of the .crate file)
+    fn integrity(&self) -> PackageIntegrity {
+        PackageIntegrity {
+            download_url: self
+                .dl_path
+                .as_ref()
+                .map(|path| match path.starts_with('/') {
+                    true => format!("https://crates.io{}", path),
+                    false => path.clone(),
+                }),
+            shasum: None,
+            integrity: None,
+            checksum: self.checksum.clone(),
+        }
+    }
 }
 
 /// Crates.io registry plugin
 pub struct CratesIoPlugin {
     project_path: PathBuf,
+    config: CratesRegistryConfig,
+    retry: RetryOptions,
+    sandbox: Option<EnvSandbox>,
+    command_timeout: Duration,
+    ci: CiEnvironment,
 }
 
 impl Default for CratesIoPlugin {
@@ -76,7 +128,51 @@ impl Default for CratesIoPlugin {
 impl CratesIoPlugin {
     /// Create a new Crates.io plugin instance
     pub fn new(project_path: PathBuf) -> Self {
-        Self { project_path }
+        Self {
+            project_path,
+            config: CratesRegistryConfig::default(),
+            retry: RetryOptions::default(),
+            sandbox: None,
+            command_timeout: DEFAULT_REGISTRY_COMMAND_TIMEOUT,
+            ci: CiEnvironment::default(),
+        }
+    }
+
+    /// Apply `registries.crates` settings
+    pub fn with_config(mut self, config: CratesRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Retry cargo commands and registry requests on transient failures
+    pub fn with_retry(mut self, retry: RetryOptions) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run cargo with a minimal environment (cargo and rustup variables and
+    /// the CI identity stay available)
+    pub fn with_sandbox(mut self, sandbox: EnvSandbox) -> Self {
+        self.sandbox = Some(
+            sandbox
+                .with_allowed(CARGO_ENV)
+                .with_allowed(CI_IDENTITY_ENV),
+        );
+        self
+    }
+
+    /// Kill cargo commands running longer than `timeout`, with the processes
+    /// they spawned
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// Read the OIDC endpoint and registry tokens for trusted publishing from
+    /// `ci` instead of the process environment
+    pub fn with_ci_environment(mut self, ci: CiEnvironment) -> Self {
+        self.ci = ci;
+        self
     }
 
     /// Load and parse Cargo.toml
@@ -99,8 +195,7 @@ impl CratesIoPlugin {
         {
             errors.push(ValidationError {
                 field: "package.name".to_string(),
-                message: "パッケージ名は英数字、ハイフン、アンダースコアのみ使用可能です"
-                    .to_string(),
+                message: tr!("crates_io.invalid_name"),
                 severity: "error".to_string(),
             });
         }
@@ -109,7 +204,7 @@ impl CratesIoPlugin {
         if name.is_empty() {
             errors.push(ValidationError {
                 field: "package.name".to_string(),
-                message: "パッケージ名は空にできません".to_string(),
+                message: tr!("crates_io.empty_name"),
                 severity: "error".to_string(),
             });
         }
@@ -118,7 +213,7 @@ impl CratesIoPlugin {
         if name.len() > 64 {
             errors.push(ValidationError {
                 field: "package.name".to_string(),
-                message: "パッケージ名は64文字以内である必要があります".to_string(),
+                message: tr!("crates_io.name_too_long"),
                 severity: "error".to_string(),
             });
         }
@@ -131,15 +226,64 @@ impl CratesIoPlugin {
         semver::Version::parse(version).is_ok()
     }
 
+    /// Registry from `registries.crates.registry`, a single `package.publish`
+    /// entry or `.cargo/config.toml`
+    async fn registry(&self) -> anyhow::Result<CargoRegistry> {
+        let name = match self.config.registry.clone() {
+            Some(name) => Some(name),
+            None => self
+                .load_cargo_toml()
+                .await
+                .ok()
+                .and_then(|toml| toml.package?.publish)
+                .and_then(|publish| match publish.as_array()?.as_slice() {
+                    [registry] => registry.as_str().map(String::from),
+                    _ => None,
+                }),
+        };
+        CargoRegistry::resolve(&self.project_path, name.as_deref()).await
+    }
+
+    /// Run cargo with `--registry <name>` for alternative registries
+    async fn run_cargo_registry(&self, args: &[&str]) -> anyhow::Result<String> {
+        let registry_args = self.registry().await?.args();
+        let mut all: Vec<&str> = args.to_vec();
+        all.extend(registry_args.iter().map(String::as_str));
+        self.run_cargo(&all).await
+    }
+
     /// Run cargo command
     async fn run_cargo(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = Command::new("cargo")
-            .args(args)
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+        RetryManager::new(self.retry.clone())
+            .retry(|| self.run_cargo_once(args, None))
+            .await
+    }
+
+    /// Run `cargo publish` with `CARGO_REGISTRY_TOKEN` set to `token`
+    async fn run_cargo_publish(
+        &self,
+        args: &[&str],
+        token: Option<&str>,
+    ) -> anyhow::Result<String> {
+        RetryManager::new(self.retry.clone())
+            .retry_publish(
+                || self.run_cargo_once(args, token),
+                |e| tr!("registry.published_by_earlier_attempt", error = e),
+            )
+            .await
+    }
+
+    /// Single attempt of a cargo command
+    async fn run_cargo_once(&self, args: &[&str], token: Option<&str>) -> anyhow::Result<String> {
+        let mut command = Command::new("cargo");
+        if let Some(sandbox) = &self.sandbox {
+            sandbox.apply(command.as_std_mut());
+        }
+        if let Some(token) = token {
+            command.env("CARGO_REGISTRY_TOKEN", token);
+        }
+        command.args(args).current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -151,27 +295,115 @@ impl CratesIoPlugin {
         Ok(stdout + &stderr)
     }
 
-    /// Fetch crate info from crates.io API
+    /// Fetch crate info from crates.io API, or the alternative registry's index
     async fn fetch_crate_info(&self, crate_name: &str) -> anyhow::Result<CratesIoCrateInfo> {
+        RetryManager::new(self.retry.clone())
+            .retry(|| self.fetch_crate_info_once(crate_name))
+            .await
+    }
+
+    /// Single attempt of [`Self::fetch_crate_info`]
+    async fn fetch_crate_info_once(&self, crate_name: &str) -> anyhow::Result<CratesIoCrateInfo> {
+        let registry = self.registry().await?;
+        if !registry.is_crates_io() {
+            let entries = registry.fetch_index(crate_name).await?;
+            let newest_version = entries
+                .iter()
+                .filter(|e| !e.yanked)
+                .filter_map(|e| semver::Version::parse(&e.vers).ok())
+                .max()
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            return Ok(CratesIoCrateInfo {
+                crate_info: CrateData { newest_version },
+                versions: entries
+                    .into_iter()
+                    .map(|e| VersionData {
+                        num: e.vers,
+                        checksum: Some(e.cksum),
+                        dl_path: None,
+                        yanked: e.yanked,
+                    })
+                    .collect(),
+            });
+        }
+
         let url = format!("https://crates.io/api/v1/crates/{}", crate_name);
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .header("User-Agent", "package-publisher/1.0.0")
-            .send()
-            .await?;
+        let client = http_client();
+        let response = client.get(&url).send_rate_limited().await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
-                "パッケージ {} が crates.io で見つかりません（HTTP {}）",
-                crate_name,
-                response.status()
+                "{}",
+                tr!(
+                    "crates_io.package_not_found",
+                    package = crate_name,
+                    status = response.status()
+                )
             );
         }
 
         let info = response.json::<CratesIoCrateInfo>().await?;
         Ok(info)
     }
+
+    /// Short-lived crates.io token from trusted publishing, per
+    /// `registries.crates.auth` (`None` publishes with the configured token;
+    /// the note explains a fallback to it)
+    async fn trusted_publishing_token(
+        &self,
+        registry: &CargoRegistry,
+    ) -> anyhow::Result<(Option<TrustedPublishingToken>, Option<String>)> {
+        let mode = self.config.auth.unwrap_or_default();
+        if mode == CratesAuthMode::Token || !registry.is_crates_io() {
+            return Ok((None, None));
+        }
+        let Some(oidc) = self.ci.oidc() else {
+            if mode == CratesAuthMode::Trusted {
+                anyhow::bail!("{}", tr!("crates_io.trusted_unavailable"));
+            }
+            return Ok((None, None));
+        };
+        match oidc.exchange().await {
+            Ok(token) => Ok((Some(token), None)),
+            // A configured token still works when the crate has no trusted publisher
+            Err(e)
+                if mode == CratesAuthMode::Auto && self.ci.var(&registry.token_env()).is_some() =>
+            {
+                Ok((None, Some(e.to_string())))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Crate name from Cargo.toml
+    async fn crate_name(&self) -> anyhow::Result<String> {
+        self.load_cargo_toml()
+            .await?
+            .package
+            .and_then(|p| p.name)
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("crates_io.name_not_found")))
+    }
+
+    /// Detect a backport release (version lower than the newest published version)
+    async fn detect_backport(
+        &self,
+        crate_name: &str,
+        version: &str,
+    ) -> Option<HashMap<String, serde_json::Value>> {
+        let info = self.fetch_crate_info(crate_name).await.ok()?;
+        let newest = info.crate_info.newest_version;
+        let channel = VersionValidator::new().backport_channel(version, &newest)?;
+
+        Some(HashMap::from([
+            ("backport".to_string(), serde_json::Value::Bool(true)),
+            (
+                "latestVersion".to_string(),
+                serde_json::Value::String(newest),
+            ),
+            ("channel".to_string(), serde_json::Value::String(channel)),
+        ]))
+    }
 }
 
 #[async_trait]
@@ -206,7 +438,7 @@ impl RegistryPlugin for CratesIoPlugin {
         if package.name.is_none() {
             errors.push(ValidationError {
                 field: "package.name".to_string(),
-                message: "nameは必須フィールドです".to_string(),
+                message: tr!("registry.name_required"),
                 severity: "error".to_string(),
             });
         }
@@ -214,7 +446,7 @@ impl RegistryPlugin for CratesIoPlugin {
         if package.version.is_none() {
             errors.push(ValidationError {
                 field: "package.version".to_string(),
-                message: "versionは必須フィールドです".to_string(),
+                message: tr!("registry.version_required"),
                 severity: "error".to_string(),
             });
         }
@@ -234,7 +466,7 @@ impl RegistryPlugin for CratesIoPlugin {
             if !self.is_valid_semver(version) {
                 errors.push(ValidationError {
                     field: "package.version".to_string(),
-                    message: format!("無効なSemVer形式: {}", version),
+                    message: tr!("registry.invalid_semver", version = version),
                     severity: "error".to_string(),
                 });
             }
@@ -248,7 +480,7 @@ impl RegistryPlugin for CratesIoPlugin {
         if package.license.is_none() {
             warnings.push(ValidationWarning {
                 field: "package.license".to_string(),
-                message: "ライセンスフィールドの指定を推奨します".to_string(),
+                message: tr!("registry.license_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -257,7 +489,7 @@ impl RegistryPlugin for CratesIoPlugin {
         if package.description.is_none() {
             warnings.push(ValidationWarning {
                 field: "package.description".to_string(),
-                message: "descriptionフィールドの指定を推奨します".to_string(),
+                message: tr!("registry.description_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -268,18 +500,61 @@ impl RegistryPlugin for CratesIoPlugin {
             Err(e) => {
                 errors.push(ValidationError {
                     field: "cargo.check".to_string(),
-                    message: format!("cargo checkに失敗: {}", e),
+                    message: tr!("crates_io.check_failed", error = e),
                     severity: "error".to_string(),
                 });
             }
         }
 
+        // Check feature combinations (if configured)
+        if let Some(matrix) = self
+            .config
+            .feature_matrix
+            .as_ref()
+            .filter(|m| m.enabled.unwrap_or(false))
+        {
+            let result = match FeatureMatrixChecker::new(&self.project_path).with_config(matrix) {
+                Ok(checker) => checker.check().await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(matrix_errors) => errors.extend(matrix_errors),
+                Err(e) => errors.push(ValidationError {
+                    field: "cargo.features".to_string(),
+                    message: tr!("crates_io.feature_matrix_failed", error = e),
+                    severity: "error".to_string(),
+                }),
+            }
+        }
+
+        // Check for breaking API changes (if configured)
+        if let Some(semver) = self
+            .config
+            .semver_checks
+            .as_ref()
+            .filter(|s| s.enabled.unwrap_or(false))
+        {
+            match SemverChecker::new(&self.project_path)
+                .with_config(semver)
+                .check()
+                .await
+            {
+                Ok(result) => {
+                    errors.extend(result.errors);
+                    warnings.extend(result.warnings);
+                }
+                Err(e) => warnings.push(ValidationWarning {
+                    field: "cargo.semver".to_string(),
+                    message: tr!("crates_io.semver_check_failed", error = e),
+                    severity: "warning".to_string(),
+                }),
+            }
+        }
+
         // Note: cargo test can be time-consuming
         warnings.push(ValidationWarning {
             field: "cargo.test".to_string(),
-            message:
-                "テストは時間がかかるためスキップしました。手動で `cargo test` を実行してください。"
-                    .to_string(),
+            message: tr!("crates_io.tests_skipped"),
             severity: "warning".to_string(),
         });
 
@@ -289,7 +564,7 @@ impl RegistryPlugin for CratesIoPlugin {
             Err(e) => {
                 warnings.push(ValidationWarning {
                     field: "cargo.clippy".to_string(),
-                    message: format!("Clippy警告が検出されました: {}", e),
+                    message: tr!("crates_io.clippy_warnings", output = e),
                     severity: "warning".to_string(),
                 });
             }
@@ -309,7 +584,7 @@ impl RegistryPlugin for CratesIoPlugin {
 
     async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
         match self
-            .run_cargo(&["publish", "--dry-run", "--allow-dirty"])
+            .run_cargo_registry(&["publish", "--dry-run", "--allow-dirty"])
             .await
         {
             Ok(output) => Ok(DryRunResult {
@@ -324,7 +599,7 @@ impl RegistryPlugin for CratesIoPlugin {
                 estimated_size: None,
                 errors: Some(vec![ValidationError {
                     field: "publish".to_string(),
-                    message: format!("Dry-runに失敗: {}", e),
+                    message: tr!("registry.dry_run_failed", output = e),
                     severity: "error".to_string(),
                 }]),
             }),
@@ -350,10 +625,59 @@ impl RegistryPlugin for CratesIoPlugin {
             args.push(tag);
         }
 
-        match self.run_cargo(&args).await {
+        // crates.io keeps the highest version as the default (and docs.rs follows it),
+        // so backports only need to be reported
+        let mut metadata = match (package.name.as_deref(), package.version.as_deref()) {
+            (Some(name), Some(version)) => self.detect_backport(name, version).await,
+            _ => None,
+        };
+
+        let registry = self.registry().await?;
+        let registry_args = registry.args();
+        args.extend(registry_args.iter().map(String::as_str));
+
+        let (trusted_token, fallback_note) = match self.trusted_publishing_token(&registry).await {
+            Ok(token) => token,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(e.to_string()),
+                    metadata: None,
+                    error_kind: Some(PublishErrorKind::AuthFailed),
+                });
+            }
+        };
+        let result = self
+            .run_cargo_publish(&args, trusted_token.as_ref().map(|t| t.expose()))
+            .await;
+        if let Some(ref token) = trusted_token {
+            // The token expires on its own; revoking only shortens its lifetime
+            let _ = token.revoke().await;
+            metadata.get_or_insert_with(HashMap::new).insert(
+                "trustedPublishing".to_string(),
+                serde_json::Value::Bool(true),
+            );
+        }
+
+        match result {
             Ok(output) => {
                 let package_name = package.name.unwrap_or_else(|| "unknown".to_string());
-                let package_url = format!("https://crates.io/crates/{}", package_name);
+                let package_url = registry.package_url(&package_name).await;
+                let output = match fallback_note {
+                    Some(note) => format!(
+                        "{}\n{}",
+                        tr!(
+                            "crates_io.trusted_fallback",
+                            reason = note,
+                            token_env = registry.token_env()
+                        ),
+                        output
+                    ),
+                    None => output,
+                };
 
                 Ok(PublishResult {
                     success: true,
@@ -361,7 +685,8 @@ impl RegistryPlugin for CratesIoPlugin {
                     package_url: Some(package_url),
                     output: Some(output),
                     error: None,
-                    metadata: None,
+                    metadata,
+                    error_kind: None,
                 })
             }
             Err(e) => {
@@ -373,11 +698,13 @@ impl RegistryPlugin for CratesIoPlugin {
                         version: None,
                         package_url: None,
                         output: None,
-                        error: Some(
-                            "crates.ioの認証に失敗しました。CARGO_REGISTRY_TOKENを確認してください"
-                                .to_string(),
-                        ),
+                        error: Some(tr!(
+                            "crates_io.auth_failed",
+                            registry = registry.display_name(),
+                            token_env = registry.token_env()
+                        )),
                         metadata: None,
+                        error_kind: Some(PublishErrorKind::AuthFailed),
                     });
                 }
 
@@ -388,6 +715,7 @@ impl RegistryPlugin for CratesIoPlugin {
                     output: None,
                     error: Some(error_msg),
                     metadata: None,
+                    error_kind: None,
                 })
             }
         }
@@ -402,30 +730,41 @@ impl RegistryPlugin for CratesIoPlugin {
 
         let crate_name = package
             .name
-            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("crates_io.name_not_found")))?;
         let expected_version = package
             .version
             .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
 
+        let registry = self.registry().await?;
+        let package_url = registry.package_url(&crate_name).await;
+
         match self.fetch_crate_info(&crate_name).await {
             Ok(info) => {
                 // Check if expected version exists
-                let version_exists = info.versions.iter().any(|v| v.num == expected_version);
+                let published_version = info.versions.iter().find(|v| v.num == expected_version);
 
-                if !version_exists {
+                let Some(published_version) = published_version else {
                     let available: Vec<String> =
                         info.versions.iter().map(|v| v.num.clone()).collect();
                     return Ok(VerificationResult {
                         verified: false,
                         version: Some(expected_version.clone()),
-                        url: Some(format!("https://crates.io/crates/{}", crate_name)),
-                        error: Some(format!(
-                            "バージョン {} が crates.io で見つかりません。利用可能なバージョン: {}",
-                            expected_version,
-                            available.join(", ")
+                        url: Some(package_url.clone()),
+                        error: Some(tr!(
+                            "registry.version_not_found_available",
+                            version = expected_version,
+                            registry = registry.display_name(),
+                            available = available.join(", ")
                         )),
                         metadata: None,
+                        integrity: None,
                     });
+                };
+
+                let mut integrity = published_version.integrity();
+                if !registry.is_crates_io() {
+                    integrity.download_url =
+                        registry.download_url(&crate_name, &expected_version).await;
                 }
 
                 let newest_version = info.crate_info.newest_version.clone();
@@ -450,17 +789,92 @@ impl RegistryPlugin for CratesIoPlugin {
                 Ok(VerificationResult {
                     verified: true,
                     version: Some(expected_version),
-                    url: Some(format!("https://crates.io/crates/{}", crate_name)),
+                    url: Some(package_url.clone()),
                     error: None,
                     metadata: Some(metadata),
+                    integrity: Some(integrity),
                 })
             }
             Err(e) => Ok(VerificationResult {
                 verified: false,
                 version: Some(expected_version),
-                url: Some(format!("https://crates.io/crates/{}", crate_name)),
-                error: Some(format!("検証に失敗: {}", e)),
+                url: Some(package_url.clone()),
+                error: Some(tr!("registry.verify_failed", error = e)),
                 metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+
+    /// Yank the version (crates.io does not allow deleting releases)
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        let crate_name = self.crate_name().await?;
+
+        match self
+            .run_cargo_registry(&["yank", "--version", version, &crate_name])
+            .await
+        {
+            Ok(_) => {
+                let registry = self
+                    .registry()
+                    .await
+                    .map(|r| r.display_name().to_string())
+                    .unwrap_or_else(|_| "crates.io".to_string());
+                Ok(RollbackResult {
+                    success: true,
+                    message: tr!(
+                        "crates_io.yanked_deprecated",
+                        package = crate_name,
+                        version = version,
+                        registry = registry
+                    ),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(RollbackResult {
+                success: false,
+                message: tr!("registry.rollback_failed"),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    async fn superseded_prereleases(&self, version: &str) -> anyhow::Result<Vec<String>> {
+        let crate_name = self.crate_name().await?;
+        let info = self.fetch_crate_info(&crate_name).await?;
+
+        let validator = VersionValidator::new();
+        let mut versions: Vec<String> = info
+            .versions
+            .iter()
+            .filter(|v| !v.yanked && validator.is_superseded_prerelease(&v.num, version))
+            .map(|v| v.num.clone())
+            .collect();
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// crates.io has no deprecation, so superseded versions are yanked
+    async fn deprecate(&self, version: &str, _message: &str) -> anyhow::Result<RollbackResult> {
+        let crate_name = self.crate_name().await?;
+
+        match self
+            .run_cargo_registry(&["yank", "--version", version, &crate_name])
+            .await
+        {
+            Ok(_) => Ok(RollbackResult {
+                success: true,
+                message: tr!("crates_io.yanked", package = crate_name, version = version),
+                error: None,
+            }),
+            Err(e) => Ok(RollbackResult {
+                success: false,
+                message: tr!(
+                    "crates_io.yank_failed",
+                    package = crate_name,
+                    version = version
+                ),
+                error: Some(e.to_string()),
             }),
         }
     }
@@ -471,6 +885,71 @@ mod tests {
     use super::*;
     use std::io::Write;
     use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned `(status, body)` response per connection
+    async fn serve(listener: TcpListener, responses: Vec<(&'static str, &'static str)>) {
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    /// Plugin in `mode` inside a CI job whose OIDC and token endpoints are
+    /// served at `base`, optionally with `CARGO_REGISTRY_TOKEN` set
+    fn ci_plugin(mode: CratesAuthMode, base: Option<String>, token: bool) -> CratesIoPlugin {
+        let tokens_url = format!("{}/tokens", base.as_deref().unwrap_or("http://127.0.0.1:1"));
+        let ci = CiEnvironment::from_fn(move |name| match name {
+            "ACTIONS_ID_TOKEN_REQUEST_URL" => base.as_ref().map(|b| format!("{}/id", b)),
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN" => base.as_ref().map(|_| "request".to_string()),
+            "CARGO_REGISTRY_TOKEN" => token.then(|| "stored-token".to_string()),
+            _ => None,
+        })
+        .with_tokens_url(tokens_url);
+        CratesIoPlugin::new(PathBuf::from("."))
+            .with_config(CratesRegistryConfig {
+                auth: Some(mode),
+                ..Default::default()
+            })
+            .with_ci_environment(ci)
+    }
+
+    async fn mock_ci(responses: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener, responses));
+        base
+    }
 
     #[test]
     fn test_new_plugin() {
@@ -479,6 +958,130 @@ mod tests {
         assert_eq!(plugin.version(), "1.0.0");
     }
 
+    #[test]
+    fn test_sandbox_keeps_ci_identity() {
+        let plugin =
+            CratesIoPlugin::new(PathBuf::from(".")).with_sandbox(EnvSandbox::new::<&str>(&[]));
+        let sandbox = plugin.sandbox.unwrap();
+        for name in [
+            "ACTIONS_ID_TOKEN_REQUEST_URL",
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+            "GITHUB_REPOSITORY",
+            "CARGO_REGISTRY_TOKEN",
+        ] {
+            assert!(sandbox.allows(name), "{}", name);
+        }
+        assert!(!sandbox.allows("AWS_SECRET_ACCESS_KEY"));
+    }
+
+    #[tokio::test]
+    async fn test_trusted_publishing_outside_ci() {
+        let registry = CargoRegistry::crates_io();
+
+        // Auto and Token publish with the configured token
+        for mode in [CratesAuthMode::Auto, CratesAuthMode::Token] {
+            let (token, note) = ci_plugin(mode, None, true)
+                .trusted_publishing_token(&registry)
+                .await
+                .unwrap();
+            assert!(token.is_none() && note.is_none());
+        }
+
+        let err = ci_plugin(CratesAuthMode::Trusted, None, true)
+            .trusted_publishing_token(&registry)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), tr!("crates_io.trusted_unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_token_mode_ignores_oidc() {
+        // Nothing listens on the endpoints: any request would fail
+        let plugin = ci_plugin(
+            CratesAuthMode::Token,
+            Some("http://127.0.0.1:1".to_string()),
+            false,
+        );
+        let (token, note) = plugin
+            .trusted_publishing_token(&CargoRegistry::crates_io())
+            .await
+            .unwrap();
+        assert!(token.is_none() && note.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_trusted_publishing_exchange() {
+        for mode in [CratesAuthMode::Auto, CratesAuthMode::Trusted] {
+            let base = mock_ci(vec![
+                ("200 OK", r#"{"value":"oidc-jwt"}"#),
+                ("200 OK", r#"{"token":"short-lived"}"#),
+            ])
+            .await;
+            let (token, note) = ci_plugin(mode, Some(base), true)
+                .trusted_publishing_token(&CargoRegistry::crates_io())
+                .await
+                .unwrap();
+            assert_eq!(token.unwrap().expose(), "short-lived");
+            assert!(note.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trusted_publishing_falls_back_to_token() {
+        let responses = || {
+            vec![
+                ("200 OK", r#"{"value":"oidc-jwt"}"#),
+                ("400 Bad Request", "no trusted publisher"),
+            ]
+        };
+
+        let base = mock_ci(responses()).await;
+        let (token, note) = ci_plugin(CratesAuthMode::Auto, Some(base), true)
+            .trusted_publishing_token(&CargoRegistry::crates_io())
+            .await
+            .unwrap();
+        assert!(token.is_none());
+        assert!(note.unwrap().contains("no trusted publisher"));
+
+        // Without a token, or when trusted publishing is required, the
+        // exchange error is returned
+        let base = mock_ci(responses()).await;
+        assert!(
+            ci_plugin(CratesAuthMode::Auto, Some(base), false)
+                .trusted_publishing_token(&CargoRegistry::crates_io())
+                .await
+                .is_err()
+        );
+        let base = mock_ci(responses()).await;
+        assert!(
+            ci_plugin(CratesAuthMode::Trusted, Some(base), true)
+                .trusted_publishing_token(&CargoRegistry::crates_io())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_version_data_integrity() {
+        let version: VersionData = serde_json::from_value(serde_json::json!({
+            "num": "0.1.0",
+            "checksum": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "dl_path": "/api/v1/crates/test-crate/0.1.0/download"
+        }))
+        .unwrap();
+
+        let integrity = version.integrity();
+        assert_eq!(
+            integrity.download_url.as_deref(),
+            Some("https://crates.io/api/v1/crates/test-crate/0.1.0/download")
+        );
+        assert_eq!(
+            integrity.checksum.as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        assert!(integrity.has_hash());
+    }
+
     #[tokio::test]
     async fn test_detect_with_cargo_toml() {
         let temp_dir = TempDir::new().unwrap();
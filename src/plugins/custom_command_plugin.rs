@@ -0,0 +1,489 @@
+//! Custom Command Plugin - registries driven by `registries.custom`
+//!
+//! Publishes to registries without a dedicated plugin by running the
+//! configured command templates:
+//! - `publishCommand` publishes the package, `verifyCommand` (optional)
+//!   confirms it is available
+//! - Commands are split into program + arguments before substitution and run
+//!   through [`SafeCommandExecutor`] (no shell involved)
+//! - `{name}`, `{version}`, `{registry}` and the scalar values of `config`
+//!   (e.g. `{repository}`) are substituted into each argument
+//! - The program must be whitelisted or listed in `allowedCommands`
+//! - With [`CustomCommandPlugin::with_sandbox`], commands only see an
+//!   allowlist of environment variables
+//!
+//! ```yaml
+//! registries:
+//!   custom:
+//!     internal:
+//!       type: command
+//!       config:
+//!         repository: releases
+//!         url: https://packages.example.com/{name}/{version}
+//!       publishCommand: make publish REPO={repository} VERSION={version}
+//!       verifyCommand: make verify VERSION={version}
+//!       allowedCommands: [make]
+//! ```
+
+use crate::core::config::CustomRegistryConfig;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::orchestration::hook_runner::split_command;
+use crate::plugins::bucket_plugin::manifest_identity;
+use crate::security::command_executor::{EnvSandbox, SafeCommandExecutor};
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Output;
+use std::time::Duration;
+
+/// Default command timeout in seconds
+const DEFAULT_COMMAND_TIMEOUT_SECS: u32 = 600;
+
+/// Substitute `{name}`, `{version}`, `{registry}` and scalar `config` values
+///
+/// Substitution is a single pass, so values are never expanded again;
+/// unknown placeholders are left as-is.
+pub fn render_template(template: &str, variables: &HashMap<String, String>) -> String {
+    let placeholder = Regex::new(r"\{([A-Za-z0-9_.-]+)\}").expect("valid placeholder regex");
+    placeholder
+        .replace_all(template, |caps: &regex::Captures| {
+            variables
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Custom command registry plugin
+pub struct CustomCommandPlugin {
+    project_path: PathBuf,
+    registry_name: String,
+    config: CustomRegistryConfig,
+    sandbox: Option<EnvSandbox>,
+}
+
+impl CustomCommandPlugin {
+    /// Create a plugin for the `registries.custom.<registry_name>` entry
+    pub fn new(
+        project_path: impl Into<PathBuf>,
+        registry_name: impl Into<String>,
+        config: CustomRegistryConfig,
+    ) -> Self {
+        Self {
+            project_path: project_path.into(),
+            registry_name: registry_name.into(),
+            config,
+            sandbox: None,
+        }
+    }
+
+    /// Run the commands with the minimal environment of `sandbox`
+    pub fn with_sandbox(mut self, sandbox: EnvSandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Package name and version (`config.name` / `config.version` override the manifest)
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        let configured = |key: &str| {
+            self.config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let name = configured("name")
+            .or(name)
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("custom_command.name_unknown")))?;
+        let version = configured("version")
+            .or(version)
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("custom_command.version_unknown")))?;
+        Ok((name, version))
+    }
+
+    /// Template variables for the package
+    fn variables(&self, name: &str, version: &str) -> HashMap<String, String> {
+        let mut variables: HashMap<String, String> = self
+            .config
+            .config
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::Number(n) => n.to_string(),
+                    serde_json::Value::Bool(b) => b.to_string(),
+                    _ => return None,
+                };
+                Some((key.clone(), value))
+            })
+            .collect();
+        variables.insert("name".to_string(), name.to_string());
+        variables.insert("version".to_string(), version.to_string());
+        variables.insert("registry".to_string(), self.registry_name.clone());
+        variables
+    }
+
+    /// Split a command template and substitute variables into each argument
+    fn materialize(
+        &self,
+        template: &str,
+        variables: &HashMap<String, String>,
+    ) -> anyhow::Result<(String, Vec<String>)> {
+        let mut parts = split_command(template).map_err(|e| anyhow::anyhow!(e))?;
+        if parts.is_empty() {
+            anyhow::bail!("{}", tr!("custom_command.empty"));
+        }
+        let program = parts.remove(0);
+        let args = parts
+            .iter()
+            .map(|arg| render_template(arg, variables))
+            .collect();
+        Ok((program, args))
+    }
+
+    /// Executor allowing the configured commands
+    fn executor(&self) -> anyhow::Result<SafeCommandExecutor> {
+        let mut executor = SafeCommandExecutor::new(&self.project_path)?;
+        executor.allow_commands(self.config.allowed_commands.as_deref().unwrap_or_default());
+        if let Some(sandbox) = &self.sandbox {
+            executor.set_env_sandbox(sandbox.clone());
+        }
+        executor.set_timeout(Duration::from_secs(u64::from(
+            self.config.timeout.unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS),
+        )));
+        Ok(executor)
+    }
+
+    /// Run a materialized command
+    async fn run(&self, program: String, args: Vec<String>) -> anyhow::Result<Output> {
+        let executor = self.executor()?;
+        let output = tokio::task::spawn_blocking(move || {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            executor.execute(&program, &args)
+        })
+        .await??;
+        Ok(output)
+    }
+
+    /// Package URL from `config.url`
+    fn package_url(&self, variables: &HashMap<String, String>) -> Option<String> {
+        variables
+            .get("url")
+            .map(|url| render_template(url, variables))
+    }
+}
+
+/// Combined stdout and stderr of a command
+fn command_output(output: &Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    [stdout.trim(), stderr.trim()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Failure message for a non-zero exit
+fn exit_error(output: &Output) -> String {
+    let code = output
+        .status
+        .code()
+        .map_or_else(|| "signal".to_string(), |c| c.to_string());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.trim() {
+        "" => tr!("custom_command.failed", code = code),
+        detail => tr!(
+            "custom_command.failed_with_output",
+            code = code,
+            output = detail
+        ),
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for CustomCommandPlugin {
+    fn name(&self) -> &str {
+        &self.registry_name
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.custom`, not a manifest
+        Ok(self.config.enabled != Some(false) && self.config.publish_command.is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+        let executor = self.executor()?;
+
+        let commands = [
+            ("publishCommand", self.config.publish_command.as_deref()),
+            ("verifyCommand", self.config.verify_command.as_deref()),
+        ];
+        for (field, template) in commands {
+            let Some(template) = template else {
+                continue;
+            };
+            match split_command(template) {
+                Ok(parts) if parts.is_empty() => errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("custom_command.empty"),
+                    severity: "error".to_string(),
+                }),
+                Ok(parts) if !executor.is_allowed(&parts[0]) => errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("custom_command.not_allowed", program = parts[0]),
+                    severity: "error".to_string(),
+                }),
+                Ok(_) => {}
+                Err(e) => errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: e,
+                    severity: "error".to_string(),
+                }),
+            }
+        }
+        if self.config.publish_command.is_none() {
+            errors.push(ValidationError {
+                field: "publishCommand".to_string(),
+                message: tr!(
+                    "custom_command.publish_command_required",
+                    name = self.registry_name
+                ),
+                severity: "error".to_string(),
+            });
+        }
+        if self.config.verify_command.is_none() {
+            warnings.push(ValidationWarning {
+                field: "verifyCommand".to_string(),
+                message: tr!("custom_command.no_verify_command"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let (name, version) = self.package_identity().await?;
+        let variables = self.variables(&name, &version);
+
+        let mut lines = Vec::new();
+        for template in [&self.config.publish_command, &self.config.verify_command]
+            .into_iter()
+            .flatten()
+        {
+            let (program, args) = self.materialize(template, &variables)?;
+            lines.push(format!("{} {}", program, args.join(" ")));
+        }
+
+        Ok(DryRunResult {
+            success: !lines.is_empty(),
+            output: lines.join("\n"),
+            estimated_size: None,
+            errors: None,
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let (name, version) = self.package_identity().await?;
+        let variables = self.variables(&name, &version);
+        let template = self.config.publish_command.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{}",
+                tr!(
+                    "custom_command.publish_command_missing",
+                    name = self.registry_name
+                )
+            )
+        })?;
+        let (program, args) = self.materialize(template, &variables)?;
+
+        Ok(match self.run(program, args).await {
+            Ok(output) if output.status.success() => PublishResult {
+                success: true,
+                version: Some(version),
+                package_url: self.package_url(&variables),
+                output: Some(command_output(&output)),
+                error: None,
+                metadata: None,
+                error_kind: None,
+            },
+            Ok(output) => PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: Some(command_output(&output)),
+                error: Some(exit_error(&output)),
+                metadata: None,
+                error_kind: None,
+            },
+            Err(e) => PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            },
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let (name, version) = self.package_identity().await?;
+        let variables = self.variables(&name, &version);
+        let url = self.package_url(&variables);
+
+        let Some(template) = self.config.verify_command.as_deref() else {
+            return Ok(VerificationResult {
+                verified: false,
+                version: Some(version),
+                url,
+                error: Some(tr!("custom_command.verify_command_missing")),
+                metadata: None,
+                integrity: None,
+            });
+        };
+        let (program, args) = self.materialize(template, &variables)?;
+
+        let (verified, error, output) = match self.run(program, args).await {
+            Ok(output) if output.status.success() => (true, None, Some(command_output(&output))),
+            Ok(output) => (
+                false,
+                Some(exit_error(&output)),
+                Some(command_output(&output)),
+            ),
+            Err(e) => (false, Some(e.to_string()), None),
+        };
+
+        Ok(VerificationResult {
+            verified,
+            version: Some(version),
+            url,
+            error,
+            metadata: output.map(|output| {
+                HashMap::from([("output".to_string(), serde_json::Value::String(output))])
+            }),
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(publish: &str, verify: Option<&str>) -> CustomRegistryConfig {
+        CustomRegistryConfig {
+            enabled: None,
+            plugin_type: "command".to_string(),
+            config: HashMap::from([
+                ("name".to_string(), serde_json::json!("my-pkg")),
+                ("version".to_string(), serde_json::json!("1.2.3")),
+                (
+                    "url".to_string(),
+                    serde_json::json!("https://example.com/{name}/{version}"),
+                ),
+            ]),
+            publish_command: Some(publish.to_string()),
+            verify_command: verify.map(str::to_string),
+            allowed_commands: None,
+            timeout: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_materialize_substitutes_per_argument() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin = CustomCommandPlugin::new(
+            temp_dir.path(),
+            "internal",
+            config(
+                "git tag -m \"{name} {version}\" v{version} {registry}",
+                None,
+            ),
+        );
+        let (name, version) = plugin.package_identity().await.unwrap();
+        let mut variables = plugin.variables(&name, &version);
+        variables.insert("name".to_string(), "a b; rm -rf /".to_string());
+
+        let (program, args) = plugin
+            .materialize(
+                plugin.config.publish_command.as_deref().unwrap(),
+                &variables,
+            )
+            .unwrap();
+        assert_eq!(program, "git");
+        assert_eq!(
+            args,
+            vec!["tag", "-m", "a b; rm -rf / 1.2.3", "v1.2.3", "internal"]
+        );
+        assert_eq!(
+            plugin.package_url(&variables).as_deref(),
+            Some("https://example.com/a b; rm -rf //1.2.3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_verify_map_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin = CustomCommandPlugin::new(
+            temp_dir.path(),
+            "internal",
+            config(
+                "cargo --version",
+                Some("cargo no-such-subcommand-{version}"),
+            ),
+        );
+
+        let published = plugin.publish(None).await.unwrap();
+        assert!(published.success, "{:?}", published.error);
+        assert_eq!(published.version.as_deref(), Some("1.2.3"));
+        assert_eq!(
+            published.package_url.as_deref(),
+            Some("https://example.com/my-pkg/1.2.3")
+        );
+        assert!(published.output.unwrap().starts_with("cargo "));
+
+        let verified = plugin.verify().await.unwrap();
+        assert!(!verified.verified);
+        assert!(verified.error.unwrap().contains("exit code"));
+
+        let denied = CustomCommandPlugin::new(temp_dir.path(), "internal", config("make", None));
+        let validation = denied.validate().await.unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.errors[0].field, "publishCommand");
+    }
+}
@@ -0,0 +1,948 @@
+//! Debian Plugin - Launchpad PPA and apt repository publishing
+//!
+//! Provides Debian/Ubuntu package publishing:
+//! - Package build with `cargo deb` or `dpkg-buildpackage` (or prebuilt
+//!   `.deb` artifacts)
+//! - Launchpad PPA: signed source upload (`dpkg-buildpackage -S`, `debsign`,
+//!   `dput`); Launchpad builds the binaries
+//! - aptly: upload through the REST API, add to the local repository and
+//!   update the publish (signed by aptly with `signKey`)
+//! - Artifactory: upload into a Debian repository with `deb.*` properties
+//! - Verification that the repository's package index lists the version
+
+use crate::core::config::DebianRegistryConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::resolve_assets;
+use crate::plugins::bucket_plugin::manifest_identity;
+use crate::plugins::repository_manager_plugin::artifactory_properties;
+use crate::security::command_executor::CommandLimits;
+use crate::security::token_manager::SecureTokenManager;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::multipart::{Form, Part};
+use secrecy::ExposeSecret;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::fs;
+
+const LAUNCHPAD_PPA_URL: &str = "https://ppa.launchpadcontent.net";
+
+/// Where the Debian package is published
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebianTarget {
+    Ppa,
+    Aptly,
+    Artifactory,
+}
+
+impl fmt::Display for DebianTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DebianTarget::Ppa => write!(f, "ppa"),
+            DebianTarget::Aptly => write!(f, "aptly"),
+            DebianTarget::Artifactory => write!(f, "artifactory"),
+        }
+    }
+}
+
+impl FromStr for DebianTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ppa" | "launchpad" => Ok(DebianTarget::Ppa),
+            "aptly" => Ok(DebianTarget::Aptly),
+            "artifactory" => Ok(DebianTarget::Artifactory),
+            other => Err(format!(
+                "Unknown Debian target: {} (expected ppa, aptly or artifactory)",
+                other
+            )),
+        }
+    }
+}
+
+/// First debian/changelog entry (source name, version, distribution)
+pub fn parse_changelog_head(content: &str) -> Option<(String, String, String)> {
+    let captures = Regex::new(r"(?m)^(\S+) \(([^)]+)\) ([^;]+);")
+        .unwrap()
+        .captures(content)?;
+    Some((
+        captures[1].to_string(),
+        captures[2].to_string(),
+        captures[3]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    ))
+}
+
+/// Whether a Debian version is `version`, ignoring the epoch and the
+/// Debian revision (`1:1.2.0-1ubuntu1` matches `1.2.0`)
+fn matches_version(candidate: &str, version: &str) -> bool {
+    let candidate = candidate
+        .split_once(':')
+        .map(|(_, v)| v)
+        .unwrap_or(candidate);
+    let version = version.split_once(':').map(|(_, v)| v).unwrap_or(version);
+    candidate == version || candidate.starts_with(&format!("{}-", version))
+}
+
+/// Whether an apt `Packages` index lists `name` at `version`
+pub fn packages_index_has_version(body: &str, name: &str, version: &str) -> bool {
+    body.split("\n\n").any(|stanza| {
+        let field = |key: &str| {
+            stanza
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{}: ", key)))
+                .map(str::trim)
+        };
+        field("Package") == Some(name)
+            && field("Version").is_some_and(|v| matches_version(v, version))
+    })
+}
+
+/// aptly publish prefix in API paths (`.` -> `:.`, `/` -> `_`, `_` -> `__`)
+pub fn aptly_prefix(prefix: &str) -> String {
+    if prefix == "." {
+        ":.".to_string()
+    } else {
+        prefix.replace('_', "__").replace('/', "_")
+    }
+}
+
+/// Architecture of a `.deb` from its file name (`name_version_arch.deb`)
+fn deb_architecture(file: &Path) -> Option<String> {
+    file.file_stem()?
+        .to_str()?
+        .rsplit('_')
+        .next()
+        .map(str::to_string)
+}
+
+/// Environment variables the Debian packaging tools need on top of the sandbox
+/// allowlist
+const TOOL_ENV: &[&str] = &["DEB*", "DPKG_*", "GNUPGHOME", "GPG_*"];
+
+/// Debian plugin
+pub struct DebianPlugin {
+    project_path: PathBuf,
+    config: DebianRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for DebianPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl DebianPlugin {
+    /// Create a new Debian plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: DebianRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.debian` settings
+    pub fn with_config(mut self, config: DebianRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run the Debian packaging tools with the sandbox and timeout of registry
+    /// commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    pub fn target(&self) -> anyhow::Result<DebianTarget> {
+        match (&self.config.target, &self.config.ppa) {
+            (Some(target), _) => target.parse().map_err(anyhow::Error::msg),
+            (None, Some(_)) => Ok(DebianTarget::Ppa),
+            (None, None) => anyhow::bail!("{}", tr!("debian.target_required")),
+        }
+    }
+
+    async fn has_debian_dir(&self) -> bool {
+        fs::try_exists(self.project_path.join("debian/control"))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn uses_cargo_deb(&self) -> bool {
+        match self.config.builder.as_deref() {
+            Some(builder) => builder == "cargo-deb",
+            None => !self.has_debian_dir().await,
+        }
+    }
+
+    async fn changelog_head(&self) -> Option<(String, String, String)> {
+        let content = fs::read_to_string(self.project_path.join("debian/changelog"))
+            .await
+            .ok()?;
+        parse_changelog_head(&content)
+    }
+
+    /// Package name and version (debian/changelog, else the project manifest)
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        if let Some((name, version, _)) = self.changelog_head().await {
+            return Ok((name, version));
+        }
+        match manifest_identity(&self.project_path).await {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!("Package name not found"),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    fn distribution(&self) -> Option<&str> {
+        self.config.distribution.as_deref()
+    }
+
+    fn component(&self) -> &str {
+        self.config.component.as_deref().unwrap_or("main")
+    }
+
+    fn architecture(&self) -> &str {
+        self.config.architecture.as_deref().unwrap_or("amd64")
+    }
+
+    fn base_url(&self) -> anyhow::Result<&str> {
+        self.config
+            .url
+            .as_deref()
+            .map(|url| url.trim_end_matches('/'))
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.url_required")))
+    }
+
+    fn repository(&self) -> anyhow::Result<&str> {
+        self.config
+            .repository
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.repository_required")))
+    }
+
+    fn token_env(&self) -> Option<String> {
+        self.config.token_env.clone().or_else(|| {
+            (self.target().ok() == Some(DebianTarget::Artifactory)).then(|| {
+                SecureTokenManager::new()
+                    .get_token_name("artifactory")
+                    .unwrap_or("ARTIFACTORY_TOKEN")
+                    .to_string()
+            })
+        })
+    }
+
+    fn token(&self) -> Option<String> {
+        match &self.config.token_env {
+            Some(env) => std::env::var(env).ok().filter(|t| !t.is_empty()),
+            None if self.target().ok() == Some(DebianTarget::Artifactory) => {
+                SecureTokenManager::new()
+                    .get_token("artifactory")
+                    .map(|t| t.expose_secret().to_string())
+            }
+            None => None,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let request = http_client().request(method, url);
+        match self.token() {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn run(&self, program: &str, args: &[&str], dir: &Path) -> anyhow::Result<String> {
+        let mut command = self.limits.command(program);
+        command.args(args).current_dir(dir);
+        let output = self.limits.output(&mut command).await.map_err(|e| {
+            anyhow::anyhow!(
+                "{}",
+                tr!("debian.spawn_failed", program = program, error = e)
+            )
+        })?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "debian.command_failed",
+                    program = program,
+                    command = args.first().unwrap_or(&""),
+                    output = String::from_utf8_lossy(&output.stderr).trim()
+                )
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Files written by dpkg-buildpackage next to the source tree
+    async fn parent_files(&self, prefix: &str, suffix: &str) -> Vec<PathBuf> {
+        let parent = self
+            .project_path
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| self.project_path.join(".."));
+        let mut files = Vec::new();
+        if let Ok(mut entries) = fs::read_dir(&parent).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with(prefix) && name.ends_with(suffix) {
+                    files.push(entry.path());
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    /// Build (or collect) the binary packages
+    async fn build_debs(&self, name: &str, version: &str) -> anyhow::Result<Vec<PathBuf>> {
+        if let Some(patterns) = &self.config.artifacts {
+            let files = resolve_assets(&self.project_path, patterns).await;
+            if files.is_empty() {
+                anyhow::bail!("No .deb files match registries.debian.artifacts");
+            }
+            return Ok(files);
+        }
+
+        if self.uses_cargo_deb().await {
+            let stdout = self.run("cargo", &["deb"], &self.project_path).await?;
+            let file = stdout
+                .lines()
+                .map(str::trim)
+                .rfind(|line| line.ends_with(".deb"))
+                .ok_or_else(|| anyhow::anyhow!("cargo deb did not report a .deb file"))?;
+            return Ok(vec![self.project_path.join(file)]);
+        }
+
+        self.run(
+            "dpkg-buildpackage",
+            &["-b", "-us", "-uc"],
+            &self.project_path,
+        )
+        .await?;
+        let upstream = version.split_once(':').map(|(_, v)| v).unwrap_or(version);
+        let files = self
+            .parent_files(&format!("{}_{}_", name, upstream), ".deb")
+            .await;
+        if files.is_empty() {
+            anyhow::bail!(
+                "dpkg-buildpackage did not produce {}_{}_*.deb",
+                name,
+                upstream
+            );
+        }
+        Ok(files)
+    }
+
+    /// Build and sign the source package; returns the `_source.changes` file
+    async fn build_source(&self, name: &str, version: &str) -> anyhow::Result<PathBuf> {
+        self.run(
+            "dpkg-buildpackage",
+            &["-S", "-us", "-uc", "-d"],
+            &self.project_path,
+        )
+        .await?;
+        let upstream = version.split_once(':').map(|(_, v)| v).unwrap_or(version);
+        let changes = self
+            .parent_files(&format!("{}_{}_source", name, upstream), ".changes")
+            .await
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("{}_{}_source.changes not found", name, upstream))?;
+
+        let changes_str = changes.display().to_string();
+        let key_arg = self
+            .config
+            .sign_key
+            .as_ref()
+            .map(|key| format!("-k{}", key));
+        let mut args: Vec<&str> = key_arg.iter().map(String::as_str).collect();
+        args.push(&changes_str);
+        self.run("debsign", &args, &self.project_path).await?;
+        Ok(changes)
+    }
+
+    /// Sign a binary package with dpkg-sig (when `signKey` is set)
+    async fn sign_deb(&self, file: &Path) -> anyhow::Result<()> {
+        if let Some(key) = &self.config.sign_key {
+            let file = file.display().to_string();
+            self.run(
+                "dpkg-sig",
+                &["-k", key, "--sign", "builder", &file],
+                &self.project_path,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn upload_aptly(
+        &self,
+        files: &[PathBuf],
+        name: &str,
+        version: &str,
+    ) -> anyhow::Result<()> {
+        let url = self.base_url()?;
+        let repository = self.repository()?;
+        let distribution = self
+            .distribution()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.distribution_required")))?;
+        let upload_dir = format!("package-publisher-{}-{}", name, version.replace(':', "_"));
+
+        for file in files {
+            let file_name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow::anyhow!("Invalid file: {}", file.display()))?;
+            let form = Form::new().part(
+                "file",
+                Part::bytes(fs::read(file).await?)
+                    .file_name(file_name.clone())
+                    .mime_str("application/octet-stream")?,
+            );
+            let response = self
+                .request(
+                    reqwest::Method::POST,
+                    &format!("{}/api/files/{}", url, upload_dir),
+                )
+                .multipart(form)
+                .send_rate_limited()
+                .await?;
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "{}",
+                    tr!(
+                        "debian.upload_status",
+                        file = file_name,
+                        status = response.status()
+                    )
+                );
+            }
+        }
+
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("{}/api/repos/{}/file/{}", url, repository, upload_dir),
+            )
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "debian.add_failed",
+                    repository = repository,
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+
+        let mut body = serde_json::json!({});
+        if let Some(key) = &self.config.sign_key {
+            body["Signing"] = serde_json::json!({ "GpgKey": key });
+        }
+        let prefix = aptly_prefix(self.config.prefix.as_deref().unwrap_or("."));
+        let response = self
+            .request(
+                reqwest::Method::PUT,
+                &format!("{}/api/publish/{}/{}", url, prefix, distribution),
+            )
+            .json(&body)
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "debian.aptly_publish_failed",
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    async fn upload_artifactory(&self, files: &[PathBuf], name: &str) -> anyhow::Result<()> {
+        let url = self.base_url()?;
+        let repository = self.repository()?;
+        let distribution = self
+            .distribution()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.distribution_required")))?;
+        if self.token().is_none() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.not_set",
+                    name = self.token_env().unwrap_or_default()
+                )
+            );
+        }
+
+        for file in files {
+            let file_name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow::anyhow!("Invalid file: {}", file.display()))?;
+            let mut properties = BTreeMap::new();
+            properties.insert("deb.distribution".to_string(), distribution.to_string());
+            properties.insert("deb.component".to_string(), self.component().to_string());
+            properties.insert(
+                "deb.architecture".to_string(),
+                deb_architecture(file).unwrap_or_else(|| self.architecture().to_string()),
+            );
+            let target = format!(
+                "{}/{}/pool/{}/{}/{};{}",
+                url,
+                repository,
+                self.component(),
+                name,
+                file_name,
+                artifactory_properties(&properties)
+            );
+            let response = self
+                .request(reqwest::Method::PUT, &target)
+                .body(fs::read(file).await?)
+                .send_rate_limited()
+                .await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!(
+                    "{}",
+                    tr!(
+                        "registry.upload_status",
+                        file = file_name,
+                        status = status,
+                        body = body.trim()
+                    )
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Package index listing the published package
+    async fn index_url(&self, target: DebianTarget) -> anyhow::Result<String> {
+        let distribution = match (self.distribution(), target) {
+            (Some(distribution), _) => distribution.to_string(),
+            (None, DebianTarget::Ppa) => self
+                .changelog_head()
+                .await
+                .map(|(_, _, distribution)| distribution)
+                .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.changelog_not_found")))?,
+            (None, _) => {
+                anyhow::bail!("{}", tr!("debian.distribution_required"))
+            }
+        };
+        Ok(match target {
+            DebianTarget::Ppa => {
+                let ppa = self
+                    .config
+                    .ppa
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.ppa_required")))?;
+                format!(
+                    "{}/{}/ubuntu/dists/{}/main/binary-{}/Packages",
+                    LAUNCHPAD_PPA_URL,
+                    ppa.trim_start_matches("ppa:"),
+                    distribution,
+                    self.architecture()
+                )
+            }
+            DebianTarget::Aptly => format!(
+                "{}/api/repos/{}/packages",
+                self.base_url()?,
+                self.repository()?
+            ),
+            DebianTarget::Artifactory => format!(
+                "{}/{}/dists/{}/{}/binary-{}/Packages",
+                self.base_url()?,
+                self.repository()?,
+                distribution,
+                self.component(),
+                self.architecture()
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for DebianPlugin {
+    fn name(&self) -> &str {
+        "debian"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.debian`
+        Ok(self.target().is_ok())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+        let mut error = |field: &str, message: String| {
+            errors.push(ValidationError {
+                field: field.to_string(),
+                message,
+                severity: "error".to_string(),
+            })
+        };
+
+        match self.target() {
+            Ok(target) => {
+                metadata.insert(
+                    "target".to_string(),
+                    serde_json::Value::String(target.to_string()),
+                );
+                match target {
+                    DebianTarget::Ppa => {
+                        if self.config.ppa.is_none() {
+                            error("ppa", tr!("debian.ppa_format_required"));
+                        }
+                        if !self.has_debian_dir().await {
+                            error("debian", tr!("debian.source_package_required"));
+                        }
+                    }
+                    DebianTarget::Aptly | DebianTarget::Artifactory => {
+                        if let Err(e) = self.base_url() {
+                            error("url", e.to_string());
+                        }
+                        if let Err(e) = self.repository() {
+                            error("repository", e.to_string());
+                        }
+                        if self.distribution().is_none() {
+                            error("distribution", tr!("debian.distribution_required"));
+                        }
+                        if target == DebianTarget::Artifactory && self.token().is_none() {
+                            error(
+                                "token",
+                                tr!(
+                                    "registry.not_set",
+                                    name = self.token_env().unwrap_or_default()
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(e) => error("target", e.to_string()),
+        }
+
+        if self.config.artifacts.is_none()
+            && self.config.builder.is_none()
+            && !self.has_debian_dir().await
+            && !fs::try_exists(self.project_path.join("Cargo.toml"))
+                .await
+                .unwrap_or(false)
+        {
+            error("builder", tr!("debian.no_package_source"));
+        }
+        if self.config.sign_key.is_none() {
+            warnings.push(ValidationWarning {
+                field: "signKey".to_string(),
+                message: tr!("debian.default_sign_key"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => error("name", e.to_string()),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let plan = async {
+            let target = self.target()?;
+            let (name, version) = self.package_identity().await?;
+            let destination = match target {
+                DebianTarget::Ppa => format!(
+                    "dput ppa:{}",
+                    self.config.ppa.as_deref().unwrap_or_default()
+                ),
+                _ => format!(
+                    "{}/{} ({} {})",
+                    self.base_url()?,
+                    self.repository()?,
+                    self.distribution().unwrap_or_default(),
+                    self.component()
+                ),
+            };
+            let build = if target == DebianTarget::Ppa {
+                "dpkg-buildpackage -S + debsign"
+            } else if self.config.artifacts.is_some() {
+                "prebuilt artifacts"
+            } else if self.uses_cargo_deb().await {
+                "cargo deb"
+            } else {
+                "dpkg-buildpackage -b"
+            };
+            Ok::<_, anyhow::Error>(format!(
+                "{} {} ({}) -> {} [{}]",
+                name, version, build, destination, target
+            ))
+        }
+        .await;
+
+        Ok(match plan {
+            Ok(output) => DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            },
+            Err(e) => DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "target".to_string(),
+                    message: e.to_string(),
+                    severity: "error".to_string(),
+                }]),
+            },
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let target = self.target()?;
+        let (name, version) = self.package_identity().await?;
+
+        let result = async {
+            match target {
+                DebianTarget::Ppa => {
+                    let ppa = self
+                        .config
+                        .ppa
+                        .as_deref()
+                        .ok_or_else(|| anyhow::anyhow!("{}", tr!("debian.ppa_required")))?;
+                    let changes = self.build_source(&name, &version).await?;
+                    let destination = format!("ppa:{}", ppa.trim_start_matches("ppa:"));
+                    self.run(
+                        "dput",
+                        &[&destination, &changes.display().to_string()],
+                        &self.project_path,
+                    )
+                    .await?;
+                    Ok::<_, anyhow::Error>((
+                        format!(
+                            "https://launchpad.net/~{}",
+                            ppa.trim_start_matches("ppa:")
+                                .replacen('/', "/+archive/ubuntu/", 1)
+                        ),
+                        vec![changes],
+                    ))
+                }
+                DebianTarget::Aptly | DebianTarget::Artifactory => {
+                    let files = self.build_debs(&name, &version).await?;
+                    for file in &files {
+                        self.sign_deb(file).await?;
+                    }
+                    if target == DebianTarget::Aptly {
+                        self.upload_aptly(&files, &name, &version).await?;
+                    } else {
+                        self.upload_artifactory(&files, &name).await?;
+                    }
+                    Ok((
+                        format!("{}/{}", self.base_url()?, self.repository()?),
+                        files,
+                    ))
+                }
+            }
+        }
+        .await;
+
+        match result {
+            Ok((url, files)) => {
+                let mut metadata = HashMap::new();
+                if target == DebianTarget::Ppa {
+                    // Launchpad builds the binaries after accepting the upload
+                    metadata.insert("pendingReview".to_string(), serde_json::Value::Bool(true));
+                }
+                Ok(PublishResult {
+                    success: true,
+                    version: Some(version),
+                    package_url: Some(url),
+                    output: Some(
+                        files
+                            .iter()
+                            .map(|f| format!("Uploaded {}", f.display()))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ),
+                    error: None,
+                    metadata: Some(metadata),
+                    error_kind: None,
+                })
+            }
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let target = self.target()?;
+        let (name, version) = self.package_identity().await?;
+        let url = self.index_url(target).await?;
+
+        let mut request = self.request(reqwest::Method::GET, &url);
+        if target == DebianTarget::Aptly {
+            request = request.query(&[("q", format!("Name (= {})", name))]);
+        }
+        let error = match request.send_rate_limited().await {
+            Ok(response) if response.status().is_success() => {
+                let body = response.text().await.unwrap_or_default();
+                let listed = match target {
+                    // Package keys: "Pamd64 name 1.2.0-1 hash"
+                    DebianTarget::Aptly => serde_json::from_str::<Vec<String>>(&body)
+                        .unwrap_or_default()
+                        .iter()
+                        .any(|key| {
+                            let mut parts = key.split_whitespace().skip(1);
+                            parts.next() == Some(name.as_str())
+                                && parts.next().is_some_and(|v| matches_version(v, &version))
+                        }),
+                    _ => packages_index_has_version(&body, &name, &version),
+                };
+                if listed {
+                    return Ok(VerificationResult {
+                        verified: true,
+                        version: Some(version),
+                        url: Some(url),
+                        error: None,
+                        metadata: None,
+                        integrity: None,
+                    });
+                }
+                tr!("debian.not_in_index", package = name, version = version)
+            }
+            Ok(response) => tr!("registry.index_fetch_status", status = response.status()),
+            Err(e) => tr!("registry.index_fetch_failed", error = e),
+        };
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(version),
+            url: Some(url),
+            error: Some(error),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changelog_and_index_helpers() {
+        let changelog = "my-tool (1.2.0-1ubuntu1) jammy; urgency=medium\n\n  * New release\n\n -- Me <me@example.com>  Mon, 01 Jan 2024 00:00:00 +0000\n";
+        assert_eq!(
+            parse_changelog_head(changelog),
+            Some((
+                "my-tool".to_string(),
+                "1.2.0-1ubuntu1".to_string(),
+                "jammy".to_string()
+            ))
+        );
+
+        let packages = "Package: my-tool\nVersion: 1:1.2.0-1\nArchitecture: amd64\n\nPackage: other\nVersion: 2.0.0\n";
+        assert!(packages_index_has_version(packages, "my-tool", "1.2.0"));
+        assert!(packages_index_has_version(packages, "my-tool", "1.2.0-1"));
+        assert!(!packages_index_has_version(packages, "my-tool", "1.2"));
+        assert!(!packages_index_has_version(packages, "other", "1.2.0"));
+
+        assert_eq!(aptly_prefix("."), ":.");
+        assert_eq!(aptly_prefix("ubuntu/my_repo"), "ubuntu_my__repo");
+        assert_eq!(
+            deb_architecture(Path::new("dist/my-tool_1.2.0-1_arm64.deb")).as_deref(),
+            Some("arm64")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_target_and_validation() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"my-tool\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+
+        let ppa =
+            DebianPlugin::new(temp_dir.path().to_path_buf()).with_config(DebianRegistryConfig {
+                ppa: Some("me/tools".to_string()),
+                ..Default::default()
+            });
+        assert_eq!(ppa.target().unwrap(), DebianTarget::Ppa);
+        let result = ppa.validate().await.unwrap();
+        assert!(result.errors.iter().any(|e| e.field == "debian"));
+
+        let aptly =
+            DebianPlugin::new(temp_dir.path().to_path_buf()).with_config(DebianRegistryConfig {
+                target: Some("aptly".to_string()),
+                url: Some("https://aptly.example.com/".to_string()),
+                repository: Some("tools".to_string()),
+                ..Default::default()
+            });
+        let result = aptly.validate().await.unwrap();
+        let fields: Vec<&str> = result.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["distribution"]);
+        assert_eq!(
+            aptly
+                .index_url(DebianTarget::Aptly)
+                .await
+                .unwrap_err()
+                .to_string(),
+            "Set registries.debian.distribution"
+        );
+
+        assert!(DebianPlugin::default().target().is_err());
+    }
+}
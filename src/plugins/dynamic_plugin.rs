@@ -0,0 +1,358 @@
+//! Dynamic Plugin - registry plugins loaded from shared libraries
+//!
+//! Third-party registry plugins are `cdylib` crates listed under `plugins:`
+//! and loaded at runtime with `libloading`. The ABI is plain C and exchanges
+//! JSON strings, so plugins do not depend on the Rust compiler version used
+//! to build this crate:
+//!
+//! ```c
+//! uint32_t package_publisher_plugin_abi_version(void);   // must return 1
+//! char *package_publisher_plugin_call(const char *method, const char *request);
+//! void package_publisher_plugin_free(char *response);
+//! ```
+//!
+//! - `method` is one of `name`, `version`, `detect`, `validate`, `dry_run`,
+//!   `publish`, `verify` and `rollback`
+//! - `request` is `{"projectPath", "config", "options"?, "version"?}`
+//! - The response is `{"ok": <result>}` with the serialized result type
+//!   (e.g. [`PublishResult`]) or `{"error": "<message>"}`; it is released
+//!   with `package_publisher_plugin_free`
+
+use crate::core::config::PluginConfig;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, RollbackResult, ValidationResult,
+    VerificationResult,
+};
+use crate::tr;
+use async_trait::async_trait;
+use libloading::Library;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// ABI version implemented by this crate
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Directory searched for plugins without `path`
+pub const PLUGIN_DIR: &str = ".package-publisher/plugins";
+
+/// `package_publisher_plugin_abi_version`
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+/// `package_publisher_plugin_call`
+pub type PluginCallFn = unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_char;
+/// `package_publisher_plugin_free`
+pub type PluginFreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// Entry points of a plugin
+#[derive(Clone, Copy)]
+pub(crate) struct PluginAbi {
+    pub(crate) call: PluginCallFn,
+    pub(crate) free: PluginFreeFn,
+}
+
+impl PluginAbi {
+    /// Call `method` with a JSON request, returning the `ok` value
+    fn invoke(
+        &self,
+        method: &str,
+        request: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let method = CString::new(method)?;
+        let request = CString::new(request.to_string())?;
+
+        // SAFETY: both strings are valid NUL-terminated C strings for the
+        // duration of the call; the plugin owns the returned buffer until it
+        // is handed back to `free`.
+        let response = unsafe { (self.call)(method.as_ptr(), request.as_ptr()) };
+        if response.is_null() {
+            anyhow::bail!(
+                "Plugin returned no response for {}",
+                method.to_string_lossy()
+            );
+        }
+        // SAFETY: a non-null response is a NUL-terminated string allocated by the plugin
+        let text = unsafe { CStr::from_ptr(response) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: the buffer came from this plugin and is not used afterwards
+        unsafe { (self.free)(response) };
+
+        let mut response: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid plugin response: {}", e))?;
+        if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+            anyhow::bail!("{}", error);
+        }
+        match response.get_mut("ok") {
+            Some(ok) => Ok(ok.take()),
+            None => anyhow::bail!("Plugin response has neither \"ok\" nor \"error\""),
+        }
+    }
+}
+
+/// Registry plugin implemented by a shared library
+pub struct DynamicPlugin {
+    abi: PluginAbi,
+    /// Keeps the entry points loaded for as long as the plugin is alive
+    library: Option<Arc<Library>>,
+    name: String,
+    version: String,
+    project_path: PathBuf,
+    config: HashMap<String, serde_json::Value>,
+}
+
+impl DynamicPlugin {
+    /// Load a plugin library and check its ABI version
+    ///
+    /// Loading runs the library's initializers, so only configure plugins
+    /// from trusted sources.
+    pub fn load(
+        library_path: &Path,
+        project_path: impl Into<PathBuf>,
+        config: HashMap<String, serde_json::Value>,
+    ) -> anyhow::Result<Self> {
+        // SAFETY: loading a library runs arbitrary code; plugins are
+        // explicitly configured by the project owner.
+        let library = unsafe { Library::new(library_path) }.map_err(|e| {
+            anyhow::anyhow!(
+                "{}",
+                tr!(
+                    "plugin.load_failed",
+                    path = library_path.display(),
+                    error = e
+                )
+            )
+        })?;
+
+        // SAFETY: the symbol types match the documented plugin ABI
+        let abi = unsafe {
+            let abi_version =
+                library.get::<AbiVersionFn>(b"package_publisher_plugin_abi_version")?;
+            let found = abi_version();
+            if found != PLUGIN_ABI_VERSION {
+                anyhow::bail!(
+                    "{}",
+                    tr!(
+                        "plugin.unsupported_abi",
+                        path = library_path.display(),
+                        found = found,
+                        supported = PLUGIN_ABI_VERSION
+                    )
+                );
+            }
+            PluginAbi {
+                call: *library.get::<PluginCallFn>(b"package_publisher_plugin_call")?,
+                free: *library.get::<PluginFreeFn>(b"package_publisher_plugin_free")?,
+            }
+        };
+
+        Self::from_abi(abi, Some(Arc::new(library)), project_path, config)
+    }
+
+    /// Create a plugin from already resolved entry points
+    ///
+    /// `library` must own the code behind `abi` (or be `None` when the
+    /// functions are statically linked). Crate-private: the entry points are
+    /// called without further checks, so only [`DynamicPlugin::load`] and
+    /// tests may supply them.
+    pub(crate) fn from_abi(
+        abi: PluginAbi,
+        library: Option<Arc<Library>>,
+        project_path: impl Into<PathBuf>,
+        config: HashMap<String, serde_json::Value>,
+    ) -> anyhow::Result<Self> {
+        let mut plugin = Self {
+            abi,
+            library,
+            name: String::new(),
+            version: String::new(),
+            project_path: project_path.into(),
+            config,
+        };
+        plugin.name = plugin.call_blocking("name", None)?;
+        plugin.version = plugin.call_blocking("version", None)?;
+        Ok(plugin)
+    }
+
+    /// Request sent with every call
+    fn request(&self, extra: Option<(&str, serde_json::Value)>) -> serde_json::Value {
+        let mut request = serde_json::json!({
+            "projectPath": self.project_path.to_string_lossy(),
+            "config": self.config,
+        });
+        if let Some((key, value)) = extra {
+            request[key] = value;
+        }
+        request
+    }
+
+    fn call_blocking<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        extra: Option<(&str, serde_json::Value)>,
+    ) -> anyhow::Result<T> {
+        let value = self.abi.invoke(method, &self.request(extra))?;
+        serde_json::from_value(value)
+            .map_err(|e| anyhow::anyhow!("Invalid {} result from {}: {}", method, self.name, e))
+    }
+
+    /// Call the plugin off the async runtime (plugins may block on I/O)
+    async fn call<T: DeserializeOwned + Send + 'static>(
+        &self,
+        method: &'static str,
+        extra: Option<(&'static str, serde_json::Value)>,
+    ) -> anyhow::Result<T> {
+        let abi = self.abi;
+        let library = self.library.clone();
+        let request = self.request(extra);
+        let name = self.name.clone();
+        tokio::task::spawn_blocking(move || {
+            let _library = library;
+            let value = abi.invoke(method, &request)?;
+            serde_json::from_value(value)
+                .map_err(|e| anyhow::anyhow!("Invalid {} result from {}: {}", method, name, e))
+        })
+        .await?
+    }
+}
+
+/// Library path of a configured plugin (`path`, or the platform file name
+/// of `name` under [`PLUGIN_DIR`])
+pub fn plugin_library_path(project_path: &Path, plugin: &PluginConfig) -> PathBuf {
+    match plugin.path.as_deref() {
+        Some(path) => project_path.join(path),
+        None => project_path
+            .join(PLUGIN_DIR)
+            .join(libloading::library_filename(&plugin.name)),
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for DynamicPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        self.call(
+            "detect",
+            Some((
+                "projectPath",
+                serde_json::Value::String(project_path.to_string()),
+            )),
+        )
+        .await
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        self.call("validate", None).await
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        self.call("dry_run", None).await
+    }
+
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let options = serde_json::to_value(options.unwrap_or_default())?;
+        self.call("publish", Some(("options", options))).await
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        self.call("verify", None).await
+    }
+
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        self.call(
+            "rollback",
+            Some(("version", serde_json::Value::String(version.to_string()))),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// In-process implementation of the plugin ABI
+    unsafe extern "C" fn call(method: *const c_char, request: *const c_char) -> *mut c_char {
+        let method = unsafe { CStr::from_ptr(method) }.to_string_lossy();
+        let request: serde_json::Value =
+            serde_json::from_str(&unsafe { CStr::from_ptr(request) }.to_string_lossy()).unwrap();
+        let response = match method.as_ref() {
+            "name" => serde_json::json!({"ok": "acme"}),
+            "version" => serde_json::json!({"ok": "0.3.0"}),
+            "detect" => serde_json::json!({"ok": request["config"]["enabled"] == true}),
+            "publish" => serde_json::json!({"ok": {
+                "success": true,
+                "version": "1.2.3",
+                "package_url": format!("{}/pkg", request["config"]["registryUrl"].as_str().unwrap()),
+                "output": request["options"]["tag"],
+            }}),
+            other => serde_json::json!({"error": format!("{} is not supported", other)}),
+        };
+        CString::new(response.to_string()).unwrap().into_raw()
+    }
+
+    unsafe extern "C" fn free(response: *mut c_char) {
+        drop(unsafe { CString::from_raw(response) });
+    }
+
+    fn plugin() -> DynamicPlugin {
+        let config = HashMap::from([
+            ("enabled".to_string(), serde_json::json!(true)),
+            (
+                "registryUrl".to_string(),
+                serde_json::json!("https://registry.acme.dev"),
+            ),
+        ]);
+        DynamicPlugin::from_abi(PluginAbi { call, free }, None, "/project", config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_calls_round_trip_json() {
+        let plugin = plugin();
+        assert_eq!(plugin.name(), "acme");
+        assert_eq!(plugin.version(), "0.3.0");
+        assert!(plugin.detect("/project").await.unwrap());
+
+        let options = PublishOptions {
+            tag: Some("next".to_string()),
+            ..Default::default()
+        };
+        let result = plugin.publish(Some(options)).await.unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.package_url.as_deref(),
+            Some("https://registry.acme.dev/pkg")
+        );
+        assert_eq!(result.output.as_deref(), Some("next"));
+
+        let error = plugin.verify().await.unwrap_err();
+        assert_eq!(error.to_string(), "verify is not supported");
+    }
+
+    #[test]
+    fn test_load_reports_missing_library() {
+        let plugin = PluginConfig {
+            name: "acme".to_string(),
+            version: None,
+            path: None,
+            command: None,
+            config: HashMap::new(),
+        };
+        let path = plugin_library_path(Path::new("/nonexistent"), &plugin);
+        assert!(path.starts_with("/nonexistent/.package-publisher/plugins"));
+        assert!(path.to_string_lossy().contains("acme"));
+
+        let error = DynamicPlugin::load(&path, "/nonexistent", HashMap::new())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Cannot load"));
+    }
+}
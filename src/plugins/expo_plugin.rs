@@ -0,0 +1,689 @@
+//! Expo Plugin - over-the-air updates for Expo / React Native apps
+//!
+//! Provides EAS Update publishing support:
+//! - Expo app detection (`app.json` with an `expo` key, `app.config.js` / `app.config.ts`)
+//! - Validation of the app config (slug, version, EAS project ID, runtime version)
+//! - `expo export` as the dry-run (the same bundle `eas update` uploads)
+//! - `eas update` to the selected channel, optionally followed by `eas submit`
+//! - Verification of the update group on the channel via the EAS GraphQL API
+//!
+//! The access token comes from `EXPO_TOKEN` (or `registries.expo.tokenEnv`).
+
+use crate::core::config::ExpoRegistryConfig;
+use crate::core::git::run_git;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const EAS_GRAPHQL_URL: &str = "https://api.expo.dev/graphql";
+
+const DEFAULT_TOKEN_ENV: &str = "EXPO_TOKEN";
+
+const DEFAULT_CHANNEL: &str = "production";
+
+/// Dynamic app config files (evaluated with `expo config`)
+const DYNAMIC_CONFIGS: [&str; 2] = ["app.config.js", "app.config.ts"];
+
+/// Latest update group of a channel's branch
+const CHANNEL_QUERY: &str = r#"query LatestUpdateGroup($appId: String!, $channel: String!) {
+  app {
+    byId(appId: $appId) {
+      updateChannelByName(name: $channel) {
+        updateBranches(offset: 0, limit: 1) {
+          name
+          updateGroups(offset: 0, limit: 1) {
+            id
+            group
+            platform
+            runtimeVersion
+            message
+            gitCommitHash
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Update published by `eas update --json`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EasUpdate {
+    pub id: String,
+    pub group: String,
+    pub platform: String,
+    #[serde(default)]
+    pub runtime_version: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub git_commit_hash: Option<String>,
+}
+
+/// Expo app config fields used for publishing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpoAppConfig {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub version: Option<String>,
+    pub owner: Option<String>,
+    pub project_id: Option<String>,
+    /// Literal runtime version, or the policy name (`appVersion`, `fingerprint`, ...)
+    pub runtime_version: Option<String>,
+}
+
+impl ExpoAppConfig {
+    /// Read the fields from an `expo` config object
+    pub fn from_json(expo: &serde_json::Value) -> Self {
+        let text = |value: &serde_json::Value| value.as_str().map(str::to_string);
+        // The project ID is in extra.eas, or in the updates URL (u.expo.dev/<id>)
+        let project_id = text(&expo["extra"]["eas"]["projectId"]).or_else(|| {
+            expo["updates"]["url"]
+                .as_str()
+                .and_then(|url| url.strip_prefix("https://u.expo.dev/"))
+                .map(|id| id.trim_end_matches('/').to_string())
+        });
+        let runtime_version =
+            text(&expo["runtimeVersion"]).or_else(|| text(&expo["runtimeVersion"]["policy"]));
+
+        Self {
+            name: text(&expo["name"]),
+            slug: text(&expo["slug"]),
+            version: text(&expo["version"]),
+            owner: text(&expo["owner"]),
+            project_id,
+            runtime_version,
+        }
+    }
+}
+
+/// Parse `eas update --json` output (an array of updates, one per platform)
+pub fn parse_eas_updates(output: &str) -> anyhow::Result<Vec<EasUpdate>> {
+    // eas-cli may print progress before the JSON document
+    let start = output
+        .find('[')
+        .ok_or_else(|| anyhow::anyhow!("eas update did not print JSON output"))?;
+    Ok(serde_json::from_str(&output[start..])?)
+}
+
+/// Environment variables the EAS CLI needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["EXPO_*", "EAS_*", "NODE_*", "NPM_CONFIG_*", "npm_config_*"];
+
+/// Expo plugin
+pub struct ExpoPlugin {
+    project_path: PathBuf,
+    config: ExpoRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for ExpoPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl ExpoPlugin {
+    /// Create a new Expo plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: ExpoRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.expo` settings
+    pub fn with_config(mut self, config: ExpoRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run the EAS CLI with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Whether the project is an Expo app
+    pub(crate) async fn is_expo_app(project_path: &Path) -> bool {
+        if let Ok(content) = fs::read_to_string(project_path.join("app.json")).await
+            && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+            && json.get("expo").is_some()
+        {
+            return true;
+        }
+        for config in DYNAMIC_CONFIGS {
+            if fs::metadata(project_path.join(config)).await.is_ok() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Resolve the app config (static `app.json`, or evaluated with `expo config`)
+    async fn load_app_config(&self) -> anyhow::Result<ExpoAppConfig> {
+        let mut dynamic = false;
+        for config in DYNAMIC_CONFIGS {
+            dynamic |= fs::metadata(self.project_path.join(config)).await.is_ok();
+        }
+
+        let expo = if dynamic {
+            let output = self
+                .run("npx", &["expo", "config", "--json", "--type", "public"])
+                .await?;
+            serde_json::from_str::<serde_json::Value>(&output)?
+        } else {
+            let content = fs::read_to_string(self.project_path.join("app.json")).await?;
+            let json: serde_json::Value = serde_json::from_str(&content)?;
+            json.get("expo")
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("app.json has no expo field"))?
+        };
+
+        Ok(ExpoAppConfig::from_json(&expo))
+    }
+
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    fn token(&self) -> Option<String> {
+        std::env::var(self.token_env())
+            .ok()
+            .filter(|t| !t.is_empty())
+    }
+
+    fn platform(&self) -> &str {
+        self.config.platform.as_deref().unwrap_or("all")
+    }
+
+    /// Channel from the config, else the publish tag, else "production"
+    fn channel(&self, options: Option<&PublishOptions>) -> String {
+        self.config
+            .channel
+            .clone()
+            .or_else(|| options.and_then(|o| o.tag.clone()))
+            .unwrap_or_else(|| DEFAULT_CHANNEL.to_string())
+    }
+
+    fn message(&self, version: &str) -> String {
+        self.config
+            .message
+            .as_deref()
+            .unwrap_or("Release {version}")
+            .replace("{version}", version)
+    }
+
+    async fn run(&self, program: &str, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = self.limits.command(program);
+        command.args(args).current_dir(&self.project_path);
+        // eas-cli reads EXPO_TOKEN; map a custom variable onto it
+        if let Some(token) = self.token() {
+            command.env("EXPO_TOKEN", token);
+        }
+
+        let output = self.limits.output(&mut command).await?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}{}", stdout, stderr);
+        }
+
+        Ok(stdout)
+    }
+
+    async fn git_head(&self) -> Option<String> {
+        run_git(&self.project_path, &["rev-parse", "HEAD"])
+            .await
+            .ok()
+    }
+
+    /// Latest update group on the channel, from the EAS API
+    async fn latest_update_group(
+        &self,
+        project_id: &str,
+        channel: &str,
+    ) -> anyhow::Result<Vec<EasUpdate>> {
+        let mut request = http_client()
+            .post(EAS_GRAPHQL_URL)
+            .json(&serde_json::json!({
+                "query": CHANNEL_QUERY,
+                "variables": { "appId": project_id, "channel": channel },
+            }));
+        if let Some(token) = self.token() {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("EAS API returned HTTP {}", response.status());
+        }
+        let body: serde_json::Value = response.json().await?;
+        if let Some(message) = body["errors"][0]["message"].as_str() {
+            anyhow::bail!("EAS API error: {}", message);
+        }
+
+        let channel_data = &body["data"]["app"]["byId"]["updateChannelByName"];
+        if channel_data.is_null() {
+            anyhow::bail!("Channel {} not found", channel);
+        }
+        let group = channel_data["updateBranches"][0]["updateGroups"][0].clone();
+        Ok(serde_json::from_value(group).unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for ExpoPlugin {
+    fn name(&self) -> &str {
+        "expo"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        Ok(Self::is_expo_app(Path::new(project_path)).await)
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let app = self.load_app_config().await?;
+
+        for (field, value) in [("name", &app.name), ("slug", &app.slug)] {
+            if value.as_deref().is_none_or(str::is_empty) {
+                errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("expo.field_required", field = field),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        match &app.version {
+            Some(version) => {
+                metadata.insert(
+                    "version".to_string(),
+                    serde_json::Value::String(version.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "version".to_string(),
+                message: tr!("expo.version_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match &app.project_id {
+            Some(project_id) => {
+                metadata.insert(
+                    "projectId".to_string(),
+                    serde_json::Value::String(project_id.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "projectId".to_string(),
+                message: tr!("expo.project_id_missing"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        if app.runtime_version.is_none() {
+            warnings.push(ValidationWarning {
+                field: "runtimeVersion".to_string(),
+                message: tr!("expo.runtime_version_recommended"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if !["all", "ios", "android"].contains(&self.platform()) {
+            errors.push(ValidationError {
+                field: "platform".to_string(),
+                message: tr!("expo.invalid_platform", platform = self.platform()),
+                severity: "error".to_string(),
+            });
+        }
+
+        if self.token().is_none() {
+            warnings.push(ValidationWarning {
+                field: "token".to_string(),
+                message: tr!("expo.token_missing", token_env = self.token_env()),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if let Some(slug) = app.slug {
+            metadata.insert("packageName".to_string(), serde_json::Value::String(slug));
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let out_dir =
+            std::env::temp_dir().join(format!("package-publisher-expo-{}", uuid::Uuid::new_v4()));
+        let out_dir_str = out_dir.display().to_string();
+
+        let result = self
+            .run(
+                "npx",
+                &[
+                    "expo",
+                    "export",
+                    "--platform",
+                    self.platform(),
+                    "--output-dir",
+                    &out_dir_str,
+                ],
+            )
+            .await;
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        match result {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output: format!(
+                    "{}\neas update --channel {} --platform {}",
+                    output.trim_end(),
+                    self.channel(None),
+                    self.platform()
+                ),
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "export".to_string(),
+                    message: tr!("expo.export_failed"),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let app = self.load_app_config().await?;
+        let version = app
+            .version
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("App version not found"))?;
+        let channel = self.channel(options.as_ref());
+        let message = self.message(&version);
+
+        let output = match self
+            .run(
+                "eas",
+                &[
+                    "update",
+                    "--channel",
+                    &channel,
+                    "--message",
+                    &message,
+                    "--platform",
+                    self.platform(),
+                    "--non-interactive",
+                    "--json",
+                ],
+            )
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!("expo.update_failed", error = e)),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+        };
+
+        let updates = parse_eas_updates(&output).unwrap_or_default();
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "channel".to_string(),
+            serde_json::Value::String(channel.clone()),
+        );
+        if let Some(update) = updates.first() {
+            metadata.insert(
+                "updateGroupId".to_string(),
+                serde_json::Value::String(update.group.clone()),
+            );
+            if let Some(runtime_version) = &update.runtime_version {
+                metadata.insert(
+                    "runtimeVersion".to_string(),
+                    serde_json::Value::String(runtime_version.clone()),
+                );
+            }
+        }
+        metadata.insert(
+            "updateIds".to_string(),
+            serde_json::Value::Array(
+                updates
+                    .iter()
+                    .map(|u| serde_json::Value::String(u.id.clone()))
+                    .collect(),
+            ),
+        );
+
+        let mut output = output;
+        if self.config.submit.unwrap_or(false) {
+            let profile = self
+                .config
+                .submit_profile
+                .as_deref()
+                .unwrap_or(DEFAULT_CHANNEL);
+            let submitted = self
+                .run(
+                    "eas",
+                    &[
+                        "submit",
+                        "--platform",
+                        self.platform(),
+                        "--profile",
+                        profile,
+                        "--latest",
+                        "--non-interactive",
+                        "--wait",
+                    ],
+                )
+                .await;
+            match submitted {
+                Ok(submit_output) => {
+                    output.push_str(&submit_output);
+                    metadata.insert("submitted".to_string(), serde_json::Value::Bool(true));
+                }
+                Err(e) => {
+                    return Ok(PublishResult {
+                        success: false,
+                        version: Some(version),
+                        package_url: None,
+                        output: Some(output),
+                        error: Some(tr!("expo.submit_failed", error = e)),
+                        metadata: Some(metadata),
+                        error_kind: None,
+                    });
+                }
+            }
+        }
+
+        let package_url = match (&app.owner, &app.slug, updates.first()) {
+            (Some(owner), Some(slug), Some(update)) => Some(format!(
+                "https://expo.dev/accounts/{}/projects/{}/updates/{}",
+                owner, slug, update.group
+            )),
+            _ => None,
+        };
+
+        Ok(PublishResult {
+            success: true,
+            version: Some(version),
+            package_url,
+            output: Some(output),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let app = self.load_app_config().await?;
+        let project_id = app
+            .project_id
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("EAS project ID not found"))?;
+        let channel = self.channel(None);
+        let head = self.git_head().await;
+
+        let group = match self.latest_update_group(&project_id, &channel).await {
+            Ok(group) => group,
+            Err(e) => {
+                return Ok(VerificationResult {
+                    verified: false,
+                    version: app.version,
+                    url: None,
+                    error: Some(tr!("registry.verify_failed", error = e)),
+                    metadata: None,
+                    integrity: None,
+                });
+            }
+        };
+
+        // The latest group must come from this commit (or carry our message)
+        let expected_message = app.version.as_deref().map(|v| self.message(v));
+        let matches = |update: &EasUpdate| match (&head, &update.git_commit_hash) {
+            (Some(head), Some(commit)) => head == commit,
+            _ => update.message == expected_message,
+        };
+
+        match group.first() {
+            Some(update) if matches(update) => {
+                let mut metadata = HashMap::new();
+                metadata.insert(
+                    "updateGroupId".to_string(),
+                    serde_json::Value::String(update.group.clone()),
+                );
+                metadata.insert(
+                    "platforms".to_string(),
+                    serde_json::Value::Array(
+                        group
+                            .iter()
+                            .map(|u| serde_json::Value::String(u.platform.clone()))
+                            .collect(),
+                    ),
+                );
+                Ok(VerificationResult {
+                    verified: true,
+                    version: app.version,
+                    url: app
+                        .owner
+                        .as_ref()
+                        .zip(app.slug.as_ref())
+                        .map(|(owner, slug)| {
+                            format!(
+                                "https://expo.dev/accounts/{}/projects/{}/updates/{}",
+                                owner, slug, update.group
+                            )
+                        }),
+                    error: None,
+                    metadata: Some(metadata),
+                    integrity: None,
+                })
+            }
+            _ => Ok(VerificationResult {
+                verified: false,
+                version: app.version,
+                url: None,
+                error: Some(tr!("expo.update_group_not_found", channel = channel)),
+                metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_app_config_from_json() {
+        let expo = serde_json::json!({
+            "name": "Acme",
+            "slug": "acme",
+            "version": "1.4.0",
+            "owner": "acme-inc",
+            "runtimeVersion": {"policy": "appVersion"},
+            "updates": {"url": "https://u.expo.dev/0f8e7f5c-1234-4d6e-9b1a-000000000000"}
+        });
+        let app = ExpoAppConfig::from_json(&expo);
+        assert_eq!(app.slug.as_deref(), Some("acme"));
+        assert_eq!(app.runtime_version.as_deref(), Some("appVersion"));
+        assert_eq!(
+            app.project_id.as_deref(),
+            Some("0f8e7f5c-1234-4d6e-9b1a-000000000000")
+        );
+    }
+
+    #[test]
+    fn test_parse_eas_updates() {
+        let output = r#"Publishing...
+[{"id": "u1", "group": "g1", "platform": "ios", "runtimeVersion": "1.4.0", "gitCommitHash": "abc"},
+ {"id": "u2", "group": "g1", "platform": "android", "runtimeVersion": "1.4.0"}]"#;
+        let updates = parse_eas_updates(output).unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].group, "g1");
+        assert_eq!(updates[1].git_commit_hash, None);
+        assert!(parse_eas_updates("error").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detect_and_channel() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!ExpoPlugin::is_expo_app(temp_dir.path()).await);
+        std::fs::write(
+            temp_dir.path().join("app.json"),
+            r#"{"expo": {"slug": "acme"}}"#,
+        )
+        .unwrap();
+        assert!(ExpoPlugin::is_expo_app(temp_dir.path()).await);
+
+        let plugin = ExpoPlugin::default();
+        let options = PublishOptions {
+            tag: Some("staging".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(plugin.channel(None), "production");
+        assert_eq!(plugin.channel(Some(&options)), "staging");
+        assert_eq!(plugin.message("1.4.0"), "Release 1.4.0");
+    }
+}
@@ -0,0 +1,484 @@
+//! Gemfury Plugin - private multi-format package hosting
+//!
+//! Provides Gemfury publishing support:
+//! - Package format (npm / cargo / python / deb) detected from the project
+//!   or set in `registries.gemfury.format`
+//! - Upload of the built package to the account's push endpoint
+//! - Verification against the format's install index (npm registry, PyPI
+//!   simple index, Cargo sparse index, APT `Packages`) using a deploy token
+//!
+//! The push token comes from the token manager (`GEMFURY_PUSH_TOKEN`).
+
+use crate::core::config::GemfuryRegistryConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::plugins::bucket_plugin::manifest_identity;
+use crate::plugins::hosted_package::{HostedPackageFormat, package_files};
+use crate::security::command_executor::CommandLimits;
+use crate::security::token_manager::SecureTokenManager;
+use crate::tr;
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use secrecy::ExposeSecret;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Upload endpoint (one per account)
+const PUSH_URL: &str = "https://push.fury.io";
+
+const DEFAULT_DEPLOY_TOKEN_ENV: &str = "GEMFURY_DEPLOY_TOKEN";
+
+/// Path of a crate in a Cargo sparse index (`1/a`, `3/a/abc`, `se/rd/serde`)
+pub fn cargo_index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// Whether an install index lists `name@version`
+pub fn index_lists_version(
+    format: HostedPackageFormat,
+    body: &str,
+    name: &str,
+    version: &str,
+) -> bool {
+    match format {
+        HostedPackageFormat::Npm => serde_json::from_str::<serde_json::Value>(body)
+            .map(|doc| doc["versions"].get(version).is_some())
+            .unwrap_or(false),
+        HostedPackageFormat::Cargo => body.lines().any(|line| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .is_ok_and(|entry| entry["vers"].as_str() == Some(version))
+        }),
+        // Simple index links: <name>-<version>.tar.gz / <name>-<version>-py3-none-any.whl
+        HostedPackageFormat::Python => {
+            body.contains(&format!("-{}.tar.gz", version))
+                || body.contains(&format!("-{}-", version))
+        }
+        HostedPackageFormat::Deb => body.split("\n\n").any(|stanza| {
+            stanza
+                .lines()
+                .any(|l| l.trim() == format!("Package: {}", name))
+                && stanza
+                    .lines()
+                    .any(|l| l.trim() == format!("Version: {}", version))
+        }),
+    }
+}
+
+/// Gemfury plugin
+pub struct GemfuryPlugin {
+    project_path: PathBuf,
+    config: GemfuryRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for GemfuryPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl GemfuryPlugin {
+    /// Create a new Gemfury plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: GemfuryRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.gemfury` settings
+    pub fn with_config(mut self, config: GemfuryRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Build npm, cargo and python packages with the sandbox and timeout of
+    /// registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    async fn format(&self) -> anyhow::Result<HostedPackageFormat> {
+        HostedPackageFormat::resolve(self.config.format.as_deref(), &self.project_path).await
+    }
+
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        match (self.config.name.clone().or(name), version) {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!("Package name not found (set registries.gemfury.name)"),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    fn push_token(&self) -> anyhow::Result<String> {
+        SecureTokenManager::new()
+            .get_token("gemfury")
+            .map(|t| t.expose_secret().to_string())
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("gemfury.token_missing")))
+    }
+
+    fn deploy_token_env(&self) -> &str {
+        self.config
+            .deploy_token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_DEPLOY_TOKEN_ENV)
+    }
+
+    fn deploy_token(&self) -> Option<String> {
+        std::env::var(self.deploy_token_env())
+            .ok()
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Install index URL for the package
+    pub fn index_url(&self, format: HostedPackageFormat, name: &str) -> String {
+        let account = &self.config.account;
+        match format {
+            HostedPackageFormat::Npm => {
+                format!(
+                    "https://npm.fury.io/{}/{}",
+                    account,
+                    name.replace('/', "%2f")
+                )
+            }
+            HostedPackageFormat::Cargo => format!(
+                "https://cargo.fury.io/{}/{}",
+                account,
+                cargo_index_path(name)
+            ),
+            HostedPackageFormat::Python => format!(
+                "https://pypi.fury.io/{}/{}/",
+                account,
+                name.to_lowercase().replace(['_', '.'], "-")
+            ),
+            HostedPackageFormat::Deb => format!("https://apt.fury.io/{}/Packages", account),
+        }
+    }
+
+    async fn push(&self, file: &Path, token: &str) -> anyhow::Result<()> {
+        let file_name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Invalid file: {}", file.display()))?;
+        let form = Form::new().part(
+            "package",
+            Part::bytes(fs::read(file).await?)
+                .file_name(file_name.clone())
+                .mime_str("application/octet-stream")?,
+        );
+
+        let response = http_client()
+            .post(format!("{}/{}/", PUSH_URL, self.config.account))
+            .basic_auth(token, None::<&str>)
+            .multipart(form)
+            .send_rate_limited()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.upload_status",
+                    file = file_name,
+                    status = status,
+                    body = body.trim()
+                )
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for GemfuryPlugin {
+    fn name(&self) -> &str {
+        "gemfury"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.gemfury`, not a manifest
+        Ok(!self.config.account.is_empty())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        if self.config.account.is_empty() {
+            errors.push(ValidationError {
+                field: "account".to_string(),
+                message: tr!("gemfury.account_required"),
+                severity: "error".to_string(),
+            });
+        }
+        if let Err(e) = self.push_token() {
+            errors.push(ValidationError {
+                field: "token".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            });
+        }
+        if self.deploy_token().is_none() {
+            warnings.push(ValidationWarning {
+                field: "deployTokenEnv".to_string(),
+                message: tr!(
+                    "gemfury.private_verify_warning",
+                    token_env = self.deploy_token_env()
+                ),
+                severity: "warning".to_string(),
+            });
+        }
+
+        match self.format().await {
+            Ok(format) => {
+                metadata.insert(
+                    "format".to_string(),
+                    serde_json::Value::String(format.to_string()),
+                );
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "format".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let format = self.format().await?;
+        let (name, version) = self.package_identity().await?;
+        let out_dir = std::env::temp_dir().join(format!(
+            "package-publisher-gemfury-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let result = package_files(
+            &self.project_path,
+            format,
+            self.config.artifacts.as_deref(),
+            &out_dir,
+            &name,
+            &version,
+            &self.limits,
+        )
+        .await;
+        let result = match result {
+            Ok(files) => {
+                let mut lines = vec![format!(
+                    "{} package -> {}/{}/",
+                    format, PUSH_URL, self.config.account
+                )];
+                for file in &files {
+                    let size = fs::metadata(file).await.map(|m| m.len()).unwrap_or(0);
+                    lines.push(format!("  {} ({} bytes)", file.display(), size));
+                }
+                Ok(lines.join("\n"))
+            }
+            Err(e) => Err(e),
+        };
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        Ok(match result {
+            Ok(output) => DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            },
+            Err(e) => DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "artifacts".to_string(),
+                    message: tr!("registry.package_create_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            },
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let format = self.format().await?;
+        let (name, version) = self.package_identity().await?;
+        let token = self.push_token()?;
+        let out_dir = std::env::temp_dir().join(format!(
+            "package-publisher-gemfury-{}",
+            uuid::Uuid::new_v4()
+        ));
+
+        let result = async {
+            let files = package_files(
+                &self.project_path,
+                format,
+                self.config.artifacts.as_deref(),
+                &out_dir,
+                &name,
+                &version,
+                &self.limits,
+            )
+            .await?;
+            let mut pushed = Vec::new();
+            for file in files {
+                self.push(&file, &token).await?;
+                pushed.push(format!("Pushed {}", file.display()));
+            }
+            Ok::<_, anyhow::Error>(pushed)
+        }
+        .await;
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        match result {
+            Ok(pushed) => Ok(PublishResult {
+                success: true,
+                version: Some(version),
+                package_url: Some(format!(
+                    "https://manage.fury.io/dashboard/{}",
+                    self.config.account
+                )),
+                output: Some(pushed.join("\n")),
+                error: None,
+                metadata: None,
+                error_kind: None,
+            }),
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let format = self.format().await?;
+        let (name, version) = self.package_identity().await?;
+        let url = self.index_url(format, &name);
+
+        let mut request = http_client().get(&url);
+        if let Some(token) = self.deploy_token() {
+            request = match format {
+                HostedPackageFormat::Npm => request.bearer_auth(token),
+                _ => request.basic_auth(token, None::<&str>),
+            };
+        }
+
+        let error = match request.send_rate_limited().await {
+            Ok(response) if response.status().is_success() => {
+                let body = response.text().await.unwrap_or_default();
+                if index_lists_version(format, &body, &name, &version) {
+                    return Ok(VerificationResult {
+                        verified: true,
+                        version: Some(version),
+                        url: Some(url),
+                        error: None,
+                        metadata: None,
+                        integrity: None,
+                    });
+                }
+                tr!("gemfury.not_in_index", package = name, version = version)
+            }
+            Ok(response) => tr!("registry.index_fetch_status", status = response.status()),
+            Err(e) => tr!("registry.index_fetch_failed", error = e),
+        };
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(version),
+            url: Some(url),
+            error: Some(error),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_urls() {
+        assert_eq!(cargo_index_path("a"), "1/a");
+        assert_eq!(cargo_index_path("Abc"), "3/a/abc");
+        assert_eq!(cargo_index_path("serde"), "se/rd/serde");
+
+        let plugin = GemfuryPlugin::default().with_config(GemfuryRegistryConfig {
+            account: "acme".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            plugin.index_url(HostedPackageFormat::Npm, "@acme/app"),
+            "https://npm.fury.io/acme/@acme%2fapp"
+        );
+        assert_eq!(
+            plugin.index_url(HostedPackageFormat::Python, "My_App"),
+            "https://pypi.fury.io/acme/my-app/"
+        );
+    }
+
+    #[test]
+    fn test_index_lists_version() {
+        use HostedPackageFormat::*;
+
+        let npm = r#"{"name": "app", "versions": {"1.0.0": {}, "1.1.0": {}}}"#;
+        assert!(index_lists_version(Npm, npm, "app", "1.1.0"));
+        assert!(!index_lists_version(Npm, npm, "app", "2.0.0"));
+
+        let cargo =
+            "{\"name\":\"app\",\"vers\":\"0.1.0\"}\n{\"name\":\"app\",\"vers\":\"0.2.0\"}\n";
+        assert!(index_lists_version(Cargo, cargo, "app", "0.2.0"));
+        assert!(!index_lists_version(Cargo, cargo, "app", "0.3.0"));
+
+        let simple = r#"<a href="/acme/app-1.0.0-py3-none-any.whl">app-1.0.0-py3-none-any.whl</a>"#;
+        assert!(index_lists_version(Python, simple, "app", "1.0.0"));
+        assert!(!index_lists_version(Python, simple, "app", "1.0.1"));
+
+        let packages = "Package: app\nVersion: 1.0.0\n\nPackage: other\nVersion: 2.0.0\n";
+        assert!(index_lists_version(Deb, packages, "app", "1.0.0"));
+        assert!(!index_lists_version(Deb, packages, "app", "2.0.0"));
+    }
+}
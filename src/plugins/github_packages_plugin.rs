@@ -0,0 +1,889 @@
+//! GitHub Packages Plugin - GitHub Packages publishing preset
+//!
+//! This module provides GitHub Packages integration for the package types
+//! GitHub hosts alongside a repository:
+//! - Scoped npm packages (`https://npm.pkg.github.com`)
+//! - Maven artifacts (`https://maven.pkg.github.com/OWNER/REPO`)
+//! - Container images (`ghcr.io/OWNER/IMAGE`)
+//!
+//! All package types authenticate with `GITHUB_TOKEN` (configurable via
+//! `registries.githubPackages.tokenEnv`) and are verified against the
+//! GitHub Packages REST API.
+
+use crate::core::config::GitHubPackagesRegistryConfig;
+use crate::core::error::PublishErrorKind;
+use crate::core::github::GitHubClient;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::parse_github_repository;
+use crate::security::command_executor::{CI_IDENTITY_ENV, CommandLimits};
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// GitHub Packages npm registry URL
+pub const NPM_REGISTRY_URL: &str = "https://npm.pkg.github.com";
+
+/// GitHub Packages Maven registry host
+pub const MAVEN_REGISTRY_HOST: &str = "maven.pkg.github.com";
+
+/// GitHub Container Registry host
+pub const CONTAINER_REGISTRY_HOST: &str = "ghcr.io";
+
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Environment variables npm, Maven and Docker need on top of the sandbox
+/// allowlist
+const TOOL_ENV: &[&str] = &[
+    "NODE_*",
+    "NPM_CONFIG_*",
+    "npm_config_*",
+    "JAVA_HOME",
+    "MAVEN_*",
+    "M2_HOME",
+    "DOCKER_*",
+];
+
+/// Package type hosted on GitHub Packages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHubPackageKind {
+    Npm,
+    Maven,
+    Container,
+}
+
+impl GitHubPackageKind {
+    /// Package type used by the GitHub Packages REST API
+    pub fn api_type(&self) -> &'static str {
+        match self {
+            GitHubPackageKind::Npm => "npm",
+            GitHubPackageKind::Maven => "maven",
+            GitHubPackageKind::Container => "container",
+        }
+    }
+}
+
+/// Package coordinates resolved from the project manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubPackage {
+    pub kind: GitHubPackageKind,
+    /// Repository owner (user or organization)
+    pub owner: String,
+    /// Repository name (required for Maven)
+    pub repository: Option<String>,
+    /// Package name as published (npm: `@owner/name`, Maven: `group:artifact`, container: image)
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl GitHubPackage {
+    /// Registry URL to publish to
+    pub fn registry_url(&self) -> String {
+        match self.kind {
+            GitHubPackageKind::Npm => NPM_REGISTRY_URL.to_string(),
+            GitHubPackageKind::Maven => format!(
+                "https://{}/{}/{}",
+                MAVEN_REGISTRY_HOST,
+                self.owner,
+                self.repository.as_deref().unwrap_or_default()
+            ),
+            GitHubPackageKind::Container => format!(
+                "{}/{}/{}",
+                CONTAINER_REGISTRY_HOST,
+                self.owner.to_lowercase(),
+                self.name.to_lowercase()
+            ),
+        }
+    }
+
+    /// Package name as used by the GitHub Packages REST API
+    pub fn api_name(&self) -> String {
+        match self.kind {
+            // npm packages are listed without their scope
+            GitHubPackageKind::Npm => self
+                .name
+                .split_once('/')
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| self.name.clone()),
+            // Maven packages are listed as groupId.artifactId
+            GitHubPackageKind::Maven => self.name.replace(':', "."),
+            GitHubPackageKind::Container => self.name.to_lowercase(),
+        }
+    }
+
+    /// Package page on github.com
+    pub fn package_url(&self) -> String {
+        let api_name = self.api_name().replace('/', "%2F");
+        match self.repository {
+            Some(ref repo) => format!(
+                "https://github.com/{}/{}/pkgs/{}/{}",
+                self.owner,
+                repo,
+                self.kind.api_type(),
+                api_name
+            ),
+            None => format!(
+                "https://github.com/{}/packages/{}/package/{}",
+                self.owner,
+                self.kind.api_type(),
+                api_name
+            ),
+        }
+    }
+}
+
+/// Package version returned by `GET /{users|orgs}/{owner}/packages/{type}/{name}/versions`
+#[derive(Debug, Deserialize)]
+struct PackageVersion {
+    name: String,
+    #[serde(default)]
+    html_url: Option<String>,
+    #[serde(default)]
+    metadata: Option<PackageVersionMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageVersionMetadata {
+    #[serde(default)]
+    container: Option<ContainerMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContainerMetadata {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl PackageVersion {
+    /// Whether this entry is the given version (container versions are digests, matched by tag)
+    fn matches(&self, version: &str) -> bool {
+        self.name == version
+            || self
+                .metadata
+                .as_ref()
+                .and_then(|m| m.container.as_ref())
+                .is_some_and(|c| c.tags.iter().any(|t| t == version))
+    }
+}
+
+/// GitHub Packages registry plugin
+pub struct GitHubPackagesPlugin {
+    project_path: PathBuf,
+    config: GitHubPackagesRegistryConfig,
+    limits: CommandLimits,
+    github: GitHubClient,
+}
+
+impl Default for GitHubPackagesPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl GitHubPackagesPlugin {
+    /// Create a new GitHub Packages plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: GitHubPackagesRegistryConfig::default(),
+            limits: CommandLimits::default(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Apply `registries.githubPackages` settings
+    pub fn with_config(mut self, config: GitHubPackagesRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    /// Run npm, Maven and Docker with the sandbox and timeout of registry
+    /// commands (their own variables and the CI identity, e.g.
+    /// `GITHUB_ACTOR`, stay available)
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV).allowing(CI_IDENTITY_ENV);
+        self
+    }
+
+    /// Environment variable holding the token
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    fn token(&self) -> Option<String> {
+        std::env::var(self.token_env())
+            .ok()
+            .filter(|t| !t.is_empty())
+    }
+
+    /// Resolve the package from package.json, pom.xml or Dockerfile (in that order)
+    pub async fn resolve_package(&self) -> anyhow::Result<GitHubPackage> {
+        let mut package = if let Ok(content) =
+            fs::read_to_string(self.project_path.join("package.json")).await
+            && let Some(package) = parse_npm_package(&content)
+        {
+            package
+        } else if let Ok(content) = fs::read_to_string(self.project_path.join("pom.xml")).await
+            && let Some(package) = parse_maven_package(&content)
+        {
+            package
+        } else if let Ok(content) = fs::read_to_string(self.project_path.join("Dockerfile")).await
+            && let Some(package) = parse_container_package(&content)
+        {
+            package
+        } else {
+            anyhow::bail!("{}", tr!("github_packages.manifest_not_found"));
+        };
+
+        // Config overrides what the manifest declares
+        if let Some(ref owner) = self.config.owner {
+            package.owner = owner.clone();
+        }
+        if let Some(ref repository) = self.config.repository {
+            package.repository = Some(repository.clone());
+        }
+        if package.kind == GitHubPackageKind::Container
+            && let Some(ref image) = self.config.image
+        {
+            package.name = image.clone();
+        }
+
+        Ok(package)
+    }
+
+    /// Run a command in the project directory, optionally piping stdin
+    async fn run_command(
+        &self,
+        program: &str,
+        args: &[&str],
+        envs: &[(&str, &str)],
+        stdin: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut command = self.limits.command(program);
+        command
+            .args(args)
+            .envs(envs.iter().copied())
+            .current_dir(&self.project_path);
+        let output = match stdin {
+            Some(input) => {
+                self.limits
+                    .output_with_stdin(&mut command, input.as_bytes())
+                    .await?
+            }
+            None => self.limits.output(&mut command).await?,
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}", stderr);
+        }
+
+        Ok(stdout + &stderr)
+    }
+
+    /// Maven settings.xml with a `github` server that reads credentials from the environment
+    fn maven_settings(&self) -> String {
+        format!(
+            r#"<settings>
+  <servers>
+    <server>
+      <id>github</id>
+      <username>${{env.GITHUB_ACTOR}}</username>
+      <password>${{env.{}}}</password>
+    </server>
+  </servers>
+</settings>
+"#,
+            self.token_env()
+        )
+    }
+
+    async fn publish_npm(
+        &self,
+        package: &GitHubPackage,
+        token: &str,
+        tag: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut args = vec!["publish", "--registry", NPM_REGISTRY_URL];
+        if let Some(tag) = tag {
+            args.push("--tag");
+            args.push(tag);
+        }
+
+        let auth_key = format!(
+            "npm_config_{}/:_authToken",
+            NPM_REGISTRY_URL.trim_start_matches("https:")
+        );
+        let output = self
+            .run_command("npm", &args, &[(auth_key.as_str(), token)], None)
+            .await?;
+        Ok(format!("{}\n{}", package.name, output))
+    }
+
+    async fn publish_maven(&self, package: &GitHubPackage, token: &str) -> anyhow::Result<String> {
+        if package.repository.is_none() {
+            anyhow::bail!("{}", tr!("github_packages.maven_repository_required"));
+        }
+
+        let settings_path = std::env::temp_dir().join(format!(
+            "package-publisher-maven-{}.xml",
+            std::process::id()
+        ));
+        fs::write(&settings_path, self.maven_settings()).await?;
+
+        let settings = settings_path.display().to_string();
+        let deployment = format!(
+            "-DaltDeploymentRepository=github::{}",
+            package.registry_url()
+        );
+        let result = self
+            .run_command(
+                "mvn",
+                &["--batch-mode", "-s", &settings, "deploy", &deployment],
+                // settings.xml reads the token from the environment
+                &[(self.token_env(), token)],
+                None,
+            )
+            .await;
+
+        let _ = fs::remove_file(&settings_path).await;
+        result
+    }
+
+    async fn publish_container(
+        &self,
+        package: &GitHubPackage,
+        token: &str,
+    ) -> anyhow::Result<String> {
+        let image = package.registry_url();
+        let tag = package.version.as_deref().unwrap_or("latest");
+        let reference = format!("{}:{}", image, tag);
+        let username = std::env::var("GITHUB_ACTOR").unwrap_or_else(|_| package.owner.clone());
+
+        let mut output = self
+            .run_command(
+                "docker",
+                &[
+                    "login",
+                    CONTAINER_REGISTRY_HOST,
+                    "--username",
+                    &username,
+                    "--password-stdin",
+                ],
+                &[],
+                Some(token),
+            )
+            .await?;
+        output += &self
+            .run_command("docker", &["build", "-t", &reference, "."], &[], None)
+            .await?;
+        output += &self
+            .run_command("docker", &["push", &reference], &[], None)
+            .await?;
+
+        Ok(output)
+    }
+
+    /// Fetch published versions from the REST API (organization first, then user)
+    async fn fetch_versions(
+        &self,
+        package: &GitHubPackage,
+        token: &str,
+    ) -> anyhow::Result<Vec<PackageVersion>> {
+        let github = self.github.authenticated(token);
+        let name = urlencoding_path(&package.api_name());
+        let mut last_status = None;
+
+        for scope in ["orgs", "users"] {
+            let path = format!(
+                "/{}/{}/packages/{}/{}/versions",
+                scope,
+                package.owner,
+                package.kind.api_type(),
+                name
+            );
+            let response = github
+                .request(reqwest::Method::GET, &path)
+                .header("X-GitHub-Api-Version", "2022-11-28")
+                .send_rate_limited()
+                .await?;
+
+            if response.status().is_success() {
+                return Ok(response.json::<Vec<PackageVersion>>().await?);
+            }
+            last_status = Some(response.status());
+        }
+
+        anyhow::bail!(
+            "{}",
+            tr!(
+                "github_packages.package_not_found",
+                package = package.name,
+                status = last_status.map(|s| s.to_string()).unwrap_or_default()
+            )
+        )
+    }
+}
+
+/// Percent-encode `/` so nested container image names fit in a single path segment
+fn urlencoding_path(name: &str) -> String {
+    name.replace('/', "%2F")
+}
+
+/// Scoped npm package whose publishConfig points at GitHub Packages
+fn parse_npm_package(content: &str) -> Option<GitHubPackage> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let registry = json
+        .get("publishConfig")
+        .and_then(|p| p.get("registry"))
+        .and_then(|r| r.as_str())?;
+    if !registry.contains("npm.pkg.github.com") {
+        return None;
+    }
+
+    let name = json.get("name").and_then(|n| n.as_str())?;
+    let owner = name.strip_prefix('@').and_then(|n| n.split_once('/'))?.0;
+    let repository = json
+        .get("repository")
+        .and_then(|r| r.as_str().or_else(|| r.get("url").and_then(|u| u.as_str())))
+        .and_then(parse_github_repository)
+        .and_then(|r| r.split_once('/').map(|(_, repo)| repo.to_string()));
+
+    Some(GitHubPackage {
+        kind: GitHubPackageKind::Npm,
+        owner: owner.to_string(),
+        repository,
+        name: name.to_string(),
+        version: json
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// Maven project whose distributionManagement points at GitHub Packages
+fn parse_maven_package(content: &str) -> Option<GitHubPackage> {
+    let url = Regex::new(r"maven\.pkg\.github\.com/([^/<\s]+)/([^/<\s]+)").unwrap();
+    let caps = url.captures(content)?;
+
+    // Coordinates inherited from <parent> must not be mistaken for the project's own
+    let parent = Regex::new(r"(?s)<parent>.*?</parent>").unwrap();
+    let own = parent.replace(content, "");
+    let element = |tag: &str| {
+        Regex::new(&format!(r"<{0}>\s*([^<]+?)\s*</{0}>", tag))
+            .unwrap()
+            .captures(&own)
+            .map(|c| c[1].to_string())
+    };
+
+    let group_id = element("groupId").or_else(|| {
+        Regex::new(r"(?s)<parent>.*?<groupId>\s*([^<]+?)\s*</groupId>")
+            .unwrap()
+            .captures(content)
+            .map(|c| c[1].to_string())
+    })?;
+    let artifact_id = element("artifactId")?;
+
+    Some(GitHubPackage {
+        kind: GitHubPackageKind::Maven,
+        owner: caps[1].to_string(),
+        repository: Some(caps[2].to_string()),
+        name: format!("{}:{}", group_id, artifact_id),
+        version: element("version"),
+    })
+}
+
+/// Container image linked to a GitHub repository via the OCI source label
+fn parse_container_package(content: &str) -> Option<GitHubPackage> {
+    let label = |key: &str| {
+        Regex::new(&format!(
+            r#"(?m)^\s*LABEL\s+.*\borg\.opencontainers\.image\.{}=["']?([^"'\s]+)"#,
+            key
+        ))
+        .unwrap()
+        .captures(content)
+        .map(|c| c[1].to_string())
+    };
+
+    let source = parse_github_repository(&label("source")?)?;
+    let (owner, repository) = source.split_once('/')?;
+
+    Some(GitHubPackage {
+        kind: GitHubPackageKind::Container,
+        owner: owner.to_string(),
+        name: repository.to_lowercase(),
+        repository: Some(repository.to_string()),
+        version: label("version"),
+    })
+}
+
+#[async_trait]
+impl RegistryPlugin for GitHubPackagesPlugin {
+    fn name(&self) -> &str {
+        "github-packages"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        let plugin = GitHubPackagesPlugin::new(Path::new(project_path).to_path_buf());
+        Ok(plugin.resolve_package().await.is_ok())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let package = self.resolve_package().await?;
+
+        if package.kind == GitHubPackageKind::Npm && !package.name.starts_with('@') {
+            errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("github_packages.npm_scope_required"),
+                severity: "error".to_string(),
+            });
+        }
+
+        if package.kind == GitHubPackageKind::Maven && package.repository.is_none() {
+            errors.push(ValidationError {
+                field: "distributionManagement".to_string(),
+                message: tr!("github_packages.maven_url_no_repository"),
+                severity: "error".to_string(),
+            });
+        }
+
+        match package.version {
+            Some(ref version) => {
+                metadata.insert(
+                    "version".to_string(),
+                    serde_json::Value::String(version.clone()),
+                );
+            }
+            None if package.kind == GitHubPackageKind::Container => {
+                warnings.push(ValidationWarning {
+                    field: "org.opencontainers.image.version".to_string(),
+                    message: tr!("github_packages.latest_tag"),
+                    severity: "warning".to_string(),
+                });
+            }
+            None => {
+                errors.push(ValidationError {
+                    field: "version".to_string(),
+                    message: tr!("registry.version_required"),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        if self.token().is_none() {
+            errors.push(ValidationError {
+                field: "auth".to_string(),
+                message: tr!(
+                    "github_packages.token_missing",
+                    token_env = self.token_env()
+                ),
+                severity: "error".to_string(),
+            });
+        }
+
+        metadata.insert(
+            "packageName".to_string(),
+            serde_json::Value::String(package.name.clone()),
+        );
+        metadata.insert(
+            "packageType".to_string(),
+            serde_json::Value::String(package.kind.api_type().to_string()),
+        );
+        metadata.insert(
+            "registryUrl".to_string(),
+            serde_json::Value::String(package.registry_url()),
+        );
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let package = self.resolve_package().await?;
+
+        let result = match package.kind {
+            GitHubPackageKind::Npm => {
+                self.run_command(
+                    "npm",
+                    &["publish", "--dry-run", "--registry", NPM_REGISTRY_URL],
+                    &[],
+                    None,
+                )
+                .await
+            }
+            GitHubPackageKind::Maven => {
+                self.run_command("mvn", &["--batch-mode", "verify"], &[], None)
+                    .await
+            }
+            GitHubPackageKind::Container => {
+                self.run_command("docker", &["build", "."], &[], None).await
+            }
+        };
+
+        let target = format!(
+            "{} → {}",
+            package.name,
+            match package.kind {
+                GitHubPackageKind::Container => format!(
+                    "{}:{}",
+                    package.registry_url(),
+                    package.version.as_deref().unwrap_or("latest")
+                ),
+                _ => package.registry_url(),
+            }
+        );
+
+        match result {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output: format!("{}\n{}", target, output),
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "publish".to_string(),
+                    message: tr!("registry.dry_run_failed", output = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let opts = options.unwrap_or_default();
+        let package = self.resolve_package().await?;
+
+        let Some(token) = self.token() else {
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!(
+                    "github_packages.auth_failed",
+                    token_env = self.token_env()
+                )),
+                metadata: None,
+                error_kind: Some(PublishErrorKind::AuthFailed),
+            });
+        };
+
+        let result = match package.kind {
+            GitHubPackageKind::Npm => {
+                self.publish_npm(&package, &token, opts.tag.as_deref())
+                    .await
+            }
+            GitHubPackageKind::Maven => self.publish_maven(&package, &token).await,
+            GitHubPackageKind::Container => self.publish_container(&package, &token).await,
+        };
+
+        match result {
+            Ok(output) => Ok(PublishResult {
+                success: true,
+                version: package.version.clone(),
+                package_url: Some(package.package_url()),
+                output: Some(output),
+                error: None,
+                metadata: Some(HashMap::from([(
+                    "registryUrl".to_string(),
+                    serde_json::Value::String(package.registry_url()),
+                )])),
+                error_kind: None,
+            }),
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let package = self.resolve_package().await?;
+        let expected_version = package
+            .version
+            .clone()
+            .unwrap_or_else(|| "latest".to_string());
+        let url = package.package_url();
+
+        let Some(token) = self.token() else {
+            return Ok(VerificationResult {
+                verified: false,
+                version: Some(expected_version),
+                url: Some(url),
+                error: Some(tr!(
+                    "github_packages.verify_token_missing",
+                    token_env = self.token_env()
+                )),
+                metadata: None,
+                integrity: None,
+            });
+        };
+
+        match self.fetch_versions(&package, &token).await {
+            Ok(versions) => match versions.iter().find(|v| v.matches(&expected_version)) {
+                Some(published) => Ok(VerificationResult {
+                    verified: true,
+                    version: Some(expected_version),
+                    url: Some(published.html_url.clone().unwrap_or(url)),
+                    error: None,
+                    metadata: Some(HashMap::from([(
+                        "versionId".to_string(),
+                        serde_json::Value::String(published.name.clone()),
+                    )])),
+                    integrity: None,
+                }),
+                None => Ok(VerificationResult {
+                    verified: false,
+                    version: Some(expected_version.clone()),
+                    url: Some(url),
+                    error: Some(tr!(
+                        "github_packages.version_not_found",
+                        version = expected_version
+                    )),
+                    metadata: None,
+                    integrity: None,
+                }),
+            },
+            Err(e) => Ok(VerificationResult {
+                verified: false,
+                version: Some(expected_version),
+                url: Some(url),
+                error: Some(tr!("registry.verify_failed", error = e)),
+                metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::command_executor::EnvSandbox;
+
+    #[test]
+    fn test_parse_npm_package() {
+        let content = r#"{
+            "name": "@octo-org/widgets",
+            "version": "1.2.0",
+            "repository": { "type": "git", "url": "git+https://github.com/octo-org/widgets.git" },
+            "publishConfig": { "registry": "https://npm.pkg.github.com" }
+        }"#;
+
+        let package = parse_npm_package(content).unwrap();
+        assert_eq!(package.kind, GitHubPackageKind::Npm);
+        assert_eq!(package.owner, "octo-org");
+        assert_eq!(package.repository.as_deref(), Some("widgets"));
+        assert_eq!(package.api_name(), "widgets");
+        assert_eq!(package.registry_url(), NPM_REGISTRY_URL);
+
+        // Unscoped or npmjs.org packages are not GitHub Packages
+        assert!(parse_npm_package(r#"{"name": "widgets", "publishConfig": {"registry": "https://npm.pkg.github.com"}}"#).is_none());
+        assert!(parse_npm_package(r#"{"name": "@octo-org/widgets"}"#).is_none());
+    }
+
+    #[test]
+    fn test_parse_maven_package() {
+        let content = r#"
+<project>
+  <parent>
+    <groupId>org.parent</groupId>
+    <artifactId>parent</artifactId>
+    <version>9.0</version>
+  </parent>
+  <groupId>com.octo</groupId>
+  <artifactId>widgets</artifactId>
+  <version>2.0.1</version>
+  <distributionManagement>
+    <repository>
+      <id>github</id>
+      <url>https://maven.pkg.github.com/octo-org/widgets</url>
+    </repository>
+  </distributionManagement>
+</project>
+"#;
+
+        let package = parse_maven_package(content).unwrap();
+        assert_eq!(package.name, "com.octo:widgets");
+        assert_eq!(package.version.as_deref(), Some("2.0.1"));
+        assert_eq!(package.api_name(), "com.octo.widgets");
+        assert_eq!(
+            package.registry_url(),
+            "https://maven.pkg.github.com/octo-org/widgets"
+        );
+    }
+
+    #[test]
+    fn test_parse_container_package() {
+        let content = r#"FROM alpine:3.20
+LABEL org.opencontainers.image.source="https://github.com/Octo-Org/Widgets"
+LABEL org.opencontainers.image.version="3.1.0"
+"#;
+
+        let package = parse_container_package(content).unwrap();
+        assert_eq!(package.kind, GitHubPackageKind::Container);
+        assert_eq!(package.version.as_deref(), Some("3.1.0"));
+        assert_eq!(package.registry_url(), "ghcr.io/octo-org/widgets");
+        assert!(parse_container_package("FROM alpine:3.20\n").is_none());
+    }
+
+    #[test]
+    fn test_container_version_matches_tag() {
+        let version: PackageVersion = serde_json::from_str(
+            r#"{"name": "sha256:abc", "metadata": {"package_type": "container", "container": {"tags": ["3.1.0", "latest"]}}}"#,
+        )
+        .unwrap();
+        assert!(version.matches("3.1.0"));
+        assert!(!version.matches("3.0.0"));
+    }
+
+    #[test]
+    fn test_command_limits_keep_ci_identity() {
+        let limits = CommandLimits::new(
+            Some(EnvSandbox::new::<&str>(&[])),
+            std::time::Duration::from_secs(60),
+        );
+        let plugin = GitHubPackagesPlugin::new(PathBuf::from(".")).with_command_limits(limits);
+        assert!(plugin.limits.allows("GITHUB_ACTOR"));
+        assert!(plugin.limits.allows("JAVA_HOME"));
+        assert!(!plugin.limits.allows("CARGO_REGISTRY_TOKEN"));
+    }
+}
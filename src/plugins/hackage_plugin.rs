@@ -0,0 +1,532 @@
+//! Hackage Plugin - Haskell package publishing to hackage.haskell.org
+//!
+//! Provides Hackage publishing support:
+//! - `.cabal` detection and validation of the package description fields
+//! - `cabal check` and `cabal sdist` as the dry-run
+//! - `cabal upload --publish` (plus optional Haddock documentation upload)
+//! - Verification against the Hackage package version list
+
+use crate::core::config::HackageRegistryConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const HACKAGE_URL: &str = "https://hackage.haskell.org";
+
+const DEFAULT_TOKEN_ENV: &str = "HACKAGE_TOKEN";
+
+/// Parse the top-level fields of a `.cabal` file
+///
+/// Field names are lower-cased; indented continuation lines are joined to the
+/// previous field and component stanzas (`library`, `executable ...`) are skipped.
+pub fn parse_cabal(content: &str) -> HashMap<String, String> {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("--") {
+            continue;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            // A lone "." is a paragraph break in free-text fields
+            if let Some(ref key) = current
+                && let Some(value) = fields.get_mut(key)
+                && trimmed != "."
+            {
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(trimmed);
+            }
+            continue;
+        }
+
+        match line.split_once(':') {
+            Some((key, value)) if !key.contains(char::is_whitespace) => {
+                let key = key.trim().to_lowercase();
+                fields.insert(key.clone(), value.trim().to_string());
+                current = Some(key);
+            }
+            // Stanza header: its indented fields belong to a component
+            _ => current = None,
+        }
+    }
+
+    fields
+}
+
+/// Hackage preferred versions (`/package/<name>/preferred`)
+#[derive(Debug, Deserialize)]
+struct PreferredVersions {
+    #[serde(rename = "normal-version", default)]
+    normal_version: Vec<String>,
+    #[serde(rename = "deprecated-version", default)]
+    deprecated_version: Vec<String>,
+}
+
+/// Environment variables cabal needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["CABAL_*", "GHC_*"];
+
+/// Hackage plugin
+pub struct HackagePlugin {
+    project_path: PathBuf,
+    config: HackageRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for HackagePlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl HackagePlugin {
+    /// Create a new Hackage plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: HackageRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.hackage` settings
+    pub fn with_config(mut self, config: HackageRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run cabal with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Find the `.cabal` file in the project root
+    pub(crate) async fn find_cabal_file(project_path: &Path) -> Option<PathBuf> {
+        let mut entries = fs::read_dir(project_path).await.ok()?;
+        let mut found = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "cabal") {
+                found.push(path);
+            }
+        }
+        found.sort();
+        found.into_iter().next()
+    }
+
+    async fn load_cabal(&self) -> anyhow::Result<HashMap<String, String>> {
+        let path = Self::find_cabal_file(&self.project_path)
+            .await
+            .ok_or_else(|| anyhow::anyhow!(".cabal file not found"))?;
+        let content = fs::read_to_string(path).await?;
+        Ok(parse_cabal(&content))
+    }
+
+    fn token(&self) -> Option<String> {
+        let env = self
+            .config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV);
+        std::env::var(env).ok().filter(|t| !t.is_empty())
+    }
+
+    /// Validate package name (alphanumeric words separated by hyphens, each with a letter)
+    fn validate_package_name(&self, name: &str) -> Vec<ValidationError> {
+        let word = Regex::new(r"^[A-Za-z0-9]*[A-Za-z][A-Za-z0-9]*$").unwrap();
+        if !name.is_empty() && name.split('-').all(|w| word.is_match(w)) {
+            Vec::new()
+        } else {
+            vec![ValidationError {
+                field: "name".to_string(),
+                message: tr!("hackage.invalid_name", name = name),
+                severity: "error".to_string(),
+            }]
+        }
+    }
+
+    /// Validate version (numeric components, PVP)
+    fn validate_version(&self, version: &str) -> Vec<ValidationError> {
+        let valid = Regex::new(r"^\d+(\.\d+)*$").unwrap();
+        if valid.is_match(version) {
+            Vec::new()
+        } else {
+            vec![ValidationError {
+                field: "version".to_string(),
+                message: tr!("hackage.invalid_version", version = version),
+                severity: "error".to_string(),
+            }]
+        }
+    }
+
+    async fn run_cabal(&self, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = self.limits.command("cabal");
+        command.args(args).current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}{}", stdout, stderr);
+        }
+
+        Ok(stdout + &stderr)
+    }
+
+    /// `cabal upload --publish` arguments, with the token when configured
+    fn upload_args<'a>(&self, token: Option<&'a str>, documentation: bool) -> Vec<&'a str> {
+        let mut args = vec!["upload", "--publish"];
+        if documentation {
+            args.push("--documentation");
+        }
+        if let Some(token) = token {
+            args.extend(["--token", token]);
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for HackagePlugin {
+    fn name(&self) -> &str {
+        "hackage"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        Ok(Self::find_cabal_file(Path::new(project_path))
+            .await
+            .is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let cabal = self.load_cabal().await?;
+
+        for field in [
+            "cabal-version",
+            "name",
+            "version",
+            "synopsis",
+            "license",
+            "maintainer",
+        ] {
+            if cabal.get(field).is_none_or(|v| v.is_empty()) {
+                errors.push(ValidationError {
+                    field: field.to_string(),
+                    message: tr!("registry.field_required", field = field),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        if let Some(name) = cabal.get("name") {
+            errors.extend(self.validate_package_name(name));
+            metadata.insert(
+                "packageName".to_string(),
+                serde_json::Value::String(name.clone()),
+            );
+        }
+
+        if let Some(version) = cabal.get("version") {
+            errors.extend(self.validate_version(version));
+            metadata.insert(
+                "version".to_string(),
+                serde_json::Value::String(version.clone()),
+            );
+        }
+
+        if cabal
+            .get("license")
+            .is_some_and(|l| l == "AllRightsReserved")
+        {
+            errors.push(ValidationError {
+                field: "license".to_string(),
+                message: tr!("hackage.all_rights_reserved"),
+                severity: "error".to_string(),
+            });
+        }
+
+        for (field, key) in [
+            ("description", "hackage.description_recommended"),
+            ("category", "hackage.category_recommended"),
+            ("homepage", "hackage.homepage_recommended"),
+        ] {
+            let present = cabal.contains_key(field)
+                || (field == "homepage" && cabal.contains_key("bug-reports"));
+            if !present {
+                warnings.push(ValidationWarning {
+                    field: field.to_string(),
+                    message: tr!(key),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+
+        if self.token().is_none() {
+            warnings.push(ValidationWarning {
+                field: "token".to_string(),
+                message: tr!(
+                    "hackage.token_missing",
+                    token_env = self
+                        .config
+                        .token_env
+                        .as_deref()
+                        .unwrap_or(DEFAULT_TOKEN_ENV)
+                ),
+                severity: "warning".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        if let Err(e) = self.run_cabal(&["check"]).await {
+            return Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "check".to_string(),
+                    message: tr!("hackage.check_failed"),
+                    severity: "error".to_string(),
+                }]),
+            });
+        }
+
+        match self.run_cabal(&["sdist"]).await {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "sdist".to_string(),
+                    message: tr!("hackage.sdist_failed"),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let cabal = self.load_cabal().await?;
+        let name = cabal
+            .get("name")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+        let version = cabal
+            .get("version")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+        let token = self.token();
+
+        if let Err(e) = self.run_cabal(&["sdist"]).await {
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("hackage.sdist_error", error = e)),
+                metadata: None,
+                error_kind: None,
+            });
+        }
+
+        let sdist = format!("dist-newstyle/sdist/{}-{}.tar.gz", name, version);
+        let mut args = self.upload_args(token.as_deref(), false);
+        args.push(&sdist);
+
+        let mut output = match self.run_cabal(&args).await {
+            Ok(output) => output,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!("hackage.upload_failed", error = e)),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+        };
+
+        // Documentation upload is best-effort: the package is already published
+        let mut metadata = HashMap::new();
+        if self.config.docs.unwrap_or(false) {
+            let docs = format!("dist-newstyle/{}-{}-docs.tar.gz", name, version);
+            let mut args = self.upload_args(token.as_deref(), true);
+            args.push(&docs);
+
+            let uploaded = match self
+                .run_cabal(&["haddock", "--haddock-for-hackage", "--enable-documentation"])
+                .await
+            {
+                Ok(_) => self.run_cabal(&args).await,
+                Err(e) => Err(e),
+            };
+            match uploaded {
+                Ok(docs_output) => {
+                    output.push_str(&docs_output);
+                    metadata.insert("documentation".to_string(), serde_json::Value::Bool(true));
+                }
+                Err(e) => {
+                    output.push_str(&tr!("hackage.docs_upload_failed", error = e));
+                    metadata.insert("documentation".to_string(), serde_json::Value::Bool(false));
+                }
+            }
+        }
+
+        Ok(PublishResult {
+            success: true,
+            version: Some(version.clone()),
+            package_url: Some(format!("{}/package/{}-{}", HACKAGE_URL, name, version)),
+            output: Some(output),
+            error: None,
+            metadata: (!metadata.is_empty()).then_some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let cabal = self.load_cabal().await?;
+        let name = cabal
+            .get("name")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+        let expected_version = cabal
+            .get("version")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+        let url = format!("{}/package/{}-{}", HACKAGE_URL, name, expected_version);
+
+        let response = http_client()
+            .get(format!("{}/package/{}/preferred", HACKAGE_URL, name))
+            .header("Accept", "application/json")
+            .send_rate_limited()
+            .await;
+
+        let versions = match response {
+            Ok(response) if response.status().is_success() => {
+                response.json::<PreferredVersions>().await.ok()
+            }
+            Ok(_) => None,
+            Err(e) => {
+                return Ok(VerificationResult {
+                    verified: false,
+                    version: Some(expected_version),
+                    url: Some(url),
+                    error: Some(tr!("registry.verify_failed", error = e)),
+                    metadata: None,
+                    integrity: None,
+                });
+            }
+        };
+
+        let published = versions.is_some_and(|v| {
+            v.normal_version.contains(&expected_version)
+                || v.deprecated_version.contains(&expected_version)
+        });
+
+        if published {
+            Ok(VerificationResult {
+                verified: true,
+                version: Some(expected_version),
+                url: Some(url),
+                error: None,
+                metadata: None,
+                integrity: None,
+            })
+        } else {
+            Ok(VerificationResult {
+                verified: false,
+                version: Some(expected_version.clone()),
+                url: Some(url),
+                error: Some(tr!("hackage.version_not_found", version = expected_version)),
+                metadata: None,
+                integrity: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CABAL: &str = r#"cabal-version:      3.0
+name:               acme-widget
+version:            0.1.2.0
+synopsis:           Widgets for everyone
+description:
+    A longer description
+    .
+    spanning lines.
+License:            BSD-3-Clause
+maintainer:         dev@example.com
+
+-- a comment
+library
+    exposed-modules:  Acme.Widget
+    build-depends:    base >=4.14 && <5
+"#;
+
+    #[test]
+    fn test_parse_cabal() {
+        let fields = parse_cabal(CABAL);
+        assert_eq!(fields.get("name").map(String::as_str), Some("acme-widget"));
+        assert_eq!(
+            fields.get("license").map(String::as_str),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            fields.get("description").map(String::as_str),
+            Some("A longer description spanning lines.")
+        );
+        assert!(!fields.contains_key("exposed-modules"));
+    }
+
+    #[test]
+    fn test_validate_name_and_version() {
+        let plugin = HackagePlugin::default();
+        assert!(plugin.validate_package_name("acme-widget2").is_empty());
+        assert!(!plugin.validate_package_name("acme-2").is_empty());
+        assert!(!plugin.validate_package_name("acme_widget").is_empty());
+        assert!(plugin.validate_version("0.1.2.0").is_empty());
+        assert!(!plugin.validate_version("1.0.0-beta").is_empty());
+    }
+}
@@ -5,21 +5,33 @@
 //! - Formula metadata parsing
 //! - Tap repository management
 //! - Git-based publishing workflow
-//! - Dry-run validation
+//! - Dry-run validation (`brew test` in a temporary tap when brew is available)
+//! - `test do` block generation for formulas without one
 //! - Formula verification via brew info
-
+//! - Rollback by reverting the formula commit
+//! - Formula generation/update from a release tarball (`autoUpdate`)
+//! - Pull requests to the tap instead of pushing to its branch (`pullRequest`)
+
+use crate::core::config::{HomebrewPullRequestConfig, HomebrewRegistryConfig};
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
 use crate::core::traits::{
-    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
-    ValidationWarning, VerificationResult,
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, RollbackResult, ValidationError,
+    ValidationResult, ValidationWarning, VerificationResult,
 };
+use crate::orchestration::changelog::ChangelogGenerator;
+use crate::orchestration::release::parse_github_repository;
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
 use async_trait::async_trait;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use tokio::fs;
-use tokio::process::Command;
 
 /// Formula metadata structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,13 +43,43 @@ pub struct FormulaMetadata {
     pub homepage: Option<String>,
     pub description: Option<String>,
     pub license: Option<String>,
+    /// Whether the formula defines a `test do` block
+    #[serde(default)]
+    pub has_test: bool,
+}
+
+/// Result of [`HomebrewPlugin::update_formula`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormulaUpdate {
+    pub path: PathBuf,
+    pub version: String,
+    pub url: String,
+    pub sha256: String,
+    /// The formula did not exist and was generated
+    pub created: bool,
+    /// The formula content changed
+    pub changed: bool,
+}
+
+/// Where a tap pull request is opened
+struct TapPullRequest {
+    repository: String,
+    token: String,
+    base: Option<String>,
 }
 
+/// Environment variables Homebrew needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["HOMEBREW_*"];
+
 /// Homebrew tap plugin
 pub struct HomebrewPlugin {
     project_path: PathBuf,
     formula_path: Option<PathBuf>,
     formula_metadata: Option<FormulaMetadata>,
+    config: HomebrewRegistryConfig,
+    github: GitHubClient,
+    limits: CommandLimits,
 }
 
 impl Default for HomebrewPlugin {
@@ -53,9 +95,228 @@ impl HomebrewPlugin {
             project_path,
             formula_path: None,
             formula_metadata: None,
+            config: HomebrewRegistryConfig::default(),
+            github: GitHubClient::new(),
+            limits: CommandLimits::default(),
         }
     }
 
+    /// Use a different GitHub API endpoint (GitHub Enterprise, tests)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    /// Apply registry-specific settings
+    pub fn with_config(mut self, config: HomebrewRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run brew with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Point the formula at a release tarball
+    ///
+    /// Downloads `url`, computes its sha256 and rewrites the `url`, `sha256`
+    /// and `version` stanzas of the formula. When the project has no formula
+    /// yet, `Formula/<name>.rb` is generated. The file is written but not
+    /// committed; `publish` commits and pushes it.
+    pub async fn update_formula(&self, version: &str, url: &str) -> anyhow::Result<FormulaUpdate> {
+        let sha256 = Self::fetch_sha256(url).await?;
+
+        let mut plugin = HomebrewPlugin::new(self.project_path.clone());
+        plugin.find_formula_file().await?;
+
+        let (path, current, content) = match plugin.formula_path {
+            Some(path) => {
+                let current = fs::read_to_string(&path).await?;
+                let content = Self::rewrite_formula(&current, url, &sha256, version)?;
+                (path, Some(current), content)
+            }
+            None => {
+                let name = self.generated_formula_name();
+                let formula_dir = self.project_path.join("Formula");
+                fs::create_dir_all(&formula_dir).await?;
+                let content = Self::generate_formula(&name, url, &sha256, version);
+                (formula_dir.join(format!("{}.rb", name)), None, content)
+            }
+        };
+
+        let changed = current.as_deref() != Some(content.as_str());
+        if changed {
+            fs::write(&path, &content).await?;
+        }
+
+        Ok(FormulaUpdate {
+            path,
+            version: version.to_string(),
+            url: url.to_string(),
+            sha256,
+            created: current.is_none(),
+            changed,
+        })
+    }
+
+    /// Download a release tarball and return its hex sha256
+    pub(crate) async fn fetch_sha256(url: &str) -> anyhow::Result<String> {
+        let response = http_client().get(url).send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "homebrew.archive_fetch_failed",
+                    status = response.status(),
+                    url = url
+                )
+            );
+        }
+        Ok(hex::encode(Sha256::digest(&response.bytes().await?)))
+    }
+
+    /// Replace the `url`, `sha256` and `version` stanzas of a formula
+    ///
+    /// Only the first (top-level) stanza of each kind is touched, so
+    /// `resource` blocks keep their own checksums. A missing `sha256` is added
+    /// after `url`; a missing `version` is only added when it cannot be
+    /// inferred from the URL.
+    fn rewrite_formula(
+        content: &str,
+        url: &str,
+        sha256: &str,
+        version: &str,
+    ) -> anyhow::Result<String> {
+        if Self::stanza_regex("url").find(content).is_none() {
+            anyhow::bail!("{}", tr!("homebrew.formula_url_missing"));
+        }
+        let content = Self::set_stanza(content, "url", url, "url");
+        let content = Self::set_stanza(&content, "sha256", sha256, "url");
+        let has_version = Self::stanza_regex("version").is_match(&content);
+        if has_version || !url.contains(version) {
+            Ok(Self::set_stanza(&content, "version", version, "sha256"))
+        } else {
+            Ok(content)
+        }
+    }
+
+    /// First `name "..."` stanza line; captures the indentation
+    fn stanza_regex(name: &str) -> Regex {
+        Regex::new(&format!(r#"(?m)^([ \t]*){}[ \t]+["'][^"'\n]*["']"#, name)).unwrap()
+    }
+
+    /// Replace the first `name` stanza, or insert it on the line after `after`
+    fn set_stanza(content: &str, name: &str, value: &str, after: &str) -> String {
+        let regex = Self::stanza_regex(name);
+        if let Some(cap) = regex.captures(content) {
+            let whole = cap.get(0).unwrap();
+            return format!(
+                "{}{}{} \"{}\"{}",
+                &content[..whole.start()],
+                &cap[1],
+                name,
+                value,
+                &content[whole.end()..]
+            );
+        }
+
+        let Some(cap) = Self::stanza_regex(after).captures(content) else {
+            return content.to_string();
+        };
+        let line_end = content[cap.get(0).unwrap().end()..]
+            .find('\n')
+            .map(|i| cap.get(0).unwrap().end() + i)
+            .unwrap_or(content.len());
+        format!(
+            "{}\n{}{} \"{}\"{}",
+            &content[..line_end],
+            &cap[1],
+            name,
+            value,
+            &content[line_end..]
+        )
+    }
+
+    /// Minimal formula installing a single binary named after the formula
+    fn generate_formula(name: &str, url: &str, sha256: &str, version: &str) -> String {
+        let class_name: String = name
+            .split(['-', '_', '.'])
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut formula = format!(
+            "class {} < Formula\n  url \"{}\"\n  sha256 \"{}\"\n",
+            class_name, url, sha256
+        );
+        if !url.contains(version) {
+            formula.push_str(&format!("  version \"{}\"\n", version));
+        }
+        formula.push_str(&format!(
+            "\n  def install\n    bin.install \"{}\"\n  end\n\n",
+            name
+        ));
+        formula.push_str(&Self::generate_test_block(name));
+        formula.push_str("end\n");
+        formula
+    }
+
+    /// Formula name for a generated formula (config, then project directory)
+    fn generated_formula_name(&self) -> String {
+        self.config
+            .auto_update
+            .as_ref()
+            .and_then(|a| a.formula.clone())
+            .or_else(|| {
+                self.project_path
+                    .canonicalize()
+                    .ok()?
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_lowercase())
+            })
+            .unwrap_or_else(|| "formula".to_string())
+    }
+
+    /// Update the formula when `autoUpdate` is enabled
+    ///
+    /// The version comes from the `version` publish option or the latest git
+    /// tag (without a leading `v`).
+    async fn auto_update(&self, opts: &PublishOptions) -> anyhow::Result<Option<FormulaUpdate>> {
+        let Some(auto_update) = self
+            .config
+            .auto_update
+            .as_ref()
+            .filter(|a| a.enabled.unwrap_or(false))
+        else {
+            return Ok(None);
+        };
+        let template = auto_update
+            .url
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("homebrew.auto_update_url_missing")))?;
+
+        let version = match opts.extra.get("version").and_then(|v| v.as_str()) {
+            Some(version) => version.to_string(),
+            None => {
+                let tag = run_git(&self.project_path, &["describe", "--tags", "--abbrev=0"])
+                    .await
+                    .map_err(|_| anyhow::anyhow!("{}", tr!("homebrew.release_tag_not_found")))?;
+                tag.trim().trim_start_matches('v').to_string()
+            }
+        };
+
+        let url = template.replace("{version}", &version);
+        self.update_formula(&version, &url).await.map(Some)
+    }
+
     /// Find formula file in project
     async fn find_formula_file(&mut self) -> anyhow::Result<()> {
         if self.formula_path.is_some() {
@@ -111,6 +372,7 @@ impl HomebrewPlugin {
             homepage: None,
             description: None,
             license: None,
+            has_test: false,
         };
 
         // Extract class name (formula name)
@@ -155,9 +417,143 @@ impl HomebrewPlugin {
             metadata.license = Some(cap[1].to_string());
         }
 
+        metadata.has_test = Regex::new(r"(?m)^\s*test\s+do\b")
+            .unwrap()
+            .is_match(content);
+
         metadata
     }
 
+    /// Generate a basic `test do` block asserting `--version` output
+    fn generate_test_block(formula_name: &str) -> String {
+        format!(
+            "  test do\n    assert_match version.to_s, shell_output(\"#{{bin}}/{} --version\")\n  end\n",
+            formula_name
+        )
+    }
+
+    /// Insert a generated `test do` block before the closing `end` of the class
+    ///
+    /// Returns `None` when the formula already has a test block or has no
+    /// top-level `end` to insert before.
+    fn ensure_test_block(content: &str, formula_name: &str) -> Option<String> {
+        if Regex::new(r"(?m)^\s*test\s+do\b")
+            .unwrap()
+            .is_match(content)
+        {
+            return None;
+        }
+
+        let class_end = content
+            .rmatch_indices("\nend")
+            .map(|(i, _)| i + 1)
+            .find(|&i| content[i + 3..].trim().is_empty())?;
+
+        let (body, tail) = content.split_at(class_end);
+        let mut result = body.trim_end().to_string();
+        result.push_str("\n\n");
+        result.push_str(&Self::generate_test_block(formula_name));
+        result.push_str(tail);
+        Some(result)
+    }
+
+    /// Repository and token for `pullRequest` mode, checked before pushing
+    async fn tap_pull_request(
+        &self,
+        config: &HomebrewPullRequestConfig,
+    ) -> anyhow::Result<TapPullRequest> {
+        let repository = match &config.repository {
+            Some(repository) => repository.clone(),
+            None => {
+                let url = run_git(&self.project_path, &["remote", "get-url", "origin"]).await?;
+                parse_github_repository(&url)
+                    .ok_or_else(|| anyhow::anyhow!("{}", tr!("homebrew.repository_unknown")))?
+            }
+        };
+        let token_env = config.token_env.as_deref().unwrap_or("GITHUB_TOKEN");
+        let token = std::env::var(token_env)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("registry.not_set", name = token_env)))?;
+        Ok(TapPullRequest {
+            repository,
+            token,
+            base: config.base.clone(),
+        })
+    }
+
+    /// PR description: the formula change and the release's changelog section
+    fn pull_request_body(
+        formula_name: &str,
+        version: Option<&str>,
+        update: Option<&FormulaUpdate>,
+        changelog: Option<&str>,
+    ) -> String {
+        let mut body = match version {
+            Some(version) => format!("Update `{}` to {}.\n", formula_name, version),
+            None => format!("Update the `{}` formula.\n", formula_name),
+        };
+        if let Some(update) = update {
+            body.push_str(&format!(
+                "\n- url: {}\n- sha256: `{}`\n",
+                update.url, update.sha256
+            ));
+        }
+        if let Some(changelog) = changelog.filter(|c| !c.trim().is_empty()) {
+            body.push_str(&format!("\n## Changelog\n\n{}\n", changelog.trim()));
+        }
+        body.push_str("\n_Automated by package-publisher_");
+        body
+    }
+
+    /// Open the pull request from `branch`; returns the PR URL
+    async fn open_pull_request(
+        &self,
+        target: &TapPullRequest,
+        base: &str,
+        branch: &str,
+        title: &str,
+        body: &str,
+    ) -> anyhow::Result<String> {
+        self.github
+            .authenticated(&target.token)
+            .open_pull_request(&target.repository, branch, base, title, body)
+            .await
+    }
+
+    /// Whether the brew command is available
+    async fn brew_available(&self) -> bool {
+        self.run_brew(&["--version"]).await.is_ok()
+    }
+
+    /// Install the formula from a temporary tap and run `brew test`
+    ///
+    /// The tap is removed afterwards regardless of the outcome.
+    async fn run_brew_test(&self, formula_name: &str, content: &str) -> anyhow::Result<String> {
+        let tap = format!("package-publisher/dry-run-{}", std::process::id());
+        self.run_brew(&["tap-new", "--no-git", &tap]).await?;
+
+        let result = async {
+            let tap_path = self.run_brew(&["--repository", &tap]).await?;
+            let formula_dir = PathBuf::from(tap_path.trim()).join("Formula");
+            fs::create_dir_all(&formula_dir).await?;
+            fs::write(formula_dir.join(format!("{}.rb", formula_name)), content).await?;
+
+            let qualified = format!("{}/{}", tap, formula_name);
+            let mut output = self
+                .run_brew(&["install", "--build-from-source", &qualified])
+                .await?;
+            let test_result = self.run_brew(&["test", &qualified]).await;
+            let _ = self.run_brew(&["uninstall", "--formula", &qualified]).await;
+            output.push_str(&test_result?);
+            Ok(output)
+        }
+        .await;
+
+        let _ = self.run_brew(&["untap", "--force", &tap]).await;
+        result
+    }
+
     /// Convert CamelCase class name to kebab-case formula name
     fn class_name_to_formula_name(&self, class_name: &str) -> String {
         // Convert CamelCase to kebab-case
@@ -171,35 +567,11 @@ impl HomebrewPlugin {
         step2.to_lowercase()
     }
 
-    /// Execute git command
-    async fn run_git(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        if !output.status.success() {
-            anyhow::bail!("{}", stderr);
-        }
-
-        Ok(stdout + &stderr)
-    }
-
     /// Execute brew command
     async fn run_brew(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = Command::new("brew")
-            .args(args)
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+        let mut command = self.limits.command("brew");
+        command.args(args).current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -259,7 +631,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if plugin.formula_path.is_none() {
             errors.push(ValidationError {
                 field: "formula".to_string(),
-                message: "Formulaファイル（.rb）が見つかりません".to_string(),
+                message: tr!("homebrew.formula_rb_not_found"),
                 severity: "error".to_string(),
             });
             return Ok(ValidationResult {
@@ -279,7 +651,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.name.is_none() {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "Formula名が見つかりません".to_string(),
+                message: tr!("homebrew.formula_name_missing"),
                 severity: "error".to_string(),
             });
         } else {
@@ -292,7 +664,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.url.is_none() {
             errors.push(ValidationError {
                 field: "url".to_string(),
-                message: "ソースURLが見つかりません".to_string(),
+                message: tr!("homebrew.source_url_missing"),
                 severity: "error".to_string(),
             });
         }
@@ -301,7 +673,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.sha256.is_none() {
             warnings.push(ValidationWarning {
                 field: "sha256".to_string(),
-                message: "SHA256ハッシュの指定を推奨します".to_string(),
+                message: tr!("homebrew.sha256_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -309,7 +681,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.description.is_none() {
             warnings.push(ValidationWarning {
                 field: "desc".to_string(),
-                message: "説明（desc）の指定を推奨します".to_string(),
+                message: tr!("homebrew.desc_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -317,7 +689,7 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.homepage.is_none() {
             warnings.push(ValidationWarning {
                 field: "homepage".to_string(),
-                message: "ホームページURLの指定を推奨します".to_string(),
+                message: tr!("homebrew.homepage_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -325,7 +697,15 @@ impl RegistryPlugin for HomebrewPlugin {
         if formula_meta.license.is_none() {
             warnings.push(ValidationWarning {
                 field: "license".to_string(),
-                message: "ライセンスの指定を推奨します".to_string(),
+                message: tr!("homebrew.license_recommended"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if !formula_meta.has_test {
+            warnings.push(ValidationWarning {
+                field: "test".to_string(),
+                message: tr!("homebrew.test_block_generated"),
                 severity: "warning".to_string(),
             });
         }
@@ -340,7 +720,7 @@ impl RegistryPlugin for HomebrewPlugin {
         // Note about brew audit
         warnings.push(ValidationWarning {
             field: "brew.audit".to_string(),
-            message: "ローカルFormulaファイルのため、brew auditをスキップしました。Tapに追加後に手動で実行してください。".to_string(),
+            message: tr!("homebrew.audit_skipped"),
             severity: "warning".to_string(),
         });
 
@@ -363,11 +743,11 @@ impl RegistryPlugin for HomebrewPlugin {
         if plugin.formula_path.is_none() {
             return Ok(DryRunResult {
                 success: false,
-                output: "Formulaファイルが見つかりません".to_string(),
+                output: tr!("homebrew.formula_not_found"),
                 estimated_size: None,
                 errors: Some(vec![ValidationError {
                     field: "formula".to_string(),
-                    message: "Formulaファイル（.rb）が見つかりません".to_string(),
+                    message: tr!("homebrew.formula_rb_not_found"),
                     severity: "error".to_string(),
                 }]),
             });
@@ -376,148 +756,300 @@ impl RegistryPlugin for HomebrewPlugin {
         // Load formula metadata
         plugin.load_formula_metadata().await?;
         let formula_meta = plugin.formula_metadata.as_ref().unwrap();
+        let formula_name = formula_meta.name.as_deref().unwrap_or("unknown");
 
-        let output = format!(
-            "Formula検証完了:\n\
-            - Formula名: {}\n\
-            - バージョン: {}\n\
-            - URL: {}\n\
-            \n\
-            ⚠️  注意: ローカルFormulaのため、brew auditとinstallテストをスキップしました\n\
-            \n\
-            Tapに追加後、以下のコマンドで詳細検証してください:\n\
-              brew audit --strict {}\n\
-              brew install --build-from-source {}",
-            formula_meta.name.as_deref().unwrap_or("unknown"),
-            formula_meta.version.as_deref().unwrap_or("unknown"),
-            formula_meta.url.as_deref().unwrap_or("none"),
-            formula_meta.name.as_deref().unwrap_or("unknown"),
-            formula_meta.name.as_deref().unwrap_or("unknown")
+        let mut output = tr!(
+            "homebrew.dry_run_summary",
+            formula = formula_name,
+            version = formula_meta.version.as_deref().unwrap_or("unknown"),
+            url = formula_meta.url.as_deref().unwrap_or("none"),
         );
 
-        Ok(DryRunResult {
-            success: true,
-            output,
-            estimated_size: None,
-            errors: None,
-        })
-    }
-
-    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
-        let opts = options.unwrap_or_default();
+        let content = fs::read_to_string(plugin.formula_path.as_ref().unwrap()).await?;
+        let content = match Self::ensure_test_block(&content, formula_name) {
+            Some(generated) => {
+                output.push_str(&tr!("homebrew.summary_test_block"));
+                generated
+            }
+            None => content,
+        };
 
-        let mut plugin = HomebrewPlugin::new(self.project_path.clone());
-        plugin.find_formula_file().await?;
-        plugin.load_formula_metadata().await?;
+        if !plugin.brew_available().await {
+            output.push_str(&tr!("homebrew.brew_missing", formula = formula_name));
+            return Ok(DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            });
+        }
 
-        if plugin.formula_path.is_none() {
-            return Ok(PublishResult {
+        match plugin.run_brew_test(formula_name, &content).await {
+            Ok(_) => {
+                output.push_str(&tr!("homebrew.summary_brew_test"));
+                Ok(DryRunResult {
+                    success: true,
+                    output,
+                    estimated_size: None,
+                    errors: None,
+                })
+            }
+            Err(e) => Ok(DryRunResult {
                 success: false,
-                version: None,
-                package_url: None,
-                output: None,
-                error: Some("Formulaファイルが見つかりません".to_string()),
-                metadata: None,
-            });
+                output,
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "test".to_string(),
+                    message: tr!("homebrew.brew_test_failed", output = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
         }
+    }
 
-        let formula_meta = plugin.formula_metadata.as_ref().unwrap();
-        let formula_name = formula_meta.name.as_deref().unwrap_or("unknown");
-        let tap_name = opts.tag.as_deref().unwrap_or("homebrew-tap");
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let opts = options.unwrap_or_default();
 
         // Check if we're in a Git repository
-        if plugin.run_git(&["rev-parse", "--git-dir"]).await.is_err() {
+        if run_git(&self.project_path, &["rev-parse", "--git-dir"])
+            .await
+            .is_err()
+        {
             return Ok(PublishResult {
                 success: false,
                 version: None,
                 package_url: None,
                 output: None,
-                error: Some("HomebrewのFormulaはGitリポジトリで管理する必要があります".to_string()),
+                error: Some(tr!("homebrew.git_required")),
                 metadata: None,
+                error_kind: None,
             });
         }
 
-        // Git add and commit
-        let formula_path_str = plugin.formula_path.as_ref().unwrap().to_str().unwrap();
-        match plugin.run_git(&["add", formula_path_str]).await {
-            Ok(_) => {}
+        // Point the formula at the new release before committing
+        let update = match self.auto_update(&opts).await {
+            Ok(update) => update,
             Err(e) => {
                 return Ok(PublishResult {
                     success: false,
                     version: None,
                     package_url: None,
                     output: None,
-                    error: Some(format!("git add に失敗: {}", e)),
+                    error: Some(tr!("homebrew.auto_update_failed", error = e)),
                     metadata: None,
+                    error_kind: None,
                 });
             }
+        };
+
+        let mut plugin = HomebrewPlugin::new(self.project_path.clone());
+        plugin.find_formula_file().await?;
+        plugin.load_formula_metadata().await?;
+
+        if plugin.formula_path.is_none() {
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("homebrew.formula_not_found")),
+                metadata: None,
+                error_kind: None,
+            });
         }
 
-        let commit_message = format!("Add/Update {} formula", formula_name);
-        match plugin.run_git(&["commit", "-m", &commit_message]).await {
-            Ok(_) => {}
-            Err(e) => {
-                // Ignore "nothing to commit" errors
-                if !e.to_string().contains("nothing to commit") {
+        let formula_meta = plugin.formula_metadata.as_ref().unwrap();
+        let formula_name = formula_meta.name.as_deref().unwrap_or("unknown");
+        let tap_name = opts.tag.as_deref().unwrap_or("homebrew-tap");
+        let version = update
+            .as_ref()
+            .map(|u| u.version.clone())
+            .or_else(|| formula_meta.version.clone());
+
+        // Ensure the formula has a test block before committing
+        let formula_path = plugin.formula_path.clone().unwrap();
+        let content = fs::read_to_string(&formula_path).await?;
+        if let Some(generated) = Self::ensure_test_block(&content, formula_name) {
+            fs::write(&formula_path, generated).await?;
+        }
+
+        // Pull request mode: commit on a release branch instead of the current one
+        let pull_request = match self
+            .config
+            .pull_request
+            .as_ref()
+            .filter(|p| p.enabled.unwrap_or(false))
+        {
+            Some(config) => match self.tap_pull_request(config).await {
+                Ok(target) => Some(target),
+                Err(e) => {
                     return Ok(PublishResult {
                         success: false,
                         version: None,
                         package_url: None,
                         output: None,
-                        error: Some(format!("git commit に失敗: {}", e)),
+                        error: Some(tr!("homebrew.pr_prepare_failed", error = e)),
                         metadata: None,
+                        error_kind: None,
                     });
                 }
+            },
+            None => None,
+        };
+        let mut pr_branch = None;
+        if pull_request.is_some() {
+            let branch = format!(
+                "{}-{}",
+                formula_name,
+                version.as_deref().unwrap_or("update")
+            );
+            let created = async {
+                let current =
+                    run_git(&plugin.project_path, &["rev-parse", "--abbrev-ref", "HEAD"]).await?;
+                run_git(&plugin.project_path, &["checkout", "-b", &branch]).await?;
+                Ok::<_, anyhow::Error>(current.trim().to_string())
             }
-        }
-
-        // Push to remote
-        match plugin.run_git(&["push"]).await {
-            Ok(output) => {
-                let package_url = format!("https://github.com/[owner]/{}", tap_name);
-
-                Ok(PublishResult {
-                    success: true,
-                    version: formula_meta.version.clone(),
-                    package_url: Some(package_url),
-                    output: Some(output),
-                    error: None,
-                    metadata: Some(HashMap::from([(
-                        "message".to_string(),
-                        serde_json::Value::String(
-                            "FormulaをGitHubにpushしました。homebrew/homebrew-coreへの公式登録はPRを作成してください。"
-                                .to_string(),
-                        ),
-                    )])),
-                })
-            }
-            Err(e) => {
-                // Check for authentication errors
-                let error_msg = e.to_string();
-                if error_msg.contains("authentication") || error_msg.contains("Permission denied") {
+            .await;
+            match created {
+                Ok(current) => pr_branch = Some((current, branch)),
+                Err(e) => {
                     return Ok(PublishResult {
                         success: false,
                         version: None,
                         package_url: None,
                         output: None,
-                        error: Some(
-                            "Gitリポジトリへの認証に失敗しました。GitHub認証情報を確認してください"
-                                .to_string(),
-                        ),
+                        error: Some(tr!("homebrew.branch_failed", branch = branch, error = e)),
                         metadata: None,
+                        error_kind: None,
                     });
                 }
+            }
+        }
 
-                Ok(PublishResult {
+        let commit_message = match &update {
+            Some(update) if update.created => {
+                format!("{} {} (new formula)", formula_name, update.version)
+            }
+            Some(update) => format!("{} {}", formula_name, update.version),
+            None => format!("Add/Update {} formula", formula_name),
+        };
+
+        // Git add, commit and push
+        let formula_path_str = formula_path.to_str().unwrap();
+        let pushed: Result<String, String> = async {
+            run_git(&plugin.project_path, &["add", formula_path_str])
+                .await
+                .map_err(|e| tr!("homebrew.git_add_failed", error = e))?;
+
+            if let Err(e) = run_git(&plugin.project_path, &["commit", "-m", &commit_message]).await
+                // Ignore "nothing to commit" errors
+                && !e.to_string().contains("nothing to commit")
+            {
+                return Err(tr!("homebrew.git_commit_failed", error = e));
+            }
+
+            let push = match &pr_branch {
+                Some((_, branch)) => {
+                    run_git(&plugin.project_path, &["push", "-u", "origin", branch]).await
+                }
+                None => run_git(&plugin.project_path, &["push"]).await,
+            };
+            push.map_err(|e| {
+                // Check for authentication errors
+                let error_msg = e.to_string();
+                if error_msg.contains("authentication") || error_msg.contains("Permission denied") {
+                    tr!("homebrew.git_auth_failed")
+                } else {
+                    error_msg
+                }
+            })
+        }
+        .await;
+
+        // The release branch only lives on the remote; go back to where we were
+        if let Some((current, _)) = &pr_branch {
+            let _ = run_git(&plugin.project_path, &["checkout", current]).await;
+        }
+
+        let output = match pushed {
+            Ok(output) => output,
+            Err(error) => {
+                return Ok(PublishResult {
                     success: false,
                     version: None,
                     package_url: None,
                     output: None,
-                    error: Some(error_msg),
+                    error: Some(error),
                     metadata: None,
-                })
+                    error_kind: None,
+                });
             }
+        };
+
+        let (package_url, message) = match (&pull_request, &pr_branch) {
+            (Some(target), Some((current, branch))) => {
+                let changelog = match (
+                    &version,
+                    fs::read_to_string(self.project_path.join("CHANGELOG.md")).await,
+                ) {
+                    (Some(version), Ok(content)) => {
+                        ChangelogGenerator::section_for(&content, version)
+                    }
+                    _ => None,
+                };
+                let title = match &version {
+                    Some(version) => format!("{} {}", formula_name, version),
+                    None => format!("Update {} formula", formula_name),
+                };
+                let body = Self::pull_request_body(
+                    formula_name,
+                    version.as_deref(),
+                    update.as_ref(),
+                    changelog.as_deref(),
+                );
+                let base = target.base.as_deref().unwrap_or(current);
+                match self
+                    .open_pull_request(target, base, branch, &title, &body)
+                    .await
+                {
+                    Ok(url) => (
+                        url,
+                        tr!("homebrew.pr_created", branch = branch, repository = base),
+                    ),
+                    Err(e) => {
+                        return Ok(PublishResult {
+                            success: false,
+                            version: None,
+                            package_url: None,
+                            output: Some(output),
+                            error: Some(tr!("homebrew.pr_failed", branch = branch, error = e)),
+                            metadata: None,
+                            error_kind: None,
+                        });
+                    }
+                }
+            }
+            _ => (
+                format!("https://github.com/[owner]/{}", tap_name),
+                tr!("homebrew.pushed"),
+            ),
+        };
+
+        let mut metadata =
+            HashMap::from([("message".to_string(), serde_json::Value::String(message))]);
+        if let Some(update) = &update {
+            metadata.insert("formulaUpdate".to_string(), serde_json::to_value(update)?);
         }
+
+        Ok(PublishResult {
+            success: true,
+            version,
+            package_url: Some(package_url),
+            output: Some(output),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
     }
 
     async fn verify(&self) -> anyhow::Result<VerificationResult> {
@@ -550,17 +1082,84 @@ impl RegistryPlugin for HomebrewPlugin {
                     url: Some(format!("https://formulae.brew.sh/formula/{}", formula_name)),
                     error: None,
                     metadata: Some(metadata),
+                    integrity: None,
                 })
             }
             Err(_) => Ok(VerificationResult {
                 verified: false,
                 version: formula_meta.version.clone(),
                 url: None,
-                error: Some(format!(
-                    "Formula {} が Homebrew で見つかりません。カスタムTapの場合は正常です。",
-                    formula_name
+                error: Some(tr!(
+                    "homebrew.formula_not_on_homebrew",
+                    formula = formula_name
                 )),
                 metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+
+    /// Revert the latest commit touching the formula and push
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        let mut plugin = HomebrewPlugin::new(self.project_path.clone());
+        plugin.find_formula_file().await?;
+        plugin.load_formula_metadata().await?;
+
+        let (Some(formula_path), Some(formula_meta)) =
+            (plugin.formula_path.clone(), plugin.formula_metadata.clone())
+        else {
+            return Ok(RollbackResult {
+                success: false,
+                message: tr!("registry.rollback_failed"),
+                error: Some(tr!("homebrew.formula_not_found")),
+            });
+        };
+
+        if formula_meta.version.as_deref() != Some(version) {
+            return Ok(RollbackResult {
+                success: false,
+                message: tr!("registry.rollback_failed"),
+                error: Some(tr!(
+                    "homebrew.version_mismatch",
+                    actual = formula_meta.version.as_deref().unwrap_or("unknown"),
+                    expected = version
+                )),
+            });
+        }
+
+        let formula_path_str = formula_path.to_string_lossy().to_string();
+        let result = async {
+            let commit = run_git(
+                &plugin.project_path,
+                &["log", "-n", "1", "--format=%H", "--", &formula_path_str],
+            )
+            .await?;
+            let commit = commit.trim().to_string();
+            if commit.is_empty() {
+                anyhow::bail!("{}", tr!("homebrew.commit_not_found"));
+            }
+            run_git(&plugin.project_path, &["revert", "--no-edit", &commit]).await?;
+            run_git(&plugin.project_path, &["push"]).await?;
+            Ok(commit)
+        }
+        .await;
+
+        let formula_name = formula_meta.name.as_deref().unwrap_or("unknown");
+        match result {
+            Ok(commit) => Ok(RollbackResult {
+                success: true,
+                message: tr!(
+                    "homebrew.reverted",
+                    package = formula_name,
+                    version = version,
+                    commit = &commit[..commit.len().min(7)]
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(RollbackResult {
+                success: false,
+                message: tr!("registry.rollback_failed"),
+                error: Some(e.to_string()),
             }),
         }
     }
@@ -650,6 +1249,253 @@ end
         assert_eq!(metadata.homepage, Some("https://example.com".to_string()));
         assert_eq!(metadata.description, Some("An awesome tool".to_string()));
         assert_eq!(metadata.license, Some("MIT".to_string()));
+        assert!(!metadata.has_test);
+    }
+
+    #[test]
+    fn test_ensure_test_block() {
+        let content = r#"class MyTool < Formula
+  url "https://example.com/my-tool-1.0.0.tar.gz"
+
+  def install
+    bin.install "my-tool"
+  end
+end
+"#;
+
+        let generated = HomebrewPlugin::ensure_test_block(content, "my-tool").unwrap();
+        assert!(generated.contains(
+            "  test do\n    assert_match version.to_s, shell_output(\"#{bin}/my-tool --version\")\n  end\nend\n"
+        ));
+        assert!(generated.starts_with("class MyTool < Formula"));
+
+        let plugin = HomebrewPlugin::new(PathBuf::from("."));
+        assert!(plugin.parse_formula(&generated).has_test);
+        assert!(HomebrewPlugin::ensure_test_block(&generated, "my-tool").is_none());
+        assert!(HomebrewPlugin::ensure_test_block("class MyTool < Formula", "my-tool").is_none());
+    }
+
+    #[test]
+    fn test_rewrite_formula_stanzas() {
+        let content = r#"class MyTool < Formula
+  desc "My tool"
+  url "https://example.com/my-tool-1.0.0.tar.gz"
+  sha256 "old"
+
+  resource "extra" do
+    url "https://example.com/extra.tar.gz"
+    sha256 "keep"
+  end
+end
+"#;
+
+        let rewritten = HomebrewPlugin::rewrite_formula(
+            content,
+            "https://example.com/my-tool-1.1.0.tar.gz",
+            "new",
+            "1.1.0",
+        )
+        .unwrap();
+        assert!(
+            rewritten.contains(
+                "  url \"https://example.com/my-tool-1.1.0.tar.gz\"\n  sha256 \"new\"\n\n"
+            )
+        );
+        assert!(
+            rewritten.contains("    url \"https://example.com/extra.tar.gz\"\n    sha256 \"keep\"")
+        );
+        assert!(!rewritten.contains("version"));
+
+        // The version cannot be inferred from a tag-less URL, so it is added
+        let rewritten = HomebrewPlugin::rewrite_formula(
+            content,
+            "https://example.com/latest.tar.gz",
+            "new",
+            "1.1.0",
+        )
+        .unwrap();
+        assert!(rewritten.contains("  sha256 \"new\"\n  version \"1.1.0\"\n"));
+
+        let generated = HomebrewPlugin::generate_formula(
+            "my-tool",
+            "https://example.com/my-tool-1.1.0.tar.gz",
+            "new",
+            "1.1.0",
+        );
+        assert!(generated.starts_with("class MyTool < Formula\n"));
+        let plugin = HomebrewPlugin::new(PathBuf::from("."));
+        let metadata = plugin.parse_formula(&generated);
+        assert_eq!(metadata.name.as_deref(), Some("my-tool"));
+        assert_eq!(metadata.sha256.as_deref(), Some("new"));
+        assert!(metadata.has_test);
+
+        assert!(
+            HomebrewPlugin::rewrite_formula("class A < Formula\nend\n", "u", "s", "1").is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_formula_downloads_tarball() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            let body = "tarball";
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let formula_dir = temp_dir.path().join("Formula");
+        std::fs::create_dir(&formula_dir).unwrap();
+        std::fs::write(
+            formula_dir.join("my-tool.rb"),
+            "class MyTool < Formula\n  url \"https://example.com/v1.0.0.tar.gz\"\n  sha256 \"old\"\nend\n",
+        )
+        .unwrap();
+
+        let url = format!("http://{}/v1.1.0.tar.gz", address);
+        let plugin = HomebrewPlugin::new(temp_dir.path().to_path_buf());
+        let update = plugin.update_formula("1.1.0", &url).await.unwrap();
+        assert!(update.changed);
+        assert!(!update.created);
+        assert_eq!(update.sha256, hex::encode(Sha256::digest(b"tarball")));
+
+        let content = std::fs::read_to_string(formula_dir.join("my-tool.rb")).unwrap();
+        assert!(content.contains(&format!("url \"{}\"", url)));
+        assert!(content.contains(&format!("sha256 \"{}\"", update.sha256)));
+    }
+
+    #[tokio::test]
+    async fn test_publish_opens_pull_request() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the JSON body is complete
+            while !String::from_utf8_lossy(&request).ends_with('}') {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = r#"{"html_url":"https://github.com/me/homebrew-tap/pull/7"}"#;
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 201 Created\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let remote = TempDir::new().unwrap();
+        git(remote.path(), &["init", "-q", "--bare"]);
+        let temp_dir = TempDir::new().unwrap();
+        let tap = temp_dir.path();
+        git(tap, &["init", "-q", "-b", "main"]);
+        git(tap, &["config", "user.name", "Test"]);
+        git(tap, &["config", "user.email", "test@example.com"]);
+        git(
+            tap,
+            &["remote", "add", "origin", remote.path().to_str().unwrap()],
+        );
+        std::fs::write(
+            tap.join("my-tool.rb"),
+            "class MyTool < Formula\n  url \"https://example.com/my-tool-1.0.0.tar.gz\"\n  version \"1.0.0\"\nend\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tap.join("CHANGELOG.md"),
+            "# Changelog\n\n## [1.0.0]\n\n- First release\n",
+        )
+        .unwrap();
+        git(tap, &["add", "CHANGELOG.md"]);
+        git(tap, &["commit", "-q", "-m", "init"]);
+
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_TAP_TOKEN", "ghp_test");
+        }
+        let plugin = HomebrewPlugin::new(tap.to_path_buf())
+            .with_config(HomebrewRegistryConfig {
+                pull_request: Some(HomebrewPullRequestConfig {
+                    enabled: Some(true),
+                    repository: Some("me/homebrew-tap".to_string()),
+                    token_env: Some("PACKAGE_PUBLISHER_TEST_TAP_TOKEN".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .with_github_api_url(format!("http://{}", address));
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.package_url.as_deref(),
+            Some("https://github.com/me/homebrew-tap/pull/7")
+        );
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /repos/me/homebrew-tap/pulls"));
+        assert!(request.contains("authorization: Bearer ghp_test"));
+        assert!(request.contains(r#""head":"my-tool-1.0.0""#));
+        assert!(request.contains(r#""base":"main""#));
+        assert!(request.contains("- First release"));
+
+        // Back on main, with the formula commit only on the pushed branch
+        assert_eq!(git(tap, &["rev-parse", "--abbrev-ref", "HEAD"]), "main");
+        assert_eq!(
+            git(
+                remote.path(),
+                &["log", "-1", "--format=%s", "my-tool-1.0.0"]
+            ),
+            "Add/Update my-tool formula"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_version_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("test.rb"),
+            "class Test < Formula\n  version \"1.0.0\"\nend\n",
+        )
+        .unwrap();
+
+        let plugin = HomebrewPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.rollback("2.0.0").await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("does not match"));
     }
 
     #[tokio::test]
@@ -0,0 +1,438 @@
+//! Package formats for hosted multi-format registries (Cloudsmith, Gemfury)
+//!
+//! Maps the project to a package format and builds the file that is
+//! uploaded, the same way the native registry tooling would:
+//! - npm: `npm pack`
+//! - cargo: `cargo package` (`target/package/<name>-<version>.crate`)
+//! - python: `python -m build` (wheel and sdist)
+//! - deb: prebuilt `.deb` files (configured artifacts, default `dist/*.deb`)
+
+use crate::orchestration::release::resolve_assets;
+use crate::plugins::crates_io_plugin::CARGO_ENV;
+use crate::plugins::npm_plugin::NPM_ENV;
+use crate::plugins::pypi_plugin::PYTHON_ENV;
+use crate::security::command_executor::CommandLimits;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::fs;
+
+/// Package format of a hosted registry upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostedPackageFormat {
+    Npm,
+    Cargo,
+    Python,
+    Deb,
+}
+
+impl HostedPackageFormat {
+    /// Detect the format from the project manifest
+    pub async fn detect(project_path: &Path) -> Option<Self> {
+        let exists = |file: &str| {
+            let path = project_path.join(file);
+            async move { fs::metadata(path).await.is_ok() }
+        };
+        if exists("package.json").await {
+            Some(HostedPackageFormat::Npm)
+        } else if exists("Cargo.toml").await {
+            Some(HostedPackageFormat::Cargo)
+        } else if exists("pyproject.toml").await || exists("setup.py").await {
+            Some(HostedPackageFormat::Python)
+        } else if exists("debian/control").await {
+            Some(HostedPackageFormat::Deb)
+        } else {
+            None
+        }
+    }
+
+    /// Format from the `format` setting, else detected from the project
+    pub async fn resolve(configured: Option<&str>, project_path: &Path) -> anyhow::Result<Self> {
+        match configured {
+            Some(format) => format.parse().map_err(anyhow::Error::msg),
+            None => Self::detect(project_path).await.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Cannot detect the package format; set format (npm, cargo, python, deb)"
+                )
+            }),
+        }
+    }
+}
+
+impl fmt::Display for HostedPackageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostedPackageFormat::Npm => write!(f, "npm"),
+            HostedPackageFormat::Cargo => write!(f, "cargo"),
+            HostedPackageFormat::Python => write!(f, "python"),
+            HostedPackageFormat::Deb => write!(f, "deb"),
+        }
+    }
+}
+
+impl FromStr for HostedPackageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "npm" => Ok(HostedPackageFormat::Npm),
+            "cargo" | "crates.io" => Ok(HostedPackageFormat::Cargo),
+            "python" | "pypi" => Ok(HostedPackageFormat::Python),
+            "deb" | "debian" => Ok(HostedPackageFormat::Deb),
+            other => Err(format!(
+                "Unknown package format: {} (expected npm, cargo, python or deb)",
+                other
+            )),
+        }
+    }
+}
+
+/// Files to upload: configured patterns, or the package built for the format
+pub(crate) async fn package_files(
+    project_path: &Path,
+    format: HostedPackageFormat,
+    patterns: Option<&[String]>,
+    out_dir: &Path,
+    name: &str,
+    version: &str,
+    limits: &CommandLimits,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let files = match (patterns, format) {
+        (Some(patterns), _) => resolve_assets(project_path, patterns).await,
+        (None, HostedPackageFormat::Deb) => {
+            resolve_assets(project_path, &["dist/*.deb".to_string()]).await
+        }
+        (None, format) => {
+            build_package(project_path, format, out_dir, name, version, limits).await?
+        }
+    };
+
+    if files.is_empty() {
+        anyhow::bail!("No {} package files found", format);
+    }
+    Ok(files)
+}
+
+/// Release artifacts: configured patterns, or the package built the way
+/// the registry builds it (npm, crates.io, PyPI) into `out_dir/packages`
+pub(crate) async fn release_artifacts(
+    project_path: &Path,
+    out_dir: &Path,
+    patterns: Option<&[String]>,
+    registry: &str,
+    name: &str,
+    version: &str,
+    limits: &CommandLimits,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let artifacts = match (patterns, registry) {
+        (Some(patterns), _) => resolve_assets(project_path, patterns).await,
+        (None, "npm" | "crates.io" | "pypi") => {
+            let format = registry.parse().map_err(anyhow::Error::msg)?;
+            build_package(
+                project_path,
+                format,
+                &out_dir.join("packages"),
+                name,
+                version,
+                limits,
+            )
+            .await?
+        }
+        (None, other) => anyhow::bail!("no default artifacts for {}", other),
+    };
+
+    if artifacts.is_empty() {
+        anyhow::bail!("no artifacts found");
+    }
+    Ok(artifacts)
+}
+
+/// Build the package for an npm / cargo / python project into `out_dir`
+///
+/// The build tool runs with `limits` and its own variables (see the npm,
+/// crates.io and PyPI plugins) passed through the sandbox.
+pub(crate) async fn build_package(
+    project_path: &Path,
+    format: HostedPackageFormat,
+    out_dir: &Path,
+    name: &str,
+    version: &str,
+    limits: &CommandLimits,
+) -> anyhow::Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir).await?;
+    let out_dir_str = out_dir.display().to_string();
+
+    match format {
+        HostedPackageFormat::Npm => {
+            let stdout = run(
+                project_path,
+                &limits.clone().allowing(NPM_ENV),
+                "npm",
+                &["pack", "--pack-destination", &out_dir_str],
+            )
+            .await?;
+            let file = stdout
+                .lines()
+                .map(str::trim)
+                .rfind(|l| l.ends_with(".tgz"))
+                .ok_or_else(|| anyhow::anyhow!("npm pack did not report a tarball"))?;
+            Ok(vec![out_dir.join(file)])
+        }
+        HostedPackageFormat::Cargo => {
+            run(
+                project_path,
+                &limits.clone().allowing(CARGO_ENV),
+                "cargo",
+                &["package", "--allow-dirty", "--no-verify"],
+            )
+            .await?;
+            let target_dir = std::env::var("CARGO_TARGET_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| project_path.join("target"));
+            let crate_file = target_dir
+                .join("package")
+                .join(format!("{}-{}.crate", name, version));
+            if fs::metadata(&crate_file).await.is_err() {
+                anyhow::bail!("{} not found after cargo package", crate_file.display());
+            }
+            Ok(vec![crate_file])
+        }
+        HostedPackageFormat::Python => {
+            run(
+                project_path,
+                &limits.clone().allowing(PYTHON_ENV),
+                "python",
+                &["-m", "build", "--outdir", &out_dir_str],
+            )
+            .await?;
+            Ok(resolve_assets(out_dir, &[".".to_string()]).await)
+        }
+        HostedPackageFormat::Deb => {
+            anyhow::bail!("deb packages are not built here; set artifacts to the .deb files")
+        }
+    }
+}
+
+async fn run(
+    project_path: &Path,
+    limits: &CommandLimits,
+    program: &str,
+    args: &[&str],
+) -> anyhow::Result<String> {
+    let mut command = limits.command(program);
+    command.args(args).current_dir(project_path);
+    let output = limits
+        .output(&mut command)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} {} failed: {}",
+            program,
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("NPM".parse(), Ok(HostedPackageFormat::Npm));
+        assert_eq!("crates.io".parse(), Ok(HostedPackageFormat::Cargo));
+        assert_eq!("pypi".parse(), Ok(HostedPackageFormat::Python));
+        assert_eq!("debian".parse(), Ok(HostedPackageFormat::Deb));
+        assert_eq!(
+            "gem".parse::<HostedPackageFormat>(),
+            Err("Unknown package format: gem (expected npm, cargo, python or deb)".to_string())
+        );
+        for format in [
+            HostedPackageFormat::Npm,
+            HostedPackageFormat::Cargo,
+            HostedPackageFormat::Python,
+            HostedPackageFormat::Deb,
+        ] {
+            assert_eq!(format.to_string().parse(), Ok(format));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert!(
+            HostedPackageFormat::resolve(None, project)
+                .await
+                .unwrap_err()
+                .to_string()
+                .starts_with("Cannot detect the package format")
+        );
+
+        std::fs::create_dir(project.join("debian")).unwrap();
+        std::fs::write(project.join("debian/control"), "Source: tool\n").unwrap();
+        assert_eq!(
+            HostedPackageFormat::detect(project).await,
+            Some(HostedPackageFormat::Deb)
+        );
+        std::fs::write(project.join("setup.py"), "").unwrap();
+        assert_eq!(
+            HostedPackageFormat::detect(project).await,
+            Some(HostedPackageFormat::Python)
+        );
+        std::fs::write(project.join("Cargo.toml"), "").unwrap();
+        assert_eq!(
+            HostedPackageFormat::detect(project).await,
+            Some(HostedPackageFormat::Cargo)
+        );
+        std::fs::write(project.join("package.json"), "{}").unwrap();
+        assert_eq!(
+            HostedPackageFormat::resolve(None, project).await.unwrap(),
+            HostedPackageFormat::Npm
+        );
+
+        // The setting wins over detection
+        assert_eq!(
+            HostedPackageFormat::resolve(Some("deb"), project)
+                .await
+                .unwrap(),
+            HostedPackageFormat::Deb
+        );
+        assert!(
+            HostedPackageFormat::resolve(Some("gem"), project)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_package_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let out_dir = project.join("out");
+        let limits = CommandLimits::default();
+
+        let err = package_files(
+            project,
+            HostedPackageFormat::Deb,
+            None,
+            &out_dir,
+            "tool",
+            "1.0.0",
+            &limits,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_string(), "No deb package files found");
+
+        std::fs::create_dir(project.join("dist")).unwrap();
+        std::fs::write(project.join("dist/tool_1.0.0_amd64.deb"), "deb").unwrap();
+        std::fs::write(project.join("dist/tool-1.0.0.tgz"), "npm").unwrap();
+        let files = package_files(
+            project,
+            HostedPackageFormat::Deb,
+            None,
+            &out_dir,
+            "tool",
+            "1.0.0",
+            &limits,
+        )
+        .await
+        .unwrap();
+        assert_eq!(files, vec![project.join("dist/tool_1.0.0_amd64.deb")]);
+
+        // Configured patterns skip the build
+        let files = package_files(
+            project,
+            HostedPackageFormat::Npm,
+            Some(&["dist/*.tgz".to_string()]),
+            &out_dir,
+            "tool",
+            "1.0.0",
+            &limits,
+        )
+        .await
+        .unwrap();
+        assert_eq!(files, vec![project.join("dist/tool-1.0.0.tgz")]);
+    }
+
+    #[tokio::test]
+    async fn test_release_artifacts_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let limits = CommandLimits::default();
+
+        let err = release_artifacts(project, project, None, "homebrew", "tool", "1.0.0", &limits)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "no default artifacts for homebrew");
+
+        let err = release_artifacts(
+            project,
+            project,
+            Some(&["dist/*.zip".to_string()]),
+            "npm",
+            "tool",
+            "1.0.0",
+            &limits,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_string(), "no artifacts found");
+
+        let err = build_package(
+            project,
+            HostedPackageFormat::Deb,
+            &project.join("out"),
+            "tool",
+            "1.0.0",
+            &limits,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("deb packages are not built here")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let limits = CommandLimits::default();
+
+        let stdout = run(temp_dir.path(), &limits, "sh", &["-c", "echo packed"])
+            .await
+            .unwrap();
+        assert_eq!(stdout, "packed\n");
+
+        let err = run(
+            temp_dir.path(),
+            &limits,
+            "sh",
+            &["-c", "echo broken manifest >&2; exit 1"],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_string(), "sh -c failed: broken manifest");
+
+        let err = run(
+            temp_dir.path(),
+            &limits,
+            "package-publisher-missing-tool",
+            &[],
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to run package-publisher-missing-tool:")
+        );
+    }
+}
@@ -0,0 +1,510 @@
+//! Julia Plugin - Julia General registry registration
+//!
+//! Packages enter the General registry through a pull request opened by the
+//! Registrator bot and merged by AutoMerge (new packages wait for human
+//! review), so this plugin submits a registration request instead of
+//! publishing directly:
+//! - Project.toml detection and validation (name, UUID, `[compat]` bounds)
+//! - `Pkg.test()` as the dry-run
+//! - Commenting `@JuliaRegistrator register` on the release commit
+//! - Reporting the comment URL (the package is not available until the PR merges)
+//! - Verification against the General registry `Versions.toml`
+
+use crate::core::config::JuliaRegistryConfig;
+use crate::core::git::run_git;
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::parse_github_repository;
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const GENERAL_REGISTRY_RAW_URL: &str =
+    "https://raw.githubusercontent.com/JuliaRegistries/General/master";
+
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+const REGISTRATOR_COMMAND: &str = "@JuliaRegistrator register";
+
+/// Standard libraries do not need `[compat]` entries
+const STDLIBS: &[&str] = &[
+    "Base64",
+    "CRC32c",
+    "Dates",
+    "DelimitedFiles",
+    "Distributed",
+    "FileWatching",
+    "InteractiveUtils",
+    "LazyArtifacts",
+    "LibGit2",
+    "Libdl",
+    "LinearAlgebra",
+    "Logging",
+    "Markdown",
+    "Mmap",
+    "Pkg",
+    "Printf",
+    "Profile",
+    "REPL",
+    "Random",
+    "SHA",
+    "Serialization",
+    "SharedArrays",
+    "Sockets",
+    "SparseArrays",
+    "Statistics",
+    "TOML",
+    "Test",
+    "UUIDs",
+    "Unicode",
+];
+
+/// Julia Project.toml (subset)
+#[derive(Debug, Clone, Deserialize)]
+pub struct JuliaProject {
+    pub name: Option<String>,
+    pub uuid: Option<String>,
+    pub version: Option<String>,
+    #[serde(default)]
+    pub deps: HashMap<String, String>,
+    #[serde(default)]
+    pub compat: HashMap<String, String>,
+}
+
+/// GitHub commit comment (subset)
+#[derive(Debug, Deserialize)]
+struct CommitComment {
+    html_url: String,
+}
+
+/// Environment variables Julia needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["JULIA_*"];
+
+/// Julia General registry plugin
+pub struct JuliaPlugin {
+    project_path: PathBuf,
+    config: JuliaRegistryConfig,
+    limits: CommandLimits,
+    github: GitHubClient,
+}
+
+impl Default for JuliaPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl JuliaPlugin {
+    /// Create a new Julia plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: JuliaRegistryConfig::default(),
+            limits: CommandLimits::default(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Apply `registries.julia` settings
+    pub fn with_config(mut self, config: JuliaRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run Julia with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    async fn load_project(&self) -> anyhow::Result<JuliaProject> {
+        let content = fs::read_to_string(self.project_path.join("Project.toml")).await?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    /// Validate `name`, `uuid` and `version`
+    fn validate_identity(&self, project: &JuliaProject, errors: &mut Vec<ValidationError>) {
+        match project.name {
+            Some(ref name) if name.ends_with(".jl") => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("julia.name_with_jl"),
+                severity: "error".to_string(),
+            }),
+            Some(ref name) if !Regex::new(r"^[A-Z][A-Za-z0-9]*$").unwrap().is_match(name) => errors
+                .push(ValidationError {
+                    field: "name".to_string(),
+                    message: tr!("julia.invalid_name", name = name),
+                    severity: "error".to_string(),
+                }),
+            Some(_) => {}
+            None => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("registry.name_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        let uuid =
+            Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$").unwrap();
+        match project.uuid {
+            Some(ref value) if !uuid.is_match(value) => errors.push(ValidationError {
+                field: "uuid".to_string(),
+                message: tr!("julia.invalid_uuid", uuid = value),
+                severity: "error".to_string(),
+            }),
+            Some(_) => {}
+            None => errors.push(ValidationError {
+                field: "uuid".to_string(),
+                message: tr!("julia.uuid_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match project.version {
+            Some(ref version) if semver::Version::parse(version).is_err() => {
+                errors.push(ValidationError {
+                    field: "version".to_string(),
+                    message: tr!("registry.invalid_semver", version = version),
+                    severity: "error".to_string(),
+                })
+            }
+            Some(_) => {}
+            None => errors.push(ValidationError {
+                field: "version".to_string(),
+                message: tr!("registry.version_required"),
+                severity: "error".to_string(),
+            }),
+        }
+    }
+
+    /// AutoMerge requires `[compat]` bounds for julia and every non-stdlib dependency
+    fn validate_compat(&self, project: &JuliaProject, errors: &mut Vec<ValidationError>) {
+        let mut missing: Vec<&str> = project
+            .deps
+            .keys()
+            .map(String::as_str)
+            .filter(|dep| !STDLIBS.contains(dep) && !project.compat.contains_key(*dep))
+            .collect();
+        missing.sort();
+        if !project.compat.contains_key("julia") {
+            missing.insert(0, "julia");
+        }
+
+        for dep in missing {
+            errors.push(ValidationError {
+                field: format!("compat.{}", dep),
+                message: tr!("julia.compat_missing", dependency = dep),
+                severity: "error".to_string(),
+            });
+        }
+    }
+
+    /// `owner/repo` from config or the `origin` remote
+    async fn repository(&self) -> anyhow::Result<String> {
+        if let Some(ref repository) = self.config.repository {
+            return Ok(repository.clone());
+        }
+
+        let url = run_git(&self.project_path, &["remote", "get-url", "origin"]).await?;
+        parse_github_repository(&url).ok_or_else(|| {
+            anyhow::anyhow!("{}", tr!("julia.repository_unknown", remote = url.trim()))
+        })
+    }
+
+    async fn run_julia(&self, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = self.limits.command("julia");
+        command.args(args).current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}{}", stdout, stderr);
+        }
+
+        Ok(stdout + &stderr)
+    }
+}
+
+/// Path of a package's `Versions.toml` in the General registry
+fn versions_toml_url(name: &str) -> String {
+    let letter = name
+        .chars()
+        .next()
+        .map(|c| c.to_ascii_uppercase())
+        .unwrap_or('_');
+    format!(
+        "{}/{}/{}/Versions.toml",
+        GENERAL_REGISTRY_RAW_URL, letter, name
+    )
+}
+
+#[async_trait]
+impl RegistryPlugin for JuliaPlugin {
+    fn name(&self) -> &str {
+        "julia"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        let path = Path::new(project_path).join("Project.toml");
+        let Ok(content) = fs::read_to_string(&path).await else {
+            return Ok(false);
+        };
+        let project: JuliaProject = toml::from_str(&content)?;
+        Ok(project.name.is_some() && project.uuid.is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let project = self.load_project().await?;
+        self.validate_identity(&project, &mut errors);
+        self.validate_compat(&project, &mut errors);
+
+        if let Some(ref name) = project.name {
+            metadata.insert(
+                "packageName".to_string(),
+                serde_json::Value::String(name.clone()),
+            );
+        }
+        if let Some(ref version) = project.version {
+            metadata.insert(
+                "version".to_string(),
+                serde_json::Value::String(version.clone()),
+            );
+        }
+
+        if fs::metadata(self.project_path.join("LICENSE"))
+            .await
+            .is_err()
+            && fs::metadata(self.project_path.join("LICENSE.md"))
+                .await
+                .is_err()
+        {
+            warnings.push(ValidationWarning {
+                field: "LICENSE".to_string(),
+                message: tr!("julia.license_missing"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if std::env::var(self.token_env()).is_err() {
+            warnings.push(ValidationWarning {
+                field: "token".to_string(),
+                message: tr!("julia.token_missing", token_env = self.token_env()),
+                severity: "warning".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        match self
+            .run_julia(&["--project=.", "-e", "using Pkg; Pkg.test()"])
+            .await
+        {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "test".to_string(),
+                    message: tr!("julia.tests_failed"),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let project = self.load_project().await?;
+        let token = std::env::var(self.token_env())
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("registry.not_configured", name = self.token_env())
+                )
+            })?;
+        let repository = self.repository().await?;
+        let commit = run_git(&self.project_path, &["rev-parse", "HEAD"]).await?;
+
+        let response = self
+            .github
+            .authenticated(&token)
+            .request(
+                reqwest::Method::POST,
+                &format!("/repos/{}/commits/{}/comments", repository, commit),
+            )
+            .json(&serde_json::json!({ "body": REGISTRATOR_COMMAND }))
+            .send_rate_limited()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("julia.register_failed", status = status, body = body)),
+                metadata: None,
+                error_kind: None,
+            });
+        }
+
+        let comment = response.json::<CommitComment>().await?;
+        let mut metadata = HashMap::new();
+        metadata.insert("pendingReview".to_string(), serde_json::Value::Bool(true));
+        metadata.insert(
+            "submissionUrl".to_string(),
+            serde_json::Value::String(comment.html_url.clone()),
+        );
+
+        Ok(PublishResult {
+            success: true,
+            version: project.version,
+            package_url: Some(comment.html_url.clone()),
+            output: Some(tr!(
+                "julia.registration_requested",
+                command = REGISTRATOR_COMMAND,
+                repository = repository,
+                commit = &commit[..commit.len().min(7)]
+            )),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let project = self.load_project().await?;
+        let name = project
+            .name
+            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+        let expected_version = project
+            .version
+            .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+        let url = format!("https://juliahub.com/ui/Packages/General/{}", name);
+
+        let registered = match http_client()
+            .get(versions_toml_url(&name))
+            .send_rate_limited()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response
+                .text()
+                .await
+                .ok()
+                .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+                .is_some_and(|versions| versions.contains_key(&expected_version)),
+            Ok(_) => false,
+            Err(e) => {
+                return Ok(VerificationResult {
+                    verified: false,
+                    version: Some(expected_version),
+                    url: Some(url),
+                    error: Some(tr!("registry.verify_failed", error = e)),
+                    metadata: None,
+                    integrity: None,
+                });
+            }
+        };
+
+        Ok(VerificationResult {
+            verified: registered,
+            version: Some(expected_version.clone()),
+            url: Some(url),
+            error: (!registered).then(|| tr!("julia.not_registered", version = expected_version)),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_versions_toml_url() {
+        assert_eq!(
+            versions_toml_url("Example"),
+            "https://raw.githubusercontent.com/JuliaRegistries/General/master/E/Example/Versions.toml"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_project_compat() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Project.toml"),
+            r#"
+name = "Example"
+uuid = "7876af07-990d-54b4-ab0e-23690620f79a"
+version = "0.5.4"
+
+[deps]
+JSON = "682c06a0-de6a-54ab-a142-c8b1cf79cde6"
+LinearAlgebra = "37e2e46d-f89d-539d-b4ee-838fcccc9c8e"
+
+[compat]
+julia = "1.6"
+"#,
+        )
+        .unwrap();
+
+        let plugin = JuliaPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.validate().await.unwrap();
+
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].field, "compat.JSON");
+        assert!(result.warnings.iter().any(|w| w.field == "LICENSE"));
+    }
+}
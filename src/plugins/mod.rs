@@ -1,11 +1,55 @@
+pub mod aur_plugin;
+pub mod bazel_plugin;
+pub mod bucket_plugin;
+pub mod cargo_registry;
+pub mod cloudsmith_plugin;
+pub mod cpan_plugin;
+pub mod cran_plugin;
 pub mod crates_io_plugin;
+pub mod custom_command_plugin;
+pub mod debian_plugin;
+pub mod dynamic_plugin;
+pub mod expo_plugin;
+pub mod gemfury_plugin;
+pub mod github_packages_plugin;
+pub mod hackage_plugin;
 pub mod homebrew_plugin;
+pub mod hosted_package;
+pub mod julia_plugin;
 pub mod npm_plugin;
+pub mod npm_registry;
 pub mod plugin_loader;
 pub mod pypi_plugin;
+pub mod repository_manager_plugin;
+pub mod subprocess_plugin;
+pub mod trusted_publishing;
+pub mod upm_plugin;
+pub mod windows_manifest_plugin;
 
+pub use aur_plugin::AurPlugin;
+pub use bazel_plugin::BazelPlugin;
+pub use bucket_plugin::BucketPlugin;
+pub use cargo_registry::CargoRegistry;
+pub use cloudsmith_plugin::CloudsmithPlugin;
+pub use cpan_plugin::CpanPlugin;
+pub use cran_plugin::CranPlugin;
 pub use crates_io_plugin::CratesIoPlugin;
+pub use custom_command_plugin::CustomCommandPlugin;
+pub use debian_plugin::{DebianPlugin, DebianTarget};
+pub use dynamic_plugin::DynamicPlugin;
+pub use expo_plugin::ExpoPlugin;
+pub use gemfury_plugin::GemfuryPlugin;
+pub use github_packages_plugin::{GitHubPackageKind, GitHubPackagesPlugin};
+pub use hackage_plugin::HackagePlugin;
 pub use homebrew_plugin::HomebrewPlugin;
+pub use hosted_package::HostedPackageFormat;
+pub use julia_plugin::JuliaPlugin;
 pub use npm_plugin::NpmPlugin;
+pub use npm_registry::{NpmRegistry, Npmrc};
 pub use plugin_loader::{DetectedPlugin, PluginLoader, RegistryType};
 pub use pypi_plugin::PyPiPlugin;
+pub use repository_manager_plugin::{RepositoryManagerKind, RepositoryManagerPlugin};
+pub use subprocess_plugin::SubprocessPlugin;
+pub use trusted_publishing::{CiEnvironment, OidcEnvironment, TrustedPublishingToken};
+pub use upm_plugin::UpmPlugin;
+pub use windows_manifest_plugin::{WindowsManifestKind, WindowsManifestPlugin};
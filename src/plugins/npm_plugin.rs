@@ -5,23 +5,40 @@
 //! - NPM naming rules enforcement
 //! - SemVer version validation
 //! - npm audit integration
-//! - Dry-run and publish operations
-//! - Package verification on npmjs.com
+//! - Dry-run and publish operations (with an optional public API diff)
+//! - Package verification on npmjs.com or the configured private registry
+//!   (`publishConfig.registry`, `.npmrc`)
 //! - Rollback with unpublish/deprecate
 
+use crate::core::config::NPMRegistryConfig;
+use crate::core::error::{PublishError, PublishErrorKind};
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::retry::{RetryManager, RetryOptions};
 use crate::core::traits::{
-    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
-    ValidationWarning, VerificationResult,
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, RollbackResult,
+    ValidationError, ValidationResult, ValidationWarning, VerificationResult,
 };
+use crate::orchestration::otp::OTP_REQUIRED;
+use crate::plugins::npm_registry::{NpmRegistry, Npmrc};
+use crate::security::command_executor::{
+    CI_IDENTITY_ENV, DEFAULT_REGISTRY_COMMAND_TIMEOUT, EnvSandbox, output_with_timeout,
+};
+use crate::tr;
+use crate::validation::api_report::{self, ApiReport};
+use crate::validation::version_validator::VersionValidator;
 use async_trait::async_trait;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
+use std::time::Duration;
 use tokio::fs;
 use tokio::process::Command;
 
+/// Environment variables npm needs on top of the sandbox allowlist
+pub(crate) const NPM_ENV: &[&str] = &["NPM_TOKEN", "NODE_*", "NPM_CONFIG_*", "npm_config_*"];
+
 /// Package.json structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageJson {
@@ -37,6 +54,15 @@ pub struct PackageJson {
     pub dependencies: Option<HashMap<String, String>>,
     #[serde(rename = "devDependencies", skip_serializing_if = "Option::is_none")]
     pub dev_dependencies: Option<HashMap<String, String>>,
+    #[serde(rename = "publishConfig", skip_serializing_if = "Option::is_none")]
+    pub publish_config: Option<NpmPublishConfig>,
+}
+
+/// `publishConfig` in package.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NpmPublishConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
 }
 
 /// NPM audit response
@@ -53,11 +79,21 @@ struct NpmRegistryInfo {
     versions: HashMap<String, serde_json::Value>,
     #[serde(rename = "dist-tags", default)]
     dist_tags: HashMap<String, String>,
+    /// Publish timestamps keyed by version
+    #[serde(default)]
+    time: HashMap<String, String>,
 }
 
+/// npm allows unpublishing within 72 hours of publishing
+const UNPUBLISH_WINDOW_HOURS: i64 = 72;
+
 /// NPM registry plugin
 pub struct NpmPlugin {
     project_path: PathBuf,
+    config: NPMRegistryConfig,
+    retry: RetryOptions,
+    sandbox: Option<EnvSandbox>,
+    command_timeout: Duration,
 }
 
 impl Default for NpmPlugin {
@@ -69,7 +105,86 @@ impl Default for NpmPlugin {
 impl NpmPlugin {
     /// Create a new NPM plugin instance
     pub fn new(project_path: PathBuf) -> Self {
-        Self { project_path }
+        Self {
+            project_path,
+            config: NPMRegistryConfig::default(),
+            retry: RetryOptions::default(),
+            sandbox: None,
+            command_timeout: DEFAULT_REGISTRY_COMMAND_TIMEOUT,
+        }
+    }
+
+    /// Apply `registries.npm` settings
+    pub fn with_config(mut self, config: NPMRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Retry npm commands and registry requests on transient failures
+    pub fn with_retry(mut self, retry: RetryOptions) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run npm commands with a minimal environment (npm's own variables and
+    /// the CI identity for `--provenance` stay available)
+    pub fn with_sandbox(mut self, sandbox: EnvSandbox) -> Self {
+        self.sandbox = Some(sandbox.with_allowed(NPM_ENV).with_allowed(CI_IDENTITY_ENV));
+        self
+    }
+
+    /// Kill npm commands running longer than `timeout`, with the processes
+    /// they spawned
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// Diff the public API against the published release (`apiReport`)
+    ///
+    /// Returns the Markdown diff and the problems found; a package that was
+    /// never published has nothing to compare against.
+    async fn api_diff(&self) -> anyhow::Result<Option<(String, Vec<String>)>> {
+        let content = fs::read_to_string(self.project_path.join("package.json")).await?;
+        let pkg: PackageJson = serde_json::from_str(&content)?;
+        let (Some(name), Some(current)) = (pkg.name, pkg.version) else {
+            return Ok(None);
+        };
+
+        let baseline = match self
+            .config
+            .api_report
+            .as_ref()
+            .and_then(|c| c.baseline_version.clone())
+        {
+            Some(version) => version,
+            None => {
+                let mut command = self.sandboxed_npm().await;
+                command
+                    .args(["view", &name, "version"])
+                    .args(self.registry().await.args())
+                    .current_dir(&self.project_path);
+                let output = output_with_timeout(&mut command, self.command_timeout).await?;
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                if !output.status.success() || version.is_empty() {
+                    return Ok(None);
+                }
+                version
+            }
+        };
+
+        let entry = api_report::types_entry(&self.project_path).await?;
+        let current_report = ApiReport::from_entry(&entry).await?;
+
+        let work_dir =
+            std::env::temp_dir().join(format!("package-publisher-api-{}", uuid::Uuid::new_v4()));
+        let previous_report =
+            api_report::published_report(&self.project_path, &name, &baseline, &work_dir).await;
+        let _ = fs::remove_dir_all(&work_dir).await;
+
+        let diff = previous_report?.diff(&current_report);
+        let problems = diff.check_bump(&baseline, &current).into_iter().collect();
+        Ok(Some((diff.to_markdown(&baseline, &current), problems)))
     }
 
     /// Validate package name according to NPM rules
@@ -81,7 +196,7 @@ impl NpmPlugin {
         if name.len() > 214 {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "パッケージ名は214文字以内である必要があります".to_string(),
+                message: tr!("registry.name_too_long_214"),
                 severity: "error".to_string(),
             });
         }
@@ -98,9 +213,7 @@ impl NpmPlugin {
         if !valid_chars_regex.is_match(name_without_scope) {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message:
-                    "パッケージ名は小文字英数字とハイフン、アンダースコア、ドットのみ使用可能です"
-                        .to_string(),
+                message: tr!("npm.invalid_name"),
                 severity: "error".to_string(),
             });
         }
@@ -109,8 +222,7 @@ impl NpmPlugin {
         if name_without_scope.starts_with('.') || name_without_scope.starts_with('_') {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "パッケージ名はドットまたはアンダースコアで始めることはできません"
-                    .to_string(),
+                message: tr!("npm.name_leading_dot"),
                 severity: "error".to_string(),
             });
         }
@@ -119,7 +231,7 @@ impl NpmPlugin {
         if name.chars().any(|c| c.is_uppercase()) {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "パッケージ名に大文字を含めることはできません".to_string(),
+                message: tr!("npm.name_uppercase"),
                 severity: "error".to_string(),
             });
         }
@@ -129,7 +241,7 @@ impl NpmPlugin {
         if !url_safe_regex.is_match(name) {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "パッケージ名はURL安全な文字のみ使用可能です".to_string(),
+                message: tr!("npm.name_not_url_safe"),
                 severity: "error".to_string(),
             });
         }
@@ -149,13 +261,12 @@ impl NpmPlugin {
 
     /// Run npm audit and collect vulnerabilities
     async fn run_npm_audit(&self) -> anyhow::Result<Option<ValidationWarning>> {
-        let output = Command::new("npm")
+        let mut command = self.sandboxed_npm().await;
+        command
             .args(["audit", "--json"])
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+            .args(self.registry().await.args())
+            .current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
 
         if !output.status.success() {
             // Try to parse audit output
@@ -164,10 +275,7 @@ impl NpmPlugin {
                 if vuln_count > 0 {
                     return Ok(Some(ValidationWarning {
                         field: "dependencies".to_string(),
-                        message: format!(
-                            "{}件の脆弱性が検出されました。npm audit fixで修正を推奨します",
-                            vuln_count
-                        ),
+                        message: tr!("npm.vulnerabilities", count = vuln_count),
                         severity: "warning".to_string(),
                     }));
                 }
@@ -179,13 +287,11 @@ impl NpmPlugin {
 
     /// Run npm script if it exists
     async fn run_script(&self, script_name: &str) -> anyhow::Result<()> {
-        let output = Command::new("npm")
+        let mut command = self.sandboxed_npm().await;
+        command
             .args(["run", script_name])
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+            .current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -195,15 +301,47 @@ impl NpmPlugin {
         Ok(())
     }
 
+    /// npm command with the sandbox applied; variables referenced in
+    /// `.npmrc` (e.g. `_authToken=${GITHUB_TOKEN}` of a scoped registry) stay
+    /// available
+    async fn sandboxed_npm(&self) -> Command {
+        let mut command = Command::new("npm");
+        if let Some(sandbox) = &self.sandbox {
+            let npmrc = Npmrc::load(&self.project_path).await;
+            let references: Vec<&str> = npmrc.env_references().collect();
+            sandbox
+                .clone()
+                .with_allowed(&references)
+                .apply(command.as_std_mut());
+        }
+        command
+    }
+
     /// Execute npm publish with retry
     async fn execute_npm_publish(&self, args: &[String]) -> anyhow::Result<String> {
-        let output = Command::new("npm")
+        RetryManager::new(self.retry.clone())
+            .retry_publish(
+                || self.execute_npm_once(args),
+                |e| tr!("registry.published_by_earlier_attempt", error = e),
+            )
+            .await
+    }
+
+    /// Execute an npm command other than publish with retry
+    async fn execute_npm(&self, args: &[String]) -> anyhow::Result<String> {
+        RetryManager::new(self.retry.clone())
+            .retry(|| self.execute_npm_once(args))
+            .await
+    }
+
+    /// Single attempt of an npm command
+    async fn execute_npm_once(&self, args: &[String]) -> anyhow::Result<String> {
+        let mut command = self.sandboxed_npm().await;
+        command
             .args(args)
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+            .args(self.registry().await.args())
+            .current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -211,9 +349,10 @@ impl NpmPlugin {
         if !output.status.success() {
             // Check for OTP requirement
             if stderr.contains("OTP") || stderr.contains("two-factor") {
-                anyhow::bail!(
-                    "2要素認証が必要です。--otpオプションでワンタイムパスワードを指定してください"
-                );
+                return Err(anyhow::Error::from(PublishError::OtpRequired {
+                    registry: "npm".to_string(),
+                })
+                .context(tr!("npm.otp_required")));
             }
             anyhow::bail!("{}", stderr);
         }
@@ -221,23 +360,108 @@ impl NpmPlugin {
         Ok(stdout + &stderr)
     }
 
-    /// Fetch package info from npm registry
+    /// Registry resolved from `publishConfig.registry` and `.npmrc`
+    async fn registry(&self) -> NpmRegistry {
+        let pkg = self.load_package_json().await.ok();
+        let npmrc = Npmrc::load(&self.project_path).await;
+        NpmRegistry::resolve(
+            pkg.as_ref().and_then(|p| p.name.as_deref()),
+            pkg.as_ref()
+                .and_then(|p| p.publish_config.as_ref())
+                .and_then(|c| c.registry.as_deref()),
+            &npmrc,
+        )
+    }
+
+    /// Fetch package info from the configured npm registry
     async fn fetch_package_info(&self, package_name: &str) -> anyhow::Result<NpmRegistryInfo> {
-        let url = format!("https://registry.npmjs.org/{}", package_name);
-        let client = reqwest::Client::new();
-        let response = client.get(&url).send().await?;
+        RetryManager::new(self.retry.clone())
+            .retry(|| self.fetch_package_info_once(package_name))
+            .await
+    }
+
+    /// Single attempt of [`Self::fetch_package_info`]
+    async fn fetch_package_info_once(&self, package_name: &str) -> anyhow::Result<NpmRegistryInfo> {
+        let registry = self.registry().await;
+        let client = http_client();
+        let mut request = client.get(registry.metadata_url(package_name));
+        if let Some(token) = registry.auth_token() {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send_rate_limited().await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
-                "パッケージ {} が npmjs.com で見つかりません（HTTP {}）",
-                package_name,
-                response.status()
+                "{}",
+                tr!(
+                    "registry.package_not_found",
+                    package = package_name,
+                    registry = registry.display_name(),
+                    status = response.status()
+                )
             );
         }
 
         let info = response.json::<NpmRegistryInfo>().await?;
         Ok(info)
     }
+
+    /// Detect a backport release (version lower than the current `latest` dist-tag)
+    ///
+    /// Returns `(latest, channel)` where channel is the dist-tag to use instead,
+    /// or `None` for new packages, registry errors and regular releases.
+    async fn detect_backport(&self, package_name: &str, version: &str) -> Option<(String, String)> {
+        let info = self.fetch_package_info(package_name).await.ok()?;
+        let latest = info.dist_tags.get("latest")?.clone();
+        let channel = VersionValidator::new().backport_channel(version, &latest)?;
+        Some((latest, channel))
+    }
+
+    /// Load package.json from the project directory
+    async fn load_package_json(&self) -> anyhow::Result<PackageJson> {
+        let content = fs::read_to_string(self.project_path.join("package.json")).await?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Prereleases superseded by `version` that are not yet deprecated (sorted)
+    fn filter_superseded(info: &NpmRegistryInfo, version: &str) -> Vec<String> {
+        let validator = VersionValidator::new();
+        let mut versions: Vec<String> = info
+            .versions
+            .iter()
+            .filter(|(v, doc)| {
+                validator.is_superseded_prerelease(v, version) && doc.get("deprecated").is_none()
+            })
+            .map(|(v, _)| v.clone())
+            .collect();
+        versions.sort();
+        versions
+    }
+
+    /// Whole hours since a version was published, from the registry `time` map
+    fn hours_since_publish(
+        info: &NpmRegistryInfo,
+        version: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<i64> {
+        let published = chrono::DateTime::parse_from_rfc3339(info.time.get(version)?).ok()?;
+        Some((now - published.with_timezone(&chrono::Utc)).num_hours())
+    }
+
+    /// Extract integrity data from a version document (`versions[<version>].dist`)
+    fn extract_integrity(version_info: &serde_json::Value) -> Option<PackageIntegrity> {
+        let dist = version_info.get("dist")?;
+        let field = |key: &str| dist.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let integrity = PackageIntegrity {
+            download_url: field("tarball"),
+            shasum: field("shasum"),
+            integrity: field("integrity"),
+            checksum: None,
+        };
+
+        (integrity.download_url.is_some() || integrity.has_hash()).then_some(integrity)
+    }
 }
 
 #[async_trait]
@@ -269,7 +493,7 @@ impl RegistryPlugin for NpmPlugin {
         if pkg.name.is_none() {
             errors.push(ValidationError {
                 field: "name".to_string(),
-                message: "nameは必須フィールドです".to_string(),
+                message: tr!("registry.name_required"),
                 severity: "error".to_string(),
             });
         }
@@ -277,7 +501,7 @@ impl RegistryPlugin for NpmPlugin {
         if pkg.version.is_none() {
             errors.push(ValidationError {
                 field: "version".to_string(),
-                message: "versionは必須フィールドです".to_string(),
+                message: tr!("registry.version_required"),
                 severity: "error".to_string(),
             });
         }
@@ -297,7 +521,7 @@ impl RegistryPlugin for NpmPlugin {
             if !self.is_valid_semver(version) {
                 errors.push(ValidationError {
                     field: "version".to_string(),
-                    message: format!("無効なSemVer形式: {}", version),
+                    message: tr!("registry.invalid_semver", version = version),
                     severity: "error".to_string(),
                 });
             }
@@ -307,11 +531,17 @@ impl RegistryPlugin for NpmPlugin {
             );
         }
 
+        let registry = self.registry().await;
+        metadata.insert(
+            "registry".to_string(),
+            serde_json::Value::String(registry.url.clone()),
+        );
+
         // Validate license
         if pkg.license.is_none() {
             warnings.push(ValidationWarning {
                 field: "license".to_string(),
-                message: "ライセンスフィールドの指定を推奨します".to_string(),
+                message: tr!("registry.license_recommended"),
                 severity: "warning".to_string(),
             });
         }
@@ -328,7 +558,7 @@ impl RegistryPlugin for NpmPlugin {
             {
                 errors.push(ValidationError {
                     field: "scripts.build".to_string(),
-                    message: format!("ビルドスクリプトの実行に失敗: {}", e),
+                    message: tr!("npm.build_failed", error = e),
                     severity: "error".to_string(),
                 });
             }
@@ -339,7 +569,7 @@ impl RegistryPlugin for NpmPlugin {
             {
                 errors.push(ValidationError {
                     field: "scripts.test".to_string(),
-                    message: format!("テストの実行に失敗: {}", e),
+                    message: tr!("npm.test_failed", error = e),
                     severity: "error".to_string(),
                 });
             }
@@ -349,14 +579,14 @@ impl RegistryPlugin for NpmPlugin {
                 if let Err(e) = self.run_script("lint").await {
                     warnings.push(ValidationWarning {
                         field: "scripts.lint".to_string(),
-                        message: format!("Lintエラーが検出されました: {}", e),
+                        message: tr!("npm.lint_failed", error = e),
                         severity: "warning".to_string(),
                     });
                 }
             } else {
                 warnings.push(ValidationWarning {
                     field: "scripts.lint".to_string(),
-                    message: "lintスクリプトの設定を推奨します".to_string(),
+                    message: tr!("npm.lint_recommended"),
                     severity: "warning".to_string(),
                 });
             }
@@ -375,13 +605,12 @@ impl RegistryPlugin for NpmPlugin {
     }
 
     async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
-        let output = Command::new("npm")
+        let mut command = self.sandboxed_npm().await;
+        command
             .args(["publish", "--dry-run"])
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await?;
+            .args(self.registry().await.args())
+            .current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -394,25 +623,65 @@ impl RegistryPlugin for NpmPlugin {
                 estimated_size: None,
                 errors: Some(vec![ValidationError {
                     field: "publish".to_string(),
-                    message: format!("Dry-runに失敗: {}", combined_output),
+                    message: tr!("registry.dry_run_failed", output = combined_output),
                     severity: "error".to_string(),
                 }]),
             });
         }
 
         // Extract package size
-        let size_regex = Regex::new(r"package size:\s*(\S+)").unwrap();
+        let size_regex = Regex::new(r"package size:\s*([\d.]+\s*[A-Za-z]+)").unwrap();
         let estimated_size = size_regex
             .captures(&combined_output)
             .and_then(|cap| cap.get(1))
             .map(|m| m.as_str().to_string());
 
-        Ok(DryRunResult {
+        let mut result = DryRunResult {
             success: true,
             output: combined_output,
             estimated_size,
             errors: None,
-        })
+        };
+
+        let Some(ref api_config) = self.config.api_report else {
+            return Ok(result);
+        };
+        if api_config.enabled != Some(true) {
+            return Ok(result);
+        }
+        match self.api_diff().await {
+            Ok(Some((markdown, problems))) => {
+                result.output.push('\n');
+                result.output.push_str(&markdown);
+                if problems.is_empty() {
+                    return Ok(result);
+                }
+                if api_config.level.as_deref() == Some("warning") {
+                    for problem in problems {
+                        result.output.push_str(&format!("\n⚠️  {}", problem));
+                    }
+                } else {
+                    result.success = false;
+                    result.errors = Some(
+                        problems
+                            .into_iter()
+                            .map(|message| ValidationError {
+                                field: "types".to_string(),
+                                message,
+                                severity: "error".to_string(),
+                            })
+                            .collect(),
+                    );
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                result
+                    .output
+                    .push_str(&tr!("npm.api_report_failed", error = e));
+            }
+        }
+        Ok(result)
     }
 
     async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
@@ -439,17 +708,57 @@ impl RegistryPlugin for NpmPlugin {
             args.push(access.clone());
         }
 
-        // Add tag
-        if let Some(ref tag) = opts.tag {
+        // Backport releases must not move the `latest` dist-tag
+        let backport = match (pkg.name.as_deref(), pkg.version.as_deref()) {
+            (Some(name), Some(version)) => self.detect_backport(name, version).await,
+            _ => None,
+        };
+
+        let mut metadata = None;
+        if let Some((ref latest, ref channel)) = backport {
+            if opts.tag.as_deref() == Some("latest") {
+                return Ok(PublishResult {
+                    success: false,
+                    version: pkg.version.clone(),
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!(
+                        "npm.older_than_latest",
+                        version = pkg.version.as_deref().unwrap_or_default(),
+                        latest = latest,
+                        suggested = channel
+                    )),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+
+            let dist_tag = opts.tag.clone().unwrap_or_else(|| channel.clone());
+            metadata = Some(HashMap::from([
+                ("backport".to_string(), serde_json::Value::Bool(true)),
+                (
+                    "latestVersion".to_string(),
+                    serde_json::Value::String(latest.clone()),
+                ),
+                ("distTag".to_string(), serde_json::Value::String(dist_tag)),
+            ]));
+        }
+
+        // Add tag (backports default to their release line, e.g. `1.x`)
+        let tag = opts
+            .tag
+            .clone()
+            .or_else(|| backport.map(|(_, channel)| channel));
+        if let Some(tag) = tag {
             args.push("--tag".to_string());
-            args.push(tag.clone());
+            args.push(tag);
         }
 
         match self.execute_npm_publish(&args).await {
             Ok(output) => {
                 let package_name = pkg.name.unwrap_or_else(|| "unknown".to_string());
                 let version = pkg.version.clone();
-                let package_url = format!("https://www.npmjs.com/package/{}", package_name);
+                let package_url = self.registry().await.package_url(&package_name);
 
                 Ok(PublishResult {
                     success: true,
@@ -457,17 +766,28 @@ impl RegistryPlugin for NpmPlugin {
                     package_url: Some(package_url),
                     output: Some(output),
                     error: None,
-                    metadata: None,
+                    metadata,
+                    error_kind: None,
+                })
+            }
+            Err(e) => {
+                // Lets the publisher retry with a fresh one-time password
+                let otp_required = matches!(
+                    e.downcast_ref::<PublishError>(),
+                    Some(PublishError::OtpRequired { .. })
+                );
+                Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(e.to_string()),
+                    metadata: otp_required.then(|| {
+                        HashMap::from([(OTP_REQUIRED.to_string(), serde_json::Value::Bool(true))])
+                    }),
+                    error_kind: Some(PublishErrorKind::of(&e)),
                 })
             }
-            Err(e) => Ok(PublishResult {
-                success: false,
-                version: None,
-                package_url: None,
-                output: None,
-                error: Some(e.to_string()),
-                metadata: None,
-            }),
         }
     }
 
@@ -479,11 +799,14 @@ impl RegistryPlugin for NpmPlugin {
 
         let package_name = pkg
             .name
-            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("npm.name_not_found")))?;
         let expected_version = pkg
             .version
             .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
 
+        let registry = self.registry().await;
+        let package_url = registry.package_url(&package_name);
+
         match self.fetch_package_info(&package_name).await {
             Ok(info) => {
                 // Check if expected version exists
@@ -492,17 +815,24 @@ impl RegistryPlugin for NpmPlugin {
                     return Ok(VerificationResult {
                         verified: false,
                         version: Some(expected_version.clone()),
-                        url: Some(format!("https://www.npmjs.com/package/{}", package_name)),
-                        error: Some(format!(
-                            "バージョン {} が npmjs.com で見つかりません。利用可能なバージョン: {}",
-                            expected_version, available
+                        url: Some(package_url.clone()),
+                        error: Some(tr!(
+                            "registry.version_not_found_available",
+                            version = expected_version,
+                            registry = registry.display_name(),
+                            available = available
                         )),
                         metadata: None,
+                        integrity: None,
                     });
                 }
 
                 let latest_version = info.dist_tags.get("latest").cloned();
                 let all_versions: Vec<String> = info.versions.keys().cloned().collect();
+                let integrity = info
+                    .versions
+                    .get(&expected_version)
+                    .and_then(Self::extract_integrity);
 
                 let mut metadata = HashMap::new();
                 if let Some(ref latest) = latest_version {
@@ -524,17 +854,97 @@ impl RegistryPlugin for NpmPlugin {
                 Ok(VerificationResult {
                     verified: true,
                     version: Some(expected_version),
-                    url: Some(format!("https://www.npmjs.com/package/{}", package_name)),
+                    url: Some(package_url),
                     error: None,
                     metadata: Some(metadata),
+                    integrity,
                 })
             }
             Err(e) => Ok(VerificationResult {
                 verified: false,
                 version: Some(expected_version),
-                url: Some(format!("https://www.npmjs.com/package/{}", package_name)),
-                error: Some(format!("検証に失敗: {}", e)),
+                url: Some(package_url),
+                error: Some(tr!("registry.verify_failed", error = e)),
                 metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+
+    /// Unpublish within npm's 72-hour window, otherwise deprecate
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        let package_name = self
+            .load_package_json()
+            .await?
+            .name
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("npm.name_not_found")))?;
+        let full_name = format!("{}@{}", package_name, version);
+
+        let hours = self
+            .fetch_package_info(&package_name)
+            .await
+            .ok()
+            .and_then(|info| Self::hours_since_publish(&info, version, chrono::Utc::now()));
+
+        if let Some(hours) = hours
+            && hours <= UNPUBLISH_WINDOW_HOURS
+            && self
+                .execute_npm(&["unpublish".to_string(), full_name.clone()])
+                .await
+                .is_ok()
+        {
+            return Ok(RollbackResult {
+                success: true,
+                message: tr!("npm.unpublished", package = full_name, hours = hours),
+                error: None,
+            });
+        }
+
+        self.deprecate(
+            version,
+            "This version has been deprecated. Please use a newer version.",
+        )
+        .await
+    }
+
+    async fn superseded_prereleases(&self, version: &str) -> anyhow::Result<Vec<String>> {
+        let package_name = self
+            .load_package_json()
+            .await?
+            .name
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("npm.name_not_found")))?;
+        let info = self.fetch_package_info(&package_name).await?;
+
+        Ok(Self::filter_superseded(&info, version))
+    }
+
+    async fn deprecate(&self, version: &str, message: &str) -> anyhow::Result<RollbackResult> {
+        let package_name = self
+            .load_package_json()
+            .await?
+            .name
+            .ok_or_else(|| anyhow::anyhow!("{}", tr!("npm.name_not_found")))?;
+
+        let args = vec![
+            "deprecate".to_string(),
+            format!("{}@{}", package_name, version),
+            message.to_string(),
+        ];
+
+        match self.execute_npm(&args).await {
+            Ok(_) => Ok(RollbackResult {
+                success: true,
+                message: tr!("npm.deprecated", package = package_name, version = version),
+                error: None,
+            }),
+            Err(e) => Ok(RollbackResult {
+                success: false,
+                message: tr!(
+                    "npm.deprecate_failed",
+                    package = package_name,
+                    version = version
+                ),
+                error: Some(e.to_string()),
             }),
         }
     }
@@ -553,6 +963,71 @@ mod tests {
         assert_eq!(plugin.version(), "1.0.0");
     }
 
+    #[test]
+    fn test_hours_since_publish() {
+        let info: NpmRegistryInfo = serde_json::from_value(serde_json::json!({
+            "time": { "1.0.0": "2024-01-01T00:00:00.000Z" }
+        }))
+        .unwrap();
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-03T12:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(
+            NpmPlugin::hours_since_publish(&info, "1.0.0", now),
+            Some(60)
+        );
+        assert_eq!(NpmPlugin::hours_since_publish(&info, "2.0.0", now), None);
+    }
+
+    #[test]
+    fn test_filter_superseded() {
+        let info: NpmRegistryInfo = serde_json::from_value(serde_json::json!({
+            "dist-tags": { "latest": "1.1.0" },
+            "versions": {
+                "1.1.0": {},
+                "1.2.0-beta.1": {},
+                "1.2.0-beta.2": { "deprecated": "Superseded" },
+                "1.2.0-rc.1": {},
+                "1.3.0-alpha.1": {}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            NpmPlugin::filter_superseded(&info, "1.2.0"),
+            vec!["1.2.0-beta.1".to_string(), "1.2.0-rc.1".to_string()]
+        );
+        assert!(NpmPlugin::filter_superseded(&info, "1.2.0-rc.2").is_empty());
+    }
+
+    #[test]
+    fn test_extract_integrity() {
+        let version_info = serde_json::json!({
+            "name": "test-pkg",
+            "version": "1.0.0",
+            "dist": {
+                "tarball": "https://registry.npmjs.org/test-pkg/-/test-pkg-1.0.0.tgz",
+                "shasum": "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                "integrity": "sha512-abc123=="
+            }
+        });
+
+        let integrity = NpmPlugin::extract_integrity(&version_info).unwrap();
+        assert_eq!(
+            integrity.download_url.as_deref(),
+            Some("https://registry.npmjs.org/test-pkg/-/test-pkg-1.0.0.tgz")
+        );
+        assert_eq!(
+            integrity.shasum.as_deref(),
+            Some("da39a3ee5e6b4b0d3255bfef95601890afd80709")
+        );
+        assert_eq!(integrity.integrity.as_deref(), Some("sha512-abc123=="));
+        assert_eq!(integrity.checksum, None);
+
+        assert!(NpmPlugin::extract_integrity(&serde_json::json!({"version": "1.0.0"})).is_none());
+    }
+
     #[tokio::test]
     async fn test_detect_with_package_json() {
         let temp_dir = TempDir::new().unwrap();
@@ -692,4 +1167,102 @@ mod tests {
         // Note: May have warnings for missing scripts
         assert!(result.errors.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_verify_against_private_registry() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let body = r#"{"versions":{"1.2.0":{}},"dist-tags":{"latest":"1.2.0"}}"#;
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "@internal/ui", "version": "1.2.0"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join(".npmrc"),
+            format!(
+                "@internal:registry=http://{0}/npm\n//{0}/npm/:_authToken=verdaccio-token\n",
+                address
+            ),
+        )
+        .unwrap();
+
+        let plugin = NpmPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.verify().await.unwrap();
+        assert!(result.verified, "{:?}", result.error);
+        assert_eq!(
+            result.url,
+            Some(format!("http://{}/npm/@internal/ui", address))
+        );
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /npm/@internal%2fui "));
+        assert!(request.contains("authorization: Bearer verdaccio-token"));
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_keeps_npmrc_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".npmrc"),
+            "@acme:registry=https://npm.pkg.github.com/\n\
+             //npm.pkg.github.com/:_authToken=${PACKAGE_PUBLISHER_TEST_GH_TOKEN}\n",
+        )
+        .unwrap();
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_GH_TOKEN", "gh-token");
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_OTHER_SECRET", "other");
+        }
+
+        let plugin = NpmPlugin::new(temp_dir.path().to_path_buf())
+            .with_sandbox(EnvSandbox::new::<&str>(&[]));
+        let command = plugin.sandboxed_npm().await;
+        let envs: HashMap<_, _> = command
+            .as_std()
+            .get_envs()
+            .filter_map(|(name, value)| Some((name.to_str()?, value?.to_str()?)))
+            .collect();
+        assert_eq!(
+            envs.get("PACKAGE_PUBLISHER_TEST_GH_TOKEN"),
+            Some(&"gh-token")
+        );
+        assert!(!envs.contains_key("PACKAGE_PUBLISHER_TEST_OTHER_SECRET"));
+    }
+
+    #[test]
+    fn test_sandbox_keeps_ci_identity() {
+        let plugin = NpmPlugin::new(PathBuf::from(".")).with_sandbox(EnvSandbox::new::<&str>(&[]));
+        let sandbox = plugin.sandbox.unwrap();
+        for name in [
+            "ACTIONS_ID_TOKEN_REQUEST_URL",
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+            "GITHUB_REPOSITORY",
+            "NPM_TOKEN",
+        ] {
+            assert!(sandbox.allows(name), "{}", name);
+        }
+        assert!(!sandbox.allows("AWS_SECRET_ACCESS_KEY"));
+    }
 }
@@ -0,0 +1,294 @@
+//! npm registry resolution for private registries (Verdaccio, Artifactory, ...)
+//!
+//! The registry a package publishes to is resolved the way npm does it:
+//! 1. `publishConfig.registry` in package.json
+//! 2. `@scope:registry=` in `.npmrc` for scoped packages
+//! 3. `registry=` in `.npmrc`
+//! 4. `https://registry.npmjs.org/`
+//!
+//! `.npmrc` is read from the user's home directory and the project, with
+//! project settings taking precedence. Auth tokens
+//! (`//host/path/:_authToken=${NPM_TOKEN}`) are picked up for registry reads
+//! such as verification.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use tokio::fs;
+
+/// Public npm registry
+pub const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org/";
+
+/// Merged `.npmrc` settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Npmrc {
+    values: HashMap<String, String>,
+    /// Names of the `${VAR}` references (npm expands them itself, so
+    /// sandboxed npm commands need these variables)
+    env_references: BTreeSet<String>,
+}
+
+impl Npmrc {
+    /// Parse `.npmrc` content (`key=value` lines, `#`/`;` comments)
+    ///
+    /// `${VAR}` references are expanded from the environment; unset variables
+    /// are left as written.
+    pub fn parse(content: &str) -> Self {
+        let mut env_references = BTreeSet::new();
+        let values = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with(';'))
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                let value = value.trim().trim_matches('"').trim_matches('\'');
+                env_references.extend(env_names(value));
+                Some((key.trim().to_string(), expand_env(value)))
+            })
+            .collect();
+        Self {
+            values,
+            env_references,
+        }
+    }
+
+    /// `~/.npmrc` overridden by `<project>/.npmrc`
+    pub async fn load(project_path: &Path) -> Self {
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(std::path::PathBuf::from);
+
+        let mut npmrc = Self::default();
+        for path in home
+            .map(|h| h.join(".npmrc"))
+            .into_iter()
+            .chain([project_path.join(".npmrc")])
+        {
+            if let Ok(content) = fs::read_to_string(&path).await {
+                let parsed = Self::parse(&content);
+                npmrc.values.extend(parsed.values);
+                npmrc.env_references.extend(parsed.env_references);
+            }
+        }
+        npmrc
+    }
+
+    /// Environment variables referenced as `${VAR}`
+    pub fn env_references(&self) -> impl Iterator<Item = &str> {
+        self.env_references.iter().map(String::as_str)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Registry configured for a package (`@scope:registry`, then `registry`)
+    pub fn registry_for(&self, package_name: Option<&str>) -> Option<&str> {
+        package_name
+            .and_then(|name| name.strip_prefix('@'))
+            .and_then(|name| name.split_once('/'))
+            .and_then(|(scope, _)| self.get(&format!("@{}:registry", scope)))
+            .or_else(|| self.get("registry"))
+    }
+
+    /// `_authToken` of the most specific `//host/path/` entry matching `registry`
+    pub fn auth_token(&self, registry: &str) -> Option<String> {
+        let nerfed = registry
+            .split_once("//")
+            .map(|(_, rest)| format!("//{}", rest))?;
+        self.values
+            .iter()
+            .filter_map(|(key, value)| {
+                let prefix = format!(
+                    "{}/",
+                    key.strip_suffix(":_authToken")?.trim_end_matches('/')
+                );
+                (!value.is_empty() && nerfed.starts_with(&prefix)).then_some((prefix.len(), value))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, token)| token.clone())
+    }
+}
+
+/// Names of the `${VAR}` references in `value`
+fn env_names(value: &str) -> Vec<String> {
+    value
+        .split("${")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Replace `${VAR}` with environment values, leaving unset variables as is
+fn expand_env(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        result.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(var) => result.push_str(&var),
+            Err(_) => result.push_str(&rest[start..=start + end]),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Registry a package is published to and verified against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpmRegistry {
+    /// Registry URL, always with a trailing slash
+    pub url: String,
+    auth_token: Option<String>,
+}
+
+impl Default for NpmRegistry {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_NPM_REGISTRY.to_string(),
+            auth_token: None,
+        }
+    }
+}
+
+impl NpmRegistry {
+    /// Resolve from `publishConfig.registry` and `.npmrc`
+    pub fn resolve(
+        package_name: Option<&str>,
+        publish_config_registry: Option<&str>,
+        npmrc: &Npmrc,
+    ) -> Self {
+        let url = publish_config_registry
+            .filter(|r| !r.trim().is_empty())
+            .or_else(|| npmrc.registry_for(package_name))
+            .unwrap_or(DEFAULT_NPM_REGISTRY);
+        let url = format!("{}/", url.trim().trim_end_matches('/'));
+        let auth_token = npmrc.auth_token(&url);
+        Self { url, auth_token }
+    }
+
+    /// Whether this is the public npmjs.com registry
+    pub fn is_default(&self) -> bool {
+        self.url == DEFAULT_NPM_REGISTRY || self.url == "https://registry.npmjs.com/"
+    }
+
+    /// `--registry <url>` for npm commands (empty for the public registry)
+    pub fn args(&self) -> Vec<String> {
+        if self.is_default() {
+            Vec::new()
+        } else {
+            vec!["--registry".to_string(), self.url.clone()]
+        }
+    }
+
+    /// Name used in messages (`npmjs.com` or the registry host)
+    pub fn display_name(&self) -> String {
+        if self.is_default() {
+            return "npmjs.com".to_string();
+        }
+        reqwest::Url::parse(&self.url)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from))
+            .unwrap_or_else(|| self.url.clone())
+    }
+
+    /// Packument URL (`@scope/name` is encoded as `@scope%2fname`)
+    pub fn metadata_url(&self, package_name: &str) -> String {
+        format!("{}{}", self.url, package_name.replace('/', "%2f"))
+    }
+
+    /// Web page of the package
+    pub fn package_url(&self, package_name: &str) -> String {
+        if self.is_default() {
+            format!("https://www.npmjs.com/package/{}", package_name)
+        } else {
+            format!("{}{}", self.url, package_name)
+        }
+    }
+
+    pub fn auth_token(&self) -> Option<&str> {
+        self.auth_token.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution_order() {
+        let npmrc = Npmrc::parse(
+            "# company registries\n\
+             registry=https://npm.acme.dev/\n\
+             @internal:registry=https://verdaccio.acme.dev/private\n\
+             //verdaccio.acme.dev/:_authToken=base\n\
+             //verdaccio.acme.dev/private/:_authToken=\"scoped\"\n",
+        );
+
+        let scoped = NpmRegistry::resolve(Some("@internal/ui"), None, &npmrc);
+        assert_eq!(scoped.url, "https://verdaccio.acme.dev/private/");
+        assert_eq!(scoped.auth_token(), Some("scoped"));
+        assert_eq!(scoped.display_name(), "verdaccio.acme.dev");
+        assert_eq!(
+            scoped.metadata_url("@internal/ui"),
+            "https://verdaccio.acme.dev/private/@internal%2fui"
+        );
+        assert_eq!(
+            scoped.args(),
+            vec!["--registry", "https://verdaccio.acme.dev/private/"]
+        );
+
+        let unscoped = NpmRegistry::resolve(Some("ui"), None, &npmrc);
+        assert_eq!(unscoped.url, "https://npm.acme.dev/");
+        assert_eq!(unscoped.auth_token(), None);
+
+        let published = NpmRegistry::resolve(
+            Some("@internal/ui"),
+            Some("https://artifactory.acme.dev/api/npm/npm-local"),
+            &npmrc,
+        );
+        assert_eq!(
+            published.url,
+            "https://artifactory.acme.dev/api/npm/npm-local/"
+        );
+
+        let public = NpmRegistry::resolve(Some("ui"), None, &Npmrc::default());
+        assert!(public.is_default());
+        assert!(public.args().is_empty());
+        assert_eq!(public.package_url("ui"), "https://www.npmjs.com/package/ui");
+    }
+
+    #[test]
+    fn test_expands_environment_variables() {
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_NPM_TOKEN", "s3cret");
+        }
+        let npmrc = Npmrc::parse(
+            "//npm.acme.dev/:_authToken=${PACKAGE_PUBLISHER_TEST_NPM_TOKEN}\n\
+             email=${PACKAGE_PUBLISHER_TEST_UNSET_VAR}\n",
+        );
+        assert_eq!(
+            npmrc.auth_token("https://npm.acme.dev/"),
+            Some("s3cret".to_string())
+        );
+        assert_eq!(
+            npmrc.get("email"),
+            Some("${PACKAGE_PUBLISHER_TEST_UNSET_VAR}")
+        );
+        assert_eq!(
+            npmrc.env_references().collect::<Vec<_>>(),
+            [
+                "PACKAGE_PUBLISHER_TEST_NPM_TOKEN",
+                "PACKAGE_PUBLISHER_TEST_UNSET_VAR"
+            ]
+        );
+    }
+}
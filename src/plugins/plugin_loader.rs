@@ -18,7 +18,10 @@
 //! # }
 //! ```
 
+use crate::core::config::PluginConfig;
 use crate::core::traits::RegistryPlugin;
+use crate::plugins::dynamic_plugin::{DynamicPlugin, plugin_library_path};
+use crate::plugins::subprocess_plugin::SubprocessPlugin;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Arc;
@@ -32,6 +35,24 @@ pub enum RegistryType {
     Crates,
     PyPI,
     Homebrew,
+    #[serde(rename = "github-packages")]
+    GitHubPackages,
+    Upm,
+    Cran,
+    Julia,
+    Bazel,
+    Cpan,
+    Hackage,
+    Expo,
+    Bucket,
+    Artifactory,
+    Nexus,
+    Cloudsmith,
+    Gemfury,
+    Scoop,
+    Winget,
+    Aur,
+    Debian,
 }
 
 impl RegistryType {
@@ -42,6 +63,23 @@ impl RegistryType {
             RegistryType::Crates => "crates.io",
             RegistryType::PyPI => "pypi",
             RegistryType::Homebrew => "homebrew",
+            RegistryType::GitHubPackages => "github-packages",
+            RegistryType::Upm => "upm",
+            RegistryType::Cran => "cran",
+            RegistryType::Julia => "julia",
+            RegistryType::Bazel => "bazel",
+            RegistryType::Cpan => "cpan",
+            RegistryType::Hackage => "hackage",
+            RegistryType::Expo => "expo",
+            RegistryType::Bucket => "bucket",
+            RegistryType::Artifactory => "artifactory",
+            RegistryType::Nexus => "nexus",
+            RegistryType::Cloudsmith => "cloudsmith",
+            RegistryType::Gemfury => "gemfury",
+            RegistryType::Scoop => "scoop",
+            RegistryType::Winget => "winget",
+            RegistryType::Aur => "aur",
+            RegistryType::Debian => "debian",
         }
     }
 }
@@ -82,6 +120,9 @@ impl PluginLoader {
 
     /// Create a new plugin loader with a specific base path
     ///
+    /// Plugin libraries are looked up under `base_path` instead of the
+    /// project.
+    ///
     /// # Arguments
     ///
     /// * `base_path` - Base directory for plugin search
@@ -116,8 +157,13 @@ impl PluginLoader {
     pub async fn detect_plugins(&self, project_path: &Path) -> anyhow::Result<Vec<DetectedPlugin>> {
         let mut detected = Vec::new();
 
-        // Detect NPM (package.json)
-        if let Ok(npm_plugin) = self.detect_npm(project_path).await {
+        // Detect UPM (package.json with a "unity" field) and Expo apps before plain npm
+        if let Ok(upm_plugin) = self.detect_upm(project_path).await {
+            detected.push(upm_plugin);
+        } else if let Ok(expo_plugin) = self.detect_expo(project_path).await {
+            detected.push(expo_plugin);
+        } else if let Ok(npm_plugin) = self.detect_npm(project_path).await {
+            // Detect NPM (package.json)
             detected.push(npm_plugin);
         }
 
@@ -136,6 +182,41 @@ impl PluginLoader {
             detected.push(homebrew_plugin);
         }
 
+        // Detect GitHub Packages (npm publishConfig, Maven distributionManagement, OCI source label)
+        if let Ok(github_plugin) = self.detect_github_packages(project_path).await {
+            detected.push(github_plugin);
+        }
+
+        // Detect CRAN (DESCRIPTION)
+        if let Ok(cran_plugin) = self.detect_cran(project_path).await {
+            detected.push(cran_plugin);
+        }
+
+        // Detect Julia General registry (Project.toml)
+        if let Ok(julia_plugin) = self.detect_julia(project_path).await {
+            detected.push(julia_plugin);
+        }
+
+        // Detect Bazel Central Registry (MODULE.bazel)
+        if let Ok(bazel_plugin) = self.detect_bazel(project_path).await {
+            detected.push(bazel_plugin);
+        }
+
+        // Detect CPAN (dist.ini, Build.PL or Makefile.PL)
+        if let Ok(cpan_plugin) = self.detect_cpan(project_path).await {
+            detected.push(cpan_plugin);
+        }
+
+        // Detect Hackage (*.cabal)
+        if let Ok(hackage_plugin) = self.detect_hackage(project_path).await {
+            detected.push(hackage_plugin);
+        }
+
+        // Detect AUR (PKGBUILD in the project root or aur/)
+        if let Ok(aur_plugin) = self.detect_aur(project_path).await {
+            detected.push(aur_plugin);
+        }
+
         Ok(detected)
     }
 
@@ -158,6 +239,42 @@ impl PluginLoader {
         }
     }
 
+    /// Detect UPM plugin (Unity packages are not published to npmjs.com)
+    async fn detect_upm(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        let manifest_path = project_path.join("package.json");
+        let content = fs::read_to_string(&manifest_path).await?;
+        let json: serde_json::Value = serde_json::from_str(&content)?;
+
+        if json.get("unity").is_some() {
+            Ok(DetectedPlugin {
+                registry_type: RegistryType::Upm,
+                manifest_path: manifest_path.display().to_string(),
+                confidence: 1.0,
+            })
+        } else {
+            Err(anyhow::anyhow!("package.json has no unity field"))
+        }
+    }
+
+    /// Detect Expo plugin (apps ship as OTA updates, not npm packages)
+    async fn detect_expo(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::expo_plugin::ExpoPlugin;
+
+        if !ExpoPlugin::is_expo_app(project_path).await {
+            return Err(anyhow::anyhow!("No Expo app config found"));
+        }
+
+        let manifest = ["app.json", "app.config.js", "app.config.ts"]
+            .into_iter()
+            .find(|m| project_path.join(m).exists())
+            .unwrap_or("app.json");
+        Ok(DetectedPlugin {
+            registry_type: RegistryType::Expo,
+            manifest_path: project_path.join(manifest).display().to_string(),
+            confidence: 1.0,
+        })
+    }
+
     /// Detect Crates.io plugin
     async fn detect_crates(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
         let manifest_path = project_path.join("Cargo.toml");
@@ -225,6 +342,169 @@ impl PluginLoader {
         Err(anyhow::anyhow!("No Homebrew formula found"))
     }
 
+    /// Detect GitHub Packages plugin
+    async fn detect_github_packages(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::github_packages_plugin::{GitHubPackageKind, GitHubPackagesPlugin};
+
+        let package = GitHubPackagesPlugin::new(project_path.to_path_buf())
+            .resolve_package()
+            .await?;
+        let manifest = match package.kind {
+            GitHubPackageKind::Npm => "package.json",
+            GitHubPackageKind::Maven => "pom.xml",
+            GitHubPackageKind::Container => "Dockerfile",
+        };
+
+        Ok(DetectedPlugin {
+            registry_type: RegistryType::GitHubPackages,
+            manifest_path: project_path.join(manifest).display().to_string(),
+            confidence: 1.0,
+        })
+    }
+
+    /// Detect CRAN plugin
+    async fn detect_cran(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::cran_plugin::CranPlugin;
+
+        let manifest_path = project_path.join("DESCRIPTION");
+        if CranPlugin::default()
+            .detect(project_path.to_str().unwrap_or("."))
+            .await?
+        {
+            Ok(DetectedPlugin {
+                registry_type: RegistryType::Cran,
+                manifest_path: manifest_path.display().to_string(),
+                confidence: 1.0,
+            })
+        } else {
+            Err(anyhow::anyhow!("DESCRIPTION not found"))
+        }
+    }
+
+    /// Detect Julia plugin (Project.toml with name and uuid)
+    async fn detect_julia(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::julia_plugin::JuliaPlugin;
+
+        let manifest_path = project_path.join("Project.toml");
+        if JuliaPlugin::default()
+            .detect(project_path.to_str().unwrap_or("."))
+            .await?
+        {
+            Ok(DetectedPlugin {
+                registry_type: RegistryType::Julia,
+                manifest_path: manifest_path.display().to_string(),
+                confidence: 1.0,
+            })
+        } else {
+            Err(anyhow::anyhow!("Project.toml not found"))
+        }
+    }
+
+    /// Detect Bazel plugin (MODULE.bazel with a module() declaration)
+    async fn detect_bazel(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::bazel_plugin::BazelPlugin;
+
+        let manifest_path = project_path.join("MODULE.bazel");
+        if BazelPlugin::default()
+            .detect(project_path.to_str().unwrap_or("."))
+            .await?
+        {
+            Ok(DetectedPlugin {
+                registry_type: RegistryType::Bazel,
+                manifest_path: manifest_path.display().to_string(),
+                confidence: 1.0,
+            })
+        } else {
+            Err(anyhow::anyhow!("MODULE.bazel not found"))
+        }
+    }
+
+    /// Detect CPAN plugin
+    async fn detect_cpan(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::cpan_plugin::CpanPlugin;
+
+        if !CpanPlugin::default()
+            .detect(project_path.to_str().unwrap_or("."))
+            .await?
+        {
+            return Err(anyhow::anyhow!(
+                "dist.ini, Build.PL or Makefile.PL not found"
+            ));
+        }
+
+        let manifest = ["dist.ini", "Build.PL", "Makefile.PL"]
+            .into_iter()
+            .find(|m| project_path.join(m).exists())
+            .unwrap_or("Makefile.PL");
+        Ok(DetectedPlugin {
+            registry_type: RegistryType::Cpan,
+            manifest_path: project_path.join(manifest).display().to_string(),
+            confidence: 1.0,
+        })
+    }
+
+    /// Detect Hackage plugin (.cabal file in the project root)
+    async fn detect_hackage(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::hackage_plugin::HackagePlugin;
+
+        let manifest_path = HackagePlugin::find_cabal_file(project_path)
+            .await
+            .ok_or_else(|| anyhow::anyhow!(".cabal file not found"))?;
+        Ok(DetectedPlugin {
+            registry_type: RegistryType::Hackage,
+            manifest_path: manifest_path.display().to_string(),
+            confidence: 1.0,
+        })
+    }
+
+    /// Detect AUR plugin (PKGBUILD in the project root or aur/)
+    async fn detect_aur(&self, project_path: &Path) -> anyhow::Result<DetectedPlugin> {
+        use crate::plugins::aur_plugin::AurPlugin;
+
+        let package_dir = AurPlugin::new(project_path.to_path_buf())
+            .package_dir()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("PKGBUILD not found"))?;
+        Ok(DetectedPlugin {
+            registry_type: RegistryType::Aur,
+            manifest_path: package_dir.join("PKGBUILD").display().to_string(),
+            confidence: 1.0,
+        })
+    }
+
+    /// Load the external plugins configured under `plugins:`
+    ///
+    /// Plugins with a `command` are started as JSON-RPC subprocesses, the
+    /// others are loaded as shared libraries (from the base path when one is
+    /// set, else from the project). Fails on the first plugin that
+    /// cannot be loaded, so a misconfigured plugin is reported before
+    /// anything is published.
+    pub async fn load_dynamic_plugins(
+        &self,
+        plugins: &[PluginConfig],
+        project_path: &Path,
+    ) -> anyhow::Result<Vec<Arc<dyn RegistryPlugin>>> {
+        let mut loaded: Vec<Arc<dyn RegistryPlugin>> = Vec::new();
+        for plugin in plugins {
+            let config = plugin.config.clone();
+            match plugin.command.as_deref() {
+                Some(command) => loaded.push(Arc::new(
+                    SubprocessPlugin::spawn(command, project_path, config).await?,
+                )),
+                None => {
+                    let search_path = self.base_path.as_deref().map_or(project_path, Path::new);
+                    let library_path = plugin_library_path(search_path, plugin);
+                    loaded.push(Arc::new(DynamicPlugin::load(
+                        &library_path,
+                        project_path,
+                        config,
+                    )?));
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
     /// Load a plugin for a specific registry type
     ///
     /// # Arguments
@@ -254,7 +534,9 @@ impl PluginLoader {
             }
             RegistryType::PyPI => {
                 use crate::plugins::pypi_plugin::PyPiPlugin;
-                Ok(Arc::new(PyPiPlugin::new()))
+                Ok(Arc::new(PyPiPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
             }
             RegistryType::Homebrew => {
                 use crate::plugins::homebrew_plugin::HomebrewPlugin;
@@ -262,6 +544,120 @@ impl PluginLoader {
                     project_path,
                 ))))
             }
+            RegistryType::Upm => {
+                use crate::plugins::upm_plugin::UpmPlugin;
+                Ok(Arc::new(UpmPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Cran => {
+                use crate::plugins::cran_plugin::CranPlugin;
+                Ok(Arc::new(CranPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Julia => {
+                use crate::plugins::julia_plugin::JuliaPlugin;
+                Ok(Arc::new(JuliaPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Bazel => {
+                use crate::plugins::bazel_plugin::BazelPlugin;
+                Ok(Arc::new(BazelPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Cpan => {
+                use crate::plugins::cpan_plugin::CpanPlugin;
+                Ok(Arc::new(CpanPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Hackage => {
+                use crate::plugins::hackage_plugin::HackagePlugin;
+                Ok(Arc::new(HackagePlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Expo => {
+                use crate::plugins::expo_plugin::ExpoPlugin;
+                Ok(Arc::new(ExpoPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Bucket => {
+                use crate::plugins::bucket_plugin::BucketPlugin;
+                Ok(Arc::new(BucketPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Artifactory => {
+                use crate::plugins::repository_manager_plugin::{
+                    RepositoryManagerKind, RepositoryManagerPlugin,
+                };
+                Ok(Arc::new(RepositoryManagerPlugin::new(
+                    std::path::PathBuf::from(project_path),
+                    RepositoryManagerKind::Artifactory,
+                )))
+            }
+            RegistryType::Nexus => {
+                use crate::plugins::repository_manager_plugin::{
+                    RepositoryManagerKind, RepositoryManagerPlugin,
+                };
+                Ok(Arc::new(RepositoryManagerPlugin::new(
+                    std::path::PathBuf::from(project_path),
+                    RepositoryManagerKind::Nexus,
+                )))
+            }
+            RegistryType::Cloudsmith => {
+                use crate::plugins::cloudsmith_plugin::CloudsmithPlugin;
+                Ok(Arc::new(CloudsmithPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Gemfury => {
+                use crate::plugins::gemfury_plugin::GemfuryPlugin;
+                Ok(Arc::new(GemfuryPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Scoop => {
+                use crate::plugins::windows_manifest_plugin::{
+                    WindowsManifestKind, WindowsManifestPlugin,
+                };
+                Ok(Arc::new(WindowsManifestPlugin::new(
+                    std::path::PathBuf::from(project_path),
+                    WindowsManifestKind::Scoop,
+                )))
+            }
+            RegistryType::Winget => {
+                use crate::plugins::windows_manifest_plugin::{
+                    WindowsManifestKind, WindowsManifestPlugin,
+                };
+                Ok(Arc::new(WindowsManifestPlugin::new(
+                    std::path::PathBuf::from(project_path),
+                    WindowsManifestKind::Winget,
+                )))
+            }
+            RegistryType::Aur => {
+                use crate::plugins::aur_plugin::AurPlugin;
+                Ok(Arc::new(AurPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::Debian => {
+                use crate::plugins::debian_plugin::DebianPlugin;
+                Ok(Arc::new(DebianPlugin::new(std::path::PathBuf::from(
+                    project_path,
+                ))))
+            }
+            RegistryType::GitHubPackages => {
+                use crate::plugins::github_packages_plugin::GitHubPackagesPlugin;
+                Ok(Arc::new(GitHubPackagesPlugin::new(
+                    std::path::PathBuf::from(project_path),
+                )))
+            }
         }
     }
 }
@@ -365,6 +761,137 @@ mod tests {
         assert_eq!(result.confidence, 1.0);
     }
 
+    #[tokio::test]
+    async fn test_detect_upm_instead_of_npm() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        let mut file = std::fs::File::create(&package_json).unwrap();
+        writeln!(
+            file,
+            r#"{{"name": "com.company.tool", "version": "1.0.0", "unity": "2021.3"}}"#
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let plugins = loader.detect_plugins(temp_dir.path()).await.unwrap();
+
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].registry_type, RegistryType::Upm);
+    }
+
+    #[tokio::test]
+    async fn test_detect_expo_instead_of_npm() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "acme-app", "private": true}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("app.json"),
+            r#"{"expo": {"name": "Acme", "slug": "acme"}}"#,
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let plugins = loader.detect_plugins(temp_dir.path()).await.unwrap();
+
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].registry_type, RegistryType::Expo);
+    }
+
+    #[tokio::test]
+    async fn test_detect_cran_and_julia() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("DESCRIPTION"),
+            "Package: mypkg\nVersion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("Project.toml"),
+            "name = \"Example\"\nuuid = \"7876af07-990d-54b4-ab0e-23690620f79a\"\n",
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let plugins = loader.detect_plugins(temp_dir.path()).await.unwrap();
+
+        let types: Vec<_> = plugins.iter().map(|p| p.registry_type).collect();
+        assert_eq!(types, vec![RegistryType::Cran, RegistryType::Julia]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_bazel() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("MODULE.bazel"),
+            "module(name = \"rules_example\", version = \"1.0.0\")\n",
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let plugin = loader.detect_bazel(temp_dir.path()).await.unwrap();
+
+        assert_eq!(plugin.registry_type, RegistryType::Bazel);
+        assert!(plugin.manifest_path.ends_with("MODULE.bazel"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_cpan() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Makefile.PL"),
+            "WriteMakefile(NAME => 'Acme::Widget', VERSION => '0.01');\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("dist.ini"), "name = Acme-Widget\n").unwrap();
+
+        let loader = PluginLoader::new();
+        let plugin = loader.detect_cpan(temp_dir.path()).await.unwrap();
+
+        assert_eq!(plugin.registry_type, RegistryType::Cpan);
+        assert!(plugin.manifest_path.ends_with("dist.ini"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_hackage() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("acme-widget.cabal"),
+            "cabal-version: 3.0\nname: acme-widget\nversion: 0.1.0.0\n",
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let plugin = loader.detect_hackage(temp_dir.path()).await.unwrap();
+
+        assert_eq!(plugin.registry_type, RegistryType::Hackage);
+        assert!(plugin.manifest_path.ends_with("acme-widget.cabal"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_github_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let dockerfile = temp_dir.path().join("Dockerfile");
+        let mut file = std::fs::File::create(&dockerfile).unwrap();
+        writeln!(
+            file,
+            "FROM alpine\nLABEL org.opencontainers.image.source=https://github.com/octo/app"
+        )
+        .unwrap();
+
+        let loader = PluginLoader::new();
+        let result = loader
+            .detect_github_packages(temp_dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(result.registry_type, RegistryType::GitHubPackages);
+        assert!(result.manifest_path.ends_with("Dockerfile"));
+        assert_eq!(RegistryType::GitHubPackages.as_str(), "github-packages");
+    }
+
     #[tokio::test]
     async fn test_detect_plugins_multiple() {
         let temp_dir = TempDir::new().unwrap();
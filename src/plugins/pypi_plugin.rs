@@ -1,29 +1,327 @@
 //! PyPI Plugin - PyPI registry publishing implementation
+//!
+//! - pyproject.toml / setup.py detection
+//! - Metadata completeness score (classifiers, requires-python, URLs, readme)
+//! - Detects the build backend from `[build-system]` (hatchling, poetry-core,
+//!   flit-core, setuptools, pdm-backend, maturin)
+//! - Builds sdist and wheels with `python -m build` into a scratch directory
+//!   (`poetry build` for Poetry projects without a `[build-system]` table);
+//!   both an sdist and at least one wheel are required
+//! - `twine check` plus wheel auditing (platform tags, auditwheel, delocate)
+//! - Uploads with `twine upload`; wheels that fail the audit are never uploaded
+//! - Rollback points to the PyPI yank page (PyPI has no yank API)
 
+use crate::core::retry::{RetryManager, RetryOptions};
 use crate::core::traits::{
-    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationResult,
-    VerificationResult,
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, RollbackResult, ValidationError,
+    ValidationResult, ValidationWarning, VerificationResult,
 };
+use crate::security::command_executor::{
+    DEFAULT_REGISTRY_COMMAND_TIMEOUT, EnvSandbox, output_with_timeout,
+};
+use crate::tr;
+use crate::validation::{PythonMetadataScorer, WheelAuditResult, WheelAuditor};
 use async_trait::async_trait;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use tokio::process::Command;
+
+/// Environment variables twine, pip and build need on top of the sandbox
+/// allowlist
+pub(crate) const PYTHON_ENV: &[&str] = &["TWINE_*", "PIP_*", "PYTHON*", "VIRTUAL_ENV", "CONDA_*"];
+
+/// PEP 517 build backend of a Python project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildBackend {
+    Hatchling,
+    Poetry,
+    Flit,
+    Setuptools,
+    Pdm,
+    Maturin,
+    /// Any other backend (`build-backend` value)
+    Other(String),
+}
+
+impl BuildBackend {
+    /// Backend for a `build-backend` value (`hatchling.build`, `poetry.core.masonry.api`, ...)
+    pub fn from_build_backend(value: &str) -> Self {
+        let module = value.split([':', '.']).next().unwrap_or_default();
+        match module {
+            "hatchling" => Self::Hatchling,
+            "poetry" => Self::Poetry,
+            "flit_core" | "flit" => Self::Flit,
+            "setuptools" => Self::Setuptools,
+            "pdm" => Self::Pdm,
+            "maturin" => Self::Maturin,
+            _ => Self::Other(value.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Hatchling => "hatchling",
+            Self::Poetry => "poetry",
+            Self::Flit => "flit",
+            Self::Setuptools => "setuptools",
+            Self::Pdm => "pdm",
+            Self::Maturin => "maturin",
+            Self::Other(value) => value,
+        }
+    }
+}
+
+/// How a project is built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildPlan {
+    pub backend: BuildBackend,
+    /// Whether `[build-system] build-backend` is declared; without it build
+    /// frontends fall back to legacy setuptools
+    pub declared: bool,
+}
+
+impl BuildPlan {
+    /// Build plan from pyproject.toml (None = setup.py-only project)
+    pub fn from_pyproject(pyproject: Option<&toml::Value>) -> Self {
+        let declared = pyproject
+            .and_then(|v| v.get("build-system"))
+            .and_then(|b| b.get("build-backend"))
+            .and_then(|b| b.as_str());
+        if let Some(value) = declared {
+            return Self {
+                backend: BuildBackend::from_build_backend(value),
+                declared: true,
+            };
+        }
+
+        let tool = pyproject.and_then(|v| v.get("tool"));
+        let backend = if tool.and_then(|t| t.get("poetry")).is_some() {
+            BuildBackend::Poetry
+        } else {
+            BuildBackend::Setuptools
+        };
+        Self {
+            backend,
+            declared: false,
+        }
+    }
+
+    /// Command building sdist and wheels into `out_dir`
+    pub fn build_command(&self, out_dir: &str) -> (&'static str, Vec<String>) {
+        if !self.declared && self.backend == BuildBackend::Poetry {
+            // `python -m build` would use legacy setuptools, which cannot read [tool.poetry]
+            return (
+                "poetry",
+                vec![
+                    "build".to_string(),
+                    "--output".to_string(),
+                    out_dir.to_string(),
+                ],
+            );
+        }
+        (
+            "python",
+            vec![
+                "-m".to_string(),
+                "build".to_string(),
+                "--outdir".to_string(),
+                out_dir.to_string(),
+            ],
+        )
+    }
+}
+
+/// Built distribution files as publish metadata (`[{file, kind, size}]`)
+async fn artifact_metadata(files: &[String]) -> serde_json::Value {
+    let mut artifacts = Vec::new();
+    for file in files {
+        let size = fs::metadata(file).await.map(|m| m.len()).unwrap_or(0);
+        artifacts.push(serde_json::json!({
+            "file": Path::new(file).file_name().map(|n| n.to_string_lossy().to_string()),
+            "kind": if file.ends_with(".whl") { "wheel" } else { "sdist" },
+            "size": size,
+        }));
+    }
+    serde_json::Value::Array(artifacts)
+}
 
 /// PyPI registry plugin
 pub struct PyPiPlugin {
-    _private: (),
+    project_path: PathBuf,
+    retry: RetryOptions,
+    sandbox: Option<EnvSandbox>,
+    command_timeout: Duration,
 }
 
 impl Default for PyPiPlugin {
     fn default() -> Self {
-        Self::new()
+        Self::new(PathBuf::from("."))
     }
 }
 
 impl PyPiPlugin {
-    pub fn new() -> Self {
-        Self { _private: () }
+    /// Create a new PyPI plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            retry: RetryOptions::default(),
+            sandbox: None,
+            command_timeout: DEFAULT_REGISTRY_COMMAND_TIMEOUT,
+        }
+    }
+
+    /// Retry build and upload commands on transient failures
+    pub fn with_retry(mut self, retry: RetryOptions) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run build and upload commands with a minimal environment (twine,
+    /// pip and Python variables stay available)
+    pub fn with_sandbox(mut self, sandbox: EnvSandbox) -> Self {
+        self.sandbox = Some(sandbox.with_allowed(PYTHON_ENV));
+        self
+    }
+
+    /// Kill build and upload commands running longer than `timeout`, with the processes
+    /// they spawned
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// Scratch directory for built distributions (keeps the project's dist/ untouched)
+    fn build_dir(&self) -> PathBuf {
+        std::env::temp_dir().join(format!("package-publisher-pypi-{}", std::process::id()))
+    }
+
+    /// Build plan from the project's pyproject.toml
+    async fn build_plan(&self) -> BuildPlan {
+        let pyproject = fs::read_to_string(self.project_path.join("pyproject.toml"))
+            .await
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok());
+        BuildPlan::from_pyproject(pyproject.as_ref())
+    }
+
+    /// Build sdist and wheels into a fresh scratch directory
+    async fn build(&self, plan: &BuildPlan) -> anyhow::Result<PathBuf> {
+        let out_dir = self.build_dir();
+        if fs::metadata(&out_dir).await.is_ok() {
+            fs::remove_dir_all(&out_dir).await?;
+        }
+
+        let (program, args) = plan.build_command(&out_dir.to_string_lossy());
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run_command(program, &args).await?;
+        Ok(out_dir)
+    }
+
+    /// Built distribution files (sorted)
+    async fn dist_files(dir: &Path) -> anyhow::Result<Vec<String>> {
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let name = path.to_string_lossy().to_string();
+            if name.ends_with(".whl") || name.ends_with(".tar.gz") {
+                files.push(name);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Build, `twine check` and audit the distributions
+    async fn build_and_audit(
+        &self,
+        plan: &BuildPlan,
+    ) -> anyhow::Result<(PathBuf, Vec<String>, WheelAuditResult)> {
+        let out_dir = self.build(plan).await?;
+        let files = Self::dist_files(&out_dir).await?;
+        if files.is_empty() {
+            anyhow::bail!("{}", tr!("pypi.no_artifacts"));
+        }
+        if !files.iter().any(|f| f.ends_with(".tar.gz")) {
+            anyhow::bail!("{}", tr!("pypi.no_sdist", backend = plan.backend.name()));
+        }
+        if !files.iter().any(|f| f.ends_with(".whl")) {
+            anyhow::bail!("{}", tr!("pypi.no_wheel", backend = plan.backend.name()));
+        }
+
+        let mut args = vec!["check"];
+        args.extend(files.iter().map(String::as_str));
+        self.run_command("twine", &args).await?;
+
+        let audit = WheelAuditor::new().audit_dir(&out_dir).await?;
+        Ok((out_dir, files, audit))
+    }
+
+    /// Project name from pyproject.toml (`[project]` or `[tool.poetry]`)
+    async fn project_name(&self) -> Option<String> {
+        let content = fs::read_to_string(self.project_path.join("pyproject.toml"))
+            .await
+            .ok()?;
+        let value: toml::Value = toml::from_str(&content).ok()?;
+        value
+            .get("project")
+            .or_else(|| value.get("tool").and_then(|t| t.get("poetry")))
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .map(str::to_string)
+    }
+
+    /// Execute a command in the project directory
+    async fn run_command(&self, program: &str, args: &[&str]) -> anyhow::Result<String> {
+        RetryManager::new(self.retry.clone())
+            .retry(|| self.run_command_once(program, args))
+            .await
+    }
+
+    /// Upload `args` with `twine upload`, retrying like a publish
+    async fn twine_upload(&self, args: &[&str]) -> anyhow::Result<String> {
+        RetryManager::new(self.retry.clone())
+            .retry_publish(
+                || self.run_command_once("twine", args),
+                |e| tr!("registry.published_by_earlier_attempt", error = e),
+            )
+            .await
+    }
+
+    /// Single attempt of a command
+    async fn run_command_once(&self, program: &str, args: &[&str]) -> anyhow::Result<String> {
+        let mut command = Command::new(program);
+        if let Some(sandbox) = &self.sandbox {
+            sandbox.apply(command.as_std_mut());
+        }
+        command.args(args).current_dir(&self.project_path);
+        let output = output_with_timeout(&mut command, self.command_timeout).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}", stderr);
+        }
+
+        Ok(stdout + &stderr)
     }
 }
 
+/// Summarize audited wheels for command output
+fn audit_summary(audit: &WheelAuditResult) -> String {
+    let mut lines = vec![tr!("pypi.wheel_audit_summary", count = audit.wheels.len())];
+    for warning in &audit.warnings {
+        lines.push(format!("  ⚠️  {}: {}", warning.field, warning.message));
+    }
+    for error in &audit.errors {
+        lines.push(format!("  ❌ {}: {}", error.field, error.message));
+    }
+    lines.join("\n")
+}
+
 #[async_trait]
 impl RegistryPlugin for PyPiPlugin {
     fn name(&self) -> &str {
@@ -44,32 +342,175 @@ impl RegistryPlugin for PyPiPlugin {
     }
 
     async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let pyproject = fs::read_to_string(self.project_path.join("pyproject.toml")).await;
+        let score = match pyproject {
+            Ok(ref content) => {
+                let value: toml::Value = match toml::from_str(content) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        return Ok(ValidationResult {
+                            valid: false,
+                            errors: vec![ValidationError {
+                                field: "pyproject.toml".to_string(),
+                                message: tr!("pypi.pyproject_parse_failed", error = e),
+                                severity: "error".to_string(),
+                            }],
+                            warnings,
+                            metadata: None,
+                        });
+                    }
+                };
+                let project = value.get("project");
+                for (key, field) in [("packageName", "name"), ("version", "version")] {
+                    if let Some(v) = project.and_then(|p| p.get(field)).and_then(|v| v.as_str()) {
+                        metadata.insert(key.to_string(), serde_json::Value::String(v.to_string()));
+                    }
+                }
+                PythonMetadataScorer::score_pyproject(content).ok()
+            }
+            Err(_) => fs::read_to_string(self.project_path.join("setup.py"))
+                .await
+                .ok()
+                .map(|content| PythonMetadataScorer::score_setup_py(&content)),
+        };
+
+        let plan = self.build_plan().await;
+        metadata.insert(
+            "buildBackend".to_string(),
+            serde_json::json!(plan.backend.name()),
+        );
+
+        if let Some(score) = score {
+            for check in score.failed() {
+                warnings.push(ValidationWarning {
+                    field: check.field.to_string(),
+                    message: tr!(
+                        "pypi.score_penalty",
+                        message = check.message,
+                        points = check.points
+                    ),
+                    severity: "warning".to_string(),
+                });
+            }
+            metadata.insert("qualityScore".to_string(), serde_json::json!(score.score));
+        }
+
         Ok(ValidationResult {
             valid: true,
             errors: Vec::new(),
-            warnings: Vec::new(),
-            metadata: None,
+            warnings,
+            metadata: if metadata.is_empty() {
+                None
+            } else {
+                Some(metadata)
+            },
         })
     }
 
     async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
-        Ok(DryRunResult {
-            success: true,
-            output: "Dry run successful (stub)".to_string(),
-            estimated_size: Some("0 B".to_string()),
-            errors: None,
-        })
+        let plan = self.build_plan().await;
+        match self.build_and_audit(&plan).await {
+            Ok((out_dir, files, audit)) => {
+                let _ = fs::remove_dir_all(&out_dir).await;
+                let names: Vec<_> = files
+                    .iter()
+                    .filter_map(|f| Path::new(f).file_name())
+                    .map(|n| n.to_string_lossy())
+                    .collect();
+                let output = tr!(
+                    "pypi.build_succeeded",
+                    backend = plan.backend.name(),
+                    count = files.len(),
+                    files = names.join(", "),
+                    report = audit_summary(&audit)
+                );
+                Ok(DryRunResult {
+                    success: audit.passed(),
+                    output,
+                    estimated_size: None,
+                    errors: (!audit.passed()).then_some(audit.errors),
+                })
+            }
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "build".to_string(),
+                    message: tr!("pypi.check_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
     }
 
     async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
-        Ok(PublishResult {
-            success: true,
-            version: Some("0.0.0".to_string()),
-            package_url: Some("https://pypi.org/project/stub-package".to_string()),
-            output: Some("Stub: Not yet implemented".to_string()),
-            error: None,
-            metadata: None,
-        })
+        let plan = self.build_plan().await;
+        let (out_dir, files, audit) = match self.build_and_audit(&plan).await {
+            Ok(built) => built,
+            Err(e) => {
+                return Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(tr!("pypi.build_failed", error = e)),
+                    metadata: None,
+                    error_kind: None,
+                });
+            }
+        };
+
+        if !audit.passed() {
+            let _ = fs::remove_dir_all(&out_dir).await;
+            return Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: Some(audit_summary(&audit)),
+                error: Some(tr!("pypi.wheel_audit_failed")),
+                metadata: None,
+                error_kind: None,
+            });
+        }
+
+        let mut args = vec!["upload", "--non-interactive"];
+        args.extend(files.iter().map(String::as_str));
+
+        let result = self.twine_upload(&args).await;
+        let artifacts = artifact_metadata(&files).await;
+        let _ = fs::remove_dir_all(&out_dir).await;
+
+        match result {
+            Ok(output) => Ok(PublishResult {
+                success: true,
+                version: None,
+                package_url: None,
+                output: Some(format!("{}\n{}", output, audit_summary(&audit))),
+                error: None,
+                metadata: Some(HashMap::from([
+                    ("wheels".to_string(), serde_json::json!(audit.wheels)),
+                    (
+                        "buildBackend".to_string(),
+                        serde_json::json!(plan.backend.name()),
+                    ),
+                    ("artifacts".to_string(), artifacts),
+                ])),
+                error_kind: None,
+            }),
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(tr!("pypi.upload_failed", error = e)),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
     }
 
     async fn verify(&self) -> anyhow::Result<VerificationResult> {
@@ -79,6 +520,28 @@ impl RegistryPlugin for PyPiPlugin {
             url: Some("https://pypi.org".to_string()),
             error: Some("Stub: Not yet implemented".to_string()),
             metadata: None,
+            integrity: None,
+        })
+    }
+
+    /// PyPI releases can only be yanked from the web UI
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        let manage_url = match self.project_name().await {
+            Some(name) => format!(
+                "https://pypi.org/manage/project/{}/release/{}/",
+                name, version
+            ),
+            None => "https://pypi.org/manage/projects/".to_string(),
+        };
+
+        Ok(RollbackResult {
+            success: false,
+            message: tr!("pypi.yank_manually", url = manage_url),
+            error: Some(tr!(
+                "pypi.rollback_unsupported",
+                version = version,
+                url = manage_url
+            )),
         })
     }
 }
@@ -96,7 +559,7 @@ mod tests {
         let mut file = std::fs::File::create(&pyproject).unwrap();
         writeln!(file, "[project]\nname = \"test\"").unwrap();
 
-        let plugin = PyPiPlugin::new();
+        let plugin = PyPiPlugin::new(temp_dir.path().to_path_buf());
         assert!(
             plugin
                 .detect(temp_dir.path().to_str().unwrap())
@@ -105,13 +568,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_plan() {
+        let plan =
+            |content: &str| BuildPlan::from_pyproject(Some(&toml::from_str(content).unwrap()));
+
+        let hatch = plan(
+            "[build-system]\nrequires = [\"hatchling\"]\nbuild-backend = \"hatchling.build\"\n",
+        );
+        assert_eq!(hatch.backend, BuildBackend::Hatchling);
+        assert!(hatch.declared);
+        assert_eq!(hatch.build_command("/tmp/out").0, "python");
+
+        let poetry_core = plan("[build-system]\nbuild-backend = \"poetry.core.masonry.api\"\n");
+        assert_eq!(poetry_core.backend, BuildBackend::Poetry);
+        assert_eq!(poetry_core.build_command("/tmp/out").0, "python");
+
+        let legacy_poetry = plan("[tool.poetry]\nname = \"demo\"\n");
+        assert_eq!(legacy_poetry.backend, BuildBackend::Poetry);
+        assert!(!legacy_poetry.declared);
+        assert_eq!(
+            legacy_poetry.build_command("/tmp/out"),
+            (
+                "poetry",
+                vec![
+                    "build".to_string(),
+                    "--output".to_string(),
+                    "/tmp/out".to_string()
+                ]
+            )
+        );
+
+        assert_eq!(
+            BuildBackend::from_build_backend("flit_core.buildapi"),
+            BuildBackend::Flit
+        );
+        assert_eq!(
+            BuildBackend::from_build_backend("setuptools.build_meta:__legacy__"),
+            BuildBackend::Setuptools
+        );
+        assert_eq!(
+            BuildPlan::from_pyproject(None).backend,
+            BuildBackend::Setuptools
+        );
+    }
+
     #[tokio::test]
     async fn test_detect_setup_py() {
         let temp_dir = TempDir::new().unwrap();
         let setup_py = temp_dir.path().join("setup.py");
         std::fs::File::create(&setup_py).unwrap();
 
-        let plugin = PyPiPlugin::new();
+        let plugin = PyPiPlugin::new(temp_dir.path().to_path_buf());
         assert!(
             plugin
                 .detect(temp_dir.path().to_str().unwrap())
@@ -120,9 +628,65 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_validate_reports_quality_score() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"demo-pkg\"\nversion = \"1.0.0\"\nrequires-python = \">=3.9\"\n",
+        )
+        .unwrap();
+
+        let plugin = PyPiPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.validate().await.unwrap();
+        assert!(result.valid);
+
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata["packageName"], "demo-pkg");
+        assert_eq!(metadata["qualityScore"], 20);
+        assert_eq!(result.warnings.len(), 7);
+        assert!(result.warnings.iter().all(|w| w.field != "requires-python"));
+    }
+
+    #[test]
+    fn test_audit_summary() {
+        let mut audit = WheelAuditResult::default();
+        audit
+            .wheels
+            .push("demo-1.0-cp312-cp312-linux_x86_64.whl".to_string());
+        audit.errors.push(ValidationError {
+            field: "demo-1.0-cp312-cp312-linux_x86_64.whl".to_string(),
+            message: "rejected".to_string(),
+            severity: "error".to_string(),
+        });
+
+        let summary = audit_summary(&audit);
+        assert!(summary.starts_with("Wheel audit: 1 issue(s)"));
+        assert!(summary.contains("❌ demo-1.0-cp312-cp312-linux_x86_64.whl: rejected"));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_points_to_yank_page() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"demo-pkg\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+
+        let plugin = PyPiPlugin::new(temp_dir.path().to_path_buf());
+        let result = plugin.rollback("1.0.0").await.unwrap();
+        assert!(!result.success);
+        assert!(
+            result
+                .message
+                .contains("https://pypi.org/manage/project/demo-pkg/release/1.0.0/")
+        );
+    }
+
     #[test]
     fn test_version() {
-        let plugin = PyPiPlugin::new();
+        let plugin = PyPiPlugin::new(PathBuf::from("."));
         assert_eq!(plugin.version(), "1.0.0");
     }
 }
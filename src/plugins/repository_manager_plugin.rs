@@ -0,0 +1,694 @@
+//! Repository Manager Plugin - JFrog Artifactory / Sonatype Nexus
+//!
+//! Publishes artifacts to enterprise repository managers over their REST APIs:
+//! - Artifactory generic repositories: `PUT /<repo>/<path>` with a SHA-256
+//!   checksum header, then `name` / `version` (and configured) properties
+//! - Nexus raw hosted repositories: `PUT /repository/<repo>/<path>`, with an
+//!   optional tag association (Nexus Pro)
+//! - Verification through the search APIs, comparing SHA-256 checksums
+//!
+//! The API token comes from the token manager (`ARTIFACTORY_TOKEN` /
+//! `NEXUS_TOKEN`). Like buckets, these registries are selected by their
+//! `registries.artifactory` / `registries.nexus` config section.
+
+use crate::core::config::RepositoryManagerConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, ValidationError,
+    ValidationResult, ValidationWarning, VerificationResult,
+};
+use crate::orchestration::release::resolve_assets;
+use crate::plugins::bucket_plugin::{manifest_identity, render_key, sha256_file};
+use crate::security::token_manager::SecureTokenManager;
+use crate::tr;
+use async_trait::async_trait;
+use secrecy::ExposeSecret;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const DEFAULT_PATH_TEMPLATE: &str = "{name}/{version}/{file}";
+
+const DEFAULT_ARTIFACTS: &str = "dist";
+
+/// Repository manager product
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepositoryManagerKind {
+    Artifactory,
+    Nexus,
+}
+
+impl RepositoryManagerKind {
+    /// Registry name (also the token manager key)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryManagerKind::Artifactory => "artifactory",
+            RepositoryManagerKind::Nexus => "nexus",
+        }
+    }
+}
+
+/// Uploaded artifact
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct UploadedArtifact {
+    path: String,
+    sha256: String,
+    size: u64,
+    url: String,
+}
+
+/// Artifactory `properties` query value (`k=v;k2=v2`, reserved characters escaped)
+pub fn artifactory_properties(properties: &BTreeMap<String, String>) -> String {
+    let escape = |value: &str| {
+        value.chars().fold(String::new(), |mut out, c| {
+            if matches!(c, '\\' | ',' | '|' | '=' | ';') {
+                out.push('\\');
+            }
+            out.push(c);
+            out
+        })
+    };
+    properties
+        .iter()
+        .map(|(k, v)| format!("{}={}", escape(k), escape(v)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Repository paths returned by an Artifactory property search
+///
+/// Result URIs look like `<url>/api/storage/<repo>/<path>`.
+pub fn parse_artifactory_search(body: &serde_json::Value, repository: &str) -> Vec<String> {
+    let marker = format!("/api/storage/{}/", repository);
+    body["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|r| r["uri"].as_str())
+        .filter_map(|uri| uri.split_once(&marker).map(|(_, path)| path.to_string()))
+        .collect()
+}
+
+/// SHA-256 and download URL of a Nexus asset search result
+pub fn parse_nexus_asset(body: &serde_json::Value, path: &str) -> Option<(String, String)> {
+    body["items"]
+        .as_array()?
+        .iter()
+        .find(|item| item["path"].as_str().map(|p| p.trim_start_matches('/')) == Some(path))
+        .and_then(|item| {
+            Some((
+                item["checksum"]["sha256"].as_str()?.to_string(),
+                item["downloadUrl"].as_str()?.to_string(),
+            ))
+        })
+}
+
+/// Artifactory / Nexus plugin
+pub struct RepositoryManagerPlugin {
+    project_path: PathBuf,
+    kind: RepositoryManagerKind,
+    config: RepositoryManagerConfig,
+}
+
+impl RepositoryManagerPlugin {
+    /// Create a new plugin instance for the given product
+    pub fn new(project_path: PathBuf, kind: RepositoryManagerKind) -> Self {
+        Self {
+            project_path,
+            kind,
+            config: RepositoryManagerConfig::default(),
+        }
+    }
+
+    /// Apply `registries.artifactory` / `registries.nexus` settings
+    pub fn with_config(mut self, config: RepositoryManagerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    fn base_url(&self) -> &str {
+        self.config.url.trim_end_matches('/')
+    }
+
+    /// Download URL of a repository path
+    pub fn artifact_url(&self, path: &str) -> String {
+        match self.kind {
+            RepositoryManagerKind::Artifactory => {
+                format!("{}/{}/{}", self.base_url(), self.config.repository, path)
+            }
+            RepositoryManagerKind::Nexus => format!(
+                "{}/repository/{}/{}",
+                self.base_url(),
+                self.config.repository,
+                path
+            ),
+        }
+    }
+
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        match (self.config.name.clone().or(name), version) {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!(
+                "Package name not found (set registries.{}.name)",
+                self.kind.as_str()
+            ),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    async fn artifacts(&self) -> Vec<PathBuf> {
+        let patterns = self
+            .config
+            .artifacts
+            .clone()
+            .unwrap_or_else(|| vec![DEFAULT_ARTIFACTS.to_string()]);
+        resolve_assets(&self.project_path, &patterns).await
+    }
+
+    fn artifact_path(&self, name: &str, version: &str, file: &Path) -> String {
+        let file = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        render_key(
+            self.config
+                .path_template
+                .as_deref()
+                .unwrap_or(DEFAULT_PATH_TEMPLATE),
+            name,
+            version,
+            &file,
+        )
+    }
+
+    /// Properties set on every Artifactory artifact
+    fn properties(&self, name: &str, version: &str) -> BTreeMap<String, String> {
+        let mut properties: BTreeMap<_, _> = self
+            .config
+            .properties
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        properties.insert("name".to_string(), name.to_string());
+        properties.insert("version".to_string(), version.to_string());
+        properties
+    }
+
+    /// Environment variable holding the API token
+    fn token_name(&self) -> String {
+        SecureTokenManager::new()
+            .get_token_name(self.kind.as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn token(&self) -> Option<String> {
+        SecureTokenManager::new()
+            .get_token(self.kind.as_str())
+            .map(|t| t.expose_secret().to_string())
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let token = self.token().ok_or_else(|| {
+            anyhow::anyhow!("{}", tr!("registry.not_set", name = self.token_name()))
+        })?;
+        let request = http_client().request(method, url);
+        Ok(match self.config.username {
+            Some(ref user) => request.basic_auth(user, Some(token)),
+            None => request.bearer_auth(token),
+        })
+    }
+
+    async fn upload(&self, file: &Path, path: &str, sha256: &str) -> anyhow::Result<String> {
+        let url = self.artifact_url(path);
+        let mut request = self.request(reqwest::Method::PUT, &url)?;
+        if self.kind == RepositoryManagerKind::Artifactory {
+            request = request.header("X-Checksum-Sha256", sha256);
+        }
+        let response = request
+            .body(fs::read(file).await?)
+            .send_rate_limited()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "HTTP {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default().trim()
+            );
+        }
+        Ok(url)
+    }
+
+    /// Set properties (Artifactory) or associate the tag (Nexus)
+    async fn tag(&self, path: &str, name: &str, version: &str) -> anyhow::Result<()> {
+        let request = match self.kind {
+            RepositoryManagerKind::Artifactory => self
+                .request(
+                    reqwest::Method::PUT,
+                    &format!(
+                        "{}/api/storage/{}/{}",
+                        self.base_url(),
+                        self.config.repository,
+                        path
+                    ),
+                )?
+                .query(&[
+                    (
+                        "properties",
+                        artifactory_properties(&self.properties(name, version)),
+                    ),
+                    ("recursive", "0".to_string()),
+                ]),
+            RepositoryManagerKind::Nexus => {
+                let Some(ref tag) = self.config.tag else {
+                    return Ok(());
+                };
+                self.request(
+                    reqwest::Method::POST,
+                    &format!("{}/service/rest/v1/tags/associate/{}", self.base_url(), tag),
+                )?
+                .query(&[
+                    ("repository", self.config.repository.as_str()),
+                    ("name", path),
+                ])
+            }
+        };
+
+        let response = request.send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP {}", response.status());
+        }
+        Ok(())
+    }
+
+    async fn get_json(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<Option<serde_json::Value>> {
+        let response = self
+            .request(reqwest::Method::GET, url)?
+            .query(query)
+            .send_rate_limited()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP {}: {}", response.status(), url);
+        }
+        Ok(Some(response.json().await?))
+    }
+
+    /// Remote SHA-256 of a repository path (None if it does not exist)
+    async fn remote_sha256(&self, path: &str, tagged: &[String]) -> anyhow::Result<Option<String>> {
+        match self.kind {
+            RepositoryManagerKind::Artifactory => {
+                if !tagged.iter().any(|p| p == path) {
+                    return Ok(None);
+                }
+                let info = self
+                    .get_json(
+                        &format!(
+                            "{}/api/storage/{}/{}",
+                            self.base_url(),
+                            self.config.repository,
+                            path
+                        ),
+                        &[],
+                    )
+                    .await?;
+                Ok(info.and_then(|i| i["checksums"]["sha256"].as_str().map(str::to_string)))
+            }
+            RepositoryManagerKind::Nexus => {
+                let body = self
+                    .get_json(
+                        &format!("{}/service/rest/v1/search/assets", self.base_url()),
+                        &[("repository", &self.config.repository), ("name", path)],
+                    )
+                    .await?;
+                Ok(body
+                    .and_then(|b| parse_nexus_asset(&b, path))
+                    .map(|(sha256, _)| sha256))
+            }
+        }
+    }
+
+    /// Paths carrying this version's properties (Artifactory property search)
+    async fn search_tagged(&self, name: &str, version: &str) -> anyhow::Result<Vec<String>> {
+        if self.kind != RepositoryManagerKind::Artifactory {
+            return Ok(Vec::new());
+        }
+        let body = self
+            .get_json(
+                &format!("{}/api/search/prop", self.base_url()),
+                &[
+                    ("name", name),
+                    ("version", version),
+                    ("repos", &self.config.repository),
+                ],
+            )
+            .await?;
+        Ok(body
+            .map(|b| parse_artifactory_search(&b, &self.config.repository))
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for RepositoryManagerPlugin {
+    fn name(&self) -> &str {
+        self.kind.as_str()
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through the config section, not a manifest
+        Ok(!self.config.url.is_empty())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+        let section = format!("registries.{}", self.kind.as_str());
+
+        if !self.config.url.starts_with("http://") && !self.config.url.starts_with("https://") {
+            errors.push(ValidationError {
+                field: "url".to_string(),
+                message: tr!("repository_manager.url_required", prefix = section),
+                severity: "error".to_string(),
+            });
+        } else if self.config.url.starts_with("http://") {
+            warnings.push(ValidationWarning {
+                field: "url".to_string(),
+                message: tr!("repository_manager.plain_http"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        if self.config.repository.is_empty() {
+            errors.push(ValidationError {
+                field: "repository".to_string(),
+                message: tr!("repository_manager.repository_required", prefix = section),
+                severity: "error".to_string(),
+            });
+        }
+
+        if self.token().is_none() {
+            errors.push(ValidationError {
+                field: "token".to_string(),
+                message: tr!("registry.not_set", name = self.token_name()),
+                severity: "error".to_string(),
+            });
+        }
+
+        match self.kind {
+            RepositoryManagerKind::Nexus if self.config.username.is_none() => {
+                errors.push(ValidationError {
+                    field: "username".to_string(),
+                    message: tr!("repository_manager.username_required", prefix = section),
+                    severity: "error".to_string(),
+                });
+            }
+            RepositoryManagerKind::Nexus if self.config.properties.is_some() => {
+                warnings.push(ValidationWarning {
+                    field: "properties".to_string(),
+                    message: tr!("repository_manager.nexus_properties"),
+                    severity: "warning".to_string(),
+                });
+            }
+            RepositoryManagerKind::Artifactory if self.config.tag.is_some() => {
+                warnings.push(ValidationWarning {
+                    field: "tag".to_string(),
+                    message: tr!("repository_manager.artifactory_tag"),
+                    severity: "warning".to_string(),
+                });
+            }
+            _ => {}
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: e.to_string(),
+                severity: "error".to_string(),
+            }),
+        }
+
+        let artifacts = self.artifacts().await;
+        if artifacts.is_empty() {
+            errors.push(ValidationError {
+                field: "artifacts".to_string(),
+                message: tr!("registry.no_artifacts_to_upload"),
+                severity: "error".to_string(),
+            });
+        }
+        let template = self
+            .config
+            .path_template
+            .as_deref()
+            .unwrap_or(DEFAULT_PATH_TEMPLATE);
+        if artifacts.len() > 1 && !template.contains("{file}") {
+            errors.push(ValidationError {
+                field: "pathTemplate".to_string(),
+                message: tr!("registry.path_template_needs_file"),
+                severity: "error".to_string(),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let (name, version) = self.package_identity().await?;
+
+        let mut lines = Vec::new();
+        let mut total = 0;
+        for file in self.artifacts().await {
+            total += fs::metadata(&file).await.map(|m| m.len()).unwrap_or(0);
+            lines.push(format!(
+                "{} -> {}",
+                file.display(),
+                self.artifact_url(&self.artifact_path(&name, &version, &file))
+            ));
+        }
+        if self.kind == RepositoryManagerKind::Artifactory {
+            lines.push(format!(
+                "properties: {}",
+                artifactory_properties(&self.properties(&name, &version))
+            ));
+        }
+
+        Ok(DryRunResult {
+            success: !lines.is_empty(),
+            output: lines.join("\n"),
+            estimated_size: Some(format!("{:.1} kB", total as f64 / 1024.0)),
+            errors: None,
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let (name, version) = self.package_identity().await?;
+
+        let mut uploaded = Vec::new();
+        for file in self.artifacts().await {
+            let path = self.artifact_path(&name, &version, &file);
+            let (sha256, size) = sha256_file(&file).await?;
+
+            let result = async {
+                let url = self.upload(&file, &path, &sha256).await?;
+                self.tag(&path, &name, &version).await?;
+                Ok::<_, anyhow::Error>(url)
+            }
+            .await;
+
+            match result {
+                Ok(url) => uploaded.push(UploadedArtifact {
+                    path,
+                    sha256,
+                    size,
+                    url,
+                }),
+                Err(e) => {
+                    return Ok(PublishResult {
+                        success: false,
+                        version: None,
+                        package_url: None,
+                        output: None,
+                        error: Some(tr!(
+                            "registry.upload_failed",
+                            file = self.artifact_url(&path),
+                            error = e
+                        )),
+                        metadata: None,
+                        error_kind: None,
+                    });
+                }
+            }
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("artifacts".to_string(), serde_json::to_value(&uploaded)?);
+
+        Ok(PublishResult {
+            success: true,
+            version: Some(version),
+            package_url: uploaded.first().map(|a| a.url.clone()),
+            output: Some(
+                uploaded
+                    .iter()
+                    .map(|a| format!("Uploaded {}", a.url))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            error: None,
+            metadata: Some(metadata),
+            error_kind: None,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let (name, version) = self.package_identity().await?;
+        let tagged = self.search_tagged(&name, &version).await?;
+
+        let mut problems = Vec::new();
+        let mut first = None;
+        for file in self.artifacts().await {
+            let path = self.artifact_path(&name, &version, &file);
+            let (expected, _) = sha256_file(&file).await?;
+
+            match self.remote_sha256(&path, &tagged).await? {
+                Some(actual) if actual == expected => {
+                    first.get_or_insert((self.artifact_url(&path), actual));
+                }
+                Some(actual) => problems.push(tr!(
+                    "registry.sha256_mismatch",
+                    file = path,
+                    expected = expected,
+                    actual = actual
+                )),
+                None => problems.push(tr!("registry.not_found", name = self.artifact_url(&path))),
+            }
+        }
+
+        if problems.is_empty()
+            && let Some((url, checksum)) = first
+        {
+            return Ok(VerificationResult {
+                verified: true,
+                version: Some(version),
+                url: Some(url.clone()),
+                error: None,
+                metadata: None,
+                integrity: Some(PackageIntegrity {
+                    download_url: Some(url),
+                    checksum: Some(checksum),
+                    ..Default::default()
+                }),
+            });
+        }
+
+        Ok(VerificationResult {
+            verified: false,
+            version: Some(version),
+            url: None,
+            error: Some(if problems.is_empty() {
+                tr!("registry.no_artifacts_to_verify")
+            } else {
+                problems.join("; ")
+            }),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_artifact_url_and_properties() {
+        let config = RepositoryManagerConfig {
+            url: "https://acme.jfrog.io/artifactory/".to_string(),
+            repository: "generic-local".to_string(),
+            properties: Some(HashMap::from([(
+                "build.url".to_string(),
+                "https://ci/1;a=b".to_string(),
+            )])),
+            ..Default::default()
+        };
+        let artifactory =
+            RepositoryManagerPlugin::new(PathBuf::from("."), RepositoryManagerKind::Artifactory)
+                .with_config(config.clone());
+        assert_eq!(
+            artifactory.artifact_url("app/1.0.0/app.tgz"),
+            "https://acme.jfrog.io/artifactory/generic-local/app/1.0.0/app.tgz"
+        );
+        assert_eq!(
+            artifactory_properties(&artifactory.properties("app", "1.0.0")),
+            r"build.url=https://ci/1\;a\=b;name=app;version=1.0.0"
+        );
+
+        let nexus = RepositoryManagerPlugin::new(PathBuf::from("."), RepositoryManagerKind::Nexus)
+            .with_config(RepositoryManagerConfig {
+                url: "https://nexus.acme.dev".to_string(),
+                ..config
+            });
+        assert_eq!(
+            nexus.artifact_url("app/1.0.0/app.tgz"),
+            "https://nexus.acme.dev/repository/generic-local/app/1.0.0/app.tgz"
+        );
+    }
+
+    #[test]
+    fn test_parse_search_responses() {
+        let search = json!({
+            "results": [
+                { "uri": "https://acme.jfrog.io/artifactory/api/storage/generic-local/app/1.0.0/app.tgz" },
+                { "uri": "https://acme.jfrog.io/artifactory/api/storage/other/app.tgz" }
+            ]
+        });
+        assert_eq!(
+            parse_artifactory_search(&search, "generic-local"),
+            vec!["app/1.0.0/app.tgz"]
+        );
+
+        let assets = json!({
+            "items": [{
+                "path": "app/1.0.0/app.tgz",
+                "downloadUrl": "https://nexus.acme.dev/repository/raw/app/1.0.0/app.tgz",
+                "checksum": { "sha1": "x", "sha256": "abc" }
+            }]
+        });
+        assert_eq!(
+            parse_nexus_asset(&assets, "app/1.0.0/app.tgz"),
+            Some((
+                "abc".to_string(),
+                "https://nexus.acme.dev/repository/raw/app/1.0.0/app.tgz".to_string()
+            ))
+        );
+        assert_eq!(parse_nexus_asset(&assets, "app/2.0.0/app.tgz"), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! Subprocess Plugin - registry plugins as external programs
+//!
+//! Plugins configured with `command` run as a child process and speak
+//! line-delimited JSON-RPC 2.0 over stdin/stdout, so they can be written in
+//! any language:
+//!
+//! ```text
+//! -> {"jsonrpc":"2.0","id":1,"method":"initialize","params":{"projectPath":"…","config":{…}}}
+//! <- {"jsonrpc":"2.0","id":1,"result":{"name":"acme","version":"1.0.0"}}
+//! -> {"jsonrpc":"2.0","id":2,"method":"publish","params":{"projectPath":"…","config":{…},"options":{…}}}
+//! <- {"jsonrpc":"2.0","id":2,"result":{"success":true,"version":"1.2.3"}}
+//! ```
+//!
+//! - Methods match [`RegistryPlugin`]: `detect`, `validate`, `dry_run`,
+//!   `publish`, `verify` and `rollback` (`version` param)
+//! - Results are the serialized result types (e.g. [`PublishResult`]);
+//!   failures are JSON-RPC errors (`{"error":{"code":…,"message":…}}`)
+//! - Anything the plugin writes to stderr is passed through; the process is
+//!   started once and killed when the plugin is dropped
+
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, RollbackResult, ValidationResult,
+    VerificationResult,
+};
+use crate::orchestration::hook_runner::split_command;
+use crate::tr;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+/// Default time a plugin may take to answer a request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Pipes of the running plugin process
+struct PluginProcess {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// Held so the process is killed on drop
+    _child: Child,
+}
+
+/// Registry plugin implemented by an external program
+pub struct SubprocessPlugin {
+    process: Mutex<PluginProcess>,
+    next_id: AtomicU64,
+    timeout: Duration,
+    name: String,
+    version: String,
+    project_path: PathBuf,
+    config: HashMap<String, serde_json::Value>,
+}
+
+impl SubprocessPlugin {
+    /// Start the plugin command in the project directory and initialize it
+    pub async fn spawn(
+        command: &str,
+        project_path: impl Into<PathBuf>,
+        config: HashMap<String, serde_json::Value>,
+    ) -> anyhow::Result<Self> {
+        let project_path = project_path.into();
+        let mut parts = split_command(command).map_err(|e| anyhow::anyhow!(e))?;
+        if parts.is_empty() {
+            anyhow::bail!("{}", tr!("plugin.empty_command"));
+        }
+        let program = parts.remove(0);
+
+        let mut child = Command::new(&program)
+            .args(&parts)
+            .current_dir(&project_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("plugin.spawn_failed", program = program, error = e)
+                )
+            })?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Plugin stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Plugin stdout unavailable"))?;
+
+        let mut plugin = Self {
+            process: Mutex::new(PluginProcess {
+                stdin,
+                stdout: BufReader::new(stdout),
+                _child: child,
+            }),
+            next_id: AtomicU64::new(1),
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            name: program,
+            version: String::new(),
+            project_path,
+            config,
+        };
+
+        let info: serde_json::Value = plugin.call("initialize", None).await?;
+        plugin.name = info["name"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("initialize result has no name"))?
+            .to_string();
+        plugin.version = info["version"].as_str().unwrap_or("0.0.0").to_string();
+        Ok(plugin)
+    }
+
+    /// Set the time a plugin may take to answer a request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send a request and wait for the response with the same id
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        extra: Option<(&str, serde_json::Value)>,
+    ) -> anyhow::Result<T> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut params = serde_json::json!({
+            "projectPath": self.project_path.to_string_lossy(),
+            "config": self.config,
+        });
+        if let Some((key, value)) = extra {
+            params[key] = value;
+        }
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let mut process = self.process.lock().await;
+        let exchange = async {
+            let line = format!("{}\n", request);
+            let written = match process.stdin.write_all(line.as_bytes()).await {
+                Ok(()) => process.stdin.flush().await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = written {
+                anyhow::bail!("Plugin {} exited unexpectedly: {}", self.name, e);
+            }
+
+            // Skip stray output and responses to abandoned requests
+            loop {
+                let mut line = String::new();
+                if process.stdout.read_line(&mut line).await? == 0 {
+                    anyhow::bail!("Plugin {} exited unexpectedly", self.name);
+                }
+                let Ok(response) = serde_json::from_str::<serde_json::Value>(&line) else {
+                    continue;
+                };
+                if response["id"].as_u64() == Some(id) {
+                    return Ok(response);
+                }
+            }
+        };
+        let mut response = tokio::time::timeout(self.timeout, exchange)
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Plugin {} did not answer {} within {:?}",
+                    self.name,
+                    method,
+                    self.timeout
+                )
+            })??;
+
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            anyhow::bail!(
+                "{}",
+                error["message"].as_str().unwrap_or("plugin request failed")
+            );
+        }
+        serde_json::from_value(response["result"].take())
+            .map_err(|e| anyhow::anyhow!("Invalid {} result from {}: {}", method, self.name, e))
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for SubprocessPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        self.call(
+            "detect",
+            Some((
+                "projectPath",
+                serde_json::Value::String(project_path.to_string()),
+            )),
+        )
+        .await
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        self.call("validate", None).await
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        self.call("dry_run", None).await
+    }
+
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let options = serde_json::to_value(options.unwrap_or_default())?;
+        self.call("publish", Some(("options", options))).await
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        self.call("verify", None).await
+    }
+
+    async fn rollback(&self, version: &str) -> anyhow::Result<RollbackResult> {
+        self.call(
+            "rollback",
+            Some(("version", serde_json::Value::String(version.to_string()))),
+        )
+        .await
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Plugin answering by method name; requests serialize with sorted keys,
+    /// so the id comes first
+    const PLUGIN_SCRIPT: &str = r#"
+while read -r line; do
+  id=$(printf '%s' "$line" | sed 's/^{"id":\([0-9]*\).*/\1/')
+  case "$line" in
+    *'"method":"initialize"'*) result='{"name":"acme","version":"0.2.0"}' ;;
+    *'"method":"publish"'*) result='{"success":true,"version":"1.2.3","output":"published"}' ;;
+    *'"method":"detect"'*) echo 'not json'; result='true' ;;
+    *) printf '{"jsonrpc":"2.0","id":%s,"error":{"code":-32601,"message":"method not found"}}\n' "$id"; continue ;;
+  esac
+  printf '{"jsonrpc":"2.0","id":%s,"result":%s}\n' "$id" "$result"
+done
+"#;
+
+    async fn plugin(temp_dir: &TempDir) -> SubprocessPlugin {
+        std::fs::write(temp_dir.path().join("plugin.sh"), PLUGIN_SCRIPT).unwrap();
+        SubprocessPlugin::spawn("sh plugin.sh", temp_dir.path(), HashMap::new())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_json_rpc_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin = plugin(&temp_dir).await;
+        assert_eq!(plugin.name(), "acme");
+        assert_eq!(plugin.version(), "0.2.0");
+
+        assert!(plugin.detect("/project").await.unwrap());
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.version.as_deref(), Some("1.2.3"));
+
+        let error = plugin.verify().await.unwrap_err();
+        assert_eq!(error.to_string(), "method not found");
+    }
+
+    #[tokio::test]
+    async fn test_reports_exited_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = SubprocessPlugin::spawn("sh -c true", temp_dir.path(), HashMap::new())
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("exited unexpectedly"));
+    }
+}
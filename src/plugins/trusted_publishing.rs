@@ -0,0 +1,256 @@
+//! crates.io trusted publishing
+//!
+//! GitHub Actions jobs with `permissions: id-token: write` can publish
+//! without a stored `CARGO_REGISTRY_TOKEN`: the job's OIDC token is exchanged
+//! at crates.io for a short-lived publish token (the repository and workflow
+//! must be configured as a trusted publisher of the crate), which is revoked
+//! after the publish.
+
+use crate::core::http::http_client;
+use crate::security::redaction::register_secret;
+use crate::tr;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// crates.io endpoint issuing and revoking trusted publishing tokens
+const TOKENS_URL: &str = "https://crates.io/api/v1/trusted_publishing/tokens";
+
+/// Audience of the OIDC token expected by crates.io
+const AUDIENCE: &str = "crates.io";
+
+/// Lookup of an environment variable
+type VarLookup = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Variables of the CI job, read from the process environment unless
+/// injected
+#[derive(Clone)]
+pub struct CiEnvironment {
+    vars: Arc<VarLookup>,
+    tokens_url: String,
+}
+
+impl std::fmt::Debug for CiEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CiEnvironment")
+            .field("tokens_url", &self.tokens_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for CiEnvironment {
+    fn default() -> Self {
+        Self::process()
+    }
+}
+
+impl CiEnvironment {
+    /// Variables of the running process
+    pub fn process() -> Self {
+        Self::from_fn(|name| std::env::var(name).ok())
+    }
+
+    /// Variables looked up through `vars`
+    pub fn from_fn(vars: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self {
+            vars: Arc::new(vars),
+            tokens_url: TOKENS_URL.to_string(),
+        }
+    }
+
+    /// Exchange and revoke tokens at `url` instead of crates.io
+    pub fn with_tokens_url(mut self, url: impl Into<String>) -> Self {
+        self.tokens_url = url.into();
+        self
+    }
+
+    /// Non-empty value of `name`
+    pub fn var(&self, name: &str) -> Option<String> {
+        (self.vars)(name).filter(|v| !v.is_empty())
+    }
+
+    /// OIDC token endpoint, when the job may request OIDC tokens
+    pub fn oidc(&self) -> Option<OidcEnvironment> {
+        let oidc = OidcEnvironment::from_env(|name| self.var(name))?;
+        Some(OidcEnvironment {
+            tokens_url: self.tokens_url.clone(),
+            ..oidc
+        })
+    }
+}
+
+/// OIDC token endpoint of a CI job
+#[derive(Debug, Clone)]
+pub struct OidcEnvironment {
+    request_url: String,
+    request_token: SecretString,
+    tokens_url: String,
+}
+
+#[derive(Deserialize)]
+struct IdTokenResponse {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct ExchangeResponse {
+    token: String,
+}
+
+impl OidcEnvironment {
+    /// Endpoint of the running GitHub Actions job, when it may request OIDC
+    /// tokens
+    pub fn detect() -> Option<Self> {
+        CiEnvironment::process().oidc()
+    }
+
+    /// Like [`detect`](Self::detect), reading variables through `var`
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let non_empty = |name| var(name).filter(|v| !v.is_empty());
+        Some(Self {
+            request_url: non_empty("ACTIONS_ID_TOKEN_REQUEST_URL")?,
+            request_token: SecretString::from(non_empty("ACTIONS_ID_TOKEN_REQUEST_TOKEN")?),
+            tokens_url: TOKENS_URL.to_string(),
+        })
+    }
+
+    /// URL requesting a token for `audience`
+    fn token_url(&self, audience: &str) -> String {
+        let separator = if self.request_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        format!("{}{}audience={}", self.request_url, separator, audience)
+    }
+
+    /// OIDC token of the job for `audience`
+    async fn id_token(&self, audience: &str) -> anyhow::Result<SecretString> {
+        let response = http_client()
+            .get(self.token_url(audience))
+            .header(
+                "Authorization",
+                format!("bearer {}", self.request_token.expose_secret()),
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!("trusted_publishing.oidc_failed", status = response.status())
+            );
+        }
+        let token = response.json::<IdTokenResponse>().await?.value;
+        register_secret(token.as_str());
+        Ok(SecretString::from(token))
+    }
+
+    /// Exchange the job's OIDC token for a crates.io publish token
+    pub async fn exchange(&self) -> anyhow::Result<TrustedPublishingToken> {
+        let jwt = self.id_token(AUDIENCE).await?;
+        let response = http_client()
+            .post(&self.tokens_url)
+            .json(&serde_json::json!({ "jwt": jwt.expose_secret() }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "trusted_publishing.exchange_failed",
+                    status = status,
+                    message = body.trim()
+                )
+            );
+        }
+        let token = response.json::<ExchangeResponse>().await?.token;
+        register_secret(token.as_str());
+        Ok(TrustedPublishingToken {
+            token: SecretString::from(token),
+            tokens_url: self.tokens_url.clone(),
+        })
+    }
+}
+
+/// Short-lived crates.io publish token
+#[derive(Debug)]
+pub struct TrustedPublishingToken {
+    token: SecretString,
+    tokens_url: String,
+}
+
+impl TrustedPublishingToken {
+    /// Value for `CARGO_REGISTRY_TOKEN`
+    pub fn expose(&self) -> &str {
+        self.token.expose_secret()
+    }
+
+    /// Revoke the token before it expires
+    pub async fn revoke(&self) -> anyhow::Result<()> {
+        let response = http_client()
+            .delete(&self.tokens_url)
+            .header("Authorization", format!("Bearer {}", self.expose()))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "trusted_publishing.revoke_failed",
+                    status = response.status()
+                )
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_github_actions_oidc() {
+        let env = |name: &str| match name {
+            "ACTIONS_ID_TOKEN_REQUEST_URL" => {
+                Some("https://token.actions.example/id?api-version=2.0".to_string())
+            }
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN" => Some("request-token".to_string()),
+            _ => None,
+        };
+        let oidc = OidcEnvironment::from_env(env).unwrap();
+        assert_eq!(
+            oidc.token_url(AUDIENCE),
+            "https://token.actions.example/id?api-version=2.0&audience=crates.io"
+        );
+
+        // Both variables are required
+        let without_token = |name: &str| {
+            (name == "ACTIONS_ID_TOKEN_REQUEST_URL").then(|| "https://token.example".to_string())
+        };
+        assert!(OidcEnvironment::from_env(without_token).is_none());
+        assert!(OidcEnvironment::from_env(|_| None).is_none());
+    }
+
+    #[test]
+    fn test_injected_ci_environment() {
+        let ci = CiEnvironment::from_fn(|name| match name {
+            "ACTIONS_ID_TOKEN_REQUEST_URL" => Some("https://token.example/id".to_string()),
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN" => Some("request-token".to_string()),
+            "CARGO_REGISTRY_TOKEN" => Some(String::new()),
+            _ => None,
+        })
+        .with_tokens_url("http://127.0.0.1:1/tokens");
+        let oidc = ci.oidc().unwrap();
+        assert_eq!(oidc.tokens_url, "http://127.0.0.1:1/tokens");
+        assert_eq!(
+            oidc.token_url(AUDIENCE),
+            "https://token.example/id?audience=crates.io"
+        );
+        // Empty variables count as unset
+        assert!(ci.var("CARGO_REGISTRY_TOKEN").is_none());
+        assert!(CiEnvironment::from_fn(|_| None).oidc().is_none());
+    }
+}
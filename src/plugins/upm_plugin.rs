@@ -0,0 +1,660 @@
+//! UPM Plugin - Unity Package Manager registry publishing
+//!
+//! This module provides publishing to npm-compatible UPM registries
+//! (Verdaccio, self-hosted scoped registries, etc.):
+//! - UPM package.json detection (`unity` field)
+//! - Reverse-domain package name and Unity version constraint validation
+//! - `Samples~` layout and `.meta` file checks
+//! - Publishing via `npm publish --registry`
+//! - Verification that the version resolves from the registry
+
+use crate::core::config::UpmRegistryConfig;
+use crate::core::http::http_client;
+use crate::core::rate_limit::RateLimitedSend;
+use crate::core::traits::{
+    DryRunResult, PackageIntegrity, PublishOptions, PublishResult, RegistryPlugin, ValidationError,
+    ValidationResult, ValidationWarning, VerificationResult,
+};
+use crate::security::command_executor::CommandLimits;
+use crate::tr;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const DEFAULT_TOKEN_ENV: &str = "UPM_TOKEN";
+
+/// UPM sample entry (`samples` array)
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpmSample {
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub path: Option<String>,
+}
+
+/// UPM package.json (subset)
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpmPackageJson {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub unity: Option<String>,
+    #[serde(rename = "unityRelease")]
+    pub unity_release: Option<String>,
+    #[serde(default)]
+    pub dependencies: HashMap<String, String>,
+    #[serde(default)]
+    pub samples: Vec<UpmSample>,
+    #[serde(rename = "publishConfig")]
+    pub publish_config: Option<serde_json::Value>,
+}
+
+/// Registry package document (subset)
+#[derive(Debug, Deserialize)]
+struct UpmRegistryInfo {
+    #[serde(default)]
+    versions: HashMap<String, serde_json::Value>,
+}
+
+/// Environment variables npm needs on top of the sandbox allowlist
+const TOOL_ENV: &[&str] = &["NODE_*", "NPM_CONFIG_*", "npm_config_*"];
+
+/// Unity Package Manager registry plugin
+pub struct UpmPlugin {
+    project_path: PathBuf,
+    config: UpmRegistryConfig,
+    limits: CommandLimits,
+}
+
+impl Default for UpmPlugin {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl UpmPlugin {
+    /// Create a new UPM plugin instance
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            config: UpmRegistryConfig::default(),
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Apply `registries.upm` settings
+    pub fn with_config(mut self, config: UpmRegistryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run npm with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits.allowing(TOOL_ENV);
+        self
+    }
+
+    async fn load_package_json(&self) -> anyhow::Result<UpmPackageJson> {
+        let content = fs::read_to_string(self.project_path.join("package.json")).await?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Registry URL (`registries.upm.registry`, then `publishConfig.registry`)
+    fn registry_url(&self, pkg: &UpmPackageJson) -> Option<String> {
+        self.config
+            .registry
+            .clone()
+            .or_else(|| {
+                pkg.publish_config
+                    .as_ref()
+                    .and_then(|p| p.get("registry"))
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string)
+            })
+            .map(|url| url.trim_end_matches('/').to_string())
+    }
+
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    /// Validate a UPM package name (reverse domain notation, e.g. `com.company.tool`)
+    fn validate_package_name(&self, name: &str) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        let valid = Regex::new(r"^[a-z0-9][a-z0-9_-]*(\.[a-z0-9_-]+)+$").unwrap();
+        if !valid.is_match(name) {
+            errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("upm.invalid_name"),
+                severity: "error".to_string(),
+            });
+        }
+
+        if name.len() > 214 {
+            errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("registry.name_too_long_214"),
+                severity: "error".to_string(),
+            });
+        }
+
+        errors
+    }
+
+    /// Validate `unity` / `unityRelease` constraints
+    fn validate_unity_version(
+        &self,
+        pkg: &UpmPackageJson,
+        errors: &mut Vec<ValidationError>,
+        warnings: &mut Vec<ValidationWarning>,
+    ) {
+        if let Some(ref unity) = pkg.unity
+            && !Regex::new(r"^\d{4}\.\d+$").unwrap().is_match(unity)
+        {
+            errors.push(ValidationError {
+                field: "unity".to_string(),
+                message: tr!("upm.invalid_unity", unity = unity),
+                severity: "error".to_string(),
+            });
+        }
+
+        if let Some(ref release) = pkg.unity_release {
+            if pkg.unity.is_none() {
+                errors.push(ValidationError {
+                    field: "unityRelease".to_string(),
+                    message: tr!("upm.unity_required"),
+                    severity: "error".to_string(),
+                });
+            }
+            if !Regex::new(r"^\d+[abfp]\d+$").unwrap().is_match(release) {
+                errors.push(ValidationError {
+                    field: "unityRelease".to_string(),
+                    message: tr!("upm.invalid_unity_release", release = release),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        // UPM resolves exact versions only
+        for (dependency, version) in &pkg.dependencies {
+            if semver::Version::parse(version).is_err() {
+                warnings.push(ValidationWarning {
+                    field: format!("dependencies.{}", dependency),
+                    message: tr!("upm.range_not_supported", dependency = version),
+                    severity: "warning".to_string(),
+                });
+            }
+        }
+    }
+
+    /// Validate the `samples` entries and `Samples~` layout
+    async fn validate_samples(
+        &self,
+        pkg: &UpmPackageJson,
+        errors: &mut Vec<ValidationError>,
+        warnings: &mut Vec<ValidationWarning>,
+    ) {
+        for (i, sample) in pkg.samples.iter().enumerate() {
+            let field = format!("samples[{}]", i);
+            if sample.display_name.is_none() {
+                errors.push(ValidationError {
+                    field: format!("{}.displayName", field),
+                    message: tr!("upm.sample_display_name_required"),
+                    severity: "error".to_string(),
+                });
+            }
+            if sample.description.is_none() {
+                warnings.push(ValidationWarning {
+                    field: format!("{}.description", field),
+                    message: tr!("upm.sample_description_recommended"),
+                    severity: "warning".to_string(),
+                });
+            }
+
+            let Some(ref path) = sample.path else {
+                errors.push(ValidationError {
+                    field: format!("{}.path", field),
+                    message: tr!("upm.sample_path_required"),
+                    severity: "error".to_string(),
+                });
+                continue;
+            };
+
+            if !path.starts_with("Samples~/") {
+                errors.push(ValidationError {
+                    field: format!("{}.path", field),
+                    message: tr!("upm.sample_outside_samples", path = path),
+                    severity: "error".to_string(),
+                });
+            }
+            if !fs::metadata(self.project_path.join(path))
+                .await
+                .is_ok_and(|m| m.is_dir())
+            {
+                errors.push(ValidationError {
+                    field: format!("{}.path", field),
+                    message: tr!("upm.sample_not_found", path = path),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        if fs::metadata(self.project_path.join("Samples"))
+            .await
+            .is_ok_and(|m| m.is_dir())
+        {
+            warnings.push(ValidationWarning {
+                field: "Samples".to_string(),
+                message: tr!("upm.samples_imported"),
+                severity: "warning".to_string(),
+            });
+        }
+    }
+
+    /// Top-level package entries missing a `.meta` file
+    async fn missing_meta_files(&self) -> Vec<String> {
+        let Ok(mut entries) = fs::read_dir(&self.project_path).await else {
+            return Vec::new();
+        };
+
+        let mut names = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+
+        let mut missing: Vec<String> = names
+            .iter()
+            .filter(|name| {
+                !name.starts_with('.')
+                    && !name.ends_with('~')
+                    && !name.ends_with(".meta")
+                    && name.as_str() != "node_modules"
+                    && !names.contains(&format!("{}.meta", name))
+            })
+            .cloned()
+            .collect();
+        missing.sort();
+        missing
+    }
+
+    async fn run_npm(&self, args: &[&str], envs: &[(&str, &str)]) -> anyhow::Result<String> {
+        let mut command = self.limits.command("npm");
+        command
+            .args(args)
+            .envs(envs.iter().copied())
+            .current_dir(&self.project_path);
+        let output = self.limits.output(&mut command).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            anyhow::bail!("{}", stderr);
+        }
+
+        Ok(stdout + &stderr)
+    }
+
+    async fn fetch_package_info(
+        &self,
+        registry: &str,
+        name: &str,
+    ) -> anyhow::Result<UpmRegistryInfo> {
+        let mut request = http_client().get(format!("{}/{}", registry, name));
+        if let Ok(token) = std::env::var(self.token_env()) {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send_rate_limited().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                tr!(
+                    "registry.package_not_found",
+                    package = name,
+                    registry = registry,
+                    status = response.status()
+                )
+            );
+        }
+
+        Ok(response.json::<UpmRegistryInfo>().await?)
+    }
+}
+
+/// npm config key for a registry's auth token (`//host/path/:_authToken`)
+fn auth_token_key(registry: &str) -> String {
+    let without_scheme = registry
+        .trim_start_matches("https:")
+        .trim_start_matches("http:");
+    format!("npm_config_{}/:_authToken", without_scheme)
+}
+
+#[async_trait]
+impl RegistryPlugin for UpmPlugin {
+    fn name(&self) -> &str {
+        "upm"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, project_path: &str) -> anyhow::Result<bool> {
+        let path = Path::new(project_path).join("package.json");
+        let Ok(content) = fs::read_to_string(&path).await else {
+            return Ok(false);
+        };
+        let json: serde_json::Value = serde_json::from_str(&content)?;
+        Ok(json.get("unity").is_some())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut metadata = HashMap::new();
+
+        let pkg = self.load_package_json().await?;
+
+        match pkg.name {
+            Some(ref name) => {
+                errors.extend(self.validate_package_name(name));
+                metadata.insert(
+                    "packageName".to_string(),
+                    serde_json::Value::String(name.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "name".to_string(),
+                message: tr!("registry.name_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        match pkg.version {
+            Some(ref version) if semver::Version::parse(version).is_err() => {
+                errors.push(ValidationError {
+                    field: "version".to_string(),
+                    message: tr!("registry.invalid_semver", version = version),
+                    severity: "error".to_string(),
+                });
+            }
+            Some(ref version) => {
+                metadata.insert(
+                    "version".to_string(),
+                    serde_json::Value::String(version.clone()),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "version".to_string(),
+                message: tr!("registry.version_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        if pkg.display_name.is_none() {
+            warnings.push(ValidationWarning {
+                field: "displayName".to_string(),
+                message: tr!("upm.display_name_recommended"),
+                severity: "warning".to_string(),
+            });
+        }
+        if pkg.description.is_none() {
+            warnings.push(ValidationWarning {
+                field: "description".to_string(),
+                message: tr!("registry.description_recommended"),
+                severity: "warning".to_string(),
+            });
+        }
+
+        self.validate_unity_version(&pkg, &mut errors, &mut warnings);
+        self.validate_samples(&pkg, &mut errors, &mut warnings)
+            .await;
+
+        let missing_meta = self.missing_meta_files().await;
+        if !missing_meta.is_empty() {
+            warnings.push(ValidationWarning {
+                field: "meta".to_string(),
+                message: tr!("upm.meta_missing", files = missing_meta.join(", ")),
+                severity: "warning".to_string(),
+            });
+        }
+
+        match self.registry_url(&pkg) {
+            Some(registry) => {
+                metadata.insert(
+                    "registryUrl".to_string(),
+                    serde_json::Value::String(registry),
+                );
+            }
+            None => errors.push(ValidationError {
+                field: "registry".to_string(),
+                message: tr!("upm.registry_required"),
+                severity: "error".to_string(),
+            }),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let pkg = self.load_package_json().await?;
+        let Some(registry) = self.registry_url(&pkg) else {
+            anyhow::bail!("{}", tr!("upm.registry_missing"));
+        };
+
+        match self
+            .run_npm(&["publish", "--dry-run", "--registry", &registry], &[])
+            .await
+        {
+            Ok(output) => Ok(DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            }),
+            Err(e) => Ok(DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "publish".to_string(),
+                    message: tr!("registry.dry_run_failed", output = e),
+                    severity: "error".to_string(),
+                }]),
+            }),
+        }
+    }
+
+    async fn publish(&self, options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let opts = options.unwrap_or_default();
+        let pkg = self.load_package_json().await?;
+        let Some(registry) = self.registry_url(&pkg) else {
+            anyhow::bail!("{}", tr!("upm.registry_missing"));
+        };
+
+        let mut args = vec!["publish", "--registry", registry.as_str()];
+        if let Some(ref tag) = opts.tag {
+            args.push("--tag");
+            args.push(tag);
+        }
+
+        // Token from the environment; otherwise rely on the user's .npmrc
+        let token = std::env::var(self.token_env()).ok();
+        let auth_key = auth_token_key(&registry);
+        let envs: Vec<(&str, &str)> = token
+            .as_deref()
+            .map(|t| vec![(auth_key.as_str(), t)])
+            .unwrap_or_default();
+
+        match self.run_npm(&args, &envs).await {
+            Ok(output) => Ok(PublishResult {
+                success: true,
+                version: pkg.version,
+                package_url: pkg.name.map(|name| format!("{}/{}", registry, name)),
+                output: Some(output),
+                error: None,
+                metadata: None,
+                error_kind: None,
+            }),
+            Err(e) => {
+                let error_msg = e.to_string();
+                let error = if error_msg.contains("E401") || error_msg.contains("ENEEDAUTH") {
+                    tr!("upm.auth_failed", token_env = self.token_env())
+                } else {
+                    error_msg
+                };
+                Ok(PublishResult {
+                    success: false,
+                    version: None,
+                    package_url: None,
+                    output: None,
+                    error: Some(error),
+                    metadata: None,
+                    error_kind: None,
+                })
+            }
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let pkg = self.load_package_json().await?;
+        let Some(registry) = self.registry_url(&pkg) else {
+            anyhow::bail!("{}", tr!("upm.registry_missing"));
+        };
+        let name = pkg
+            .name
+            .ok_or_else(|| anyhow::anyhow!("Package name not found"))?;
+        let expected_version = pkg
+            .version
+            .ok_or_else(|| anyhow::anyhow!("Package version not found"))?;
+        let url = format!("{}/{}", registry, name);
+
+        match self.fetch_package_info(&registry, &name).await {
+            Ok(info) => match info.versions.get(&expected_version) {
+                Some(document) => {
+                    let dist = document.get("dist");
+                    let field = |key: &str| {
+                        dist.and_then(|d| d.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    };
+                    Ok(VerificationResult {
+                        verified: true,
+                        version: Some(expected_version),
+                        url: Some(url),
+                        error: None,
+                        metadata: None,
+                        integrity: Some(PackageIntegrity {
+                            download_url: field("tarball"),
+                            shasum: field("shasum"),
+                            integrity: field("integrity"),
+                            checksum: None,
+                        }),
+                    })
+                }
+                None => Ok(VerificationResult {
+                    verified: false,
+                    version: Some(expected_version.clone()),
+                    url: Some(url),
+                    error: Some(tr!(
+                        "upm.version_not_found",
+                        version = expected_version,
+                        registry = registry
+                    )),
+                    metadata: None,
+                    integrity: None,
+                }),
+            },
+            Err(e) => Ok(VerificationResult {
+                verified: false,
+                version: Some(expected_version),
+                url: Some(url),
+                error: Some(tr!("registry.verify_failed", error = e)),
+                metadata: None,
+                integrity: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_package_name() {
+        let plugin = UpmPlugin::default();
+        assert!(plugin.validate_package_name("com.company.tool").is_empty());
+        assert!(!plugin.validate_package_name("Tool").is_empty());
+        assert!(!plugin.validate_package_name("@scope/tool").is_empty());
+    }
+
+    #[test]
+    fn test_auth_token_key() {
+        assert_eq!(
+            auth_token_key("https://upm.example.com/registry"),
+            "npm_config_//upm.example.com/registry/:_authToken"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_unity_package() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{
+                "name": "com.company.tool",
+                "version": "1.0.0",
+                "displayName": "Tool",
+                "description": "A tool",
+                "unity": "2021.3",
+                "unityRelease": "0f1",
+                "dependencies": { "com.unity.ugui": "^1.0.0" },
+                "samples": [
+                    { "displayName": "Basic", "description": "Basic usage", "path": "Samples~/Basic" },
+                    { "displayName": "Broken", "path": "Samples/Broken" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Samples~/Basic")).unwrap();
+
+        let plugin = UpmPlugin::new(temp_dir.path().to_path_buf()).with_config(UpmRegistryConfig {
+            registry: Some("https://upm.example.com/".to_string()),
+            ..Default::default()
+        });
+        let result = plugin.validate().await.unwrap();
+
+        // Broken sample: outside Samples~ and missing directory
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors.iter().all(|e| e.field == "samples[1].path"));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.field == "dependencies.com.unity.ugui")
+        );
+        assert!(result.warnings.iter().any(|w| w.field == "meta"));
+        assert_eq!(
+            result.metadata.unwrap()["registryUrl"],
+            "https://upm.example.com"
+        );
+    }
+}
@@ -0,0 +1,1125 @@
+//! Windows Manifest Plugin - Scoop bucket and winget manifest publishing
+//!
+//! Windows builds released elsewhere (GitHub releases, a bucket, ...) become
+//! installable by updating package manager manifests:
+//! - Scoop: `bucket/<name>.json` in the bucket repository is updated in place
+//!   (`version`, `url`/`hash` or `architecture.*`), or generated when missing
+//! - winget: version, installer and default-locale manifests are generated
+//!   under `manifests/<letter>/<Publisher>/<Package>/<version>/`
+//! - SHA-256 hashes are computed from the downloaded release assets
+//! - Manifests are written through the GitHub API and either committed to the
+//!   default branch or pushed to a branch and opened as a pull request
+//!   (always for winget, whose repository only accepts PRs)
+
+use crate::core::config::WindowsManifestConfig;
+use crate::core::github::GitHubClient;
+use crate::core::traits::{
+    DryRunResult, PublishOptions, PublishResult, RegistryPlugin, ValidationError, ValidationResult,
+    VerificationResult,
+};
+use crate::plugins::bucket_plugin::manifest_identity;
+use crate::plugins::homebrew_plugin::HomebrewPlugin;
+use crate::tr;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const DEFAULT_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+const DEFAULT_WINGET_REPOSITORY: &str = "microsoft/winget-pkgs";
+
+const DEFAULT_SCOOP_MANIFEST_PATH: &str = "bucket/{name}.json";
+
+const WINGET_MANIFEST_VERSION: &str = "1.6.0";
+
+/// Manifest format handled by a [`WindowsManifestPlugin`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowsManifestKind {
+    Scoop,
+    Winget,
+}
+
+impl WindowsManifestKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindowsManifestKind::Scoop => "scoop",
+            WindowsManifestKind::Winget => "winget",
+        }
+    }
+}
+
+/// Windows build for one architecture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowsInstaller {
+    /// "x64", "x86" or "arm64"
+    pub architecture: String,
+    pub url: String,
+    pub sha256: String,
+}
+
+/// Scoop `architecture` key for an architecture name
+fn scoop_architecture(architecture: &str) -> anyhow::Result<&'static str> {
+    match architecture {
+        "x64" => Ok("64bit"),
+        "x86" => Ok("32bit"),
+        "arm64" => Ok("arm64"),
+        other => anyhow::bail!("Unsupported architecture: {} (x64, x86 or arm64)", other),
+    }
+}
+
+/// Update (or create) a Scoop manifest for a new version
+///
+/// Key order and unrelated fields (`autoupdate`, `checkver`, `notes`, ...) are
+/// kept. A single installer updates the top-level `url`/`hash` unless the
+/// manifest already uses `architecture`; several installers always go under
+/// `architecture`.
+pub fn update_scoop_manifest(
+    existing: Option<&str>,
+    config: &WindowsManifestConfig,
+    name: &str,
+    version: &str,
+    installers: &[WindowsInstaller],
+) -> anyhow::Result<String> {
+    use serde_yaml::Value;
+
+    // serde_yaml's mapping keeps insertion order, unlike serde_json's map
+    let mut manifest: Value = match existing {
+        Some(content) => serde_json::from_str(content)?,
+        None => {
+            let mut manifest = serde_yaml::Mapping::new();
+            manifest.insert("version".into(), Value::Null);
+            manifest.insert(
+                "description".into(),
+                config.description.as_deref().unwrap_or(name).into(),
+            );
+            if let Some(homepage) = &config.homepage {
+                manifest.insert("homepage".into(), homepage.as_str().into());
+            }
+            if let Some(license) = &config.license {
+                manifest.insert("license".into(), license.as_str().into());
+            }
+            Value::Mapping(manifest)
+        }
+    };
+    if !manifest.is_mapping() {
+        anyhow::bail!("Scoop manifest is not a JSON object");
+    }
+
+    manifest["version"] = version.into();
+    if installers.len() == 1 && manifest.get("architecture").is_none() {
+        manifest["url"] = installers[0].url.as_str().into();
+        manifest["hash"] = installers[0].sha256.as_str().into();
+    } else {
+        if let Some(mapping) = manifest.as_mapping_mut() {
+            mapping.remove("url");
+            mapping.remove("hash");
+        }
+        for installer in installers {
+            let key = scoop_architecture(&installer.architecture)?;
+            manifest["architecture"][key]["url"] = installer.url.as_str().into();
+            manifest["architecture"][key]["hash"] = installer.sha256.as_str().into();
+        }
+    }
+    if existing.is_none() {
+        let bin = config
+            .bin
+            .clone()
+            .unwrap_or_else(|| format!("{}.exe", name));
+        manifest["bin"] = bin.into();
+    }
+
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut out,
+        serde_json::ser::PrettyFormatter::with_indent(b"    "),
+    );
+    manifest.serialize(&mut serializer)?;
+    out.push(b'\n');
+    Ok(String::from_utf8(out)?)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WingetVersionManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    default_locale: &'a str,
+    manifest_type: &'a str,
+    manifest_version: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WingetInstallerManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    installer_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nested_installer_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    nested_installer_files: Vec<WingetNestedInstallerFile<'a>>,
+    installers: Vec<WingetInstaller<'a>>,
+    manifest_type: &'a str,
+    manifest_version: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WingetNestedInstallerFile<'a> {
+    relative_file_path: &'a str,
+    portable_command_alias: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WingetInstaller<'a> {
+    architecture: &'a str,
+    installer_url: &'a str,
+    installer_sha256: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WingetLocaleManifest<'a> {
+    package_identifier: &'a str,
+    package_version: &'a str,
+    package_locale: &'a str,
+    publisher: &'a str,
+    package_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_url: Option<&'a str>,
+    license: &'a str,
+    short_description: &'a str,
+    manifest_type: &'a str,
+    manifest_version: &'a str,
+}
+
+/// Directory of a winget package version (`manifests/m/Me/Tool/1.0.0`)
+pub fn winget_manifest_dir(
+    config: &WindowsManifestConfig,
+    identifier: &str,
+    version: &str,
+) -> String {
+    match &config.manifest_path {
+        Some(path) => path
+            .replace("{version}", version)
+            .trim_end_matches('/')
+            .to_string(),
+        None => format!(
+            "manifests/{}/{}/{}",
+            identifier
+                .chars()
+                .next()
+                .unwrap_or('_')
+                .to_ascii_lowercase(),
+            identifier.replace('.', "/"),
+            version
+        ),
+    }
+}
+
+/// winget installer type (and nested installer type) for a download URL
+fn winget_installer_type(url: &str) -> anyhow::Result<(&'static str, Option<&'static str>)> {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("zip") => Ok(("zip", Some("portable"))),
+        Some("msi") => Ok(("msi", None)),
+        Some("msix") => Ok(("msix", None)),
+        Some("exe") => Ok(("portable", None)),
+        _ => anyhow::bail!(
+            "{}",
+            tr!("windows_manifest.unsupported_installer", installer = url)
+        ),
+    }
+}
+
+/// winget version, installer and default-locale manifests (path, content)
+pub fn winget_manifests(
+    config: &WindowsManifestConfig,
+    name: &str,
+    version: &str,
+    installers: &[WindowsInstaller],
+) -> anyhow::Result<Vec<(String, String)>> {
+    let identifier = config
+        .package_identifier
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("{}", tr!("windows_manifest.identifier_required")))?;
+    let publisher = config
+        .publisher
+        .as_deref()
+        .unwrap_or_else(|| identifier.split('.').next().unwrap_or(identifier));
+    let license = config
+        .license
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("{}", tr!("windows_manifest.license_required")))?;
+    let description = config
+        .description
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("{}", tr!("windows_manifest.description_required")))?;
+    let first = installers
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("{}", tr!("windows_manifest.url_required")))?;
+    let (installer_type, nested_installer_type) = winget_installer_type(&first.url)?;
+    let bin = config
+        .bin
+        .clone()
+        .unwrap_or_else(|| format!("{}.exe", name));
+    let locale = "en-US";
+
+    let version_manifest = WingetVersionManifest {
+        package_identifier: identifier,
+        package_version: version,
+        default_locale: locale,
+        manifest_type: "version",
+        manifest_version: WINGET_MANIFEST_VERSION,
+    };
+    let installer_manifest = WingetInstallerManifest {
+        package_identifier: identifier,
+        package_version: version,
+        installer_type,
+        nested_installer_type,
+        nested_installer_files: match nested_installer_type {
+            Some(_) => vec![WingetNestedInstallerFile {
+                relative_file_path: &bin,
+                portable_command_alias: name,
+            }],
+            None => Vec::new(),
+        },
+        installers: installers
+            .iter()
+            .map(|installer| WingetInstaller {
+                architecture: &installer.architecture,
+                installer_url: &installer.url,
+                installer_sha256: installer.sha256.to_uppercase(),
+            })
+            .collect(),
+        manifest_type: "installer",
+        manifest_version: WINGET_MANIFEST_VERSION,
+    };
+    let locale_manifest = WingetLocaleManifest {
+        package_identifier: identifier,
+        package_version: version,
+        package_locale: locale,
+        publisher,
+        package_name: name,
+        package_url: config.homepage.as_deref(),
+        license,
+        short_description: description,
+        manifest_type: "defaultLocale",
+        manifest_version: WINGET_MANIFEST_VERSION,
+    };
+
+    let document = |schema: &str, body: String| {
+        format!(
+            "# Created with package-publisher\n# yaml-language-server: $schema=https://aka.ms/winget-manifest.{}.{}.schema.json\n\n{}",
+            schema, WINGET_MANIFEST_VERSION, body
+        )
+    };
+    let dir = winget_manifest_dir(config, identifier, version);
+    Ok(vec![
+        (
+            format!("{}/{}.yaml", dir, identifier),
+            document("version", serde_yaml::to_string(&version_manifest)?),
+        ),
+        (
+            format!("{}/{}.installer.yaml", dir, identifier),
+            document("installer", serde_yaml::to_string(&installer_manifest)?),
+        ),
+        (
+            format!("{}/{}.locale.{}.yaml", dir, identifier, locale),
+            document("defaultLocale", serde_yaml::to_string(&locale_manifest)?),
+        ),
+    ])
+}
+
+/// Scoop / winget manifest plugin
+pub struct WindowsManifestPlugin {
+    project_path: PathBuf,
+    kind: WindowsManifestKind,
+    config: WindowsManifestConfig,
+    github: GitHubClient,
+}
+
+impl WindowsManifestPlugin {
+    /// Create a new plugin instance for `kind`
+    pub fn new(project_path: PathBuf, kind: WindowsManifestKind) -> Self {
+        Self {
+            project_path,
+            kind,
+            config: WindowsManifestConfig::default(),
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Apply `registries.scoop` / `registries.winget` settings
+    pub fn with_config(mut self, config: WindowsManifestConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    async fn package_identity(&self) -> anyhow::Result<(String, String)> {
+        let (name, version) = manifest_identity(&self.project_path).await;
+        match (self.config.name.clone().or(name), version) {
+            (Some(name), Some(version)) => Ok((name, version)),
+            (None, _) => anyhow::bail!(
+                "Package name not found (set registries.{}.name)",
+                self.kind.as_str()
+            ),
+            (_, None) => anyhow::bail!("Package version not found"),
+        }
+    }
+
+    fn repository(&self) -> anyhow::Result<String> {
+        match (&self.config.repository, self.kind) {
+            (Some(repository), _) => Ok(repository.clone()),
+            (None, WindowsManifestKind::Winget) => Ok(DEFAULT_WINGET_REPOSITORY.to_string()),
+            (None, WindowsManifestKind::Scoop) => {
+                anyhow::bail!("{}", tr!("windows_manifest.bucket_required"))
+            }
+        }
+    }
+
+    fn pull_request(&self) -> bool {
+        self.kind == WindowsManifestKind::Winget || self.config.pull_request.unwrap_or(false)
+    }
+
+    /// Configured download URL templates as (architecture, template)
+    fn url_templates(&self) -> Vec<(String, String)> {
+        match (&self.config.urls, &self.config.url) {
+            (Some(urls), _) if !urls.is_empty() => urls
+                .iter()
+                .map(|(arch, url)| (arch.clone(), url.clone()))
+                .collect(),
+            (_, Some(url)) => vec![("x64".to_string(), url.clone())],
+            _ => Vec::new(),
+        }
+    }
+
+    fn token_env(&self) -> &str {
+        self.config
+            .token_env
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV)
+    }
+
+    fn token(&self) -> anyhow::Result<String> {
+        std::env::var(self.token_env())
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}",
+                    tr!("registry.not_configured", name = self.token_env())
+                )
+            })
+    }
+
+    fn scoop_manifest_path(&self, name: &str) -> String {
+        self.config
+            .manifest_path
+            .as_deref()
+            .unwrap_or(DEFAULT_SCOOP_MANIFEST_PATH)
+            .replace("{name}", name)
+    }
+
+    /// Download the release assets and hash them
+    async fn installers(&self, version: &str) -> anyhow::Result<Vec<WindowsInstaller>> {
+        let mut installers = Vec::new();
+        for (architecture, template) in self.url_templates() {
+            let url = template.replace("{version}", version);
+            let sha256 = HomebrewPlugin::fetch_sha256(&url).await?;
+            installers.push(WindowsInstaller {
+                architecture,
+                url,
+                sha256,
+            });
+        }
+        Ok(installers)
+    }
+
+    /// Manifest files for the release (path, content)
+    async fn manifest_files(
+        &self,
+        name: &str,
+        version: &str,
+        installers: &[WindowsInstaller],
+        repository: &str,
+        base: &str,
+        github: &GitHubClient,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        match self.kind {
+            WindowsManifestKind::Scoop => {
+                let path = self.scoop_manifest_path(name);
+                let existing = github.fetch_file(repository, &path, base).await?;
+                let content = update_scoop_manifest(
+                    existing.as_ref().map(|(_, content)| content.as_str()),
+                    &self.config,
+                    name,
+                    version,
+                    installers,
+                )?;
+                Ok(vec![(path, content)])
+            }
+            WindowsManifestKind::Winget => {
+                winget_manifests(&self.config, name, version, installers)
+            }
+        }
+    }
+
+    fn commit_message(&self, name: &str, version: &str) -> String {
+        match self.kind {
+            WindowsManifestKind::Scoop => format!("{}: Update to version {}", name, version),
+            WindowsManifestKind::Winget => format!(
+                "New version: {} version {}",
+                self.config.package_identifier.as_deref().unwrap_or(name),
+                version
+            ),
+        }
+    }
+
+    /// Write the manifests; returns the PR URL or the manifest URL
+    async fn submit(
+        &self,
+        name: &str,
+        version: &str,
+        files: &[(String, String)],
+        repository: &str,
+        base: &str,
+        github: &GitHubClient,
+    ) -> anyhow::Result<String> {
+        let message = self.commit_message(name, version);
+        if !self.pull_request() {
+            for (path, content) in files {
+                github
+                    .put_file(repository, base, path, content, &message)
+                    .await?;
+            }
+            return Ok(format!(
+                "https://github.com/{}/blob/{}/{}",
+                repository, base, files[0].0
+            ));
+        }
+
+        let head_repository = self.config.fork.as_deref().unwrap_or(repository);
+        let branch = format!("{}-{}", name, version);
+        let sha = github.branch_sha(repository, base).await?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "{}",
+                tr!(
+                    "windows_manifest.branch_not_found",
+                    repository = repository,
+                    branch = base
+                )
+            )
+        })?;
+        github.create_branch(head_repository, &branch, &sha).await?;
+
+        for (path, content) in files {
+            github
+                .put_file(head_repository, &branch, path, content, &message)
+                .await?;
+        }
+
+        let head_ref = if head_repository == repository {
+            branch.clone()
+        } else {
+            let owner = head_repository.split('/').next().unwrap_or(head_repository);
+            format!("{}:{}", owner, branch)
+        };
+        github
+            .open_pull_request(
+                repository,
+                &head_ref,
+                base,
+                &message,
+                &format!(
+                    "Release: {} {}\n\n_Automated by package-publisher_",
+                    name, version
+                ),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl RegistryPlugin for WindowsManifestPlugin {
+    fn name(&self) -> &str {
+        self.kind.as_str()
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn detect(&self, _project_path: &str) -> anyhow::Result<bool> {
+        // Selected through `registries.scoop` / `registries.winget`
+        Ok(!self.url_templates().is_empty())
+    }
+
+    async fn validate(&self) -> anyhow::Result<ValidationResult> {
+        let mut errors = Vec::new();
+        let mut metadata = HashMap::new();
+        let section = format!("registries.{}", self.kind.as_str());
+        let mut error = |field: &str, message: String| {
+            errors.push(ValidationError {
+                field: field.to_string(),
+                message,
+                severity: "error".to_string(),
+            })
+        };
+
+        match self.repository() {
+            Ok(repository) => {
+                metadata.insert(
+                    "repository".to_string(),
+                    serde_json::Value::String(repository),
+                );
+            }
+            Err(e) => error("repository", e.to_string()),
+        }
+        let templates = self.url_templates();
+        if templates.is_empty() {
+            error(
+                "url",
+                tr!("windows_manifest.download_url_required", prefix = section),
+            );
+        }
+        for (architecture, _) in &templates {
+            if let Err(e) = scoop_architecture(architecture) {
+                error("urls", e.to_string());
+            }
+        }
+        if let Err(e) = self.token() {
+            error("token", e.to_string());
+        }
+
+        if self.kind == WindowsManifestKind::Winget {
+            for (field, value) in [
+                ("packageIdentifier", &self.config.package_identifier),
+                ("license", &self.config.license),
+                ("description", &self.config.description),
+            ] {
+                if value.is_none() {
+                    error(
+                        field,
+                        tr!(
+                            "windows_manifest.field_required",
+                            prefix = section,
+                            field = field
+                        ),
+                    );
+                }
+            }
+            if let Some((_, url)) = templates.first()
+                && let Err(e) = winget_installer_type(url)
+            {
+                error("url", e.to_string());
+            }
+        }
+
+        match self.package_identity().await {
+            Ok((name, version)) => {
+                metadata.insert("packageName".to_string(), serde_json::Value::String(name));
+                metadata.insert("version".to_string(), serde_json::Value::String(version));
+            }
+            Err(e) => error("name", e.to_string()),
+        }
+
+        Ok(ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+            metadata: Some(metadata),
+        })
+    }
+
+    async fn dry_run(&self) -> anyhow::Result<DryRunResult> {
+        let plan = async {
+            let (name, version) = self.package_identity().await?;
+            let repository = self.repository()?;
+            let paths = match self.kind {
+                WindowsManifestKind::Scoop => vec![self.scoop_manifest_path(&name)],
+                WindowsManifestKind::Winget => {
+                    let identifier =
+                        self.config.package_identifier.as_deref().ok_or_else(|| {
+                            anyhow::anyhow!("{}", tr!("windows_manifest.identifier_required"))
+                        })?;
+                    vec![winget_manifest_dir(&self.config, identifier, &version) + "/"]
+                }
+            };
+            let mut lines = vec![format!(
+                "{} manifest for {} {} -> {} ({})",
+                self.kind.as_str(),
+                name,
+                version,
+                repository,
+                if self.pull_request() {
+                    "pull request"
+                } else {
+                    "commit"
+                }
+            )];
+            lines.extend(paths.iter().map(|path| format!("  {}", path)));
+            for (architecture, template) in self.url_templates() {
+                lines.push(format!(
+                    "  {}: {}",
+                    architecture,
+                    template.replace("{version}", &version)
+                ));
+            }
+            Ok::<_, anyhow::Error>(lines.join("\n"))
+        }
+        .await;
+
+        Ok(match plan {
+            Ok(output) => DryRunResult {
+                success: true,
+                output,
+                estimated_size: None,
+                errors: None,
+            },
+            Err(e) => DryRunResult {
+                success: false,
+                output: e.to_string(),
+                estimated_size: None,
+                errors: Some(vec![ValidationError {
+                    field: "manifest".to_string(),
+                    message: tr!("windows_manifest.generate_failed", error = e),
+                    severity: "error".to_string(),
+                }]),
+            },
+        })
+    }
+
+    async fn publish(&self, _options: Option<PublishOptions>) -> anyhow::Result<PublishResult> {
+        let token = self.token()?;
+        let (name, version) = self.package_identity().await?;
+        let repository = self.repository()?;
+
+        let github = self.github.authenticated(&token);
+
+        let result = async {
+            let base = github.default_branch(&repository).await?;
+            let installers = self.installers(&version).await?;
+            let files = self
+                .manifest_files(&name, &version, &installers, &repository, &base, &github)
+                .await?;
+            self.submit(&name, &version, &files, &repository, &base, &github)
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(url) => {
+                let mut metadata = HashMap::new();
+                if self.pull_request() {
+                    metadata.insert("pendingReview".to_string(), serde_json::Value::Bool(true));
+                    metadata.insert(
+                        "submissionUrl".to_string(),
+                        serde_json::Value::String(url.clone()),
+                    );
+                }
+                Ok(PublishResult {
+                    success: true,
+                    version: Some(version.clone()),
+                    package_url: Some(url),
+                    output: Some(if self.pull_request() {
+                        tr!(
+                            "windows_manifest.pr_created",
+                            package = name,
+                            version = version,
+                            kind = self.kind.as_str(),
+                            repository = repository
+                        )
+                    } else {
+                        tr!(
+                            "windows_manifest.committed",
+                            package = name,
+                            version = version,
+                            kind = self.kind.as_str(),
+                            repository = repository
+                        )
+                    }),
+                    error: None,
+                    metadata: Some(metadata),
+                    error_kind: None,
+                })
+            }
+            Err(e) => Ok(PublishResult {
+                success: false,
+                version: None,
+                package_url: None,
+                output: None,
+                error: Some(e.to_string()),
+                metadata: None,
+                error_kind: None,
+            }),
+        }
+    }
+
+    async fn verify(&self) -> anyhow::Result<VerificationResult> {
+        let (name, version) = self.package_identity().await?;
+        let repository = self.repository()?;
+        let github = match self.token() {
+            Ok(token) => self.github.authenticated(&token),
+            Err(_) => self.github.clone(),
+        };
+        let base = github.default_branch(&repository).await?;
+
+        let (path, published) = match self.kind {
+            WindowsManifestKind::Scoop => {
+                let path = self.scoop_manifest_path(&name);
+                let published = github
+                    .fetch_file(&repository, &path, &base)
+                    .await?
+                    .and_then(|(_, content)| {
+                        serde_json::from_str::<serde_json::Value>(&content).ok()
+                    })
+                    .is_some_and(|manifest| manifest["version"].as_str() == Some(&version));
+                (path, published)
+            }
+            WindowsManifestKind::Winget => {
+                let identifier = self.config.package_identifier.as_deref().unwrap_or(&name);
+                let path = format!(
+                    "{}/{}.yaml",
+                    winget_manifest_dir(&self.config, identifier, &version),
+                    identifier
+                );
+                let published = github
+                    .fetch_file(&repository, &path, &base)
+                    .await?
+                    .is_some();
+                (path, published)
+            }
+        };
+
+        Ok(VerificationResult {
+            verified: published,
+            version: Some(version.clone()),
+            url: Some(format!(
+                "https://github.com/{}/blob/{}/{}",
+                repository, base, path
+            )),
+            error: (!published).then(|| {
+                tr!(
+                    "windows_manifest.not_published",
+                    package = name,
+                    version = version,
+                    repository = repository
+                )
+            }),
+            metadata: None,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use sha2::{Digest, Sha256};
+    use std::path::Path;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned `(status, body)` response per connection, returning
+    /// the requests
+    async fn serve(listener: TcpListener, responses: Vec<(&'static str, String)>) -> Vec<String> {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request).to_string());
+        }
+        requests
+    }
+
+    /// Scoop plugin for a `tool` 1.2.0 project whose GitHub API and release
+    /// download are served at `base`
+    fn scoop_plugin(
+        project: &Path,
+        base: &str,
+        token_env: &str,
+        fork: Option<&str>,
+    ) -> WindowsManifestPlugin {
+        std::fs::write(
+            project.join("package.json"),
+            r#"{"name":"tool","version":"1.2.0"}"#,
+        )
+        .unwrap();
+        unsafe {
+            std::env::set_var(token_env, "ghp_test");
+        }
+        WindowsManifestPlugin::new(project.to_path_buf(), WindowsManifestKind::Scoop)
+            .with_config(WindowsManifestConfig {
+                repository: Some("me/bucket".to_string()),
+                fork: fork.map(String::from),
+                pull_request: Some(fork.is_some()),
+                url: Some(format!("{}/download/tool-{{version}}.zip", base)),
+                token_env: Some(token_env.to_string()),
+                ..Default::default()
+            })
+            .with_github_api_url(base)
+    }
+
+    #[tokio::test]
+    async fn test_publish_opens_pull_request_from_fork() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                ("200 OK", r#"{"default_branch":"master"}"#.to_string()),
+                ("200 OK", "zip".to_string()),
+                // New manifest
+                ("404 Not Found", "{}".to_string()),
+                ("200 OK", r#"{"object":{"sha":"base-sha"}}"#.to_string()),
+                ("201 Created", "{}".to_string()),
+                ("404 Not Found", "{}".to_string()),
+                ("201 Created", "{}".to_string()),
+                (
+                    "201 Created",
+                    r#"{"html_url":"https://github.com/me/bucket/pull/3"}"#.to_string(),
+                ),
+            ],
+        ));
+
+        let temp_dir = TempDir::new().unwrap();
+        let plugin = scoop_plugin(
+            temp_dir.path(),
+            &base,
+            "PACKAGE_PUBLISHER_TEST_SCOOP_PR_TOKEN",
+            Some("bot/bucket"),
+        );
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.package_url.as_deref(),
+            Some("https://github.com/me/bucket/pull/3")
+        );
+
+        let requests = server.await.unwrap();
+        let request_lines: Vec<&str> = requests.iter().map(|r| r.lines().next().unwrap()).collect();
+        assert_eq!(
+            request_lines,
+            [
+                "GET /repos/me/bucket HTTP/1.1",
+                "GET /download/tool-1.2.0.zip HTTP/1.1",
+                "GET /repos/me/bucket/contents/bucket/tool.json?ref=master HTTP/1.1",
+                "GET /repos/me/bucket/git/ref/heads/master HTTP/1.1",
+                "POST /repos/bot/bucket/git/refs HTTP/1.1",
+                "GET /repos/bot/bucket/contents/bucket/tool.json?ref=tool-1.2.0 HTTP/1.1",
+                "PUT /repos/bot/bucket/contents/bucket/tool.json HTTP/1.1",
+                "POST /repos/me/bucket/pulls HTTP/1.1",
+            ]
+        );
+        assert!(requests[0].contains("authorization: Bearer ghp_test"));
+        assert!(requests[4].contains(r#""ref":"refs/heads/tool-1.2.0""#));
+        assert!(requests[4].contains(r#""sha":"base-sha""#));
+        assert!(!requests[6].contains(r#""sha""#));
+        assert!(requests[6].contains(r#""branch":"tool-1.2.0""#));
+        assert!(requests[7].contains(r#""head":"bot:tool-1.2.0""#));
+        assert!(requests[7].contains(r#""base":"master""#));
+
+        // The pushed manifest points at the hashed download
+        let body = &requests[6][requests[6].find("\r\n\r\n").unwrap() + 4..];
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let manifest = BASE64.decode(body["content"].as_str().unwrap()).unwrap();
+        let manifest: serde_json::Value = serde_json::from_slice(&manifest).unwrap();
+        assert_eq!(manifest["version"], "1.2.0");
+        assert_eq!(manifest["hash"], hex::encode(Sha256::digest(b"zip")));
+    }
+
+    #[tokio::test]
+    async fn test_publish_commits_to_default_branch() {
+        let existing = BASE64.encode(r#"{"version":"1.1.0","url":"old","hash":"old"}"#);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(
+            listener,
+            vec![
+                ("200 OK", r#"{"default_branch":"main"}"#.to_string()),
+                ("200 OK", "zip".to_string()),
+                (
+                    "200 OK",
+                    format!(r#"{{"sha":"blob-1","content":"{}"}}"#, existing),
+                ),
+                (
+                    "200 OK",
+                    format!(r#"{{"sha":"blob-1","content":"{}"}}"#, existing),
+                ),
+                ("200 OK", "{}".to_string()),
+            ],
+        ));
+
+        let temp_dir = TempDir::new().unwrap();
+        let plugin = scoop_plugin(
+            temp_dir.path(),
+            &base,
+            "PACKAGE_PUBLISHER_TEST_SCOOP_COMMIT_TOKEN",
+            None,
+        );
+        let result = plugin.publish(None).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.package_url.as_deref(),
+            Some("https://github.com/me/bucket/blob/main/bucket/tool.json")
+        );
+
+        let requests = server.await.unwrap();
+        assert!(requests[4].starts_with("PUT /repos/me/bucket/contents/bucket/tool.json"));
+        // Replacing the file requires its blob sha
+        assert!(requests[4].contains(r#""sha":"blob-1""#));
+        assert!(requests[4].contains(r#""branch":"main""#));
+    }
+
+    fn installer(architecture: &str, sha256: &str) -> WindowsInstaller {
+        WindowsInstaller {
+            architecture: architecture.to_string(),
+            url: format!(
+                "https://github.com/me/tool/releases/download/v1.2.0/tool-{}.zip",
+                architecture
+            ),
+            sha256: sha256.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_update_scoop_manifest() {
+        let existing = r#"{
+    "version": "1.1.0",
+    "description": "My tool",
+    "url": "https://github.com/me/tool/releases/download/v1.1.0/tool-x64.zip",
+    "hash": "old",
+    "bin": "tool.exe",
+    "autoupdate": {
+        "url": "https://github.com/me/tool/releases/download/v$version/tool-x64.zip"
+    }
+}"#;
+        let config = WindowsManifestConfig::default();
+        let updated = update_scoop_manifest(
+            Some(existing),
+            &config,
+            "tool",
+            "1.2.0",
+            &[installer("x64", "abc")],
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            existing
+                .replace("1.1.0", "1.2.0")
+                .replace("\"old\"", "\"abc\"")
+                + "\n"
+        );
+
+        let created = update_scoop_manifest(
+            None,
+            &WindowsManifestConfig {
+                license: Some("MIT".to_string()),
+                ..Default::default()
+            },
+            "tool",
+            "1.2.0",
+            &[installer("x64", "abc"), installer("arm64", "def")],
+        )
+        .unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&created).unwrap();
+        assert_eq!(manifest["version"], "1.2.0");
+        assert_eq!(manifest["description"], "tool");
+        assert_eq!(manifest["architecture"]["64bit"]["hash"], "abc");
+        assert_eq!(manifest["architecture"]["arm64"]["hash"], "def");
+        assert_eq!(manifest["bin"], "tool.exe");
+        assert!(manifest.get("url").is_none());
+        assert!(created.starts_with("{\n    \"version\": \"1.2.0\",\n    \"description\""));
+    }
+
+    #[test]
+    fn test_winget_manifests() {
+        let config = WindowsManifestConfig {
+            package_identifier: Some("Me.Tool".to_string()),
+            license: Some("MIT".to_string()),
+            description: Some("My tool".to_string()),
+            homepage: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let files = winget_manifests(&config, "tool", "1.2.0", &[installer("x64", "abc")]).unwrap();
+        let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "manifests/m/Me/Tool/1.2.0/Me.Tool.yaml",
+                "manifests/m/Me/Tool/1.2.0/Me.Tool.installer.yaml",
+                "manifests/m/Me/Tool/1.2.0/Me.Tool.locale.en-US.yaml",
+            ]
+        );
+
+        let manifest: serde_yaml::Value = serde_yaml::from_str(&files[1].1).unwrap();
+        assert_eq!(manifest["InstallerType"], "zip");
+        assert_eq!(manifest["NestedInstallerType"], "portable");
+        assert_eq!(
+            manifest["NestedInstallerFiles"][0]["RelativeFilePath"],
+            "tool.exe"
+        );
+        assert_eq!(manifest["Installers"][0]["InstallerSha256"], "ABC");
+        assert_eq!(manifest["ManifestType"], "installer");
+
+        let locale: serde_yaml::Value = serde_yaml::from_str(&files[2].1).unwrap();
+        assert_eq!(locale["Publisher"], "Me");
+        assert_eq!(locale["PackageUrl"], "https://example.com");
+        assert!(
+            files[0]
+                .1
+                .contains("winget-manifest.version.1.6.0.schema.json")
+        );
+
+        assert!(
+            winget_manifests(
+                &WindowsManifestConfig::default(),
+                "tool",
+                "1.2.0",
+                &[installer("x64", "abc")]
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Dependency Audit - unified npm audit / cargo audit / pip-audit report
+//!
+//! Runs the audit tool of each ecosystem and normalizes its JSON output into
+//! a single [`AuditReport`]:
+//! - npm (and Expo apps): `npm audit --json`
+//! - crates.io: `cargo audit --json` (from cargo-audit)
+//! - PyPI: `pip-audit -f json`
+//!
+//! Each finding carries a severity and a remediation hint. The report is used
+//! by the `audit` command and by `PackagePublisher` as a publish gate
+//! (`security.audit`).
+
+use crate::plugins::plugin_loader::RegistryType;
+use crate::validation::dependency_checker::IssueSeverity;
+use crate::validation::osv::{cvss3_base_score, severity_from_score};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+/// Audit tool for an ecosystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditBackend {
+    NpmAudit,
+    CargoAudit,
+    PipAudit,
+}
+
+impl AuditBackend {
+    /// Backend for a registry, if its ecosystem has one
+    pub fn for_registry(registry: RegistryType) -> Option<Self> {
+        match registry {
+            RegistryType::Npm | RegistryType::Expo => Some(AuditBackend::NpmAudit),
+            RegistryType::Crates => Some(AuditBackend::CargoAudit),
+            RegistryType::PyPI => Some(AuditBackend::PipAudit),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditBackend::NpmAudit => "npm audit",
+            AuditBackend::CargoAudit => "cargo audit",
+            AuditBackend::PipAudit => "pip-audit",
+        }
+    }
+
+    fn install_hint(&self) -> &'static str {
+        match self {
+            AuditBackend::NpmAudit => "npm audit needs package-lock.json (run npm install)",
+            AuditBackend::CargoAudit => "install it with: cargo install cargo-audit",
+            AuditBackend::PipAudit => "install it with: pip install pip-audit",
+        }
+    }
+}
+
+/// Vulnerability or advisory reported by an audit tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFinding {
+    pub backend: AuditBackend,
+    pub package: String,
+    /// Installed version, when the tool reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Affected version range, when the tool reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affected_range: Option<String>,
+    /// Advisory identifier (GHSA-..., RUSTSEC-..., PYSEC-...)
+    pub advisory_id: String,
+    pub title: String,
+    pub severity: IssueSeverity,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_versions: Vec<String>,
+    /// What to do about it
+    pub remediation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Normalized result of one or more audit backends
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    /// Backends that ran
+    pub backends: Vec<AuditBackend>,
+    pub findings: Vec<AuditFinding>,
+    /// Backends that could not run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl AuditReport {
+    /// Number of findings with the given severity
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    /// Findings at or above `threshold`, except ignored advisory IDs
+    pub fn blocking<'a>(
+        &'a self,
+        threshold: IssueSeverity,
+        ignore: &'a [String],
+    ) -> impl Iterator<Item = &'a AuditFinding> + 'a {
+        self.findings
+            .iter()
+            .filter(move |f| f.severity.meets(threshold) && !ignore.contains(&f.advisory_id))
+    }
+
+    /// One-line severity summary ("1 critical, 2 high, 0 medium, 3 low")
+    pub fn summary(&self) -> String {
+        format!(
+            "{} critical, {} high, {} medium, {} low",
+            self.count(IssueSeverity::Critical),
+            self.count(IssueSeverity::High),
+            self.count(IssueSeverity::Medium),
+            self.count(IssueSeverity::Low)
+        )
+    }
+
+    fn merge(&mut self, other: AuditReport) {
+        self.backends.extend(other.backends);
+        self.findings.extend(other.findings);
+        self.errors.extend(other.errors);
+    }
+}
+
+/// Parse `npm audit --json` (npm 7+ report format)
+pub fn parse_npm_audit(output: &str) -> anyhow::Result<Vec<AuditFinding>> {
+    let report: serde_json::Value = serde_json::from_str(output)?;
+    let mut findings = Vec::new();
+
+    let Some(vulnerabilities) = report["vulnerabilities"].as_object() else {
+        return Ok(findings);
+    };
+    for (package, entry) in vulnerabilities {
+        let remediation = match &entry["fixAvailable"] {
+            serde_json::Value::Bool(true) => "Run npm audit fix".to_string(),
+            serde_json::Value::Object(fix) => {
+                let target = format!(
+                    "{}@{}",
+                    fix.get("name").and_then(|n| n.as_str()).unwrap_or(package),
+                    fix.get("version")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("latest")
+                );
+                if fix.get("isSemVerMajor").and_then(|m| m.as_bool()) == Some(true) {
+                    format!(
+                        "Run npm audit fix --force (installs {}, a breaking change)",
+                        target
+                    )
+                } else {
+                    format!("Update to {}", target)
+                }
+            }
+            _ => "No fix available; consider replacing the dependency".to_string(),
+        };
+
+        // "via" lists advisories (objects) or the dependencies that pull one in (strings);
+        // only the advisories themselves become findings
+        for via in entry["via"].as_array().into_iter().flatten() {
+            let Some(advisory) = via.as_object() else {
+                continue;
+            };
+            let url = advisory
+                .get("url")
+                .and_then(|u| u.as_str())
+                .map(str::to_string);
+            let advisory_id = url
+                .as_deref()
+                .and_then(|u| u.rsplit('/').next())
+                .map(str::to_string)
+                .or_else(|| advisory.get("source").map(|s| s.to_string()))
+                .unwrap_or_default();
+            findings.push(AuditFinding {
+                backend: AuditBackend::NpmAudit,
+                package: package.clone(),
+                version: None,
+                affected_range: advisory
+                    .get("range")
+                    .and_then(|r| r.as_str())
+                    .map(str::to_string),
+                advisory_id,
+                title: advisory
+                    .get("title")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                severity: advisory
+                    .get("severity")
+                    .and_then(|s| s.as_str())
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(IssueSeverity::Medium),
+                fixed_versions: Vec::new(),
+                remediation: remediation.clone(),
+                url,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Parse `cargo audit --json`
+pub fn parse_cargo_audit(output: &str) -> anyhow::Result<Vec<AuditFinding>> {
+    let report: serde_json::Value = serde_json::from_str(output)?;
+    let mut findings = Vec::new();
+
+    let mut entries: Vec<(&serde_json::Value, bool)> = report["vulnerabilities"]["list"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|entry| (entry, false))
+        .collect();
+    // Informational warnings (unmaintained, unsound, yanked)
+    if let Some(warnings) = report["warnings"].as_object() {
+        entries.extend(
+            warnings
+                .values()
+                .filter_map(|list| list.as_array())
+                .flatten()
+                .filter(|entry| entry["advisory"].is_object())
+                .map(|entry| (entry, true)),
+        );
+    }
+
+    for (entry, informational) in entries {
+        let advisory = &entry["advisory"];
+        let patched: Vec<String> = entry["versions"]["patched"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+        let severity = if informational {
+            IssueSeverity::Low
+        } else {
+            advisory["cvss"]
+                .as_str()
+                .and_then(cvss3_base_score)
+                .map(severity_from_score)
+                .unwrap_or(IssueSeverity::Medium)
+        };
+        let remediation = if patched.is_empty() {
+            "No patched version; consider an alternative crate".to_string()
+        } else {
+            format!("Upgrade to {} (cargo update)", patched.join(" or "))
+        };
+
+        findings.push(AuditFinding {
+            backend: AuditBackend::CargoAudit,
+            package: entry["package"]["name"]
+                .as_str()
+                .or(advisory["package"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            version: entry["package"]["version"].as_str().map(str::to_string),
+            affected_range: None,
+            advisory_id: advisory["id"].as_str().unwrap_or_default().to_string(),
+            title: advisory["title"].as_str().unwrap_or_default().to_string(),
+            severity,
+            fixed_versions: patched,
+            remediation,
+            url: advisory["url"].as_str().map(str::to_string).or_else(|| {
+                advisory["id"]
+                    .as_str()
+                    .map(|id| format!("https://rustsec.org/advisories/{}", id))
+            }),
+        });
+    }
+
+    Ok(findings)
+}
+
+/// Parse `pip-audit -f json`
+///
+/// pip-audit reports no severity, so findings are rated medium.
+pub fn parse_pip_audit(output: &str) -> anyhow::Result<Vec<AuditFinding>> {
+    let report: serde_json::Value = serde_json::from_str(output)?;
+    // Older pip-audit versions print the dependency list at the top level
+    let dependencies = report
+        .get("dependencies")
+        .unwrap_or(&report)
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut findings = Vec::new();
+    for dependency in &dependencies {
+        let package = dependency["name"].as_str().unwrap_or_default();
+        for vuln in dependency["vulns"].as_array().into_iter().flatten() {
+            let id = vuln["id"].as_str().unwrap_or_default().to_string();
+            let fixed: Vec<String> = vuln["fix_versions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            let remediation = match fixed.first() {
+                Some(version) => format!("Upgrade to {}>={}", package, version),
+                None => "No fixed version available".to_string(),
+            };
+            let title = vuln["description"]
+                .as_str()
+                .and_then(|d| d.lines().next())
+                .unwrap_or_default()
+                .to_string();
+
+            findings.push(AuditFinding {
+                backend: AuditBackend::PipAudit,
+                package: package.to_string(),
+                version: dependency["version"].as_str().map(str::to_string),
+                affected_range: None,
+                url: Some(format!("https://osv.dev/vulnerability/{}", id)),
+                advisory_id: id,
+                title,
+                severity: IssueSeverity::Medium,
+                fixed_versions: fixed,
+                remediation,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Runs audit backends for a project
+pub struct DependencyAuditor {
+    project_path: PathBuf,
+}
+
+impl DependencyAuditor {
+    /// Create an auditor for the project directory
+    pub fn new(project_path: impl AsRef<Path>) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Audit the ecosystems of the given registries (each backend runs once)
+    ///
+    /// Backends that cannot run are recorded in [`AuditReport::errors`].
+    pub async fn audit_registries(&self, registries: &[RegistryType]) -> AuditReport {
+        let mut backends: Vec<AuditBackend> = Vec::new();
+        for backend in registries
+            .iter()
+            .filter_map(|r| AuditBackend::for_registry(*r))
+        {
+            if !backends.contains(&backend) {
+                backends.push(backend);
+            }
+        }
+
+        let mut report = AuditReport::default();
+        for backend in backends {
+            match self.audit(backend).await {
+                Ok(backend_report) => report.merge(backend_report),
+                Err(e) => report.errors.push(format!("{}: {}", backend.as_str(), e)),
+            }
+        }
+        report
+    }
+
+    /// Run one backend
+    pub async fn audit(&self, backend: AuditBackend) -> anyhow::Result<AuditReport> {
+        let requirements = self.project_path.join("requirements.txt");
+        let args: Vec<String> = match backend {
+            AuditBackend::NpmAudit => vec!["npm", "audit", "--json"],
+            AuditBackend::CargoAudit => vec!["cargo", "audit", "--json"],
+            AuditBackend::PipAudit if fs::metadata(&requirements).await.is_ok() => {
+                vec![
+                    "pip-audit",
+                    "-f",
+                    "json",
+                    "--progress-spinner",
+                    "off",
+                    "-r",
+                    "requirements.txt",
+                ]
+            }
+            AuditBackend::PipAudit => {
+                vec!["pip-audit", "-f", "json", "--progress-spinner", "off", "."]
+            }
+        }
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+        let output = Command::new(&args[0])
+            .args(&args[1..])
+            .current_dir(&self.project_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("{} ({})", e, backend.install_hint()))?;
+
+        // The tools exit non-zero when they find vulnerabilities; the JSON
+        // report decides whether the run itself failed
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let parsed = match backend {
+            AuditBackend::NpmAudit => parse_npm_audit(&stdout),
+            AuditBackend::CargoAudit => parse_cargo_audit(&stdout),
+            AuditBackend::PipAudit => parse_pip_audit(&stdout),
+        };
+
+        match parsed {
+            Ok(findings) => Ok(AuditReport {
+                backends: vec![backend],
+                findings,
+                errors: Vec::new(),
+            }),
+            Err(_) => anyhow::bail!(
+                "{} ({})",
+                String::from_utf8_lossy(&output.stderr).trim(),
+                backend.install_hint()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_npm_audit() {
+        let output = r#"{
+            "auditReportVersion": 2,
+            "vulnerabilities": {
+                "minimist": {
+                    "name": "minimist",
+                    "severity": "critical",
+                    "via": [{
+                        "source": 1179,
+                        "name": "minimist",
+                        "title": "Prototype Pollution in minimist",
+                        "url": "https://github.com/advisories/GHSA-xvch-5gv4-984h",
+                        "severity": "critical",
+                        "range": "<0.2.4"
+                    }],
+                    "fixAvailable": true
+                },
+                "mkdirp": {
+                    "name": "mkdirp",
+                    "severity": "critical",
+                    "via": ["minimist"],
+                    "fixAvailable": {"name": "mkdirp", "version": "1.0.4", "isSemVerMajor": true}
+                }
+            }
+        }"#;
+        let findings = parse_npm_audit(output).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].advisory_id, "GHSA-xvch-5gv4-984h");
+        assert_eq!(findings[0].severity, IssueSeverity::Critical);
+        assert_eq!(findings[0].remediation, "Run npm audit fix");
+    }
+
+    #[test]
+    fn test_parse_cargo_audit() {
+        let output = r#"{
+            "vulnerabilities": {"found": true, "count": 1, "list": [{
+                "advisory": {
+                    "id": "RUSTSEC-2020-0071",
+                    "package": "time",
+                    "title": "Potential segfault in the time crate",
+                    "cvss": "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H",
+                    "url": null
+                },
+                "versions": {"patched": [">=0.2.23"], "unaffected": ["=0.2.0"]},
+                "package": {"name": "time", "version": "0.1.45"}
+            }]},
+            "warnings": {"unmaintained": [{
+                "kind": "unmaintained",
+                "package": {"name": "ansi_term", "version": "0.12.1"},
+                "advisory": {"id": "RUSTSEC-2021-0139", "title": "ansi_term is Unmaintained"},
+                "versions": {"patched": []}
+            }]}
+        }"#;
+        let findings = parse_cargo_audit(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, IssueSeverity::Medium);
+        assert_eq!(findings[0].fixed_versions, vec![">=0.2.23"]);
+        assert_eq!(
+            findings[0].url.as_deref(),
+            Some("https://rustsec.org/advisories/RUSTSEC-2020-0071")
+        );
+        assert_eq!(findings[1].severity, IssueSeverity::Low);
+    }
+
+    #[test]
+    fn test_parse_pip_audit_and_blocking() {
+        let output = r#"{"dependencies": [
+            {"name": "requests", "version": "2.19.0", "vulns": [
+                {"id": "PYSEC-2018-28", "fix_versions": ["2.20.0"], "description": "Requests leaks credentials."}
+            ]},
+            {"name": "six", "version": "1.16.0", "vulns": []}
+        ]}"#;
+        let findings = parse_pip_audit(output).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].remediation, "Upgrade to requests>=2.20.0");
+
+        let report = AuditReport {
+            backends: vec![AuditBackend::PipAudit],
+            findings,
+            errors: Vec::new(),
+        };
+        assert_eq!(report.blocking(IssueSeverity::Medium, &[]).count(), 1);
+        assert_eq!(report.blocking(IssueSeverity::High, &[]).count(), 0);
+        assert_eq!(
+            report
+                .blocking(IssueSeverity::Low, &["PYSEC-2018-28".to_string()])
+                .count(),
+            0
+        );
+    }
+}
@@ -5,8 +5,12 @@
 //! - **Whitelist-based validation**: Only pre-approved commands can execute
 //! - **Injection prevention**: Uses `std::process::Command` which prevents shell injection
 //! - **Argument sanitization**: Arguments passed as Vec, never interpolated into shell strings
-//! - **Working directory validation**: Validates existence before execution
-//! - **Timeout control**: Prevents long-running or hanging processes
+//! - **Working directory validation**: Validates existence before execution,
+//!   optionally confined to a jail directory (e.g. the project)
+//! - **Environment scrubbing**: With an [`EnvSandbox`], commands only see an
+//!   allowlist of variables, so build scripts cannot read unrelated tokens
+//! - **Timeout control**: Prevents long-running or hanging processes; on Unix
+//!   the whole process group is killed, including spawned grandchildren
 //!
 //! # Example
 //!
@@ -21,9 +25,14 @@
 //! println!("{}", String::from_utf8_lossy(&output.stdout));
 //! ```
 
+use std::ffi::OsString;
+use std::io::Read;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
-use std::time::Duration;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Allowed commands whitelist for security.
@@ -32,6 +41,125 @@ use thiserror::Error;
 /// This prevents arbitrary command execution and potential security vulnerabilities.
 const ALLOWED_COMMANDS: &[&str] = &["npm", "cargo", "python", "pip", "twine", "brew", "git"];
 
+/// Environment variables every sandboxed command keeps: toolchain lookup,
+/// locale, temporary directories, proxies and CA certificates
+pub const BASE_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "TERM",
+    "TZ",
+    "LANG",
+    "LC_*",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "CI",
+    // Windows
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+];
+
+/// Variables of CI identity: the OIDC token request of GitHub Actions and
+/// GitLab, and the build metadata `npm publish --provenance` and trusted
+/// publishing read
+pub const CI_IDENTITY_ENV: &[&str] = &[
+    "ACTIONS_ID_TOKEN_REQUEST_URL",
+    "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+    "GITHUB_*",
+    "RUNNER_*",
+    "GITLAB_CI",
+    "CI_*",
+    "SIGSTORE_ID_TOKEN",
+    "NPM_ID_TOKEN",
+];
+
+/// Minimal environment for commands
+///
+/// Commands run with [`BASE_ENV_ALLOWLIST`] plus the allowed names (`NAME`,
+/// or `PREFIX_*` for every variable starting with `PREFIX_`); all other
+/// variables of the current process are dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use package_publisher::security::command_executor::EnvSandbox;
+///
+/// let sandbox = EnvSandbox::new(&["NPM_TOKEN", "npm_config_*"]);
+/// assert!(sandbox.allows("PATH"));
+/// assert!(sandbox.allows("npm_config_registry"));
+/// assert!(!sandbox.allows("AWS_SECRET_ACCESS_KEY"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvSandbox {
+    allowed: Vec<String>,
+}
+
+impl EnvSandbox {
+    /// Sandbox passing [`BASE_ENV_ALLOWLIST`] and `allowed` through
+    pub fn new<S: AsRef<str>>(allowed: &[S]) -> Self {
+        Self {
+            allowed: Vec::new(),
+        }
+        .with_allowed(allowed)
+    }
+
+    /// Also pass `names` through
+    pub fn with_allowed<S: AsRef<str>>(mut self, names: &[S]) -> Self {
+        for name in names {
+            let name = name.as_ref();
+            if !self.allowed.iter().any(|a| a == name) {
+                self.allowed.push(name.to_string());
+            }
+        }
+        self
+    }
+
+    /// Whether the variable `name` is passed to commands
+    pub fn allows(&self, name: &str) -> bool {
+        BASE_ENV_ALLOWLIST
+            .iter()
+            .copied()
+            .chain(self.allowed.iter().map(String::as_str))
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                // Windows variable names are case-insensitive
+                None if cfg!(windows) => name.eq_ignore_ascii_case(pattern),
+                None => name == pattern,
+            })
+    }
+
+    /// Allowed variables of the current process
+    pub fn vars(&self) -> Vec<(OsString, OsString)> {
+        std::env::vars_os()
+            .filter(|(name, _)| name.to_str().is_some_and(|name| self.allows(name)))
+            .collect()
+    }
+
+    /// Replace the environment of `command` with the allowed variables
+    pub fn apply(&self, command: &mut Command) {
+        command.env_clear().envs(self.vars());
+    }
+}
+
 /// Errors that can occur during command execution
 #[derive(Error, Debug)]
 pub enum CommandError {
@@ -50,6 +178,150 @@ pub enum CommandError {
     /// Command exceeded the timeout duration
     #[error("Command timeout after {0:?}")]
     Timeout(Duration),
+
+    /// Working directory is outside the jail directory
+    #[error("Working directory {dir} is outside of {jail}")]
+    OutsideJail { dir: PathBuf, jail: PathBuf },
+}
+
+/// Default timeout of registry commands (`npm publish`, `cargo publish`,
+/// `twine upload`, builds); `publish.commandTimeout` overrides it
+pub const DEFAULT_REGISTRY_COMMAND_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Run an async command to completion, capturing its output
+///
+/// The command runs in its own process group on Unix; when `timeout` passes
+/// the group is killed, so spawned grandchildren (e.g. build scripts) do not
+/// outlive it. Stdin is closed, so commands cannot wait for input.
+pub async fn output_with_timeout(
+    command: &mut tokio::process::Command,
+    timeout: Duration,
+) -> Result<Output, CommandError> {
+    run_with_timeout(command, None, timeout).await
+}
+
+/// [`output_with_timeout`] with `input` written to the command's stdin
+pub async fn output_with_stdin(
+    command: &mut tokio::process::Command,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<Output, CommandError> {
+    run_with_timeout(command, Some(input), timeout).await
+}
+
+async fn run_with_timeout(
+    command: &mut tokio::process::Command,
+    input: Option<&[u8]>,
+    timeout: Duration,
+) -> Result<Output, CommandError> {
+    use tokio::io::AsyncWriteExt;
+
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    command.process_group(0);
+    let mut child = command
+        .spawn()
+        .map_err(|e| CommandError::ExecutionFailed(e.to_string()))?;
+    let pid = child.id();
+
+    let run = async {
+        if let Some(input) = input
+            && let Some(mut stdin) = child.stdin.take()
+        {
+            // Closing stdin afterwards lets the command see the end of input
+            stdin.write_all(input).await?;
+        }
+        child.wait_with_output().await
+    };
+    match tokio::time::timeout(timeout, run).await {
+        Ok(output) => output.map_err(|e| CommandError::ExecutionFailed(e.to_string())),
+        Err(_) => {
+            // Dropping the output future kills the child itself
+            #[cfg(unix)]
+            if let Some(pgid) = pid.and_then(|pid| i32::try_from(pid).ok()) {
+                // SAFETY: kill(2) has no memory safety requirements; the
+                // negative pid addresses the group the child leads
+                unsafe {
+                    libc::kill(-pgid, libc::SIGKILL);
+                }
+            }
+            #[cfg(not(unix))]
+            let _ = pid;
+            Err(CommandError::Timeout(timeout))
+        }
+    }
+}
+
+/// Sandbox and time limit of the registry tooling a plugin runs
+///
+/// Plugins create their commands with [`command`](Self::command), so the
+/// sandboxed environment is in place before plugin-specific variables (e.g.
+/// a token) are set, and run them with [`output`](Self::output).
+#[derive(Debug, Clone)]
+pub struct CommandLimits {
+    sandbox: Option<EnvSandbox>,
+    timeout: Duration,
+}
+
+impl Default for CommandLimits {
+    fn default() -> Self {
+        Self::new(None, DEFAULT_REGISTRY_COMMAND_TIMEOUT)
+    }
+}
+
+impl CommandLimits {
+    /// Limits with `sandbox` (`None` keeps the caller's environment) and
+    /// `timeout`
+    pub fn new(sandbox: Option<EnvSandbox>, timeout: Duration) -> Self {
+        Self { sandbox, timeout }
+    }
+
+    /// Also pass `names` through the sandbox
+    pub fn allowing<S: AsRef<str>>(mut self, names: &[S]) -> Self {
+        self.sandbox = self.sandbox.map(|sandbox| sandbox.with_allowed(names));
+        self
+    }
+
+    /// Whether the variable `name` reaches commands
+    pub fn allows(&self, name: &str) -> bool {
+        self.sandbox
+            .as_ref()
+            .is_none_or(|sandbox| sandbox.allows(name))
+    }
+
+    /// Command running `program` with the sandboxed environment
+    pub fn command(&self, program: impl AsRef<std::ffi::OsStr>) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(program);
+        if let Some(sandbox) = &self.sandbox {
+            sandbox.apply(command.as_std_mut());
+        }
+        command
+    }
+
+    /// Run `command` with the timeout (see [`output_with_timeout`])
+    pub async fn output(
+        &self,
+        command: &mut tokio::process::Command,
+    ) -> Result<Output, CommandError> {
+        output_with_timeout(command, self.timeout).await
+    }
+
+    /// Run `command` with `input` on stdin and the timeout
+    pub async fn output_with_stdin(
+        &self,
+        command: &mut tokio::process::Command,
+        input: &[u8],
+    ) -> Result<Output, CommandError> {
+        output_with_stdin(command, input, self.timeout).await
+    }
 }
 
 /// Safe command executor with security controls
@@ -59,12 +331,17 @@ pub enum CommandError {
 /// - Working directory control
 /// - Timeout management
 /// - Injection prevention through `std::process::Command`
+/// - Optional environment scrubbing
 #[derive(Debug)]
 pub struct SafeCommandExecutor {
     /// Working directory where commands will be executed
     working_dir: PathBuf,
     /// Optional timeout for command execution
     timeout: Option<Duration>,
+    /// Additional commands allowed on top of `ALLOWED_COMMANDS` (e.g. from hook config)
+    extra_allowed: Vec<String>,
+    /// Environment restrictions (the full environment is inherited when unset)
+    env_sandbox: Option<EnvSandbox>,
 }
 
 impl SafeCommandExecutor {
@@ -95,9 +372,50 @@ impl SafeCommandExecutor {
         Ok(Self {
             working_dir,
             timeout: None,
+            extra_allowed: Vec::new(),
+            env_sandbox: None,
         })
     }
 
+    /// Check that the working directory is inside `jail`.
+    ///
+    /// Both paths are resolved first, so `..` components and symlinks cannot
+    /// escape the jail.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandError::OutsideJail` if the working directory is not
+    /// inside `jail`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use package_publisher::SafeCommandExecutor;
+    ///
+    /// let executor = SafeCommandExecutor::new("/tmp").unwrap();
+    /// assert!(executor.check_jail("/tmp").is_ok());
+    /// assert!(executor.check_jail("/tmp/nested/project").is_err());
+    /// ```
+    pub fn check_jail<P: AsRef<Path>>(&self, jail: P) -> Result<(), CommandError> {
+        let jail = jail.as_ref();
+        let outside = || CommandError::OutsideJail {
+            dir: self.working_dir.clone(),
+            jail: jail.to_path_buf(),
+        };
+        let (Ok(dir), Ok(root)) = (self.working_dir.canonicalize(), jail.canonicalize()) else {
+            return Err(outside());
+        };
+        if !dir.starts_with(&root) {
+            return Err(outside());
+        }
+        Ok(())
+    }
+
+    /// Run commands with the minimal environment of `sandbox`.
+    pub fn set_env_sandbox(&mut self, sandbox: EnvSandbox) {
+        self.env_sandbox = Some(sandbox);
+    }
+
     /// Set command execution timeout.
     ///
     /// Commands exceeding this duration will be terminated.
@@ -119,6 +437,34 @@ impl SafeCommandExecutor {
         self.timeout = Some(timeout);
     }
 
+    /// Allow additional commands for this executor.
+    ///
+    /// Used for explicitly configured commands (e.g. `allowedCommands` of a hook).
+    /// The built-in whitelist still applies to every other command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use package_publisher::SafeCommandExecutor;
+    ///
+    /// let mut executor = SafeCommandExecutor::new("/tmp").unwrap();
+    /// executor.allow_commands(&["make".to_string()]);
+    /// assert!(executor.is_allowed("make"));
+    /// assert!(!executor.is_allowed("rm"));
+    /// ```
+    pub fn allow_commands(&mut self, commands: &[String]) {
+        for command in commands {
+            if !self.extra_allowed.contains(command) {
+                self.extra_allowed.push(command.clone());
+            }
+        }
+    }
+
+    /// Check whether a command may be executed by this executor.
+    pub fn is_allowed(&self, command: &str) -> bool {
+        ALLOWED_COMMANDS.contains(&command) || self.extra_allowed.iter().any(|c| c == command)
+    }
+
     /// Execute a command with whitelist validation and argument sanitization.
     ///
     /// # Security Features
@@ -136,6 +482,7 @@ impl SafeCommandExecutor {
     ///
     /// - `CommandError::CommandNotAllowed` - Command not in whitelist
     /// - `CommandError::ExecutionFailed` - Binary not found or execution error
+    /// - `CommandError::Timeout` - Command exceeded the configured timeout (process is killed)
     ///
     /// # Example
     ///
@@ -147,8 +494,21 @@ impl SafeCommandExecutor {
     /// assert_eq!(output.status.code(), Some(0));
     /// ```
     pub fn execute(&self, command: &str, args: &[&str]) -> Result<Output, CommandError> {
+        self.execute_with_timeout(command, args, self.timeout)
+    }
+
+    /// Execute a command with its own timeout instead of the executor's.
+    ///
+    /// On timeout the command is killed together with the processes it
+    /// spawned (its process group on Unix).
+    pub fn execute_with_timeout(
+        &self,
+        command: &str,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<Output, CommandError> {
         // Whitelist validation: Only pre-approved commands
-        if !ALLOWED_COMMANDS.contains(&command) {
+        if !self.is_allowed(command) {
             return Err(CommandError::CommandNotAllowed(command.to_string()));
         }
 
@@ -166,17 +526,87 @@ impl SafeCommandExecutor {
 
         // Execute using std::process::Command (type-safe, prevents injection)
         // Arguments are passed as Vec, never interpolated into shell strings
-        let output = Command::new(&command_name)
+        let mut process = Command::new(&command_name);
+        process
             .args(args)
             .current_dir(&self.working_dir)
-            .output()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(sandbox) = &self.env_sandbox {
+            sandbox.apply(&mut process);
+        }
+        // Own process group, so a timeout also kills spawned grandchildren
+        #[cfg(unix)]
+        if timeout.is_some() {
+            process.process_group(0);
+        }
+        let mut child = process
+            .spawn()
             .map_err(|e| CommandError::ExecutionFailed(e.to_string()))?;
 
-        Ok(output)
+        let Some(timeout) = timeout else {
+            return child
+                .wait_with_output()
+                .map_err(|e| CommandError::ExecutionFailed(e.to_string()));
+        };
+
+        // Drain pipes on separate threads so a chatty process cannot block on a full pipe
+        let stdout_reader = child.stdout.take().map(Self::spawn_reader);
+        let stderr_reader = child.stderr.take().map(Self::spawn_reader);
+
+        let started = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if started.elapsed() >= timeout => {
+                    Self::kill_process_group(&mut child);
+                    let _ = child.wait();
+                    return Err(CommandError::Timeout(timeout));
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                Err(e) => return Err(CommandError::ExecutionFailed(e.to_string())),
+            }
+        };
+
+        let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+            reader
+                .and_then(|handle| handle.join().ok())
+                .unwrap_or_default()
+        };
+
+        Ok(Output {
+            status,
+            stdout: collect(stdout_reader),
+            stderr: collect(stderr_reader),
+        })
+    }
+
+    /// Kill a child started in its own process group, with all its descendants
+    fn kill_process_group(child: &mut std::process::Child) {
+        #[cfg(unix)]
+        if let Ok(pgid) = i32::try_from(child.id()) {
+            // SAFETY: kill(2) has no memory safety requirements; the negative
+            // pid addresses the group the child leads
+            unsafe {
+                libc::kill(-pgid, libc::SIGKILL);
+            }
+        }
+        let _ = child.kill();
+    }
+
+    /// Read a child pipe to the end on a background thread
+    fn spawn_reader<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod tests {
     use super::*;
 
@@ -188,6 +618,59 @@ mod tests {
             .to_string()
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_async_timeout_kills_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("survived");
+        let script = format!("(sleep 1; touch {}) & wait", marker.display());
+        let mut command = tokio::process::Command::new("sh");
+        command.args(["-c", &script]);
+        let started = Instant::now();
+        let result = output_with_timeout(&mut command, Duration::from_millis(200)).await;
+        assert!(matches!(result, Err(CommandError::Timeout(_))));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!marker.exists(), "grandchild outlived the timeout");
+
+        let mut command = tokio::process::Command::new("sh");
+        command.args(["-c", "echo done"]);
+        let output = output_with_timeout(&mut command, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "done");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_limits() {
+        unsafe {
+            std::env::set_var("PACKAGE_PUBLISHER_TEST_LIMITS_SECRET", "secret");
+        }
+        let limits =
+            CommandLimits::new(Some(EnvSandbox::new::<&str>(&[])), Duration::from_secs(10))
+                .allowing(&["PACKAGE_PUBLISHER_TEST_LIMITS_KEPT"]);
+        assert!(limits.allows("PACKAGE_PUBLISHER_TEST_LIMITS_KEPT"));
+        assert!(!limits.allows("PACKAGE_PUBLISHER_TEST_LIMITS_SECRET"));
+
+        let mut command = limits.command("sh");
+        command
+            .args([
+                "-c",
+                "read line; echo \"$line:$PACKAGE_PUBLISHER_TEST_LIMITS_SECRET:$TOKEN\"",
+            ])
+            .env("TOKEN", "set-by-plugin");
+        let output = limits
+            .output_with_stdin(&mut command, b"input\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "input::set-by-plugin"
+        );
+    }
+
     #[test]
     fn test_allowed_command_npm() {
         let executor = SafeCommandExecutor::new(&get_test_dir()).unwrap();
@@ -257,6 +740,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extra_allowed_command_timeout() {
+        let mut executor = SafeCommandExecutor::new(get_test_dir()).unwrap();
+        executor.allow_commands(&["sleep".to_string()]);
+        executor.set_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let result = executor.execute("sleep", &["5"]);
+        assert!(
+            matches!(result, Err(CommandError::Timeout(_))),
+            "Allowed long-running command should time out"
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_timeout_kills_process_group() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let marker = temp_dir.path().join("survived");
+        let mut executor = SafeCommandExecutor::new(temp_dir.path()).unwrap();
+        executor.allow_commands(&["sh".to_string()]);
+
+        // The grandchild would create the marker after the timeout
+        let script = format!("(sleep 1; touch {}) & sleep 5", marker.display());
+        let result =
+            executor.execute_with_timeout("sh", &["-c", &script], Some(Duration::from_millis(200)));
+        assert!(matches!(result, Err(CommandError::Timeout(_))));
+
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists(), "grandchild outlived the timeout");
+    }
+
+    #[test]
+    fn test_env_sandbox_drops_unlisted_variables() {
+        let mut executor = SafeCommandExecutor::new(get_test_dir()).unwrap();
+        executor.allow_commands(&["env".to_string()]);
+        executor.set_env_sandbox(EnvSandbox::new(&["CARGO_*"]));
+
+        let output = executor.execute("env", &[]).unwrap();
+        let names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once('=').map(|(name, _)| name.to_string()))
+            .collect();
+        assert!(names.iter().any(|n| n == "PATH"));
+        assert!(names.iter().any(|n| n.starts_with("CARGO_")));
+        let sandbox = EnvSandbox::new(&["CARGO_*"]);
+        assert!(names.iter().all(|n| sandbox.allows(n)), "{:?}", names);
+    }
+
+    #[test]
+    fn test_working_directory_jail() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("packages/app");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let inside = SafeCommandExecutor::new(&nested).unwrap();
+        assert!(inside.check_jail(temp_dir.path()).is_ok());
+
+        let escaped = SafeCommandExecutor::new(nested.join("../../..")).unwrap();
+        assert!(matches!(
+            escaped.check_jail(temp_dir.path()),
+            Err(CommandError::OutsideJail { .. })
+        ));
+    }
+
+    #[test]
+    fn test_output_capture_with_timeout() {
+        let mut executor = SafeCommandExecutor::new(get_test_dir()).unwrap();
+        executor.set_timeout(Duration::from_secs(30));
+        let output = executor.execute("cargo", &["--version"]).unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("cargo"));
+    }
+
     #[test]
     fn test_output_capture() {
         let executor = SafeCommandExecutor::new(&get_test_dir()).unwrap();
@@ -0,0 +1,797 @@
+//! Credential Preflight - verifies registry credentials before publishing
+//!
+//! Each detected registry is checked for a usable credential so that a
+//! publish (or a batch of publishes) does not fail half-way on authentication:
+//! - npm: `npm whoami` against the configured registry
+//! - crates.io: `CARGO_REGISTRY_TOKEN` / `~/.cargo/credentials.toml`, checked against the API
+//!   (or trusted publishing when the CI job can request an OIDC token)
+//! - PyPI: API token from `TWINE_PASSWORD` / `PYPI_TOKEN` / `~/.pypirc` (format only)
+//! - Homebrew: push access to the tap (`git push --dry-run`, or the GitHub API for `registries.homebrew.tap`)
+//! - Token-based registries (GitHub Packages, UPM, Julia, Bazel, CPAN, Hackage, Expo,
+//!   Artifactory, Nexus, Cloudsmith, Gemfury): token presence
+//!
+//! Tokens are also run through [`CredentialValidator`] so that placeholder
+//! values (`your_token_here`, `xxx...`) are reported as invalid rather than
+//! failing later at the registry.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::plugins::PluginLoader;
+//! use package_publisher::security::{CredentialPreflight, CredentialValidator};
+//! use std::path::Path;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let detected = PluginLoader::new().detect_plugins(Path::new(".")).await?;
+//! let validator = CredentialValidator::new();
+//! let preflight = CredentialPreflight::new(".", &validator);
+//! for check in preflight.check_all(&detected).await {
+//!     println!("{}: {:?} {}", check.registry, check.status, check.detail);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::{CratesAuthMode, PublishConfig};
+use crate::core::github::GitHubClient;
+use crate::core::http::http_client;
+use crate::plugins::cargo_registry::CargoRegistry;
+use crate::plugins::plugin_loader::{DetectedPlugin, RegistryType};
+use crate::plugins::trusted_publishing::OidcEnvironment;
+use crate::security::credential_validator::CredentialValidator;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+const CRATES_IO_ME_URL: &str = "https://crates.io/api/v1/me";
+
+/// Outcome of a credential check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialStatus {
+    /// Credential found and accepted by the registry
+    Ok,
+    /// Credential found, but the registry offers no way to verify it
+    Unverified,
+    /// No credential configured
+    Missing,
+    /// Credential is malformed or a placeholder
+    Invalid,
+    /// Credential was rejected by the registry (expired or revoked)
+    Expired,
+    /// Registry does not need credentials for this step
+    NotRequired,
+}
+
+impl CredentialStatus {
+    /// Whether publishing is expected to fail authentication
+    pub fn is_problem(&self) -> bool {
+        matches!(
+            self,
+            CredentialStatus::Missing | CredentialStatus::Invalid | CredentialStatus::Expired
+        )
+    }
+}
+
+/// Result of checking one registry's credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialCheck {
+    pub registry: String,
+    /// Where the credential is read from (env var, file, command)
+    pub source: String,
+    pub status: CredentialStatus,
+    pub detail: String,
+    /// Authenticated account, when the registry reports one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+}
+
+impl CredentialCheck {
+    fn new(
+        registry: RegistryType,
+        source: impl Into<String>,
+        status: CredentialStatus,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            registry: registry.as_str().to_string(),
+            source: source.into(),
+            status,
+            detail: detail.into(),
+            account: None,
+        }
+    }
+
+    fn with_account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+}
+
+/// Checks registry credentials for a project
+pub struct CredentialPreflight<'a> {
+    project_path: PathBuf,
+    validator: &'a CredentialValidator,
+    config: Option<&'a PublishConfig>,
+    github: GitHubClient,
+}
+
+impl<'a> CredentialPreflight<'a> {
+    /// Create a preflight for `project_path`
+    pub fn new(project_path: impl AsRef<Path>, validator: &'a CredentialValidator) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            validator,
+            config: None,
+            github: GitHubClient::new(),
+        }
+    }
+
+    /// Use registry settings (token env names, Homebrew tap) from `config`
+    pub fn with_config(mut self, config: Option<&'a PublishConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use another GitHub API endpoint (GitHub Enterprise)
+    pub fn with_github_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.github = self.github.with_api_url(api_url);
+        self
+    }
+
+    /// Check every detected registry
+    pub async fn check_all(&self, detected: &[DetectedPlugin]) -> Vec<CredentialCheck> {
+        let mut checks = Vec::new();
+        for plugin in detected {
+            checks.push(self.check(plugin.registry_type).await);
+        }
+        checks
+    }
+
+    /// Check the credentials of one registry
+    pub async fn check(&self, registry: RegistryType) -> CredentialCheck {
+        let registries = self.config.map(|c| &c.registries);
+        match registry {
+            RegistryType::Npm => self.check_npm().await,
+            RegistryType::Crates => self.check_crates().await,
+            RegistryType::PyPI => self.check_pypi().await,
+            RegistryType::Homebrew => self.check_homebrew().await,
+            RegistryType::GitHubPackages => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.github_packages.as_ref())
+                    .and_then(|g| g.token_env.as_deref())
+                    .unwrap_or("GITHUB_TOKEN"),
+            ),
+            RegistryType::Upm => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.upm.as_ref())
+                    .and_then(|u| u.token_env.as_deref())
+                    .unwrap_or("UPM_TOKEN"),
+            ),
+            RegistryType::Julia => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.julia.as_ref())
+                    .and_then(|j| j.token_env.as_deref())
+                    .unwrap_or("GITHUB_TOKEN"),
+            ),
+            RegistryType::Bazel => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.bazel.as_ref())
+                    .and_then(|b| b.token_env.as_deref())
+                    .unwrap_or("GITHUB_TOKEN"),
+            ),
+            RegistryType::Cpan => self.check_env_token(registry, "PAUSE_PASSWORD"),
+            RegistryType::Hackage => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.hackage.as_ref())
+                    .and_then(|h| h.token_env.as_deref())
+                    .unwrap_or("HACKAGE_TOKEN"),
+            ),
+            RegistryType::Expo => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| r.expo.as_ref())
+                    .and_then(|e| e.token_env.as_deref())
+                    .unwrap_or("EXPO_TOKEN"),
+            ),
+            RegistryType::Bucket => self.check_bucket(),
+            RegistryType::Artifactory => self.check_env_token(registry, "ARTIFACTORY_TOKEN"),
+            RegistryType::Nexus => self.check_env_token(registry, "NEXUS_TOKEN"),
+            RegistryType::Cloudsmith => self.check_env_token(registry, "CLOUDSMITH_API_KEY"),
+            RegistryType::Gemfury => self.check_env_token(registry, "GEMFURY_PUSH_TOKEN"),
+            RegistryType::Aur => self.check_aur(),
+            RegistryType::Debian => self.check_debian(),
+            RegistryType::Scoop | RegistryType::Winget => self.check_env_token(
+                registry,
+                registries
+                    .and_then(|r| match registry {
+                        RegistryType::Scoop => r.scoop.as_ref(),
+                        _ => r.winget.as_ref(),
+                    })
+                    .and_then(|c| c.token_env.as_deref())
+                    .unwrap_or("GITHUB_TOKEN"),
+            ),
+            RegistryType::Cran => CredentialCheck::new(
+                registry,
+                "-",
+                CredentialStatus::NotRequired,
+                "CRAN submissions are confirmed by the maintainer via email",
+            ),
+        }
+    }
+
+    /// Reject placeholder / low-entropy values before asking the registry
+    fn placeholder_reason(&self, token: &str) -> Option<String> {
+        if self.validator.is_placeholder(token) {
+            return Some("looks like a placeholder value".to_string());
+        }
+        let result = self.validator.validate_token(token);
+        (!result.is_likely_real).then_some(result.reason)
+    }
+
+    fn check_env_token(&self, registry: RegistryType, env_var: &str) -> CredentialCheck {
+        match non_empty_env(env_var) {
+            None => CredentialCheck::new(
+                registry,
+                env_var,
+                CredentialStatus::Missing,
+                format!("{} is not set", env_var),
+            ),
+            Some(token) => match self.placeholder_reason(&token) {
+                Some(reason) => CredentialCheck::new(
+                    registry,
+                    env_var,
+                    CredentialStatus::Invalid,
+                    format!("{} {}", env_var, reason),
+                ),
+                None => CredentialCheck::new(
+                    registry,
+                    env_var,
+                    CredentialStatus::Unverified,
+                    format!("{} is set", env_var),
+                ),
+            },
+        }
+    }
+
+    /// Alternative cargo registries have no common "who am I" endpoint, so the
+    /// token is only checked for presence
+    async fn check_alternative_cargo_registry(&self, name: &str) -> CredentialCheck {
+        let registry = RegistryType::Crates;
+        match CargoRegistry::resolve(&self.project_path, Some(name)).await {
+            Err(e) => CredentialCheck::new(
+                registry,
+                ".cargo/config.toml",
+                CredentialStatus::Missing,
+                e.to_string(),
+            ),
+            Ok(cargo_registry) if cargo_registry.has_token() => CredentialCheck::new(
+                registry,
+                cargo_registry.token_env(),
+                CredentialStatus::Unverified,
+                format!("token for registry {} found", name),
+            ),
+            Ok(cargo_registry) => CredentialCheck::new(
+                registry,
+                cargo_registry.token_env(),
+                CredentialStatus::Missing,
+                format!(
+                    "no token for registry {} (set {} or run `cargo login --registry {}`)",
+                    name,
+                    cargo_registry.token_env(),
+                    name
+                ),
+            ),
+        }
+    }
+
+    /// Bucket uploads use the provider CLI's own credential chain
+    /// AUR pushes authenticate with an SSH key registered with the account
+    fn check_aur(&self) -> CredentialCheck {
+        let registry = RegistryType::Aur;
+        match self
+            .config
+            .and_then(|c| c.registries.aur.as_ref())
+            .and_then(|a| a.ssh_key.as_deref())
+        {
+            Some(key) if Path::new(key).exists() => CredentialCheck::new(
+                registry,
+                key,
+                CredentialStatus::Unverified,
+                format!("SSH key {} found", key),
+            ),
+            Some(key) => CredentialCheck::new(
+                registry,
+                key,
+                CredentialStatus::Missing,
+                format!("SSH key {} not found", key),
+            ),
+            None => CredentialCheck::new(
+                registry,
+                "ssh",
+                CredentialStatus::Unverified,
+                "uses the SSH agent / ~/.ssh/config for aur.archlinux.org",
+            ),
+        }
+    }
+
+    /// PPA uploads are GPG-signed; apt repositories use an API token
+    fn check_debian(&self) -> CredentialCheck {
+        let registry = RegistryType::Debian;
+        let config = self.config.and_then(|c| c.registries.debian.as_ref());
+        let target = config.and_then(|d| d.target.as_deref());
+        match (target, config.and_then(|d| d.token_env.as_deref())) {
+            (_, Some(token_env)) => self.check_env_token(registry, token_env),
+            (Some("artifactory"), None) => self.check_env_token(registry, "ARTIFACTORY_TOKEN"),
+            (Some("aptly"), None) => CredentialCheck::new(
+                registry,
+                "-",
+                CredentialStatus::NotRequired,
+                "the aptly API is called without authentication",
+            ),
+            _ => CredentialCheck::new(
+                registry,
+                "gpg",
+                CredentialStatus::Unverified,
+                "uploads are signed with debsign and sent with dput",
+            ),
+        }
+    }
+
+    fn check_bucket(&self) -> CredentialCheck {
+        let provider = self
+            .config
+            .and_then(|c| c.registries.bucket.as_ref())
+            .and_then(|b| b.provider.as_deref())
+            .unwrap_or("s3");
+        let (source, env_vars): (&str, &[&str]) = match provider {
+            "gcs" | "gs" => ("gcloud", &["GOOGLE_APPLICATION_CREDENTIALS"]),
+            "azure" => ("az", &["AZURE_STORAGE_KEY", "AZURE_CLIENT_ID"]),
+            _ => ("aws", &["AWS_ACCESS_KEY_ID", "AWS_PROFILE"]),
+        };
+        let detail = match env_vars.iter().find(|v| non_empty_env(v).is_some()) {
+            Some(var) => format!("{} is set", var),
+            None => format!("credentials are resolved by the {} CLI", source),
+        };
+        CredentialCheck::new(
+            RegistryType::Bucket,
+            source,
+            CredentialStatus::Unverified,
+            detail,
+        )
+    }
+
+    async fn check_npm(&self) -> CredentialCheck {
+        let registry = RegistryType::Npm;
+        if let Some(token) = non_empty_env("NPM_TOKEN")
+            && let Some(reason) = self.placeholder_reason(&token)
+        {
+            return CredentialCheck::new(
+                registry,
+                "NPM_TOKEN",
+                CredentialStatus::Invalid,
+                format!("NPM_TOKEN {}", reason),
+            );
+        }
+
+        match run(&self.project_path, "npm", &["whoami"], &[]).await {
+            Ok(output) => CredentialCheck::new(
+                registry,
+                "npm whoami",
+                CredentialStatus::Ok,
+                "authenticated",
+            )
+            .with_account(output.trim()),
+            Err(e) => {
+                let message = e.to_string();
+                let status = if message.contains("E401") {
+                    CredentialStatus::Expired
+                } else {
+                    CredentialStatus::Missing
+                };
+                CredentialCheck::new(
+                    registry,
+                    "npm whoami",
+                    status,
+                    match status {
+                        CredentialStatus::Expired => {
+                            "npm rejected the token (expired or revoked); run `npm login`"
+                                .to_string()
+                        }
+                        _ => format!("not logged in: {}", first_line(&message)),
+                    },
+                )
+            }
+        }
+    }
+
+    async fn check_crates(&self) -> CredentialCheck {
+        let registry = RegistryType::Crates;
+        if let Some(name) = self
+            .config
+            .and_then(|c| c.registries.crates.as_ref())
+            .and_then(|c| c.registry.as_deref())
+            .filter(|name| *name != "crates-io")
+        {
+            return self.check_alternative_cargo_registry(name).await;
+        }
+        let auth = self
+            .config
+            .and_then(|c| c.registries.crates.as_ref())
+            .and_then(|c| c.auth)
+            .unwrap_or_default();
+        if auth != CratesAuthMode::Token && OidcEnvironment::detect().is_some() {
+            return CredentialCheck::new(
+                registry,
+                "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+                CredentialStatus::Unverified,
+                "trusted publishing: the CI OIDC token is exchanged when publishing",
+            );
+        }
+        if auth == CratesAuthMode::Trusted {
+            return CredentialCheck::new(
+                registry,
+                "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+                CredentialStatus::Missing,
+                "trusted publishing requires a GitHub Actions job with `id-token: write`",
+            );
+        }
+        let (source, token) = match non_empty_env("CARGO_REGISTRY_TOKEN") {
+            Some(token) => ("CARGO_REGISTRY_TOKEN".to_string(), token),
+            None => match cargo_credentials_token().await {
+                Some(token) => ("~/.cargo/credentials.toml".to_string(), token),
+                None => {
+                    return CredentialCheck::new(
+                        registry,
+                        "CARGO_REGISTRY_TOKEN",
+                        CredentialStatus::Missing,
+                        "no crates.io token (set CARGO_REGISTRY_TOKEN or run `cargo login`)",
+                    );
+                }
+            },
+        };
+
+        if let Some(reason) = self.placeholder_reason(&token) {
+            return CredentialCheck::new(
+                registry,
+                source,
+                CredentialStatus::Invalid,
+                format!("token {}", reason),
+            );
+        }
+
+        let response = http_client()
+            .get(CRATES_IO_ME_URL)
+            .header("Authorization", &token)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => {
+                let login = response
+                    .json::<serde_json::Value>()
+                    .await
+                    .ok()
+                    .and_then(|body| body["user"]["login"].as_str().map(str::to_string));
+                let check = CredentialCheck::new(
+                    registry,
+                    source,
+                    CredentialStatus::Ok,
+                    "token accepted by crates.io",
+                );
+                match login {
+                    Some(login) => check.with_account(login),
+                    None => check,
+                }
+            }
+            Ok(response) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                CredentialCheck::new(
+                    registry,
+                    source,
+                    CredentialStatus::Expired,
+                    "crates.io rejected the token (expired or revoked)",
+                )
+            }
+            Ok(response) => CredentialCheck::new(
+                registry,
+                source,
+                CredentialStatus::Unverified,
+                format!(
+                    "token present; crates.io did not confirm it (HTTP {})",
+                    response.status()
+                ),
+            ),
+            Err(e) => CredentialCheck::new(
+                registry,
+                source,
+                CredentialStatus::Unverified,
+                format!("token present; crates.io unreachable: {}", e),
+            ),
+        }
+    }
+
+    async fn check_pypi(&self) -> CredentialCheck {
+        let registry = RegistryType::PyPI;
+        let token = match non_empty_env("TWINE_PASSWORD") {
+            Some(token) => Some(("TWINE_PASSWORD".to_string(), token)),
+            None => match non_empty_env("PYPI_TOKEN") {
+                Some(token) => Some(("PYPI_TOKEN".to_string(), token)),
+                None => pypirc_password()
+                    .await
+                    .map(|token| ("~/.pypirc".to_string(), token)),
+            },
+        };
+
+        let Some((source, token)) = token else {
+            return CredentialCheck::new(
+                registry,
+                "TWINE_PASSWORD",
+                CredentialStatus::Missing,
+                "no PyPI token (set TWINE_PASSWORD or configure ~/.pypirc)",
+            );
+        };
+
+        if !token.starts_with("pypi-") {
+            return CredentialCheck::new(
+                registry,
+                source,
+                CredentialStatus::Invalid,
+                "PyPI requires an API token (pypi-...); passwords are no longer accepted",
+            );
+        }
+        if let Some(reason) = self.placeholder_reason(&token) {
+            return CredentialCheck::new(
+                registry,
+                source,
+                CredentialStatus::Invalid,
+                format!("token {}", reason),
+            );
+        }
+
+        CredentialCheck::new(
+            registry,
+            source,
+            CredentialStatus::Unverified,
+            "API token format is valid (PyPI cannot verify tokens without uploading)",
+        )
+    }
+
+    async fn check_homebrew(&self) -> CredentialCheck {
+        let registry = RegistryType::Homebrew;
+        let tap = self
+            .config
+            .and_then(|c| c.registries.homebrew.as_ref())
+            .and_then(|h| h.tap.clone());
+
+        // A configured tap is checked through the GitHub API
+        if let Some(tap) = tap {
+            let repository = tap_repository(&tap);
+            let Some(token) = non_empty_env("GITHUB_TOKEN") else {
+                return CredentialCheck::new(
+                    registry,
+                    "GITHUB_TOKEN",
+                    CredentialStatus::Missing,
+                    format!(
+                        "GITHUB_TOKEN is required to check push access to {}",
+                        repository
+                    ),
+                );
+            };
+
+            let response = self
+                .github
+                .authenticated(&token)
+                .request(reqwest::Method::GET, &format!("/repos/{}", repository))
+                .send()
+                .await;
+            return match response {
+                Ok(response) if response.status().is_success() => {
+                    let body = response
+                        .json::<serde_json::Value>()
+                        .await
+                        .unwrap_or_default();
+                    if body["permissions"]["push"].as_bool() == Some(true) {
+                        CredentialCheck::new(
+                            registry,
+                            "GITHUB_TOKEN",
+                            CredentialStatus::Ok,
+                            format!("push access to {}", repository),
+                        )
+                    } else {
+                        CredentialCheck::new(
+                            registry,
+                            "GITHUB_TOKEN",
+                            CredentialStatus::Invalid,
+                            format!("token has no push access to {}", repository),
+                        )
+                    }
+                }
+                Ok(response) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                    CredentialCheck::new(
+                        registry,
+                        "GITHUB_TOKEN",
+                        CredentialStatus::Expired,
+                        "GitHub rejected the token (expired or revoked)",
+                    )
+                }
+                Ok(response) => CredentialCheck::new(
+                    registry,
+                    "GITHUB_TOKEN",
+                    CredentialStatus::Invalid,
+                    format!("cannot access {} (HTTP {})", repository, response.status()),
+                ),
+                Err(e) => CredentialCheck::new(
+                    registry,
+                    "GITHUB_TOKEN",
+                    CredentialStatus::Unverified,
+                    format!("GitHub unreachable: {}", e),
+                ),
+            };
+        }
+
+        // Otherwise the formula repository itself is pushed
+        match run(
+            &self.project_path,
+            "git",
+            &["push", "--dry-run", "--porcelain"],
+            &[("GIT_TERMINAL_PROMPT", "0")],
+        )
+        .await
+        {
+            Ok(_) => CredentialCheck::new(
+                registry,
+                "git push --dry-run",
+                CredentialStatus::Ok,
+                "push access to the tap repository",
+            ),
+            Err(e) => {
+                let message = e.to_string();
+                let status = if message.contains("Authentication failed")
+                    || message.contains("Permission denied")
+                    || message.contains("could not read Username")
+                    || message.contains("403")
+                {
+                    CredentialStatus::Missing
+                } else {
+                    CredentialStatus::Invalid
+                };
+                CredentialCheck::new(
+                    registry,
+                    "git push --dry-run",
+                    status,
+                    format!("cannot push to the tap: {}", first_line(&message)),
+                )
+            }
+        }
+    }
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("")
+}
+
+/// `owner/homebrew-name` for a tap given as `owner/name` or `owner/homebrew-name`
+fn tap_repository(tap: &str) -> String {
+    match tap.split_once('/') {
+        Some((owner, name)) if !name.starts_with("homebrew-") => {
+            format!("{}/homebrew-{}", owner, name)
+        }
+        _ => tap.to_string(),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Token from `$CARGO_HOME/credentials.toml` (`[registry] token`)
+async fn cargo_credentials_token() -> Option<String> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".cargo")))?;
+
+    for file in ["credentials.toml", "credentials"] {
+        if let Ok(content) = fs::read_to_string(cargo_home.join(file)).await
+            && let Ok(table) = toml::from_str::<toml::Table>(&content)
+            && let Some(token) = table
+                .get("registry")
+                .and_then(|r| r.get("token"))
+                .and_then(|t| t.as_str())
+        {
+            return Some(token.to_string());
+        }
+    }
+    None
+}
+
+/// `password` of the `[pypi]` section in `~/.pypirc`
+async fn pypirc_password() -> Option<String> {
+    let content = fs::read_to_string(home_dir()?.join(".pypirc")).await.ok()?;
+    parse_pypirc_password(&content)
+}
+
+fn parse_pypirc_password(content: &str) -> Option<String> {
+    let mut in_pypi = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_pypi = line == "[pypi]";
+            continue;
+        }
+        if in_pypi
+            && let Some((key, value)) = line.split_once(['=', ':'])
+            && key.trim() == "password"
+        {
+            return Some(value.trim().to_string());
+        }
+    }
+    None
+}
+
+async fn run(
+    dir: &Path,
+    program: &str,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> anyhow::Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tap_repository() {
+        assert_eq!(tap_repository("acme/tools"), "acme/homebrew-tools");
+        assert_eq!(tap_repository("acme/homebrew-tools"), "acme/homebrew-tools");
+    }
+
+    #[test]
+    fn test_parse_pypirc_password() {
+        let content = "[distutils]\nindex-servers = pypi\n\n[testpypi]\npassword = pypi-test\n\n[pypi]\nusername = __token__\npassword = pypi-AgEIcHlwaS5vcmc\n";
+        assert_eq!(
+            parse_pypirc_password(content).as_deref(),
+            Some("pypi-AgEIcHlwaS5vcmc")
+        );
+        assert!(parse_pypirc_password("[testpypi]\npassword = x\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cran_needs_no_credentials() {
+        let validator = CredentialValidator::new();
+        let preflight = CredentialPreflight::new(".", &validator);
+        let check = preflight.check(RegistryType::Cran).await;
+        assert_eq!(check.status, CredentialStatus::NotRequired);
+        assert!(!check.status.is_problem());
+    }
+}
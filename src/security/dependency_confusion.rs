@@ -0,0 +1,526 @@
+//! Dependency Confusion - guard internal package names against public shadowing
+//!
+//! Internal packages are the names matching `security.dependencyConfusion`
+//! (`internalPackages` patterns and npm `internalScopes`), plus the package
+//! itself when it is published to a private registry. For each of them:
+//! - the public registry (npmjs.org, PyPI, crates.io) is queried; an
+//!   identically named public package could be installed instead of ours
+//! - the resolver configuration is checked for fallbacks to the public
+//!   registry: npm scopes without `@scope:registry=` in `.npmrc`, pip
+//!   `--extra-index-url` (pip picks the highest version across indexes), and
+//!   Cargo dependencies without `registry = "..."`
+
+use crate::core::http::http_client;
+use crate::plugins::plugin_loader::RegistryType;
+use crate::validation::dependency_checker::IssueSeverity;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Ecosystem whose public registry can shadow internal names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageEcosystem {
+    Npm,
+    PyPI,
+    Crates,
+}
+
+impl PackageEcosystem {
+    /// Ecosystem of a registry, if it hosts npm / Python / Cargo packages
+    pub fn for_registry(registry: RegistryType) -> Option<Self> {
+        match registry {
+            RegistryType::Npm | RegistryType::Expo | RegistryType::GitHubPackages => {
+                Some(PackageEcosystem::Npm)
+            }
+            RegistryType::PyPI => Some(PackageEcosystem::PyPI),
+            RegistryType::Crates => Some(PackageEcosystem::Crates),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageEcosystem::Npm => "npm",
+            PackageEcosystem::PyPI => "pypi",
+            PackageEcosystem::Crates => "crates.io",
+        }
+    }
+
+    fn public_url(&self, name: &str) -> String {
+        match self {
+            PackageEcosystem::Npm => {
+                format!("https://registry.npmjs.org/{}", name.replace('/', "%2F"))
+            }
+            PackageEcosystem::PyPI => format!("https://pypi.org/pypi/{}/json", name),
+            PackageEcosystem::Crates => format!("https://crates.io/api/v1/crates/{}", name),
+        }
+    }
+}
+
+/// Kind of dependency confusion risk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfusionKind {
+    /// An identically named package exists on the public registry
+    PublicNameTaken,
+    /// The resolver may fall back to the public registry for this name
+    RegistryFallback,
+}
+
+/// Dependency confusion finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfusionFinding {
+    pub ecosystem: PackageEcosystem,
+    pub package: String,
+    pub kind: ConfusionKind,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+/// Result of a dependency confusion check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfusionReport {
+    /// Internal package names that were checked
+    pub checked: Vec<String>,
+    pub findings: Vec<ConfusionFinding>,
+    /// Public registry lookups that failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl ConfusionReport {
+    /// Findings at or above `threshold`
+    pub fn blocking(&self, threshold: IssueSeverity) -> impl Iterator<Item = &ConfusionFinding> {
+        self.findings
+            .iter()
+            .filter(move |f| f.severity.meets(threshold))
+    }
+}
+
+/// Package name and the dependencies declared by a manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestPackages {
+    pub name: Option<String>,
+    pub dependencies: Vec<String>,
+    /// Cargo dependencies pinned to an alternate registry (`registry = "..."`)
+    pub registry_pinned: Vec<String>,
+}
+
+/// Internal package names from `internalPackages` (`*` wildcards) and npm
+/// `internalScopes`
+#[derive(Debug, Clone, Default)]
+pub struct InternalNames {
+    patterns: Vec<Regex>,
+    scopes: Vec<String>,
+}
+
+impl InternalNames {
+    pub fn new(patterns: &[String], scopes: &[String]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .filter_map(|p| {
+                    let regex = format!("^{}$", regex::escape(p).replace(r"\*", ".*"));
+                    Regex::new(&regex).ok()
+                })
+                .collect(),
+            scopes: scopes
+                .iter()
+                .map(|s| format!("@{}", s.trim_start_matches('@')))
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.patterns.iter().any(|p| p.is_match(name))
+            || self
+                .scopes
+                .iter()
+                .any(|s| name.split_once('/').is_some_and(|(scope, _)| scope == s))
+    }
+
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+}
+
+/// npm scopes routed to a registry by `.npmrc` (`@scope:registry=...`)
+pub fn npmrc_scoped_registries(npmrc: &str) -> Vec<String> {
+    npmrc
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            let scope = key.trim().strip_suffix(":registry")?;
+            (scope.starts_with('@') && !value.trim().is_empty()).then(|| scope.to_string())
+        })
+        .collect()
+}
+
+/// Whether a pip configuration or requirements file adds a fallback index
+pub fn has_extra_index_url(content: &str) -> bool {
+    content.lines().any(|line| {
+        let line = line.trim();
+        !line.starts_with('#')
+            && (line.starts_with("--extra-index-url")
+                || line.starts_with("extra-index-url")
+                || line.starts_with("extra_index_url"))
+    })
+}
+
+/// Python requirement name (`pkg[extra]>=1.0; marker` -> `pkg`)
+fn requirement_name(requirement: &str) -> Option<String> {
+    let name: String = requirement
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Checks internal package names against public registries
+pub struct DependencyConfusionChecker {
+    project_path: PathBuf,
+    internal: InternalNames,
+    client: reqwest::Client,
+}
+
+impl DependencyConfusionChecker {
+    pub fn new(project_path: impl Into<PathBuf>, internal: InternalNames) -> Self {
+        Self {
+            project_path: project_path.into(),
+            internal,
+            client: http_client(),
+        }
+    }
+
+    /// Check the project's manifest for `registry`
+    ///
+    /// `private_target` marks the package itself as internal (it is being
+    /// published to a private registry).
+    pub async fn check(&self, registry: RegistryType, private_target: bool) -> ConfusionReport {
+        let mut report = ConfusionReport::default();
+        let Some(ecosystem) = PackageEcosystem::for_registry(registry) else {
+            return report;
+        };
+        let manifest = match self.read_manifest(ecosystem).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                report.errors.push(e.to_string());
+                return report;
+            }
+        };
+
+        let mut internal: Vec<String> = manifest
+            .dependencies
+            .iter()
+            .filter(|d| self.internal.matches(d))
+            .cloned()
+            .collect();
+        internal.sort();
+        internal.dedup();
+        let own_name = manifest
+            .name
+            .clone()
+            .filter(|n| private_target || self.internal.matches(n));
+        if let Some(ref name) = own_name {
+            internal.retain(|d| d != name);
+            internal.insert(0, name.clone());
+        }
+
+        for name in &internal {
+            match self.public_package_exists(ecosystem, name).await {
+                Ok(true) => report.findings.push(ConfusionFinding {
+                    ecosystem,
+                    package: name.clone(),
+                    kind: ConfusionKind::PublicNameTaken,
+                    severity: IssueSeverity::High,
+                    message: format!(
+                        "{} exists on the public {} registry; installs may resolve to it",
+                        name,
+                        ecosystem.as_str()
+                    ),
+                }),
+                Ok(false) => {}
+                Err(e) => report.errors.push(format!("{}: {}", name, e)),
+            }
+        }
+
+        let dependencies: Vec<&String> = internal
+            .iter()
+            .filter(|n| own_name.as_ref() != Some(*n))
+            .collect();
+        let fallbacks = self
+            .registry_fallbacks(ecosystem, &manifest, &dependencies)
+            .await;
+        report.findings.extend(fallbacks);
+        report.checked = internal;
+        report
+    }
+
+    /// Whether `name` exists on the public registry of `ecosystem`
+    pub async fn public_package_exists(
+        &self,
+        ecosystem: PackageEcosystem,
+        name: &str,
+    ) -> anyhow::Result<bool> {
+        let response = self.client.get(ecosystem.public_url(name)).send().await?;
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            status => anyhow::bail!("{} returned HTTP {}", ecosystem.as_str(), status),
+        }
+    }
+
+    /// Name and dependencies from package.json / pyproject.toml / Cargo.toml
+    pub async fn read_manifest(
+        &self,
+        ecosystem: PackageEcosystem,
+    ) -> anyhow::Result<ManifestPackages> {
+        match ecosystem {
+            PackageEcosystem::Npm => {
+                let content = fs::read_to_string(self.project_path.join("package.json")).await?;
+                let json: serde_json::Value = serde_json::from_str(&content)?;
+                let dependencies = [
+                    "dependencies",
+                    "devDependencies",
+                    "optionalDependencies",
+                    "peerDependencies",
+                ]
+                .iter()
+                .filter_map(|key| json.get(*key).and_then(|d| d.as_object()))
+                .flat_map(|deps| deps.keys().cloned())
+                .collect();
+                Ok(ManifestPackages {
+                    name: json
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .map(str::to_string),
+                    dependencies,
+                    registry_pinned: Vec::new(),
+                })
+            }
+            PackageEcosystem::Crates => {
+                let content = fs::read_to_string(self.project_path.join("Cargo.toml")).await?;
+                let toml: toml::Value = toml::from_str(&content)?;
+                let mut manifest = ManifestPackages {
+                    name: toml
+                        .get("package")
+                        .and_then(|p| p.get("name"))
+                        .and_then(|n| n.as_str())
+                        .map(str::to_string),
+                    ..Default::default()
+                };
+                for table in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                    let Some(deps) = toml.get(table).and_then(|d| d.as_table()) else {
+                        continue;
+                    };
+                    for (key, spec) in deps {
+                        let name = spec
+                            .get("package")
+                            .and_then(|p| p.as_str())
+                            .unwrap_or(key)
+                            .to_string();
+                        if spec.get("registry").is_some() || spec.get("path").is_some() {
+                            manifest.registry_pinned.push(name.clone());
+                        }
+                        manifest.dependencies.push(name);
+                    }
+                }
+                Ok(manifest)
+            }
+            PackageEcosystem::PyPI => {
+                let mut manifest = ManifestPackages::default();
+                if let Ok(content) =
+                    fs::read_to_string(self.project_path.join("pyproject.toml")).await
+                {
+                    let toml: toml::Value = toml::from_str(&content)?;
+                    let project = toml.get("project");
+                    manifest.name = project
+                        .and_then(|p| p.get("name"))
+                        .and_then(|n| n.as_str())
+                        .map(str::to_string);
+                    manifest.dependencies.extend(
+                        project
+                            .and_then(|p| p.get("dependencies"))
+                            .and_then(|d| d.as_array())
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|d| d.as_str().and_then(requirement_name)),
+                    );
+                }
+                if let Ok(content) =
+                    fs::read_to_string(self.project_path.join("requirements.txt")).await
+                {
+                    manifest.dependencies.extend(
+                        content
+                            .lines()
+                            .filter(|l| !l.trim().starts_with(['#', '-']))
+                            .filter_map(requirement_name),
+                    );
+                }
+                Ok(manifest)
+            }
+        }
+    }
+
+    async fn registry_fallbacks(
+        &self,
+        ecosystem: PackageEcosystem,
+        manifest: &ManifestPackages,
+        dependencies: &[&String],
+    ) -> Vec<ConfusionFinding> {
+        let fallback = |package: String, message: String| ConfusionFinding {
+            ecosystem,
+            package,
+            kind: ConfusionKind::RegistryFallback,
+            severity: IssueSeverity::Medium,
+            message,
+        };
+
+        match ecosystem {
+            PackageEcosystem::Npm => {
+                let npmrc = fs::read_to_string(self.project_path.join(".npmrc"))
+                    .await
+                    .unwrap_or_default();
+                let routed = npmrc_scoped_registries(&npmrc);
+                let mut findings: Vec<ConfusionFinding> = self
+                    .internal
+                    .scopes()
+                    .iter()
+                    .filter(|scope| !routed.contains(scope))
+                    .map(|scope| {
+                        fallback(
+                            scope.clone(),
+                            format!(
+                                "{} has no {}:registry= in .npmrc; it resolves from the public registry",
+                                scope, scope
+                            ),
+                        )
+                    })
+                    .collect();
+                findings.extend(
+                    dependencies
+                        .iter()
+                        .filter(|d| !d.starts_with('@'))
+                        .map(|d| {
+                            fallback(
+                                d.to_string(),
+                                format!(
+                                    "{} is unscoped; move it to a scope routed to the private registry",
+                                    d
+                                ),
+                            )
+                        }),
+                );
+                findings
+            }
+            PackageEcosystem::PyPI => {
+                let mut sources = Vec::new();
+                for file in ["pip.conf", "pip.ini", "requirements.txt"] {
+                    if let Ok(content) = fs::read_to_string(self.project_path.join(file)).await
+                        && has_extra_index_url(&content)
+                    {
+                        sources.push(file);
+                    }
+                }
+                if sources.is_empty() || dependencies.is_empty() {
+                    return Vec::new();
+                }
+                vec![fallback(
+                    dependencies
+                        .iter()
+                        .map(|d| d.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    format!(
+                        "extra-index-url in {} lets pip pick a public package with a higher version; use a single index-url",
+                        sources.join(", ")
+                    ),
+                )]
+            }
+            PackageEcosystem::Crates => dependencies
+                .iter()
+                .filter(|d| !manifest.registry_pinned.contains(d))
+                .map(|d| {
+                    fallback(
+                        d.to_string(),
+                        format!(
+                            "{} has no registry = \"...\" in Cargo.toml; it resolves from crates.io",
+                            d
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_internal_names() {
+        let internal = InternalNames::new(&["acme-*".to_string()], &["corp".to_string()]);
+        assert!(internal.matches("acme-utils"));
+        assert!(internal.matches("@corp/ui"));
+        assert!(!internal.matches("@corporate/ui"));
+        assert!(!internal.matches("lodash"));
+    }
+
+    #[test]
+    fn test_resolver_config_parsing() {
+        let npmrc =
+            "@corp:registry=https://npm.corp.example/\n//npm.corp.example/:_authToken=${TOKEN}\n";
+        assert_eq!(npmrc_scoped_registries(npmrc), vec!["@corp".to_string()]);
+
+        assert!(has_extra_index_url(
+            "--extra-index-url https://pypi.corp.example/simple\nacme-core==1.0\n"
+        ));
+        assert!(!has_extra_index_url(
+            "--index-url https://pypi.corp.example/simple\n# --extra-index-url x\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cargo_registry_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"[package]
+name = "app"
+version = "1.0.0"
+
+[dependencies]
+serde = "1"
+acme-core = { version = "1", registry = "acme" }
+acme-utils = "1"
+"#,
+        )
+        .unwrap();
+
+        let checker = DependencyConfusionChecker::new(
+            temp_dir.path(),
+            InternalNames::new(&["acme-*".to_string()], &[]),
+        );
+        let manifest = checker
+            .read_manifest(PackageEcosystem::Crates)
+            .await
+            .unwrap();
+        assert_eq!(manifest.registry_pinned, vec!["acme-core".to_string()]);
+
+        let core = "acme-core".to_string();
+        let utils = "acme-utils".to_string();
+        let findings = checker
+            .registry_fallbacks(PackageEcosystem::Crates, &manifest, &[&core, &utils])
+            .await;
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].package, "acme-utils");
+        assert_eq!(findings[0].kind, ConfusionKind::RegistryFallback);
+    }
+}
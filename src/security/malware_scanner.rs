@@ -0,0 +1,346 @@
+//! Malware Scanner - virus/malware scanning of release artifacts
+//!
+//! Built artifacts are passed through a configurable scanner before they are
+//! signed and published:
+//! - ClamAV: `clamscan --no-summary <file>` (exit 0 clean, 1 infected)
+//! - Command: any scanner command; `{file}` is replaced with the artifact path
+//!   and the same exit code convention applies
+//! - REST: the artifact is POSTed as `application/octet-stream` to a scanning
+//!   API, which answers with JSON such as `{"infected": true, "threat": "..."}`
+//!   or `{"verdict": "malicious", "signature": "..."}`
+//!
+//! Every artifact gets an [`ArtifactScanResult`] that is recorded in the
+//! publish report; any detection blocks publishing.
+
+use crate::core::config::MalwareScanningConfig;
+use crate::core::http::http_client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+const DEFAULT_TOKEN_ENV: &str = "MALWARE_SCAN_TOKEN";
+
+/// Outcome of scanning one artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanVerdict {
+    Clean,
+    Infected,
+    /// The scanner could not produce a verdict
+    Error,
+}
+
+impl fmt::Display for ScanVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanVerdict::Clean => write!(f, "clean"),
+            ScanVerdict::Infected => write!(f, "infected"),
+            ScanVerdict::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Scan verdict recorded in the publish report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactScanResult {
+    pub artifact: String,
+    /// SHA-256 hex digest of the scanned artifact
+    pub sha256: String,
+    pub scanner: String,
+    pub verdict: ScanVerdict,
+    /// Detection name (`Eicar-Signature`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threat: Option<String>,
+    /// Scanner output or error message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Scanner backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScannerBackend {
+    /// Command line scanner; `{file}` in the arguments is the artifact path
+    Command { program: String, args: Vec<String> },
+    /// REST scanning API
+    Rest { url: String, token_env: String },
+}
+
+impl ScannerBackend {
+    /// Backend from `security.malwareScanning`
+    pub fn from_config(config: &MalwareScanningConfig) -> anyhow::Result<Self> {
+        match config.scanner.as_deref().unwrap_or("clamav") {
+            "clamav" => Ok(ScannerBackend::Command {
+                program: "clamscan".to_string(),
+                args: vec!["--no-summary".to_string(), "{file}".to_string()],
+            }),
+            "command" => {
+                let command = config
+                    .command
+                    .as_deref()
+                    .filter(|c| !c.is_empty())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("security.malwareScanning.command is required")
+                    })?;
+                Ok(ScannerBackend::Command {
+                    program: command[0].clone(),
+                    args: command[1..].to_vec(),
+                })
+            }
+            "rest" => Ok(ScannerBackend::Rest {
+                url: config
+                    .url
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("security.malwareScanning.url is required"))?,
+                token_env: config
+                    .token_env
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_TOKEN_ENV.to_string()),
+            }),
+            other => anyhow::bail!(
+                "Unknown malware scanner: {} (expected clamav, command or rest)",
+                other
+            ),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            ScannerBackend::Command { program, .. } => program.clone(),
+            ScannerBackend::Rest { url, .. } => url.clone(),
+        }
+    }
+}
+
+/// Detection name from ClamAV-style output (`<file>: <threat> FOUND`)
+pub fn parse_clamav_threat(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let line = line.trim().strip_suffix(" FOUND")?;
+        let (_, threat) = line.rsplit_once(": ")?;
+        Some(threat.trim().to_string())
+    })
+}
+
+/// Verdict from a REST scanning API response
+pub fn parse_rest_verdict(body: &serde_json::Value) -> (ScanVerdict, Option<String>) {
+    let infected = match (
+        body.get("infected"),
+        body.get("verdict").or(body.get("result")),
+    ) {
+        (Some(serde_json::Value::Bool(infected)), _) => Some(*infected),
+        (_, Some(serde_json::Value::String(verdict))) => match verdict.to_lowercase().as_str() {
+            "clean" | "ok" | "safe" | "harmless" | "undetected" => Some(false),
+            "infected" | "malicious" | "suspicious" | "detected" | "virus" => Some(true),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let threat = ["threat", "signature", "malware"]
+        .iter()
+        .find_map(|key| body.get(*key).and_then(|t| t.as_str()))
+        .map(str::to_string)
+        .or_else(|| {
+            body.get("viruses")
+                .and_then(|v| v.as_array())
+                .map(|viruses| {
+                    viruses
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .filter(|v| !v.is_empty())
+        });
+
+    match infected {
+        Some(true) => (ScanVerdict::Infected, threat),
+        Some(false) => (ScanVerdict::Clean, None),
+        None => (ScanVerdict::Error, threat),
+    }
+}
+
+/// Scans artifacts with the configured backend
+pub struct MalwareScanner {
+    backend: ScannerBackend,
+}
+
+impl MalwareScanner {
+    pub fn new(backend: ScannerBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Scan one artifact; scanner failures become an `Error` verdict
+    pub async fn scan(&self, artifact: &Path) -> anyhow::Result<ArtifactScanResult> {
+        let content = fs::read(artifact).await?;
+        let sha256 = hex::encode(Sha256::digest(&content));
+
+        let (verdict, threat, detail) = match &self.backend {
+            ScannerBackend::Command { program, args } => {
+                self.scan_command(program, args, artifact).await
+            }
+            ScannerBackend::Rest { url, token_env } => {
+                self.scan_rest(url, token_env, artifact, content).await
+            }
+        };
+
+        Ok(ArtifactScanResult {
+            artifact: artifact.display().to_string(),
+            sha256,
+            scanner: self.backend.name(),
+            verdict,
+            threat,
+            detail,
+        })
+    }
+
+    async fn scan_command(
+        &self,
+        program: &str,
+        args: &[String],
+        artifact: &Path,
+    ) -> (ScanVerdict, Option<String>, Option<String>) {
+        let file = artifact.display().to_string();
+        let args: Vec<String> = args.iter().map(|a| a.replace("{file}", &file)).collect();
+
+        let output = Command::new(program)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await;
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                return (
+                    ScanVerdict::Error,
+                    None,
+                    Some(format!("Failed to run {}: {}", program, e)),
+                );
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        match output.status.code() {
+            Some(0) => (ScanVerdict::Clean, None, None),
+            Some(1) => (
+                ScanVerdict::Infected,
+                parse_clamav_threat(&stdout),
+                Some(stdout),
+            ),
+            _ => (
+                ScanVerdict::Error,
+                None,
+                Some(if stderr.is_empty() { stdout } else { stderr }),
+            ),
+        }
+    }
+
+    async fn scan_rest(
+        &self,
+        url: &str,
+        token_env: &str,
+        artifact: &Path,
+        content: Vec<u8>,
+    ) -> (ScanVerdict, Option<String>, Option<String>) {
+        let file_name = artifact
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut request = http_client()
+            .post(url)
+            .header("Content-Type", "application/octet-stream")
+            .header("X-File-Name", file_name)
+            .body(content);
+        if let Ok(token) = std::env::var(token_env)
+            && !token.is_empty()
+        {
+            request = request.bearer_auth(token);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return (ScanVerdict::Error, None, Some(e.to_string())),
+        };
+        let status = response.status();
+        if !status.is_success() {
+            return (
+                ScanVerdict::Error,
+                None,
+                Some(format!("Scanning API returned HTTP {}", status)),
+            );
+        }
+        match response.json::<serde_json::Value>().await {
+            Ok(body) => {
+                let (verdict, threat) = parse_rest_verdict(&body);
+                let detail = (verdict == ScanVerdict::Error)
+                    .then(|| format!("Unrecognized scanning API response: {}", body));
+                (verdict, threat, detail)
+            }
+            Err(e) => (ScanVerdict::Error, None, Some(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_clamav_threat() {
+        let output = "/tmp/dist/app-1.0.0.tgz: Eicar-Signature FOUND";
+        assert_eq!(
+            parse_clamav_threat(output).as_deref(),
+            Some("Eicar-Signature")
+        );
+        assert_eq!(parse_clamav_threat("/tmp/app.tgz: OK"), None);
+    }
+
+    #[test]
+    fn test_parse_rest_verdict() {
+        let (verdict, threat) =
+            parse_rest_verdict(&serde_json::json!({"infected": true, "viruses": ["Trojan.X"]}));
+        assert_eq!(verdict, ScanVerdict::Infected);
+        assert_eq!(threat.as_deref(), Some("Trojan.X"));
+
+        let (verdict, _) = parse_rest_verdict(&serde_json::json!({"verdict": "clean"}));
+        assert_eq!(verdict, ScanVerdict::Clean);
+
+        let (verdict, _) = parse_rest_verdict(&serde_json::json!({"status": "queued"}));
+        assert_eq!(verdict, ScanVerdict::Error);
+    }
+
+    #[tokio::test]
+    async fn test_command_scanner_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let artifact = temp_dir.path().join("app-1.0.0.tgz");
+        std::fs::write(&artifact, "package").unwrap();
+
+        let scanner = MalwareScanner::new(ScannerBackend::Command {
+            program: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                "echo \"$0: Test-Signature FOUND\"; exit 1".to_string(),
+                "{file}".to_string(),
+            ],
+        });
+        let result = scanner.scan(&artifact).await.unwrap();
+        assert_eq!(result.verdict, ScanVerdict::Infected);
+        assert_eq!(result.threat.as_deref(), Some("Test-Signature"));
+
+        let scanner = MalwareScanner::new(ScannerBackend::Command {
+            program: "true".to_string(),
+            args: Vec::new(),
+        });
+        let result = scanner.scan(&artifact).await.unwrap();
+        assert_eq!(result.verdict, ScanVerdict::Clean);
+        assert_eq!(result.sha256.len(), 64);
+    }
+}
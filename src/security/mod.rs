@@ -1,9 +1,27 @@
+pub mod artifact_inspector;
+pub mod audit;
 pub mod command_executor;
+pub mod credential_preflight;
 pub mod credential_validator;
+pub mod dependency_confusion;
+pub mod malware_scanner;
+pub mod redaction;
+pub mod secrets_baseline;
 pub mod secrets_scanner;
 pub mod token_manager;
 
-pub use command_executor::{CommandError, SafeCommandExecutor};
+pub use artifact_inspector::{ArtifactInspection, ArtifactInspector};
+pub use audit::{AuditBackend, AuditFinding, AuditReport, DependencyAuditor};
+pub use command_executor::{CommandError, EnvSandbox, SafeCommandExecutor};
+pub use credential_preflight::{CredentialCheck, CredentialPreflight, CredentialStatus};
 pub use credential_validator::{CredentialValidator, ValidationResult};
-pub use secrets_scanner::{ScanReport, SecretFinding, SecretsScanner, Severity};
+pub use dependency_confusion::{
+    ConfusionFinding, ConfusionKind, ConfusionReport, DependencyConfusionChecker, InternalNames,
+};
+pub use malware_scanner::{ArtifactScanResult, MalwareScanner, ScanVerdict, ScannerBackend};
+pub use redaction::{RedactingReporter, Redactor, redact, register_secret};
+pub use secrets_baseline::{BaselineEntry, SecretsBaseline};
+pub use secrets_scanner::{
+    HistoricalSecretFinding, HistoryScanReport, ScanReport, SecretFinding, SecretsScanner, Severity,
+};
 pub use token_manager::SecureTokenManager;
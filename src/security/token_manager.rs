@@ -14,8 +14,16 @@ const REGISTRY_TOKENS: &[(&str, &str)] = &[
     ("crates.io", "CARGO_REGISTRY_TOKEN"),
     ("pypi", "PYPI_TOKEN"),
     ("homebrew", "HOMEBREW_GITHUB_API_TOKEN"),
+    ("cpan", "PAUSE_PASSWORD"),
+    ("artifactory", "ARTIFACTORY_TOKEN"),
+    ("nexus", "NEXUS_TOKEN"),
+    ("cloudsmith", "CLOUDSMITH_API_KEY"),
+    ("gemfury", "GEMFURY_PUSH_TOKEN"),
 ];
 
+/// Integration services (issue trackers) with their environment variable names
+const SERVICE_TOKENS: &[(&str, &str)] = &[("jira", "JIRA_API_TOKEN"), ("linear", "LINEAR_API_KEY")];
+
 /// Secure token manager for package registry authentication
 ///
 /// # Examples
@@ -32,6 +40,7 @@ const REGISTRY_TOKENS: &[(&str, &str)] = &[
 #[derive(Default)]
 pub struct SecureTokenManager {
     registry_map: HashMap<String, String>,
+    service_map: HashMap<String, String>,
 }
 
 impl SecureTokenManager {
@@ -43,15 +52,22 @@ impl SecureTokenManager {
     /// use package_publisher::security::SecureTokenManager;
     ///
     /// let manager = SecureTokenManager::new();
-    /// assert_eq!(manager.get_supported_registries().len(), 4);
+    /// assert_eq!(manager.get_supported_registries().len(), 9);
     /// ```
     pub fn new() -> Self {
         let registry_map = REGISTRY_TOKENS
             .iter()
             .map(|(k, v)| (k.to_string(), v.to_string()))
             .collect();
+        let service_map = SERVICE_TOKENS
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
 
-        Self { registry_map }
+        Self {
+            registry_map,
+            service_map,
+        }
     }
 
     /// Retrieves a token for the specified registry from environment variables
@@ -79,6 +95,30 @@ impl SecureTokenManager {
         Some(SecretString::new(token_value.into()))
     }
 
+    /// Retrieves an API token for an integration service ("jira", "linear")
+    ///
+    /// `env_override` replaces the default environment variable name
+    /// (e.g. a `tokenEnv` config option).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use package_publisher::security::SecureTokenManager;
+    ///
+    /// let manager = SecureTokenManager::new();
+    /// assert!(manager.get_service_token("unknown-service", None).is_none());
+    /// ```
+    pub fn get_service_token(
+        &self,
+        service: &str,
+        env_override: Option<&str>,
+    ) -> Option<SecretString> {
+        let token_name =
+            env_override.or_else(|| self.service_map.get(service).map(|s| s.as_str()))?;
+        let token_value = env::var(token_name).ok().filter(|t| !t.is_empty())?;
+        Some(SecretString::new(token_value.into()))
+    }
+
     /// Checks if a token is set for the specified registry
     ///
     /// # Arguments
@@ -161,10 +201,38 @@ impl SecureTokenManager {
                 }
             }
         }
+        for service in self.service_map.keys() {
+            if let Some(token) = self.get_service_token(service, None) {
+                let token_str = token.expose_secret();
+                masked = masked.replace(token_str, &self.mask_token(token_str));
+            }
+        }
 
         masked
     }
 
+    /// Returns every registry and service token that is set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use package_publisher::security::SecureTokenManager;
+    ///
+    /// let manager = SecureTokenManager::new();
+    /// println!("{} token(s) configured", manager.loaded_tokens().len());
+    /// ```
+    pub fn loaded_tokens(&self) -> Vec<SecretString> {
+        let registry_tokens = self
+            .registry_map
+            .keys()
+            .filter_map(|registry| self.get_token(registry));
+        let service_tokens = self
+            .service_map
+            .keys()
+            .filter_map(|service| self.get_service_token(service, None));
+        registry_tokens.chain(service_tokens).collect()
+    }
+
     /// Gets the environment variable name for a registry
     ///
     /// # Arguments
@@ -216,7 +284,26 @@ mod tests {
     #[test]
     fn test_new_manager() {
         let manager = SecureTokenManager::new();
-        assert_eq!(manager.get_supported_registries().len(), 4);
+        assert_eq!(manager.get_supported_registries().len(), 9);
+    }
+
+    #[test]
+    fn test_get_service_token() {
+        unsafe {
+            env::set_var("LINEAR_API_KEY", "lin_api_test_key_12345");
+            env::set_var("PACKAGE_PUBLISHER_TEST_JIRA", "jira-test-token-67890");
+        }
+        let manager = SecureTokenManager::new();
+        let linear = manager.get_service_token("linear", None).unwrap();
+        assert_eq!(linear.expose_secret(), "lin_api_test_key_12345");
+        let jira = manager
+            .get_service_token("jira", Some("PACKAGE_PUBLISHER_TEST_JIRA"))
+            .unwrap();
+        assert_eq!(jira.expose_secret(), "jira-test-token-67890");
+        assert_eq!(
+            manager.mask_tokens_in_string("key=lin_api_test_key_12345"),
+            "key=lin...345"
+        );
     }
 
     #[test]
@@ -316,11 +403,16 @@ mod tests {
     fn test_get_supported_registries() {
         let manager = SecureTokenManager::new();
         let registries = manager.get_supported_registries();
-        assert_eq!(registries.len(), 4);
+        assert_eq!(registries.len(), 9);
         assert!(registries.contains(&"npm".to_string()));
         assert!(registries.contains(&"crates.io".to_string()));
         assert!(registries.contains(&"pypi".to_string()));
         assert!(registries.contains(&"homebrew".to_string()));
+        assert!(registries.contains(&"cpan".to_string()));
+        assert!(registries.contains(&"artifactory".to_string()));
+        assert!(registries.contains(&"nexus".to_string()));
+        assert!(registries.contains(&"cloudsmith".to_string()));
+        assert!(registries.contains(&"gemfury".to_string()));
     }
 
     #[test]
@@ -0,0 +1,13 @@
+//! Supply chain security for published artifacts
+//!
+//! This module provides the components that describe and attest what is
+//! being released, such as SBOM documents, artifact signatures and SLSA
+//! provenance.
+
+pub mod provenance;
+pub mod sbom;
+pub mod signing;
+
+pub use provenance::{ProvenanceBuilder, ProvenanceReference, ResourceDescriptor};
+pub use sbom::{SbomComponent, SbomDocument, SbomFormat, SbomGenerator, SbomReference};
+pub use signing::{ArtifactSignature, ArtifactSigner, SigningMode};
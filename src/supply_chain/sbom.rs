@@ -0,0 +1,699 @@
+//! SBOM Generator - CycloneDX / SPDX documents for a release
+//!
+//! Components are collected from the project's manifests and lockfiles:
+//! - npm: `package-lock.json` (lockfile v1-v3), falling back to `package.json` dependencies
+//! - Cargo: `Cargo.lock` (registry and git dependencies)
+//! - Python: `poetry.lock` / `uv.lock`, falling back to pinned `requirements.txt` entries
+//!
+//! The document is written before publishing, stored with the release
+//! artifacts and optionally attached to the GitHub Release. Its SHA-256 is
+//! recorded in the publish report.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use package_publisher::supply_chain::{SbomFormat, SbomGenerator};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let sbom = SbomGenerator::new(".")
+//!     .with_subject("my-package", "1.2.0")
+//!     .generate(SbomFormat::CycloneDx)
+//!     .await?;
+//! println!("{} ({} components, sha256 {})", sbom.file_name, sbom.component_count, sbom.sha256);
+//! # Ok(())
+//! # }
+//! ```
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::fs;
+
+const TOOL_NAME: &str = "package-publisher";
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// SBOM document format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    #[default]
+    CycloneDx,
+    /// SPDX 2.3 JSON
+    Spdx,
+}
+
+impl SbomFormat {
+    /// File name of the generated document
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDx => "sbom.cdx.json",
+            SbomFormat::Spdx => "sbom.spdx.json",
+        }
+    }
+}
+
+impl fmt::Display for SbomFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SbomFormat::CycloneDx => write!(f, "cyclonedx"),
+            SbomFormat::Spdx => write!(f, "spdx"),
+        }
+    }
+}
+
+impl FromStr for SbomFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Ok(SbomFormat::CycloneDx),
+            "spdx" => Ok(SbomFormat::Spdx),
+            other => Err(format!(
+                "Unknown SBOM format: {} (expected cyclonedx or spdx)",
+                other
+            )),
+        }
+    }
+}
+
+/// Component hash (algorithm in CycloneDX notation, e.g. "SHA-256")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentHash {
+    pub algorithm: String,
+    /// Lower-case hex digest
+    pub value: String,
+}
+
+/// A dependency listed in the SBOM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SbomComponent {
+    pub name: String,
+    pub version: String,
+    /// Package URL (`pkg:npm/...`, `pkg:cargo/...`, `pkg:pypi/...`)
+    pub purl: String,
+    /// Development-only dependency
+    pub dev: bool,
+    pub hashes: Vec<ComponentHash>,
+}
+
+impl SbomComponent {
+    fn new(ecosystem: &str, name: &str, version: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            purl: purl(ecosystem, name, version),
+            dev: false,
+            hashes: Vec::new(),
+        }
+    }
+}
+
+/// Generated SBOM document
+#[derive(Debug, Clone)]
+pub struct SbomDocument {
+    pub format: SbomFormat,
+    pub file_name: String,
+    /// Pretty-printed JSON
+    pub content: String,
+    /// SHA-256 of `content` (hex)
+    pub sha256: String,
+    pub component_count: usize,
+}
+
+impl SbomDocument {
+    /// Reference recorded in the publish report once the document is stored at `path`
+    pub fn reference(&self, path: &Path) -> SbomReference {
+        SbomReference {
+            format: self.format,
+            path: path.display().to_string(),
+            sha256: self.sha256.clone(),
+            components: self.component_count,
+        }
+    }
+}
+
+/// SBOM recorded in a publish report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SbomReference {
+    pub format: SbomFormat,
+    /// Stored document
+    pub path: String,
+    pub sha256: String,
+    /// Number of dependency components
+    pub components: usize,
+}
+
+/// Generates SBOM documents from project manifests and lockfiles
+pub struct SbomGenerator {
+    project_path: PathBuf,
+    subject: Option<(String, String)>,
+}
+
+impl SbomGenerator {
+    /// Create a generator for `project_path`
+    pub fn new(project_path: impl AsRef<Path>) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            subject: None,
+        }
+    }
+
+    /// Name and version of the package being released (default: from the manifest)
+    pub fn with_subject(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.subject = Some((name.into(), version.into()));
+        self
+    }
+
+    /// Generate the SBOM document
+    pub async fn generate(&self, format: SbomFormat) -> anyhow::Result<SbomDocument> {
+        let (ecosystem, name, version) = self.root_package().await?;
+        let components = self.collect_components().await?;
+        let root = SbomComponent::new(&ecosystem, &name, &version);
+
+        let document = match format {
+            SbomFormat::CycloneDx => cyclonedx_document(&root, &components),
+            SbomFormat::Spdx => spdx_document(&root, &components),
+        };
+        let content = serde_json::to_string_pretty(&document)?;
+        let sha256 = hex::encode(Sha256::digest(content.as_bytes()));
+
+        Ok(SbomDocument {
+            format,
+            file_name: format.file_name().to_string(),
+            content,
+            sha256,
+            component_count: components.len(),
+        })
+    }
+
+    /// Ecosystem, name and version of the package described by the SBOM
+    async fn root_package(&self) -> anyhow::Result<(String, String, String)> {
+        let ecosystem = self.ecosystem().await;
+        if let Some((ref name, ref version)) = self.subject {
+            return Ok((ecosystem, name.clone(), version.clone()));
+        }
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join("package.json")).await {
+            let manifest: serde_json::Value = serde_json::from_str(&content)?;
+            if let (Some(name), Some(version)) =
+                (manifest["name"].as_str(), manifest["version"].as_str())
+            {
+                return Ok(("npm".to_string(), name.to_string(), version.to_string()));
+            }
+        }
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join("Cargo.toml")).await {
+            let manifest: toml::Table = toml::from_str(&content)?;
+            let package = manifest.get("package");
+            if let (Some(name), Some(version)) = (
+                package.and_then(|p| p.get("name")).and_then(|v| v.as_str()),
+                package
+                    .and_then(|p| p.get("version"))
+                    .and_then(|v| v.as_str()),
+            ) {
+                return Ok(("cargo".to_string(), name.to_string(), version.to_string()));
+            }
+        }
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join("pyproject.toml")).await {
+            let manifest: toml::Table = toml::from_str(&content)?;
+            let project = manifest
+                .get("project")
+                .or_else(|| manifest.get("tool").and_then(|t| t.get("poetry")));
+            if let (Some(name), Some(version)) = (
+                project.and_then(|p| p.get("name")).and_then(|v| v.as_str()),
+                project
+                    .and_then(|p| p.get("version"))
+                    .and_then(|v| v.as_str()),
+            ) {
+                return Ok(("pypi".to_string(), name.to_string(), version.to_string()));
+            }
+        }
+
+        anyhow::bail!("Cannot determine package name and version for the SBOM")
+    }
+
+    /// purl type of the root package
+    async fn ecosystem(&self) -> String {
+        for (manifest, ecosystem) in [
+            ("package.json", "npm"),
+            ("Cargo.toml", "cargo"),
+            ("pyproject.toml", "pypi"),
+        ] {
+            if fs::metadata(self.project_path.join(manifest)).await.is_ok() {
+                return ecosystem.to_string();
+            }
+        }
+        "generic".to_string()
+    }
+
+    /// Dependencies from all lockfiles, de-duplicated by purl
+    pub async fn collect_components(&self) -> anyhow::Result<Vec<SbomComponent>> {
+        let mut components = BTreeMap::new();
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join("package-lock.json")).await {
+            for component in parse_package_lock(&content)? {
+                components.insert(component.purl.clone(), component);
+            }
+        } else if let Ok(content) = fs::read_to_string(self.project_path.join("package.json")).await
+        {
+            for component in parse_package_json(&content)? {
+                components.insert(component.purl.clone(), component);
+            }
+        }
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join("Cargo.lock")).await {
+            for component in parse_cargo_lock(&content)? {
+                components.insert(component.purl.clone(), component);
+            }
+        }
+
+        let mut python_locked = false;
+        for lockfile in ["poetry.lock", "uv.lock"] {
+            if let Ok(content) = fs::read_to_string(self.project_path.join(lockfile)).await {
+                for component in parse_python_lock(&content)? {
+                    components.insert(component.purl.clone(), component);
+                }
+                python_locked = true;
+            }
+        }
+        if !python_locked
+            && let Ok(content) =
+                fs::read_to_string(self.project_path.join("requirements.txt")).await
+        {
+            for component in parse_requirements(&content) {
+                components.insert(component.purl.clone(), component);
+            }
+        }
+
+        Ok(components.into_values().collect())
+    }
+}
+
+/// Package URL for a component
+pub(crate) fn purl(ecosystem: &str, name: &str, version: &str) -> String {
+    let name = match ecosystem {
+        // Scoped npm packages: @scope/name -> %40scope/name
+        "npm" => name.replacen('@', "%40", 1),
+        "pypi" => name.to_lowercase().replace('_', "-"),
+        _ => name.to_string(),
+    };
+    format!("pkg:{}/{}@{}", ecosystem, name, version)
+}
+
+/// `package-lock.json` (v2/v3 `packages`, v1 `dependencies`)
+pub fn parse_package_lock(content: &str) -> anyhow::Result<Vec<SbomComponent>> {
+    let lock: serde_json::Value = serde_json::from_str(content)?;
+    let mut components = Vec::new();
+
+    if let Some(packages) = lock["packages"].as_object() {
+        for (path, info) in packages {
+            // "" is the root project; links are workspace packages
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if info["link"].as_bool() == Some(true) {
+                continue;
+            }
+            let Some(version) = info["version"].as_str() else {
+                continue;
+            };
+            let mut component = SbomComponent::new("npm", name, version);
+            component.dev = info["dev"].as_bool().unwrap_or(false);
+            if let Some(hash) = info["integrity"].as_str().and_then(sri_hash) {
+                component.hashes.push(hash);
+            }
+            components.push(component);
+        }
+    } else if let Some(dependencies) = lock["dependencies"].as_object() {
+        for (name, info) in dependencies {
+            let Some(version) = info["version"].as_str() else {
+                continue;
+            };
+            let mut component = SbomComponent::new("npm", name, version);
+            component.dev = info["dev"].as_bool().unwrap_or(false);
+            if let Some(hash) = info["integrity"].as_str().and_then(sri_hash) {
+                component.hashes.push(hash);
+            }
+            components.push(component);
+        }
+    }
+
+    Ok(components)
+}
+
+/// `package.json` dependencies (version ranges, when no lockfile exists)
+fn parse_package_json(content: &str) -> anyhow::Result<Vec<SbomComponent>> {
+    let manifest: serde_json::Value = serde_json::from_str(content)?;
+    let mut components = Vec::new();
+
+    for (section, dev) in [("dependencies", false), ("devDependencies", true)] {
+        if let Some(dependencies) = manifest[section].as_object() {
+            for (name, range) in dependencies {
+                let Some(range) = range.as_str() else {
+                    continue;
+                };
+                let version = range.trim_start_matches(['^', '~', '=']);
+                let mut component = SbomComponent::new("npm", name, version);
+                component.dev = dev;
+                components.push(component);
+            }
+        }
+    }
+
+    Ok(components)
+}
+
+/// `Cargo.lock` packages with a source (local workspace members are skipped)
+pub fn parse_cargo_lock(content: &str) -> anyhow::Result<Vec<SbomComponent>> {
+    let lock: toml::Table = toml::from_str(content)?;
+    let mut components = Vec::new();
+
+    for package in lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if package.get("source").is_none() {
+            continue;
+        }
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|v| v.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        let mut component = SbomComponent::new("cargo", name, version);
+        if let Some(checksum) = package.get("checksum").and_then(|v| v.as_str()) {
+            component.hashes.push(ComponentHash {
+                algorithm: "SHA-256".to_string(),
+                value: checksum.to_string(),
+            });
+        }
+        components.push(component);
+    }
+
+    Ok(components)
+}
+
+/// `poetry.lock` / `uv.lock` packages
+fn parse_python_lock(content: &str) -> anyhow::Result<Vec<SbomComponent>> {
+    let lock: toml::Table = toml::from_str(content)?;
+    let mut components = Vec::new();
+
+    for package in lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        // uv.lock lists the project itself as an editable/virtual source
+        if package
+            .get("source")
+            .and_then(|s| s.as_table())
+            .is_some_and(|s| s.contains_key("editable") || s.contains_key("virtual"))
+        {
+            continue;
+        }
+        let (Some(name), Some(version)) = (
+            package.get("name").and_then(|v| v.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        let mut component = SbomComponent::new("pypi", name, version);
+        component.dev = package.get("category").and_then(|v| v.as_str()) == Some("dev");
+        components.push(component);
+    }
+
+    Ok(components)
+}
+
+/// Pinned (`name==version`) entries of a requirements file
+fn parse_requirements(content: &str) -> Vec<SbomComponent> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let (name, version) = line.split_once("==")?;
+            let name = name.split('[').next()?.trim();
+            let version = version.split([';', ' ']).next()?.trim();
+            (!name.is_empty() && !version.is_empty())
+                .then(|| SbomComponent::new("pypi", name, version))
+        })
+        .collect()
+}
+
+/// Convert an SRI string (`sha512-<base64>`) into a hex hash
+fn sri_hash(integrity: &str) -> Option<ComponentHash> {
+    let (algorithm, digest) = integrity.split_whitespace().next()?.split_once('-')?;
+    let algorithm = match algorithm {
+        "sha1" => "SHA-1",
+        "sha256" => "SHA-256",
+        "sha384" => "SHA-384",
+        "sha512" => "SHA-512",
+        _ => return None,
+    };
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(digest)
+        .ok()?;
+    Some(ComponentHash {
+        algorithm: algorithm.to_string(),
+        value: hex::encode(bytes),
+    })
+}
+
+fn cyclonedx_component(component: &SbomComponent) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "type": "library",
+        "bom-ref": component.purl,
+        "name": component.name,
+        "version": component.version,
+        "purl": component.purl,
+        "scope": if component.dev { "optional" } else { "required" },
+    });
+    if !component.hashes.is_empty() {
+        value["hashes"] = component
+            .hashes
+            .iter()
+            .map(|h| serde_json::json!({ "alg": h.algorithm, "content": h.value }))
+            .collect();
+    }
+    value
+}
+
+/// CycloneDX 1.5 JSON document
+fn cyclonedx_document(root: &SbomComponent, components: &[SbomComponent]) -> serde_json::Value {
+    let mut root_component = cyclonedx_component(root);
+    if let Some(object) = root_component.as_object_mut() {
+        object.remove("scope");
+    }
+
+    serde_json::json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        "version": 1,
+        "metadata": {
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "tools": {
+                "components": [{ "type": "application", "name": TOOL_NAME, "version": TOOL_VERSION }],
+            },
+            "component": root_component,
+        },
+        "components": components.iter().map(cyclonedx_component).collect::<Vec<_>>(),
+        "dependencies": [{
+            "ref": root.purl,
+            "dependsOn": components.iter().map(|c| c.purl.clone()).collect::<Vec<_>>(),
+        }],
+    })
+}
+
+/// SPDX identifier for a component (letters, digits, `.` and `-` only)
+fn spdx_id(component: &SbomComponent) -> String {
+    let id: String = format!("{}-{}", component.name, component.version)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-Package-{}", id)
+}
+
+fn spdx_package(component: &SbomComponent) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "SPDXID": spdx_id(component),
+        "name": component.name,
+        "versionInfo": component.version,
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "externalRefs": [{
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": component.purl,
+        }],
+    });
+    if !component.hashes.is_empty() {
+        value["checksums"] = component
+            .hashes
+            .iter()
+            .map(|h| {
+                serde_json::json!({
+                    "algorithm": h.algorithm.replace('-', ""),
+                    "checksumValue": h.value,
+                })
+            })
+            .collect();
+    }
+    value
+}
+
+/// SPDX 2.3 JSON document
+fn spdx_document(root: &SbomComponent, components: &[SbomComponent]) -> serde_json::Value {
+    let root_id = spdx_id(root);
+    let mut packages = vec![spdx_package(root)];
+    packages.extend(components.iter().map(spdx_package));
+
+    let mut relationships = vec![serde_json::json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": root_id,
+    })];
+    // DEV_DEPENDENCY_OF points from the dependency to the package
+    relationships.extend(components.iter().map(|c| {
+        if c.dev {
+            serde_json::json!({
+                "spdxElementId": spdx_id(c),
+                "relationshipType": "DEV_DEPENDENCY_OF",
+                "relatedSpdxElement": root_id,
+            })
+        } else {
+            serde_json::json!({
+                "spdxElementId": root_id,
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": spdx_id(c),
+            })
+        }
+    }));
+
+    serde_json::json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": format!("{}-{}", root.name, root.version),
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/{}-{}-{}",
+            root.name.replace('@', "").replace('/', "-"),
+            root.version,
+            uuid::Uuid::new_v4()
+        ),
+        "creationInfo": {
+            "created": chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "creators": [format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION)],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_package_lock_v3() {
+        let lock = r#"{
+            "name": "app", "version": "1.0.0", "lockfileVersion": 3,
+            "packages": {
+                "": { "name": "app", "version": "1.0.0" },
+                "node_modules/@scope/util": { "version": "2.1.0", "integrity": "sha512-AAAA" },
+                "node_modules/left-pad": { "version": "1.3.0", "dev": true },
+                "node_modules/left-pad/node_modules/tiny": { "version": "0.0.1" },
+                "packages/local": { "version": "0.1.0" }
+            }
+        }"#;
+        let components = parse_package_lock(lock).unwrap();
+        let purls: Vec<_> = components.iter().map(|c| c.purl.as_str()).collect();
+        assert_eq!(
+            purls,
+            vec![
+                "pkg:npm/%40scope/util@2.1.0",
+                "pkg:npm/left-pad@1.3.0",
+                "pkg:npm/tiny@0.0.1"
+            ]
+        );
+        assert!(components[1].dev);
+        assert_eq!(components[0].hashes[0].algorithm, "SHA-512");
+        assert_eq!(components[0].hashes[0].value, "000000");
+    }
+
+    #[test]
+    fn test_parse_cargo_lock_and_requirements() {
+        let lock = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+
+[[package]]
+name = "serde"
+version = "1.0.228"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc123"
+"#;
+        let components = parse_cargo_lock(lock).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].purl, "pkg:cargo/serde@1.0.228");
+        assert_eq!(components[0].hashes[0].value, "abc123");
+
+        let requirements =
+            parse_requirements("Requests[socks]==2.32.3 ; python_version>'3'\nflask>=3\n# x==1\n");
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(requirements[0].purl, "pkg:pypi/requests@2.32.3");
+    }
+
+    #[tokio::test]
+    async fn test_generate_cyclonedx_and_spdx() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "app", "version": "1.2.0", "dependencies": {"left-pad": "^1.3.0"}}"#,
+        )
+        .unwrap();
+
+        let generator = SbomGenerator::new(temp_dir.path());
+        let cdx = generator.generate(SbomFormat::CycloneDx).await.unwrap();
+        assert_eq!(cdx.file_name, "sbom.cdx.json");
+        assert_eq!(cdx.component_count, 1);
+        assert_eq!(
+            cdx.sha256,
+            hex::encode(Sha256::digest(cdx.content.as_bytes()))
+        );
+        let json: serde_json::Value = serde_json::from_str(&cdx.content).unwrap();
+        assert_eq!(json["metadata"]["component"]["purl"], "pkg:npm/app@1.2.0");
+        assert_eq!(json["components"][0]["purl"], "pkg:npm/left-pad@1.3.0");
+
+        let spdx = generator
+            .with_subject("app", "1.2.1")
+            .generate(SbomFormat::Spdx)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&spdx.content).unwrap();
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["packages"][0]["versionInfo"], "1.2.1");
+        assert_eq!(json["relationships"][1]["relationshipType"], "DEPENDS_ON");
+    }
+}
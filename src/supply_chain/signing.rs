@@ -0,0 +1,294 @@
+//! Artifact Signer - Sigstore / cosign signatures for release artifacts
+//!
+//! Each artifact is signed with `cosign sign-blob`, producing a Sigstore
+//! bundle (`<artifact>.sigstore.json`) that holds the signature, the signing
+//! certificate and the transparency log entry. Without a key, keyless
+//! signing is used and the identity comes from the CI OIDC token (or the
+//! browser flow when run locally).
+//!
+//! When an SBOM was generated it is attested for every artifact with
+//! `cosign attest-blob` (`<artifact>.att.sigstore.json`).
+//!
+//! Unless artifacts are configured, the package is built the way the
+//! registry builds it:
+//! - npm: `npm pack`
+//! - crates.io: `cargo package` (`target/package/<name>-<version>.crate`)
+//! - PyPI: `python -m build` (wheel and sdist)
+
+use crate::plugins::hosted_package::release_artifacts;
+use crate::security::command_executor::CommandLimits;
+use crate::supply_chain::sbom::{SbomFormat, SbomReference};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+/// How artifacts are signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningMode {
+    /// Sigstore keyless (Fulcio certificate bound to an OIDC identity)
+    Keyless,
+    /// cosign key pair or KMS key
+    Key,
+}
+
+impl fmt::Display for SigningMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningMode::Keyless => write!(f, "keyless"),
+            SigningMode::Key => write!(f, "key"),
+        }
+    }
+}
+
+/// Signature recorded in the publish report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSignature {
+    /// Signed artifact
+    pub artifact: String,
+    /// SHA-256 hex digest of the artifact
+    pub sha256: String,
+    pub mode: SigningMode,
+    /// Sigstore bundle with the signature
+    pub bundle: String,
+    /// Sigstore bundle with the SBOM attestation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<String>,
+}
+
+/// Signs artifacts with cosign, writing bundles to an output directory
+pub struct ArtifactSigner {
+    project_path: PathBuf,
+    output_dir: PathBuf,
+    key: Option<String>,
+    limits: CommandLimits,
+}
+
+impl ArtifactSigner {
+    /// Create a signer writing bundles (and built packages) to `output_dir`
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(project_path: P, output_dir: Q) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            output_dir: output_dir.as_ref().to_path_buf(),
+            key: None,
+            limits: CommandLimits::default(),
+        }
+    }
+
+    /// Sign with a cosign key reference instead of keyless signing
+    pub fn with_key(mut self, key: Option<String>) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Build packages with the sandbox and timeout of registry commands
+    pub fn with_command_limits(mut self, limits: CommandLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn mode(&self) -> SigningMode {
+        match self.key {
+            Some(_) => SigningMode::Key,
+            None => SigningMode::Keyless,
+        }
+    }
+
+    /// Resolve configured artifact patterns, or build the registry's package
+    pub async fn collect_artifacts(
+        &self,
+        patterns: Option<&[String]>,
+        registry: &str,
+        name: &str,
+        version: &str,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        release_artifacts(
+            &self.project_path,
+            &self.output_dir,
+            patterns,
+            registry,
+            name,
+            version,
+            &self.limits,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("No artifacts to sign: {} (set signing.artifacts)", e))
+    }
+
+    /// Sign an artifact, attesting the SBOM when given
+    pub async fn sign(
+        &self,
+        artifact: &Path,
+        sbom: Option<&SbomReference>,
+    ) -> anyhow::Result<ArtifactSignature> {
+        fs::create_dir_all(&self.output_dir).await?;
+        let file_name = artifact
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Invalid artifact path: {}", artifact.display()))?;
+        let content = fs::read(artifact).await?;
+
+        let bundle = self.output_dir.join(format!("{}.sigstore.json", file_name));
+        self.run("cosign", &to_str_args(&self.sign_args(artifact, &bundle)))
+            .await?;
+
+        let attestation = match sbom {
+            Some(sbom) => {
+                let path = self
+                    .output_dir
+                    .join(format!("{}.att.sigstore.json", file_name));
+                self.run(
+                    "cosign",
+                    &to_str_args(&self.attest_args(artifact, sbom, &path)),
+                )
+                .await?;
+                Some(path.display().to_string())
+            }
+            None => None,
+        };
+
+        Ok(ArtifactSignature {
+            artifact: artifact.display().to_string(),
+            sha256: hex::encode(Sha256::digest(&content)),
+            mode: self.mode(),
+            bundle: bundle.display().to_string(),
+            attestation,
+        })
+    }
+
+    fn sign_args(&self, artifact: &Path, bundle: &Path) -> Vec<String> {
+        let mut args = vec![
+            "sign-blob".to_string(),
+            "--yes".to_string(),
+            "--bundle".to_string(),
+            bundle.display().to_string(),
+        ];
+        if let Some(ref key) = self.key {
+            args.extend(["--key".to_string(), key.clone()]);
+        }
+        args.push(artifact.display().to_string());
+        args
+    }
+
+    fn attest_args(&self, artifact: &Path, sbom: &SbomReference, bundle: &Path) -> Vec<String> {
+        let predicate_type = match sbom.format {
+            SbomFormat::CycloneDx => "cyclonedx",
+            SbomFormat::Spdx => "spdxjson",
+        };
+        let mut args = vec![
+            "attest-blob".to_string(),
+            "--yes".to_string(),
+            "--predicate".to_string(),
+            sbom.path.clone(),
+            "--type".to_string(),
+            predicate_type.to_string(),
+            "--bundle".to_string(),
+            bundle.display().to_string(),
+        ];
+        if let Some(ref key) = self.key {
+            args.extend(["--key".to_string(), key.clone()]);
+        }
+        args.push(artifact.display().to_string());
+        args
+    }
+
+    async fn run(&self, program: &str, args: &[&str]) -> anyhow::Result<String> {
+        let output = Command::new(program)
+            .args(args)
+            .current_dir(&self.project_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} {} failed: {}",
+                program,
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+fn to_str_args(args: &[String]) -> Vec<&str> {
+    args.iter().map(String::as_str).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_attest_args() {
+        let signer = ArtifactSigner::new(".", "out");
+        assert_eq!(signer.mode(), SigningMode::Keyless);
+        assert_eq!(
+            signer.sign_args(Path::new("pkg.tgz"), Path::new("out/pkg.tgz.sigstore.json")),
+            vec![
+                "sign-blob",
+                "--yes",
+                "--bundle",
+                "out/pkg.tgz.sigstore.json",
+                "pkg.tgz"
+            ]
+        );
+
+        let signer = signer.with_key(Some("cosign.key".to_string()));
+        assert_eq!(signer.mode(), SigningMode::Key);
+        let sbom = SbomReference {
+            format: SbomFormat::Spdx,
+            path: "out/sbom.spdx.json".to_string(),
+            sha256: String::new(),
+            components: 0,
+        };
+        let args = signer.attest_args(Path::new("pkg.tgz"), &sbom, Path::new("att.json"));
+        assert_eq!(
+            args,
+            vec![
+                "attest-blob",
+                "--yes",
+                "--predicate",
+                "out/sbom.spdx.json",
+                "--type",
+                "spdxjson",
+                "--bundle",
+                "att.json",
+                "--key",
+                "cosign.key",
+                "pkg.tgz"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_artifacts() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("dist")).unwrap();
+        std::fs::write(temp_dir.path().join("dist/app-1.0.0.whl"), "wheel").unwrap();
+        std::fs::write(temp_dir.path().join("dist/notes.txt"), "notes").unwrap();
+
+        let signer = ArtifactSigner::new(temp_dir.path(), temp_dir.path().join("out"));
+        let artifacts = signer
+            .collect_artifacts(Some(&["dist/*.whl".to_string()]), "pypi", "app", "1.0.0")
+            .await
+            .unwrap();
+        assert_eq!(artifacts, vec![temp_dir.path().join("dist/app-1.0.0.whl")]);
+
+        let err = signer
+            .collect_artifacts(None, "cran", "app", "1.0.0")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("signing.artifacts"));
+    }
+}
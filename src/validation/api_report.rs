@@ -0,0 +1,568 @@
+//! API Report - public API diff for npm TypeScript packages
+//!
+//! Builds a report of the exported declarations reachable from the package's
+//! `types` entry (`.d.ts`), following relative `export * from` and
+//! `export { ... } from` re-exports, in the spirit of an api-extractor report.
+//! The report of the previously published version is built the same way from
+//! its tarball (`npm pack <name>@<version>`), and the two are diffed:
+//! - removed or changed declarations are breaking (major bump; minor for 0.x)
+//! - added declarations need a minor bump (patch for 0.x)
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::api_report::ApiReport;
+//! use package_publisher::validation::BumpLevel;
+//!
+//! let before = ApiReport::from_declarations("export declare function a(x: string): void;");
+//! let after = ApiReport::from_declarations("export declare function a(x: number): void;");
+//! let diff = before.diff(&after);
+//! assert_eq!(diff.changed.len(), 1);
+//! assert_eq!(diff.required_bump(), Some(BumpLevel::Major));
+//! ```
+
+use crate::tr;
+use crate::validation::version_bumper::BumpLevel;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+/// Re-exports followed at most this deep
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// Exported declarations keyed by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiReport {
+    /// Name -> normalized declaration (overloads joined by newlines)
+    pub entries: BTreeMap<String, String>,
+}
+
+/// Difference between two API reports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// (name, before, after)
+    pub changed: Vec<(String, String, String)>,
+}
+
+/// Statement that re-exports from another module
+enum ReExport {
+    All(String),
+    Named(String, Vec<(String, String)>),
+}
+
+impl ApiReport {
+    /// Report from a single declaration file (re-exports are not followed)
+    pub fn from_declarations(content: &str) -> Self {
+        let mut report = Self::default();
+        for statement in split_statements(&strip_comments(content)) {
+            report.add_statement(&statement);
+        }
+        report
+    }
+
+    /// Report from a `.d.ts` entry point, following relative re-exports
+    pub async fn from_entry(entry: &Path) -> anyhow::Result<Self> {
+        let mut visited = HashSet::new();
+        Self::collect(entry, &mut visited, 0).await
+    }
+
+    async fn collect(
+        path: &Path,
+        visited: &mut HashSet<PathBuf>,
+        depth: usize,
+    ) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        visited.insert(path.to_path_buf());
+
+        let mut report = Self::default();
+        for statement in split_statements(&strip_comments(&content)) {
+            let Some(reexport) = parse_reexport(&statement) else {
+                report.add_statement(&statement);
+                continue;
+            };
+            let module = match &reexport {
+                ReExport::All(module) | ReExport::Named(module, _) => module,
+            };
+            if depth >= MAX_REEXPORT_DEPTH || !module.starts_with('.') {
+                continue;
+            }
+            let Some(target) = resolve_module(path, module).await else {
+                continue;
+            };
+            if visited.contains(&target) {
+                continue;
+            }
+            let inner = Box::pin(Self::collect(&target, visited, depth + 1)).await?;
+            match reexport {
+                ReExport::All(_) => report.entries.extend(inner.entries),
+                ReExport::Named(_, names) => {
+                    for (name, alias) in names {
+                        if let Some(declaration) = inner.entries.get(&name) {
+                            report.entries.insert(alias, declaration.clone());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn add_statement(&mut self, statement: &str) {
+        static DECLARATION: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+        let regex = DECLARATION.get_or_init(|| {
+            Regex::new(
+                r"^export\s+(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:function|class|interface|type|const|let|var|enum|namespace)\s+([A-Za-z0-9_$]+)",
+            )
+            .unwrap()
+        });
+
+        let normalized = statement
+            .split_whitespace()
+            .filter(|word| *word != "declare")
+            .collect::<Vec<_>>()
+            .join(" ");
+        let Some(name) = regex
+            .captures(statement)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string())
+        else {
+            return;
+        };
+
+        self.entries
+            .entry(name)
+            .and_modify(|existing| {
+                existing.push('\n');
+                existing.push_str(&normalized);
+            })
+            .or_insert(normalized);
+    }
+
+    /// Changes from `self` (previous release) to `current`
+    pub fn diff(&self, current: &ApiReport) -> ApiDiff {
+        let mut diff = ApiDiff::default();
+        for (name, before) in &self.entries {
+            match current.entries.get(name) {
+                None => diff.removed.push(name.clone()),
+                Some(after) if after != before => {
+                    diff.changed
+                        .push((name.clone(), before.clone(), after.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        diff.added = current
+            .entries
+            .keys()
+            .filter(|name| !self.entries.contains_key(*name))
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+impl ApiDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    /// Smallest bump that covers the changes (`None` when the API is unchanged)
+    pub fn required_bump(&self) -> Option<BumpLevel> {
+        if self.is_breaking() {
+            Some(BumpLevel::Major)
+        } else if !self.added.is_empty() {
+            Some(BumpLevel::Minor)
+        } else {
+            None
+        }
+    }
+
+    /// Problem when the version bump from `previous` to `current` is too small
+    pub fn check_bump(&self, previous: &str, current: &str) -> Option<String> {
+        let required = self.required_bump()?;
+        let previous = semver::Version::parse(previous).ok()?;
+        let current = semver::Version::parse(current).ok()?;
+
+        // 0.x: breaking changes bump the minor version, additions the patch version
+        let (breaking_ok, additive_ok) = if previous.major == 0 {
+            (
+                current.major > 0 || current.minor > previous.minor,
+                current > previous,
+            )
+        } else {
+            (
+                current.major > previous.major,
+                current.major > previous.major || current.minor > previous.minor,
+            )
+        };
+
+        match required {
+            BumpLevel::Major if !breaking_ok => Some(tr!(
+                "api_report.breaking",
+                previous = previous,
+                current = current,
+                removed = self.removed.len(),
+                changed = self.changed.len()
+            )),
+            BumpLevel::Minor if !additive_ok => Some(tr!(
+                "api_report.additive",
+                previous = previous,
+                current = current,
+                added = self.added.len()
+            )),
+            _ => None,
+        }
+    }
+
+    /// Markdown diff for reports
+    pub fn to_markdown(&self, previous: &str, current: &str) -> String {
+        let mut out = format!("## API diff {} → {}\n\n", previous, current);
+        if self.is_empty() {
+            out.push_str("No public API changes.\n");
+            return out;
+        }
+
+        if !self.removed.is_empty() {
+            out.push_str("### Removed\n\n");
+            for name in &self.removed {
+                out.push_str(&format!("- `{}`\n", name));
+            }
+            out.push('\n');
+        }
+        if !self.changed.is_empty() {
+            out.push_str("### Changed\n\n```diff\n");
+            for (_, before, after) in &self.changed {
+                for line in before.lines() {
+                    out.push_str(&format!("- {}\n", line));
+                }
+                for line in after.lines() {
+                    out.push_str(&format!("+ {}\n", line));
+                }
+            }
+            out.push_str("```\n\n");
+        }
+        if !self.added.is_empty() {
+            out.push_str("### Added\n\n");
+            for name in &self.added {
+                out.push_str(&format!("- `{}`\n", name));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Remove `//` and `/* */` comments (string literals are left alone)
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+                out.push(' ');
+            }
+            ('"' | '\'' | '`', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Split into top-level statements (`;` or a closing `}` at depth 0)
+fn split_statements(content: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in content.chars() {
+        current.push(c);
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                if c == '}' && depth == 0 && is_block_declaration(&current) {
+                    statements.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            ';' if depth == 0 => {
+                statements.push(current.trim().trim_end_matches(';').trim().to_string());
+                current.clear();
+            }
+            _ => {}
+        }
+    }
+    if !current.trim().is_empty() {
+        statements.push(current.trim().to_string());
+    }
+    statements.retain(|s| !s.is_empty());
+    statements
+}
+
+/// Class/interface/enum/namespace bodies end without a semicolon;
+/// `type X = { ... };` and `function f(): { ... };` continue until one
+fn is_block_declaration(statement: &str) -> bool {
+    let header = statement.split('{').next().unwrap_or_default();
+    !header.contains('=')
+        && !header.contains('(')
+        && header.split_whitespace().any(|word| {
+            matches!(
+                word,
+                "class" | "interface" | "enum" | "namespace" | "module" | "global"
+            )
+        })
+}
+
+fn parse_reexport(statement: &str) -> Option<ReExport> {
+    let statement = statement.trim();
+    let rest = statement
+        .strip_prefix("export type ")
+        .or_else(|| statement.strip_prefix("export "))?;
+    let (spec, module) = rest.split_once(" from ")?;
+    let module = module.trim().trim_matches(['"', '\'']).to_string();
+    let spec = spec.trim();
+
+    if spec == "*" {
+        return Some(ReExport::All(module));
+    }
+    let names = spec.strip_prefix('{')?.strip_suffix('}')?;
+    let names = names
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|n| {
+            let n = n.trim_start_matches("type ").trim();
+            match n.split_once(" as ") {
+                Some((name, alias)) => (name.trim().to_string(), alias.trim().to_string()),
+                None => (n.to_string(), n.to_string()),
+            }
+        })
+        .collect();
+    Some(ReExport::Named(module, names))
+}
+
+/// `./foo` relative to `from` -> `foo.d.ts` / `foo/index.d.ts`
+async fn resolve_module(from: &Path, module: &str) -> Option<PathBuf> {
+    let base = from.parent()?.join(module);
+    let stem = base.to_string_lossy().trim_end_matches(".js").to_string();
+    for candidate in [
+        format!("{}.d.ts", stem),
+        format!("{}.d.mts", stem),
+        format!("{}/index.d.ts", stem),
+        stem.clone(),
+    ] {
+        let path = PathBuf::from(candidate);
+        if fs::metadata(&path).await.is_ok_and(|m| m.is_file()) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// `.d.ts` entry of a package directory (`types` / `typings`, else `index.d.ts`)
+pub async fn types_entry(package_dir: &Path) -> anyhow::Result<PathBuf> {
+    let content = fs::read_to_string(package_dir.join("package.json")).await?;
+    let json: serde_json::Value = serde_json::from_str(&content)?;
+    let entry = json
+        .get("types")
+        .or_else(|| json.get("typings"))
+        .and_then(|t| t.as_str())
+        .unwrap_or("index.d.ts");
+    let path = package_dir.join(entry);
+    if fs::metadata(&path).await.is_err() {
+        anyhow::bail!("{}", tr!("api_report.types_not_found", entry = entry));
+    }
+    Ok(path)
+}
+
+/// Report of a published version, built from its tarball
+pub async fn published_report(
+    project_path: &Path,
+    name: &str,
+    version: &str,
+    work_dir: &Path,
+) -> anyhow::Result<ApiReport> {
+    fs::create_dir_all(work_dir).await?;
+    let spec = format!("{}@{}", name, version);
+    let stdout = run(
+        project_path,
+        "npm",
+        &[
+            "pack",
+            &spec,
+            "--pack-destination",
+            &work_dir.display().to_string(),
+        ],
+    )
+    .await?;
+    let tarball = stdout
+        .lines()
+        .rev()
+        .find(|l| l.trim().ends_with(".tgz"))
+        .ok_or_else(|| anyhow::anyhow!("npm pack produced no tarball for {}", spec))?;
+
+    let extract_dir = work_dir.join(version);
+    fs::create_dir_all(&extract_dir).await?;
+    run(
+        work_dir,
+        "tar",
+        &[
+            "-xzf",
+            tarball.trim(),
+            "-C",
+            &extract_dir.display().to_string(),
+        ],
+    )
+    .await?;
+
+    let entry = types_entry(&extract_dir.join("package")).await?;
+    ApiReport::from_entry(&entry).await
+}
+
+async fn run(dir: &Path, program: &str, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_from_declarations() {
+        let report = ApiReport::from_declarations(
+            r#"
+/** Adds numbers */
+export declare function add(a: number, b: number): number;
+export declare function add(a: bigint, b: bigint): bigint;
+export interface Options {
+    // verbose output
+    verbose?: boolean;
+}
+export type Mode = { kind: "fast" } | { kind: "slow" };
+declare const internal: number;
+export declare class Client {
+    constructor(options: Options);
+}
+"#,
+        );
+        assert_eq!(
+            report.entries.keys().collect::<Vec<_>>(),
+            vec!["Client", "Mode", "Options", "add"]
+        );
+        assert_eq!(report.entries["add"].lines().count(), 2);
+        assert_eq!(
+            report.entries["Options"],
+            "export interface Options { verbose?: boolean; }"
+        );
+    }
+
+    #[test]
+    fn test_diff_and_bump() {
+        let before = ApiReport::from_declarations(
+            "export declare function a(): void;\nexport declare function b(): void;",
+        );
+        let after = ApiReport::from_declarations(
+            "export declare function a(): void;\nexport declare function c(): void;",
+        );
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed, vec!["b"]);
+        assert_eq!(diff.added, vec!["c"]);
+        assert!(diff.check_bump("1.2.0", "1.3.0").is_some());
+        assert!(diff.check_bump("1.2.0", "2.0.0").is_none());
+        assert!(diff.check_bump("0.2.0", "0.3.0").is_none());
+
+        let additive =
+            ApiReport::from_declarations("export declare function a(): void;").diff(&after);
+        assert_eq!(additive.required_bump(), Some(BumpLevel::Minor));
+        assert!(additive.check_bump("1.2.0", "1.2.1").is_some());
+        assert!(additive.to_markdown("1.2.0", "1.3.0").contains("### Added"));
+    }
+
+    #[tokio::test]
+    async fn test_report_follows_reexports() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("lib")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("index.d.ts"),
+            "export * from './lib/math';\nexport { Client as HttpClient } from './lib/client.js';\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("lib/math.d.ts"),
+            "export declare function add(a: number, b: number): number;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("lib/client.d.ts"),
+            "export declare class Client {\n    get(url: string): Promise<string>;\n}\nexport declare const VERSION: string;\n",
+        )
+        .unwrap();
+
+        let report = ApiReport::from_entry(&temp_dir.path().join("index.d.ts"))
+            .await
+            .unwrap();
+        assert_eq!(
+            report.entries.keys().collect::<Vec<_>>(),
+            vec!["HttpClient", "add"]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Bundle Size - package size regression gate for npm packages
+//!
+//! Measures the tarball with `npm pack --dry-run --json` (packed size,
+//! unpacked size and file count) and compares it against:
+//! - absolute limits (`maxPackedSize`, `maxUnpackedSize`)
+//! - growth over the previous release (`maxGrowthPercent`)
+//!
+//! The previous size comes from the analytics history; packages published
+//! before sizes were tracked fall back to the registry's `dist.unpackedSize`.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::bundle_size::{BundleSizeGate, PackageSize};
+//!
+//! let gate = BundleSizeGate {
+//!     max_growth_percent: Some(10.0),
+//!     ..Default::default()
+//! };
+//! let previous = PackageSize { packed: 10_000, unpacked: 40_000, files: 12 };
+//! let current = PackageSize { packed: 15_000, unpacked: 41_000, files: 12 };
+//! assert_eq!(gate.check(&current, Some(&previous)).len(), 1);
+//! ```
+
+use crate::core::config::BundleSizeConfig;
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Size of a packed npm package
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageSize {
+    /// Tarball size in bytes (0 when unknown)
+    pub packed: u64,
+    /// Unpacked size in bytes
+    pub unpacked: u64,
+    /// Number of files in the tarball
+    pub files: usize,
+}
+
+/// Limits checked by the gate
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BundleSizeGate {
+    pub max_packed_size: Option<u64>,
+    pub max_unpacked_size: Option<u64>,
+    pub max_growth_percent: Option<f64>,
+}
+
+impl BundleSizeGate {
+    /// Gate for `registries.npm.bundleSize`
+    pub fn from_config(config: &BundleSizeConfig) -> Self {
+        Self {
+            max_packed_size: config.max_packed_size,
+            max_unpacked_size: config.max_unpacked_size,
+            max_growth_percent: config.max_growth_percent,
+        }
+    }
+
+    /// Violated limits (empty when the package is within all of them)
+    pub fn check(&self, current: &PackageSize, previous: Option<&PackageSize>) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(max) = self.max_packed_size
+            && current.packed > max
+        {
+            violations.push(tr!(
+                "size.packed_over_limit",
+                size = format_size(current.packed),
+                max = format_size(max)
+            ));
+        }
+        if let Some(max) = self.max_unpacked_size
+            && current.unpacked > max
+        {
+            violations.push(tr!(
+                "size.unpacked_over_limit",
+                size = format_size(current.unpacked),
+                max = format_size(max)
+            ));
+        }
+
+        if let (Some(max_growth), Some(previous)) = (self.max_growth_percent, previous) {
+            // Packed size when both releases have it, else unpacked (registry fallback)
+            let (label, before, after) = if previous.packed > 0 && current.packed > 0 {
+                (tr!("size.packed"), previous.packed, current.packed)
+            } else {
+                (tr!("size.unpacked"), previous.unpacked, current.unpacked)
+            };
+            if let Some(growth) = growth_percent(before, after)
+                && growth > max_growth
+            {
+                violations.push(tr!(
+                    "size.growth",
+                    label = label,
+                    growth = format!("{:.1}", growth),
+                    before = format_size(before),
+                    after = format_size(after),
+                    max = max_growth
+                ));
+            }
+        }
+
+        violations
+    }
+}
+
+/// Growth from `before` to `after` in percent (`None` without a baseline)
+pub fn growth_percent(before: u64, after: u64) -> Option<f64> {
+    (before > 0).then(|| (after as f64 - before as f64) / before as f64 * 100.0)
+}
+
+/// Human readable size (`12.3 kB`), decimal units like npm
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1_000_000 => format!("{:.1} MB", b as f64 / 1_000_000.0),
+        b if b >= 1_000 => format!("{:.1} kB", b as f64 / 1_000.0),
+        b => format!("{} B", b),
+    }
+}
+
+/// Size from `npm pack --dry-run --json` output
+pub fn parse_npm_pack_json(output: &str) -> anyhow::Result<PackageSize> {
+    // npm prints lifecycle script output before the JSON array
+    let start = output
+        .find('[')
+        .ok_or_else(|| anyhow::anyhow!("npm pack produced no JSON output"))?;
+    let json: serde_json::Value = serde_json::from_str(&output[start..])?;
+    let package = json
+        .get(0)
+        .ok_or_else(|| anyhow::anyhow!("npm pack produced an empty result"))?;
+
+    Ok(PackageSize {
+        packed: package.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+        unpacked: package
+            .get("unpackedSize")
+            .and_then(|s| s.as_u64())
+            .unwrap_or(0),
+        files: package
+            .get("entryCount")
+            .and_then(|c| c.as_u64())
+            .or_else(|| {
+                package
+                    .get("files")
+                    .and_then(|f| f.as_array())
+                    .map(|f| f.len() as u64)
+            })
+            .unwrap_or(0) as usize,
+    })
+}
+
+/// Measure the package in `project_path` with `npm pack --dry-run --json`
+pub async fn measure(project_path: &Path) -> anyhow::Result<PackageSize> {
+    let output = Command::new("npm")
+        .args(["pack", "--dry-run", "--json", "--ignore-scripts"])
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "npm pack failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_npm_pack_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Size of the latest published version from the registry (`dist.unpackedSize`)
+///
+/// The registry does not report the tarball size, so `packed` is 0.
+pub async fn published_size(project_path: &Path, package_name: &str) -> Option<PackageSize> {
+    let output = Command::new("npm")
+        .args(["view", package_name, "dist", "--json"])
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let dist: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    Some(PackageSize {
+        packed: 0,
+        unpacked: dist.get("unpackedSize")?.as_u64()?,
+        files: dist.get("fileCount").and_then(|c| c.as_u64()).unwrap_or(0) as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_npm_pack_json() {
+        let output = r#"> demo@1.0.0 prepack
+> tsc
+
+[
+  {
+    "id": "demo@1.0.0",
+    "name": "demo",
+    "version": "1.0.0",
+    "size": 2048,
+    "unpackedSize": 8192,
+    "filename": "demo-1.0.0.tgz",
+    "files": [{ "path": "index.js", "size": 8000, "mode": 420 }],
+    "entryCount": 3
+  }
+]
+"#;
+        let size = parse_npm_pack_json(output).unwrap();
+        assert_eq!(
+            size,
+            PackageSize {
+                packed: 2048,
+                unpacked: 8192,
+                files: 3
+            }
+        );
+        assert!(parse_npm_pack_json("npm ERR! oops").is_err());
+    }
+
+    #[test]
+    fn test_gate_limits_and_growth() {
+        let gate = BundleSizeGate {
+            max_packed_size: Some(100_000),
+            max_unpacked_size: None,
+            max_growth_percent: Some(20.0),
+        };
+        let previous = PackageSize {
+            packed: 50_000,
+            unpacked: 200_000,
+            files: 10,
+        };
+
+        let ok = PackageSize {
+            packed: 55_000,
+            unpacked: 220_000,
+            files: 10,
+        };
+        assert!(gate.check(&ok, Some(&previous)).is_empty());
+
+        let grown = PackageSize {
+            packed: 120_000,
+            unpacked: 500_000,
+            files: 10,
+        };
+        let violations = gate.check(&grown, Some(&previous));
+        assert_eq!(violations.len(), 2);
+        assert!(violations[1].contains("140.0%"));
+
+        // Registry fallback only knows the unpacked size
+        let registry = PackageSize {
+            packed: 0,
+            unpacked: 200_000,
+            files: 0,
+        };
+        let violations = gate.check(&ok, Some(&registry));
+        assert!(violations.is_empty());
+        assert_eq!(growth_percent(0, 10), None);
+        assert_eq!(format_size(1_500_000), "1.5 MB");
+    }
+}
@@ -1,7 +1,8 @@
 //! Dependency Checker - Analyzes package dependencies
 //!
 //! This module provides dependency analysis for package manifests,
-//! including version range checking and basic vulnerability detection.
+//! including version range checking and vulnerability lookups against the
+//! OSV database (see [`osv`](crate::validation::osv)).
 //!
 //! # Example
 //!
@@ -18,8 +19,9 @@
 //! # }
 //! ```
 
+use crate::validation::osv::{Advisory, OsvClient};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::path::Path;
 use tokio::fs;
 
@@ -49,12 +51,48 @@ pub enum IssueSeverity {
     Low,
 }
 
+impl IssueSeverity {
+    fn rank(self) -> u8 {
+        match self {
+            IssueSeverity::Critical => 3,
+            IssueSeverity::High => 2,
+            IssueSeverity::Medium => 1,
+            IssueSeverity::Low => 0,
+        }
+    }
+
+    /// Whether this severity is at or above `threshold`
+    pub fn meets(self, threshold: IssueSeverity) -> bool {
+        self.rank() >= threshold.rank()
+    }
+}
+
+impl std::str::FromStr for IssueSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "critical" => Ok(IssueSeverity::Critical),
+            "high" => Ok(IssueSeverity::High),
+            "medium" | "moderate" => Ok(IssueSeverity::Medium),
+            "low" | "info" => Ok(IssueSeverity::Low),
+            other => Err(format!(
+                "Unknown severity: {} (expected critical, high, medium or low)",
+                other
+            )),
+        }
+    }
+}
+
 /// Issue found in dependencies
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DependencyIssue {
     pub dependency: String,
     pub severity: IssueSeverity,
     pub description: String,
+    /// Advisory details for vulnerabilities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub advisory: Option<Advisory>,
 }
 
 /// Result of dependency check
@@ -67,7 +105,9 @@ pub struct DependencyCheckResult {
 }
 
 /// Checker for package dependencies
-pub struct DependencyChecker;
+pub struct DependencyChecker {
+    osv: OsvClient,
+}
 
 impl Default for DependencyChecker {
     fn default() -> Self {
@@ -86,7 +126,15 @@ impl DependencyChecker {
     /// let checker = DependencyChecker::new();
     /// ```
     pub fn new() -> Self {
-        Self
+        Self {
+            osv: OsvClient::new(),
+        }
+    }
+
+    /// Use a configured OSV client (API URL, cache directory, offline mode)
+    pub fn with_osv_client(mut self, osv: OsvClient) -> Self {
+        self.osv = osv;
+        self
     }
 
     /// Check dependencies in a manifest file
@@ -143,6 +191,7 @@ impl DependencyChecker {
                         dependency: name.clone(),
                         severity: IssueSeverity::Medium,
                         description: "Wildcard version (*) is not recommended".to_string(),
+                        advisory: None,
                     });
                 }
             }
@@ -163,6 +212,7 @@ impl DependencyChecker {
                         dependency: name.clone(),
                         severity: IssueSeverity::Low,
                         description: "Wildcard version (*) in devDependency".to_string(),
+                        advisory: None,
                     });
                 }
             }
@@ -200,6 +250,7 @@ impl DependencyChecker {
                         dependency: name.clone(),
                         severity: IssueSeverity::Medium,
                         description: "Wildcard version (*) is not recommended".to_string(),
+                        advisory: None,
                     });
                 }
             }
@@ -240,36 +291,176 @@ impl DependencyChecker {
         }
     }
 
-    /// Check for known vulnerable patterns (basic implementation)
+    /// Dependencies pinned by the lockfile next to the manifest
     ///
-    /// # Arguments
+    /// Reads `package-lock.json` (v1-v3) or `Cargo.lock` and returns every
+    /// locked registry package, including transitive ones. Returns `None`
+    /// when there is no lockfile.
+    pub async fn locked_dependencies(
+        &self,
+        manifest_path: &Path,
+        manifest_type: ManifestType,
+    ) -> anyhow::Result<Option<Vec<Dependency>>> {
+        let dir = manifest_path.parent().unwrap_or(Path::new("."));
+        let lockfile = match manifest_type {
+            ManifestType::Npm => dir.join("package-lock.json"),
+            ManifestType::Cargo => dir.join("Cargo.lock"),
+        };
+        let Ok(content) = fs::read_to_string(&lockfile).await else {
+            return Ok(None);
+        };
+
+        let dependencies = match manifest_type {
+            ManifestType::Npm => parse_package_lock(&content)?,
+            ManifestType::Cargo => parse_cargo_lock(&content)?,
+        };
+        Ok(Some(dependencies))
+    }
+
+    /// Check dependencies against the OSV vulnerability database
     ///
-    /// * `dependencies` - List of dependencies to check
+    /// Version requirements are resolved to their lowest matching version
+    /// (`^4.17.1` -> `4.17.1`); pass [`locked_dependencies`](Self::locked_dependencies)
+    /// for exact versions. Dependencies without a concrete version (`*`, git
+    /// or path dependencies) are skipped.
     ///
-    /// # Note
+    /// # Arguments
     ///
-    /// This is a simplified implementation. Production use should integrate
-    /// with vulnerability databases like npm audit, cargo audit, or OSV.
-    pub fn check_vulnerabilities(&self, dependencies: &[Dependency]) -> Vec<DependencyIssue> {
-        let mut issues = Vec::new();
+    /// * `dependencies` - List of dependencies to check
+    /// * `manifest_type` - Ecosystem of the dependencies
+    pub async fn check_vulnerabilities(
+        &self,
+        dependencies: &[Dependency],
+        manifest_type: ManifestType,
+    ) -> anyhow::Result<Vec<DependencyIssue>> {
+        let ecosystem = match manifest_type {
+            ManifestType::Npm => "npm",
+            ManifestType::Cargo => "crates.io",
+        };
+        let packages: BTreeSet<(String, String)> = dependencies
+            .iter()
+            .filter_map(|dep| {
+                concrete_version(&dep.version_requirement).map(|v| (dep.name.clone(), v))
+            })
+            .collect();
+        let packages: Vec<(String, String)> = packages.into_iter().collect();
+
+        let findings = self.osv.query(ecosystem, &packages).await?;
+        Ok(findings
+            .into_iter()
+            .map(|finding| {
+                let advisory = finding.advisory;
+                let mut description = format!(
+                    "{} ({}@{}): {}",
+                    advisory.id, finding.package, finding.version, advisory.summary
+                );
+                if !advisory.fixed_versions.is_empty() {
+                    description.push_str(&format!(
+                        " - fixed in {}",
+                        advisory.fixed_versions.join(", ")
+                    ));
+                }
+                DependencyIssue {
+                    dependency: finding.package,
+                    severity: advisory.severity,
+                    description,
+                    advisory: Some(advisory),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Lowest version matching a requirement, padded to `major.minor.patch`
+fn concrete_version(requirement: &str) -> Option<String> {
+    let first = requirement.split("||").next()?.trim();
+    let first = first.split([',', ' ']).next()?.trim();
+    let version = first.trim_start_matches(['^', '~', '=', '>', 'v']).trim();
+    if version.is_empty() || !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
 
-        // Example: Check for known problematic packages (simplified)
-        let known_issues: HashMap<&str, IssueSeverity> = HashMap::from([
-            ("event-stream", IssueSeverity::Critical), // Historical npm issue
-        ]);
-
-        for dep in dependencies {
-            if let Some(&severity) = known_issues.get(dep.name.as_str()) {
-                issues.push(DependencyIssue {
-                    dependency: dep.name.clone(),
-                    severity,
-                    description: "Known vulnerability detected".to_string(),
+    let (core, rest) = match version.find(['-', '+']) {
+        Some(i) => version.split_at(i),
+        None => (version, ""),
+    };
+    let mut parts: Vec<&str> = core
+        .split('.')
+        .take_while(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    Some(format!("{}{}", parts.join("."), rest))
+}
+
+fn parse_package_lock(content: &str) -> anyhow::Result<Vec<Dependency>> {
+    let parsed: serde_json::Value = serde_json::from_str(content)?;
+    let mut dependencies = Vec::new();
+
+    if let Some(packages) = parsed.get("packages").and_then(|v| v.as_object()) {
+        // v2 / v3: "node_modules/a/node_modules/b"
+        for (path, info) in packages {
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+                dependencies.push(Dependency {
+                    name: name.to_string(),
+                    version_requirement: version.to_string(),
+                    dev: info.get("dev").and_then(|d| d.as_bool()).unwrap_or(false),
                 });
             }
         }
-
-        issues
+    } else if let Some(deps) = parsed.get("dependencies").and_then(|v| v.as_object()) {
+        // v1: nested "dependencies"
+        let mut stack: Vec<&serde_json::Map<String, serde_json::Value>> = vec![deps];
+        while let Some(deps) = stack.pop() {
+            for (name, info) in deps {
+                if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+                    dependencies.push(Dependency {
+                        name: name.clone(),
+                        version_requirement: version.to_string(),
+                        dev: info.get("dev").and_then(|d| d.as_bool()).unwrap_or(false),
+                    });
+                }
+                if let Some(nested) = info.get("dependencies").and_then(|v| v.as_object()) {
+                    stack.push(nested);
+                }
+            }
+        }
     }
+
+    Ok(dependencies)
+}
+
+fn parse_cargo_lock(content: &str) -> anyhow::Result<Vec<Dependency>> {
+    let parsed: toml::Value = toml::from_str(content)?;
+    Ok(parsed
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        // Workspace and path crates have no source
+        .filter(|pkg| {
+            pkg.get("source")
+                .and_then(|s| s.as_str())
+                .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+        })
+        .filter_map(|pkg| {
+            Some(Dependency {
+                name: pkg.get("name")?.as_str()?.to_string(),
+                version_requirement: pkg.get("version")?.as_str()?.to_string(),
+                dev: false,
+            })
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -388,31 +579,128 @@ serde = "*"
         assert_eq!(version, "1.0.0");
     }
 
-    #[test]
-    fn test_check_vulnerabilities_clean() {
-        let checker = DependencyChecker::new();
+    fn offline_checker(temp_dir: &tempfile::TempDir) -> DependencyChecker {
+        let npm_dir = temp_dir.path().join("npm");
+        std::fs::create_dir_all(&npm_dir).unwrap();
+        std::fs::write(
+            npm_dir.join("GHSA-mh6f-8j2x-4483.json"),
+            r#"{
+                "id": "GHSA-mh6f-8j2x-4483",
+                "summary": "Critical severity vulnerability that affects event-stream and flatmap-stream",
+                "affected": [{
+                    "package": {"ecosystem": "npm", "name": "flatmap-stream"},
+                    "versions": ["0.1.1"]
+                }, {
+                    "package": {"ecosystem": "npm", "name": "event-stream"},
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "3.3.6"}, {"fixed": "4.0.0"}]}]
+                }],
+                "database_specific": {"severity": "CRITICAL"}
+            }"#,
+        )
+        .unwrap();
+
+        DependencyChecker::new().with_osv_client(
+            OsvClient::new()
+                .with_cache_dir(temp_dir.path())
+                .offline(true),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_check_vulnerabilities_clean() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let checker = offline_checker(&temp_dir);
         let dependencies = vec![Dependency {
             name: "express".to_string(),
             version_requirement: "^4.17.1".to_string(),
             dev: false,
         }];
 
-        let issues = checker.check_vulnerabilities(&dependencies);
+        let issues = checker
+            .check_vulnerabilities(&dependencies, ManifestType::Npm)
+            .await
+            .unwrap();
         assert_eq!(issues.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_check_vulnerabilities_known_issue() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let checker = offline_checker(&temp_dir);
+        let dependencies = vec![
+            Dependency {
+                name: "event-stream".to_string(),
+                version_requirement: "3.3.6".to_string(),
+                dev: false,
+            },
+            Dependency {
+                name: "flatmap-stream".to_string(),
+                version_requirement: "0.1.1".to_string(),
+                dev: false,
+            },
+            Dependency {
+                name: "event-stream".to_string(),
+                version_requirement: "^4.0.0".to_string(),
+                dev: false,
+            },
+        ];
+
+        let issues = checker
+            .check_vulnerabilities(&dependencies, ManifestType::Npm)
+            .await
+            .unwrap();
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.severity == IssueSeverity::Critical));
+        let event_stream = issues
+            .iter()
+            .find(|i| i.dependency == "event-stream")
+            .unwrap();
+        let advisory = event_stream.advisory.as_ref().unwrap();
+        assert_eq!(advisory.affected_ranges, vec![">= 3.3.6, < 4.0.0"]);
+        assert_eq!(advisory.fixed_versions, vec!["4.0.0"]);
+    }
+
     #[test]
-    fn test_check_vulnerabilities_known_issue() {
-        let checker = DependencyChecker::new();
-        let dependencies = vec![Dependency {
-            name: "event-stream".to_string(),
-            version_requirement: "3.3.4".to_string(),
-            dev: false,
-        }];
+    fn test_concrete_version() {
+        assert_eq!(concrete_version("^4.17.1").as_deref(), Some("4.17.1"));
+        assert_eq!(concrete_version("1.0").as_deref(), Some("1.0.0"));
+        assert_eq!(concrete_version(">=1.2.0 <2").as_deref(), Some("1.2.0"));
+        assert_eq!(
+            concrete_version("1.0.0-beta.1").as_deref(),
+            Some("1.0.0-beta.1")
+        );
+        assert_eq!(concrete_version("*"), None);
+        assert_eq!(concrete_version("github:user/repo"), None);
+    }
 
-        let issues = checker.check_vulnerabilities(&dependencies);
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].severity, IssueSeverity::Critical);
+    #[test]
+    fn test_parse_lockfiles() {
+        let package_lock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": {"name": "app"},
+                "node_modules/express": {"version": "4.17.1"},
+                "node_modules/express/node_modules/debug": {"version": "2.6.9", "dev": true},
+                "node_modules/local": {"link": true}
+            }
+        }"#;
+        let deps = parse_package_lock(package_lock).unwrap();
+        assert_eq!(deps.len(), 2);
+        assert!(deps.iter().any(|d| d.name == "debug" && d.dev));
+
+        let cargo_lock = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let deps = parse_cargo_lock(cargo_lock).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version_requirement, "1.0.200");
     }
 
     #[tokio::test]
@@ -0,0 +1,334 @@
+//! Feature Matrix - Cargo feature combination checks before publishing
+//!
+//! Crates often build with their default features but break with others.
+//! The matrix runs `cargo check` for:
+//! - `--no-default-features`
+//! - each feature on its own (`--no-default-features --features <f>`)
+//! - `--all-features`
+//!
+//! When `cargo hack` is installed it is used instead
+//! (`cargo hack check --each-feature`, or `--feature-powerset` when configured).
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::{FeatureMatrix, FeatureStrategy};
+//!
+//! let manifest = r#"
+//! [package]
+//! name = "demo"
+//! version = "1.0.0"
+//!
+//! [features]
+//! default = ["std"]
+//! std = []
+//! serde = []
+//! "#;
+//! let matrix = FeatureMatrix::from_manifest(manifest, &FeatureStrategy::DEFAULT, &[]).unwrap();
+//! assert_eq!(matrix.runs.len(), 4); // no-default, std, serde, all
+//! ```
+
+use crate::core::config::FeatureMatrixConfig;
+use crate::core::traits::ValidationError;
+use crate::tr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Feature combinations to check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureStrategy {
+    NoDefaultFeatures,
+    EachFeature,
+    AllFeatures,
+    /// Every combination of features (cargo hack only)
+    Powerset,
+}
+
+impl FeatureStrategy {
+    /// Strategies used when none are configured
+    pub const DEFAULT: [FeatureStrategy; 3] = [
+        FeatureStrategy::NoDefaultFeatures,
+        FeatureStrategy::EachFeature,
+        FeatureStrategy::AllFeatures,
+    ];
+}
+
+impl std::str::FromStr for FeatureStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-default-features" => Ok(FeatureStrategy::NoDefaultFeatures),
+            "each-feature" => Ok(FeatureStrategy::EachFeature),
+            "all-features" => Ok(FeatureStrategy::AllFeatures),
+            "powerset" => Ok(FeatureStrategy::Powerset),
+            other => Err(format!(
+                "Unknown feature strategy: {} (expected no-default-features, each-feature, all-features or powerset)",
+                other
+            )),
+        }
+    }
+}
+
+/// One `cargo check` invocation of the matrix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureRun {
+    /// Human readable combination (`--no-default-features`, `serde`, ...)
+    pub label: String,
+    /// Arguments after `cargo check`
+    pub args: Vec<String>,
+}
+
+/// Feature combinations planned for a crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureMatrix {
+    pub features: Vec<String>,
+    pub runs: Vec<FeatureRun>,
+    /// Arguments for `cargo hack check`
+    pub hack_args: Vec<String>,
+}
+
+impl FeatureMatrix {
+    /// Plan the matrix from Cargo.toml content
+    ///
+    /// Features are the `[features]` table (without `default`) plus optional
+    /// dependencies that are not hidden behind `dep:`.
+    pub fn from_manifest(
+        manifest: &str,
+        strategies: &[FeatureStrategy],
+        skip: &[String],
+    ) -> anyhow::Result<Self> {
+        let toml: toml::Value = toml::from_str(manifest)?;
+        let feature_table = toml.get("features").and_then(|f| f.as_table());
+
+        let mut features: Vec<String> = feature_table
+            .map(|table| table.keys().filter(|k| *k != "default").cloned().collect())
+            .unwrap_or_default();
+
+        let hidden: Vec<String> = feature_table
+            .into_iter()
+            .flat_map(|table| table.values())
+            .filter_map(|v| v.as_array())
+            .flatten()
+            .filter_map(|v| v.as_str()?.strip_prefix("dep:").map(str::to_string))
+            .collect();
+        if let Some(deps) = toml.get("dependencies").and_then(|d| d.as_table()) {
+            for (name, spec) in deps {
+                let optional = spec
+                    .get("optional")
+                    .and_then(|o| o.as_bool())
+                    .unwrap_or(false);
+                if optional && !hidden.contains(name) && !features.contains(name) {
+                    features.push(name.clone());
+                }
+            }
+        }
+
+        features.retain(|f| !skip.contains(f));
+        features.sort();
+
+        let mut runs = Vec::new();
+        let mut hack_args = Vec::new();
+        for strategy in strategies {
+            match strategy {
+                FeatureStrategy::NoDefaultFeatures => runs.push(FeatureRun {
+                    label: "--no-default-features".to_string(),
+                    args: vec!["--no-default-features".to_string()],
+                }),
+                FeatureStrategy::EachFeature => {
+                    runs.extend(features.iter().map(|feature| FeatureRun {
+                        label: feature.clone(),
+                        args: vec![
+                            "--no-default-features".to_string(),
+                            "--features".to_string(),
+                            feature.clone(),
+                        ],
+                    }));
+                    hack_args.push("--each-feature".to_string());
+                }
+                FeatureStrategy::AllFeatures => runs.push(FeatureRun {
+                    label: "--all-features".to_string(),
+                    args: vec!["--all-features".to_string()],
+                }),
+                FeatureStrategy::Powerset => {
+                    hack_args.push("--feature-powerset".to_string());
+                }
+            }
+        }
+
+        // --feature-powerset already covers each feature
+        if hack_args.contains(&"--feature-powerset".to_string()) {
+            hack_args.retain(|a| a != "--each-feature");
+        }
+        if hack_args.is_empty() {
+            hack_args.push("--each-feature".to_string());
+        }
+        if !skip.is_empty() {
+            hack_args.push("--exclude-features".to_string());
+            hack_args.push(skip.join(","));
+        }
+
+        Ok(Self {
+            features,
+            runs,
+            hack_args,
+        })
+    }
+}
+
+/// Runs the feature matrix for a crate
+pub struct FeatureMatrixChecker {
+    project_path: PathBuf,
+    strategies: Vec<FeatureStrategy>,
+    skip: Vec<String>,
+    use_cargo_hack: bool,
+}
+
+impl FeatureMatrixChecker {
+    pub fn new(project_path: impl Into<PathBuf>) -> Self {
+        Self {
+            project_path: project_path.into(),
+            strategies: FeatureStrategy::DEFAULT.to_vec(),
+            skip: Vec::new(),
+            use_cargo_hack: true,
+        }
+    }
+
+    /// Apply `registries.crates.featureMatrix`
+    pub fn with_config(mut self, config: &FeatureMatrixConfig) -> anyhow::Result<Self> {
+        if let Some(ref strategies) = config.strategies {
+            self.strategies = strategies
+                .iter()
+                .map(|s| s.parse())
+                .collect::<Result<_, _>>()
+                .map_err(anyhow::Error::msg)?;
+        }
+        self.skip = config.skip.clone().unwrap_or_default();
+        self.use_cargo_hack = config.cargo_hack != Some(false);
+        Ok(self)
+    }
+
+    /// Check every combination; failures become validation errors
+    pub async fn check(&self) -> anyhow::Result<Vec<ValidationError>> {
+        let manifest = tokio::fs::read_to_string(self.project_path.join("Cargo.toml")).await?;
+        let matrix = FeatureMatrix::from_manifest(&manifest, &self.strategies, &self.skip)?;
+
+        if self.use_cargo_hack && cargo_hack_available(&self.project_path).await {
+            let mut args = vec!["hack", "check", "--no-dev-deps"];
+            args.extend(matrix.hack_args.iter().map(String::as_str));
+            return Ok(match run_cargo(&self.project_path, &args).await {
+                Ok(_) => Vec::new(),
+                Err(e) => vec![ValidationError {
+                    field: "cargo.features".to_string(),
+                    message: tr!("feature_matrix.hack_failed", error = e),
+                    severity: "error".to_string(),
+                }],
+            });
+        }
+
+        if self.strategies.contains(&FeatureStrategy::Powerset) {
+            anyhow::bail!("powerset strategy requires cargo-hack (cargo install cargo-hack)");
+        }
+
+        let mut errors = Vec::new();
+        for run in &matrix.runs {
+            let mut args = vec!["check"];
+            args.extend(run.args.iter().map(String::as_str));
+            if let Err(e) = run_cargo(&self.project_path, &args).await {
+                errors.push(ValidationError {
+                    field: "cargo.features".to_string(),
+                    message: tr!(
+                        "feature_matrix.check_failed",
+                        features = run.label,
+                        error = e
+                    ),
+                    severity: "error".to_string(),
+                });
+            }
+        }
+        Ok(errors)
+    }
+}
+
+async fn cargo_hack_available(project_path: &Path) -> bool {
+    run_cargo(project_path, &["hack", "--version"])
+        .await
+        .is_ok()
+}
+
+async fn run_cargo(project_path: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("cargo")
+        .args(args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[package]
+name = "demo"
+version = "1.0.0"
+
+[features]
+default = ["std"]
+std = []
+json = ["dep:serde_json"]
+unstable = []
+
+[dependencies]
+serde_json = { version = "1", optional = true }
+rayon = { version = "1", optional = true }
+"#;
+
+    #[test]
+    fn test_matrix_from_manifest() {
+        let matrix =
+            FeatureMatrix::from_manifest(MANIFEST, &FeatureStrategy::DEFAULT, &[]).unwrap();
+        assert_eq!(matrix.features, vec!["json", "rayon", "std", "unstable"]);
+
+        let labels: Vec<&str> = matrix.runs.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "--no-default-features",
+                "json",
+                "rayon",
+                "std",
+                "unstable",
+                "--all-features"
+            ]
+        );
+        assert_eq!(matrix.hack_args, vec!["--each-feature"]);
+    }
+
+    #[test]
+    fn test_matrix_skip_and_powerset() {
+        let strategies = ["all-features", "powerset"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<FeatureStrategy>>();
+        let matrix =
+            FeatureMatrix::from_manifest(MANIFEST, &strategies, &["unstable".to_string()]).unwrap();
+
+        assert!(!matrix.features.contains(&"unstable".to_string()));
+        assert_eq!(matrix.runs.len(), 1);
+        assert_eq!(
+            matrix.hack_args,
+            vec!["--feature-powerset", "--exclude-features", "unstable"]
+        );
+        assert!("everything".parse::<FeatureStrategy>().is_err());
+    }
+}
@@ -1,7 +1,29 @@
+pub mod api_report;
+pub mod bundle_size;
 pub mod dependency_checker;
+pub mod feature_matrix;
 pub mod manifest_validator;
+pub mod osv;
+pub mod package_contents;
+pub mod python_metadata;
+pub mod semver_checks;
+pub mod size_budget;
+pub mod version_bumper;
 pub mod version_validator;
+pub mod warning_budget;
+pub mod wheel_auditor;
 
+pub use api_report::{ApiDiff, ApiReport};
+pub use bundle_size::{BundleSizeGate, PackageSize};
 pub use dependency_checker::{DependencyCheckResult, DependencyChecker, DependencyIssue};
+pub use feature_matrix::{FeatureMatrix, FeatureMatrixChecker, FeatureRun, FeatureStrategy};
 pub use manifest_validator::{ManifestMetadata, ManifestType, ManifestValidator, ValidationResult};
+pub use osv::{Advisory, OsvClient, OsvFinding};
+pub use package_contents::{PackageContentReport, PackageContentValidator};
+pub use python_metadata::{MetadataCheck, PythonMetadataScore, PythonMetadataScorer};
+pub use semver_checks::{SemverCheckResult, SemverChecker, SemverViolation};
+pub use size_budget::{FileSize, SizeMeasurement};
+pub use version_bumper::{BumpLevel, BumpResult, VersionBumper, VersionedFile, VersionedManifest};
 pub use version_validator::{VersionValidationResult, VersionValidator};
+pub use warning_budget::{WarningBudget, WarningBudgetExceeded};
+pub use wheel_auditor::{WheelAuditResult, WheelAuditor, WheelTags};
@@ -0,0 +1,726 @@
+//! OSV Client - vulnerability lookups against the OSV database
+//!
+//! Looks up advisories for npm and crates.io packages:
+//! - Online: OSV.dev batch API (`/v1/querybatch`); advisory details come from
+//!   `/v1/vulns/{id}` and are cached on disk
+//! - Offline: the cache directory (advisories fetched earlier, plus an
+//!   ecosystem dump `all.zip` from [`update_database`](OsvClient::update_database)),
+//!   matched locally against affected versions and ranges
+//!
+//! When the API cannot be reached the offline database is used instead.
+
+use crate::core::http::http_client;
+use crate::validation::dependency_checker::IssueSeverity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// OSV.dev API
+pub const DEFAULT_API_URL: &str = "https://api.osv.dev";
+
+/// Ecosystem dumps (`<ecosystem>/all.zip`)
+const DUMP_URL: &str = "https://osv-vulnerabilities.storage.googleapis.com";
+
+/// Queries per batch request (API limit)
+const BATCH_SIZE: usize = 1000;
+
+/// Advisory affecting a dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Advisory {
+    /// OSV identifier (GHSA-..., RUSTSEC-...)
+    pub id: String,
+    /// Other identifiers (CVE-...)
+    pub aliases: Vec<String>,
+    pub summary: String,
+    pub severity: IssueSeverity,
+    /// Affected ranges for the package (">= 1.0.0, < 1.2.3")
+    pub affected_ranges: Vec<String>,
+    /// Versions that fix the advisory
+    pub fixed_versions: Vec<String>,
+    pub url: String,
+}
+
+/// Advisory matched to a package version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsvFinding {
+    pub package: String,
+    pub version: String,
+    pub advisory: Advisory,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    modified: Option<String>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    details: Option<String>,
+    #[serde(default)]
+    severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+    #[serde(default)]
+    database_specific: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvSeverity {
+    #[serde(rename = "type")]
+    kind: String,
+    score: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvAffected {
+    #[serde(default)]
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<HashMap<String, String>>,
+}
+
+impl OsvRecord {
+    fn affected_for<'a>(
+        &'a self,
+        ecosystem: &'a str,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a OsvAffected> + 'a {
+        self.affected.iter().filter(move |a| {
+            a.package
+                .as_ref()
+                .is_some_and(|p| p.ecosystem.eq_ignore_ascii_case(ecosystem) && p.name == name)
+        })
+    }
+
+    /// Whether `name@version` is affected
+    fn affects(&self, ecosystem: &str, name: &str, version: &str) -> bool {
+        if self.withdrawn.is_some() {
+            return false;
+        }
+        self.affected_for(ecosystem, name).any(|affected| {
+            affected.versions.iter().any(|v| v == version)
+                || affected
+                    .ranges
+                    .iter()
+                    .filter(|r| r.kind == "SEMVER" || r.kind == "ECOSYSTEM")
+                    .any(|r| version_in_range(&r.events, version))
+        })
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        let label = self
+            .database_specific
+            .as_ref()
+            .and_then(|d| d.get("severity"))
+            .and_then(|s| s.as_str());
+        if let Some(label) = label {
+            match label.to_uppercase().as_str() {
+                "CRITICAL" => return IssueSeverity::Critical,
+                "HIGH" => return IssueSeverity::High,
+                "MODERATE" | "MEDIUM" => return IssueSeverity::Medium,
+                "LOW" => return IssueSeverity::Low,
+                _ => {}
+            }
+        }
+
+        let score = self
+            .severity
+            .iter()
+            .filter(|s| s.kind == "CVSS_V3")
+            .find_map(|s| cvss3_base_score(&s.score));
+        match score {
+            Some(score) => severity_from_score(score),
+            // RustSec informational advisories (unmaintained, unsound)
+            None if self
+                .database_specific
+                .as_ref()
+                .and_then(|d| d.get("informational"))
+                .is_some_and(|i| !i.is_null()) =>
+            {
+                IssueSeverity::Low
+            }
+            None => IssueSeverity::Medium,
+        }
+    }
+
+    fn to_advisory(&self, ecosystem: &str, name: &str) -> Advisory {
+        let mut affected_ranges = Vec::new();
+        let mut fixed_versions = BTreeSet::new();
+        for affected in self.affected_for(ecosystem, name) {
+            for range in affected
+                .ranges
+                .iter()
+                .filter(|r| r.kind == "SEMVER" || r.kind == "ECOSYSTEM")
+            {
+                affected_ranges.extend(describe_range(&range.events));
+                fixed_versions.extend(range.events.iter().filter_map(|e| e.get("fixed").cloned()));
+            }
+        }
+
+        let summary = self
+            .summary
+            .clone()
+            .or_else(|| {
+                self.details
+                    .as_ref()
+                    .and_then(|d| d.lines().next().map(str::to_string))
+            })
+            .unwrap_or_default();
+
+        Advisory {
+            id: self.id.clone(),
+            aliases: self.aliases.clone(),
+            summary,
+            severity: self.severity(),
+            affected_ranges,
+            fixed_versions: fixed_versions.into_iter().collect(),
+            url: format!("https://osv.dev/vulnerability/{}", self.id),
+        }
+    }
+}
+
+/// Lenient semver parse ("0" and "1.2" are padded, a leading "v" is dropped)
+fn parse_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim().trim_start_matches('v');
+    if let Ok(parsed) = semver::Version::parse(version) {
+        return Some(parsed);
+    }
+    let (core, rest) = match version.find(['-', '+']) {
+        Some(i) => version.split_at(i),
+        None => (version, ""),
+    };
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    semver::Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+/// Evaluate OSV range events (introduced / fixed / last_affected) for a version
+fn version_in_range(events: &[HashMap<String, String>], version: &str) -> bool {
+    let Some(version) = parse_version(version) else {
+        return false;
+    };
+
+    let mut events: Vec<(&str, semver::Version)> = events
+        .iter()
+        .flat_map(|e| e.iter())
+        .filter_map(|(kind, v)| parse_version(v).map(|parsed| (kind.as_str(), parsed)))
+        .collect();
+    events.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let mut affected = false;
+    for (kind, event_version) in events {
+        if event_version > version {
+            break;
+        }
+        match kind {
+            "introduced" => affected = true,
+            "fixed" => affected = false,
+            "last_affected" if event_version < version => affected = false,
+            _ => {}
+        }
+    }
+    affected
+}
+
+/// Human-readable ranges for OSV events
+fn describe_range(events: &[HashMap<String, String>]) -> Vec<String> {
+    let mut ranges = Vec::new();
+    let mut lower: Option<String> = None;
+    for event in events {
+        if let Some(introduced) = event.get("introduced") {
+            lower = Some(if introduced == "0" {
+                String::new()
+            } else {
+                format!(">= {}", introduced)
+            });
+        } else if let Some(lower_bound) = lower.take() {
+            let upper = match (event.get("fixed"), event.get("last_affected")) {
+                (Some(fixed), _) => format!("< {}", fixed),
+                (None, Some(last)) => format!("<= {}", last),
+                _ => {
+                    lower = Some(lower_bound);
+                    continue;
+                }
+            };
+            ranges.push(if lower_bound.is_empty() {
+                upper
+            } else {
+                format!("{}, {}", lower_bound, upper)
+            });
+        }
+    }
+    if let Some(lower_bound) = lower {
+        ranges.push(if lower_bound.is_empty() {
+            "*".to_string()
+        } else {
+            lower_bound
+        });
+    }
+    ranges
+}
+
+/// CVSS v3.x base score from a vector string
+pub fn cvss3_base_score(vector: &str) -> Option<f64> {
+    let metrics: HashMap<&str, &str> = vector
+        .split('/')
+        .skip(1)
+        .filter_map(|m| m.split_once(':'))
+        .collect();
+    let changed = match *metrics.get("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+
+    let av = match *metrics.get("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match *metrics.get("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let pr = match (*metrics.get("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match *metrics.get("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let cia = |key: &str| match metrics.get(key).copied() {
+        Some("H") => Some(0.56),
+        Some("L") => Some(0.22),
+        Some("N") => Some(0.0),
+        _ => None,
+    };
+    let (c, i, a) = (cia("C")?, cia("I")?, cia("A")?);
+
+    let iss = 1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02_f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let score = if changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+    // CVSS "round up" to one decimal
+    Some((score.min(10.0) * 10.0 - 1e-9).ceil() / 10.0)
+}
+
+/// Severity band of a CVSS base score
+pub(crate) fn severity_from_score(score: f64) -> IssueSeverity {
+    if score >= 9.0 {
+        IssueSeverity::Critical
+    } else if score >= 7.0 {
+        IssueSeverity::High
+    } else if score >= 4.0 {
+        IssueSeverity::Medium
+    } else {
+        IssueSeverity::Low
+    }
+}
+
+fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("package-publisher")
+        .join("osv")
+}
+
+/// OSV database client with an on-disk cache
+#[derive(Debug, Clone)]
+pub struct OsvClient {
+    api_url: String,
+    cache_dir: PathBuf,
+    offline: bool,
+}
+
+impl Default for OsvClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OsvClient {
+    /// Create a client for OSV.dev with the default cache directory
+    pub fn new() -> Self {
+        Self {
+            api_url: DEFAULT_API_URL.to_string(),
+            cache_dir: default_cache_dir(),
+            offline: false,
+        }
+    }
+
+    /// Use another OSV-compatible API
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Cache / offline database directory (one subdirectory per ecosystem)
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Only use the offline database
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    fn ecosystem_dir(&self, ecosystem: &str) -> PathBuf {
+        self.cache_dir.join(ecosystem)
+    }
+
+    /// Advisories affecting the given `(name, version)` packages
+    pub async fn query(
+        &self,
+        ecosystem: &str,
+        packages: &[(String, String)],
+    ) -> anyhow::Result<Vec<OsvFinding>> {
+        if packages.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.offline {
+            return self.query_offline(ecosystem, packages).await;
+        }
+
+        match self.query_online(ecosystem, packages).await {
+            Ok(findings) => Ok(findings),
+            Err(online_error) => {
+                tracing::warn!(
+                    target: "package_publisher",
+                    "OSV API unavailable ({}); using the offline database",
+                    online_error
+                );
+                self.query_offline(ecosystem, packages)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{} (OSV API: {})", e, online_error))
+            }
+        }
+    }
+
+    async fn query_online(
+        &self,
+        ecosystem: &str,
+        packages: &[(String, String)],
+    ) -> anyhow::Result<Vec<OsvFinding>> {
+        let client = http_client();
+        let mut findings = Vec::new();
+        let mut records: HashMap<String, OsvRecord> = HashMap::new();
+
+        for chunk in packages.chunks(BATCH_SIZE) {
+            let queries: Vec<serde_json::Value> = chunk
+                .iter()
+                .map(|(name, version)| {
+                    serde_json::json!({
+                        "package": { "name": name, "ecosystem": ecosystem },
+                        "version": version,
+                    })
+                })
+                .collect();
+
+            let response = client
+                .post(format!("{}/v1/querybatch", self.api_url))
+                .json(&serde_json::json!({ "queries": queries }))
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                anyhow::bail!("querybatch returned HTTP {}", response.status());
+            }
+            let body: serde_json::Value = response.json().await?;
+            let results = body["results"].as_array().cloned().unwrap_or_default();
+
+            for ((name, version), result) in chunk.iter().zip(results) {
+                for vuln in result["vulns"].as_array().into_iter().flatten() {
+                    let Some(id) = vuln["id"].as_str() else {
+                        continue;
+                    };
+                    if !records.contains_key(id) {
+                        let record = self
+                            .fetch_record(&client, ecosystem, id, vuln["modified"].as_str())
+                            .await?;
+                        records.insert(id.to_string(), record);
+                    }
+                    let record = &records[id];
+                    if record.withdrawn.is_none() {
+                        findings.push(OsvFinding {
+                            package: name.clone(),
+                            version: version.clone(),
+                            advisory: record.to_advisory(ecosystem, name),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Advisory details, from the cache when it is up to date
+    async fn fetch_record(
+        &self,
+        client: &reqwest::Client,
+        ecosystem: &str,
+        id: &str,
+        modified: Option<&str>,
+    ) -> anyhow::Result<OsvRecord> {
+        let cache_path = self.ecosystem_dir(ecosystem).join(format!("{}.json", id));
+        if let Ok(cached) = fs::read_to_string(&cache_path).await
+            && let Ok(record) = serde_json::from_str::<OsvRecord>(&cached)
+            && modified.is_none_or(|m| record.modified.as_deref() == Some(m))
+        {
+            return Ok(record);
+        }
+
+        let response = client
+            .get(format!("{}/v1/vulns/{}", self.api_url, id))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("{} returned HTTP {}", id, response.status());
+        }
+        let body = response.text().await?;
+        let record: OsvRecord = serde_json::from_str(&body)?;
+
+        // Best effort: a missing cache only disables the offline fallback
+        if fs::create_dir_all(cache_path.parent().unwrap())
+            .await
+            .is_ok()
+        {
+            let _ = fs::write(&cache_path, &body).await;
+        }
+        Ok(record)
+    }
+
+    async fn query_offline(
+        &self,
+        ecosystem: &str,
+        packages: &[(String, String)],
+    ) -> anyhow::Result<Vec<OsvFinding>> {
+        let records = self.load_database(ecosystem).await?;
+        let mut findings = Vec::new();
+        for (name, version) in packages {
+            for record in records
+                .iter()
+                .filter(|r| r.affects(ecosystem, name, version))
+            {
+                findings.push(OsvFinding {
+                    package: name.clone(),
+                    version: version.clone(),
+                    advisory: record.to_advisory(ecosystem, name),
+                });
+            }
+        }
+        Ok(findings)
+    }
+
+    /// Records from `<cache>/<ecosystem>/*.json` and `all.zip`
+    async fn load_database(&self, ecosystem: &str) -> anyhow::Result<Vec<OsvRecord>> {
+        let dir = self.ecosystem_dir(ecosystem);
+        let mut entries = fs::read_dir(&dir).await.map_err(|_| {
+            anyhow::anyhow!(
+                "No offline OSV database for {} at {}",
+                ecosystem,
+                dir.display()
+            )
+        })?;
+
+        let mut records: HashMap<String, OsvRecord> = HashMap::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "json")
+                && let Ok(content) = fs::read_to_string(&path).await
+                && let Ok(record) = serde_json::from_str::<OsvRecord>(&content)
+            {
+                records.insert(record.id.clone(), record);
+            }
+        }
+
+        let dump = dir.join("all.zip");
+        if fs::metadata(&dump).await.is_ok() {
+            let dump_records = tokio::task::spawn_blocking(move || read_dump(&dump)).await??;
+            for record in dump_records {
+                records.entry(record.id.clone()).or_insert(record);
+            }
+        }
+
+        Ok(records.into_values().collect())
+    }
+
+    /// Download the full ecosystem dump for offline use
+    ///
+    /// Returns the number of advisories in the dump.
+    pub async fn update_database(&self, ecosystem: &str) -> anyhow::Result<usize> {
+        let response = http_client()
+            .get(format!("{}/{}/all.zip", DUMP_URL, ecosystem))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to download the {} OSV database (HTTP {})",
+                ecosystem,
+                response.status()
+            );
+        }
+        let bytes = response.bytes().await?;
+
+        let dir = self.ecosystem_dir(ecosystem);
+        fs::create_dir_all(&dir).await?;
+        let dump = dir.join("all.zip");
+        fs::write(&dump, &bytes).await?;
+
+        let records = tokio::task::spawn_blocking(move || read_dump(&dump)).await??;
+        Ok(records.len())
+    }
+}
+
+fn read_dump(path: &Path) -> anyhow::Result<Vec<OsvRecord>> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+    let mut records = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.name().ends_with(".json") {
+            continue;
+        }
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        if let Ok(record) = serde_json::from_str::<OsvRecord>(&content) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ADVISORY: &str = r#"{
+        "id": "GHSA-test-0001",
+        "modified": "2024-01-01T00:00:00Z",
+        "aliases": ["CVE-2024-0001"],
+        "summary": "Prototype pollution in left-pad",
+        "affected": [{
+            "package": {"ecosystem": "npm", "name": "left-pad"},
+            "ranges": [{"type": "SEMVER", "events": [
+                {"introduced": "0"}, {"fixed": "1.3.0"},
+                {"introduced": "2.0.0"}, {"last_affected": "2.1.0"}
+            ]}]
+        }],
+        "database_specific": {"severity": "HIGH"}
+    }"#;
+
+    #[test]
+    fn test_version_in_range_and_description() {
+        let record: OsvRecord = serde_json::from_str(ADVISORY).unwrap();
+        assert!(record.affects("npm", "left-pad", "1.2.9"));
+        assert!(!record.affects("npm", "left-pad", "1.3.0"));
+        assert!(record.affects("npm", "left-pad", "2.1.0"));
+        assert!(!record.affects("npm", "left-pad", "2.1.1"));
+        assert!(!record.affects("crates.io", "left-pad", "1.0.0"));
+
+        let advisory = record.to_advisory("npm", "left-pad");
+        assert_eq!(advisory.severity, IssueSeverity::High);
+        assert_eq!(
+            advisory.affected_ranges,
+            vec!["< 1.3.0", ">= 2.0.0, <= 2.1.0"]
+        );
+        assert_eq!(advisory.fixed_versions, vec!["1.3.0"]);
+    }
+
+    #[test]
+    fn test_cvss3_base_score() {
+        assert_eq!(
+            cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Some(9.8)
+        );
+        assert_eq!(
+            cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"),
+            Some(6.1)
+        );
+        assert_eq!(cvss3_base_score("not a vector"), None);
+    }
+
+    #[tokio::test]
+    async fn test_offline_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let npm_dir = temp_dir.path().join("npm");
+        std::fs::create_dir_all(&npm_dir).unwrap();
+        std::fs::write(npm_dir.join("GHSA-test-0001.json"), ADVISORY).unwrap();
+
+        let client = OsvClient::new()
+            .with_cache_dir(temp_dir.path())
+            .offline(true);
+        let findings = client
+            .query(
+                "npm",
+                &[
+                    ("left-pad".to_string(), "1.0.0".to_string()),
+                    ("express".to_string(), "4.17.1".to_string()),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].package, "left-pad");
+        assert_eq!(findings[0].advisory.id, "GHSA-test-0001");
+
+        assert!(
+            client
+                .query("crates.io", &[("serde".to_string(), "1.0.0".to_string())])
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! Package Contents - file allowlist/denylist for the published package
+//!
+//! Checks the list of files the registry tool will publish
+//! (`npm pack --dry-run --json`, `cargo package --list`) against
+//! `validation.packageContents`:
+//! - `required`: each pattern must match at least one published file
+//!   (default: README and LICENSE)
+//! - `forbidden`: no published file may match (default: tests, CI and
+//!   editor configuration, local publish config)
+//!
+//! Patterns use `.gitignore` syntax and are matched case-insensitively.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::package_contents::PackageContentValidator;
+//!
+//! let validator = PackageContentValidator::new(None, None).unwrap();
+//! let report = validator.validate(&["README.md", "LICENSE", "dist/index.js", "tests/a.test.js"]);
+//! assert!(report.missing.is_empty());
+//! assert_eq!(report.forbidden, vec!["tests/a.test.js"]);
+//! ```
+
+use crate::core::config::PackageContentsConfig;
+use crate::plugins::plugin_loader::RegistryType;
+use crate::tr;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Files every package must contain
+pub const DEFAULT_REQUIRED_FILES: &[&str] = &["README*", "LICEN[CS]E*"];
+
+/// Files that must not be published
+pub const DEFAULT_FORBIDDEN_FILES: &[&str] = &[
+    "tests/",
+    "test/",
+    "__tests__/",
+    ".github/",
+    ".gitlab-ci.yml",
+    ".vscode/",
+    ".idea/",
+    ".publish-config.yaml",
+    ".publish-config.yml",
+    ".publish-config.toml",
+    ".publish-config.json",
+    ".env",
+    ".env.*",
+    "!.env.example",
+];
+
+/// Result of checking the published file list
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageContentReport {
+    /// Number of published files
+    pub files: usize,
+    /// Required patterns without a matching file
+    pub missing: Vec<String>,
+    /// Published files matched by a forbidden pattern
+    pub forbidden: Vec<String>,
+}
+
+impl PackageContentReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.forbidden.is_empty()
+    }
+
+    /// One message per violated rule
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self
+            .missing
+            .iter()
+            .map(|p| tr!("package_contents.missing", file = p))
+            .collect();
+        problems.extend(
+            self.forbidden
+                .iter()
+                .map(|f| tr!("package_contents.forbidden", file = f)),
+        );
+        problems
+    }
+}
+
+/// Checks published file lists against required/forbidden patterns
+pub struct PackageContentValidator {
+    required: Vec<(String, Gitignore)>,
+    forbidden: Gitignore,
+}
+
+impl PackageContentValidator {
+    /// Validator with the given patterns (defaults: [`DEFAULT_REQUIRED_FILES`],
+    /// [`DEFAULT_FORBIDDEN_FILES`])
+    pub fn new(required: Option<&[String]>, forbidden: Option<&[String]>) -> anyhow::Result<Self> {
+        let required: Vec<String> = match required {
+            Some(patterns) => patterns.to_vec(),
+            None => DEFAULT_REQUIRED_FILES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        };
+        let forbidden: Vec<String> = match forbidden {
+            Some(patterns) => patterns.to_vec(),
+            None => DEFAULT_FORBIDDEN_FILES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        };
+
+        Ok(Self {
+            required: required
+                .into_iter()
+                .map(|p| {
+                    let matcher = build_matcher(std::slice::from_ref(&p))?;
+                    Ok((p, matcher))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            forbidden: build_matcher(&forbidden)?,
+        })
+    }
+
+    /// Validator for `validation.packageContents`
+    pub fn from_config(config: &PackageContentsConfig) -> anyhow::Result<Self> {
+        Self::new(config.required.as_deref(), config.forbidden.as_deref())
+    }
+
+    /// Check a list of published paths (relative to the package root)
+    pub fn validate<S: AsRef<str>>(&self, files: &[S]) -> PackageContentReport {
+        let files: Vec<&str> = files.iter().map(|f| f.as_ref()).collect();
+
+        let missing = self
+            .required
+            .iter()
+            .filter(|(_, matcher)| !files.iter().any(|f| matches(matcher, f)))
+            .map(|(pattern, _)| pattern.clone())
+            .collect();
+        let mut forbidden: Vec<String> = files
+            .iter()
+            .filter(|f| matches(&self.forbidden, f))
+            .map(|f| f.to_string())
+            .collect();
+        forbidden.sort();
+
+        PackageContentReport {
+            files: files.len(),
+            missing,
+            forbidden,
+        }
+    }
+}
+
+fn build_matcher(patterns: &[String]) -> anyhow::Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new("");
+    builder.case_insensitive(true)?;
+    for pattern in patterns {
+        builder.add_line(None, pattern)?;
+    }
+    Ok(builder.build()?)
+}
+
+fn matches(matcher: &Gitignore, file: &str) -> bool {
+    matcher
+        .matched_path_or_any_parents(Path::new(file), false)
+        .is_ignore()
+}
+
+/// Paths from `npm pack --dry-run --json` output
+pub fn parse_npm_file_list(output: &str) -> anyhow::Result<Vec<String>> {
+    // npm prints lifecycle script output before the JSON array
+    let start = output
+        .find('[')
+        .ok_or_else(|| anyhow::anyhow!("npm pack produced no JSON output"))?;
+    let json: serde_json::Value = serde_json::from_str(&output[start..])?;
+    let files = json
+        .get(0)
+        .and_then(|p| p.get("files"))
+        .and_then(|f| f.as_array())
+        .ok_or_else(|| anyhow::anyhow!("npm pack output has no file list"))?;
+    Ok(files
+        .iter()
+        .filter_map(|f| f.get("path").and_then(|p| p.as_str()))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Paths from `cargo package --list` output
+///
+/// Files cargo generates while packaging (`Cargo.toml.orig`,
+/// `.cargo_vcs_info.json`) are listed as they are published.
+pub fn parse_cargo_file_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.replace('\\', "/"))
+        .collect()
+}
+
+/// Files the registry tool will publish for the project
+pub async fn published_files(
+    project_path: &Path,
+    registry_type: RegistryType,
+) -> anyhow::Result<Vec<String>> {
+    let (program, args): (&str, &[&str]) = match registry_type {
+        RegistryType::Npm => ("npm", &["pack", "--dry-run", "--json", "--ignore-scripts"]),
+        RegistryType::Crates => ("cargo", &["package", "--list", "--allow-dirty"]),
+        other => anyhow::bail!("file list is not available for {:?}", other),
+    };
+
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} {} failed: {}",
+            program,
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match registry_type {
+        RegistryType::Npm => parse_npm_file_list(&stdout),
+        _ => Ok(parse_cargo_file_list(&stdout)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_rules() {
+        let validator = PackageContentValidator::new(
+            Some(&["LICENSE".to_string(), "CHANGELOG.md".to_string()]),
+            Some(&["*.map".to_string(), "fixtures/".to_string()]),
+        )
+        .unwrap();
+        let report = validator.validate(&[
+            "license",
+            "src/lib.rs",
+            "dist/index.js.map",
+            "src/fixtures/sample.json",
+        ]);
+        assert_eq!(report.files, 4);
+        assert_eq!(report.missing, vec!["CHANGELOG.md"]);
+        assert_eq!(
+            report.forbidden,
+            vec!["dist/index.js.map", "src/fixtures/sample.json"]
+        );
+        assert_eq!(report.problems().len(), 3);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_parse_file_lists() {
+        let npm = r#"> demo@1.0.0 prepack
+[
+  {
+    "name": "demo",
+    "files": [
+      { "path": "LICENSE", "size": 1070, "mode": 420 },
+      { "path": "dist/index.js", "size": 800, "mode": 420 }
+    ]
+  }
+]"#;
+        assert_eq!(
+            parse_npm_file_list(npm).unwrap(),
+            vec!["LICENSE", "dist/index.js"]
+        );
+
+        let cargo = ".cargo_vcs_info.json\nCargo.toml\nCargo.toml.orig\nREADME.md\nsrc/lib.rs\n";
+        let files = parse_cargo_file_list(cargo);
+        assert_eq!(files.len(), 5);
+
+        let validator = PackageContentValidator::new(None, None).unwrap();
+        let report = validator.validate(&files);
+        assert_eq!(report.missing, vec!["LICEN[CS]E*"]);
+        assert!(report.forbidden.is_empty());
+    }
+}
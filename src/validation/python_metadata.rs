@@ -0,0 +1,342 @@
+//! Python Metadata Scorer - Completeness score for PyPI project pages
+//!
+//! Scores `pyproject.toml` (PEP 621 `[project]`) or `setup.py` metadata out of
+//! 100 so packages can be nudged toward better PyPI pages:
+//!
+//! | Check | Points |
+//! |-------|--------|
+//! | Trove classifiers present | 20 |
+//! | License / Python version classifiers | 10 |
+//! | `requires-python` / `python_requires` set | 20 |
+//! | Project URLs | 15 |
+//! | Readme with a correct content type | 20 |
+//! | Description | 5 |
+//! | License | 5 |
+//! | Keywords | 5 |
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::PythonMetadataScorer;
+//!
+//! let score = PythonMetadataScorer::score_pyproject(
+//!     r#"
+//! [project]
+//! name = "demo"
+//! requires-python = ">=3.9"
+//! "#,
+//! )
+//! .unwrap();
+//! assert_eq!(score.score, 20);
+//! ```
+
+use crate::tr;
+use regex::Regex;
+
+/// Result of a single metadata check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataCheck {
+    /// Metadata field the check covers
+    pub field: &'static str,
+    pub passed: bool,
+    /// Points awarded when passed
+    pub points: u32,
+    /// Suggestion shown when the check fails
+    pub message: String,
+}
+
+/// Metadata completeness score (0-100)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonMetadataScore {
+    pub score: u32,
+    pub checks: Vec<MetadataCheck>,
+}
+
+impl PythonMetadataScore {
+    /// Failed checks, in scoring order
+    pub fn failed(&self) -> impl Iterator<Item = &MetadataCheck> {
+        self.checks.iter().filter(|c| !c.passed)
+    }
+
+    fn from_checks(checks: Vec<MetadataCheck>) -> Self {
+        let score = checks.iter().filter(|c| c.passed).map(|c| c.points).sum();
+        Self { score, checks }
+    }
+}
+
+/// Metadata fields extracted from either pyproject.toml or setup.py
+#[derive(Debug, Default)]
+struct PythonMetadata {
+    classifiers: Vec<String>,
+    requires_python: bool,
+    has_urls: bool,
+    /// Readme file name, if any
+    readme: Option<String>,
+    /// Explicit readme content type, if any
+    readme_content_type: Option<String>,
+    description: bool,
+    license: bool,
+    keywords: bool,
+}
+
+/// Scores Python package metadata completeness
+pub struct PythonMetadataScorer;
+
+impl PythonMetadataScorer {
+    /// Score a pyproject.toml document (PEP 621 `[project]` table)
+    pub fn score_pyproject(content: &str) -> anyhow::Result<PythonMetadataScore> {
+        let value: toml::Value = toml::from_str(content)?;
+        let project = value.get("project");
+        let get = |key: &str| project.and_then(|p| p.get(key));
+
+        let (readme, readme_content_type) = match get("readme") {
+            Some(toml::Value::String(file)) => (Some(file.clone()), None),
+            Some(toml::Value::Table(table)) => (
+                table
+                    .get("file")
+                    .and_then(|f| f.as_str())
+                    .map(str::to_string)
+                    .or_else(|| table.get("text").map(|_| String::new())),
+                table
+                    .get("content-type")
+                    .and_then(|c| c.as_str())
+                    .map(str::to_string),
+            ),
+            _ => (None, None),
+        };
+
+        let metadata = PythonMetadata {
+            classifiers: get("classifiers")
+                .and_then(|c| c.as_array())
+                .map(|c| {
+                    c.iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            requires_python: get("requires-python").is_some(),
+            has_urls: get("urls")
+                .and_then(|u| u.as_table())
+                .is_some_and(|u| !u.is_empty()),
+            readme,
+            readme_content_type,
+            description: get("description")
+                .and_then(|d| d.as_str())
+                .is_some_and(|d| !d.trim().is_empty()),
+            license: get("license").is_some(),
+            keywords: get("keywords")
+                .and_then(|k| k.as_array())
+                .is_some_and(|k| !k.is_empty()),
+        };
+
+        Ok(Self::score(&metadata))
+    }
+
+    /// Score a setup.py file (keyword arguments to `setup()`)
+    pub fn score_setup_py(content: &str) -> PythonMetadataScore {
+        let has_kwarg = |name: &str| {
+            Regex::new(&format!(r"\b{}\s*=", name))
+                .unwrap()
+                .is_match(content)
+        };
+        let string_kwarg = |name: &str| {
+            Regex::new(&format!(r#"\b{}\s*=\s*['"]([^'"]*)['"]"#, name))
+                .unwrap()
+                .captures(content)
+                .map(|c| c[1].to_string())
+        };
+
+        let classifiers = Regex::new(r#"['"]([A-Za-z ]+ :: [^'"]+)['"]"#)
+            .unwrap()
+            .captures_iter(content)
+            .map(|c| c[1].to_string())
+            .collect();
+
+        let metadata = PythonMetadata {
+            classifiers,
+            requires_python: has_kwarg("python_requires"),
+            has_urls: has_kwarg("project_urls") || has_kwarg("url"),
+            readme: has_kwarg("long_description").then(String::new),
+            readme_content_type: string_kwarg("long_description_content_type"),
+            description: string_kwarg("description").is_some_and(|d| !d.trim().is_empty()),
+            license: has_kwarg("license"),
+            keywords: has_kwarg("keywords"),
+        };
+
+        Self::score(&metadata)
+    }
+
+    fn score(metadata: &PythonMetadata) -> PythonMetadataScore {
+        let has_detail_classifiers = metadata
+            .classifiers
+            .iter()
+            .any(|c| c.starts_with("License ::"))
+            && metadata
+                .classifiers
+                .iter()
+                .any(|c| c.starts_with("Programming Language :: Python :: 3"));
+
+        let readme_issue = Self::readme_issue(metadata);
+
+        PythonMetadataScore::from_checks(vec![
+            check(
+                "classifiers",
+                !metadata.classifiers.is_empty(),
+                20,
+                &tr!("python_metadata.classifiers"),
+            ),
+            check(
+                "classifiers",
+                has_detail_classifiers,
+                10,
+                &tr!("python_metadata.detail_classifiers"),
+            ),
+            check(
+                "requires-python",
+                metadata.requires_python,
+                20,
+                &tr!("python_metadata.requires_python"),
+            ),
+            check("urls", metadata.has_urls, 15, &tr!("python_metadata.urls")),
+            check(
+                "readme",
+                readme_issue.is_none(),
+                20,
+                readme_issue.as_deref().unwrap_or_default(),
+            ),
+            check(
+                "description",
+                metadata.description,
+                5,
+                &tr!("python_metadata.description"),
+            ),
+            check(
+                "license",
+                metadata.license,
+                5,
+                &tr!("python_metadata.license"),
+            ),
+            check(
+                "keywords",
+                metadata.keywords,
+                5,
+                &tr!("python_metadata.keywords"),
+            ),
+        ])
+    }
+
+    /// Why the readme would render poorly on PyPI, if it would
+    fn readme_issue(metadata: &PythonMetadata) -> Option<String> {
+        let Some(ref readme) = metadata.readme else {
+            return Some(tr!("python_metadata.readme_missing"));
+        };
+
+        let expected = match readme.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()) {
+            Some(ext) if ext == "md" || ext == "markdown" => Some("text/markdown"),
+            Some(ext) if ext == "rst" => Some("text/x-rst"),
+            Some(ext) if ext == "txt" => Some("text/plain"),
+            _ => None,
+        };
+
+        match (&metadata.readme_content_type, expected) {
+            (Some(actual), Some(expected)) if !actual.starts_with(expected) => Some(tr!(
+                "python_metadata.readme_type_mismatch",
+                actual = actual,
+                readme = readme,
+                expected = expected
+            )),
+            (Some(actual), None)
+                if !["text/markdown", "text/x-rst", "text/plain"]
+                    .iter()
+                    .any(|t| actual.starts_with(t)) =>
+            {
+                Some(tr!(
+                    "python_metadata.readme_type_unsupported",
+                    actual = actual
+                ))
+            }
+            (None, None) => Some(tr!("python_metadata.readme_type_unknown")),
+            _ => None,
+        }
+    }
+}
+
+fn check(field: &'static str, passed: bool, points: u32, message: &str) -> MetadataCheck {
+    MetadataCheck {
+        field,
+        passed,
+        points,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPLETE: &str = r#"
+[project]
+name = "demo"
+version = "1.0.0"
+description = "A demo package"
+readme = "README.md"
+requires-python = ">=3.9"
+license = { text = "MIT" }
+keywords = ["demo"]
+classifiers = [
+    "License :: OSI Approved :: MIT License",
+    "Programming Language :: Python :: 3",
+]
+
+[project.urls]
+Homepage = "https://example.com"
+"#;
+
+    #[test]
+    fn test_complete_pyproject_scores_100() {
+        let score = PythonMetadataScorer::score_pyproject(COMPLETE).unwrap();
+        assert_eq!(score.score, 100);
+        assert_eq!(score.failed().count(), 0);
+    }
+
+    #[test]
+    fn test_minimal_pyproject() {
+        let score = PythonMetadataScorer::score_pyproject("[project]\nname = \"demo\"\n").unwrap();
+        assert_eq!(score.score, 0);
+        assert_eq!(score.failed().count(), 8);
+    }
+
+    #[test]
+    fn test_readme_content_type_mismatch() {
+        let content = COMPLETE.replace(
+            r#"readme = "README.md""#,
+            r#"readme = { file = "README.md", content-type = "text/x-rst" }"#,
+        );
+        let score = PythonMetadataScorer::score_pyproject(&content).unwrap();
+        assert_eq!(score.score, 80);
+
+        let failed: Vec<_> = score.failed().collect();
+        assert_eq!(failed[0].field, "readme");
+        assert!(failed[0].message.contains("text/markdown"));
+    }
+
+    #[test]
+    fn test_setup_py() {
+        let content = r#"
+from setuptools import setup
+
+setup(
+    name="demo",
+    version="1.0.0",
+    description="A demo package",
+    long_description=open("README.md").read(),
+    long_description_content_type="text/markdown",
+    python_requires=">=3.8",
+    classifiers=["Programming Language :: Python :: 3"],
+)
+"#;
+        let score = PythonMetadataScorer::score_setup_py(content);
+        // classifiers (20) + requires-python (20) + readme (20) + description (5)
+        assert_eq!(score.score, 65);
+    }
+}
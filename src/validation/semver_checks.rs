@@ -0,0 +1,249 @@
+//! Semver Checks - API breaking change detection for Rust crates
+//!
+//! Runs `cargo semver-checks check-release`, which builds rustdoc JSON for the
+//! crate and for its latest release on crates.io and compares the public API.
+//! Breaking changes under a patch or minor bump are reported as violations;
+//! a major bump accepts them.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::semver_checks::parse_semver_checks_output;
+//!
+//! let output = "\
+//! --- failure function_missing: pub fn removed or renamed ---
+//!
+//! Failed in:
+//!   function demo::foo, previously in file src/lib.rs:1
+//! ";
+//! let violations = parse_semver_checks_output(output);
+//! assert_eq!(violations[0].lint, "function_missing");
+//! assert_eq!(violations[0].locations.len(), 1);
+//! ```
+
+use crate::core::config::SemverChecksConfig;
+use crate::core::traits::{ValidationError, ValidationWarning};
+use crate::tr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// A failed semver lint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemverViolation {
+    /// Lint ID (`function_missing`, `enum_variant_added`, ...)
+    pub lint: String,
+    pub title: String,
+    /// Items that broke (`function demo::foo, previously in file src/lib.rs:1`)
+    pub locations: Vec<String>,
+}
+
+/// Result of a semver check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SemverCheckResult {
+    pub violations: Vec<SemverViolation>,
+    /// Version bump the changes require (`major`, `minor`), if reported
+    pub required_bump: Option<String>,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// Violations from `cargo semver-checks` output
+pub fn parse_semver_checks_output(output: &str) -> Vec<SemverViolation> {
+    let mut violations: Vec<SemverViolation> = Vec::new();
+    let mut in_locations = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed
+            .strip_prefix("--- failure ")
+            .and_then(|h| h.strip_suffix(" ---"))
+        {
+            let (lint, title) = header.split_once(": ").unwrap_or((header, ""));
+            violations.push(SemverViolation {
+                lint: lint.to_string(),
+                title: title.to_string(),
+                locations: Vec::new(),
+            });
+            in_locations = false;
+        } else if trimmed == "Failed in:" {
+            in_locations = true;
+        } else if in_locations {
+            if trimmed.is_empty() || trimmed.starts_with("Summary") {
+                in_locations = false;
+            } else if let Some(violation) = violations.last_mut() {
+                violation.locations.push(trimmed.to_string());
+            }
+        }
+    }
+
+    violations
+}
+
+/// Required bump from the summary line
+/// (`Summary semver requires new major version: ...`)
+fn parse_required_bump(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("Summary semver requires new ")?;
+        rest.split_whitespace().next().map(str::to_string)
+    })
+}
+
+/// Runs `cargo semver-checks` for a crate
+pub struct SemverChecker {
+    project_path: PathBuf,
+    baseline_version: Option<String>,
+    warn_only: bool,
+}
+
+impl SemverChecker {
+    pub fn new(project_path: impl Into<PathBuf>) -> Self {
+        Self {
+            project_path: project_path.into(),
+            baseline_version: None,
+            warn_only: false,
+        }
+    }
+
+    /// Apply `registries.crates.semverChecks`
+    pub fn with_config(mut self, config: &SemverChecksConfig) -> Self {
+        self.baseline_version = config.baseline_version.clone();
+        self.warn_only = config.level.as_deref() == Some("warning");
+        self
+    }
+
+    /// Compare the crate against its latest release (or `baselineVersion`)
+    pub async fn check(&self) -> anyhow::Result<SemverCheckResult> {
+        let mut result = SemverCheckResult::default();
+
+        let installed = Command::new("cargo")
+            .args(["semver-checks", "--version"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .is_ok_and(|s| s.success());
+        if !installed {
+            result.warnings.push(ValidationWarning {
+                field: "cargo.semver".to_string(),
+                message: tr!("semver_checks.not_installed"),
+                severity: "warning".to_string(),
+            });
+            return Ok(result);
+        }
+
+        let mut args = vec!["semver-checks", "check-release", "--color", "never"];
+        if let Some(ref version) = self.baseline_version {
+            args.push("--baseline-version");
+            args.push(version);
+        }
+        let output = Command::new("cargo")
+            .args(&args)
+            .current_dir(&self.project_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await?;
+        let combined = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        result.violations = parse_semver_checks_output(&combined);
+        result.required_bump = parse_required_bump(&combined);
+
+        if output.status.success() {
+            return Ok(result);
+        }
+        if result.violations.is_empty() {
+            // Not a lint failure: no baseline on crates.io, rustdoc errors, ...
+            result.warnings.push(ValidationWarning {
+                field: "cargo.semver".to_string(),
+                message: tr!(
+                    "semver_checks.run_failed",
+                    error = String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                severity: "warning".to_string(),
+            });
+            return Ok(result);
+        }
+
+        let mut messages: Vec<String> = result
+            .violations
+            .iter()
+            .map(|violation| {
+                let mut message = tr!(
+                    "semver_checks.violation",
+                    lint = violation.lint,
+                    title = violation.title
+                );
+                if !violation.locations.is_empty() {
+                    message.push_str(&format!(" - {}", violation.locations.join(", ")));
+                }
+                message
+            })
+            .collect();
+        if let Some(ref bump) = result.required_bump {
+            messages.push(tr!("semver_checks.required_bump", bump = bump));
+        }
+        for message in messages {
+            if self.warn_only {
+                result.warnings.push(ValidationWarning {
+                    field: "cargo.semver".to_string(),
+                    message,
+                    severity: "warning".to_string(),
+                });
+            } else {
+                result.errors.push(ValidationError {
+                    field: "cargo.semver".to_string(),
+                    message,
+                    severity: "error".to_string(),
+                });
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_semver_checks_output() {
+        let output = r#"     Parsing demo v1.1.0 (current)
+     Parsing demo v1.0.0 (baseline)
+    Checking demo v1.0.0 -> v1.1.0 (minor change)
+     Checked [   0.010s] 52 checks: 50 pass, 2 fail, 0 warn, 0 skip
+
+--- failure function_missing: pub fn removed or renamed ---
+
+Description:
+A publicly-visible function cannot be imported by its prior path.
+        ref: https://doc.rust-lang.org/cargo/reference/semver.html#item-remove
+
+Failed in:
+  function demo::foo, previously in file src/lib.rs:1
+  function demo::bar, previously in file src/lib.rs:5
+
+--- failure enum_variant_added: enum variant added on exhaustive enum ---
+
+Failed in:
+  variant Mode:Fast in src/lib.rs:12
+     Summary semver requires new major version: 2 major and 0 minor checks failed
+"#;
+        let violations = parse_semver_checks_output(output);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].lint, "function_missing");
+        assert_eq!(violations[0].title, "pub fn removed or renamed");
+        assert_eq!(violations[0].locations.len(), 2);
+        assert_eq!(
+            violations[1].locations,
+            vec!["variant Mode:Fast in src/lib.rs:12"]
+        );
+        assert_eq!(parse_required_bump(output).as_deref(), Some("major"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Size Budget - maximum package size gate (`validation.maxPackageSize`)
+//!
+//! The package size is taken from the packed artifact when one can be built
+//! (npm tarball, `.crate`, wheel/sdist), with a breakdown of the largest
+//! files it contains; otherwise the estimate reported by the dry-run is used.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::size_budget::parse_size;
+//!
+//! assert_eq!(parse_size("10MB").unwrap(), 10_000_000);
+//! assert_eq!(parse_size("512 KiB").unwrap(), 524_288);
+//! assert_eq!(parse_size("2048").unwrap(), 2048);
+//! ```
+
+use crate::security::artifact_inspector;
+use crate::tr;
+use crate::validation::bundle_size::format_size;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Files listed in the breakdown
+pub const BREAKDOWN_FILES: usize = 10;
+
+/// Size of a file inside the artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSize {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Measured package size
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeMeasurement {
+    /// Packed size in bytes (sum over all artifacts)
+    pub total: u64,
+    /// Largest files contained in the artifacts (empty for dry-run estimates)
+    pub largest_files: Vec<FileSize>,
+}
+
+impl SizeMeasurement {
+    /// Budget violation message with the largest files, if over `max`
+    pub fn check(&self, max: u64) -> Option<String> {
+        if self.total <= max {
+            return None;
+        }
+        let mut message = tr!(
+            "size.packed_over_limit",
+            size = format_size(self.total),
+            max = format_size(max)
+        );
+        if !self.largest_files.is_empty() {
+            message.push_str(&format!("\n{}", tr!("size.largest_files")));
+            for file in &self.largest_files {
+                message.push_str(&format!(
+                    "\n  {:>10}  {}",
+                    format_size(file.size),
+                    file.path
+                ));
+            }
+        }
+        Some(message)
+    }
+}
+
+/// Parse a size such as `10MB`, `1.5 MiB`, `500kB` or `2048` (bytes)
+///
+/// Decimal units (kB, MB, GB) are powers of 1000, binary units
+/// (KiB, MiB, GiB) powers of 1024.
+pub fn parse_size(size: &str) -> anyhow::Result<u64> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid size: {}", size))?;
+
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "k" => 1_000,
+        "mb" | "m" => 1_000_000,
+        "gb" | "g" => 1_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        other => anyhow::bail!(
+            "Invalid size unit: {} (expected B, kB, MB, GB, KiB, MiB or GiB)",
+            other
+        ),
+    };
+    Ok((number * multiplier as f64).round() as u64)
+}
+
+/// Measure the packed artifacts and list their largest files
+pub async fn measure_artifacts(artifacts: &[PathBuf]) -> anyhow::Result<SizeMeasurement> {
+    let mut measurement = SizeMeasurement::default();
+    let mut files = Vec::new();
+
+    for artifact in artifacts {
+        measurement.total += tokio::fs::metadata(artifact).await?.len();
+
+        let work_dir =
+            std::env::temp_dir().join(format!("package-publisher-size-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&work_dir).await?;
+        let listed = list_files(artifact, &work_dir).await;
+        let _ = tokio::fs::remove_dir_all(&work_dir).await;
+        // Artifacts that cannot be unpacked still count towards the total
+        if let Ok(listed) = listed {
+            files.extend(listed);
+        }
+    }
+
+    files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    files.truncate(BREAKDOWN_FILES);
+    measurement.largest_files = files;
+    Ok(measurement)
+}
+
+async fn list_files(artifact: &Path, work_dir: &Path) -> anyhow::Result<Vec<FileSize>> {
+    artifact_inspector::unpack(artifact, work_dir).await?;
+    let name = artifact
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut files = Vec::new();
+    for entry in artifact_inspector::walkdir(work_dir) {
+        let relative = entry
+            .strip_prefix(work_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.push(FileSize {
+            path: format!("{}:{}", name, relative),
+            size: tokio::fs::metadata(&entry).await?.len(),
+        });
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1.5 MB").unwrap(), 1_500_000);
+        assert_eq!(parse_size("12.3 kB").unwrap(), 12_300);
+        assert_eq!(parse_size("1GiB").unwrap(), 1 << 30);
+        assert!(parse_size("ten MB").is_err());
+        assert!(parse_size("10 parsecs").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_measure_artifacts_breakdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel = temp_dir.path().join("demo-1.0.0-py3-none-any.whl");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&wheel).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("demo/__init__.py", options).unwrap();
+        writer.write_all(b"x = 1\n").unwrap();
+        writer.start_file("demo/data/model.bin", options).unwrap();
+        writer.write_all(&[7u8; 4096]).unwrap();
+        writer.finish().unwrap();
+
+        let measurement = measure_artifacts(std::slice::from_ref(&wheel))
+            .await
+            .unwrap();
+        assert!(measurement.total > 4096);
+        assert_eq!(
+            measurement.largest_files[0],
+            FileSize {
+                path: "demo-1.0.0-py3-none-any.whl:demo/data/model.bin".to_string(),
+                size: 4096
+            }
+        );
+
+        let message = measurement.check(1000).unwrap();
+        assert!(message.contains("model.bin"));
+        assert!(measurement.check(1_000_000).is_none());
+    }
+}
@@ -0,0 +1,495 @@
+//! Version Bumper - Consistent version bumps across manifests
+//!
+//! Bumps the version in every manifest found in a project (`package.json`,
+//! `Cargo.toml`, `pyproject.toml` and `Formula/*.rb`) so that all registries
+//! publish the same version. Only the version value is rewritten; the rest of
+//! each file (formatting, comments, key order) is left untouched.
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::{BumpLevel, VersionBumper};
+//!
+//! assert_eq!(VersionBumper::next_version("1.2.3", BumpLevel::Minor, None).unwrap(), "1.3.0");
+//! assert_eq!(
+//!     VersionBumper::next_version("1.2.4-rc.1", BumpLevel::Prerelease, None).unwrap(),
+//!     "1.2.4-rc.2"
+//! );
+//! ```
+
+use regex::Regex;
+use semver::{Prerelease, Version};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::fs;
+
+/// Prerelease identifier used when starting a prerelease from a release
+const DEFAULT_PREID: &str = "beta";
+
+/// Version component to bump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpLevel {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
+impl FromStr for BumpLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "major" => Ok(BumpLevel::Major),
+            "minor" => Ok(BumpLevel::Minor),
+            "patch" => Ok(BumpLevel::Patch),
+            "prerelease" | "pre" => Ok(BumpLevel::Prerelease),
+            _ => anyhow::bail!(
+                "Invalid bump level: {} (expected major, minor, patch or prerelease)",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for BumpLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            BumpLevel::Major => "major",
+            BumpLevel::Minor => "minor",
+            BumpLevel::Patch => "patch",
+            BumpLevel::Prerelease => "prerelease",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+/// Manifest kind holding a version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionedManifest {
+    PackageJson,
+    CargoToml,
+    PyProject,
+    Formula,
+}
+
+/// Version found in a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedFile {
+    pub manifest: VersionedManifest,
+    pub path: PathBuf,
+    pub version: String,
+}
+
+/// Result of a version bump
+#[derive(Debug, Clone)]
+pub struct BumpResult {
+    pub previous_version: String,
+    pub new_version: String,
+    /// Files that were (or, for a dry run, would be) updated
+    pub files: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
+
+/// Bumps versions consistently across all manifests in a project
+pub struct VersionBumper {
+    project_path: PathBuf,
+    preid: Option<String>,
+}
+
+impl VersionBumper {
+    /// Create a new bumper for a project directory
+    pub fn new(project_path: impl AsRef<Path>) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            preid: None,
+        }
+    }
+
+    /// Set the prerelease identifier (default: keep the current one, or "beta")
+    pub fn with_preid(mut self, preid: impl Into<String>) -> Self {
+        self.preid = Some(preid.into());
+        self
+    }
+
+    /// Compute the next version
+    ///
+    /// Follows `npm version` semantics: bumping a prerelease to the release it
+    /// precedes drops the prerelease (`2.0.0-beta.1` → major → `2.0.0`), and
+    /// `prerelease` increments the trailing number or starts `<preid>.0`.
+    pub fn next_version(
+        current: &str,
+        level: BumpLevel,
+        preid: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut version = Version::parse(current)
+            .map_err(|e| anyhow::anyhow!("Invalid version {}: {}", current, e))?;
+        let is_pre = !version.pre.is_empty();
+        version.build = semver::BuildMetadata::EMPTY;
+
+        match level {
+            BumpLevel::Major => {
+                if !(is_pre && version.minor == 0 && version.patch == 0) {
+                    version.major += 1;
+                }
+                version.minor = 0;
+                version.patch = 0;
+                version.pre = Prerelease::EMPTY;
+            }
+            BumpLevel::Minor => {
+                if !(is_pre && version.patch == 0) {
+                    version.minor += 1;
+                }
+                version.patch = 0;
+                version.pre = Prerelease::EMPTY;
+            }
+            BumpLevel::Patch => {
+                if !is_pre {
+                    version.patch += 1;
+                }
+                version.pre = Prerelease::EMPTY;
+            }
+            BumpLevel::Prerelease => {
+                let pre = if is_pre {
+                    Self::next_prerelease(version.pre.as_str(), preid)
+                } else {
+                    version.patch += 1;
+                    format!("{}.0", preid.unwrap_or(DEFAULT_PREID))
+                };
+                version.pre = Prerelease::new(&pre)?;
+            }
+        }
+
+        Ok(version.to_string())
+    }
+
+    /// Next prerelease identifier (`beta.1` → `beta.2`, `alpha.3` with preid beta → `beta.0`)
+    fn next_prerelease(current: &str, preid: Option<&str>) -> String {
+        let (prefix, number) = match current.rsplit_once('.') {
+            Some((prefix, last)) if last.parse::<u64>().is_ok() => {
+                (prefix, last.parse::<u64>().ok())
+            }
+            _ => match current.parse::<u64>() {
+                Ok(n) => ("", Some(n)),
+                Err(_) => (current, None),
+            },
+        };
+
+        if let Some(preid) = preid
+            && prefix != preid
+        {
+            return format!("{}.0", preid);
+        }
+
+        match (prefix, number) {
+            ("", Some(n)) => (n + 1).to_string(),
+            (prefix, Some(n)) => format!("{}.{}", prefix, n + 1),
+            (prefix, None) => format!("{}.0", prefix),
+        }
+    }
+
+    /// Find every manifest with a version in the project
+    pub async fn find_versions(&self) -> Vec<VersionedFile> {
+        let mut files = Vec::new();
+
+        let candidates = [
+            (VersionedManifest::PackageJson, "package.json"),
+            (VersionedManifest::CargoToml, "Cargo.toml"),
+            (VersionedManifest::PyProject, "pyproject.toml"),
+        ];
+        for (manifest, name) in candidates {
+            let path = self.project_path.join(name);
+            if let Ok(content) = fs::read_to_string(&path).await
+                && let Some(version) = read_version(manifest, &content)
+            {
+                files.push(VersionedFile {
+                    manifest,
+                    path,
+                    version,
+                });
+            }
+        }
+
+        if let Ok(mut entries) = fs::read_dir(self.project_path.join("Formula")).await {
+            let mut formulas = Vec::new();
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                if path.extension().is_some_and(|e| e == "rb") {
+                    formulas.push(path);
+                }
+            }
+            formulas.sort();
+
+            for path in formulas {
+                if let Ok(content) = fs::read_to_string(&path).await
+                    && let Some(version) = read_version(VersionedManifest::Formula, &content)
+                {
+                    files.push(VersionedFile {
+                        manifest: VersionedManifest::Formula,
+                        path,
+                        version,
+                    });
+                }
+            }
+        }
+
+        files
+    }
+
+    /// Bump the version in all manifests
+    ///
+    /// Fails without touching any file when the manifests disagree on the
+    /// current version. With `dry_run`, only computes the result.
+    pub async fn bump(&self, level: BumpLevel, dry_run: bool) -> anyhow::Result<BumpResult> {
+        let files = self.find_versions().await;
+        let Some(first) = files.first() else {
+            anyhow::bail!(
+                "No versioned manifest found (package.json, Cargo.toml, pyproject.toml, Formula/*.rb)"
+            );
+        };
+
+        let mismatched: Vec<String> = files
+            .iter()
+            .filter(|f| f.version != first.version)
+            .map(|f| format!("{} ({})", f.path.display(), f.version))
+            .collect();
+        if !mismatched.is_empty() {
+            anyhow::bail!(
+                "Manifest versions are out of sync: {} ({}) vs {}",
+                first.path.display(),
+                first.version,
+                mismatched.join(", ")
+            );
+        }
+
+        let previous_version = first.version.clone();
+        let new_version = Self::next_version(&previous_version, level, self.preid.as_deref())?;
+        let mut warnings = Vec::new();
+
+        for file in &files {
+            if dry_run {
+                continue;
+            }
+
+            let content = fs::read_to_string(&file.path).await?;
+            let updated = write_version(file.manifest, &content, &previous_version, &new_version)
+                .ok_or_else(|| {
+                anyhow::anyhow!("Failed to update version in {}", file.path.display())
+            })?;
+            fs::write(&file.path, updated).await?;
+        }
+
+        for file in files
+            .iter()
+            .filter(|f| f.manifest == VersionedManifest::Formula)
+        {
+            warnings.push(format!(
+                "{}: url/sha256 must point to the {} release archive",
+                file.path.display(),
+                new_version
+            ));
+        }
+
+        Ok(BumpResult {
+            previous_version,
+            new_version,
+            files: files.into_iter().map(|f| f.path).collect(),
+            warnings,
+        })
+    }
+}
+
+/// Byte range of the `version = "..."` value inside a TOML table
+fn toml_version_range(content: &str, tables: &[&str]) -> Option<std::ops::Range<usize>> {
+    let header = Regex::new(r"(?m)^\s*\[([^\]]+)\]\s*$").unwrap();
+    let version = Regex::new(r#"(?m)^\s*version\s*=\s*["']([^"']+)["']"#).unwrap();
+
+    let headers: Vec<_> = header.captures_iter(content).collect();
+    for (i, caps) in headers.iter().enumerate() {
+        if !tables.contains(&caps[1].trim()) {
+            continue;
+        }
+        let start = caps.get(0).unwrap().end();
+        let end = headers
+            .get(i + 1)
+            .map(|next| next.get(0).unwrap().start())
+            .unwrap_or(content.len());
+        if let Some(found) = version.captures(&content[start..end]) {
+            let value = found.get(1).unwrap();
+            return Some(start + value.start()..start + value.end());
+        }
+    }
+
+    None
+}
+
+fn version_range(manifest: VersionedManifest, content: &str) -> Option<std::ops::Range<usize>> {
+    match manifest {
+        VersionedManifest::PackageJson => {
+            // Top-level "version" is the first one in a well-formed package.json
+            serde_json::from_str::<serde_json::Value>(content)
+                .ok()?
+                .get("version")?;
+            let caps = Regex::new(r#""version"\s*:\s*"([^"]*)""#)
+                .unwrap()
+                .captures(content)?;
+            let value = caps.get(1).unwrap();
+            Some(value.start()..value.end())
+        }
+        VersionedManifest::CargoToml => toml_version_range(content, &["package"]),
+        VersionedManifest::PyProject => toml_version_range(content, &["project", "tool.poetry"]),
+        VersionedManifest::Formula => {
+            let caps = Regex::new(r#"(?m)^\s*version\s+["']([^"']+)["']"#)
+                .unwrap()
+                .captures(content)?;
+            let value = caps.get(1).unwrap();
+            Some(value.start()..value.end())
+        }
+    }
+}
+
+fn read_version(manifest: VersionedManifest, content: &str) -> Option<String> {
+    version_range(manifest, content).map(|range| content[range].to_string())
+}
+
+fn write_version(
+    manifest: VersionedManifest,
+    content: &str,
+    previous: &str,
+    version: &str,
+) -> Option<String> {
+    let range = version_range(manifest, content)?;
+    let mut updated = format!(
+        "{}{}{}",
+        &content[..range.start],
+        version,
+        &content[range.end..]
+    );
+
+    // Formula download URLs usually embed the version (…/v1.2.3.tar.gz)
+    if manifest == VersionedManifest::Formula {
+        let url = Regex::new(r#"(?m)^(\s*url\s+["'])([^"']+)(["'])"#).unwrap();
+        updated = url
+            .replace_all(&updated, |caps: &regex::Captures| {
+                format!(
+                    "{}{}{}",
+                    &caps[1],
+                    caps[2].replace(previous, version),
+                    &caps[3]
+                )
+            })
+            .into_owned();
+    }
+
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_next_version() {
+        let next = |v: &str, level| VersionBumper::next_version(v, level, None).unwrap();
+
+        assert_eq!(next("1.2.3", BumpLevel::Major), "2.0.0");
+        assert_eq!(next("1.2.3", BumpLevel::Minor), "1.3.0");
+        assert_eq!(next("1.2.3", BumpLevel::Patch), "1.2.4");
+        assert_eq!(next("1.2.3", BumpLevel::Prerelease), "1.2.4-beta.0");
+
+        assert_eq!(next("2.0.0-beta.1", BumpLevel::Major), "2.0.0");
+        assert_eq!(next("1.3.0-beta.1", BumpLevel::Minor), "1.3.0");
+        assert_eq!(next("1.2.4-beta.1", BumpLevel::Patch), "1.2.4");
+        assert_eq!(next("1.2.4-beta.1", BumpLevel::Prerelease), "1.2.4-beta.2");
+        assert_eq!(next("1.2.4-rc", BumpLevel::Prerelease), "1.2.4-rc.0");
+        assert_eq!(
+            VersionBumper::next_version("1.2.4-alpha.3", BumpLevel::Prerelease, Some("beta"))
+                .unwrap(),
+            "1.2.4-beta.0"
+        );
+
+        assert!(VersionBumper::next_version("latest", BumpLevel::Patch, None).is_err());
+    }
+
+    #[test]
+    fn test_bump_level_from_str() {
+        assert_eq!("Minor".parse::<BumpLevel>().unwrap(), BumpLevel::Minor);
+        assert_eq!("pre".parse::<BumpLevel>().unwrap(), BumpLevel::Prerelease);
+        assert!("huge".parse::<BumpLevel>().is_err());
+    }
+
+    #[test]
+    fn test_toml_version_only_in_package_table() {
+        let content = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1.0" }
+"#;
+        let updated =
+            write_version(VersionedManifest::CargoToml, content, "0.1.0", "0.2.0").unwrap();
+        assert!(updated.contains("version = \"0.2.0\""));
+        assert!(updated.contains("serde = { version = \"1.0\" }"));
+
+        // Workspace-inherited versions are not bumped here
+        assert!(
+            read_version(
+                VersionedManifest::CargoToml,
+                "[package]\nversion.workspace = true\n"
+            )
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bump_all_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(root.join("Formula")).unwrap();
+        std::fs::write(
+            root.join("Formula/demo.rb"),
+            "class Demo < Formula\n  url \"https://example.com/v1.0.0.tar.gz\"\n  version \"1.0.0\"\nend\n",
+        )
+        .unwrap();
+
+        let bumper = VersionBumper::new(root);
+        let dry = bumper.bump(BumpLevel::Minor, true).await.unwrap();
+        assert_eq!(dry.new_version, "1.1.0");
+        assert_eq!(dry.files.len(), 3);
+        assert!(
+            std::fs::read_to_string(root.join("Cargo.toml"))
+                .unwrap()
+                .contains("1.0.0")
+        );
+
+        let result = bumper.bump(BumpLevel::Minor, false).await.unwrap();
+        assert_eq!(result.previous_version, "1.0.0");
+        assert_eq!(result.warnings.len(), 1);
+        for file in bumper.find_versions().await {
+            assert_eq!(file.version, "1.1.0");
+        }
+        let formula = std::fs::read_to_string(root.join("Formula/demo.rb")).unwrap();
+        assert!(formula.contains("v1.1.0.tar.gz"));
+
+        // Out-of-sync manifests are rejected
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"9.9.9\"\n",
+        )
+        .unwrap();
+        assert!(bumper.bump(BumpLevel::Patch, false).await.is_err());
+    }
+}
@@ -196,6 +196,73 @@ impl VersionValidator {
         };
         req.matches(&version)
     }
+
+    /// Check whether a prerelease is superseded by a stable version
+    ///
+    /// A prerelease is superseded when it targets the same `major.minor.patch`
+    /// as the stable release (e.g. `1.2.0-beta.1` by `1.2.0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use package_publisher::validation::version_validator::VersionValidator;
+    ///
+    /// let validator = VersionValidator::new();
+    ///
+    /// assert!(validator.is_superseded_prerelease("1.2.0-beta.1", "1.2.0"));
+    /// assert!(!validator.is_superseded_prerelease("1.3.0-beta.1", "1.2.0"));
+    /// assert!(!validator.is_superseded_prerelease("1.1.0", "1.2.0"));
+    /// ```
+    pub fn is_superseded_prerelease(&self, candidate: &str, stable: &str) -> bool {
+        let (Ok(candidate), Ok(stable)) = (Version::parse(candidate), Version::parse(stable))
+        else {
+            return false;
+        };
+
+        !candidate.pre.is_empty()
+            && stable.pre.is_empty()
+            && candidate.major == stable.major
+            && candidate.minor == stable.minor
+            && candidate.patch == stable.patch
+    }
+
+    /// Determine the maintenance channel for a backport release
+    ///
+    /// A release is a backport when its version is lower than the registry's
+    /// current latest version. The channel names the release line it belongs
+    /// to (`1.x`, or `0.3.x` for pre-1.0 lines) and is used as the dist-tag
+    /// instead of `latest`.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version being published
+    /// * `latest` - Current latest version on the registry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use package_publisher::validation::version_validator::VersionValidator;
+    ///
+    /// let validator = VersionValidator::new();
+    ///
+    /// assert_eq!(validator.backport_channel("1.4.2", "2.0.0"), Some("1.x".to_string()));
+    /// assert_eq!(validator.backport_channel("0.3.9", "0.4.0"), Some("0.3.x".to_string()));
+    /// assert_eq!(validator.backport_channel("2.1.0", "2.0.0"), None);
+    /// ```
+    pub fn backport_channel(&self, version: &str, latest: &str) -> Option<String> {
+        let version = Version::parse(version).ok()?;
+        let latest = Version::parse(latest).ok()?;
+
+        if version >= latest {
+            return None;
+        }
+
+        if version.major == 0 {
+            Some(format!("0.{}.x", version.minor))
+        } else {
+            Some(format!("{}.x", version.major))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -335,6 +402,37 @@ mod tests {
         assert!(!validator.satisfies("invalid", "^1.0.0"));
     }
 
+    #[test]
+    fn test_is_superseded_prerelease() {
+        let validator = VersionValidator::new();
+        assert!(validator.is_superseded_prerelease("2.0.0-rc.1", "2.0.0"));
+        assert!(validator.is_superseded_prerelease("2.0.0-alpha", "2.0.0"));
+        assert!(!validator.is_superseded_prerelease("2.0.0-rc.1", "2.0.0-rc.2"));
+        assert!(!validator.is_superseded_prerelease("2.0.1-rc.1", "2.0.0"));
+        assert!(!validator.is_superseded_prerelease("2.0.0", "2.0.0"));
+        assert!(!validator.is_superseded_prerelease("invalid", "2.0.0"));
+    }
+
+    #[test]
+    fn test_backport_channel() {
+        let validator = VersionValidator::new();
+        assert_eq!(
+            validator.backport_channel("1.9.3", "2.1.0"),
+            Some("1.x".to_string())
+        );
+        assert_eq!(
+            validator.backport_channel("2.0.5", "2.1.0"),
+            Some("2.x".to_string())
+        );
+        assert_eq!(
+            validator.backport_channel("0.2.1", "0.3.0"),
+            Some("0.2.x".to_string())
+        );
+        assert_eq!(validator.backport_channel("2.1.0", "2.1.0"), None);
+        assert_eq!(validator.backport_channel("3.0.0", "2.1.0"), None);
+        assert_eq!(validator.backport_channel("invalid", "2.1.0"), None);
+    }
+
     #[test]
     fn test_satisfies_invalid_requirement() {
         let validator = VersionValidator::new();
@@ -0,0 +1,169 @@
+//! Warning Budget - Turns validation warnings into failures
+//!
+//! Teams can ratchet package quality over time by capping the number of
+//! validation warnings (`validation.maxWarnings`) or by treating every warning
+//! as a failure (`--strict` / `validation.strict`).
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::WarningBudget;
+//!
+//! let budget = WarningBudget::new(false, Some(2));
+//! assert!(budget.check(2).is_ok());
+//! assert!(budget.check(3).is_err());
+//! ```
+
+use crate::core::config::ValidationConfig;
+use std::fmt;
+
+/// Budget for validation warnings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarningBudget {
+    /// Treat any warning as a failure
+    pub strict: bool,
+    /// Maximum number of warnings allowed (None = unlimited)
+    pub max_warnings: Option<usize>,
+}
+
+/// Error returned when the warning count exceeds the budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarningBudgetExceeded {
+    /// Number of warnings reported
+    pub count: usize,
+    /// Effective limit that was exceeded
+    pub limit: usize,
+    /// Whether the limit comes from strict mode
+    pub strict: bool,
+}
+
+impl fmt::Display for WarningBudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.strict {
+            write!(
+                f,
+                "Strict mode: {} warning(s) treated as errors",
+                self.count
+            )
+        } else {
+            write!(
+                f,
+                "Warning budget exceeded: {} warning(s) (max: {})",
+                self.count, self.limit
+            )
+        }
+    }
+}
+
+impl std::error::Error for WarningBudgetExceeded {}
+
+impl WarningBudget {
+    /// Create a new WarningBudget
+    pub fn new(strict: bool, max_warnings: Option<usize>) -> Self {
+        Self {
+            strict,
+            max_warnings,
+        }
+    }
+
+    /// Build a budget from validation config, with `strict` forced on by CLI
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Validation configuration (optional)
+    /// * `strict` - Strict flag from CLI (takes priority when true)
+    pub fn from_config(config: Option<&ValidationConfig>, strict: bool) -> Self {
+        let strict = strict || config.and_then(|c| c.strict).unwrap_or(false);
+        let max_warnings = config.and_then(|c| c.max_warnings);
+        Self::new(strict, max_warnings)
+    }
+
+    /// Effective warning limit (strict mode means zero)
+    pub fn limit(&self) -> Option<usize> {
+        if self.strict {
+            Some(0)
+        } else {
+            self.max_warnings
+        }
+    }
+
+    /// Check a warning count against the budget
+    pub fn check(&self, warning_count: usize) -> Result<(), WarningBudgetExceeded> {
+        match self.limit() {
+            Some(limit) if warning_count > limit => Err(WarningBudgetExceeded {
+                count: warning_count,
+                limit,
+                strict: self.strict,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_budget_is_unlimited() {
+        let budget = WarningBudget::default();
+        assert_eq!(budget.limit(), None);
+        assert!(budget.check(1000).is_ok());
+    }
+
+    #[test]
+    fn test_max_warnings() {
+        let budget = WarningBudget::new(false, Some(3));
+        assert!(budget.check(0).is_ok());
+        assert!(budget.check(3).is_ok());
+
+        let err = budget.check(4).unwrap_err();
+        assert_eq!(err.count, 4);
+        assert_eq!(err.limit, 3);
+        assert!(!err.strict);
+        assert!(err.to_string().contains("max: 3"));
+    }
+
+    #[test]
+    fn test_strict_overrides_max_warnings() {
+        let budget = WarningBudget::new(true, Some(10));
+        assert_eq!(budget.limit(), Some(0));
+        assert!(budget.check(0).is_ok());
+
+        let err = budget.check(1).unwrap_err();
+        assert!(err.strict);
+        assert!(err.to_string().contains("Strict mode"));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = ValidationConfig {
+            rules: None,
+            strict: None,
+            max_warnings: Some(5),
+            max_package_size: None,
+            package_size_level: None,
+            package_contents: None,
+        };
+
+        let budget = WarningBudget::from_config(Some(&config), false);
+        assert_eq!(budget, WarningBudget::new(false, Some(5)));
+
+        let budget = WarningBudget::from_config(Some(&config), true);
+        assert!(budget.strict);
+
+        let config = ValidationConfig {
+            rules: None,
+            strict: Some(true),
+            max_warnings: None,
+            max_package_size: None,
+            package_size_level: None,
+            package_contents: None,
+        };
+        assert!(WarningBudget::from_config(Some(&config), false).strict);
+        assert_eq!(
+            WarningBudget::from_config(None, false),
+            WarningBudget::default()
+        );
+    }
+}
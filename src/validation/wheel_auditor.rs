@@ -0,0 +1,506 @@
+//! Wheel Auditor - Platform tag validation and native wheel auditing
+//!
+//! Checks built wheels (`dist/*.whl`) before they reach PyPI:
+//! - Filename tags are parsed per PEP 427 (`{name}-{ver}(-{build})?-{py}-{abi}-{plat}.whl`)
+//! - Platform tags are validated (manylinux / musllinux / macOS / Windows);
+//!   plain `linux_*` tags are rejected by PyPI
+//! - Native Linux wheels are checked with `auditwheel show`
+//! - Native macOS wheels are checked with `delocate-listdeps` for unbundled dylibs
+//!
+//! # Example
+//!
+//! ```
+//! use package_publisher::validation::WheelTags;
+//!
+//! let tags = WheelTags::parse("demo-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl").unwrap();
+//! assert_eq!(tags.platforms, vec!["manylinux_2_17_x86_64"]);
+//! assert!(!tags.is_pure());
+//! ```
+
+use crate::core::traits::{ValidationError, ValidationWarning};
+use crate::tr;
+use regex::Regex;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::fs;
+use tokio::process::Command;
+
+/// Architectures accepted for manylinux / musllinux tags
+const LINUX_ARCHS: &[&str] = &[
+    "x86_64", "i686", "aarch64", "armv7l", "ppc64", "ppc64le", "s390x", "riscv64",
+];
+
+/// Architectures accepted for macOS tags
+const MACOS_ARCHS: &[&str] = &[
+    "x86_64",
+    "arm64",
+    "universal2",
+    "intel",
+    "universal",
+    "i386",
+    "fat",
+    "fat3",
+    "fat64",
+];
+
+/// Tags parsed from a wheel filename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WheelTags {
+    pub name: String,
+    pub version: String,
+    pub build: Option<String>,
+    pub python: String,
+    pub abi: String,
+    /// Platform tags (compressed tag sets are split on `.`)
+    pub platforms: Vec<String>,
+}
+
+impl WheelTags {
+    /// Parse tags from a wheel filename
+    pub fn parse(filename: &str) -> Option<Self> {
+        let stem = filename.strip_suffix(".whl")?;
+        let parts: Vec<&str> = stem.split('-').collect();
+
+        let (build, rest) = match parts.len() {
+            5 => (None, &parts[2..]),
+            6 => (Some(parts[2].to_string()), &parts[3..]),
+            _ => return None,
+        };
+
+        Some(Self {
+            name: parts[0].to_string(),
+            version: parts[1].to_string(),
+            build,
+            python: rest[0].to_string(),
+            abi: rest[1].to_string(),
+            platforms: rest[2].split('.').map(str::to_string).collect(),
+        })
+    }
+
+    /// Pure-Python wheel (no native code)
+    pub fn is_pure(&self) -> bool {
+        self.abi == "none" && self.platforms.iter().all(|p| p == "any")
+    }
+
+    /// Whether any platform tag targets Linux
+    pub fn targets_linux(&self) -> bool {
+        self.platforms.iter().any(|p| p.contains("linux"))
+    }
+
+    /// Whether any platform tag targets macOS
+    pub fn targets_macos(&self) -> bool {
+        self.platforms.iter().any(|p| p.starts_with("macosx_"))
+    }
+}
+
+/// Result of auditing all wheels in a directory
+#[derive(Debug, Clone, Default)]
+pub struct WheelAuditResult {
+    /// Audited wheel filenames
+    pub wheels: Vec<String>,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl WheelAuditResult {
+    /// No blocking issues found
+    pub fn passed(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn error(&mut self, wheel: &str, message: String) {
+        self.errors.push(ValidationError {
+            field: wheel.to_string(),
+            message,
+            severity: "error".to_string(),
+        });
+    }
+
+    fn warning(&mut self, wheel: &str, message: String) {
+        self.warnings.push(ValidationWarning {
+            field: wheel.to_string(),
+            message,
+            severity: "warning".to_string(),
+        });
+    }
+}
+
+/// Audits built wheels for platform tag and native library problems
+pub struct WheelAuditor {
+    /// Run auditwheel / delocate on native wheels (default: true)
+    run_tools: bool,
+}
+
+impl Default for WheelAuditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WheelAuditor {
+    /// Create a new WheelAuditor
+    pub fn new() -> Self {
+        Self { run_tools: true }
+    }
+
+    /// Only validate filename tags, without running external tools
+    pub fn tags_only() -> Self {
+        Self { run_tools: false }
+    }
+
+    /// Audit every `*.whl` in a directory (usually `dist/`)
+    pub async fn audit_dir(&self, dist_dir: &Path) -> anyhow::Result<WheelAuditResult> {
+        let mut wheels = Vec::new();
+        if fs::metadata(dist_dir).await.is_ok() {
+            let mut entries = fs::read_dir(dist_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().map(|e| e == "whl").unwrap_or(false) {
+                    wheels.push(path);
+                }
+            }
+        }
+        wheels.sort();
+
+        let mut result = WheelAuditResult::default();
+        for wheel in wheels {
+            self.audit_wheel(&wheel, &mut result).await;
+        }
+        Ok(result)
+    }
+
+    /// Audit a single wheel file
+    async fn audit_wheel(&self, path: &Path, result: &mut WheelAuditResult) {
+        let filename = path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        result.wheels.push(filename.clone());
+
+        let Some(tags) = WheelTags::parse(&filename) else {
+            result.error(&filename, tr!("wheel_auditor.invalid_filename"));
+            return;
+        };
+
+        for (is_error, message) in check_tags(&tags) {
+            if is_error {
+                result.error(&filename, message);
+            } else {
+                result.warning(&filename, message);
+            }
+        }
+
+        if !self.run_tools || tags.is_pure() {
+            return;
+        }
+
+        if tags.targets_linux() && tags.platforms.iter().all(|p| !p.starts_with("linux_")) {
+            self.run_auditwheel(path, &filename, result).await;
+        } else if tags.targets_macos() {
+            self.run_delocate(path, &filename, result).await;
+        }
+    }
+
+    /// Check a native Linux wheel with `auditwheel show`
+    async fn run_auditwheel(&self, path: &Path, filename: &str, result: &mut WheelAuditResult) {
+        if !cfg!(target_os = "linux") {
+            result.warning(filename, tr!("wheel_auditor.auditwheel_linux_only"));
+            return;
+        }
+
+        match run_tool("auditwheel", &["show", &path.to_string_lossy()]).await {
+            Ok((true, _)) => {}
+            Ok((false, output)) => result.error(
+                filename,
+                tr!("wheel_auditor.auditwheel_failed", output = output.trim()),
+            ),
+            Err(_) => result.warning(filename, tr!("wheel_auditor.auditwheel_missing")),
+        }
+    }
+
+    /// Check a native macOS wheel for dylibs that are not bundled
+    async fn run_delocate(&self, path: &Path, filename: &str, result: &mut WheelAuditResult) {
+        if !cfg!(target_os = "macos") {
+            result.warning(filename, tr!("wheel_auditor.delocate_macos_only"));
+            return;
+        }
+
+        match run_tool("delocate-listdeps", &["--all", &path.to_string_lossy()]).await {
+            Ok((true, output)) => {
+                let external = external_dylibs(&output);
+                if !external.is_empty() {
+                    result.error(
+                        filename,
+                        tr!(
+                            "wheel_auditor.external_dylibs",
+                            libraries = external.join(", ")
+                        ),
+                    );
+                }
+            }
+            Ok((false, output)) => result.error(
+                filename,
+                tr!("wheel_auditor.delocate_failed", output = output.trim()),
+            ),
+            Err(_) => result.warning(filename, tr!("wheel_auditor.delocate_missing")),
+        }
+    }
+}
+
+/// Validate abi/platform tag combinations; returns `(is_error, message)` pairs
+fn check_tags(tags: &WheelTags) -> Vec<(bool, String)> {
+    let mut issues = Vec::new();
+
+    if tags.abi != "none" && tags.platforms.iter().any(|p| p == "any") {
+        issues.push((true, tr!("wheel_auditor.abi_with_any", abi = tags.abi)));
+    }
+
+    for platform in &tags.platforms {
+        match check_platform_tag(platform) {
+            PlatformCheck::Valid => {}
+            PlatformCheck::Rejected(message) => issues.push((true, message)),
+            PlatformCheck::Unknown => issues.push((
+                false,
+                tr!("wheel_auditor.unknown_platform", platform = platform),
+            )),
+        }
+    }
+
+    issues
+}
+
+/// Outcome of validating a single platform tag
+enum PlatformCheck {
+    Valid,
+    Rejected(String),
+    Unknown,
+}
+
+/// Validate a single platform tag
+fn check_platform_tag(platform: &str) -> PlatformCheck {
+    if matches!(platform, "any" | "win32" | "win_amd64" | "win_arm64") {
+        return PlatformCheck::Valid;
+    }
+
+    if platform.starts_with("linux_") {
+        return PlatformCheck::Rejected(tr!("wheel_auditor.linux_tag", platform = platform));
+    }
+
+    let legacy = Regex::new(r"^manylinux(1|2010|2014)_([a-z0-9_]+)$").unwrap();
+    if let Some(cap) = legacy.captures(platform) {
+        let allowed = match &cap[1] {
+            "1" | "2010" => ["x86_64", "i686"].contains(&&cap[2]),
+            _ => LINUX_ARCHS.contains(&&cap[2]),
+        };
+        return if allowed {
+            PlatformCheck::Valid
+        } else {
+            PlatformCheck::Rejected(tr!(
+                "wheel_auditor.legacy_manylinux_arch",
+                arch = &cap[2],
+                policy = &cap[1]
+            ))
+        };
+    }
+
+    let pep600 = Regex::new(r"^(manylinux|musllinux)_(\d+)_(\d+)_([a-z0-9_]+)$").unwrap();
+    if let Some(cap) = pep600.captures(platform) {
+        let major: u32 = cap[2].parse().unwrap_or(0);
+        let minor: u32 = cap[3].parse().unwrap_or(0);
+        if &cap[1] == "manylinux" && (major < 2 || (major == 2 && minor < 5)) {
+            return PlatformCheck::Rejected(tr!(
+                "wheel_auditor.glibc_too_old",
+                platform = platform
+            ));
+        }
+        if !LINUX_ARCHS.contains(&&cap[4]) {
+            return PlatformCheck::Rejected(tr!(
+                "wheel_auditor.unsupported_arch",
+                platform = platform
+            ));
+        }
+        return PlatformCheck::Valid;
+    }
+
+    let macos = Regex::new(r"^macosx_(\d+)_(\d+)_([a-z0-9_]+)$").unwrap();
+    if let Some(cap) = macos.captures(platform) {
+        let major: u32 = cap[1].parse().unwrap_or(0);
+        let minor: u32 = cap[2].parse().unwrap_or(0);
+        let arch = &cap[3];
+        if !MACOS_ARCHS.contains(&arch) {
+            return PlatformCheck::Rejected(tr!(
+                "wheel_auditor.unsupported_macos_arch",
+                platform = platform
+            ));
+        }
+        if major >= 11 && minor != 0 {
+            return PlatformCheck::Rejected(tr!(
+                "wheel_auditor.macos_minor_not_zero",
+                platform = platform,
+                major = major,
+                arch = arch
+            ));
+        }
+        let min = if arch == "arm64" { (11, 0) } else { (10, 9) };
+        if matches!(arch, "arm64" | "universal2") && (major, minor) < min {
+            return PlatformCheck::Rejected(tr!(
+                "wheel_auditor.macos_too_old",
+                platform = platform,
+                arch = arch,
+                minimum = format!("{}.{}", min.0, min.1)
+            ));
+        }
+        return PlatformCheck::Valid;
+    }
+
+    PlatformCheck::Unknown
+}
+
+/// Library paths reported by `delocate-listdeps` that are not system libraries
+fn external_dylibs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| {
+            !line.starts_with("/usr/lib/")
+                && !line.starts_with("/System/")
+                && !line.starts_with("@loader_path")
+                && !line.starts_with("@rpath")
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Run an audit tool, returning (success, combined output)
+async fn run_tool(program: &str, args: &[&str]) -> anyhow::Result<(bool, String)> {
+    let output = Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+
+    let combined = String::from_utf8_lossy(&output.stdout).to_string()
+        + &String::from_utf8_lossy(&output.stderr);
+    Ok((output.status.success(), combined))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_wheel_tags() {
+        let tags = WheelTags::parse("demo_pkg-1.0.0-py3-none-any.whl").unwrap();
+        assert_eq!(tags.name, "demo_pkg");
+        assert_eq!(tags.version, "1.0.0");
+        assert_eq!(tags.build, None);
+        assert!(tags.is_pure());
+
+        let tags = WheelTags::parse(
+            "demo-2.0-1-cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+        )
+        .unwrap();
+        assert_eq!(tags.build, Some("1".to_string()));
+        assert_eq!(tags.abi, "cp311");
+        assert_eq!(
+            tags.platforms,
+            vec!["manylinux_2_17_x86_64", "manylinux2014_x86_64"]
+        );
+        assert!(tags.targets_linux());
+
+        assert!(WheelTags::parse("demo-1.0.0.tar.gz").is_none());
+        assert!(WheelTags::parse("demo-1.0.0-any.whl").is_none());
+    }
+
+    #[test]
+    fn test_check_platform_tags() {
+        for valid in [
+            "any",
+            "win_amd64",
+            "manylinux1_x86_64",
+            "manylinux2014_aarch64",
+            "manylinux_2_28_x86_64",
+            "musllinux_1_2_aarch64",
+            "macosx_10_9_x86_64",
+            "macosx_11_0_arm64",
+            "macosx_10_9_universal2",
+        ] {
+            assert!(
+                matches!(check_platform_tag(valid), PlatformCheck::Valid),
+                "{}",
+                valid
+            );
+        }
+
+        for rejected in [
+            "linux_x86_64",
+            "manylinux1_aarch64",
+            "manylinux_2_3_x86_64",
+            "macosx_11_3_arm64",
+            "macosx_10_6_arm64",
+            "macosx_10_9_arm64",
+        ] {
+            assert!(
+                matches!(check_platform_tag(rejected), PlatformCheck::Rejected(_)),
+                "{}",
+                rejected
+            );
+        }
+
+        assert!(matches!(
+            check_platform_tag("emscripten_3_1_wasm32"),
+            PlatformCheck::Unknown
+        ));
+    }
+
+    #[test]
+    fn test_abi_with_any_platform_is_error() {
+        let tags = WheelTags::parse("demo-1.0-cp312-cp312-any.whl").unwrap();
+        let issues = check_tags(&tags);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].0);
+    }
+
+    #[test]
+    fn test_external_dylibs() {
+        let output = "/usr/lib/libSystem.B.dylib\n@loader_path/.dylibs/libfoo.dylib\n/opt/homebrew/lib/libssl.3.dylib\n";
+        assert_eq!(
+            external_dylibs(output),
+            vec!["/opt/homebrew/lib/libssl.3.dylib"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audit_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "demo-1.0.0-py3-none-any.whl",
+            "demo-1.0.0-cp312-cp312-linux_x86_64.whl",
+            "demo-1.0.0.tar.gz",
+        ] {
+            std::fs::write(temp_dir.path().join(name), b"").unwrap();
+        }
+
+        let result = WheelAuditor::tags_only()
+            .audit_dir(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(result.wheels.len(), 2);
+        assert!(!result.passed());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].field,
+            "demo-1.0.0-cp312-cp312-linux_x86_64.whl"
+        );
+
+        let missing = WheelAuditor::new()
+            .audit_dir(&temp_dir.path().join("missing"))
+            .await
+            .unwrap();
+        assert!(missing.wheels.is_empty());
+        assert!(missing.passed());
+    }
+}